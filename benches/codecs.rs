@@ -2,13 +2,16 @@ use criterion::{
     criterion_group, criterion_main, AxisScale, BenchmarkId, Criterion, PlotConfiguration,
     Throughput,
 };
-use zarrs::array::{
-    codec::{
-        array_to_bytes::bytes::Endianness,
-        bytes_to_bytes::blosc::{BloscCompressor, BloscShuffleMode},
-        ArrayCodecTraits, BloscCodec, BytesCodec, BytesToBytesCodecTraits, CodecOptions,
+use zarrs::{
+    array::{
+        codec::{
+            array_to_bytes::bytes::Endianness,
+            bytes_to_bytes::blosc::{BloscCompressor, BloscShuffleMode},
+            ArrayCodecTraits, BloscCodec, BytesCodec, BytesToBytesCodecTraits, CodecOptions,
+        },
+        BytesRepresentation, ChunkRepresentation, DataType,
     },
-    BytesRepresentation, ChunkRepresentation, DataType,
+    bytes::Bytes,
 };
 
 fn codec_bytes(c: &mut Criterion) {
@@ -64,9 +67,10 @@ fn codec_blosc(c: &mut Criterion) {
         let rep = BytesRepresentation::FixedSize(size3);
 
         let data_decoded: Vec<u8> = (0..size3).map(|i| i as u8).collect();
-        let data_encoded = codec
+        let data_encoded: Bytes = codec
             .encode(data_decoded.clone(), &CodecOptions::default())
-            .unwrap();
+            .unwrap()
+            .into();
         group.throughput(Throughput::Bytes(size3));
         group.bench_function(BenchmarkId::new("encode", size3), |b| {
             b.iter(|| {
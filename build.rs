@@ -1,7 +1,30 @@
 #[rustversion::nightly]
-fn main() {
+fn nightly() {
     println!("cargo:rustc-cfg=nightly");
 }
 
 #[rustversion::not(nightly)]
-fn main() {}
+fn nightly() {}
+
+/// Generate the gRPC store client and server from the protocol definition.
+#[cfg(feature = "grpc")]
+fn grpc() {
+    const PROTO: &str = "proto/zarrs/store/v1/store.proto";
+    println!("cargo:rerun-if-changed={PROTO}");
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var(
+            "PROTOC",
+            protoc_bin_vendored::protoc_bin_path().expect("a vendored protoc is available"),
+        );
+    }
+    tonic_build::configure()
+        .bytes(["."])
+        .compile(&[PROTO], &["proto"])
+        .expect("the gRPC store protocol compiles");
+}
+
+fn main() {
+    nightly();
+    #[cfg(feature = "grpc")]
+    grpc();
+}
@@ -0,0 +1,244 @@
+use std::{num::NonZeroU64, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use zarrs::{
+    array::{
+        chunk_grid::{ChunkGrid, ChunkGridRegistry, ChunkGridTraits},
+        Array, ArrayBuilder, ArrayIndices, ArrayShape, ChunkShape, DataType, FillValue,
+    },
+    array_subset::ArraySubset,
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+    storage::store,
+};
+
+/// The identifier of the custom chunk grid.
+const IDENTIFIER: &str = "example.hilbert";
+
+fn create_chunk_grid_hilbert(metadata: &Metadata) -> Result<ChunkGrid, PluginCreateError> {
+    let chunk_grid: HilbertChunkGrid = metadata
+        .to_configuration()
+        .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "chunk grid", metadata.clone()))?;
+    if chunk_grid.chunk_shape.len() != 2 {
+        return Err(PluginCreateError::from(
+            "the hilbert chunk grid only supports 2D arrays",
+        ));
+    }
+    Ok(ChunkGrid::new(chunk_grid))
+}
+
+/// A 2D chunk grid of regularly shaped chunks which are ordered along a Hilbert curve.
+///
+/// The chunks of a chunk grid must be axis-aligned hyperrectangles whose chunk indices are non-decreasing along each dimension (see [`ChunkGridTraits`]).
+/// So the Hilbert curve does not change the chunk indices, but instead defines a space-filling order of the chunks.
+/// Visiting chunks in [`HilbertChunkGrid::chunks_hilbert_order`] keeps consecutive chunks spatially adjacent, which improves locality when chunks are written or read in that order.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct HilbertChunkGrid {
+    chunk_shape: ChunkShape,
+}
+
+impl HilbertChunkGrid {
+    /// Return the chunk indices at distance `d` along a Hilbert curve covering an `n` by `n` grid, where `n` is a power of two.
+    fn hilbert_d2xy(n: u64, d: u64) -> ArrayIndices {
+        let (mut x, mut y, mut t) = (0, 0, d);
+        let mut s = 1;
+        while s < n {
+            let rx = 1 & (t / 2);
+            let ry = 1 & (t ^ rx);
+            if ry == 0 {
+                if rx == 1 {
+                    x = s - 1 - x;
+                    y = s - 1 - y;
+                }
+                std::mem::swap(&mut x, &mut y);
+            }
+            x += s * rx;
+            y += s * ry;
+            t /= 4;
+            s *= 2;
+        }
+        vec![x, y]
+    }
+
+    /// Return the indices of all chunks of an array with `array_shape` in Hilbert curve order.
+    fn chunks_hilbert_order(&self, array_shape: &[u64]) -> Vec<ArrayIndices> {
+        let grid_shape = self.grid_shape(array_shape).unwrap().unwrap();
+        let n = grid_shape.iter().max().map_or(1, |n| n.next_power_of_two());
+        (0..n * n)
+            .map(|d| Self::hilbert_d2xy(n, d))
+            .filter(|chunk_indices| std::iter::zip(chunk_indices, &grid_shape).all(|(i, n)| i < n))
+            .collect()
+    }
+}
+
+impl ChunkGridTraits for HilbertChunkGrid {
+    fn create_metadata(&self) -> Metadata {
+        Metadata::new_with_serializable_configuration(IDENTIFIER, self).unwrap()
+    }
+
+    fn dimensionality(&self) -> usize {
+        self.chunk_shape.len()
+    }
+
+    unsafe fn grid_shape_unchecked(&self, array_shape: &[u64]) -> Option<ArrayShape> {
+        Some(
+            std::iter::zip(array_shape, self.chunk_shape.as_slice())
+                .map(|(a, s)| (a + s.get() - 1) / s.get())
+                .collect(),
+        )
+    }
+
+    unsafe fn chunk_shape_unchecked(
+        &self,
+        _chunk_indices: &[u64],
+        _array_shape: &[u64],
+    ) -> Option<ChunkShape> {
+        Some(self.chunk_shape.clone())
+    }
+
+    unsafe fn chunk_shape_u64_unchecked(
+        &self,
+        _chunk_indices: &[u64],
+        _array_shape: &[u64],
+    ) -> Option<ArrayShape> {
+        Some(self.chunk_shape.iter().map(|s| s.get()).collect())
+    }
+
+    unsafe fn chunk_origin_unchecked(
+        &self,
+        chunk_indices: &[u64],
+        _array_shape: &[u64],
+    ) -> Option<ArrayIndices> {
+        Some(
+            std::iter::zip(chunk_indices, self.chunk_shape.as_slice())
+                .map(|(i, s)| i * s.get())
+                .collect(),
+        )
+    }
+
+    unsafe fn chunk_indices_unchecked(
+        &self,
+        array_indices: &[u64],
+        _array_shape: &[u64],
+    ) -> Option<ArrayIndices> {
+        Some(
+            std::iter::zip(array_indices, self.chunk_shape.as_slice())
+                .map(|(i, s)| i / s.get())
+                .collect(),
+        )
+    }
+
+    unsafe fn chunk_element_indices_unchecked(
+        &self,
+        array_indices: &[u64],
+        _array_shape: &[u64],
+    ) -> Option<ArrayIndices> {
+        Some(
+            std::iter::zip(array_indices, self.chunk_shape.as_slice())
+                .map(|(i, s)| i % s.get())
+                .collect(),
+        )
+    }
+}
+
+fn custom_chunk_grid() -> Result<(), Box<dyn std::error::Error>> {
+    // Register the chunk grid at runtime, so that arrays using it can be opened from their metadata.
+    // Alternatively, register a `ChunkGridPlugin` with `inventory::submit!`.
+    ChunkGridRegistry::register(IDENTIFIER, create_chunk_grid_hilbert);
+
+    let store = Arc::new(store::MemoryStore::new());
+
+    // Create an array with the custom chunk grid
+    let chunk_grid = HilbertChunkGrid {
+        chunk_shape: vec![NonZeroU64::new(2).unwrap(); 2].into(),
+    };
+    let array = ArrayBuilder::new(
+        vec![8, 8],
+        DataType::UInt8,
+        ChunkGrid::new(chunk_grid.clone()),
+        FillValue::from(0u8),
+    )
+    .build(store.clone(), "/array")?;
+    array.store_metadata()?;
+
+    // Write each chunk in Hilbert curve order, filled with its position along the curve
+    println!("The chunks in Hilbert curve order are:");
+    for (d, chunk_indices) in chunk_grid
+        .chunks_hilbert_order(array.shape())
+        .into_iter()
+        .enumerate()
+    {
+        println!("  {d:2}: {chunk_indices:?}");
+        array.store_chunk_elements::<u8>(&chunk_indices, vec![u8::try_from(d)?; 4])?;
+    }
+
+    println!(
+        "\nThe array metadata is:\n{}\n",
+        serde_json::to_string_pretty(&array.metadata()).unwrap()
+    );
+
+    // Open the array, the chunk grid is created from the metadata by the registered create function
+    let array = Array::new(store, "/array")?;
+    let elements = array.retrieve_array_subset_elements::<u8>(&ArraySubset::new_with_shape(
+        array.shape().to_vec(),
+    ))?;
+    println!("The array is:");
+    for row in elements.chunks(8) {
+        println!("  {row:2?}");
+    }
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = custom_chunk_grid() {
+        println!("{}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hilbert_order() {
+        let chunk_grid = HilbertChunkGrid {
+            chunk_shape: vec![NonZeroU64::new(2).unwrap(); 2].into(),
+        };
+        assert_eq!(
+            chunk_grid.chunks_hilbert_order(&[4, 4]),
+            vec![vec![0, 0], vec![0, 1], vec![1, 1], vec![1, 0]]
+        );
+
+        // Every chunk is visited once and consecutive chunks are adjacent
+        let order = chunk_grid.chunks_hilbert_order(&[16, 16]);
+        assert_eq!(order.len(), 64);
+        assert!(order.windows(2).all(|w| {
+            std::iter::zip(&w[0], &w[1])
+                .map(|(a, b)| a.abs_diff(*b))
+                .sum::<u64>()
+                == 1
+        }));
+
+        // Chunks outside of a non-square grid are skipped
+        assert_eq!(chunk_grid.chunks_hilbert_order(&[6, 2]).len(), 3);
+    }
+
+    #[test]
+    fn hilbert_metadata() {
+        ChunkGridRegistry::register(IDENTIFIER, create_chunk_grid_hilbert);
+        let metadata: Metadata = serde_json::from_str(
+            r#"{"name":"example.hilbert","configuration":{"chunk_shape":[2,3]}}"#,
+        )
+        .unwrap();
+        let chunk_grid = ChunkGrid::from_metadata(&metadata).unwrap();
+        assert_eq!(chunk_grid.create_metadata(), metadata);
+        assert_eq!(chunk_grid.grid_shape(&[4, 6]).unwrap(), Some(vec![2, 2]));
+
+        let metadata: Metadata = serde_json::from_str(
+            r#"{"name":"example.hilbert","configuration":{"chunk_shape":[2]}}"#,
+        )
+        .unwrap();
+        assert!(ChunkGrid::from_metadata(&metadata).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use zarrs::{
+    array::{
+        chunk_key_encoding::{ChunkKeyEncoding, ChunkKeyEncodingPlugin, ChunkKeyEncodingTraits},
+        Array, ArrayBuilder, DataType, FillValue,
+    },
+    array_subset::ArraySubset,
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+    storage::{store, ListableStorageTraits, StoreKey},
+};
+
+/// The identifier of the custom chunk key encoding.
+const IDENTIFIER: &str = "example.hashed_prefix";
+
+// Register the chunk key encoding, so that arrays using it can be opened from their metadata.
+inventory::submit! {
+    ChunkKeyEncodingPlugin::new(IDENTIFIER, is_name_hashed_prefix, create_chunk_key_encoding_hashed_prefix)
+}
+
+fn is_name_hashed_prefix(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+}
+
+fn create_chunk_key_encoding_hashed_prefix(
+    metadata: &Metadata,
+) -> Result<ChunkKeyEncoding, PluginCreateError> {
+    let configuration: HashedPrefixChunkKeyEncoding =
+        metadata.to_configuration().map_err(|_| {
+            PluginMetadataInvalidError::new(IDENTIFIER, "chunk key encoding", metadata.clone())
+        })?;
+    Ok(ChunkKeyEncoding::new(configuration))
+}
+
+/// A chunk key encoding which prefixes the `default` chunk key with `levels` nested components derived from a hash of the chunk indices.
+///
+/// For example, the chunk with grid index (1, 2) has a key like `c/3f/a2/1/2` with 2 levels.
+/// Chunks are spread uniformly across `256^levels` prefixes, which distributes requests in object stores that rate-limit requests per prefix.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct HashedPrefixChunkKeyEncoding {
+    levels: usize,
+}
+
+impl HashedPrefixChunkKeyEncoding {
+    /// A 64-bit FNV-1a hash, which (unlike [`std::hash::DefaultHasher`]) is stable across platforms and versions.
+    fn hash(chunk_grid_indices: &[u64]) -> u64 {
+        chunk_grid_indices
+            .iter()
+            .flat_map(|index| index.to_le_bytes())
+            .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+}
+
+impl ChunkKeyEncodingTraits for HashedPrefixChunkKeyEncoding {
+    fn create_metadata(&self) -> Metadata {
+        Metadata::new_with_serializable_configuration(IDENTIFIER, self).unwrap()
+    }
+
+    fn encode(&self, chunk_grid_indices: &[u64]) -> StoreKey {
+        let hash = Self::hash(chunk_grid_indices).to_be_bytes();
+        let mut components = vec!["c".to_string()];
+        components.extend(
+            hash.iter()
+                .take(self.levels)
+                .map(|byte| format!("{byte:02x}")),
+        );
+        components.extend(chunk_grid_indices.iter().map(ToString::to_string));
+        StoreKey::new(components.join("/")).unwrap()
+    }
+}
+
+fn custom_chunk_key_encoding() -> Result<(), Box<dyn std::error::Error>> {
+    let store = Arc::new(store::MemoryStore::new());
+
+    // Create an array with the custom chunk key encoding
+    let array = ArrayBuilder::new(
+        vec![4, 4],
+        DataType::UInt8,
+        vec![2, 2].try_into()?,
+        FillValue::from(0u8),
+    )
+    .chunk_key_encoding(HashedPrefixChunkKeyEncoding { levels: 2 }.into())
+    .build(store.clone(), "/array")?;
+    array.store_metadata()?;
+    array.store_array_subset_elements::<u8>(
+        &ArraySubset::new_with_ranges(&[0..4, 0..4]),
+        (0..16).collect(),
+    )?;
+
+    println!(
+        "The array metadata is:\n{}\n",
+        serde_json::to_string_pretty(&array.metadata()).unwrap()
+    );
+    println!("The store keys are:");
+    for key in store.list()? {
+        println!("  {key}");
+    }
+
+    // Open the array, the chunk key encoding is created from the metadata by the registered plugin
+    let array = Array::new(store, "/array")?;
+    let elements =
+        array.retrieve_array_subset_ndarray::<u8>(&ArraySubset::new_with_ranges(&[0..4, 0..4]))?;
+    println!("\nThe array is:\n{elements}");
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = custom_chunk_key_encoding() {
+        println!("{}", err);
+    }
+}
@@ -7,10 +7,29 @@
 //! The documentation for [`Array`] details how to interact with arrays.
 
 mod array_builder;
+mod array_chunk_keys;
+mod array_chunks_iter;
+mod array_compare;
+mod array_copy;
+mod array_elements_iter;
 mod array_errors;
+mod array_fold;
 mod array_metadata;
+#[cfg(feature = "n5")]
+mod array_metadata_n5;
+mod array_metadata_v2;
+mod array_origin;
+mod array_partial_decoder_cache;
+mod array_read_plan;
+mod array_recompress;
 mod array_representation;
+mod array_staged_writer;
+mod array_statistics;
+mod array_storage_size;
+mod array_transaction;
+mod array_verify;
 mod array_view;
+mod array_write_journal;
 mod bytes_representation;
 pub mod chunk_grid;
 pub mod chunk_key_encoding;
@@ -24,20 +43,48 @@ mod fill_value_metadata;
 mod nan_representations;
 mod unsafe_cell_slice;
 
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashSet},
+    num::NonZeroU64,
+    sync::Arc,
+};
+
+#[cfg(feature = "async")]
+pub use self::{array_compare::async_compare, array_copy::async_copy_array};
+
+#[cfg(feature = "n5")]
+pub use self::array_metadata_n5::{
+    array_metadata_n5_to_v3, codec_metadata_n5_to_v3, data_type_metadata_n5_to_v3, ArrayMetadataN5,
+    ArrayMetadataN5ToV3ConversionError,
+};
 
 pub use self::{
     array_builder::ArrayBuilder,
+    array_chunks_iter::ArrayChunksIter,
+    array_compare::{compare, ChunkDiff, CompareOptions, DiffReport},
+    array_copy::copy_array,
+    array_elements_iter::ArraySubsetElementsIter,
     array_errors::{ArrayCreateError, ArrayError},
     array_metadata::{ArrayMetadata, ArrayMetadataV3},
+    array_metadata_v2::{
+        array_metadata_v2_to_v3, codec_metadata_v2_to_v3, data_type_metadata_v2_to_v3,
+        ArrayMetadataV2, ArrayMetadataV2Order, ArrayMetadataV2ToV3ConversionError,
+    },
+    array_partial_decoder_cache::PartialDecoderCache,
+    array_read_plan::{ChunkReadPlan, ReadPlan},
     array_representation::{ArrayRepresentation, ChunkRepresentation},
-    array_view::{ArrayView, ArrayViewCreateError},
+    array_staged_writer::StagedArrayWriter,
+    array_statistics::{ArrayStatistics, ChunkStatistics, HistogramBins},
+    array_transaction::{ArrayTransaction, TransactionStorage},
+    array_verify::{ArrayVerifyReport, CorruptChunk},
+    array_view::{ArrayView, ArrayViewCreateError, ArrayViewMut},
+    array_write_journal::WriteJournal,
     bytes_representation::BytesRepresentation,
-    chunk_grid::ChunkGrid,
+    chunk_grid::{ChunkGrid, RectangularChunkGrid},
     chunk_key_encoding::ChunkKeyEncoding,
     chunk_shape::{chunk_shape_to_array_shape, ChunkShape},
-    codec::ArrayCodecTraits,
     codec::CodecChain,
+    codec::{ArrayCodecTraits, ArrayToBytesCodecTraits, CodecOptions},
     concurrency::RecommendedConcurrency,
     data_type::DataType,
     dimension_name::DimensionName,
@@ -47,16 +94,26 @@ pub use self::{
     unsafe_cell_slice::UnsafeCellSlice,
 };
 
-use serde::Serialize;
+use array_statistics::STATISTICS_ATTRIBUTE;
+use bytes::Bytes;
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Serialize};
 use thiserror::Error;
 
 use crate::{
     array_subset::{ArraySubset, IncompatibleDimensionalityError},
+    config::global_config,
     metadata::AdditionalFields,
     node::NodePath,
-    storage::storage_transformer::StorageTransformerChain,
+    storage::{
+        data_key, storage_adapter::ReadOnlyStorageAdapter,
+        storage_transformer::StorageTransformerChain, store_lock::StoreLocks, GenerationToken,
+    },
 };
 
+#[cfg(feature = "async")]
+use crate::storage::store_lock::AsyncStoreLocks;
+
 /// An ND index to an element in an array.
 pub type ArrayIndices = Vec<u64>;
 
@@ -76,7 +133,7 @@ pub struct NonZeroError;
 /// When a value is read from a store, it returns `MaybeBytes` which is [`None`] if the key is not available.
 /// A bytes to bytes codec only decodes `MaybeBytes` holding actual bytes, otherwise the bytes are propagated to the next decoder.
 /// An array to bytes partial decoder must take care of converting missing chunks to the fill value.
-pub type MaybeBytes = Option<Vec<u8>>;
+pub type MaybeBytes = Option<Bytes>;
 
 /// A Zarr array.
 ///
@@ -124,6 +181,7 @@ pub type MaybeBytes = Option<Vec<u8>>;
 ///    - [`retrieve_chunk_subset_into_array_view`](Array::retrieve_chunk_subset_into_array_view)
 ///    - [`retrieve_array_subset`](Array::retrieve_array_subset)
 ///    - [`retrieve_array_subset_into_array_view`](Array::retrieve_array_subset_into_array_view)
+///    - [`retrieve_array_subsets`](Array::retrieve_array_subsets)
 ///    - [`partial_decoder`](Array::partial_decoder)
 ///  - [`WritableStorageTraits`](crate::storage::WritableStorageTraits): store/erase array data and store metadata
 ///    - [`store_metadata`](Array::store_metadata)
@@ -131,29 +189,38 @@ pub type MaybeBytes = Option<Vec<u8>>;
 ///    - [`store_chunks`](Array::store_chunks)
 ///    - [`erase_chunk`](Array::erase_chunk)
 ///    - [`erase_chunks`](Array::erase_chunks)
+///    - [`erase`](Array::erase)
 ///  - [`ReadableWritableStorageTraits`](crate::storage::ReadableWritableStorageTraits): store operations requiring reading
 ///    - [`store_chunk_subset`](Array::store_chunk_subset)
 ///    - [`store_array_subset`](Array::store_array_subset)
+///    - [`resize`](Array::resize)
+///    - [`append`](Array::append)
 ///
 /// All `retrieve` and `store` methods have multiple variants:
 ///   - Standard variants store or retrieve data represented as bytes.
 ///   - `_elements` suffix variants can store or retrieve chunks with a known type.
 ///   - `_ndarray` suffix variants can store or retrieve [`ndarray::Array`]s (requires `ndarray` feature).
+///   - `_into` and `_into_ndarray` suffix variants of `retrieve` methods decode into a preallocated slice of elements or [`ndarray::ArrayViewMutD`], which can be reused across calls.
+///   - `_arrow` suffix variants of [`retrieve_array_subset`](Array::retrieve_array_subset) and [`store_array_subset`](Array::store_array_subset) can store or retrieve array subsets as one dimensional [`arrow_array::PrimitiveArray`]s in C order (requires `arrow` feature).
 ///   - Retrieve and store methods have an `_opt` variant with an additional [`CodecOptions`](crate::array::codec::CodecOptions) argument for fine-grained concurrency control.
 ///   - Variants without the `_opt` suffix use default [`CodecOptions`](crate::array::codec::CodecOptions) which just maximises concurrent operations. This is preferred unless using external parallelisation.
 ///
 /// #### Async API
 /// With the `async` feature and an async store, there are equivalent methods to the sync API with an `async_` prefix.
+/// Chunk retrieval, storage, erasure, and chunk statistics share a single implementation with the sync API, which is generic over sync and async storage.
+/// Other `async_` methods are implemented separately from their sync equivalents and may lack some of their functionality.
 ///
 /// <div class="warning">
 /// The async API is not as performant as sync API.
 /// </div>
 ///
 /// This crate is async runtime-agnostic and does not spawn tasks internally.
-/// The implication is that methods like [`async_retrieve_array_subset`](Array::async_retrieve_array_subset) or [`async_retrieve_chunks`](Array::async_retrieve_chunks) do not parallelise over chunks and can be slow compared to the sync API (especially when they involve a large number of chunks).
+/// Methods like [`async_retrieve_array_subset`](Array::async_retrieve_array_subset) or [`async_retrieve_chunks`](Array::async_retrieve_chunks) retrieve chunks concurrently (bounded by the concurrent target of the [`CodecOptions`](crate::array::codec::CodecOptions)), and decode retrieved chunks in parallel with [`rayon`] on the calling thread.
+/// Decoding blocks the calling task, so these methods may still be slower than the sync API.
 ///
-/// This limitation can be circumvented by spawning tasks outside of zarrs.
+/// This can be circumvented by spawning tasks outside of zarrs.
 /// For example, instead of using [`async_retrieve_chunks`](Array::async_retrieve_chunks), multiple tasks executing [`async_retrieve_chunk_into_array_view`](Array::async_retrieve_chunk_into_array_view) could be spawned that output to a preallocated buffer.
+/// [`ArrayViewMut::split_disjoint`] splits a preallocated buffer into views of disjoint array subsets which can be safely passed to such tasks with [`async_retrieve_chunk_into_array_view_mut`](Array::async_retrieve_chunk_into_array_view_mut).
 /// An example of such an approach can be found in the [`zarrs_benchmark_read_async`](https://github.com/LDeakin/zarrs_tools/blob/v0.3.0/src/bin/zarrs_benchmark_read_async.rs) application in the [zarrs_tools](https://github.com/LDeakin/zarrs_tools) crate.
 ///
 /// ### Parallel Writing
@@ -167,6 +234,7 @@ pub type MaybeBytes = Option<Vec<u8>>;
 /// #### Default Store Locking ([`DefaultStoreLocks`](crate::storage::store_lock::DefaultStoreLocks))
 ///
 /// By default, stores use [`DefaultStoreLocks`](crate::storage::store_lock::DefaultStoreLocks) internally, but this can be changed with a `new_with_locks` store constructor if implemented.
+/// The store locks can also be overridden per array with [`ArrayBuilder::store_locks`] or [`Array::set_store_locks`], so that arrays in the same store can use different locking policies.
 ///
 /// With [`DefaultStoreLocks`](crate::storage::store_lock::DefaultStoreLocks), if data is written in overlapping array subsets with the [`store_chunk_subset`](Array::store_chunk_subset) or [`store_array_subset`](Array::store_array_subset) methods, the value of an element in overlapping regions depends on whichever operation wrote to its associated chunk last.
 /// Consider the case of parallel writing of the following subsets to a `1x6` array and a `1x3` chunk size (**do not do this, it is just an example**):
@@ -194,8 +262,10 @@ pub type MaybeBytes = Option<Vec<u8>>;
 ///
 /// #### Distributed Processes
 ///
-/// The synchronisation guarantees provided by an [`Array`] and its underlying store are not applicable in a distributed context (e.g. a distributed program on a cluster).
-/// In such cases, the recommendations outlined in [Disabled Store Locking](#disabled-store-locking-disabledstorelocks) should be followed to ensure written data is not lost.
+/// The synchronisation guarantees provided by [`DefaultStoreLocks`](crate::storage::store_lock::DefaultStoreLocks) are not applicable in a distributed context (e.g. a distributed program on a cluster).
+/// In such cases, either:
+///  - the recommendations outlined in [Disabled Store Locking](#disabled-store-locking-disabledstorelocks) should be followed to ensure written data is not lost, or
+///  - the store (or array) should use [`KeyLockProviderStoreLocks`](crate::storage::store_lock::KeyLockProviderStoreLocks) with a [`KeyLockProvider`](crate::storage::store_lock::KeyLockProvider) shared by all processes, such as a [`FileKeyLockProvider`](crate::storage::store_lock::FileKeyLockProvider) on a shared filesystem.
 ///
 /// ### Best Practices
 ///
@@ -213,7 +283,16 @@ pub type MaybeBytes = Option<Vec<u8>>;
 ///
 /// ### `zarrs` Metadata
 /// By default, the `zarrs` version and a link to its source code is written to the `_zarrs` attribute in array metadata.
-/// This can be disabled with [`set_include_zarrs_metadata(false)`](Array::set_include_zarrs_metadata).
+/// This can be disabled with [`set_include_zarrs_metadata(false)`](Array::set_include_zarrs_metadata), or for all arrays with [`Config::set_include_zarrs_metadata`](crate::config::Config::set_include_zarrs_metadata).
+///
+/// ### Statistics
+/// An array can optionally compute [`ChunkStatistics`] (count, min, max, sum, and an optional histogram) of each chunk as it is stored with [`store_chunk`](Array::store_chunk) and methods built upon it (e.g. [`store_array_subset`](Array::store_array_subset)).
+/// This is enabled with [`ArrayBuilder::compute_statistics`] or [`set_compute_statistics`](Array::set_compute_statistics).
+/// The statistics of each chunk are stored under the `zarrs_statistics/` prefix of the array (see [`storage::statistics_key`](crate::storage::statistics_key)) and are retrieved with [`chunk_statistics`](Array::chunk_statistics).
+///
+/// Whole array [`ArrayStatistics`] are aggregated from the stored chunk statistics with [`update_statistics`](Array::update_statistics) and retrieved with [`statistics`](Array::statistics), which avoids scanning every chunk to determine the data range of an array.
+/// Only the aggregate statistics are written to the `_zarrs_statistics` attribute in array metadata with [`store_metadata`](Array::store_metadata), and they are restored when an array with this attribute is opened.
+/// Statistics are only accurate if all chunks are written by [`Array`]s with statistics enabled, and they are not updated by chunks written directly to the store (e.g. with a [`ShardWriter`](crate::array::codec::array_to_bytes::sharding::ShardWriter)).
 #[derive(Debug)]
 pub struct Array<TStorage: ?Sized> {
     /// The storage (including storage transformers).
@@ -242,6 +321,15 @@ pub struct Array<TStorage: ?Sized> {
     additional_fields: AdditionalFields,
     /// Zarrs metadata.
     include_zarrs_metadata: bool,
+    /// Aggregate array statistics, if statistics are computed.
+    statistics: Option<ArrayStatistics>,
+    /// The generation of the array metadata in the store, if known.
+    metadata_generation: Mutex<Option<GenerationToken>>,
+    /// Store locks overriding those of the store.
+    store_locks: Option<StoreLocks>,
+    /// Asynchronous store locks overriding those of the store.
+    #[cfg(feature = "async")]
+    async_store_locks: Option<AsyncStoreLocks>,
 }
 
 impl<TStorage: ?Sized> Array<TStorage> {
@@ -259,7 +347,10 @@ impl<TStorage: ?Sized> Array<TStorage> {
     ) -> Result<Self, ArrayCreateError> {
         let path = NodePath::new(path)?;
 
-        let ArrayMetadata::V3(metadata) = metadata;
+        let metadata = match metadata {
+            ArrayMetadata::V3(metadata) => metadata,
+            ArrayMetadata::V2(metadata) => array_metadata_v2_to_v3(&metadata)?,
+        };
         if !metadata.validate_format() {
             return Err(ArrayCreateError::InvalidZarrFormat(metadata.zarr_format));
         }
@@ -299,6 +390,11 @@ impl<TStorage: ?Sized> Array<TStorage> {
             }
         }
 
+        let mut attributes = metadata.attributes;
+        let statistics = attributes
+            .remove(STATISTICS_ATTRIBUTE)
+            .and_then(ArrayStatistics::from_value);
+
         Ok(Self {
             storage,
             path,
@@ -308,25 +404,164 @@ impl<TStorage: ?Sized> Array<TStorage> {
             chunk_key_encoding,
             fill_value,
             codecs,
-            attributes: metadata.attributes,
+            attributes,
             additional_fields: metadata.additional_fields,
             storage_transformers,
             dimension_names: metadata.dimension_names,
-            include_zarrs_metadata: true,
+            include_zarrs_metadata: global_config().include_zarrs_metadata(),
+            statistics,
+            metadata_generation: Mutex::new(None),
+            store_locks: None,
+            #[cfg(feature = "async")]
+            async_store_locks: None,
         })
     }
 
+    /// Return a read only view of the array.
+    ///
+    /// The returned array shares the storage of this array, wrapped in a [`ReadOnlyStorageAdapter`].
+    /// Any operation on the returned array that writes to the store (e.g. storing chunks or metadata) fails with [`StorageError::ReadOnly`](crate::storage::StorageError::ReadOnly).
+    #[must_use]
+    pub fn readonly(&self) -> Array<ReadOnlyStorageAdapter<TStorage>> {
+        Array {
+            storage: Arc::new(ReadOnlyStorageAdapter::new(self.storage.clone())),
+            path: self.path.clone(),
+            shape: self.shape.clone(),
+            data_type: self.data_type.clone(),
+            chunk_grid: self.chunk_grid.clone(),
+            chunk_key_encoding: self.chunk_key_encoding.clone(),
+            fill_value: self.fill_value.clone(),
+            codecs: self.codecs.clone(),
+            attributes: self.attributes.clone(),
+            storage_transformers: self.storage_transformers.clone(),
+            dimension_names: self.dimension_names.clone(),
+            additional_fields: self.additional_fields.clone(),
+            include_zarrs_metadata: self.include_zarrs_metadata,
+            statistics: self.statistics.clone(),
+            metadata_generation: Mutex::new(self.metadata_generation.lock().clone()),
+            store_locks: self.store_locks.clone(),
+            #[cfg(feature = "async")]
+            async_store_locks: self.async_store_locks.clone(),
+        }
+    }
+
+    /// Use `store_locks` for chunk locking rather than the locks of the store.
+    ///
+    /// See [`set_store_locks`](Array::set_store_locks).
+    #[must_use]
+    pub fn with_store_locks(mut self, store_locks: StoreLocks) -> Self {
+        self.store_locks = Some(store_locks);
+        self
+    }
+
+    /// Set the store locks used for chunk locking.
+    ///
+    /// If [`Some`], the locks of the store are bypassed by this array.
+    /// For example, an array with [`DisabledStoreLocks`](crate::storage::store_lock::DisabledStoreLocks) can be written lock-free (if each chunk is exclusively written by a single thread), while other arrays in the same store remain locked.
+    /// If [`None`], the locks of the store are used.
+    pub fn set_store_locks(&mut self, store_locks: Option<StoreLocks>) {
+        self.store_locks = store_locks;
+    }
+
+    /// Return the store locks used for chunk locking, if they override those of the store.
+    #[must_use]
+    pub const fn store_locks(&self) -> Option<&StoreLocks> {
+        self.store_locks.as_ref()
+    }
+
+    /// Use `async_store_locks` for chunk locking with asynchronous methods rather than the locks of the store.
+    ///
+    /// See [`set_async_store_locks`](Array::set_async_store_locks).
+    #[cfg(feature = "async")]
+    #[must_use]
+    pub fn with_async_store_locks(mut self, async_store_locks: AsyncStoreLocks) -> Self {
+        self.async_store_locks = Some(async_store_locks);
+        self
+    }
+
+    /// Set the asynchronous store locks used for chunk locking with asynchronous methods.
+    ///
+    /// If [`None`], the locks of the store are used.
+    #[cfg(feature = "async")]
+    pub fn set_async_store_locks(&mut self, async_store_locks: Option<AsyncStoreLocks>) {
+        self.async_store_locks = async_store_locks;
+    }
+
+    /// Return the asynchronous store locks used for chunk locking, if they override those of the store.
+    #[cfg(feature = "async")]
+    #[must_use]
+    pub const fn async_store_locks(&self) -> Option<&AsyncStoreLocks> {
+        self.async_store_locks.as_ref()
+    }
+
+    /// Return the generation of the array metadata in the store, if known.
+    ///
+    /// The generation is set when an array is opened from a store that supports conditional writes, and is updated by [`store_metadata`](Array<WritableStorageTraits>::store_metadata).
+    #[must_use]
+    pub fn metadata_generation(&self) -> Option<GenerationToken> {
+        self.metadata_generation.lock().clone()
+    }
+
+    /// Set the generation of the array metadata in the store.
+    ///
+    /// If [`Some`], [`store_metadata`](Array<WritableStorageTraits>::store_metadata) fails with [`StorageError::PreconditionFailed`](crate::storage::StorageError::PreconditionFailed) if the metadata in the store has a different generation.
+    /// If [`None`], metadata is stored unconditionally.
+    pub fn set_metadata_generation(&self, generation: Option<GenerationToken>) {
+        *self.metadata_generation.lock() = generation;
+    }
+
     /// Set the shape of the array.
     pub fn set_shape(&mut self, shape: ArrayShape) {
         self.shape = shape;
     }
 
+    /// Set the codecs of the array.
+    ///
+    /// Chunks stored after this are encoded with `codecs`, but existing chunks are not rewritten and will fail to decode if they are incompatible with `codecs`.
+    /// Use [`recompress_chunks`](Array::recompress_chunks) to re-encode existing chunks with new codecs.
+    /// This does **not** write to the store, use [`store_metadata`](Array::store_metadata) to write the codecs to the store.
+    pub fn set_codecs(&mut self, codecs: CodecChain) {
+        self.codecs = codecs;
+    }
+
     /// Mutably borrow the array attributes.
     #[must_use]
     pub fn attributes_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
         &mut self.attributes
     }
 
+    /// Deserialise the attribute `key` as `T`.
+    ///
+    /// Returns [`None`] if the attribute does not exist.
+    ///
+    /// # Errors
+    /// Returns a [`serde_json::Error`] if the attribute cannot be deserialised as `T`.
+    pub fn attribute<T: DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, serde_json::Error> {
+        self.attributes
+            .get(key)
+            .map(|value| T::deserialize(value))
+            .transpose()
+    }
+
+    /// Serialise `value` and set it as the attribute `key`, returning the previous value of the attribute.
+    ///
+    /// This does **not** write to the store, use [`store_attributes`](Array::store_attributes) or [`store_metadata`](Array::store_metadata) to write the attributes to the store.
+    ///
+    /// # Errors
+    /// Returns a [`serde_json::Error`] if `value` cannot be serialised.
+    pub fn set_attribute<T: Serialize>(
+        &mut self,
+        key: &str,
+        value: T,
+    ) -> Result<Option<serde_json::Value>, serde_json::Error> {
+        Ok(self
+            .attributes
+            .insert(key.to_string(), serde_json::to_value(value)?))
+    }
+
     /// Get the node path.
     #[must_use]
     pub const fn path(&self) -> &NodePath {
@@ -399,17 +634,101 @@ impl<TStorage: ?Sized> Array<TStorage> {
         &self.additional_fields
     }
 
-    /// Enable or disable the inclusion of zarrs metadata in the array attributes. Enabled by default.
+    /// Enable or disable the inclusion of zarrs metadata in the array attributes.
+    ///
+    /// Defaults to the [include zarrs metadata](crate::config::Config#include-zarrs-metadata) global configuration, which is enabled by default.
     ///
     /// Zarrs metadata includes the zarrs version and some parameters.
     pub fn set_include_zarrs_metadata(&mut self, include_zarrs_metadata: bool) {
         self.include_zarrs_metadata = include_zarrs_metadata;
     }
 
-    /// Create [`ArrayMetadata`].
+    /// Returns true if chunk statistics are computed when chunks are stored.
     #[must_use]
-    pub fn metadata(&self) -> ArrayMetadata {
-        let attributes = if self.include_zarrs_metadata {
+    pub const fn compute_statistics(&self) -> bool {
+        self.statistics.is_some()
+    }
+
+    /// Enable or disable the computation of chunk statistics when chunks are stored. Disabled by default.
+    ///
+    /// Disabling statistics discards the aggregate array statistics, but not the stored chunk statistics.
+    /// Use [`ArrayBuilder::statistics_histogram_bins`] to also compute histograms.
+    pub fn set_compute_statistics(&mut self, compute_statistics: bool) {
+        if !compute_statistics {
+            self.statistics = None;
+        } else if self.statistics.is_none() {
+            self.statistics = Some(ArrayStatistics::new(None));
+        }
+    }
+
+    /// Return the histogram bins of the statistics, if statistics and histograms are computed.
+    #[must_use]
+    pub fn statistics_histogram_bins(&self) -> Option<HistogramBins> {
+        self.statistics
+            .as_ref()
+            .and_then(|statistics| statistics.histogram_bins().copied())
+    }
+
+    /// Return the statistics of the array, as of the last [`update_statistics`](Array::update_statistics) or when the array was opened.
+    ///
+    /// Returns [`None`] if statistics are not computed.
+    #[must_use]
+    pub const fn statistics(&self) -> Option<&ArrayStatistics> {
+        self.statistics.as_ref()
+    }
+
+    /// Compute the statistics of the chunk at `chunk_indices` with `chunk_bytes`.
+    ///
+    /// Returns [`None`] if statistics are not computed or the data type does not support statistics.
+    fn compute_chunk_statistics(
+        &self,
+        chunk_indices: &[u64],
+        chunk_bytes: &[u8],
+    ) -> Result<Option<ChunkStatistics>, ArrayError> {
+        let Some(statistics) = &self.statistics else {
+            return Ok(None);
+        };
+        let histogram_bins = statistics.histogram_bins().copied();
+
+        // Exclude elements of edge chunks outside of the array
+        let chunk_subset = self.chunk_subset(chunk_indices)?;
+        let chunk_subset_bounded = unsafe { chunk_subset.bound_unchecked(self.shape()) };
+        if chunk_subset == chunk_subset_bounded {
+            Ok(ChunkStatistics::compute(
+                chunk_bytes,
+                self.data_type(),
+                histogram_bins.as_ref(),
+            ))
+        } else {
+            let chunk_bytes_bounded = unsafe {
+                chunk_subset_bounded
+                    .relative_to_unchecked(chunk_subset.start())
+                    .extract_bytes_unchecked(
+                        chunk_bytes,
+                        chunk_subset.shape(),
+                        self.data_type().size(),
+                    )
+            };
+            Ok(ChunkStatistics::compute(
+                &chunk_bytes_bounded,
+                self.data_type(),
+                histogram_bins.as_ref(),
+            ))
+        }
+    }
+
+    /// Return the attributes to store in the array metadata, including zarrs metadata and statistics if enabled.
+    fn metadata_attributes(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut attributes = self.metadata_attributes_zarrs();
+        if let Some(statistics) = &self.statistics {
+            attributes.insert(STATISTICS_ATTRIBUTE.to_string(), statistics.to_value());
+        }
+        attributes
+    }
+
+    /// Return the attributes to store in the array metadata, including zarrs metadata if enabled.
+    fn metadata_attributes_zarrs(&self) -> serde_json::Map<String, serde_json::Value> {
+        if self.include_zarrs_metadata {
             #[derive(Serialize)]
             struct ZarrsMetadata {
                 description: String,
@@ -428,8 +747,12 @@ impl<TStorage: ?Sized> Array<TStorage> {
             attributes
         } else {
             self.attributes().clone()
-        };
+        }
+    }
 
+    /// Create [`ArrayMetadata`].
+    #[must_use]
+    pub fn metadata(&self) -> ArrayMetadata {
         ArrayMetadataV3::new(
             self.shape().to_vec(),
             self.data_type().metadata(),
@@ -437,7 +760,7 @@ impl<TStorage: ?Sized> Array<TStorage> {
             self.chunk_key_encoding().create_metadata(),
             self.data_type().metadata_fill_value(self.fill_value()),
             self.codecs().create_metadatas(),
-            attributes,
+            self.metadata_attributes(),
             self.storage_transformers().create_metadatas(),
             self.dimension_names().clone(),
             self.additional_fields().clone(),
@@ -556,6 +879,314 @@ impl<TStorage: ?Sized> Array<TStorage> {
         )
     }
 
+    /// Return an error if `chunk_indices` do not match the dimensionality of the array.
+    fn validate_chunk_indices(&self, chunk_indices: &[u64]) -> Result<(), ArrayError> {
+        if chunk_indices.len() == self.dimensionality() {
+            Ok(())
+        } else {
+            Err(ArrayError::InvalidChunkGridIndicesError(
+                chunk_indices.to_vec(),
+            ))
+        }
+    }
+
+    /// Decode the encoded chunk at `chunk_indices`.
+    ///
+    /// This is the storage independent part of chunk retrieval, shared by the sync and async APIs.
+    fn decode_chunk(
+        &self,
+        chunk_encoded: Bytes,
+        chunk_indices: &[u64],
+        options: &codec::CodecOptions,
+    ) -> Result<Vec<u8>, ArrayError> {
+        let chunk_representation = self.chunk_array_representation(chunk_indices)?;
+        let chunk_decoded = self
+            .codecs()
+            .decode(chunk_encoded, &chunk_representation, options)
+            .map_err(ArrayError::CodecError)?;
+        let chunk_decoded_size =
+            chunk_representation.num_elements_usize() * chunk_representation.data_type().size();
+        if chunk_decoded.len() == chunk_decoded_size {
+            Ok(chunk_decoded)
+        } else {
+            Err(ArrayError::UnexpectedChunkDecodedSize(
+                chunk_decoded.len(),
+                chunk_decoded_size,
+            ))
+        }
+    }
+
+    /// Return the bytes of the chunk at `chunk_indices` with all elements set to the fill value.
+    fn fill_value_chunk(&self, chunk_indices: &[u64]) -> Result<Vec<u8>, ArrayError> {
+        let chunk_representation = self.chunk_array_representation(chunk_indices)?;
+        let fill_value = chunk_representation.fill_value().as_ne_bytes();
+        Ok(fill_value.repeat(chunk_representation.num_elements_usize()))
+    }
+
+    /// Encode `chunk_bytes` of the chunk at `chunk_indices` and compute its statistics.
+    ///
+    /// Returns [`None`] if the chunk is entirely the fill value and empty chunks are not stored, in which case the chunk should be erased.
+    /// This is the storage independent part of chunk storage, shared by the sync and async APIs.
+    fn encode_chunk(
+        &self,
+        chunk_indices: &[u64],
+        chunk_bytes: Vec<u8>,
+        options: &codec::CodecOptions,
+    ) -> Result<Option<EncodedChunk>, ArrayError> {
+        let Some((chunk_array_representation, chunk_statistics)) =
+            self.prepare_chunk_encode(chunk_indices, &chunk_bytes, options)?
+        else {
+            return Ok(None);
+        };
+        let chunk_encoded = self
+            .codecs()
+            .encode(chunk_bytes, &chunk_array_representation, options)
+            .map_err(ArrayError::CodecError)?;
+        Ok(Some((chunk_encoded, chunk_statistics)))
+    }
+
+    /// Encode `chunk_bytes` of the chunk at `chunk_indices` as a sequence of segments and compute its statistics.
+    ///
+    /// This is [`encode_chunk`](Array::encode_chunk), except that segments are encoded as they are consumed so that the encoded chunk can be streamed into a store.
+    #[cfg(feature = "async")]
+    fn encode_chunk_segments(
+        &self,
+        chunk_indices: &[u64],
+        chunk_bytes: Vec<u8>,
+        options: &codec::CodecOptions,
+    ) -> Result<Option<(codec::EncodedSegments<'_>, Option<ChunkStatistics>)>, ArrayError> {
+        let Some((chunk_array_representation, chunk_statistics)) =
+            self.prepare_chunk_encode(chunk_indices, &chunk_bytes, options)?
+        else {
+            return Ok(None);
+        };
+        let segments = self
+            .codecs()
+            .encode_segments(chunk_bytes, &chunk_array_representation, options)
+            .map_err(ArrayError::CodecError)?;
+        Ok(Some((segments, chunk_statistics)))
+    }
+
+    /// Validate `chunk_bytes` of the chunk at `chunk_indices` and compute its statistics before it is encoded.
+    ///
+    /// Returns [`None`] if the chunk is entirely the fill value and empty chunks are not stored.
+    fn prepare_chunk_encode(
+        &self,
+        chunk_indices: &[u64],
+        chunk_bytes: &[u8],
+        options: &codec::CodecOptions,
+    ) -> Result<Option<(ChunkRepresentation, Option<ChunkStatistics>)>, ArrayError> {
+        let chunk_array_representation = self.chunk_array_representation(chunk_indices)?;
+        if chunk_bytes.len() as u64 != chunk_array_representation.size() {
+            return Err(ArrayError::InvalidBytesInputSize(
+                chunk_bytes.len(),
+                chunk_array_representation.size(),
+            ));
+        }
+
+        if !options.store_empty_chunks() && self.fill_value().equals_all(chunk_bytes) {
+            return Ok(None);
+        }
+        let chunk_statistics = self.compute_chunk_statistics(chunk_indices, chunk_bytes)?;
+        Ok(Some((chunk_array_representation, chunk_statistics)))
+    }
+
+    /// Decode an encoded chunk into `array_view`, or fill `array_view` with the fill value if the chunk does not exist.
+    fn decode_chunk_into_array_view(
+        &self,
+        chunk_encoded: Option<&[u8]>,
+        chunk_indices: &[u64],
+        array_view: &ArrayView,
+        options: &codec::CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let chunk_representation = self.chunk_array_representation(chunk_indices)?;
+        let chunk_shape_u64 = chunk_representation.shape_u64();
+        if chunk_shape_u64 != array_view.subset().shape() {
+            return Err(ArrayError::InvalidArraySubset(
+                array_view.subset().clone(),
+                chunk_shape_u64,
+            ));
+        }
+
+        if let Some(chunk_encoded) = chunk_encoded {
+            self.codecs()
+                .decode_into_array_view(chunk_encoded, &chunk_representation, array_view, options)
+                .map_err(ArrayError::CodecError)
+        } else {
+            // fill array_view with fill value
+            let contiguous_indices = unsafe {
+                array_view
+                    .subset()
+                    .contiguous_linearised_indices_unchecked(array_view.array_shape())
+            };
+            let element_size = chunk_representation.element_size();
+            let length = contiguous_indices.contiguous_elements_usize() * element_size;
+            let fill = self
+                .fill_value()
+                .as_ne_bytes()
+                .repeat(contiguous_indices.contiguous_elements_usize());
+            // FIXME: Par iteration?
+            let output = unsafe { array_view.bytes_mut() };
+            for (array_subset_element_index, _num_elements) in &contiguous_indices {
+                let output_offset =
+                    usize::try_from(array_subset_element_index).unwrap() * element_size;
+                debug_assert!((output_offset + length) <= output.len());
+                output[output_offset..output_offset + length].copy_from_slice(&fill);
+            }
+            Ok(())
+        }
+    }
+
+    /// Return the chunks intersecting the regions of the array outside of `new_shape`.
+    fn chunks_outside_shape(&self, new_shape: &[u64]) -> Result<HashSet<ArrayIndices>, ArrayError> {
+        if new_shape.len() != self.dimensionality() {
+            return Err(IncompatibleDimensionalityError::new(
+                new_shape.len(),
+                self.dimensionality(),
+            )
+            .into());
+        }
+
+        // Find the chunks intersecting the regions removed along each axis
+        let mut chunks_outside = HashSet::new();
+        for (axis, (&size, &new_size)) in std::iter::zip(self.shape(), new_shape).enumerate() {
+            if new_size < size {
+                let mut start = vec![0; self.dimensionality()];
+                start[axis] = new_size;
+                let mut shape = self.shape().to_vec();
+                shape[axis] = size - new_size;
+                let removed = unsafe { ArraySubset::new_with_start_shape_unchecked(start, shape) };
+                if let Some(chunks) = self.chunks_in_array_subset(&removed)? {
+                    chunks_outside.extend(&chunks.indices());
+                }
+            }
+        }
+        Ok(chunks_outside)
+    }
+
+    /// Return `chunk_bytes` of the chunk at `chunk_subset` with the elements outside of `array_shape` set to the fill value.
+    fn chunk_bytes_bounded(
+        &self,
+        chunk_subset: &ArraySubset,
+        chunk_bytes: &[u8],
+        array_shape: &[u64],
+    ) -> Vec<u8> {
+        let element_size = self.data_type().size();
+        let array_subset = ArraySubset::new_with_shape(array_shape.to_vec());
+        let overlap = unsafe { chunk_subset.overlap_unchecked(&array_subset) };
+        let overlap_in_chunk = unsafe { overlap.relative_to_unchecked(chunk_subset.start()) };
+        let mut chunk_bytes_bounded = self
+            .fill_value()
+            .as_ne_bytes()
+            .repeat(chunk_subset.num_elements_usize());
+        let contiguous_indices = unsafe {
+            overlap_in_chunk.contiguous_linearised_indices_unchecked(chunk_subset.shape())
+        };
+        let length = contiguous_indices.contiguous_elements_usize() * element_size;
+        for (chunk_element_index, _num_elements) in &contiguous_indices {
+            let offset = usize::try_from(chunk_element_index).unwrap() * element_size;
+            chunk_bytes_bounded[offset..offset + length]
+                .copy_from_slice(&chunk_bytes[offset..offset + length]);
+        }
+        chunk_bytes_bounded
+    }
+
+    /// Return the array subset and the new array shape for appending `append_bytes_len` bytes along `axis`.
+    fn append_subset(
+        &self,
+        axis: usize,
+        append_bytes_len: usize,
+    ) -> Result<(ArraySubset, ArrayShape), ArrayError> {
+        if axis >= self.dimensionality() {
+            return Err(ArrayError::InvalidAxis(axis, self.dimensionality()));
+        }
+        let slice_size = self
+            .shape()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, size)| if i == axis { None } else { Some(*size) })
+            .product::<u64>()
+            * self.data_type().size() as u64;
+        let append_length = (append_bytes_len as u64)
+            .checked_div(slice_size)
+            .unwrap_or_default();
+        if append_length * slice_size != append_bytes_len as u64 {
+            return Err(ArrayError::InvalidBytesInputSize(
+                append_bytes_len,
+                (append_length + 1) * slice_size,
+            ));
+        }
+
+        let mut start = vec![0; self.dimensionality()];
+        start[axis] = self.shape()[axis];
+        let mut append_shape = self.shape().to_vec();
+        append_shape[axis] = append_length;
+        let mut new_shape = self.shape().to_vec();
+        new_shape[axis] += append_length;
+        let append_subset =
+            unsafe { ArraySubset::new_with_start_shape_unchecked(start, append_shape) };
+        Ok((append_subset, new_shape))
+    }
+
+    /// Return the chunk grid of the array with a chunk of the length of `append_subset` along `axis` appended to the end of `axis`.
+    fn append_ragged_chunk_grid(
+        &self,
+        axis: usize,
+        append_subset: &ArraySubset,
+    ) -> Result<ChunkGrid, ArrayError> {
+        let invalid = |reason: String| ArrayError::InvalidRaggedAppend(axis, reason);
+        let mut chunk_grid =
+            RectangularChunkGrid::from_metadata(&self.chunk_grid.create_metadata())
+                .ok_or_else(|| invalid("the chunk grid is not rectangular".to_string()))?;
+        let size = self.shape()[axis];
+        let append_length = NonZeroU64::new(append_subset.shape()[axis])
+            .ok_or_else(|| invalid("the appended chunk is empty".to_string()))?;
+        let extent = chunk_grid.dimension_extent(axis);
+        if let Some(extent) = extent.filter(|extent| *extent != size) {
+            return Err(invalid(format!(
+                "the chunk grid extent {extent} does not match the array shape {size}"
+            )));
+        }
+        chunk_grid
+            .push_chunk_size(axis, append_length)
+            .map_err(|err| invalid(err.to_string()))?;
+        if extent.is_none() && size % append_length.get() != 0 {
+            // A fixed chunk size, the array shape must end on a chunk boundary
+            return Err(invalid(format!(
+                "the array shape {size} is not a multiple of the chunk size {append_length}"
+            )));
+        }
+        Ok(ChunkGrid::new(chunk_grid))
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Validate that the shape of an array appended along `axis` matches the array shape excluding `axis`.
+    fn validate_append_ndarray_shape(
+        &self,
+        axis: usize,
+        append_shape: &[usize],
+    ) -> Result<(), ArrayError> {
+        if axis >= self.dimensionality() {
+            return Err(ArrayError::InvalidAxis(axis, self.dimensionality()));
+        }
+        let mut expected_shape: Vec<usize> = self
+            .shape()
+            .iter()
+            .map(|u| usize::try_from(*u).unwrap())
+            .collect();
+        if append_shape.len() == expected_shape.len() {
+            expected_shape[axis] = append_shape[axis];
+        }
+        if append_shape == expected_shape {
+            Ok(())
+        } else {
+            Err(ArrayError::InvalidDataShape(
+                append_shape.to_vec(),
+                expected_shape,
+            ))
+        }
+    }
+
     /// Return an array subset indicating the chunks intersecting `array_subset`.
     ///
     /// Returns [`None`] if the intersecting chunks cannot be determined.
@@ -590,6 +1221,101 @@ impl<TStorage: ?Sized> Array<TStorage> {
         }
     }
 
+    /// Return the chunks intersecting any of `array_subsets` in C order, mapped to the indices of the array subsets they intersect.
+    ///
+    /// # Errors
+    /// Returns [`ArrayError::InvalidArraySubset`] if any array subset is incompatible with the array.
+    fn chunks_in_array_subsets(
+        &self,
+        array_subsets: &[ArraySubset],
+    ) -> Result<BTreeMap<ArrayIndices, Vec<usize>>, ArrayError> {
+        let mut chunks_subsets: BTreeMap<ArrayIndices, Vec<usize>> = BTreeMap::new();
+        for (index, array_subset) in array_subsets.iter().enumerate() {
+            let chunks = if array_subset.dimensionality() == self.dimensionality() {
+                self.chunks_in_array_subset(array_subset)?
+            } else {
+                None
+            };
+            let Some(chunks) = chunks else {
+                return Err(ArrayError::InvalidArraySubset(
+                    array_subset.clone(),
+                    self.shape().to_vec(),
+                ));
+            };
+            if !array_subset.is_empty() {
+                for chunk_indices in &chunks.indices() {
+                    chunks_subsets.entry(chunk_indices).or_default().push(index);
+                }
+            }
+        }
+        Ok(chunks_subsets)
+    }
+
+    /// Group the elements at `array_indices` by the chunk containing them.
+    ///
+    /// Returns the chunks in C order mapped to the position of each element in `array_indices` and its indices within the chunk.
+    ///
+    /// # Errors
+    /// Returns [`ArrayError::InvalidArrayIndices`] if any array indices are not within the bounds of the array.
+    fn chunks_elements(
+        &self,
+        array_indices: &[ArrayIndices],
+    ) -> Result<BTreeMap<ArrayIndices, Vec<(usize, ArrayIndices)>>, ArrayError> {
+        let mut chunks_elements: BTreeMap<ArrayIndices, Vec<(usize, ArrayIndices)>> =
+            BTreeMap::new();
+        for (index, indices) in array_indices.iter().enumerate() {
+            let inbounds = indices.len() == self.dimensionality()
+                && std::iter::zip(indices, self.shape()).all(|(index, shape)| index < shape);
+            let chunk_element = if inbounds {
+                let chunk_grid = self.chunk_grid();
+                Option::zip(
+                    unsafe { chunk_grid.chunk_indices_unchecked(indices, self.shape()) },
+                    unsafe { chunk_grid.chunk_element_indices_unchecked(indices, self.shape()) },
+                )
+            } else {
+                None
+            };
+            let Some((chunk_indices, chunk_element_indices)) = chunk_element else {
+                return Err(ArrayError::InvalidArrayIndices(
+                    indices.clone(),
+                    self.shape().to_vec(),
+                ));
+            };
+            chunks_elements
+                .entry(chunk_indices)
+                .or_default()
+                .push((index, chunk_element_indices));
+        }
+        Ok(chunks_elements)
+    }
+
+    /// Return the chunks intersecting `array_subset` if it is aligned with chunk boundaries.
+    ///
+    /// # Errors
+    /// Returns [`ArrayError::InvalidArraySubset`] if `array_subset` is incompatible with the array, or [`ArrayError::UnalignedArraySubset`] if it does not exactly cover the chunks it intersects.
+    fn chunks_in_aligned_array_subset(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<ArraySubset, ArrayError> {
+        if array_subset.dimensionality() != self.dimensionality() {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        }
+        let Some(chunks) = self.chunks_in_array_subset(array_subset)? else {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        };
+        if array_subset.num_elements() == 0 || self.chunks_subset(&chunks)? == *array_subset {
+            Ok(chunks)
+        } else {
+            Err(ArrayError::UnalignedArraySubset(array_subset.clone()))
+        }
+    }
+
     /// Calculate the recommended codec concurrency.
     fn recommended_codec_concurrency(
         &self,
@@ -599,6 +1325,33 @@ impl<TStorage: ?Sized> Array<TStorage> {
             .codecs()
             .recommended_concurrency(chunk_representation)?)
     }
+
+    /// Estimate the memory usage (in bytes) of decoding or encoding a chunk.
+    ///
+    /// This is the decoded size plus the encoded size of the chunk, where an unbounded encoded size is assumed to be the decoded size.
+    fn chunk_memory_usage(
+        &self,
+        chunk_representation: &ChunkRepresentation,
+    ) -> Result<u64, ArrayError> {
+        let decoded_size = chunk_representation.size();
+        let encoded_size = self
+            .codecs()
+            .compute_encoded_size(chunk_representation)?
+            .size()
+            .unwrap_or(decoded_size);
+        Ok(decoded_size.saturating_add(encoded_size))
+    }
+
+    /// Invalidate the shard index of the chunk at `chunk_indices` in the shard index cache of `options`, if set.
+    fn invalidate_cached_shard_index(&self, chunk_indices: &[u64], options: &CodecOptions) {
+        if let Some(shard_index_cache) = options.shard_index_cache() {
+            shard_index_cache.invalidate(&data_key(
+                self.path(),
+                chunk_indices,
+                self.chunk_key_encoding(),
+            ));
+        }
+    }
 }
 
 macro_rules! array_store_elements {
@@ -671,12 +1424,16 @@ macro_rules! array_async_store_ndarray {
     };
 }
 
+mod array_maybe_async;
+
 mod array_sync_readable;
 
 mod array_sync_writable;
 
 mod array_sync_readable_writable;
 
+mod array_sync_listable;
+
 #[cfg(feature = "async")]
 mod array_async_readable;
 
@@ -686,21 +1443,168 @@ mod array_async_writable;
 #[cfg(feature = "async")]
 mod array_async_readable_writable;
 
-/// Transmute from `Vec<u8>` to `Vec<T>`.
-#[must_use]
-pub fn transmute_from_bytes_vec<T: bytemuck::Pod>(from: Vec<u8>) -> Vec<T> {
-    bytemuck::allocation::try_cast_vec(from)
-        .unwrap_or_else(|(_err, from)| bytemuck::allocation::pod_collect_to_vec(&from))
-}
+#[cfg(feature = "async")]
+mod array_async_listable;
 
-/// Transmute from `Vec<T>` to `Vec<u8>`.
-#[must_use]
-pub fn transmute_to_bytes_vec<T: bytemuck::NoUninit>(from: Vec<T>) -> Vec<u8> {
-    bytemuck::allocation::try_cast_vec(from)
-        .unwrap_or_else(|(_err, from)| bytemuck::allocation::pod_collect_to_vec(&from))
+/// An element type for the `_elements` and `_ndarray` variants of [`Array`] methods.
+///
+/// This is implemented for all [`bytemuck::Pod`] types (e.g. integers, floats, and complex numbers) and [`bool`].
+pub trait Element: bytemuck::NoUninit + bytemuck::CheckedBitPattern {}
+
+impl<T: bytemuck::NoUninit + bytemuck::CheckedBitPattern> Element for T {}
+
+/// An encoded chunk and its statistics.
+type EncodedChunk = (Vec<u8>, Option<ChunkStatistics>);
+
+/// Convert from `Vec<u8>` to `Vec<T>`, validating that the bytes of each element are a valid `T`.
+///
+/// # Errors
+/// Returns [`ArrayError::InvalidElementValue`] if the bytes of any element are not a valid `T`.
+fn elements_from_bytes_vec<T: Element>(from: Vec<u8>) -> Result<Vec<T>, ArrayError> {
+    let size = std::mem::size_of::<T>();
+    if size == 0 || from.len() % size != 0 {
+        return Err(ArrayError::IncompatibleElementSize(from.len(), size));
+    }
+    if !from
+        .chunks_exact(size)
+        .all(|bytes| T::is_valid_bit_pattern(&bytemuck::pod_read_unaligned::<T::Bits>(bytes)))
+    {
+        return Err(ArrayError::InvalidElementValue(
+            std::any::type_name::<T>().to_string(),
+        ));
+    }
+
+    let num_elements = from.len() / size;
+    if size == 1 && std::mem::align_of::<T>() == 1 {
+        // Reuse the allocation, the size and alignment of `T` match `u8`
+        let mut from = std::mem::ManuallyDrop::new(from);
+        Ok(unsafe {
+            Vec::from_raw_parts(from.as_mut_ptr().cast::<T>(), num_elements, from.capacity())
+        })
+    } else {
+        let mut elements = Vec::<T>::with_capacity(num_elements);
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                from.as_ptr(),
+                elements.as_mut_ptr().cast::<u8>(),
+                from.len(),
+            );
+            elements.set_len(num_elements);
+        }
+        Ok(elements)
+    }
 }
 
-/// Unravel a linearised index to ND indices.
+/// Return the smallest array subset containing all of `array_subsets`.
+///
+/// `array_subsets` must be non-empty and have the same dimensionality.
+fn bounding_array_subset(array_subsets: &[ArraySubset]) -> ArraySubset {
+    let mut start = array_subsets[0].start().to_vec();
+    let mut end = array_subsets[0].end_exc();
+    for array_subset in &array_subsets[1..] {
+        for (start, &start_i) in std::iter::zip(&mut start, array_subset.start()) {
+            *start = (*start).min(start_i);
+        }
+        for (end, end_i) in std::iter::zip(&mut end, array_subset.end_exc()) {
+            *end = (*end).max(end_i);
+        }
+    }
+    unsafe { ArraySubset::new_with_start_end_exc_unchecked(start, end) }
+}
+
+/// Apply `f` to each element of the decoded chunk `chunk_bytes` within `overlap`.
+///
+/// `chunk_subset` is the subset of the chunk in the array, and `f` is called with the array indices of each element in C order.
+fn update_chunk_elements<T: Element>(
+    chunk_bytes: Vec<u8>,
+    chunk_subset: &ArraySubset,
+    overlap: &ArraySubset,
+    f: &mut impl FnMut(&ArrayIndices, &mut T),
+) -> Result<Vec<u8>, ArrayError> {
+    let mut elements = elements_from_bytes_vec::<T>(chunk_bytes)?;
+    for array_indices in &overlap.indices() {
+        let chunk_element_indices: ArrayIndices =
+            std::iter::zip(&array_indices, chunk_subset.start())
+                .map(|(index, start)| index - start)
+                .collect();
+        let element_index =
+            usize::try_from(ravel_indices(&chunk_element_indices, chunk_subset.shape())).unwrap();
+        f(&array_indices, &mut elements[element_index]);
+    }
+    Ok(transmute_to_bytes_vec(elements))
+}
+
+/// Copy the bytes of `subsets`, which are within `bytes_subset` with bytes `bytes`, into the `outputs` of `array_subsets`.
+///
+/// Each output must hold the bytes of the corresponding array subset, and each subset must be within its array subset.
+fn copy_subsets_bytes_into_outputs(
+    bytes: &[u8],
+    bytes_subset: &ArraySubset,
+    subsets: &[(usize, ArraySubset)],
+    array_subsets: &[ArraySubset],
+    outputs: &[UnsafeCellSlice<u8>],
+    element_size: usize,
+) {
+    for (index, subset) in subsets {
+        let array_subset = &array_subsets[*index];
+        let subset_bytes = unsafe {
+            subset
+                .relative_to_unchecked(bytes_subset.start())
+                .extract_bytes_unchecked(bytes, bytes_subset.shape(), element_size)
+        };
+        let output = unsafe { outputs[*index].get() };
+        unsafe {
+            subset
+                .relative_to_unchecked(array_subset.start())
+                .store_bytes_unchecked(
+                    &subset_bytes,
+                    &mut output[..array_subset.num_elements_usize() * element_size],
+                    array_subset.shape(),
+                    element_size,
+                );
+        }
+    }
+}
+
+/// Convert the `elements` of `subset` and write them into `output`, which holds the elements of an array subset with `output_shape`.
+///
+/// `subset` is relative to the start of the array subset of `output`.
+fn convert_elements_into_subset<TSrc, TDst>(
+    elements: Vec<TSrc>,
+    subset: &ArraySubset,
+    output: &mut [TDst],
+    output_shape: &[u64],
+    convert: &(impl Fn(TSrc) -> TDst + ?Sized),
+) {
+    let mut elements = elements.into_iter();
+    for (index, num_elements) in
+        &unsafe { subset.contiguous_linearised_indices_unchecked(output_shape) }
+    {
+        let index = usize::try_from(index).unwrap();
+        let num_elements = usize::try_from(num_elements).unwrap();
+        for (output, element) in
+            std::iter::zip(&mut output[index..index + num_elements], elements.by_ref())
+        {
+            *output = convert(element);
+        }
+    }
+}
+
+/// Transmute from `Vec<u8>` to `Vec<T>`.
+#[must_use]
+pub fn transmute_from_bytes_vec<T: bytemuck::Pod>(from: Vec<u8>) -> Vec<T> {
+    bytemuck::allocation::try_cast_vec(from)
+        .unwrap_or_else(|(_err, from)| bytemuck::allocation::pod_collect_to_vec(&from))
+}
+
+/// Transmute from `Vec<T>` to `Vec<u8>`.
+#[must_use]
+pub fn transmute_to_bytes_vec<T: bytemuck::NoUninit>(from: Vec<T>) -> Vec<u8> {
+    bytemuck::allocation::try_cast_vec(from)
+        .unwrap_or_else(|(_err, from)| bytemuck::allocation::pod_collect_to_vec(&from))
+}
+
+/// Unravel a linearised index to ND indices.
 #[must_use]
 pub fn unravel_index(mut index: u64, shape: &[u64]) -> ArrayIndices {
     let len = shape.len();
@@ -734,6 +1638,45 @@ fn iter_u64_to_usize<'a, I: Iterator<Item = &'a u64>>(iter: I) -> Vec<usize> {
         .collect::<Vec<_>>()
 }
 
+/// Create an [`ArrayView`] of `elements`, which are the elements of an array with `shape`.
+///
+/// # Errors
+/// Returns an [`ArrayError`] if the size of `T` does not match the size of `data_type` or the number of `elements` does not match `shape`.
+fn elements_array_view<'a, T: bytemuck::Pod>(
+    data_type: &DataType,
+    elements: &'a mut [T],
+    shape: &'a [u64],
+) -> Result<ArrayView<'a>, ArrayError> {
+    validate_element_size::<T>(data_type)?;
+    let num_elements = shape.iter().product::<u64>();
+    if elements.len() as u64 != num_elements {
+        return Err(ArrayError::InvalidBytesInputSize(
+            std::mem::size_of_val(elements),
+            num_elements * std::mem::size_of::<T>() as u64,
+        ));
+    }
+    Ok(ArrayView::new(
+        bytemuck::cast_slice_mut(elements),
+        shape,
+        ArraySubset::new_with_shape(shape.to_vec()),
+    )
+    .map_err(|err| codec::CodecError::from(err.to_string()))?)
+}
+
+#[cfg(feature = "ndarray")]
+/// Validate that `array` has `shape`.
+fn validate_ndarray_shape<T>(
+    array: &ndarray::ArrayViewMutD<'_, T>,
+    shape: &[u64],
+) -> Result<(), ArrayError> {
+    let shape = iter_u64_to_usize(shape.iter());
+    if array.shape() == shape.as_slice() {
+        Ok(())
+    } else {
+        Err(ArrayError::InvalidDataShape(array.shape().to_vec(), shape))
+    }
+}
+
 fn validate_element_size<T>(data_type: &DataType) -> Result<(), ArrayError> {
     if data_type.size() == std::mem::size_of::<T>() {
         Ok(())
@@ -745,6 +1688,17 @@ fn validate_element_size<T>(data_type: &DataType) -> Result<(), ArrayError> {
     }
 }
 
+#[cfg(feature = "arrow")]
+/// Validate that an [`arrow_array::PrimitiveArray`] has no null elements.
+fn validate_arrow_nulls<T: arrow_array::ArrowPrimitiveType>(
+    array: &arrow_array::PrimitiveArray<T>,
+) -> Result<(), ArrayError> {
+    match arrow_array::Array::null_count(array) {
+        0 => Ok(()),
+        null_count => Err(ArrayError::NullElements(null_count)),
+    }
+}
+
 #[cfg(feature = "ndarray")]
 /// Convert a vector of elements to an [`ndarray::ArrayD`].
 ///
@@ -787,7 +1741,8 @@ mod tests {
 
     use crate::storage::{
         store::MemoryStore,
-        store_lock::{DefaultStoreLocks, StoreLocks},
+        store_lock::{DefaultStoreLocks, DisabledStoreLocks, StoreLocks},
+        StorageError,
     };
 
     use super::*;
@@ -815,6 +1770,136 @@ mod tests {
         assert_eq!(metadata, array.metadata());
     }
 
+    #[test]
+    fn array_v2_read() {
+        use crate::storage::{StoreKey, WritableStorageTraits};
+
+        let store = Arc::new(MemoryStore::new());
+        store
+            .set(
+                &StoreKey::new("array/.zarray").unwrap(),
+                br#"{
+                    "zarr_format": 2,
+                    "shape": [4, 4],
+                    "chunks": [2, 2],
+                    "dtype": ">u2",
+                    "compressor": null,
+                    "fill_value": 7,
+                    "order": "F",
+                    "filters": null,
+                    "dimension_separator": "/"
+                }"#,
+            )
+            .unwrap();
+        store
+            .set(
+                &StoreKey::new("array/.zattrs").unwrap(),
+                br#"{"spam": "ham"}"#,
+            )
+            .unwrap();
+        // Chunk [0, 1] holds elements [[2, 3], [6, 7]] in F order and big endian
+        store
+            .set(
+                &StoreKey::new("array/0/1").unwrap(),
+                &[0, 2, 0, 6, 0, 3, 0, 7],
+            )
+            .unwrap();
+
+        let array = Array::new(store, "/array").unwrap();
+        assert_eq!(array.data_type(), &DataType::UInt16);
+        assert_eq!(array.fill_value(), &FillValue::from(7u16));
+        assert_eq!(
+            array.attributes().get("spam"),
+            Some(&serde_json::Value::String("ham".to_string()))
+        );
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<u16>(&ArraySubset::new_with_ranges(&[0..2, 1..4]))
+                .unwrap(),
+            vec![7, 2, 3, 7, 6, 7]
+        );
+    }
+
+    #[cfg(feature = "n5")]
+    #[test]
+    fn array_n5_read() {
+        use crate::storage::{StoreKey, WritableStorageTraits};
+
+        let store = Arc::new(MemoryStore::new());
+        store
+            .set(
+                &StoreKey::new("dataset/attributes.json").unwrap(),
+                br#"{
+                    "dimensions": [3, 4],
+                    "blockSize": [2, 2],
+                    "dataType": "int16",
+                    "compression": {"type": "raw"},
+                    "spam": "ham"
+                }"#,
+            )
+            .unwrap();
+        // Block [1, 1] is truncated to the N5 shape [1, 2] and holds elements [[10], [11]] in big endian
+        store
+            .set(
+                &StoreKey::new("dataset/1/1").unwrap(),
+                &[0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 10, 0, 11],
+            )
+            .unwrap();
+
+        let array = Array::new(store, "/dataset").unwrap();
+        assert_eq!(array.shape(), &[4, 3]);
+        assert_eq!(array.data_type(), &DataType::Int16);
+        assert_eq!(
+            array.attributes().get("spam"),
+            Some(&serde_json::Value::String("ham".to_string()))
+        );
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<i16>(&ArraySubset::new_with_ranges(&[1..4, 1..3]))
+                .unwrap(),
+            vec![0, 0, 0, 10, 0, 11]
+        );
+    }
+
+    #[test]
+    fn array_readonly() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/array")
+        .unwrap();
+        array.store_chunk_elements(&[0, 0], vec![1u8; 4]).unwrap();
+
+        let array_readonly = array.readonly();
+        assert_eq!(array_readonly.metadata(), array.metadata());
+        assert_eq!(
+            array_readonly
+                .retrieve_chunk_elements::<u8>(&[0, 0])
+                .unwrap(),
+            vec![1u8; 4]
+        );
+        assert!(matches!(
+            array_readonly.store_metadata(),
+            Err(StorageError::ReadOnly)
+        ));
+        assert!(matches!(
+            array_readonly.store_chunk_elements(&[0, 0], vec![2u8; 4]),
+            Err(ArrayError::StorageError(StorageError::ReadOnly))
+        ));
+        assert!(matches!(
+            array_readonly.erase_chunk(&[0, 0]),
+            Err(StorageError::ReadOnly)
+        ));
+        assert_eq!(
+            array.retrieve_chunk_elements::<u8>(&[0, 0]).unwrap(),
+            vec![1u8; 4]
+        );
+    }
+
     #[test]
     fn array_set_shape_and_attributes() {
         let store = MemoryStore::new();
@@ -847,6 +1932,103 @@ mod tests {
         );
     }
 
+    #[test]
+    fn array_typed_attributes() {
+        let store = Arc::new(MemoryStore::new());
+        let array_path = "/array";
+        let mut array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), array_path)
+        .unwrap();
+        assert!(array.store_attributes().is_err()); // no metadata
+        array.store_metadata().unwrap();
+
+        assert_eq!(array.set_attribute("scale", [0.5, 2.0]).unwrap(), None);
+        assert_eq!(array.set_attribute("name", "apple").unwrap(), None);
+        assert_eq!(
+            array.attribute::<[f64; 2]>("scale").unwrap(),
+            Some([0.5, 2.0])
+        );
+        assert_eq!(
+            array.attribute::<String>("name").unwrap().as_deref(),
+            Some("apple")
+        );
+        assert_eq!(array.attribute::<String>("missing").unwrap(), None);
+        assert!(array.attribute::<u64>("name").is_err());
+
+        array.set_shape(vec![16, 16]);
+        array.store_attributes().unwrap();
+        let array_stored = Array::new(store, array_path).unwrap();
+        assert_eq!(array_stored.shape(), &[8, 8]); // only attributes are updated
+        assert_eq!(
+            array_stored.attribute::<[f64; 2]>("scale").unwrap(),
+            Some([0.5, 2.0])
+        );
+    }
+
+    #[test]
+    fn array_complex_elements() {
+        use num::complex::{Complex32, Complex64};
+
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::Complex64,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(Complex32::new(1.0, -1.0)),
+        )
+        .array_to_bytes_codec(Box::new(codec::BytesCodec::big()))
+        .build(store.clone(), "/complex64")
+        .unwrap();
+        let subset = ArraySubset::new_with_ranges(&[1..3, 1..2]);
+        let elements = vec![Complex32::new(0.5, 2.0), Complex32::new(-3.0, 0.25)];
+        array
+            .store_array_subset_elements(&subset, elements.clone())
+            .unwrap();
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<Complex32>(&subset)
+                .unwrap(),
+            elements
+        );
+        assert_eq!(
+            array.retrieve_chunk_elements::<Complex32>(&[0, 0]).unwrap(),
+            vec![
+                Complex32::new(1.0, -1.0),
+                Complex32::new(1.0, -1.0),
+                Complex32::new(1.0, -1.0),
+                Complex32::new(0.5, 2.0)
+            ]
+        );
+
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::Complex128,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(Complex64::new(0.0, 0.0)),
+        )
+        .array_to_bytes_codec(Box::new(codec::BytesCodec::big()))
+        .build(store, "/complex128")
+        .unwrap();
+        let elements = vec![Complex64::new(0.5, 2.0), Complex64::new(-3.0, 0.25)];
+        array
+            .store_array_subset_elements(&subset, elements.clone())
+            .unwrap();
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<Complex64>(&subset)
+                .unwrap(),
+            elements
+        );
+        assert!(array
+            .retrieve_array_subset_elements::<Complex32>(&subset)
+            .is_err());
+    }
+
     #[test]
     fn array_subset_round_trip() {
         let store = Arc::new(MemoryStore::default());
@@ -902,18 +2084,250 @@ mod tests {
             .is_none());
     }
 
-    fn array_subset_locking(locks: StoreLocks, expect_equal: bool) {
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn array_arrow() {
+        use arrow_array::{types::Float32Type, Array as _, Float32Array};
+
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::Float32,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0.0f32),
+        )
+        .build(store, "/array")
+        .unwrap();
+
+        let subset = ArraySubset::new_with_ranges(&[1..3, 0..3]);
+        let values = Float32Array::from(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        array.store_array_subset_arrow(&subset, &values).unwrap();
+        let retrieved = array
+            .retrieve_array_subset_arrow::<Float32Type>(&ArraySubset::new_with_ranges(&[
+                0..4,
+                0..2,
+            ]))
+            .unwrap();
+        assert_eq!(retrieved.null_count(), 0);
+        assert_eq!(
+            retrieved.values().to_vec(),
+            vec![0.0, 0.0, 1.0, 2.0, 4.0, 5.0, 0.0, 0.0]
+        );
+
+        let nulls = Float32Array::from(vec![Some(1.0), None, Some(3.0), None, None, None]);
+        assert!(matches!(
+            array.store_array_subset_arrow(&subset, &nulls),
+            Err(ArrayError::NullElements(4))
+        ));
+        assert!(array
+            .retrieve_array_subset_arrow::<arrow_array::types::Int16Type>(&subset)
+            .is_err());
+    }
+
+    #[test]
+    fn array_subset_aligned() {
+        use crate::storage::storage_transformer::{
+            PerformanceMetricsStorageTransformer, StorageTransformerExtension,
+        };
+
+        let store = Arc::new(MemoryStore::default());
+        let performance_metrics = Arc::new(PerformanceMetricsStorageTransformer::new());
+        let storage = performance_metrics
+            .clone()
+            .create_readable_writable_transformer(store);
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(storage, "/array")
+        .unwrap();
+
+        // An aligned subset is stored without retrieving or locking chunks
+        let subset = ArraySubset::new_with_ranges(&[4..8, 0..8]);
+        let elements: Vec<u8> = (1..=32).collect();
+        array
+            .store_array_subset_elements(&subset, elements.clone())
+            .unwrap();
+        assert_eq!(performance_metrics.reads(), 0);
+        assert_eq!(performance_metrics.locks(), 0);
+        assert_eq!(performance_metrics.writes(), 2);
+        array
+            .store_array_subset_exact(&ArraySubset::new_with_ranges(&[0..4, 4..8]), vec![1; 16])
+            .unwrap();
+        assert_eq!(performance_metrics.reads(), 0);
+        assert_eq!(performance_metrics.locks(), 0);
+        assert_eq!(
+            array.retrieve_array_subset_elements::<u8>(&subset).unwrap(),
+            elements
+        );
+        assert_eq!(
+            array.retrieve_chunk_elements::<u8>(&[0, 1]).unwrap(),
+            vec![1; 16]
+        );
+
+        // An unaligned subset is rejected by store_array_subset_exact
+        assert!(matches!(
+            array.store_array_subset_exact(
+                &ArraySubset::new_with_ranges(&[0..4, 0..3]),
+                vec![1; 12]
+            ),
+            Err(ArrayError::UnalignedArraySubset(_))
+        ));
+        assert_eq!(
+            array.retrieve_chunk_elements::<u8>(&[0, 0]).unwrap(),
+            vec![0; 16]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sharding")]
+    fn array_shard_index_cache() {
+        use crate::{
+            array::codec::{array_to_bytes::sharding::ShardingCodecBuilder, ShardIndexCache},
+            storage::storage_transformer::{
+                PerformanceMetricsStorageTransformer, StorageTransformerExtension,
+            },
+        };
+
+        let store = Arc::new(MemoryStore::default());
+        let performance_metrics = Arc::new(PerformanceMetricsStorageTransformer::new());
+        let storage = performance_metrics
+            .clone()
+            .create_readable_writable_transformer(store);
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .array_to_bytes_codec(Box::new(
+            ShardingCodecBuilder::new(vec![2, 2].try_into().unwrap()).build(),
+        ))
+        .build(storage, "/array")
+        .unwrap();
+        array
+            .store_array_subset_elements(
+                &ArraySubset::new_with_ranges(&[0..8, 0..8]),
+                (0..64).collect::<Vec<u8>>(),
+            )
+            .unwrap();
+
+        let cache = ShardIndexCache::new(std::num::NonZeroUsize::new(1).unwrap());
+        let options = CodecOptions::builder()
+            .shard_index_cache(cache.clone())
+            .build();
+
+        // The first read of a shard retrieves its index and an inner chunk
+        let reads = performance_metrics.reads();
+        assert_eq!(
+            array
+                .retrieve_chunk_subset_elements_opt::<u8>(
+                    &[0, 0],
+                    &ArraySubset::new_with_ranges(&[0..2, 0..2]),
+                    &options
+                )
+                .unwrap(),
+            vec![0, 1, 8, 9]
+        );
+        assert_eq!(performance_metrics.reads() - reads, 2);
+        assert_eq!(cache.len(), 1);
+
+        // Subsequent reads of the shard only retrieve inner chunks
+        let reads = performance_metrics.reads();
+        assert_eq!(
+            array
+                .retrieve_chunk_subset_elements_opt::<u8>(
+                    &[0, 0],
+                    &ArraySubset::new_with_ranges(&[2..4, 2..4]),
+                    &options
+                )
+                .unwrap(),
+            vec![18, 19, 26, 27]
+        );
+        assert_eq!(performance_metrics.reads() - reads, 1);
+
+        // Storing a shard invalidates its cached index
+        array
+            .store_chunk_elements_opt::<u8>(&[0, 0], vec![1; 16], &options)
+            .unwrap();
+        assert!(cache.is_empty());
+        assert_eq!(
+            array
+                .retrieve_chunk_subset_elements_opt::<u8>(
+                    &[0, 0],
+                    &ArraySubset::new_with_ranges(&[2..4, 2..4]),
+                    &options
+                )
+                .unwrap(),
+            vec![1; 4]
+        );
+        assert_eq!(cache.len(), 1);
+
+        // Reading another shard evicts the least recently used shard index
+        array
+            .retrieve_chunk_subset_elements_opt::<u8>(
+                &[1, 1],
+                &ArraySubset::new_with_ranges(&[0..2, 0..2]),
+                &options,
+            )
+            .unwrap();
+        assert_eq!(cache.len(), 1);
+        let key = data_key(array.path(), &[0, 0], array.chunk_key_encoding());
+        assert!(cache.get(&key).is_none());
+    }
+
+    #[test]
+    fn array_subset_fill_value_sparse() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![6, 6],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/array")
+        .unwrap();
+
+        // Chunks that do not exist are not stored if only updated with the fill value
+        let subset = ArraySubset::new_with_ranges(&[1..6, 1..6]);
+        array.store_array_subset(&subset, vec![0; 25]).unwrap();
+        assert!(array.list_stored_chunks().unwrap().is_empty());
+
+        // Only chunks with a non fill value element are stored
+        let mut elements = vec![0u8; 25];
+        elements[0] = 1;
+        array
+            .store_array_subset_elements(&subset, elements)
+            .unwrap();
+        assert_eq!(array.list_stored_chunks().unwrap(), vec![vec![0, 0]]);
+
+        // An existing chunk is still updated with the fill value
+        array
+            .store_array_subset(&ArraySubset::new_with_ranges(&[0..2, 0..2]), vec![0; 4])
+            .unwrap();
+        assert!(array.list_stored_chunks().unwrap().is_empty());
+    }
+
+    fn array_subset_locking(
+        locks: StoreLocks,
+        array_locks: Option<StoreLocks>,
+        expect_equal: bool,
+    ) {
         let store = Arc::new(MemoryStore::new_with_locks(locks));
 
         let array_path = "/array";
-        let array = ArrayBuilder::new(
+        let mut builder = ArrayBuilder::new(
             vec![100, 4],
             DataType::UInt8,
             vec![10, 2].try_into().unwrap(),
             FillValue::from(0u8),
-        )
-        .build(store, array_path)
-        .unwrap();
+        );
+        if let Some(array_locks) = array_locks {
+            builder.store_locks(array_locks);
+        }
+        let array = builder.build(store, array_path).unwrap();
 
         let mut any_not_equal = false;
         for j in 1..10 {
@@ -938,7 +2352,554 @@ mod tests {
     #[test]
     #[cfg_attr(miri, ignore)]
     fn array_subset_locking_default() {
-        array_subset_locking(Arc::new(DefaultStoreLocks::default()), true);
+        array_subset_locking(Arc::new(DefaultStoreLocks::default()), None, true);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn array_subset_locking_array_locks() {
+        array_subset_locking(
+            Arc::new(DisabledStoreLocks),
+            Some(Arc::new(DefaultStoreLocks::default())),
+            true,
+        );
+    }
+
+    #[test]
+    fn array_chunks_exist() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::UInt8,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        assert!(array.list_stored_chunks().unwrap().is_empty());
+
+        array.store_chunk_elements(&[0, 1], vec![1u8; 6]).unwrap();
+        array.store_chunk_elements(&[1, 0], vec![2u8; 6]).unwrap();
+        array.store_chunk_elements(&[1, 1], vec![0u8; 6]).unwrap(); // fill value, not stored
+        assert_eq!(
+            array
+                .chunks_exist(&ArraySubset::new_with_shape(vec![2, 2]))
+                .unwrap(),
+            vec![false, true, true, false]
+        );
+        assert_eq!(
+            array
+                .chunks_exist(&ArraySubset::new_with_ranges(&[1..2, 0..2]))
+                .unwrap(),
+            vec![true, false]
+        );
+        assert!(array
+            .chunks_exist(&ArraySubset::new_with_shape(vec![3, 2]))
+            .is_err());
+        assert_eq!(
+            array.list_stored_chunks().unwrap(),
+            vec![vec![0, 1], vec![1, 0]]
+        );
+
+        // Listing the stored chunks of a sparse array does not visit every chunk of the chunk grid
+        let array = ArrayBuilder::new(
+            vec![1 << 40, 1 << 40],
+            DataType::UInt8,
+            vec![1, 1].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(array.storage.clone(), "/sparse")
+        .unwrap();
+        array
+            .store_chunk_elements(&[1 << 39, 7], vec![1u8])
+            .unwrap();
+        array
+            .store_chunk_elements(&[3, 1 << 30], vec![1u8])
+            .unwrap();
+        assert_eq!(
+            array.list_stored_chunks().unwrap(),
+            vec![vec![3, 1 << 30], vec![1 << 39, 7]]
+        );
+        assert_eq!(array.stored_chunk_count().unwrap(), 2);
+    }
+
+    #[test]
+    fn array_encoded_chunk() {
+        let mut builder = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt16,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u16),
+        );
+        builder.bytes_to_bytes_codecs(vec![
+            #[cfg(feature = "gzip")]
+            Box::new(codec::GzipCodec::new(5).unwrap()),
+        ]);
+        let array_src = builder
+            .build(Arc::new(MemoryStore::new()), "/array")
+            .unwrap();
+        let store_dst = Arc::new(MemoryStore::new());
+        let array_dst = builder.build(store_dst.clone(), "/array").unwrap();
+
+        array_src
+            .store_chunk_elements(&[0, 1], vec![1u16, 2, 3, 4])
+            .unwrap();
+        let encoded_chunk = array_src.retrieve_encoded_chunk(&[0, 1]).unwrap().unwrap();
+        assert_ne!(
+            encoded_chunk.as_ref(),
+            crate::array::transmute_to_bytes_vec(vec![1u16, 2, 3, 4])
+        );
+        assert!(array_src.retrieve_encoded_chunk(&[1, 1]).unwrap().is_none());
+        assert!(array_src.retrieve_encoded_chunk(&[0]).is_err());
+
+        array_dst
+            .store_encoded_chunk(&[0, 1], &encoded_chunk)
+            .unwrap();
+        assert_eq!(
+            crate::storage::ReadableStorageTraits::get(
+                &*store_dst,
+                &data_key(array_dst.path(), &[0, 1], array_dst.chunk_key_encoding())
+            )
+            .unwrap()
+            .unwrap(),
+            encoded_chunk
+        );
+        assert_eq!(
+            array_dst.retrieve_chunk_elements::<u16>(&[0, 1]).unwrap(),
+            vec![1u16, 2, 3, 4]
+        );
+        assert!(array_dst
+            .store_encoded_chunk(&[0, 0, 0], &encoded_chunk)
+            .is_err());
+    }
+
+    #[test]
+    fn array_store_empty_chunks() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::UInt8,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/array")
+        .unwrap();
+        let options = codec::CodecOptions::builder()
+            .store_empty_chunks(true)
+            .build();
+        array
+            .store_chunk_elements_opt(&[0, 0], vec![0u8; 6], &options)
+            .unwrap();
+        array
+            .store_array_subset_elements_opt(
+                &ArraySubset::new_with_ranges(&[2..3, 3..4]),
+                vec![0u8],
+                &options,
+            )
+            .unwrap();
+        assert_eq!(
+            array.list_stored_chunks().unwrap(),
+            vec![vec![0, 0], vec![1, 1]]
+        );
+        assert_eq!(
+            array.retrieve_chunk_elements::<u8>(&[1, 1]).unwrap(),
+            vec![0u8; 6]
+        );
+
+        // Empty chunks are erased by default
+        array.store_chunk_elements(&[0, 0], vec![0u8; 6]).unwrap();
+        assert_eq!(array.list_stored_chunks().unwrap(), vec![vec![1, 1]]);
+    }
+
+    #[test]
+    fn array_update_array_subset_with() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::UInt16,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store, "/array")
+        .unwrap();
+        array
+            .store_array_subset_elements(
+                &ArraySubset::new_with_ranges(&[0..2, 0..6]),
+                (1..=12).collect::<Vec<u16>>(),
+            )
+            .unwrap();
+
+        let mut visited = Vec::new();
+        array
+            .update_array_subset_with::<u16>(
+                &ArraySubset::new_with_ranges(&[1..3, 2..4]),
+                |indices, element| {
+                    visited.push(indices.clone());
+                    *element += u16::try_from(indices[0] * 100 + indices[1]).unwrap();
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            visited,
+            vec![vec![1, 2], vec![1, 3], vec![2, 2], vec![2, 3]]
+        );
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<u16>(&ArraySubset::new_with_ranges(&[0..4, 0..6]))
+                .unwrap(),
+            vec![
+                1, 2, 3, 4, 5, 6, //
+                7, 8, 111, 113, 11, 12, //
+                0, 0, 202, 203, 0, 0, //
+                0, 0, 0, 0, 0, 0, //
+            ]
+        );
+
+        assert!(array
+            .update_array_subset_with::<u8>(&ArraySubset::new_with_ranges(&[0..1, 0..1]), |_, _| {})
+            .is_err());
+    }
+
+    #[test]
+    fn array_retrieve_into() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::UInt16,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store, "/array")
+        .unwrap();
+        let elements: Vec<u16> = (0..24).collect();
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_ranges(&[0..4, 0..6]), elements)
+            .unwrap();
+
+        let mut output = vec![0u16; 6];
+        array.retrieve_chunk_into(&[1, 1], &mut output).unwrap();
+        assert_eq!(output, [15, 16, 17, 21, 22, 23]);
+        array
+            .retrieve_array_subset_into(&ArraySubset::new_with_ranges(&[1..3, 2..5]), &mut output)
+            .unwrap();
+        assert_eq!(output, [8, 9, 10, 14, 15, 16]);
+        array
+            .retrieve_chunk_subset_into(
+                &[0, 0],
+                &ArraySubset::new_with_ranges(&[0..2, 1..2]),
+                &mut output[..2],
+            )
+            .unwrap();
+        assert_eq!(output[..2], [1, 7]);
+        let mut output_chunks = vec![0u16; 12];
+        array
+            .retrieve_chunks_into(
+                &ArraySubset::new_with_ranges(&[1..2, 0..2]),
+                &mut output_chunks,
+            )
+            .unwrap();
+        assert_eq!(output_chunks, (12..24).collect::<Vec<u16>>());
+
+        // Invalid element size or number of elements
+        assert!(array.retrieve_chunk_into(&[0, 0], &mut [0u32; 6]).is_err());
+        assert!(array.retrieve_chunk_into(&[0, 0], &mut [0u16; 5]).is_err());
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn array_retrieve_into_ndarray() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::UInt16,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store, "/array")
+        .unwrap();
+        let elements: Vec<u16> = (0..24).collect();
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_ranges(&[0..4, 0..6]), elements)
+            .unwrap();
+        let expected = array
+            .retrieve_array_subset_ndarray::<u16>(&ArraySubset::new_with_ranges(&[1..3, 2..5]))
+            .unwrap();
+
+        // Standard layout
+        let mut output = ndarray::Array2::<u16>::zeros((2, 3)).into_dyn();
+        array
+            .retrieve_array_subset_into_ndarray(
+                &ArraySubset::new_with_ranges(&[1..3, 2..5]),
+                output.view_mut(),
+            )
+            .unwrap();
+        assert_eq!(output, expected);
+
+        // Non-standard layout
+        let mut output = ndarray::Array2::<u16>::zeros((3, 2)).into_dyn();
+        array
+            .retrieve_array_subset_into_ndarray(
+                &ArraySubset::new_with_ranges(&[1..3, 2..5]),
+                output.view_mut().reversed_axes(),
+            )
+            .unwrap();
+        assert_eq!(output.t(), expected);
+
+        // Incompatible shape
+        assert!(array
+            .retrieve_array_subset_into_ndarray(
+                &ArraySubset::new_with_ranges(&[1..3, 2..5]),
+                output.view_mut(),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn array_progress_reporter() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::UInt16,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store, "/array")
+        .unwrap();
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let options = codec::CodecOptions::builder()
+            .progress_reporter(codec::ProgressReporter::new({
+                let progress = progress.clone();
+                move |p| progress.lock().push(p)
+            }))
+            .build();
+
+        array
+            .store_array_subset_elements_opt(
+                &ArraySubset::new_with_ranges(&[1..4, 0..6]),
+                vec![1u16; 18],
+                &options,
+            )
+            .unwrap();
+        let final_progress = *progress
+            .lock()
+            .iter()
+            .max_by_key(|p| p.chunks_completed())
+            .unwrap();
+        assert_eq!(progress.lock().len(), 4);
+        assert_eq!(final_progress.chunks_completed(), 4);
+        assert_eq!(final_progress.chunks_total(), 4);
+        assert_eq!(final_progress.bytes_processed(), 36);
+
+        progress.lock().clear();
+        array
+            .retrieve_array_subset_opt(&ArraySubset::new_with_ranges(&[0..4, 2..4]), &options)
+            .unwrap();
+        let final_progress = *progress
+            .lock()
+            .iter()
+            .max_by_key(|p| p.chunks_completed())
+            .unwrap();
+        assert_eq!(final_progress.chunks_total(), 4);
+        assert_eq!(final_progress.chunks_completed(), 4);
+        assert_eq!(final_progress.bytes_processed(), 16);
+
+        // Operations without a progress reporter do not report progress
+        progress.lock().clear();
+        array
+            .retrieve_array_subset(&ArraySubset::new_with_ranges(&[0..4, 0..6]))
+            .unwrap();
+        assert!(progress.lock().is_empty());
+    }
+
+    #[test]
+    fn array_cancellation() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::UInt16,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store, "/array")
+        .unwrap();
+        let token = codec::CancellationToken::new();
+        let options = codec::CodecOptions::builder()
+            .cancellation_token(token.clone())
+            .build();
+
+        // Not cancelled
+        array
+            .store_chunks_elements_opt(
+                &ArraySubset::new_with_ranges(&[0..2, 0..2]),
+                vec![1u16; 24],
+                &options,
+            )
+            .unwrap();
+
+        token.cancel();
+        assert!(matches!(
+            array.store_chunks_elements_opt(
+                &ArraySubset::new_with_ranges(&[0..2, 0..2]),
+                vec![2u16; 24],
+                &options,
+            ),
+            Err(ArrayError::Cancelled)
+        ));
+        assert!(matches!(
+            array.retrieve_array_subset_opt(&ArraySubset::new_with_ranges(&[0..4, 0..6]), &options),
+            Err(ArrayError::Cancelled)
+        ));
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<u16>(&ArraySubset::new_with_ranges(&[0..4, 0..6]))
+                .unwrap(),
+            vec![1u16; 24]
+        );
+
+        // Cancel from the progress reporter after the first chunk
+        let token = codec::CancellationToken::new();
+        let options = codec::CodecOptions::builder()
+            .concurrent_target(1)
+            .cancellation_token(token.clone())
+            .progress_reporter(codec::ProgressReporter::new(move |_| token.cancel()))
+            .build();
+        assert!(matches!(
+            array.retrieve_array_subset_opt(&ArraySubset::new_with_ranges(&[0..4, 0..6]), &options),
+            Err(ArrayError::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn array_store_metadata_conditional() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::UInt8,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        assert!(array.metadata_generation().is_none());
+        array.store_metadata().unwrap();
+
+        let mut array_a = Array::new(store.clone(), "/array").unwrap();
+        let mut array_b = Array::new(store.clone(), "/array").unwrap();
+        assert!(array_a.metadata_generation().is_some());
+        assert_eq!(array_a.metadata_generation(), array_b.metadata_generation());
+
+        array_a.set_shape(vec![8, 6]);
+        array_a.store_metadata().unwrap();
+        array_b.set_shape(vec![4, 12]);
+        assert!(matches!(
+            array_b.store_metadata(),
+            Err(StorageError::PreconditionFailed(_))
+        ));
+        assert_eq!(
+            Array::new(store.clone(), "/array").unwrap().shape(),
+            &[8, 6]
+        );
+
+        // Subsequent writes from the same array succeed
+        array_a.set_shape(vec![8, 12]);
+        array_a.store_metadata().unwrap();
+
+        // Clearing the generation stores metadata unconditionally
+        array_b.set_metadata_generation(None);
+        array_b.store_metadata().unwrap();
+        assert_eq!(Array::new(store, "/array").unwrap().shape(), &[4, 12]);
+    }
+
+    #[test]
+    fn array_bool_elements() {
+        let array = ArrayBuilder::new(
+            vec![3, 5],
+            DataType::Bool,
+            vec![2, 5].try_into().unwrap(),
+            FillValue::from(false),
+        )
+        .array_to_bytes_codec(Box::<codec::BytesCodec>::default())
+        .build(Arc::new(MemoryStore::new()), "/")
+        .unwrap();
+        let elements: Vec<bool> = (0..15).map(|i| i % 3 == 0).collect();
+        let array_subset = ArraySubset::new_with_shape(vec![3, 5]);
+        array
+            .store_array_subset_elements(&array_subset, elements.clone())
+            .unwrap();
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<bool>(&array_subset)
+                .unwrap(),
+            elements
+        );
+        #[cfg(feature = "ndarray")]
+        assert_eq!(
+            array
+                .retrieve_chunk_ndarray::<bool>(&[1, 0])
+                .unwrap()
+                .into_raw_vec(),
+            vec![false, false, true, false, false, false, false, false, false, false]
+        );
+
+        // Bytes which are not a valid bool
+        array.store_chunk(&[0, 0], vec![2; 10]).unwrap();
+        assert!(matches!(
+            array.retrieve_chunk_elements::<bool>(&[0, 0]),
+            Err(ArrayError::InvalidElementValue(_))
+        ));
+        assert_eq!(
+            array.retrieve_chunk_elements::<u8>(&[0, 0]).unwrap(),
+            vec![2; 10]
+        );
+    }
+
+    #[cfg(feature = "packbits")]
+    #[test]
+    fn array_bool_packbits() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![3, 5],
+            DataType::Bool,
+            vec![2, 5].try_into().unwrap(),
+            FillValue::from(false),
+        )
+        .array_to_bytes_codec(Box::<codec::PackBitsCodec>::default())
+        .build(store.clone(), "/")
+        .unwrap();
+        array.store_metadata().unwrap();
+        let elements: Vec<bool> = (0..15).map(|i| i % 2 == 0).collect();
+        let array_subset = ArraySubset::new_with_shape(vec![3, 5]);
+        array
+            .store_array_subset_elements(&array_subset, elements.clone())
+            .unwrap();
+        assert_eq!(
+            crate::storage::ReadableStorageTraits::size(&*store).unwrap()
+                - crate::storage::ReadableStorageTraits::size_key(
+                    &*store,
+                    &crate::storage::meta_key(array.path())
+                )
+                .unwrap()
+                .unwrap(),
+            4 // two chunks of 10 elements packed into 2 bytes
+        );
+
+        let array = Array::new(store, "/").unwrap();
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<bool>(&array_subset)
+                .unwrap(),
+            elements
+        );
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<bool>(&ArraySubset::new_with_ranges(&[
+                    1..3,
+                    1..4
+                ]))
+                .unwrap(),
+            vec![true, false, true, false, true, false]
+        );
     }
 
     // // Due to the nature of this test, it can fail sometimes. It was used for development but is now disabled.
@@ -0,0 +1,44 @@
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{
+    array_subset::ArraySubset,
+    storage::{AsyncListableStorageTraits, StorageError, StorageHandle, StoreKey, StorePrefix},
+};
+
+use super::{Array, ArrayError, ArrayIndices};
+
+impl<TStorage: ?Sized + AsyncListableStorageTraits + 'static> Array<TStorage> {
+    /// List the keys in the store under the array prefix.
+    pub(super) async fn async_stored_keys(&self) -> Result<HashSet<StoreKey>, ArrayError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_listable_transformer(storage_handle);
+        let prefix = StorePrefix::try_from(self.path()).map_err(StorageError::StorePrefixError)?;
+        Ok(storage_transformer
+            .list_prefix(&prefix)
+            .await?
+            .into_iter()
+            .collect())
+    }
+
+    /// Async variant of [`chunks_exist`](Array::chunks_exist).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_chunks_exist(&self, chunks: &ArraySubset) -> Result<Vec<bool>, ArrayError> {
+        let stored_keys = self.async_stored_keys().await?;
+        self.chunks_exist_in_keys(chunks, &stored_keys)
+    }
+
+    /// Async variant of [`list_stored_chunks`](Array::list_stored_chunks).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_list_stored_chunks(&self) -> Result<Vec<ArrayIndices>, ArrayError> {
+        let stored_keys = self.async_stored_keys().await?;
+        self.stored_chunks_in_keys(&stored_keys)
+    }
+
+    /// Async variant of [`stored_chunk_count`](Array::stored_chunk_count).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_stored_chunk_count(&self) -> Result<usize, ArrayError> {
+        Ok(self.async_list_stored_chunks().await?.len())
+    }
+}
@@ -1,26 +1,41 @@
 use std::sync::Arc;
 
 use futures::StreamExt;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use crate::{
     array_subset::ArraySubset,
     node::NodePath,
-    storage::{data_key, meta_key, AsyncReadableStorageTraits, StorageHandle},
+    storage::{
+        data_key, meta_key, meta_key_v2_array, meta_key_v2_attributes, AsyncReadableStorageTraits,
+        StorageError, StorageHandle,
+    },
+    vec_spare_capacity_to_mut_slice,
 };
 
 use super::{
+    bounding_array_subset,
     codec::{
-        options::CodecOptions, ArrayCodecTraits, ArrayToBytesCodecTraits,
-        AsyncArrayPartialDecoderTraits, AsyncStoragePartialDecoder, CodecError,
+        options::{CodecOptions, ProgressTracker},
+        ArrayToBytesCodecTraits, AsyncArrayPartialDecoderTraits, AsyncPrefetchingPartialDecoder,
+        AsyncSpawn, AsyncStoragePartialDecoder, CodecError, PrefetchPattern,
     },
     concurrency::concurrency_chunks_and_codec,
-    transmute_from_bytes_vec,
+    convert_elements_into_subset, copy_subsets_bytes_into_outputs, elements_array_view,
+    elements_from_bytes_vec,
     unsafe_cell_slice::UnsafeCellSlice,
-    validate_element_size, Array, ArrayCreateError, ArrayError, ArrayMetadata, ArrayView,
+    validate_element_size, Array, ArrayCreateError, ArrayError, ArrayIndices, ArrayMetadata,
+    ArrayMetadataV2, ArrayView, ArrayViewMut, Element, MaybeBytes,
+};
+
+#[cfg(feature = "n5")]
+use crate::{
+    array::{array_metadata_n5_to_v3, ArrayMetadataN5},
+    storage::meta_key_n5,
 };
 
 #[cfg(feature = "ndarray")]
-use super::elements_to_ndarray;
+use super::{elements_to_ndarray, validate_ndarray_shape};
 
 impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
     /// Async variant of [`new`](Array::new).
@@ -28,16 +43,49 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
     pub async fn async_new(storage: Arc<TStorage>, path: &str) -> Result<Self, ArrayCreateError> {
         let node_path = NodePath::new(path)?;
         let key = meta_key(&node_path);
-        let metadata: ArrayMetadata = serde_json::from_slice(
-            &storage
-                .get(&key)
-                .await?
-                .ok_or(ArrayCreateError::MissingMetadata)?,
-        )
-        .map_err(|err| crate::storage::StorageError::InvalidMetadata(key, err.to_string()))?;
+        let metadata: ArrayMetadata =
+            if let Some((metadata, generation)) = storage.get_with_generation(&key).await? {
+                let metadata = serde_json::from_slice(&metadata)
+                    .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
+                let array = Self::new_with_metadata(storage, path, metadata)?;
+                array.set_metadata_generation(generation);
+                return Ok(array);
+            } else {
+                let key = meta_key_v2_array(&node_path);
+                let metadata_v2 = storage.get(&key).await?;
+                #[cfg(feature = "n5")]
+                if metadata_v2.is_none() {
+                    let key = meta_key_n5(&node_path);
+                    if let Some(metadata) = storage.get(&key).await? {
+                        let metadata: ArrayMetadataN5 = serde_json::from_slice(&metadata)
+                            .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
+                        let metadata = array_metadata_n5_to_v3(&metadata)?;
+                        return Self::new_with_metadata(storage, path, metadata.into());
+                    }
+                }
+                let mut metadata: ArrayMetadataV2 =
+                    serde_json::from_slice(&metadata_v2.ok_or(ArrayCreateError::MissingMetadata)?)
+                        .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
+                let key = meta_key_v2_attributes(&node_path);
+                if let Some(attributes) = storage.get(&key).await? {
+                    metadata.attributes = serde_json::from_slice(&attributes)
+                        .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
+                }
+                metadata.into()
+            };
         Self::new_with_metadata(storage, path, metadata)
     }
 
+    /// Async variant of [`retrieve_encoded_chunk`](Array::retrieve_encoded_chunk).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_encoded_chunk(
+        &self,
+        chunk_indices: &[u64],
+    ) -> Result<MaybeBytes, ArrayError> {
+        self.maybe_async_retrieve_encoded_chunk(&self.async_readable_storage(), chunk_indices)
+            .await
+    }
+
     /// Async variant of [`retrieve_chunk_if_exists`](Array::retrieve_chunk_if_exists).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_retrieve_chunk_if_exists(
@@ -50,7 +98,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
 
     /// Async variant of [`retrieve_chunk_elements_if_exists`](Array::retrieve_chunk_elements_if_exists).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_retrieve_chunk_elements_if_exists<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_elements_if_exists<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
     ) -> Result<Option<Vec<T>>, ArrayError> {
@@ -61,7 +109,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
     #[cfg(feature = "ndarray")]
     /// Async variant of [`retrieve_chunk_ndarray_if_exists`](Array::retrieve_chunk_ndarray_if_exists).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_retrieve_chunk_ndarray_if_exists<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_ndarray_if_exists<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
     ) -> Result<Option<ndarray::ArrayD<T>>, ArrayError> {
@@ -78,7 +126,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
 
     /// Async variant of [`retrieve_chunk_elements`](Array::retrieve_chunk_elements).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_retrieve_chunk_elements<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_elements<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
     ) -> Result<Vec<T>, ArrayError> {
@@ -89,7 +137,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
     #[cfg(feature = "ndarray")]
     /// Async variant of [`retrieve_chunk_ndarray`](Array::retrieve_chunk_ndarray).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_retrieve_chunk_ndarray<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_ndarray<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
     ) -> Result<ndarray::ArrayD<T>, ArrayError> {
@@ -112,6 +160,21 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         .await
     }
 
+    /// Async variant of [`retrieve_chunk_into_array_view_mut`](Array::retrieve_chunk_into_array_view_mut).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_into_array_view_mut(
+        &self,
+        chunk_indices: &[u64],
+        array_view: &mut ArrayViewMut<'_>,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunk_into_array_view_mut_opt(
+            chunk_indices,
+            array_view,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
     /// Async variant of [`retrieve_chunks`](Array::retrieve_chunks).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_retrieve_chunks(&self, chunks: &ArraySubset) -> Result<Vec<u8>, ArrayError> {
@@ -121,7 +184,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
 
     /// Async variant of [`retrieve_chunks_elements`](Array::retrieve_chunks_elements).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_retrieve_chunks_elements<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunks_elements<T: Element + Send + Sync>(
         &self,
         chunks: &ArraySubset,
     ) -> Result<Vec<T>, ArrayError> {
@@ -132,7 +195,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
     #[cfg(feature = "ndarray")]
     /// Async variant of [`retrieve_chunks_ndarray`](Array::retrieve_chunks_ndarray).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_retrieve_chunks_ndarray<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunks_ndarray<T: Element + Send + Sync>(
         &self,
         chunks: &ArraySubset,
     ) -> Result<ndarray::ArrayD<T>, ArrayError> {
@@ -151,6 +214,21 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             .await
     }
 
+    /// Async variant of [`retrieve_chunks_into_array_view_mut`](Array::retrieve_chunks_into_array_view_mut).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunks_into_array_view_mut(
+        &self,
+        chunks: &ArraySubset,
+        array_view: &mut ArrayViewMut<'_>,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunks_into_array_view_mut_opt(
+            chunks,
+            array_view,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
     /// Async variant of [`retrieve_chunk_subset`](Array::retrieve_chunk_subset).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_retrieve_chunk_subset(
@@ -164,7 +242,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
 
     /// Async variant of [`retrieve_chunk_subset_elements`](Array::retrieve_chunk_subset_elements).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_retrieve_chunk_subset_elements<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_subset_elements<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -180,7 +258,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
     #[cfg(feature = "ndarray")]
     /// Async variant of [`retrieve_chunk_subset_ndarray`](Array::retrieve_chunk_subset_ndarray).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_retrieve_chunk_subset_ndarray<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_subset_ndarray<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -210,6 +288,23 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         .await
     }
 
+    /// Async variant of [`retrieve_chunk_subset_into_array_view_mut`](Array::retrieve_chunk_subset_into_array_view_mut).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_subset_into_array_view_mut(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        array_view: &mut ArrayViewMut<'_>,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunk_subset_into_array_view_mut_opt(
+            chunk_indices,
+            chunk_subset,
+            array_view,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
     /// Async variant of [`retrieve_array_subset`](Array::retrieve_array_subset).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_retrieve_array_subset(
@@ -222,7 +317,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
 
     /// Async variant of [`retrieve_array_subset_elements`](Array::retrieve_array_subset_elements).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_retrieve_array_subset_elements<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_array_subset_elements<T: Element + Send + Sync>(
         &self,
         array_subset: &ArraySubset,
     ) -> Result<Vec<T>, ArrayError> {
@@ -230,10 +325,58 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             .await
     }
 
+    /// Async variant of [`retrieve_array_subset_elements_as`](Array::retrieve_array_subset_elements_as).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_array_subset_elements_as<
+        TSrc: Element + Send + Sync,
+        TDst: Element + Send + Sync,
+    >(
+        &self,
+        array_subset: &ArraySubset,
+        convert: impl Fn(TSrc) -> TDst + Send + Sync,
+    ) -> Result<Vec<TDst>, ArrayError> {
+        self.async_retrieve_array_subset_elements_as_opt(
+            array_subset,
+            convert,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
+    /// Async variant of [`retrieve_array_subsets`](Array::retrieve_array_subsets).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_array_subsets(
+        &self,
+        array_subsets: &[ArraySubset],
+    ) -> Result<Vec<Vec<u8>>, ArrayError> {
+        self.async_retrieve_array_subsets_opt(array_subsets, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`retrieve_array_subsets_elements`](Array::retrieve_array_subsets_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_array_subsets_elements<T: Element + Send + Sync>(
+        &self,
+        array_subsets: &[ArraySubset],
+    ) -> Result<Vec<Vec<T>>, ArrayError> {
+        self.async_retrieve_array_subsets_elements_opt(array_subsets, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`retrieve_elements_at`](Array::retrieve_elements_at).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_elements_at<T: Element + Send + Sync>(
+        &self,
+        array_indices: &[ArrayIndices],
+    ) -> Result<Vec<T>, ArrayError> {
+        self.async_retrieve_elements_at_opt(array_indices, &CodecOptions::default())
+            .await
+    }
+
     #[cfg(feature = "ndarray")]
     /// Async variant of [`retrieve_array_subset_ndarray`](Array::retrieve_array_subset_ndarray).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_retrieve_array_subset_ndarray<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_array_subset_ndarray<T: Element + Send + Sync>(
         &self,
         array_subset: &ArraySubset,
     ) -> Result<ndarray::ArrayD<T>, ArrayError> {
@@ -241,6 +384,20 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             .await
     }
 
+    #[cfg(feature = "arrow")]
+    /// Async variant of [`retrieve_array_subset_arrow`](Array::retrieve_array_subset_arrow).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_array_subset_arrow<T: arrow_array::ArrowPrimitiveType>(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<arrow_array::PrimitiveArray<T>, ArrayError>
+    where
+        T::Native: Element,
+    {
+        self.async_retrieve_array_subset_arrow_opt(array_subset, &CodecOptions::default())
+            .await
+    }
+
     /// Async variant of [`retrieve_array_subset_into_array_view`](Array::retrieve_array_subset_into_array_view).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_retrieve_array_subset_into_array_view(
@@ -256,6 +413,129 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         .await
     }
 
+    /// Async variant of [`retrieve_array_subset_into_array_view_mut`](Array::retrieve_array_subset_into_array_view_mut).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_array_subset_into_array_view_mut(
+        &self,
+        array_subset: &ArraySubset,
+        array_view: &mut ArrayViewMut<'_>,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_array_subset_into_array_view_mut_opt(
+            array_subset,
+            array_view,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
+    /// Async variant of [`retrieve_chunk_into`](Array::retrieve_chunk_into).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_into<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunk_indices: &[u64],
+        elements: &mut [T],
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunk_into_opt(chunk_indices, elements, &CodecOptions::default())
+            .await
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`retrieve_chunk_into_ndarray`](Array::retrieve_chunk_into_ndarray).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_into_ndarray<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunk_indices: &[u64],
+        array: ndarray::ArrayViewMutD<'_, T>,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunk_into_ndarray_opt(chunk_indices, array, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`retrieve_chunks_into`](Array::retrieve_chunks_into).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunks_into<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunks: &ArraySubset,
+        elements: &mut [T],
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunks_into_opt(chunks, elements, &CodecOptions::default())
+            .await
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`retrieve_chunks_into_ndarray`](Array::retrieve_chunks_into_ndarray).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunks_into_ndarray<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunks: &ArraySubset,
+        array: ndarray::ArrayViewMutD<'_, T>,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunks_into_ndarray_opt(chunks, array, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`retrieve_chunk_subset_into`](Array::retrieve_chunk_subset_into).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_subset_into<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        elements: &mut [T],
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunk_subset_into_opt(
+            chunk_indices,
+            chunk_subset,
+            elements,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`retrieve_chunk_subset_into_ndarray`](Array::retrieve_chunk_subset_into_ndarray).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_subset_into_ndarray<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        array: ndarray::ArrayViewMutD<'_, T>,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunk_subset_into_ndarray_opt(
+            chunk_indices,
+            chunk_subset,
+            array,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
+    /// Async variant of [`retrieve_array_subset_into`](Array::retrieve_array_subset_into).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_array_subset_into<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        array_subset: &ArraySubset,
+        elements: &mut [T],
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_array_subset_into_opt(array_subset, elements, &CodecOptions::default())
+            .await
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`retrieve_array_subset_into_ndarray`](Array::retrieve_array_subset_into_ndarray).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_array_subset_into_ndarray<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        array_subset: &ArraySubset,
+        array: ndarray::ArrayViewMutD<'_, T>,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_array_subset_into_ndarray_opt(
+            array_subset,
+            array,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
     /// Async variant of [`partial_decoder`](Array::partial_decoder).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_partial_decoder<'a>(
@@ -266,53 +546,47 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             .await
     }
 
+    /// Async variant of [`prefetching_partial_decoder`](Array::prefetching_partial_decoder).
+    ///
+    /// Read-aheads are spawned with `spawn`.
+    /// See [`AsyncPrefetchingPartialDecoder`].
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_prefetching_partial_decoder<'a>(
+        &'a self,
+        chunk_indices: &[u64],
+        pattern: PrefetchPattern,
+        spawn: AsyncSpawn,
+    ) -> Result<Box<dyn AsyncArrayPartialDecoderTraits + 'a>, ArrayError> {
+        self.async_prefetching_partial_decoder_opt(
+            chunk_indices,
+            pattern,
+            spawn,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
     /////////////////////////////////////////////////////////////////////////////
     // Advanced methods
     /////////////////////////////////////////////////////////////////////////////
 
     /// Async variant of [`retrieve_chunk_if_exists_opt`](Array::retrieve_chunk_if_exists_opt).
     #[allow(clippy::missing_errors_doc)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(path = %self.path(), chunk_indices = ?chunk_indices))
+    )]
     pub async fn async_retrieve_chunk_if_exists_opt(
         &self,
         chunk_indices: &[u64],
         options: &CodecOptions,
     ) -> Result<Option<Vec<u8>>, ArrayError> {
-        if chunk_indices.len() != self.dimensionality() {
-            return Err(ArrayError::InvalidChunkGridIndicesError(
-                chunk_indices.to_vec(),
-            ));
-        }
-        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-        let storage_transformer = self
-            .storage_transformers()
-            .create_async_readable_transformer(storage_handle);
-        let chunk_encoded = crate::storage::async_retrieve_chunk(
-            &*storage_transformer,
-            self.path(),
+        self.maybe_async_retrieve_chunk_if_exists_opt(
+            &self.async_readable_storage(),
             chunk_indices,
-            self.chunk_key_encoding(),
+            options,
         )
         .await
-        .map_err(ArrayError::StorageError)?;
-        if let Some(chunk_encoded) = chunk_encoded {
-            let chunk_representation = self.chunk_array_representation(chunk_indices)?;
-            let chunk_decoded = self
-                .codecs()
-                .decode(chunk_encoded, &chunk_representation, options)
-                .map_err(ArrayError::CodecError)?;
-            let chunk_decoded_size =
-                chunk_representation.num_elements_usize() * chunk_representation.data_type().size();
-            if chunk_decoded.len() == chunk_decoded_size {
-                Ok(Some(chunk_decoded))
-            } else {
-                Err(ArrayError::UnexpectedChunkDecodedSize(
-                    chunk_decoded.len(),
-                    chunk_decoded_size,
-                ))
-            }
-        } else {
-            Ok(None)
-        }
     }
 
     /// Async variant of [`retrieve_chunk_opt`](Array::retrieve_chunk_opt).
@@ -322,21 +596,13 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         chunk_indices: &[u64],
         options: &CodecOptions,
     ) -> Result<Vec<u8>, ArrayError> {
-        let chunk = self
-            .async_retrieve_chunk_if_exists_opt(chunk_indices, options)
-            .await?;
-        if let Some(chunk) = chunk {
-            Ok(chunk)
-        } else {
-            let chunk_representation = self.chunk_array_representation(chunk_indices)?;
-            let fill_value = chunk_representation.fill_value().as_ne_bytes();
-            Ok(fill_value.repeat(chunk_representation.num_elements_usize()))
-        }
+        self.maybe_async_retrieve_chunk_opt(&self.async_readable_storage(), chunk_indices, options)
+            .await
     }
 
     /// Async variant of [`retrieve_chunk_elements_if_exists_opt`](Array::retrieve_chunk_elements_if_exists_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_retrieve_chunk_elements_if_exists_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_elements_if_exists_opt<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         options: &CodecOptions,
@@ -345,12 +611,12 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         let bytes = self
             .async_retrieve_chunk_if_exists_opt(chunk_indices, options)
             .await?;
-        Ok(bytes.map(|bytes| transmute_from_bytes_vec::<T>(bytes)))
+        bytes.map(elements_from_bytes_vec::<T>).transpose()
     }
 
     /// Async variant of [`retrieve_chunk_elements_opt`](Array::retrieve_chunk_elements_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_retrieve_chunk_elements_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_elements_opt<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         options: &CodecOptions,
@@ -359,13 +625,13 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         let bytes = self
             .async_retrieve_chunk_opt(chunk_indices, options)
             .await?;
-        Ok(transmute_from_bytes_vec::<T>(bytes))
+        elements_from_bytes_vec::<T>(bytes)
     }
 
     #[cfg(feature = "ndarray")]
     /// Async variant of [`retrieve_chunk_ndarray_if_exists_opt`](Array::retrieve_chunk_ndarray_if_exists_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_retrieve_chunk_ndarray_if_exists_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_ndarray_if_exists_opt<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         options: &CodecOptions,
@@ -388,7 +654,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
     #[cfg(feature = "ndarray")]
     /// Async variant of [`retrieve_chunk_ndarray_opt`](Array::retrieve_chunk_ndarray_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_retrieve_chunk_ndarray_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_ndarray_opt<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         options: &CodecOptions,
@@ -433,33 +699,28 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         )
         .await
         .map_err(ArrayError::StorageError)?;
-        if let Some(chunk_encoded) = chunk_encoded {
-            self.codecs()
-                .decode_into_array_view(&chunk_encoded, &chunk_representation, array_view, options)
-                .map_err(ArrayError::CodecError)
-        } else {
-            // fill array_view with fill value
-            let contiguous_indices = unsafe {
-                array_view
-                    .subset()
-                    .contiguous_linearised_indices_unchecked(array_view.array_shape())
-            };
-            let element_size = chunk_representation.element_size();
-            let length = contiguous_indices.contiguous_elements_usize() * element_size;
-            let fill = self
-                .fill_value()
-                .as_ne_bytes()
-                .repeat(contiguous_indices.contiguous_elements_usize());
-            // FIXME: Par iteration?
-            let output = unsafe { array_view.bytes_mut() };
-            for (array_subset_element_index, _num_elements) in &contiguous_indices {
-                let output_offset =
-                    usize::try_from(array_subset_element_index).unwrap() * element_size;
-                debug_assert!((output_offset + length) <= output.len());
-                output[output_offset..output_offset + length].copy_from_slice(&fill);
-            }
-            Ok(())
-        }
+        self.decode_chunk_into_array_view(
+            chunk_encoded.as_deref(),
+            chunk_indices,
+            array_view,
+            options,
+        )
+    }
+
+    /// Async variant of [`retrieve_chunk_into_array_view_mut_opt`](Array::retrieve_chunk_into_array_view_mut_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_into_array_view_mut_opt(
+        &self,
+        chunk_indices: &[u64],
+        array_view: &mut ArrayViewMut<'_>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunk_into_array_view_opt(
+            chunk_indices,
+            array_view.array_view(),
+            options,
+        )
+        .await
     }
 
     /// Async variant of [`retrieve_chunks_opt`](Array::retrieve_chunks_opt).
@@ -480,11 +741,17 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
 
         // Retrieve chunk bytes
         let num_chunks = chunks.num_elements_usize();
+        let progress = ProgressTracker::new(options, num_chunks);
+        progress.check_cancelled()?;
         match num_chunks {
             0 => Ok(vec![]),
             1 => {
                 let chunk_indices = chunks.start();
-                self.async_retrieve_chunk_opt(chunk_indices, options).await
+                let bytes = self
+                    .async_retrieve_chunk_opt(chunk_indices, options)
+                    .await?;
+                progress.chunk_completed(bytes.len() as u64);
+                Ok(bytes)
             }
             _ => {
                 // Decode chunks and copy to output
@@ -502,39 +769,28 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
                     num_chunks,
                     options,
                     &codec_concurrency,
+                    self.chunk_memory_usage(&chunk_representation)?,
                 );
 
                 let mut output = Vec::with_capacity(size_output);
                 {
                     let output_slice =
                         UnsafeCellSlice::new_from_vec_with_spare_capacity(&mut output);
-                    let indices = chunks.indices();
-                    let chunk0_subset = self.chunk_subset(chunks.start())?;
-                    let futures = indices.into_iter().map(|chunk_indices| {
-                        let options = options.clone();
-                        let array_subset = array_subset.clone();
-                        let chunk_subset = self.chunk_subset(&chunk_indices).unwrap(); // FIXME: unwrap
-                        let array_view_subset =
-                            unsafe { chunk_subset.relative_to_unchecked(chunk0_subset.start()) };
-                        async move {
-                            self.async_retrieve_chunk_into_array_view_opt(
-                                &chunk_indices,
-                                &ArrayView::new(
-                                    unsafe { output_slice.get() },
-                                    array_subset.shape(),
-                                    array_view_subset,
-                                )
-                                .unwrap(), // FIXME: unwrap
-                                &options,
-                            )
-                            .await
-                        }
-                    });
-                    let mut stream =
-                        futures::stream::iter(futures).buffer_unordered(chunk_concurrent_limit);
-                    while let Some(item) = stream.next().await {
-                        item?;
-                    }
+                    let array_view = ArrayView::new(
+                        unsafe { output_slice.get() },
+                        array_subset.shape(),
+                        ArraySubset::new_with_shape(array_subset.shape().to_vec()),
+                    )
+                    .map_err(|err| CodecError::from(err.to_string()))?;
+                    self.async_retrieve_chunks_decode_into_array_view(
+                        chunks.indices().into_iter().collect(),
+                        &array_view,
+                        array_subset.start(),
+                        chunk_concurrent_limit,
+                        &options,
+                        &progress,
+                    )
+                    .await?;
                 }
                 unsafe { output.set_len(size_output) };
                 Ok(output)
@@ -544,20 +800,20 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
 
     /// Async variant of [`retrieve_chunks_elements_opt`](Array::retrieve_chunks_elements_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_retrieve_chunks_elements_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunks_elements_opt<T: Element + Send + Sync>(
         &self,
         chunks: &ArraySubset,
         options: &CodecOptions,
     ) -> Result<Vec<T>, ArrayError> {
         validate_element_size::<T>(self.data_type())?;
         let bytes = self.async_retrieve_chunks_opt(chunks, options).await?;
-        Ok(transmute_from_bytes_vec::<T>(bytes))
+        elements_from_bytes_vec::<T>(bytes)
     }
 
     #[cfg(feature = "ndarray")]
     /// Async variant of [`retrieve_chunks_ndarray_opt`](Array::retrieve_chunks_ndarray_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_retrieve_chunks_ndarray_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunks_ndarray_opt<T: Element + Send + Sync>(
         &self,
         chunks: &ArraySubset,
         options: &CodecOptions,
@@ -573,6 +829,10 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
     /// Async variant of [`retrieve_array_subset_opt`](Array::retrieve_array_subset_opt).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     #[allow(clippy::too_many_lines)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(path = %self.path(), array_subset = %array_subset))
+    )]
     pub async fn async_retrieve_array_subset_opt(
         &self,
         array_subset: &ArraySubset,
@@ -596,6 +856,8 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
 
         // Retrieve chunk bytes
         let num_chunks = chunks.num_elements_usize();
+        let progress = ProgressTracker::new(options, num_chunks);
+        progress.check_cancelled()?;
         match num_chunks {
             0 => Ok(self
                 .fill_value()
@@ -604,9 +866,10 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             1 => {
                 let chunk_indices = chunks.start();
                 let chunk_subset = self.chunk_subset(chunk_indices)?;
-                if &chunk_subset == array_subset {
+                let bytes = if &chunk_subset == array_subset {
                     // Single chunk fast path if the array subset domain matches the chunk domain
-                    self.async_retrieve_chunk_opt(chunk_indices, options).await
+                    self.async_retrieve_chunk_opt(chunk_indices, options)
+                        .await?
                 } else {
                     let array_subset_in_chunk_subset =
                         unsafe { array_subset.relative_to_unchecked(chunk_subset.start()) };
@@ -615,8 +878,10 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
                         &array_subset_in_chunk_subset,
                         options,
                     )
-                    .await
-                }
+                    .await?
+                };
+                progress.chunk_completed(bytes.len() as u64);
+                Ok(bytes)
             }
             _ => {
                 // Decode chunks and copy to output
@@ -634,47 +899,28 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
                     num_chunks,
                     options,
                     &codec_concurrency,
+                    self.chunk_memory_usage(&chunk_representation)?,
                 );
 
-                // let mut output = vec![0; size_output];
-                // let output_slice = output.as_mut_slice();
                 let mut output = Vec::with_capacity(size_output);
                 {
-                    let output = UnsafeCellSlice::new_from_vec_with_spare_capacity(&mut output);
-                    let retrieve_chunk = |chunk_indices: Vec<u64>| {
-                        let options = options.clone();
-                        let chunk_subset = self.chunk_subset(&chunk_indices).unwrap(); // FIXME: unwrap
-                        let chunk_subset_in_array_subset =
-                            unsafe { chunk_subset.overlap_unchecked(array_subset) };
-                        let chunk_subset = unsafe {
-                            chunk_subset_in_array_subset.relative_to_unchecked(chunk_subset.start())
-                        };
-                        let array_view_subset = unsafe {
-                            chunk_subset_in_array_subset.relative_to_unchecked(array_subset.start())
-                        };
-                        let array_view = ArrayView::new(
-                            unsafe { output.get() },
-                            array_subset.shape(),
-                            array_view_subset,
-                        )
-                        .unwrap(); // FIXME: unwrap
-                        async move {
-                            self.async_retrieve_chunk_subset_into_array_view_opt(
-                                &chunk_indices,
-                                &chunk_subset,
-                                &array_view,
-                                &options,
-                            )
-                            .await
-                        }
-                    };
-                    let indices = chunks.indices();
-                    let futures = indices.into_iter().map(retrieve_chunk);
-                    let mut stream =
-                        futures::stream::iter(futures).buffer_unordered(chunk_concurrent_limit);
-                    while let Some(item) = stream.next().await {
-                        item?;
-                    }
+                    let output_slice =
+                        UnsafeCellSlice::new_from_vec_with_spare_capacity(&mut output);
+                    let array_view = ArrayView::new(
+                        unsafe { output_slice.get() },
+                        array_subset.shape(),
+                        ArraySubset::new_with_shape(array_subset.shape().to_vec()),
+                    )
+                    .map_err(|err| CodecError::from(err.to_string()))?;
+                    self.async_retrieve_array_subset_chunks_into_array_view(
+                        array_subset,
+                        &chunks,
+                        &array_view,
+                        chunk_concurrent_limit,
+                        &options,
+                        &progress,
+                    )
+                    .await?;
                 }
                 unsafe { output.set_len(size_output) };
                 Ok(output)
@@ -684,7 +930,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
 
     /// Async variant of [`retrieve_array_subset_elements_opt`](Array::retrieve_array_subset_elements_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_retrieve_array_subset_elements_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_array_subset_elements_opt<T: Element + Send + Sync>(
         &self,
         array_subset: &ArraySubset,
         options: &CodecOptions,
@@ -693,25 +939,303 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         let bytes = self
             .async_retrieve_array_subset_opt(array_subset, options)
             .await?;
-        Ok(transmute_from_bytes_vec::<T>(bytes))
+        elements_from_bytes_vec::<T>(bytes)
     }
 
-    #[cfg(feature = "ndarray")]
-    /// Async variant of [`retrieve_array_subset_ndarray_opt`](Array::retrieve_array_subset_ndarray_opt).
+    /// Async variant of [`retrieve_array_subset_elements_as_opt`](Array::retrieve_array_subset_elements_as_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_retrieve_array_subset_ndarray_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_array_subset_elements_as_opt<
+        TSrc: Element + Send + Sync,
+        TDst: Element + Send + Sync,
+    >(
         &self,
         array_subset: &ArraySubset,
+        convert: impl Fn(TSrc) -> TDst + Send + Sync,
         options: &CodecOptions,
-    ) -> Result<ndarray::ArrayD<T>, ArrayError> {
-        // validate_element_size::<T>(self.data_type())?; // in async_retrieve_array_subset_elements
-        let elements = self
-            .async_retrieve_array_subset_elements_opt(array_subset, options)
-            .await?;
-        elements_to_ndarray(array_subset.shape(), elements)
-    }
+    ) -> Result<Vec<TDst>, ArrayError> {
+        validate_element_size::<TSrc>(self.data_type())?;
+        if array_subset.dimensionality() != self.dimensionality() {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        }
 
-    /// Async variant of [`retrieve_chunks_into_array_view_opt`](Array::retrieve_chunks_into_array_view_opt).
+        // Find the chunks intersecting this array subset
+        let Some(chunks) = self.chunks_in_array_subset(array_subset)? else {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        };
+
+        let num_elements = array_subset.num_elements_usize();
+        let mut output = Vec::with_capacity(num_elements);
+        if num_elements > 0 {
+            // Calculate chunk/codec concurrency
+            let num_chunks = chunks.num_elements_usize();
+            let chunk_representation =
+                self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+            let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+            let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+                options.concurrent_target(),
+                num_chunks,
+                options,
+                &codec_concurrency,
+                self.chunk_memory_usage(&chunk_representation)?,
+            );
+
+            let output_slice = unsafe { vec_spare_capacity_to_mut_slice(&mut output) };
+            let indices = chunks.indices();
+            let futures = indices.into_iter().map(|chunk_indices| {
+                let options = &options;
+                async move {
+                    let chunk_subset = self.chunk_subset(&chunk_indices)?;
+                    let chunk_subset_in_array_subset =
+                        unsafe { chunk_subset.overlap_unchecked(array_subset) };
+                    let elements = self
+                        .async_retrieve_chunk_subset_elements_opt::<TSrc>(
+                            &chunk_indices,
+                            &unsafe {
+                                chunk_subset_in_array_subset
+                                    .relative_to_unchecked(chunk_subset.start())
+                            },
+                            options,
+                        )
+                        .await?;
+                    Ok::<_, ArrayError>((chunk_subset_in_array_subset, elements))
+                }
+            });
+            let mut stream =
+                futures::stream::iter(futures).buffer_unordered(chunk_concurrent_limit.max(1));
+            while let Some(item) = stream.next().await {
+                let (chunk_subset_in_array_subset, elements) = item?;
+                convert_elements_into_subset(
+                    elements,
+                    &unsafe {
+                        chunk_subset_in_array_subset.relative_to_unchecked(array_subset.start())
+                    },
+                    output_slice,
+                    array_subset.shape(),
+                    &convert,
+                );
+            }
+        }
+        unsafe { output.set_len(num_elements) };
+        Ok(output)
+    }
+
+    /// Async variant of [`retrieve_array_subsets_opt`](Array::retrieve_array_subsets_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_array_subsets_opt(
+        &self,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<u8>>, ArrayError> {
+        // Find the chunks intersecting the array subsets
+        let chunks_subsets = self.chunks_in_array_subsets(array_subsets)?;
+
+        // Allocate the outputs
+        let element_size = self.data_type().size();
+        let mut outputs: Vec<Vec<u8>> = array_subsets
+            .iter()
+            .map(|array_subset| {
+                Vec::with_capacity(array_subset.num_elements_usize() * element_size)
+            })
+            .collect();
+
+        if !chunks_subsets.is_empty() {
+            // Calculate chunk/codec concurrency
+            let num_chunks = chunks_subsets.len();
+            let chunk_representation =
+                self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+            let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+            let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+                options.concurrent_target(),
+                num_chunks,
+                options,
+                &codec_concurrency,
+                self.chunk_memory_usage(&chunk_representation)?,
+            );
+
+            let output_slices: Vec<UnsafeCellSlice<u8>> = outputs
+                .iter_mut()
+                .map(UnsafeCellSlice::new_from_vec_with_spare_capacity)
+                .collect();
+            let futures = chunks_subsets
+                .into_iter()
+                .map(|(chunk_indices, indices)| {
+                    let options = &options;
+                    async move {
+                        let chunk_subset = self.chunk_subset(&chunk_indices)?;
+                        let subsets: Vec<(usize, ArraySubset)> = indices
+                            .into_iter()
+                            .map(|index| {
+                                (index, unsafe {
+                                    chunk_subset.overlap_unchecked(&array_subsets[index])
+                                })
+                            })
+                            .collect();
+
+                        // Only retrieve the region of the chunk intersecting the array subsets
+                        let bytes_subset = bounding_array_subset(
+                            &subsets
+                                .iter()
+                                .map(|(_, subset)| subset.clone())
+                                .collect::<Vec<_>>(),
+                        );
+                        let bytes = if bytes_subset == chunk_subset {
+                            self.async_retrieve_chunk_opt(&chunk_indices, options)
+                                .await?
+                        } else {
+                            self.async_retrieve_chunk_subset_opt(
+                                &chunk_indices,
+                                &unsafe {
+                                    bytes_subset.relative_to_unchecked(chunk_subset.start())
+                                },
+                                options,
+                            )
+                            .await?
+                        };
+                        Ok::<_, ArrayError>((bytes, bytes_subset, subsets))
+                    }
+                })
+                .collect::<Vec<_>>();
+            let mut stream =
+                futures::stream::iter(futures).buffer_unordered(chunk_concurrent_limit.max(1));
+            while let Some(item) = stream.next().await {
+                let (bytes, bytes_subset, subsets) = item?;
+                copy_subsets_bytes_into_outputs(
+                    &bytes,
+                    &bytes_subset,
+                    &subsets,
+                    array_subsets,
+                    &output_slices,
+                    element_size,
+                );
+            }
+        }
+        for (output, array_subset) in std::iter::zip(&mut outputs, array_subsets) {
+            unsafe { output.set_len(array_subset.num_elements_usize() * element_size) };
+        }
+        Ok(outputs)
+    }
+
+    /// Async variant of [`retrieve_array_subsets_elements_opt`](Array::retrieve_array_subsets_elements_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_array_subsets_elements_opt<T: Element + Send + Sync>(
+        &self,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<T>>, ArrayError> {
+        validate_element_size::<T>(self.data_type())?;
+        self.async_retrieve_array_subsets_opt(array_subsets, options)
+            .await?
+            .into_iter()
+            .map(elements_from_bytes_vec::<T>)
+            .collect()
+    }
+
+    /// Async variant of [`retrieve_elements_at_opt`](Array::retrieve_elements_at_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_elements_at_opt<T: Element + Send + Sync>(
+        &self,
+        array_indices: &[ArrayIndices],
+        options: &CodecOptions,
+    ) -> Result<Vec<T>, ArrayError> {
+        validate_element_size::<T>(self.data_type())?;
+
+        // Group the elements by chunk
+        let chunks_elements = self.chunks_elements(array_indices)?;
+
+        let element_size = self.data_type().size();
+        let size_output = array_indices.len() * element_size;
+        let mut output = Vec::with_capacity(size_output);
+        if size_output > 0 {
+            // Calculate chunk/codec concurrency
+            let num_chunks = chunks_elements.len();
+            let chunk_representation =
+                self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+            let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+            let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+                options.concurrent_target(),
+                num_chunks,
+                options,
+                &codec_concurrency,
+                self.chunk_memory_usage(&chunk_representation)?,
+            );
+
+            let output_slice = unsafe { vec_spare_capacity_to_mut_slice(&mut output) };
+            let futures = chunks_elements
+                .into_iter()
+                .map(|(chunk_indices, elements)| {
+                    let options = &options;
+                    async move {
+                        let element_subsets: Vec<ArraySubset> = elements
+                            .iter()
+                            .map(|(_, indices)| unsafe {
+                                ArraySubset::new_with_start_shape_unchecked(
+                                    indices.clone(),
+                                    vec![1; indices.len()],
+                                )
+                            })
+                            .collect();
+                        let decoded = self
+                            .async_partial_decoder_opt(&chunk_indices, options)
+                            .await?
+                            .partial_decode_opt(&element_subsets, options)
+                            .await?;
+                        Ok::<_, ArrayError>((elements, decoded))
+                    }
+                })
+                .collect::<Vec<_>>();
+            let mut stream =
+                futures::stream::iter(futures).buffer_unordered(chunk_concurrent_limit.max(1));
+            while let Some(item) = stream.next().await {
+                let (elements, decoded) = item?;
+                for ((index, _), bytes) in std::iter::zip(elements, decoded) {
+                    output_slice[index * element_size..(index + 1) * element_size]
+                        .copy_from_slice(&bytes);
+                }
+            }
+        }
+        unsafe { output.set_len(size_output) };
+        elements_from_bytes_vec::<T>(output)
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`retrieve_array_subset_ndarray_opt`](Array::retrieve_array_subset_ndarray_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_array_subset_ndarray_opt<T: Element + Send + Sync>(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<ndarray::ArrayD<T>, ArrayError> {
+        // validate_element_size::<T>(self.data_type())?; // in async_retrieve_array_subset_elements
+        let elements = self
+            .async_retrieve_array_subset_elements_opt(array_subset, options)
+            .await?;
+        elements_to_ndarray(array_subset.shape(), elements)
+    }
+
+    #[cfg(feature = "arrow")]
+    /// Async variant of [`retrieve_array_subset_arrow_opt`](Array::retrieve_array_subset_arrow_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_array_subset_arrow_opt<T: arrow_array::ArrowPrimitiveType>(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<arrow_array::PrimitiveArray<T>, ArrayError>
+    where
+        T::Native: Element,
+    {
+        let elements = self
+            .async_retrieve_array_subset_elements_opt::<T::Native>(array_subset, options)
+            .await?;
+        Ok(arrow_array::PrimitiveArray::new(elements.into(), None))
+    }
+
+    /// Async variant of [`retrieve_chunks_into_array_view_opt`](Array::retrieve_chunks_into_array_view_opt).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_retrieve_chunks_into_array_view_opt(
         &self,
@@ -749,35 +1273,33 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
                 num_chunks,
                 options,
                 &codec_concurrency,
+                self.chunk_memory_usage(&chunk_representation)?,
             );
 
-            {
-                let indices = chunks.indices();
-                let futures = indices.into_iter().map(|chunk_indices| {
-                    let chunk0_start = array_subset.start().to_vec();
-                    let options = options.clone();
-                    async move {
-                        let chunk_subset = self.chunk_subset(&chunk_indices).unwrap();
-                        let array_view_subset =
-                            unsafe { chunk_subset.relative_to_unchecked(&chunk0_start) };
-                        self.async_retrieve_chunk_into_array_view_opt(
-                            &chunk_indices,
-                            &unsafe { array_view.subset_view(&array_view_subset) }.unwrap(), // FIXME: unwrap
-                            &options,
-                        )
-                        .await
-                    }
-                });
-                let mut stream =
-                    futures::stream::iter(futures).buffer_unordered(chunk_concurrent_limit);
-                while let Some(item) = stream.next().await {
-                    item?;
-                }
-            }
-            Ok(())
+            self.async_retrieve_chunks_decode_into_array_view(
+                chunks.indices().into_iter().collect(),
+                array_view,
+                array_subset.start(),
+                chunk_concurrent_limit,
+                &options,
+                &ProgressTracker::new(&options, num_chunks),
+            )
+            .await
         }
     }
 
+    /// Async variant of [`retrieve_chunks_into_array_view_mut_opt`](Array::retrieve_chunks_into_array_view_mut_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunks_into_array_view_mut_opt(
+        &self,
+        chunks: &ArraySubset,
+        array_view: &mut ArrayViewMut<'_>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunks_into_array_view_opt(chunks, array_view.array_view(), options)
+            .await
+    }
+
     /// Async variant of [`retrieve_array_subset_into_array_view_opt`](Array::retrieve_array_subset_into_array_view_opt).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_retrieve_array_subset_into_array_view_opt(
@@ -850,42 +1372,198 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
                     num_chunks,
                     options,
                     &codec_concurrency,
+                    self.chunk_memory_usage(&chunk_representation)?,
                 );
 
-                {
-                    let indices = chunks.indices();
-                    let futures = indices.into_iter().map(|chunk_indices| {
-                        let chunk_subset = self.chunk_subset(&chunk_indices).unwrap();
-                        let chunk_subset_in_array_subset =
-                            unsafe { chunk_subset.overlap_unchecked(array_subset) };
-                        let chunk_subset = unsafe {
-                            chunk_subset_in_array_subset.relative_to_unchecked(chunk_subset.start())
-                        };
-                        let array_view_subset = unsafe {
-                            chunk_subset_in_array_subset.relative_to_unchecked(array_subset.start())
-                        };
-                        let options = options.clone();
-                        async move {
-                            self.async_retrieve_chunk_subset_into_array_view_opt(
-                                &chunk_indices,
-                                &chunk_subset,
-                                &unsafe { array_view.subset_view(&array_view_subset) }.unwrap(),
-                                &options,
-                            )
-                            .await
-                        }
-                    });
-                    let mut stream =
-                        futures::stream::iter(futures).buffer_unordered(chunk_concurrent_limit);
-                    while let Some(item) = stream.next().await {
-                        item?;
-                    }
-                }
-                Ok(())
+                self.async_retrieve_array_subset_chunks_into_array_view(
+                    array_subset,
+                    &chunks,
+                    array_view,
+                    chunk_concurrent_limit,
+                    &options,
+                    &ProgressTracker::new(&options, num_chunks),
+                )
+                .await
             }
         }
     }
 
+    /// Async variant of [`retrieve_array_subset_into_array_view_mut_opt`](Array::retrieve_array_subset_into_array_view_mut_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_array_subset_into_array_view_mut_opt(
+        &self,
+        array_subset: &ArraySubset,
+        array_view: &mut ArrayViewMut<'_>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_array_subset_into_array_view_opt(
+            array_subset,
+            array_view.array_view(),
+            options,
+        )
+        .await
+    }
+
+    /// Async variant of [`retrieve_chunk_into_opt`](Array::retrieve_chunk_into_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_into_opt<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunk_indices: &[u64],
+        elements: &mut [T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = self.chunk_subset(chunk_indices)?.shape().to_vec();
+        let array_view = elements_array_view(self.data_type(), elements, &shape)?;
+        self.async_retrieve_chunk_into_array_view_opt(chunk_indices, &array_view, options)
+            .await
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`retrieve_chunk_into_ndarray_opt`](Array::retrieve_chunk_into_ndarray_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_into_ndarray_opt<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunk_indices: &[u64],
+        mut array: ndarray::ArrayViewMutD<'_, T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = self.chunk_subset(chunk_indices)?.shape().to_vec();
+        validate_ndarray_shape(&array, &shape)?;
+        if let Some(elements) = array.as_slice_mut() {
+            self.async_retrieve_chunk_into_opt(chunk_indices, elements, options)
+                .await
+        } else {
+            let elements = self
+                .async_retrieve_chunk_elements_opt::<T>(chunk_indices, options)
+                .await?;
+            array.assign(&elements_to_ndarray(&shape, elements)?);
+            Ok(())
+        }
+    }
+
+    /// Async variant of [`retrieve_chunks_into_opt`](Array::retrieve_chunks_into_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunks_into_opt<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunks: &ArraySubset,
+        elements: &mut [T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = self.chunks_subset(chunks)?.shape().to_vec();
+        let array_view = elements_array_view(self.data_type(), elements, &shape)?;
+        self.async_retrieve_chunks_into_array_view_opt(chunks, &array_view, options)
+            .await
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`retrieve_chunks_into_ndarray_opt`](Array::retrieve_chunks_into_ndarray_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunks_into_ndarray_opt<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunks: &ArraySubset,
+        mut array: ndarray::ArrayViewMutD<'_, T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = self.chunks_subset(chunks)?.shape().to_vec();
+        validate_ndarray_shape(&array, &shape)?;
+        if let Some(elements) = array.as_slice_mut() {
+            self.async_retrieve_chunks_into_opt(chunks, elements, options)
+                .await
+        } else {
+            let elements = self
+                .async_retrieve_chunks_elements_opt::<T>(chunks, options)
+                .await?;
+            array.assign(&elements_to_ndarray(&shape, elements)?);
+            Ok(())
+        }
+    }
+
+    /// Async variant of [`retrieve_chunk_subset_into_opt`](Array::retrieve_chunk_subset_into_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_subset_into_opt<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        elements: &mut [T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = chunk_subset.shape().to_vec();
+        let array_view = elements_array_view(self.data_type(), elements, &shape)?;
+        self.async_retrieve_chunk_subset_into_array_view_opt(
+            chunk_indices,
+            chunk_subset,
+            &array_view,
+            options,
+        )
+        .await
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`retrieve_chunk_subset_into_ndarray_opt`](Array::retrieve_chunk_subset_into_ndarray_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_subset_into_ndarray_opt<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        mut array: ndarray::ArrayViewMutD<'_, T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = chunk_subset.shape().to_vec();
+        validate_ndarray_shape(&array, &shape)?;
+        if let Some(elements) = array.as_slice_mut() {
+            self.async_retrieve_chunk_subset_into_opt(
+                chunk_indices,
+                chunk_subset,
+                elements,
+                options,
+            )
+            .await
+        } else {
+            let elements = self
+                .async_retrieve_chunk_subset_elements_opt::<T>(chunk_indices, chunk_subset, options)
+                .await?;
+            array.assign(&elements_to_ndarray(&shape, elements)?);
+            Ok(())
+        }
+    }
+
+    /// Async variant of [`retrieve_array_subset_into_opt`](Array::retrieve_array_subset_into_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_array_subset_into_opt<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        array_subset: &ArraySubset,
+        elements: &mut [T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = array_subset.shape().to_vec();
+        let array_view = elements_array_view(self.data_type(), elements, &shape)?;
+        self.async_retrieve_array_subset_into_array_view_opt(array_subset, &array_view, options)
+            .await
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`retrieve_array_subset_into_ndarray_opt`](Array::retrieve_array_subset_into_ndarray_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_array_subset_into_ndarray_opt<T: bytemuck::Pod + Send + Sync>(
+        &self,
+        array_subset: &ArraySubset,
+        mut array: ndarray::ArrayViewMutD<'_, T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = array_subset.shape().to_vec();
+        validate_ndarray_shape(&array, &shape)?;
+        if let Some(elements) = array.as_slice_mut() {
+            self.async_retrieve_array_subset_into_opt(array_subset, elements, options)
+                .await
+        } else {
+            let elements = self
+                .async_retrieve_array_subset_elements_opt::<T>(array_subset, options)
+                .await?;
+            array.assign(&elements_to_ndarray(&shape, elements)?);
+            Ok(())
+        }
+    }
+
     /// Async variant of [`retrieve_chunk_subset_opt`](Array::retrieve_chunk_subset_opt).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_retrieve_chunk_subset_opt(
@@ -933,7 +1611,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
 
     /// Async variant of [`retrieve_chunk_subset_elements_opt`](Array::retrieve_chunk_subset_elements_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_retrieve_chunk_subset_elements_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_subset_elements_opt<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -943,13 +1621,13 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         let bytes = self
             .async_retrieve_chunk_subset_opt(chunk_indices, chunk_subset, options)
             .await?;
-        Ok(transmute_from_bytes_vec::<T>(bytes))
+        elements_from_bytes_vec::<T>(bytes)
     }
 
     #[cfg(feature = "ndarray")]
     /// Async variant of [`retrieve_chunk_subset_ndarray_opt`](Array::retrieve_chunk_subset_ndarray_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_retrieve_chunk_subset_ndarray_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_retrieve_chunk_subset_ndarray_opt<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -1001,6 +1679,24 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         }
     }
 
+    /// Async variant of [`retrieve_chunk_subset_into_array_view_mut_opt`](Array::retrieve_chunk_subset_into_array_view_mut_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunk_subset_into_array_view_mut_opt(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        array_view: &mut ArrayViewMut<'_>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        self.async_retrieve_chunk_subset_into_array_view_opt(
+            chunk_indices,
+            chunk_subset,
+            array_view.array_view(),
+            options,
+        )
+        .await
+    }
+
     /// Async variant of [`partial_decoder_opt`](Array::partial_decoder_opt).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_partial_decoder_opt<'a>(
@@ -1022,4 +1718,165 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             .async_partial_decoder(input_handle, &chunk_representation, options)
             .await?)
     }
+
+    /// Async variant of [`prefetching_partial_decoder_opt`](Array::prefetching_partial_decoder_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_prefetching_partial_decoder_opt<'a>(
+        &'a self,
+        chunk_indices: &[u64],
+        pattern: PrefetchPattern,
+        spawn: AsyncSpawn,
+        options: &CodecOptions,
+    ) -> Result<Box<dyn AsyncArrayPartialDecoderTraits + 'a>, ArrayError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_readable_transformer(storage_handle);
+        let input_handle = Box::new(AsyncPrefetchingPartialDecoder::new(
+            storage_transformer,
+            data_key(self.path(), chunk_indices, self.chunk_key_encoding()),
+            pattern,
+            spawn,
+        ));
+        let chunk_representation = self.chunk_array_representation(chunk_indices)?;
+        Ok(self
+            .codecs()
+            .async_partial_decoder(input_handle, &chunk_representation, options)
+            .await?)
+    }
+
+    /// Retrieve the encoded chunks with `chunks_indices` concurrently and decode them in parallel into `array_view`.
+    ///
+    /// At most `chunk_concurrent_limit` chunks are retrieved concurrently.
+    /// Chunks that have been retrieved are decoded in parallel batches, rather than sequentially on the calling task.
+    /// `array_view_start` is the array index of the start of the `array_view` subset, and each chunk must be entirely within `array_view`.
+    async fn async_retrieve_chunks_decode_into_array_view(
+        &self,
+        chunks_indices: Vec<ArrayIndices>,
+        array_view: &ArrayView<'_>,
+        array_view_start: &[u64],
+        chunk_concurrent_limit: usize,
+        options: &CodecOptions,
+        progress: &ProgressTracker,
+    ) -> Result<(), ArrayError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_readable_transformer(storage_handle);
+        let retrieve_encoded_chunk = |chunk_indices: ArrayIndices| {
+            let storage_transformer = &storage_transformer;
+            async move {
+                let chunk_encoded = crate::storage::async_retrieve_chunk(
+                    &**storage_transformer,
+                    self.path(),
+                    &chunk_indices,
+                    self.chunk_key_encoding(),
+                )
+                .await?;
+                Ok::<_, StorageError>((chunk_indices, chunk_encoded))
+            }
+        };
+        let chunk_concurrent_limit = chunk_concurrent_limit.max(1);
+        let mut stream =
+            futures::stream::iter(chunks_indices.into_iter().map(retrieve_encoded_chunk))
+                .buffer_unordered(chunk_concurrent_limit)
+                .ready_chunks(chunk_concurrent_limit);
+        while let Some(chunks_encoded) = stream.next().await {
+            progress.check_cancelled()?;
+            let chunks_encoded = chunks_encoded.into_iter().collect::<Result<Vec<_>, _>>()?;
+            chunks_encoded
+                .into_par_iter()
+                .try_for_each(|(chunk_indices, chunk_encoded)| {
+                    progress.check_cancelled()?;
+                    let chunk_subset = self.chunk_subset(&chunk_indices)?;
+                    let array_view_subset =
+                        unsafe { chunk_subset.relative_to_unchecked(array_view_start) };
+                    self.decode_chunk_into_array_view(
+                        chunk_encoded.as_deref(),
+                        &chunk_indices,
+                        &unsafe { array_view.subset_view(&array_view_subset) }
+                            .map_err(|err| CodecError::from(err.to_string()))?,
+                        options,
+                    )?;
+                    progress.chunk_completed(
+                        chunk_subset.num_elements() * self.data_type().size() as u64,
+                    );
+                    Ok::<_, ArrayError>(())
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Retrieve the `chunks` intersecting `array_subset` into `array_view`, which has the same shape as `array_subset`.
+    ///
+    /// Chunks entirely within `array_subset` are retrieved with [`async_retrieve_chunks_decode_into_array_view`](Array::async_retrieve_chunks_decode_into_array_view).
+    /// Chunks that are only partially within `array_subset` are concurrently retrieved with partial decoders.
+    async fn async_retrieve_array_subset_chunks_into_array_view(
+        &self,
+        array_subset: &ArraySubset,
+        chunks: &ArraySubset,
+        array_view: &ArrayView<'_>,
+        chunk_concurrent_limit: usize,
+        options: &CodecOptions,
+        progress: &ProgressTracker,
+    ) -> Result<(), ArrayError> {
+        let mut chunks_indices_full = Vec::new();
+        let mut chunks_subsets_partial = Vec::new();
+        for chunk_indices in &chunks.indices() {
+            let chunk_subset = self.chunk_subset(&chunk_indices)?;
+            let chunk_subset_in_array_subset =
+                unsafe { chunk_subset.overlap_unchecked(array_subset) };
+            if chunk_subset_in_array_subset == chunk_subset {
+                chunks_indices_full.push(chunk_indices);
+            } else {
+                chunks_subsets_partial.push((
+                    chunk_indices,
+                    chunk_subset,
+                    chunk_subset_in_array_subset,
+                ));
+            }
+        }
+
+        self.async_retrieve_chunks_decode_into_array_view(
+            chunks_indices_full,
+            array_view,
+            array_subset.start(),
+            chunk_concurrent_limit,
+            options,
+            progress,
+        )
+        .await?;
+
+        let futures = chunks_subsets_partial.into_iter().map(
+            |(chunk_indices, chunk_subset, chunk_subset_in_array_subset)| {
+                let chunk_subset = unsafe {
+                    chunk_subset_in_array_subset.relative_to_unchecked(chunk_subset.start())
+                };
+                let array_view_subset = unsafe {
+                    chunk_subset_in_array_subset.relative_to_unchecked(array_subset.start())
+                };
+                async move {
+                    progress.check_cancelled()?;
+                    self.async_retrieve_chunk_subset_into_array_view_opt(
+                        &chunk_indices,
+                        &chunk_subset,
+                        &unsafe { array_view.subset_view(&array_view_subset) }
+                            .map_err(|err| CodecError::from(err.to_string()))?,
+                        options,
+                    )
+                    .await?;
+                    progress.chunk_completed(
+                        chunk_subset.num_elements() * self.data_type().size() as u64,
+                    );
+                    Ok::<_, ArrayError>(())
+                }
+            },
+        );
+        let mut stream =
+            futures::stream::iter(futures).buffer_unordered(chunk_concurrent_limit.max(1));
+        while let Some(item) = stream.next().await {
+            item?;
+        }
+        Ok(())
+    }
 }
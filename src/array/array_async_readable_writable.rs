@@ -1,15 +1,31 @@
-use futures::StreamExt;
+use futures::{stream::FuturesUnordered, StreamExt};
 
 use crate::{
     array_subset::ArraySubset,
-    storage::{data_key, AsyncReadableWritableStorageTraits},
+    storage::{
+        async_update_attributes, data_key, AsyncReadableWritableStorageTraits, StorageError,
+        StorageHandle,
+    },
 };
 
 use super::{
-    codec::options::CodecOptions, concurrency::concurrency_chunks_and_codec, Array, ArrayError,
+    codec::options::{CodecOptions, ProgressTracker},
+    concurrency::concurrency_chunks_and_codec,
+    update_chunk_elements, validate_element_size, Array, ArrayError, ArrayIndices, ArrayShape,
+    Element,
 };
 
+#[cfg(feature = "arrow")]
+use super::validate_arrow_nulls;
+
 impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TStorage> {
+    /// Async variant of [`store_attributes`](Array::store_attributes).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_store_attributes(&self) -> Result<(), StorageError> {
+        let storage_handle = StorageHandle::new(self.storage.clone());
+        async_update_attributes(&storage_handle, self.path(), &self.metadata_attributes()).await
+    }
+
     /// Async variant of [`store_chunk_subset`](Array::store_chunk_subset).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_store_chunk_subset(
@@ -29,7 +45,7 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
 
     /// Async variant of [`store_chunk_subset_elements`](Array::store_chunk_subset_elements).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_store_chunk_subset_elements<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_store_chunk_subset_elements<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -48,7 +64,7 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
     /// Async variant of [`store_chunk_subset_ndarray`](Array::store_chunk_subset_ndarray).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_store_chunk_subset_ndarray<
-        T: bytemuck::Pod + Send + Sync,
+        T: Element + Send + Sync,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -79,7 +95,7 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
 
     /// Async variant of [`store_array_subset_elements`](Array::store_array_subset_elements).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_store_array_subset_elements<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_store_array_subset_elements<T: Element + Send + Sync>(
         &self,
         array_subset: &ArraySubset,
         subset_elements: Vec<T>,
@@ -96,7 +112,7 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
     /// Async variant of [`store_array_subset_ndarray`](Array::store_array_subset_ndarray).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_store_array_subset_ndarray<
-        T: bytemuck::Pod + Send + Sync,
+        T: Element + Send + Sync,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -112,6 +128,119 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
         .await
     }
 
+    #[cfg(feature = "arrow")]
+    /// Async variant of [`store_array_subset_arrow`](Array::store_array_subset_arrow).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_store_array_subset_arrow<T: arrow_array::ArrowPrimitiveType>(
+        &self,
+        array_subset: &ArraySubset,
+        subset_array: &arrow_array::PrimitiveArray<T>,
+    ) -> Result<(), ArrayError>
+    where
+        T::Native: Element,
+    {
+        self.async_store_array_subset_arrow_opt(
+            array_subset,
+            subset_array,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
+    /// Async variant of [`update_array_subset_with`](Array::update_array_subset_with).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_update_array_subset_with<T: Element + Send + Sync>(
+        &self,
+        array_subset: &ArraySubset,
+        f: impl FnMut(&ArrayIndices, &mut T) + Send,
+    ) -> Result<(), ArrayError> {
+        self.async_update_array_subset_with_opt(array_subset, f, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`resize`](Array::resize).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_resize(&mut self, new_shape: ArrayShape) -> Result<(), ArrayError> {
+        self.async_resize_opt(new_shape, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`append`](Array::append).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append(
+        &mut self,
+        axis: usize,
+        append_bytes: Vec<u8>,
+    ) -> Result<(), ArrayError> {
+        self.async_append_opt(axis, append_bytes, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`append_elements`](Array::append_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append_elements<T: Element + Send + Sync>(
+        &mut self,
+        axis: usize,
+        append_elements: Vec<T>,
+    ) -> Result<(), ArrayError> {
+        self.async_append_elements_opt(axis, append_elements, &CodecOptions::default())
+            .await
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`append_ndarray`](Array::append_ndarray).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append_ndarray<
+        T: Element + Send + Sync,
+        TArray: Into<ndarray::Array<T, D>>,
+        D: ndarray::Dimension,
+    >(
+        &mut self,
+        axis: usize,
+        append_array: TArray,
+    ) -> Result<(), ArrayError> {
+        self.async_append_ndarray_opt(axis, append_array, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`append_ragged`](Array::append_ragged).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append_ragged(
+        &mut self,
+        axis: usize,
+        append_bytes: Vec<u8>,
+    ) -> Result<(), ArrayError> {
+        self.async_append_ragged_opt(axis, append_bytes, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`append_ragged_elements`](Array::append_ragged_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append_ragged_elements<T: Element + Send + Sync>(
+        &mut self,
+        axis: usize,
+        append_elements: Vec<T>,
+    ) -> Result<(), ArrayError> {
+        self.async_append_ragged_elements_opt(axis, append_elements, &CodecOptions::default())
+            .await
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`append_ragged_ndarray`](Array::append_ragged_ndarray).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append_ragged_ndarray<
+        T: Element + Send + Sync,
+        TArray: Into<ndarray::Array<T, D>>,
+        D: ndarray::Dimension,
+    >(
+        &mut self,
+        axis: usize,
+        append_array: TArray,
+    ) -> Result<(), ArrayError> {
+        self.async_append_ragged_ndarray_opt(axis, append_array, &CodecOptions::default())
+            .await
+    }
+
     /////////////////////////////////////////////////////////////////////////////
     // Advanced methods
     /////////////////////////////////////////////////////////////////////////////
@@ -154,13 +283,32 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
         } else {
             // Lock the chunk
             let key = data_key(self.path(), chunk_indices, self.chunk_key_encoding());
-            let mutex = self.storage.mutex(&key).await?;
-            let _lock = mutex.lock();
+            let mutex = match &self.async_store_locks {
+                Some(store_locks) => store_locks.mutex(&key).await,
+                None => self.storage.mutex(&key).await?,
+            };
+            let _lock = mutex.lock().await?;
 
             // Decode the entire chunk
-            let mut chunk_bytes = self
-                .async_retrieve_chunk_opt(chunk_indices, options)
+            let chunk_bytes = self
+                .async_retrieve_chunk_if_exists_opt(chunk_indices, options)
                 .await?;
+            let mut chunk_bytes = match chunk_bytes {
+                Some(chunk_bytes) => chunk_bytes,
+                None if !options.store_empty_chunks()
+                    && self.fill_value().equals_all(&chunk_subset_bytes) =>
+                {
+                    // The chunk does not exist and would remain entirely fill value, so skip storing it
+                    return Ok(());
+                }
+                None => {
+                    let chunk_representation = self.chunk_array_representation(chunk_indices)?;
+                    chunk_representation
+                        .fill_value()
+                        .as_ne_bytes()
+                        .repeat(chunk_representation.num_elements_usize())
+                }
+            };
 
             // Update the intersecting subset of the chunk
             let element_size = self.data_type().size();
@@ -185,7 +333,7 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
 
     /// Async variant of [`store_chunk_subset_elements_opt`](Array::store_chunk_subset_elements_opt).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub async fn async_store_chunk_subset_elements_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_store_chunk_subset_elements_opt<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -204,10 +352,11 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
         )
     }
 
+    #[cfg(feature = "ndarray")]
     /// Async variant of [`store_chunk_subset_ndarray_opt`](Array::store_chunk_subset_ndarray_opt).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_store_chunk_subset_ndarray_opt<
-        T: bytemuck::Pod + Send + Sync,
+        T: Element + Send + Sync,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -241,6 +390,10 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
     /// Async variant of [`store_array_subset_opt`](Array::store_array_subset_opt).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     #[allow(clippy::too_many_lines)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(path = %self.path(), array_subset = %array_subset))
+    )]
     pub async fn async_store_array_subset_opt(
         &self,
         array_subset: &ArraySubset,
@@ -270,7 +423,19 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
                 self.shape().to_vec(),
             ));
         };
+        if self.chunks_subset(&chunks)? == *array_subset {
+            // A fast path if the array subset is aligned with chunk boundaries
+            // This skips retrieving and locking chunks in store_chunk_subset
+            let existing_chunks = self
+                .async_write_journal_existing_chunks(&chunks, options)
+                .await?;
+            return self
+                .async_store_chunks_journaled_opt(&chunks, subset_bytes, existing_chunks, options)
+                .await;
+        }
         let num_chunks = chunks.num_elements_usize();
+        let progress = ProgressTracker::new(options, num_chunks);
+        progress.check_cancelled()?;
         if num_chunks == 1 {
             let chunk_indices = chunks.start();
             let chunk_subset_in_array = unsafe {
@@ -278,35 +443,36 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
                     .subset_unchecked(chunk_indices, self.shape())
                     .unwrap()
             };
-            if array_subset == &chunk_subset_in_array {
-                // A fast path if the array subset matches the chunk subset
-                // This skips the internal decoding occurring in store_chunk_subset
-                self.async_store_chunk_opt(chunk_indices, subset_bytes, options)
-                    .await?;
-            } else {
-                let overlap = unsafe { array_subset.overlap_unchecked(&chunk_subset_in_array) };
-                let chunk_subset_in_array_subset =
-                    unsafe { overlap.relative_to_unchecked(array_subset.start()) };
-                let chunk_subset_bytes = unsafe {
-                    chunk_subset_in_array_subset.extract_bytes_unchecked(
-                        &subset_bytes,
-                        array_subset.shape(),
-                        self.data_type().size(),
-                    )
-                };
-
-                // Store the chunk subset
-                let array_subset_in_chunk_subset =
-                    unsafe { overlap.relative_to_unchecked(chunk_subset_in_array.start()) };
-                self.async_store_chunk_subset_opt(
-                    chunk_indices,
-                    &array_subset_in_chunk_subset,
-                    chunk_subset_bytes,
-                    options,
+            let overlap = unsafe { array_subset.overlap_unchecked(&chunk_subset_in_array) };
+            let chunk_subset_in_array_subset =
+                unsafe { overlap.relative_to_unchecked(array_subset.start()) };
+            let chunk_subset_bytes = unsafe {
+                chunk_subset_in_array_subset.extract_bytes_unchecked(
+                    &subset_bytes,
+                    array_subset.shape(),
+                    self.data_type().size(),
                 )
-                .await?;
-            }
+            };
+
+            // Store the chunk subset
+            let array_subset_in_chunk_subset =
+                unsafe { overlap.relative_to_unchecked(chunk_subset_in_array.start()) };
+            self.async_store_chunk_subset_opt(
+                chunk_indices,
+                &array_subset_in_chunk_subset,
+                chunk_subset_bytes,
+                options,
+            )
+            .await?;
+            progress.chunk_completed(subset_bytes.len() as u64);
         } else {
+            let existing_chunks = self
+                .async_write_journal_existing_chunks(&chunks, options)
+                .await?;
+            let write_journal = self
+                .async_begin_write_journal(&chunks, existing_chunks, options)
+                .await?;
+
             // Calculate chunk/codec concurrency
             let chunk_representation =
                 self.chunk_array_representation(&vec![0; self.dimensionality()])?;
@@ -316,8 +482,10 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
                 num_chunks,
                 options,
                 &codec_concurrency,
+                self.chunk_memory_usage(&chunk_representation)?,
             );
 
+            let progress = &progress;
             let store_chunk = |chunk_indices: Vec<u64>| {
                 let chunk_subset_in_array = unsafe {
                     self.chunk_grid()
@@ -337,7 +505,9 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
                     )
                 };
                 let options = options.clone();
+                let num_bytes = chunk_subset_bytes.len() as u64;
                 async move {
+                    progress.check_cancelled()?;
                     self.async_store_chunk_subset_opt(
                         &chunk_indices,
                         &array_subset_in_chunk_subset,
@@ -345,6 +515,7 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
                         &options,
                     )
                     .await
+                    .map(|()| (chunk_indices, num_bytes))
                 }
             };
 
@@ -352,16 +523,78 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
             let futures = indices.into_iter().map(store_chunk);
             let mut stream =
                 futures::stream::iter(futures).buffer_unordered(chunk_concurrent_limit);
+            let mut completed_chunks = Vec::new();
+            let mut result = Ok(());
             while let Some(item) = stream.next().await {
-                item?;
+                match item {
+                    Ok((chunk_indices, num_bytes)) => {
+                        progress.chunk_completed(num_bytes);
+                        completed_chunks.push(chunk_indices);
+                    }
+                    Err(err) => {
+                        result = Err(err);
+                        break;
+                    }
+                }
             }
+            drop(stream);
+            self.async_finish_write_journal(write_journal, result, completed_chunks)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Async variant of [`update_array_subset_with_opt`](Array::update_array_subset_with_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_update_array_subset_with_opt<T: Element + Send + Sync>(
+        &self,
+        array_subset: &ArraySubset,
+        mut f: impl FnMut(&ArrayIndices, &mut T) + Send,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        validate_element_size::<T>(self.data_type())?;
+        if array_subset.dimensionality() != self.dimensionality() {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        }
+        let Some(chunks) = self.chunks_in_array_subset(array_subset)? else {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        };
+        let progress = ProgressTracker::new(options, chunks.num_elements_usize());
+        for chunk_indices in &chunks.indices() {
+            progress.check_cancelled()?;
+
+            // Lock the chunk
+            let key = data_key(self.path(), &chunk_indices, self.chunk_key_encoding());
+            let mutex = match &self.async_store_locks {
+                Some(store_locks) => store_locks.mutex(&key).await,
+                None => self.storage.mutex(&key).await?,
+            };
+            let _lock = mutex.lock().await?;
+
+            // Retrieve, update, and store the chunk
+            let chunk_subset = self.chunk_subset(&chunk_indices)?;
+            let overlap = unsafe { chunk_subset.overlap_unchecked(array_subset) };
+            let chunk_bytes = self
+                .async_retrieve_chunk_opt(&chunk_indices, options)
+                .await?;
+            let chunk_bytes = update_chunk_elements(chunk_bytes, &chunk_subset, &overlap, &mut f)?;
+            let num_bytes = chunk_bytes.len() as u64;
+            self.async_store_chunk_opt(&chunk_indices, chunk_bytes, options)
+                .await?;
+            progress.chunk_completed(num_bytes);
         }
         Ok(())
     }
 
     /// Async variant of [`store_array_subset_elements_opt`](Array::store_array_subset_elements_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_store_array_subset_elements_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_store_array_subset_elements_opt<T: Element + Send + Sync>(
         &self,
         array_subset: &ArraySubset,
         subset_elements: Vec<T>,
@@ -378,7 +611,7 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
     /// Async variant of [`store_array_subset_ndarray_opt`](Array::store_array_subset_ndarray_opt).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_store_array_subset_ndarray_opt<
-        T: bytemuck::Pod + Send + Sync,
+        T: Element + Send + Sync,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -398,4 +631,177 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
             async_store_array_subset_elements_opt(&subset, subset_array, options)
         )
     }
+
+    #[cfg(feature = "arrow")]
+    /// Async variant of [`store_array_subset_arrow_opt`](Array::store_array_subset_arrow_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_store_array_subset_arrow_opt<T: arrow_array::ArrowPrimitiveType>(
+        &self,
+        array_subset: &ArraySubset,
+        subset_array: &arrow_array::PrimitiveArray<T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError>
+    where
+        T::Native: Element,
+    {
+        validate_arrow_nulls(subset_array)?;
+        self.async_store_array_subset_elements_opt(
+            array_subset,
+            subset_array.values().to_vec(),
+            options,
+        )
+        .await
+    }
+
+    /// Async variant of [`resize_opt`](Array::resize_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_resize_opt(
+        &mut self,
+        new_shape: ArrayShape,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let chunks_outside = self.chunks_outside_shape(&new_shape)?;
+
+        // Erase chunks outside of the new shape, and fill elements of chunks partially outside of the new shape
+        let resize_chunk = |chunk_indices: ArrayIndices| {
+            let new_shape = &new_shape;
+            let this = &*self;
+            async move {
+                let chunk_subset = this
+                    .chunk_grid()
+                    .subset(&chunk_indices, this.shape())?
+                    .ok_or_else(|| {
+                        ArrayError::InvalidChunkGridIndicesError(chunk_indices.clone())
+                    })?;
+                if std::iter::zip(chunk_subset.start(), new_shape)
+                    .any(|(start, size)| start >= size)
+                {
+                    this.async_erase_chunk(&chunk_indices).await?;
+                } else if let Some(chunk_bytes) = this
+                    .async_retrieve_chunk_if_exists_opt(&chunk_indices, options)
+                    .await?
+                {
+                    let chunk_bytes =
+                        this.chunk_bytes_bounded(&chunk_subset, &chunk_bytes, new_shape);
+                    this.async_store_chunk_opt(&chunk_indices, chunk_bytes, options)
+                        .await?;
+                }
+                Ok::<_, ArrayError>(())
+            }
+        };
+        let mut futures = chunks_outside
+            .into_iter()
+            .map(resize_chunk)
+            .collect::<FuturesUnordered<_>>();
+        while let Some(item) = futures.next().await {
+            item?;
+        }
+        drop(futures);
+
+        self.set_shape(new_shape);
+        Ok(())
+    }
+
+    /// Async variant of [`append_opt`](Array::append_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append_opt(
+        &mut self,
+        axis: usize,
+        append_bytes: Vec<u8>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let (append_subset, new_shape) = self.append_subset(axis, append_bytes.len())?;
+        self.async_resize_opt(new_shape, options).await?;
+        self.async_store_array_subset_opt(&append_subset, append_bytes, options)
+            .await
+    }
+
+    /// Async variant of [`append_elements_opt`](Array::append_elements_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append_elements_opt<T: Element + Send + Sync>(
+        &mut self,
+        axis: usize,
+        append_elements: Vec<T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        array_async_store_elements!(
+            self,
+            append_elements,
+            async_append_opt(axis, append_elements, options)
+        )
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`append_ndarray_opt`](Array::append_ndarray_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append_ndarray_opt<
+        T: Element + Send + Sync,
+        TArray: Into<ndarray::Array<T, D>>,
+        D: ndarray::Dimension,
+    >(
+        &mut self,
+        axis: usize,
+        append_array: TArray,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let append_array: ndarray::Array<T, D> = append_array.into();
+        self.validate_append_ndarray_shape(axis, append_array.shape())?;
+        array_async_store_ndarray!(
+            self,
+            append_array,
+            async_append_elements_opt(axis, append_array, options)
+        )
+    }
+
+    /// Async variant of [`append_ragged_opt`](Array::append_ragged_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append_ragged_opt(
+        &mut self,
+        axis: usize,
+        append_bytes: Vec<u8>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let (append_subset, new_shape) = self.append_subset(axis, append_bytes.len())?;
+        self.chunk_grid = self.append_ragged_chunk_grid(axis, &append_subset)?;
+        self.set_shape(new_shape);
+        self.async_store_array_subset_opt(&append_subset, append_bytes, options)
+            .await
+    }
+
+    /// Async variant of [`append_ragged_elements_opt`](Array::append_ragged_elements_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append_ragged_elements_opt<T: Element + Send + Sync>(
+        &mut self,
+        axis: usize,
+        append_elements: Vec<T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        array_async_store_elements!(
+            self,
+            append_elements,
+            async_append_ragged_opt(axis, append_elements, options)
+        )
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Async variant of [`append_ragged_ndarray_opt`](Array::append_ragged_ndarray_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_append_ragged_ndarray_opt<
+        T: Element + Send + Sync,
+        TArray: Into<ndarray::Array<T, D>>,
+        D: ndarray::Dimension,
+    >(
+        &mut self,
+        axis: usize,
+        append_array: TArray,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let append_array: ndarray::Array<T, D> = append_array.into();
+        self.validate_append_ndarray_shape(axis, append_array.shape())?;
+        array_async_store_ndarray!(
+            self,
+            append_array,
+            async_append_ragged_elements_opt(axis, append_array, options)
+        )
+    }
 }
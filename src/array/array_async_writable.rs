@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
+use bytes::Bytes;
 use futures::{stream::FuturesUnordered, StreamExt};
+use parking_lot::Mutex;
 
 use crate::{
     array_subset::ArraySubset,
@@ -8,9 +10,9 @@ use crate::{
 };
 
 use super::{
-    codec::{options::CodecOptions, ArrayCodecTraits},
+    codec::options::{CodecOptions, ProgressTracker},
     concurrency::concurrency_chunks_and_codec,
-    Array, ArrayError,
+    Array, ArrayError, ArrayIndices, Element,
 };
 
 impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
@@ -21,6 +23,25 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
         let storage_transformer = self
             .storage_transformers()
             .create_async_writable_transformer(storage_handle);
+        let generation = self.metadata_generation();
+        if let Some(generation) = generation {
+            match crate::storage::async_create_array_if_match(
+                &*storage_transformer,
+                self.path(),
+                &self.metadata(),
+                Some(&generation),
+            )
+            .await
+            {
+                Ok(generation) => {
+                    self.set_metadata_generation(generation);
+                    return Ok(());
+                }
+                Err(StorageError::Unsupported(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.set_metadata_generation(None);
         crate::storage::async_create_array(&*storage_transformer, self.path(), &self.metadata())
             .await
     }
@@ -38,7 +59,7 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
 
     /// Async variant of [`store_chunk_elements`](Array::store_chunk_elements).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_store_chunk_elements<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_store_chunk_elements<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         chunk_elements: Vec<T>,
@@ -51,7 +72,7 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
     /// Async variant of [`store_chunk_ndarray`](Array::store_chunk_ndarray).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_store_chunk_ndarray<
-        T: bytemuck::Pod + Send + Sync,
+        T: Element + Send + Sync,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -75,9 +96,24 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
             .await
     }
 
+    /// Async variant of [`store_array_subset_exact`](Array::store_array_subset_exact).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_store_array_subset_exact(
+        &self,
+        array_subset: &ArraySubset,
+        subset_bytes: Vec<u8>,
+    ) -> Result<(), ArrayError> {
+        self.async_store_array_subset_exact_opt(
+            array_subset,
+            subset_bytes,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
     /// Async variant of [`store_chunks_elements`](Array::store_chunks_elements).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_store_chunks_elements<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_store_chunks_elements<T: Element + Send + Sync>(
         &self,
         chunks: &ArraySubset,
         chunks_elements: Vec<T>,
@@ -90,7 +126,7 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
     /// Async variant of [`store_chunks_ndarray`](Array::store_chunks_ndarray).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_store_chunks_ndarray<
-        T: bytemuck::Pod + Send + Sync,
+        T: Element + Send + Sync,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -102,40 +138,36 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
             .await
     }
 
-    /// Async variant of [`erase_chunk`](Array::erase_chunk).
+    /// Async variant of [`store_encoded_chunk`](Array::store_encoded_chunk).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_erase_chunk(&self, chunk_indices: &[u64]) -> Result<(), StorageError> {
-        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-        let storage_transformer = self
-            .storage_transformers()
-            .create_async_writable_transformer(storage_handle);
-        crate::storage::async_erase_chunk(
-            &*storage_transformer,
-            self.path(),
+    pub async fn async_store_encoded_chunk(
+        &self,
+        chunk_indices: &[u64],
+        encoded_chunk: Bytes,
+    ) -> Result<(), ArrayError> {
+        self.maybe_async_store_encoded_chunk(
+            &self.async_writable_storage(),
             chunk_indices,
-            self.chunk_key_encoding(),
+            encoded_chunk,
+            None,
         )
         .await
     }
 
+    /// Async variant of [`erase_chunk`](Array::erase_chunk).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_erase_chunk(&self, chunk_indices: &[u64]) -> Result<(), StorageError> {
+        self.maybe_async_erase_chunk(&self.async_writable_storage(), chunk_indices)
+            .await
+    }
+
     /// Async variant of [`erase_chunks`](Array::erase_chunks).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_erase_chunks(&self, chunks: &ArraySubset) -> Result<(), StorageError> {
-        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-        let storage_transformer = self
-            .storage_transformers()
-            .create_async_writable_transformer(storage_handle);
+        let storage = self.async_writable_storage();
         let erase_chunk = |chunk_indices: Vec<u64>| {
-            let storage_transformer = storage_transformer.clone();
-            async move {
-                crate::storage::async_erase_chunk(
-                    &*storage_transformer,
-                    self.path(),
-                    &chunk_indices,
-                    self.chunk_key_encoding(),
-                )
-                .await
-            }
+            let storage = &storage;
+            async move { self.maybe_async_erase_chunk(storage, &chunk_indices).await }
         };
 
         let mut futures = chunks
@@ -149,55 +181,78 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
         Ok(())
     }
 
+    /// Async variant of [`erase`](Array::erase).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_erase(&self) -> Result<(), StorageError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_writable_transformer(storage_handle);
+        crate::storage::async_erase_node(&*storage_transformer, self.path()).await
+    }
+
     /////////////////////////////////////////////////////////////////////////////
     // Advanced methods
     /////////////////////////////////////////////////////////////////////////////
 
     /// Async variant of [`store_chunk_opt`](Array::store_chunk_opt).
     #[allow(clippy::missing_errors_doc)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(path = %self.path(), chunk_indices = ?chunk_indices))
+    )]
     pub async fn async_store_chunk_opt(
         &self,
         chunk_indices: &[u64],
         chunk_bytes: Vec<u8>,
         options: &CodecOptions,
     ) -> Result<(), ArrayError> {
-        // Validation
-        let chunk_array_representation = self.chunk_array_representation(chunk_indices)?;
-        if chunk_bytes.len() as u64 != chunk_array_representation.size() {
-            return Err(ArrayError::InvalidBytesInputSize(
-                chunk_bytes.len(),
-                chunk_array_representation.size(),
-            ));
-        }
-
-        let all_fill_value = self.fill_value().equals_all(&chunk_bytes);
-        if all_fill_value {
-            self.async_erase_chunk(chunk_indices).await?;
-            Ok(())
-        } else {
+        self.validate_chunk_indices(chunk_indices)?;
+        if let Some((segments, chunk_statistics)) =
+            self.encode_chunk_segments(chunk_indices, chunk_bytes, options)?
+        {
+            // Stream the encoded chunk into the store, so that it is not held in memory if the codecs encode it incrementally (e.g. sharding)
+            let codec_error = Mutex::new(None);
+            let stream = futures::stream::iter(segments.map(|segment| {
+                segment.map(Bytes::from).map_err(|err| {
+                    let message = err.to_string();
+                    *codec_error.lock() = Some(err);
+                    StorageError::Other(message)
+                })
+            }))
+            .boxed();
             let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
             let storage_transformer = self
                 .storage_transformers()
                 .create_async_writable_transformer(storage_handle);
-            let chunk_encoded: Vec<u8> = self
-                .codecs()
-                .encode(chunk_bytes, &chunk_array_representation, options)
-                .map_err(ArrayError::CodecError)?;
-            crate::storage::async_store_chunk(
+            let result = crate::storage::async_store_chunk_stream(
                 &*storage_transformer,
                 self.path(),
                 chunk_indices,
                 self.chunk_key_encoding(),
-                chunk_encoded.into(),
+                stream,
             )
-            .await
-            .map_err(ArrayError::StorageError)
+            .await;
+            if let Some(err) = codec_error.into_inner() {
+                return Err(ArrayError::CodecError(err));
+            }
+            result?;
+            self.maybe_async_store_chunk_statistics(
+                &self.async_writable_storage(),
+                chunk_indices,
+                chunk_statistics.as_ref(),
+            )
+            .await?;
+        } else {
+            self.async_erase_chunk(chunk_indices).await?;
         }
+        self.invalidate_cached_shard_index(chunk_indices, options);
+        Ok(())
     }
 
     /// Async variant of [`store_chunk_elements_opt`](Array::store_chunk_elements_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_store_chunk_elements_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_store_chunk_elements_opt<T: Element + Send + Sync>(
         &self,
         chunk_indices: &[u64],
         chunk_elements: Vec<T>,
@@ -214,7 +269,7 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
     /// Async variant of [`store_chunk_ndarray_opt`](Array::store_chunk_ndarray_opt).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_store_chunk_ndarray_opt<
-        T: bytemuck::Pod + Send + Sync,
+        T: Element + Send + Sync,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -247,14 +302,31 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
         chunks: &ArraySubset,
         chunks_bytes: Vec<u8>,
         options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        self.async_store_chunks_journaled_opt(chunks, chunks_bytes, None, options)
+            .await
+    }
+
+    /// Async variant of [`store_chunks_journaled_opt`](Array::store_chunks_journaled_opt).
+    #[allow(clippy::similar_names)]
+    pub(super) async fn async_store_chunks_journaled_opt(
+        &self,
+        chunks: &ArraySubset,
+        chunks_bytes: Vec<u8>,
+        existing_chunks: Option<Vec<ArrayIndices>>,
+        options: &CodecOptions,
     ) -> Result<(), ArrayError> {
         let num_chunks = chunks.num_elements_usize();
+        let progress = ProgressTracker::new(options, num_chunks);
+        progress.check_cancelled()?;
         match num_chunks {
             0 => {}
             1 => {
                 let chunk_indices = chunks.start();
+                let num_bytes = chunks_bytes.len() as u64;
                 self.async_store_chunk_opt(chunk_indices, chunks_bytes, options)
                     .await?;
+                progress.chunk_completed(num_bytes);
             }
             _ => {
                 let array_subset = self.chunks_subset(chunks)?;
@@ -267,6 +339,10 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
                     ));
                 }
 
+                let write_journal = self
+                    .async_begin_write_journal(chunks, existing_chunks, options)
+                    .await?;
+
                 // Calculate chunk/codec concurrency
                 let chunk_representation =
                     self.chunk_array_representation(&vec![0; self.dimensionality()])?;
@@ -277,8 +353,10 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
                     num_chunks,
                     options,
                     &codec_concurrency,
+                    self.chunk_memory_usage(&chunk_representation)?,
                 );
 
+                let progress = &progress;
                 let store_chunk = |chunk_indices: Vec<u64>| {
                     let chunk_subset_in_array = unsafe {
                         self.chunk_grid()
@@ -302,18 +380,35 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
                     );
 
                     let options = options.clone();
+                    let num_bytes = chunk_bytes.len() as u64;
                     async move {
+                        progress.check_cancelled()?;
                         self.async_store_chunk_opt(&chunk_indices, chunk_bytes, &options)
                             .await
+                            .map(|()| (chunk_indices, num_bytes))
                     }
                 };
                 let indices = chunks.indices();
                 let futures = indices.into_iter().map(store_chunk);
                 let mut stream =
                     futures::stream::iter(futures).buffer_unordered(chunk_concurrent_limit);
+                let mut completed_chunks = Vec::new();
+                let mut result = Ok(());
                 while let Some(item) = stream.next().await {
-                    item?;
+                    match item {
+                        Ok((chunk_indices, num_bytes)) => {
+                            progress.chunk_completed(num_bytes);
+                            completed_chunks.push(chunk_indices);
+                        }
+                        Err(err) => {
+                            result = Err(err);
+                            break;
+                        }
+                    }
                 }
+                drop(stream);
+                self.async_finish_write_journal(write_journal, result, completed_chunks)
+                    .await?;
             }
         }
 
@@ -322,7 +417,7 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
 
     /// Async variant of [`store_chunks_elements_opt`](Array::store_chunks_elements_opt).
     #[allow(clippy::missing_errors_doc)]
-    pub async fn async_store_chunks_elements_opt<T: bytemuck::Pod + Send + Sync>(
+    pub async fn async_store_chunks_elements_opt<T: Element + Send + Sync>(
         &self,
         chunks: &ArraySubset,
         chunks_elements: Vec<T>,
@@ -339,7 +434,7 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
     /// Async variant of [`store_chunks_ndarray_opt`](Array::store_chunks_ndarray_opt).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_store_chunks_ndarray_opt<
-        T: bytemuck::Pod + Send + Sync,
+        T: Element + Send + Sync,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -364,4 +459,17 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
             ))
         }
     }
+
+    /// Async variant of [`store_array_subset_exact_opt`](Array::store_array_subset_exact_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_store_array_subset_exact_opt(
+        &self,
+        array_subset: &ArraySubset,
+        subset_bytes: Vec<u8>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let chunks = self.chunks_in_aligned_array_subset(array_subset)?;
+        self.async_store_chunks_opt(&chunks, subset_bytes, options)
+            .await
+    }
 }
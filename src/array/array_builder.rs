@@ -1,14 +1,29 @@
 use std::sync::Arc;
 
-use crate::{metadata::AdditionalFields, node::NodePath, storage::StorageTransformerChain};
+use parking_lot::Mutex;
+
+use crate::{
+    config::global_config,
+    metadata::AdditionalFields,
+    node::NodePath,
+    storage::{store_lock::StoreLocks, StorageTransformerChain},
+};
+
+#[cfg(feature = "async")]
+use crate::storage::store_lock::AsyncStoreLocks;
+
+#[cfg(feature = "sharding")]
+use super::{codec::array_to_bytes::sharding::ShardingCodecBuilder, ChunkShape};
 
 use super::{
+    array_statistics::ArrayStatistics,
     chunk_key_encoding::{ChunkKeyEncoding, ChunkKeySeparator, DefaultChunkKeyEncoding},
     codec::{
         ArrayToArrayCodecTraits, ArrayToBytesCodecTraits, BytesCodec, BytesToBytesCodecTraits,
     },
     data_type::IncompatibleFillValueError,
-    Array, ArrayCreateError, ArrayShape, ChunkGrid, CodecChain, DataType, DimensionName, FillValue,
+    Array, ArrayCreateError, ArrayShape, ChunkGrid, ChunkRepresentation, CodecChain, DataType,
+    DimensionName, FillValue, HistogramBins,
 };
 
 /// An [`Array`] builder.
@@ -18,6 +33,7 @@ use super::{
 ///  - The default chunk key encoding is `default` with the `/` chunk key separator.
 ///  - Attributes, storage transformers, and dimension names are empty.
 ///  - Codecs are configured to use multiple threads where possible.
+///  - Chunk statistics are not computed.
 ///
 /// Use the methods in the array builder to change the configuration away from these defaults, and then build the array at a path of some storage with [`ArrayBuilder::build`].
 /// Note that [`build`](ArrayBuilder::build) does not modify the store; the array metadata has to be explicitly written with [`Array::store_metadata`].
@@ -78,6 +94,15 @@ pub struct ArrayBuilder {
     pub dimension_names: Option<Vec<DimensionName>>,
     /// Additional fields.
     pub additional_fields: AdditionalFields,
+    /// Compute chunk statistics.
+    pub compute_statistics: bool,
+    /// Statistics histogram bins.
+    pub statistics_histogram_bins: Option<HistogramBins>,
+    /// Store locks overriding those of the store.
+    pub store_locks: Option<StoreLocks>,
+    /// Asynchronous store locks overriding those of the store.
+    #[cfg(feature = "async")]
+    pub async_store_locks: Option<AsyncStoreLocks>,
 }
 
 impl ArrayBuilder {
@@ -105,6 +130,11 @@ impl ArrayBuilder {
             storage_transformers: StorageTransformerChain::default(),
             dimension_names: None,
             additional_fields: AdditionalFields::default(),
+            compute_statistics: false,
+            statistics_histogram_bins: None,
+            store_locks: None,
+            #[cfg(feature = "async")]
+            async_store_locks: None,
         }
     }
 
@@ -125,7 +155,14 @@ impl ArrayBuilder {
             .array_to_array_codecs(array.codecs().array_to_array_codecs().to_vec())
             .array_to_bytes_codec(array.codecs().array_to_bytes_codec().clone())
             .bytes_to_bytes_codecs(array.codecs().bytes_to_bytes_codecs().to_vec())
-            .storage_transformers(array.storage_transformers().clone());
+            .storage_transformers(array.storage_transformers().clone())
+            .compute_statistics(array.compute_statistics())
+            .statistics_histogram_bins(array.statistics_histogram_bins());
+        builder.store_locks = array.store_locks().cloned();
+        #[cfg(feature = "async")]
+        {
+            builder.async_store_locks = array.async_store_locks().cloned();
+        }
         builder
     }
 
@@ -205,6 +242,29 @@ impl ArrayBuilder {
         self
     }
 
+    /// Shard the chunks of the array into inner chunks with shape `inner_chunk_shape`.
+    ///
+    /// The chunks of the array become shards encoded with the `sharding_indexed` codec, with the index at the end of the shard encoded with the `bytes` and `crc32c checksum` (if supported) codecs.
+    /// The codecs configured before calling this method become the codecs of the inner chunks, and the array is left with only the `sharding_indexed` codec.
+    /// So compression (e.g. a bytes to bytes codec) should be configured **before** sharding, otherwise it applies to entire shards and partial reads of a shard are not possible.
+    ///
+    /// The inner chunk shape must evenly divide the chunk shape, otherwise [`build`](ArrayBuilder::build) will fail.
+    /// Use a [`ShardingCodecBuilder`](super::codec::array_to_bytes::sharding::ShardingCodecBuilder) with [`array_to_bytes_codec`](ArrayBuilder::array_to_bytes_codec) for more control over the sharding configuration.
+    #[cfg(feature = "sharding")]
+    pub fn shard(&mut self, inner_chunk_shape: ChunkShape) -> &mut Self {
+        let inner_codecs = CodecChain::new(
+            std::mem::take(&mut self.array_to_array_codecs),
+            self.array_to_bytes_codec.clone(),
+            std::mem::take(&mut self.bytes_to_bytes_codecs),
+        );
+        self.array_to_bytes_codec = Box::new(
+            ShardingCodecBuilder::new(inner_chunk_shape)
+                .inner_codecs(&inner_codecs)
+                .build(),
+        );
+        self
+    }
+
     /// Set the user defined attributes.
     ///
     /// If left unmodified, the user defined attributes of the array will be empty.
@@ -260,12 +320,79 @@ impl ArrayBuilder {
         self
     }
 
+    /// Set whether chunk statistics are computed when chunks are stored.
+    ///
+    /// If left unmodified, statistics are not computed.
+    /// See [`Array::statistics`].
+    pub fn compute_statistics(&mut self, compute_statistics: bool) -> &mut Self {
+        self.compute_statistics = compute_statistics;
+        self
+    }
+
+    /// Set the histogram bins of chunk statistics.
+    ///
+    /// If left unmodified, histograms are not computed.
+    /// Histograms are only computed if statistics are enabled with [`compute_statistics`](ArrayBuilder::compute_statistics).
+    pub fn statistics_histogram_bins(
+        &mut self,
+        statistics_histogram_bins: Option<HistogramBins>,
+    ) -> &mut Self {
+        self.statistics_histogram_bins = statistics_histogram_bins;
+        self
+    }
+
+    /// Set the store locks used for chunk locking by the array.
+    ///
+    /// If left unmodified, the locks of the store are used.
+    /// See [`Array::set_store_locks`].
+    pub fn store_locks(&mut self, store_locks: StoreLocks) -> &mut Self {
+        self.store_locks = Some(store_locks);
+        self
+    }
+
+    /// Set the asynchronous store locks used for chunk locking by the array.
+    ///
+    /// If left unmodified, the locks of the store are used.
+    /// See [`Array::set_async_store_locks`].
+    #[cfg(feature = "async")]
+    pub fn async_store_locks(&mut self, async_store_locks: AsyncStoreLocks) -> &mut Self {
+        self.async_store_locks = Some(async_store_locks);
+        self
+    }
+
+    /// Validate `codecs` against the representation of the first chunk of the array.
+    fn validate_codecs(&self, codecs: &CodecChain) -> Result<(), ArrayCreateError> {
+        let chunk_shape = self
+            .chunk_grid
+            .chunk_shape(&vec![0; self.shape.len()], &self.shape)
+            .map_err(|_| {
+                ArrayCreateError::InvalidChunkGridDimensionality(
+                    self.chunk_grid.dimensionality(),
+                    self.shape.len(),
+                )
+            })?;
+        if let Some(chunk_shape) = chunk_shape {
+            let chunk_representation = ChunkRepresentation::new(
+                chunk_shape.to_vec(),
+                self.data_type.clone(),
+                self.fill_value.clone(),
+            )?;
+            codecs
+                .compute_encoded_size(&chunk_representation)
+                .map_err(|err| {
+                    ArrayCreateError::IncompatibleCodecs(chunk_representation.shape_u64(), err)
+                })?;
+        }
+        Ok(())
+    }
+
     /// Build into an [`Array`].
     ///
     /// # Errors
     ///
     /// Returns [`ArrayCreateError`] if there is an error creating the array.
     /// This can be due to a storage error, an invalid path, or a problem with array configuration.
+    /// The codecs are validated against the chunk shape of the first chunk, so an incompatible codec configuration (e.g. a `sharding` inner chunk shape that does not evenly divide the chunk shape) is an [`ArrayCreateError::IncompatibleCodecs`] error.
     pub fn build<TStorage: ?Sized>(
         &self,
         storage: Arc<TStorage>,
@@ -296,6 +423,13 @@ impl ArrayBuilder {
 
         self.additional_fields.validate()?;
 
+        let codecs = CodecChain::new(
+            self.array_to_array_codecs.clone(),
+            self.array_to_bytes_codec.clone(),
+            self.bytes_to_bytes_codecs.clone(),
+        );
+        self.validate_codecs(&codecs)?;
+
         Ok(Array {
             storage,
             path,
@@ -304,16 +438,19 @@ impl ArrayBuilder {
             chunk_grid: self.chunk_grid.clone(),
             chunk_key_encoding: self.chunk_key_encoding.clone(),
             fill_value: self.fill_value.clone(),
-            codecs: CodecChain::new(
-                self.array_to_array_codecs.clone(),
-                self.array_to_bytes_codec.clone(),
-                self.bytes_to_bytes_codecs.clone(),
-            ),
+            codecs,
             storage_transformers: self.storage_transformers.clone(),
             attributes: self.attributes.clone(),
             dimension_names: self.dimension_names.clone(),
             additional_fields: self.additional_fields.clone(),
-            include_zarrs_metadata: true,
+            include_zarrs_metadata: global_config().include_zarrs_metadata(),
+            statistics: self
+                .compute_statistics
+                .then(|| ArrayStatistics::new(self.statistics_histogram_bins)),
+            metadata_generation: Mutex::new(None),
+            store_locks: self.store_locks.clone(),
+            #[cfg(feature = "async")]
+            async_store_locks: self.async_store_locks.clone(),
         })
     }
 }
@@ -416,4 +553,92 @@ mod tests {
         builder.dimension_names(["z", "y", "x"].into());
         assert!(builder.build(storage.clone(), "/").is_err());
     }
+
+    #[cfg(feature = "sharding")]
+    #[test]
+    fn array_builder_shard() {
+        use crate::{
+            array::codec::{array_to_bytes::sharding, CodecTraits},
+            array_subset::ArraySubset,
+        };
+
+        let storage = Arc::new(MemoryStore::new());
+        let mut builder = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt16,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u16),
+        );
+        builder
+            .array_to_bytes_codec(Box::new(BytesCodec::big()))
+            .shard(vec![2, 2].try_into().unwrap());
+        assert!(builder.array_to_array_codecs.is_empty());
+        assert!(builder.bytes_to_bytes_codecs.is_empty());
+        let metadata = builder.array_to_bytes_codec.create_metadata().unwrap();
+        assert_eq!(metadata.name(), sharding::IDENTIFIER);
+        let sharding::ShardingCodecConfiguration::V1(configuration) =
+            metadata.to_configuration().unwrap();
+        assert_eq!(configuration.chunk_shape, vec![2, 2].try_into().unwrap());
+        assert_eq!(
+            configuration.codecs,
+            vec![BytesCodec::big().create_metadata().unwrap()]
+        );
+        assert_eq!(
+            configuration.index_location,
+            sharding::ShardingIndexLocation::End
+        );
+
+        let array = builder.build(storage.clone(), "/").unwrap();
+        let elements: Vec<u16> = (0..64).collect();
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_shape(vec![8, 8]), elements.clone())
+            .unwrap();
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<u16>(&ArraySubset::new_with_shape(vec![8, 8]))
+                .unwrap(),
+            elements
+        );
+
+        // The inner chunk shape must evenly divide the chunk shape
+        builder.shard(vec![3, 3].try_into().unwrap());
+        assert!(builder.build(storage, "/").is_err());
+    }
+
+    #[cfg(feature = "sharding")]
+    #[test]
+    fn array_builder_invalid_sharding() {
+        use crate::array::codec::{
+            array_to_bytes::sharding::ShardingCodecBuilder,
+            bytes_to_bytes::test_unbounded::TestUnboundedCodec,
+        };
+
+        let storage = Arc::new(MemoryStore::new());
+        let mut builder = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::Int8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0i8),
+        );
+        builder.array_to_bytes_codec(Box::new(
+            ShardingCodecBuilder::new(vec![2, 2].try_into().unwrap()).build(),
+        ));
+        assert!(builder.build(storage.clone(), "/").is_ok());
+
+        // The inner chunk shape does not evenly divide the chunk shape
+        builder.array_to_bytes_codec(Box::new(
+            ShardingCodecBuilder::new(vec![3, 2].try_into().unwrap()).build(),
+        ));
+        let err = builder.build(storage.clone(), "/").unwrap_err();
+        assert!(matches!(err, ArrayCreateError::IncompatibleCodecs(..)));
+        assert!(err.to_string().contains("[3, 2]"));
+
+        // Also validated if the inner chunks have an unbounded encoded size
+        builder.array_to_bytes_codec(Box::new(
+            ShardingCodecBuilder::new(vec![3, 2].try_into().unwrap())
+                .bytes_to_bytes_codecs(vec![Box::new(TestUnboundedCodec::new())])
+                .build(),
+        ));
+        assert!(builder.build(storage, "/").is_err());
+    }
 }
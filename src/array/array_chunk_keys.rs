@@ -0,0 +1,223 @@
+use std::{collections::HashSet, sync::Arc};
+
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::storage::{
+    data_key, ListableStorageTraits, ReadableWritableStorageTraits, StorageError, StorageHandle,
+    StoreKey,
+};
+
+#[cfg(feature = "async")]
+use crate::storage::{AsyncListableStorageTraits, AsyncReadableWritableStorageTraits};
+
+use super::{chunk_key_encoding::ChunkKeyEncoding, Array, ArrayError};
+
+impl<TStorage: ?Sized> Array<TStorage> {
+    /// Return the current and new keys of the stored chunks in `stored_keys` if encoded with `chunk_key_encoding`.
+    ///
+    /// Chunks with an unchanged key are skipped.
+    fn rewrite_chunk_keys_plan(
+        &self,
+        stored_keys: &HashSet<StoreKey>,
+        chunk_key_encoding: &ChunkKeyEncoding,
+    ) -> Result<Vec<(StoreKey, StoreKey)>, ArrayError> {
+        let keys = self
+            .stored_chunks_in_keys(stored_keys)?
+            .into_iter()
+            .map(|chunk_indices| {
+                (
+                    data_key(self.path(), &chunk_indices, self.chunk_key_encoding()),
+                    data_key(self.path(), &chunk_indices, chunk_key_encoding),
+                )
+            })
+            .filter(|(key, key_new)| key != key_new)
+            .collect::<Vec<_>>();
+        if let Some((_, key_new)) = keys
+            .iter()
+            .find(|(_, key_new)| stored_keys.contains(key_new))
+        {
+            return Err(StorageError::Other(format!(
+                "rewritten chunk key {key_new} collides with an existing key"
+            ))
+            .into());
+        }
+        Ok(keys)
+    }
+}
+
+impl<TStorage: ?Sized + ReadableWritableStorageTraits + ListableStorageTraits + 'static>
+    Array<TStorage>
+{
+    /// Rewrite the keys of all stored chunks with a new chunk key encoding, and store the updated metadata.
+    ///
+    /// This migrates an array in place, e.g. from the default chunk key encoding with a `/` separator to a `.` separator or `v2` chunk keys.
+    /// Stored chunks are copied to their new keys, then the metadata is stored, then the chunks at the old keys are erased.
+    /// If interrupted before the metadata is stored, the array is unchanged and the operation can be repeated.
+    /// If interrupted after, the array is migrated but some chunks may remain at their old keys.
+    ///
+    /// Chunks are copied without being decoded, and the array should not be written to concurrently.
+    /// Returns the number of chunks with a rewritten key.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the chunk grid shape cannot be determined,
+    ///  - a new chunk key collides with an existing key, or
+    ///  - there is an underlying store error.
+    pub fn rewrite_chunk_keys(
+        &mut self,
+        chunk_key_encoding: ChunkKeyEncoding,
+    ) -> Result<usize, ArrayError> {
+        let stored_keys = self.stored_keys()?;
+        let keys = self.rewrite_chunk_keys_plan(&stored_keys, &chunk_key_encoding)?;
+
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_readable_writable_transformer(storage_handle);
+        keys.par_iter().try_for_each(|(key, key_new)| {
+            if let Some(value) = storage_transformer.get(key)? {
+                storage_transformer.set(key_new, &value)?;
+            }
+            Ok::<_, StorageError>(())
+        })?;
+
+        self.chunk_key_encoding = chunk_key_encoding;
+        self.store_metadata()?;
+
+        let keys_old = keys.into_iter().map(|(key, _)| key).collect::<Vec<_>>();
+        storage_transformer.erase_values(&keys_old)?;
+        Ok(keys_old.len())
+    }
+}
+
+#[cfg(feature = "async")]
+impl<
+        TStorage: ?Sized + AsyncReadableWritableStorageTraits + AsyncListableStorageTraits + 'static,
+    > Array<TStorage>
+{
+    /// Async variant of [`rewrite_chunk_keys`](Array::rewrite_chunk_keys).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_rewrite_chunk_keys(
+        &mut self,
+        chunk_key_encoding: ChunkKeyEncoding,
+    ) -> Result<usize, ArrayError> {
+        let stored_keys = self.async_stored_keys().await?;
+        let keys = self.rewrite_chunk_keys_plan(&stored_keys, &chunk_key_encoding)?;
+
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer_read = self
+            .storage_transformers()
+            .create_async_readable_transformer(storage_handle.clone());
+        let storage_transformer_write = self
+            .storage_transformers()
+            .create_async_writable_transformer(storage_handle);
+        for (key, key_new) in &keys {
+            if let Some(value) = storage_transformer_read.get(key).await? {
+                storage_transformer_write.set(key_new, value).await?;
+            }
+        }
+
+        self.chunk_key_encoding = chunk_key_encoding;
+        self.async_store_metadata().await?;
+
+        let keys_old = keys.into_iter().map(|(key, _)| key).collect::<Vec<_>>();
+        storage_transformer_write.erase_values(&keys_old).await?;
+        Ok(keys_old.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{
+            chunk_key_encoding::{
+                ChunkKeyEncodingTraits, DefaultChunkKeyEncoding, V2ChunkKeyEncoding,
+            },
+            ArrayBuilder, DataType, FillValue,
+        },
+        array_subset::ArraySubset,
+        storage::{store::MemoryStore, ListableStorageTraits, StorePrefix},
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_rewrite_chunk_keys() {
+        let store = Arc::new(MemoryStore::new());
+        let mut array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        let elements: Vec<u8> = (0..8).collect();
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_ranges(&[0..2, 0..4]), elements)
+            .unwrap();
+        let list_keys = || {
+            let mut keys = store
+                .list_prefix(&StorePrefix::new("array/").unwrap())
+                .unwrap()
+                .into_iter()
+                .map(|key| key.to_string())
+                .collect::<Vec<_>>();
+            keys.sort();
+            keys
+        };
+        assert_eq!(
+            list_keys(),
+            vec!["array/c/0/0", "array/c/0/1", "array/zarr.json"]
+        );
+
+        assert_eq!(
+            array
+                .rewrite_chunk_keys(DefaultChunkKeyEncoding::new_dot().into())
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            list_keys(),
+            vec!["array/c.0.0", "array/c.0.1", "array/zarr.json"]
+        );
+        let array_reopened = Array::new(store.clone(), "/array").unwrap();
+        assert_eq!(
+            array_reopened.chunk_key_encoding().create_metadata(),
+            DefaultChunkKeyEncoding::new_dot().create_metadata()
+        );
+        assert_eq!(
+            array_reopened
+                .retrieve_array_subset_elements::<u8>(&ArraySubset::new_with_shape(vec![4, 4]))
+                .unwrap(),
+            [(0..8).collect::<Vec<u8>>(), vec![0; 8]].concat()
+        );
+
+        assert_eq!(
+            array
+                .rewrite_chunk_keys(V2ChunkKeyEncoding::new_dot().into())
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            list_keys(),
+            vec!["array/0.0", "array/0.1", "array/zarr.json"]
+        );
+        assert_eq!(
+            array
+                .rewrite_chunk_keys(V2ChunkKeyEncoding::new_dot().into())
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            Array::new(store, "/array")
+                .unwrap()
+                .retrieve_chunk_elements::<u8>(&[0, 1])
+                .unwrap(),
+            vec![2, 3, 6, 7]
+        );
+    }
+}
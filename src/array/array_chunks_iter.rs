@@ -0,0 +1,164 @@
+use std::iter::FusedIterator;
+
+use crate::{array_subset::ArraySubset, storage::ReadableStorageTraits};
+
+use super::{codec::CodecOptions, unravel_index, Array, ArrayError, ArrayIndices};
+
+/// An iterator over the decoded chunks of an array.
+///
+/// The iterator item is a [`Result`] of the chunk indices and the decoded bytes of the chunk.
+/// Chunks are retrieved and decoded lazily as the iterator is advanced, so only one chunk is held in memory at a time.
+///
+/// See [`Array::chunks_iter`] and [`Array::par_chunks_iter`].
+pub struct ArrayChunksIter<'a, TStorage: ?Sized> {
+    array: &'a Array<TStorage>,
+    chunks: ArraySubset,
+    options: CodecOptions,
+    index_front: u64,
+    index_back: u64,
+}
+
+impl<'a, TStorage: ?Sized + ReadableStorageTraits + 'static> ArrayChunksIter<'a, TStorage> {
+    /// Create a new iterator over the decoded chunks at `chunks` of `array`.
+    ///
+    /// `chunks` is a subset of the chunk grid, and must be within its bounds.
+    #[must_use]
+    pub fn new(array: &'a Array<TStorage>, chunks: ArraySubset, options: CodecOptions) -> Self {
+        let index_back = chunks.num_elements();
+        Self {
+            array,
+            chunks,
+            options,
+            index_front: 0,
+            index_back,
+        }
+    }
+
+    /// Return the chunks subset of the chunk grid.
+    #[must_use]
+    pub const fn chunks(&self) -> &ArraySubset {
+        &self.chunks
+    }
+
+    fn chunk_indices(&self, index: u64) -> ArrayIndices {
+        let mut chunk_indices = unravel_index(index, self.chunks.shape());
+        std::iter::zip(chunk_indices.iter_mut(), self.chunks.start())
+            .for_each(|(chunk_index, start)| *chunk_index += start);
+        chunk_indices
+    }
+
+    fn retrieve(&self, index: u64) -> Result<(ArrayIndices, Vec<u8>), ArrayError> {
+        let chunk_indices = self.chunk_indices(index);
+        let chunk_bytes = self
+            .array
+            .retrieve_chunk_opt(&chunk_indices, &self.options)?;
+        Ok((chunk_indices, chunk_bytes))
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Iterator
+    for ArrayChunksIter<'_, TStorage>
+{
+    type Item = Result<(ArrayIndices, Vec<u8>), ArrayError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index_front < self.index_back {
+            let item = self.retrieve(self.index_front);
+            self.index_front += 1;
+            Some(item)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = usize::try_from(self.index_back - self.index_front).unwrap_or(usize::MAX);
+        (length, Some(length))
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> DoubleEndedIterator
+    for ArrayChunksIter<'_, TStorage>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index_back > self.index_front {
+            self.index_back -= 1;
+            Some(self.retrieve(self.index_back))
+        } else {
+            None
+        }
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> ExactSizeIterator
+    for ArrayChunksIter<'_, TStorage>
+{
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> FusedIterator
+    for ArrayChunksIter<'_, TStorage>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rayon::iter::ParallelIterator;
+
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_chunks_iter() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::UInt8,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/")
+        .unwrap();
+        let elements: Vec<u8> = (0..24).collect();
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_shape(vec![4, 6]), elements)
+            .unwrap();
+
+        let mut iter = array
+            .chunks_iter(&ArraySubset::new_with_ranges(&[1..3, 2..4]))
+            .unwrap();
+        assert_eq!(iter.chunks(), &ArraySubset::new_with_ranges(&[0..2, 0..2]));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(
+            iter.next().unwrap().unwrap(),
+            (vec![0, 0], vec![0, 1, 2, 6, 7, 8])
+        );
+        assert_eq!(
+            iter.next_back().unwrap().unwrap(),
+            (vec![1, 1], vec![15, 16, 17, 21, 22, 23])
+        );
+        let remaining: Vec<_> = iter.map(|item| item.unwrap().0).collect();
+        assert_eq!(remaining, vec![vec![0, 1], vec![1, 0]]);
+
+        let mut chunks: Vec<_> = array
+            .par_chunks_iter(&ArraySubset::new_with_shape(vec![4, 6]))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        chunks.sort();
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[1], (vec![0, 1], vec![3, 4, 5, 9, 10, 11]));
+
+        assert!(array
+            .chunks_iter(&ArraySubset::new_with_ranges(&[0..5, 0..6]))
+            .is_err());
+        assert!(array
+            .par_chunks_iter(&ArraySubset::new_with_shape(vec![1]))
+            .is_err());
+    }
+}
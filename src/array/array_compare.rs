@@ -0,0 +1,529 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+use crate::{array_subset::ArraySubset, storage::ReadableStorageTraits};
+
+#[cfg(feature = "async")]
+use crate::storage::AsyncReadableStorageTraits;
+
+use super::{
+    codec::{options::ProgressTracker, CodecOptions},
+    concurrency::concurrency_chunks_and_codec,
+    unravel_index, Array, ArrayError, ArrayIndices, DataType,
+};
+
+/// Options for [`compare`].
+///
+/// By default, elements must be exactly equal and all chunks are compared.
+#[derive(Debug, Clone, Default)]
+pub struct CompareOptions {
+    codec_options: CodecOptions,
+    tolerance: f64,
+    early_exit: bool,
+}
+
+impl CompareOptions {
+    /// Create new compare options with the default values.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the codec options used to retrieve chunks.
+    ///
+    /// The concurrency target of the codec options sets the number of chunks compared in parallel.
+    #[must_use]
+    pub fn codec_options(mut self, codec_options: CodecOptions) -> Self {
+        self.codec_options = codec_options;
+        self
+    }
+
+    /// Set the absolute tolerance for elements of floating point data types.
+    ///
+    /// Floating point elements are equal if their absolute difference does not exceed `tolerance` or if they are both NaN.
+    /// The real and imaginary components of complex elements are compared separately.
+    /// The tolerance is ignored for other data types, which must be exactly equal.
+    #[must_use]
+    pub const fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set whether to stop comparing chunks once a differing chunk is found.
+    ///
+    /// Chunks which are already being compared when a differing chunk is found are still reported.
+    #[must_use]
+    pub const fn early_exit(mut self, early_exit: bool) -> Self {
+        self.early_exit = early_exit;
+        self
+    }
+}
+
+/// A chunk which differs between the arrays compared with [`compare`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkDiff {
+    chunk_indices: ArrayIndices,
+    differing_elements: u64,
+    first_difference: ArrayIndices,
+}
+
+impl ChunkDiff {
+    /// Return the indices of the chunk.
+    #[must_use]
+    pub fn chunk_indices(&self) -> &[u64] {
+        &self.chunk_indices
+    }
+
+    /// Return the number of differing elements in the chunk.
+    #[must_use]
+    pub const fn differing_elements(&self) -> u64 {
+        self.differing_elements
+    }
+
+    /// Return the array indices of the first differing element in the chunk, in C order.
+    #[must_use]
+    pub fn first_difference(&self) -> &[u64] {
+        &self.first_difference
+    }
+}
+
+/// The report of [`compare`].
+#[derive(Debug, Clone, Default)]
+pub struct DiffReport {
+    num_chunks: usize,
+    chunks_compared: usize,
+    differing_chunks: Vec<ChunkDiff>,
+}
+
+impl DiffReport {
+    /// Return the number of chunks that were compared.
+    ///
+    /// This is less than the number of chunks of the array if the comparison exited early.
+    #[must_use]
+    pub const fn chunks_compared(&self) -> usize {
+        self.chunks_compared
+    }
+
+    /// Return the chunks that differ, in C order.
+    #[must_use]
+    pub fn differing_chunks(&self) -> &[ChunkDiff] {
+        &self.differing_chunks
+    }
+
+    /// Return the total number of differing elements in the compared chunks.
+    #[must_use]
+    pub fn differing_elements(&self) -> u64 {
+        self.differing_chunks
+            .iter()
+            .map(ChunkDiff::differing_elements)
+            .sum()
+    }
+
+    /// Returns true if all chunks were compared.
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.chunks_compared == self.num_chunks
+    }
+
+    /// Returns true if no compared chunks differ.
+    ///
+    /// The arrays are equal if the report is also complete.
+    #[must_use]
+    pub fn is_equal(&self) -> bool {
+        self.differing_chunks.is_empty()
+    }
+
+    fn new(
+        num_chunks: usize,
+        chunks_compared: usize,
+        mut differing_chunks: Vec<ChunkDiff>,
+    ) -> Self {
+        differing_chunks.sort_by(|a, b| a.chunk_indices.cmp(&b.chunk_indices));
+        Self {
+            num_chunks,
+            chunks_compared,
+            differing_chunks,
+        }
+    }
+}
+
+/// Validate that `a` and `b` can be compared and return the chunks of `a`.
+fn compare_validate<TA: ?Sized, TB: ?Sized>(
+    a: &Array<TA>,
+    b: &Array<TB>,
+) -> Result<ArraySubset, ArrayError> {
+    if a.shape() != b.shape() {
+        return Err(ArrayError::IncompatibleShape(
+            a.shape().to_vec(),
+            b.shape().to_vec(),
+        ));
+    }
+    if a.data_type() != b.data_type() {
+        return Err(ArrayError::IncompatibleDataType(
+            a.data_type().clone(),
+            b.data_type().clone(),
+        ));
+    }
+    let chunk_grid_shape = a
+        .chunk_grid_shape()
+        .ok_or_else(|| ArrayError::UnknownChunkGridShape(a.shape().to_vec()))?;
+    Ok(ArraySubset::new_with_shape(chunk_grid_shape))
+}
+
+/// Return the chunk concurrent limit and codec options for comparing `num_chunks` chunks of `a`.
+fn compare_options<TA: ?Sized>(
+    a: &Array<TA>,
+    num_chunks: usize,
+    options: &CodecOptions,
+) -> Result<(usize, CodecOptions), ArrayError> {
+    let chunk_representation = a.chunk_array_representation(&vec![0; a.dimensionality()])?;
+    let codec_concurrency = a.recommended_codec_concurrency(&chunk_representation)?;
+    // Two chunks are held in memory per compared chunk
+    Ok(concurrency_chunks_and_codec(
+        options.concurrent_target(),
+        num_chunks,
+        options,
+        &codec_concurrency,
+        a.chunk_memory_usage(&chunk_representation)? * 2,
+    ))
+}
+
+/// Returns true if `a` and `b` are equal within `tolerance` or are both NaN.
+///
+/// Exact equality is checked first so that equal infinities are equal.
+#[allow(clippy::float_cmp)]
+fn floats_equal(a: f64, b: f64, tolerance: f64) -> bool {
+    a == b || (a.is_nan() && b.is_nan()) || (a - b).abs() <= tolerance
+}
+
+/// Return the number of differing elements of `a` and `b` and the index of the first differing element.
+fn compare_elements(
+    a: &[u8],
+    b: &[u8],
+    data_type: &DataType,
+    tolerance: f64,
+) -> (u64, Option<u64>) {
+    fn count(
+        a: &[u8],
+        b: &[u8],
+        element_size: usize,
+        equal: impl Fn(&[u8], &[u8]) -> bool,
+    ) -> (u64, Option<u64>) {
+        let mut differing_elements = 0;
+        let mut first_difference = None;
+        for (index, (a, b)) in
+            std::iter::zip(a.chunks_exact(element_size), b.chunks_exact(element_size)).enumerate()
+        {
+            if !equal(a, b) {
+                differing_elements += 1;
+                first_difference.get_or_insert(index as u64);
+            }
+        }
+        (differing_elements, first_difference)
+    }
+
+    macro_rules! float {
+        ( $t:ty, $bytes:expr ) => {
+            f64::from(<$t>::from_ne_bytes($bytes.try_into().unwrap()))
+        };
+    }
+    macro_rules! compare_float {
+        ( $t:ty ) => {
+            count(a, b, std::mem::size_of::<$t>(), |a, b| {
+                floats_equal(float!($t, a), float!($t, b), tolerance)
+            })
+        };
+    }
+    macro_rules! compare_complex {
+        ( $t:ty ) => {{
+            let size = std::mem::size_of::<$t>();
+            count(a, b, 2 * size, |a, b| {
+                floats_equal(float!($t, a[..size]), float!($t, b[..size]), tolerance)
+                    && floats_equal(float!($t, a[size..]), float!($t, b[size..]), tolerance)
+            })
+        }};
+    }
+
+    match data_type {
+        DataType::Float16 => compare_float!(half::f16),
+        DataType::BFloat16 => compare_float!(half::bf16),
+        DataType::Float32 => compare_float!(f32),
+        DataType::Float64 => compare_float!(f64),
+        DataType::Complex64 => compare_complex!(f32),
+        DataType::Complex128 => compare_complex!(f64),
+        _ => count(a, b, data_type.size(), |a, b| a == b),
+    }
+}
+
+/// Return the [`ChunkDiff`] of the decoded bytes `a` and `b` of `chunk_subset`, or [`None`] if they are equal.
+fn compare_chunk(
+    chunk_indices: ArrayIndices,
+    chunk_subset: &ArraySubset,
+    a: &[u8],
+    b: &[u8],
+    data_type: &DataType,
+    tolerance: f64,
+) -> Option<ChunkDiff> {
+    let (differing_elements, first_difference) = compare_elements(a, b, data_type, tolerance);
+    first_difference.map(|index| {
+        let first_difference = std::iter::zip(
+            unravel_index(index, chunk_subset.shape()),
+            chunk_subset.start(),
+        )
+        .map(|(index, start)| index + start)
+        .collect();
+        ChunkDiff {
+            chunk_indices,
+            differing_elements,
+            first_difference,
+        }
+    })
+}
+
+/// Compare the data of the `a` and `b` arrays.
+///
+/// The arrays are compared chunk-by-chunk following the chunk grid of `a`, so the arrays can have differing chunk grids and codecs.
+/// This can be used to validate that a migration, rechunking, or recompression of an array preserved its data.
+/// Elements of floating point data types can be compared with a tolerance, and the comparison can exit early once a differing chunk is found (see [`CompareOptions`]).
+/// Chunks are compared in parallel, subject to the concurrency target of the codec options.
+///
+/// Elements outside of the array shape in edge chunks are not compared, so arrays with different fill values can be equal.
+///
+/// # Errors
+/// Returns an [`ArrayError`] if
+///  - `a` and `b` have a different shape or data type,
+///  - there is a codec decoding error,
+///  - an underlying store error, or
+///  - the operation is cancelled.
+///
+/// # Panics
+/// Panics if a chunk of `a` exceeds [`usize::MAX`] bytes.
+pub fn compare<
+    TA: ?Sized + ReadableStorageTraits + 'static,
+    TB: ?Sized + ReadableStorageTraits + 'static,
+>(
+    a: &Array<TA>,
+    b: &Array<TB>,
+    options: &CompareOptions,
+) -> Result<DiffReport, ArrayError> {
+    let chunks = compare_validate(a, b)?;
+    let num_chunks = chunks.num_elements_usize();
+    if num_chunks == 0 {
+        return Ok(DiffReport::default());
+    }
+    let (chunk_concurrent_limit, codec_options) =
+        compare_options(a, num_chunks, &options.codec_options)?;
+    let progress = ProgressTracker::new(&codec_options, num_chunks);
+    progress.check_cancelled()?;
+
+    let chunks_compared = AtomicUsize::new(0);
+    let exited = AtomicBool::new(false);
+    let compare_chunk = |chunk_indices: ArrayIndices| {
+        if exited.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        progress.check_cancelled()?;
+        let chunk_subset = a.chunk_subset_bounded(&chunk_indices)?;
+        let bytes_a = a.retrieve_array_subset_opt(&chunk_subset, &codec_options)?;
+        let bytes_b = b.retrieve_array_subset_opt(&chunk_subset, &codec_options)?;
+        let chunk_diff = compare_chunk(
+            chunk_indices,
+            &chunk_subset,
+            &bytes_a,
+            &bytes_b,
+            a.data_type(),
+            options.tolerance,
+        );
+        if chunk_diff.is_some() && options.early_exit {
+            exited.store(true, Ordering::Relaxed);
+        }
+        chunks_compared.fetch_add(1, Ordering::Relaxed);
+        progress.chunk_completed(bytes_a.len() as u64);
+        Ok::<_, ArrayError>(chunk_diff)
+    };
+    let indices = chunks.indices();
+    let differing_chunks = iter_concurrent_limit!(
+        chunk_concurrent_limit,
+        indices.into_par_iter(),
+        map,
+        compare_chunk
+    )
+    .filter_map(Result::transpose)
+    .collect::<Result<Vec<_>, _>>()?;
+    Ok(DiffReport::new(
+        num_chunks,
+        chunks_compared.into_inner(),
+        differing_chunks,
+    ))
+}
+
+#[cfg(feature = "async")]
+/// Async variant of [`compare`].
+///
+/// # Errors
+/// See [`compare`].
+///
+/// # Panics
+/// See [`compare`].
+pub async fn async_compare<
+    TA: ?Sized + AsyncReadableStorageTraits + 'static,
+    TB: ?Sized + AsyncReadableStorageTraits + 'static,
+>(
+    a: &Array<TA>,
+    b: &Array<TB>,
+    options: &CompareOptions,
+) -> Result<DiffReport, ArrayError> {
+    use futures::StreamExt;
+
+    let chunks = compare_validate(a, b)?;
+    let num_chunks = chunks.num_elements_usize();
+    if num_chunks == 0 {
+        return Ok(DiffReport::default());
+    }
+    let (chunk_concurrent_limit, codec_options) =
+        compare_options(a, num_chunks, &options.codec_options)?;
+    let progress = ProgressTracker::new(&codec_options, num_chunks);
+    progress.check_cancelled()?;
+
+    let progress = &progress;
+    let codec_options = &codec_options;
+    let compare_chunk = |chunk_indices: ArrayIndices| async move {
+        progress.check_cancelled()?;
+        let chunk_subset = a.chunk_subset_bounded(&chunk_indices)?;
+        let bytes_a = a
+            .async_retrieve_array_subset_opt(&chunk_subset, codec_options)
+            .await?;
+        let bytes_b = b
+            .async_retrieve_array_subset_opt(&chunk_subset, codec_options)
+            .await?;
+        let chunk_diff = compare_chunk(
+            chunk_indices,
+            &chunk_subset,
+            &bytes_a,
+            &bytes_b,
+            a.data_type(),
+            options.tolerance,
+        );
+        progress.chunk_completed(bytes_a.len() as u64);
+        Ok::<_, ArrayError>(chunk_diff)
+    };
+    let indices = chunks.indices();
+    let mut stream = futures::stream::iter(indices.into_iter().map(compare_chunk))
+        .buffer_unordered(chunk_concurrent_limit.max(1));
+    let mut chunks_compared = 0;
+    let mut differing_chunks = Vec::new();
+    while let Some(item) = stream.next().await {
+        chunks_compared += 1;
+        if let Some(chunk_diff) = item? {
+            differing_chunks.push(chunk_diff);
+            if options.early_exit {
+                break;
+            }
+        }
+    }
+    Ok(DiffReport::new(
+        num_chunks,
+        chunks_compared,
+        differing_chunks,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{ArrayBuilder, FillValue},
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_compare() {
+        let store = Arc::new(MemoryStore::new());
+        let array_a = ArrayBuilder::new(
+            vec![7, 9],
+            DataType::Float32,
+            vec![3, 4].try_into().unwrap(),
+            FillValue::from(0.0f32),
+        )
+        .build(store.clone(), "/a")
+        .unwrap();
+        let array_b = ArrayBuilder::new(
+            vec![7, 9],
+            DataType::Float32,
+            vec![5, 2].try_into().unwrap(),
+            FillValue::from(f32::NAN),
+        )
+        .build(store.clone(), "/b")
+        .unwrap();
+        let subset = ArraySubset::new_with_shape(vec![7, 9]);
+        #[allow(clippy::cast_precision_loss)]
+        let mut elements: Vec<f32> = (0..63).map(|i| i as f32).collect();
+        elements[10] = f32::NAN;
+        array_a
+            .store_array_subset_elements(&subset, elements.clone())
+            .unwrap();
+        array_b
+            .store_array_subset_elements(&subset, elements.clone())
+            .unwrap();
+
+        let report = compare(&array_a, &array_b, &CompareOptions::new()).unwrap();
+        assert_eq!(report.chunks_compared(), 9);
+        assert!(report.is_complete());
+        assert!(report.is_equal());
+
+        // Differences within and outside of the tolerance
+        elements[1] += 0.25;
+        elements[62] += 1.0;
+        array_b
+            .store_array_subset_elements(&subset, elements)
+            .unwrap();
+        let report = compare(&array_a, &array_b, &CompareOptions::new()).unwrap();
+        assert!(report.is_complete());
+        assert_eq!(report.differing_elements(), 2);
+        assert_eq!(
+            report.differing_chunks(),
+            &[
+                ChunkDiff {
+                    chunk_indices: vec![0, 0],
+                    differing_elements: 1,
+                    first_difference: vec![0, 1],
+                },
+                ChunkDiff {
+                    chunk_indices: vec![2, 2],
+                    differing_elements: 1,
+                    first_difference: vec![6, 8],
+                }
+            ]
+        );
+        let report = compare(&array_a, &array_b, &CompareOptions::new().tolerance(0.5)).unwrap();
+        assert_eq!(report.differing_elements(), 1);
+        assert_eq!(report.differing_chunks()[0].chunk_indices(), &[2, 2]);
+
+        // Early exit
+        let options = CompareOptions::new()
+            .codec_options(CodecOptions::builder().concurrent_target(1).build())
+            .early_exit(true);
+        let report = compare(&array_a, &array_b, &options).unwrap();
+        assert!(!report.is_equal());
+        assert!(!report.is_complete());
+
+        // Incompatible arrays
+        let array_c = ArrayBuilder::new(
+            vec![7, 9],
+            DataType::Float64,
+            vec![3, 4].try_into().unwrap(),
+            FillValue::from(0.0f64),
+        )
+        .build(store, "/c")
+        .unwrap();
+        assert!(matches!(
+            compare(&array_a, &array_c, &CompareOptions::new()),
+            Err(ArrayError::IncompatibleDataType(_, _))
+        ));
+    }
+}
@@ -0,0 +1,271 @@
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+use crate::{
+    array_subset::ArraySubset,
+    storage::{ReadableStorageTraits, WritableStorageTraits},
+};
+
+#[cfg(feature = "async")]
+use crate::storage::{AsyncReadableStorageTraits, AsyncWritableStorageTraits};
+
+use super::{
+    codec::CodecOptions, concurrency::concurrency_chunks_and_codec, Array, ArrayError,
+    ArrayIndices, ArrayView,
+};
+
+/// Validate that `src` can be copied to `dst` and return the chunks of `dst`.
+fn copy_array_validate<TSrc: ?Sized, TDst: ?Sized>(
+    src: &Array<TSrc>,
+    dst: &Array<TDst>,
+) -> Result<ArraySubset, ArrayError> {
+    if src.shape() != dst.shape() {
+        return Err(ArrayError::IncompatibleShape(
+            src.shape().to_vec(),
+            dst.shape().to_vec(),
+        ));
+    }
+    if src.data_type() != dst.data_type() {
+        return Err(ArrayError::IncompatibleDataType(
+            src.data_type().clone(),
+            dst.data_type().clone(),
+        ));
+    }
+    let chunk_grid_shape = dst
+        .chunk_grid_shape()
+        .ok_or_else(|| ArrayError::UnknownChunkGridShape(dst.shape().to_vec()))?;
+    Ok(ArraySubset::new_with_shape(chunk_grid_shape))
+}
+
+/// Prepare a destination chunk buffer for the chunk of `dst` at `chunk_indices`.
+///
+/// Returns the chunk subset, the part of the chunk subset within the array bounds, and a buffer initialised with the fill value of `dst`.
+fn copy_array_chunk_buffer<TDst: ?Sized>(
+    dst: &Array<TDst>,
+    chunk_indices: &[u64],
+) -> Result<(ArraySubset, ArraySubset, Vec<u8>), ArrayError> {
+    let chunk_subset = dst.chunk_subset(chunk_indices)?;
+    let chunk_subset_bounded = unsafe { chunk_subset.bound_unchecked(dst.shape()) };
+    let chunk_bytes = dst
+        .fill_value()
+        .as_ne_bytes()
+        .repeat(chunk_subset.num_elements_usize());
+    Ok((chunk_subset, chunk_subset_bounded, chunk_bytes))
+}
+
+/// Copy the data of the `src` array to the `dst` array.
+///
+/// Data is retrieved from `src` and stored in `dst` chunk-by-chunk following the chunk grid of `dst`, so the arrays can have differing chunk grids and codecs.
+/// This can be used to rechunk or recompress an array.
+/// Chunks are processed in parallel, subject to the concurrency target of `options`.
+///
+/// The metadata of `dst` is not stored, use [`Array::store_metadata`] to store it.
+/// Elements of edge chunks of `dst` outside of the array shape are set to the fill value of `dst`.
+/// As with [`Array::store_chunk`], chunks of `dst` composed entirely of the fill value are not written to the store.
+///
+/// # Errors
+/// Returns an [`ArrayError`] if
+///  - `src` and `dst` have a different shape or data type,
+///  - there is a codec encoding or decoding error, or
+///  - an underlying store error.
+///
+/// # Panics
+/// Panics if a chunk of `dst` exceeds [`usize::MAX`] bytes.
+pub fn copy_array<
+    TSrc: ?Sized + ReadableStorageTraits + 'static,
+    TDst: ?Sized + WritableStorageTraits + 'static,
+>(
+    src: &Array<TSrc>,
+    dst: &Array<TDst>,
+    options: &CodecOptions,
+) -> Result<(), ArrayError> {
+    let chunks = copy_array_validate(src, dst)?;
+    let num_chunks = chunks.num_elements_usize();
+    if num_chunks == 0 {
+        return Ok(());
+    }
+
+    // Calculate chunk/codec concurrency
+    let chunk_representation = dst.chunk_array_representation(&vec![0; dst.dimensionality()])?;
+    let codec_concurrency = dst.recommended_codec_concurrency(&chunk_representation)?;
+    let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+        options.concurrent_target(),
+        num_chunks,
+        options,
+        &codec_concurrency,
+        dst.chunk_memory_usage(&chunk_representation)?,
+    );
+
+    let copy_chunk = |chunk_indices: ArrayIndices| -> Result<(), ArrayError> {
+        let (chunk_subset, chunk_subset_bounded, mut chunk_bytes) =
+            copy_array_chunk_buffer(dst, &chunk_indices)?;
+        if chunk_subset == chunk_subset_bounded {
+            chunk_bytes = src.retrieve_array_subset_opt(&chunk_subset, &options)?;
+        } else {
+            let chunk_shape = chunk_subset.shape().to_vec();
+            let array_view = ArrayView::new(&mut chunk_bytes, &chunk_shape, unsafe {
+                chunk_subset_bounded.relative_to_unchecked(chunk_subset.start())
+            })
+            .unwrap(); // safe, the bounded chunk subset is within the chunk subset
+            src.retrieve_array_subset_into_array_view_opt(
+                &chunk_subset_bounded,
+                &array_view,
+                &options,
+            )?;
+        }
+        dst.store_chunk_opt(&chunk_indices, chunk_bytes, &options)
+    };
+    let indices = chunks.indices();
+    iter_concurrent_limit!(
+        chunk_concurrent_limit,
+        indices.into_par_iter(),
+        try_for_each,
+        copy_chunk
+    )
+}
+
+#[cfg(feature = "async")]
+/// Async variant of [`copy_array`].
+///
+/// # Errors
+/// See [`copy_array`].
+///
+/// # Panics
+/// See [`copy_array`].
+pub async fn async_copy_array<
+    TSrc: ?Sized + AsyncReadableStorageTraits + 'static,
+    TDst: ?Sized + AsyncWritableStorageTraits + 'static,
+>(
+    src: &Array<TSrc>,
+    dst: &Array<TDst>,
+    options: &CodecOptions,
+) -> Result<(), ArrayError> {
+    use futures::StreamExt;
+
+    let chunks = copy_array_validate(src, dst)?;
+    let num_chunks = chunks.num_elements_usize();
+    if num_chunks == 0 {
+        return Ok(());
+    }
+
+    // Calculate chunk/codec concurrency
+    let chunk_representation = dst.chunk_array_representation(&vec![0; dst.dimensionality()])?;
+    let codec_concurrency = dst.recommended_codec_concurrency(&chunk_representation)?;
+    let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+        options.concurrent_target(),
+        num_chunks,
+        options,
+        &codec_concurrency,
+        dst.chunk_memory_usage(&chunk_representation)?,
+    );
+
+    let copy_chunk = |chunk_indices: ArrayIndices| {
+        let options = options.clone();
+        async move {
+            let (chunk_subset, chunk_subset_bounded, mut chunk_bytes) =
+                copy_array_chunk_buffer(dst, &chunk_indices)?;
+            if chunk_subset == chunk_subset_bounded {
+                chunk_bytes = src
+                    .async_retrieve_array_subset_opt(&chunk_subset, &options)
+                    .await?;
+            } else {
+                let chunk_shape = chunk_subset.shape().to_vec();
+                let array_view = ArrayView::new(&mut chunk_bytes, &chunk_shape, unsafe {
+                    chunk_subset_bounded.relative_to_unchecked(chunk_subset.start())
+                })
+                .unwrap(); // safe, the bounded chunk subset is within the chunk subset
+                src.async_retrieve_array_subset_into_array_view_opt(
+                    &chunk_subset_bounded,
+                    &array_view,
+                    &options,
+                )
+                .await?;
+            }
+            dst.async_store_chunk_opt(&chunk_indices, chunk_bytes, &options)
+                .await
+        }
+    };
+    let indices = chunks.indices();
+    let futures = indices.into_iter().map(copy_chunk);
+    let mut stream = futures::stream::iter(futures).buffer_unordered(chunk_concurrent_limit);
+    while let Some(item) = stream.next().await {
+        item?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_copy() {
+        let store_src = Arc::new(MemoryStore::new());
+        let array_src = ArrayBuilder::new(
+            vec![7, 9],
+            DataType::UInt16,
+            vec![3, 4].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store_src, "/src")
+        .unwrap();
+        let elements: Vec<u16> = (0..63).collect();
+        array_src
+            .store_array_subset_elements(&ArraySubset::new_with_shape(vec![7, 9]), elements.clone())
+            .unwrap();
+
+        let store_dst = Arc::new(MemoryStore::new());
+        let array_dst = ArrayBuilder::new(
+            vec![7, 9],
+            DataType::UInt16,
+            vec![5, 2].try_into().unwrap(),
+            FillValue::from(u16::MAX),
+        )
+        .bytes_to_bytes_codecs(vec![
+            #[cfg(feature = "gzip")]
+            Box::new(crate::array::codec::GzipCodec::new(5).unwrap()),
+        ])
+        .build(store_dst, "/dst")
+        .unwrap();
+        copy_array(&array_src, &array_dst, &CodecOptions::default()).unwrap();
+        assert_eq!(
+            array_dst
+                .retrieve_array_subset_elements::<u16>(&ArraySubset::new_with_shape(vec![7, 9]))
+                .unwrap(),
+            elements
+        );
+        // Elements of the edge chunk outside of the array are the fill value of the destination
+        assert_eq!(
+            array_dst.retrieve_chunk_elements::<u16>(&[1, 4]).unwrap(),
+            vec![
+                53,
+                u16::MAX,
+                62,
+                u16::MAX,
+                u16::MAX,
+                u16::MAX,
+                u16::MAX,
+                u16::MAX,
+                u16::MAX,
+                u16::MAX
+            ]
+        );
+
+        let array_incompatible = ArrayBuilder::new(
+            vec![7, 8],
+            DataType::UInt16,
+            vec![5, 2].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(Arc::new(MemoryStore::new()), "/")
+        .unwrap();
+        assert!(copy_array(&array_src, &array_incompatible, &CodecOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use std::iter::FusedIterator;
+
+use crate::{array_subset::ArraySubset, storage::ReadableStorageTraits};
+
+use super::{codec::CodecOptions, unravel_index, Array, ArrayError, ArrayIndices, Element};
+
+/// The decoded elements of the intersection of an array subset with a chunk.
+struct ChunkElements<T> {
+    subset: ArraySubset,
+    elements: std::iter::Enumerate<std::vec::IntoIter<T>>,
+}
+
+/// An iterator over the elements of an array subset.
+///
+/// The iterator item is a [`Result`] of the array indices and the value of an element.
+/// Elements are visited chunk-by-chunk, with the chunks intersecting the array subset visited in C order and the elements within each chunk visited in C order.
+/// Chunks are retrieved and decoded lazily as the iterator is advanced, so only the elements of one chunk are held in memory at a time.
+///
+/// See [`Array::iter_array_subset_elements`].
+pub struct ArraySubsetElementsIter<'a, TStorage: ?Sized, T> {
+    array: &'a Array<TStorage>,
+    array_subset: ArraySubset,
+    chunks: ArraySubset,
+    options: CodecOptions,
+    chunk_index: u64,
+    chunk_elements: Option<ChunkElements<T>>,
+}
+
+impl<'a, TStorage: ?Sized + ReadableStorageTraits + 'static, T: Element>
+    ArraySubsetElementsIter<'a, TStorage, T>
+{
+    /// Create a new iterator over the elements of `array_subset` of `array`.
+    ///
+    /// `chunks` is the subset of the chunk grid intersecting `array_subset`, and must be within its bounds.
+    #[must_use]
+    pub fn new(
+        array: &'a Array<TStorage>,
+        array_subset: ArraySubset,
+        chunks: ArraySubset,
+        options: CodecOptions,
+    ) -> Self {
+        Self {
+            array,
+            array_subset,
+            chunks,
+            options,
+            chunk_index: 0,
+            chunk_elements: None,
+        }
+    }
+
+    /// Return the array subset.
+    #[must_use]
+    pub const fn array_subset(&self) -> &ArraySubset {
+        &self.array_subset
+    }
+
+    fn retrieve(&self, index: u64) -> Result<ChunkElements<T>, ArrayError> {
+        let mut chunk_indices = unravel_index(index, self.chunks.shape());
+        std::iter::zip(chunk_indices.iter_mut(), self.chunks.start())
+            .for_each(|(chunk_index, start)| *chunk_index += start);
+        let chunk_subset = self.array.chunk_subset(&chunk_indices)?;
+        let subset = chunk_subset.overlap(&self.array_subset)?;
+        let elements = self.array.retrieve_chunk_subset_elements_opt::<T>(
+            &chunk_indices,
+            &subset.relative_to(chunk_subset.start())?,
+            &self.options,
+        )?;
+        Ok(ChunkElements {
+            subset,
+            elements: elements.into_iter().enumerate(),
+        })
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static, T: Element> Iterator
+    for ArraySubsetElementsIter<'_, TStorage, T>
+{
+    type Item = Result<(ArrayIndices, T), ArrayError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(chunk_elements) = &mut self.chunk_elements {
+                if let Some((index, element)) = chunk_elements.elements.next() {
+                    let mut indices = unravel_index(index as u64, chunk_elements.subset.shape());
+                    std::iter::zip(indices.iter_mut(), chunk_elements.subset.start())
+                        .for_each(|(index, start)| *index += start);
+                    return Some(Ok((indices, element)));
+                }
+                self.chunk_elements = None;
+            }
+
+            if self.chunk_index >= self.chunks.num_elements() {
+                return None;
+            }
+            let chunk_elements = self.retrieve(self.chunk_index);
+            self.chunk_index += 1;
+            match chunk_elements {
+                Ok(chunk_elements) => self.chunk_elements = Some(chunk_elements),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static, T: Element> FusedIterator
+    for ArraySubsetElementsIter<'_, TStorage, T>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_subset_elements_iter() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::UInt8,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/")
+        .unwrap();
+        let elements: Vec<u8> = (0..24).collect();
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_shape(vec![4, 6]), elements)
+            .unwrap();
+
+        let iter = array
+            .iter_array_subset_elements::<u8>(&ArraySubset::new_with_ranges(&[1..3, 2..4]))
+            .unwrap();
+        assert_eq!(
+            iter.array_subset(),
+            &ArraySubset::new_with_ranges(&[1..3, 2..4])
+        );
+        let elements: Vec<_> = iter.collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            elements,
+            vec![
+                (vec![1, 2], 8),
+                (vec![1, 3], 9),
+                (vec![2, 2], 14),
+                (vec![2, 3], 15),
+            ]
+        );
+
+        let multiples_of_ten = array
+            .iter_array_subset_elements::<u8>(&ArraySubset::new_with_shape(vec![4, 6]))
+            .unwrap()
+            .filter(|item| {
+                item.as_ref()
+                    .map_or(true, |(_, element)| *element % 10 == 0)
+            })
+            .map(|item| item.map(|(indices, _)| indices))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(multiples_of_ten, vec![vec![0, 0], vec![1, 4], vec![3, 2]]);
+
+        assert!(array
+            .iter_array_subset_elements::<u16>(&ArraySubset::new_with_shape(vec![4, 6]))
+            .unwrap()
+            .next()
+            .unwrap()
+            .is_err());
+        assert!(array
+            .iter_array_subset_elements::<u8>(&ArraySubset::new_with_ranges(&[0..5, 0..6]))
+            .is_err());
+    }
+}
@@ -3,7 +3,7 @@ use thiserror::Error;
 use crate::{
     array_subset::{ArraySubset, IncompatibleDimensionalityError},
     metadata::UnsupportedAdditionalFieldError,
-    node::NodePathError,
+    node::{NodeNameError, NodePathError},
     plugin::PluginCreateError,
     storage::StorageError,
 };
@@ -14,7 +14,7 @@ use super::{
         IncompatibleFillValueError, IncompatibleFillValueErrorMetadataError,
         UnsupportedDataTypeError,
     },
-    ArrayIndices, ArrayShape,
+    ArrayIndices, ArrayMetadataV2ToV3ConversionError, ArrayShape, DataType,
 };
 
 /// An array creation error.
@@ -29,6 +29,9 @@ pub enum ArrayCreateError {
     /// An invalid node path
     #[error(transparent)]
     NodePathError(#[from] NodePathError),
+    /// An invalid node name.
+    #[error(transparent)]
+    NodeNameError(#[from] NodeNameError),
     /// Unsupported additional field.
     #[error(transparent)]
     UnsupportedAdditionalFieldError(#[from] UnsupportedAdditionalFieldError),
@@ -59,12 +62,22 @@ pub enum ArrayCreateError {
     /// The number of dimension names does not match the array dimensionality.
     #[error("the number of dimension names {0} does not match array dimensionality {1}")]
     InvalidDimensionNames(usize, usize),
+    /// The codecs are incompatible with the chunk shape (e.g. a `sharding` inner chunk shape that does not evenly divide the chunk shape).
+    #[error("codecs are incompatible with chunk shape {0:?}: {1}")]
+    IncompatibleCodecs(ArrayShape, CodecError),
     /// Storage error.
     #[error(transparent)]
     StorageError(#[from] StorageError),
     /// Missing metadata.
     #[error("array metadata is missing")]
     MissingMetadata,
+    /// Error converting Zarr V2 array metadata to Zarr V3 array metadata.
+    #[error(transparent)]
+    ArrayMetadataV2ToV3ConversionError(#[from] ArrayMetadataV2ToV3ConversionError),
+    /// Error converting N5 dataset metadata to Zarr V3 array metadata.
+    #[cfg(feature = "n5")]
+    #[error(transparent)]
+    ArrayMetadataN5ToV3ConversionError(#[from] super::ArrayMetadataN5ToV3ConversionError),
 }
 
 /// Array errors.
@@ -82,12 +95,24 @@ pub enum ArrayError {
     /// Invalid chunk grid indices.
     #[error("invalid chunk grid indices: {_0:?}")]
     InvalidChunkGridIndicesError(Vec<u64>),
+    /// The chunk grid shape cannot be determined for the array shape.
+    #[error("the chunk grid shape cannot be determined for array shape {_0:?}")]
+    UnknownChunkGridShape(ArrayShape),
     /// Incompatible dimensionality.
     #[error(transparent)]
     IncompatibleDimensionalityError(#[from] IncompatibleDimensionalityError),
     /// Incompatible array subset.
     #[error("array subset {_0} is not compatible with array shape {_1:?}")]
     InvalidArraySubset(ArraySubset, ArrayShape),
+    /// Invalid array indices.
+    #[error("array indices {_0:?} are not within array shape {_1:?}")]
+    InvalidArrayIndices(ArrayIndices, ArrayShape),
+    /// An invalid array origin.
+    #[error("invalid array origin: {_0}")]
+    InvalidOrigin(String),
+    /// A world subset cannot be translated to an array subset.
+    #[error("world subset {_0:?} is not compatible with array origin {_1:?} and shape {_2:?}")]
+    InvalidWorldSubset(Vec<std::ops::Range<i64>>, Vec<i64>, ArrayShape),
     /// Incompatible chunk subset.
     #[error("chunk subset {_0} is not compatible with chunk {_1:?} with shape {_2:?}")]
     InvalidChunkSubset(ArraySubset, ArrayIndices, ArrayShape),
@@ -106,4 +131,28 @@ pub enum ArrayError {
     /// Invalid data shape.
     #[error("data has shape {_0:?}, expected {_1:?}")]
     InvalidDataShape(Vec<usize>, Vec<usize>),
+    /// An array subset is not aligned with chunk boundaries.
+    #[error("array subset {_0} is not aligned with chunk boundaries")]
+    UnalignedArraySubset(ArraySubset),
+    /// Invalid axis.
+    #[error("invalid axis {_0} for an array with dimensionality {_1}")]
+    InvalidAxis(usize, usize),
+    /// A ragged chunk cannot be appended along an axis.
+    #[error("cannot append a ragged chunk along axis {_0}: {_1}")]
+    InvalidRaggedAppend(usize, String),
+    /// Incompatible array shapes.
+    #[error("array shape {_0:?} is incompatible with array shape {_1:?}")]
+    IncompatibleShape(ArrayShape, ArrayShape),
+    /// Incompatible data types.
+    #[error("data type {_0} is incompatible with data type {_1}")]
+    IncompatibleDataType(DataType, DataType),
+    /// The bytes of an element are not a valid value of the element type.
+    #[error("the bytes of an element are not a valid {_0}")]
+    InvalidElementValue(String),
+    /// Data has null elements, which cannot be stored.
+    #[error("data has {_0} null elements, which cannot be stored")]
+    NullElements(usize),
+    /// The operation was cancelled with a [`CancellationToken`](crate::array::codec::CancellationToken).
+    #[error("the operation was cancelled")]
+    Cancelled,
 }
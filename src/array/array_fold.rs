@@ -0,0 +1,222 @@
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+use crate::{array_subset::ArraySubset, storage::ReadableStorageTraits};
+
+use super::{
+    codec::{options::ProgressTracker, CodecOptions},
+    concurrency::concurrency_chunks_and_codec,
+    Array, ArrayError, ArrayIndices, Element,
+};
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
+    /// Fold the elements of `array_subset` into an accumulator with `fold`, starting from `init`.
+    ///
+    /// Chunks are retrieved and folded one at a time in C order, so only the elements of one chunk are held in memory at a time.
+    /// The elements of each chunk are visited in C order, so elements are not visited in the C order of `array_subset` if it spans multiple chunks.
+    /// This suits reductions such as the sum, minimum, maximum, or count of non fill value elements of an array too large to retrieve in full.
+    ///
+    /// Use [`par_fold_array_subset`](Array::par_fold_array_subset) to fold chunks in parallel.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - `array_subset` is not within the bounds of the array,
+    ///  - the size of `T` does not match the data type size,
+    ///  - a retrieved element is not a valid `T`,
+    ///  - there is a codec decoding error, or
+    ///  - an underlying store error.
+    pub fn fold_array_subset<T: Element, Acc>(
+        &self,
+        array_subset: &ArraySubset,
+        init: Acc,
+        fold: impl FnMut(Acc, &T) -> Acc,
+    ) -> Result<Acc, ArrayError> {
+        self.fold_array_subset_opt(array_subset, init, fold, &CodecOptions::default())
+    }
+
+    /// Fold the elements of `array_subset` in parallel.
+    ///
+    /// The elements of each chunk are folded with `fold` into an accumulator starting from `identity()`, and the accumulators of the chunks are combined with `reduce`.
+    /// Chunks are folded concurrently subject to the concurrency target of the codec options, so the memory usage is bounded by the number of concurrently retrieved chunks.
+    /// The order in which chunk accumulators are combined is unspecified, so `reduce` should be associative.
+    ///
+    /// # Errors
+    /// See [`fold_array_subset`](Array::fold_array_subset).
+    pub fn par_fold_array_subset<T: Element, Acc: Send>(
+        &self,
+        array_subset: &ArraySubset,
+        identity: impl Fn() -> Acc + Send + Sync,
+        fold: impl Fn(Acc, &T) -> Acc + Send + Sync,
+        reduce: impl Fn(Acc, Acc) -> Acc + Send + Sync,
+    ) -> Result<Acc, ArrayError> {
+        self.par_fold_array_subset_opt(
+            array_subset,
+            identity,
+            fold,
+            reduce,
+            &CodecOptions::default(),
+        )
+    }
+
+    /// Return the elements of the chunk at `chunk_indices` within `array_subset`.
+    fn fold_retrieve_chunk_elements<T: Element>(
+        &self,
+        chunk_indices: &[u64],
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<Vec<T>, ArrayError> {
+        let chunk_subset = self.chunk_subset(chunk_indices)?;
+        let overlap = unsafe { chunk_subset.overlap_unchecked(array_subset) };
+        let array_subset_in_chunk_subset =
+            unsafe { overlap.relative_to_unchecked(chunk_subset.start()) };
+        self.retrieve_chunk_subset_elements_opt(
+            chunk_indices,
+            &array_subset_in_chunk_subset,
+            options,
+        )
+    }
+
+    /// Explicit options version of [`fold_array_subset`](Array::fold_array_subset).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn fold_array_subset_opt<T: Element, Acc>(
+        &self,
+        array_subset: &ArraySubset,
+        init: Acc,
+        mut fold: impl FnMut(Acc, &T) -> Acc,
+        options: &CodecOptions,
+    ) -> Result<Acc, ArrayError> {
+        let chunks = self.chunks_in_array_subset_inbounds(array_subset)?;
+        let progress = ProgressTracker::new(options, chunks.num_elements_usize());
+        let mut acc = init;
+        for chunk_indices in &chunks.indices() {
+            progress.check_cancelled()?;
+            let elements =
+                self.fold_retrieve_chunk_elements::<T>(&chunk_indices, array_subset, options)?;
+            acc = elements.iter().fold(acc, &mut fold);
+            progress.chunk_completed(std::mem::size_of_val(elements.as_slice()) as u64);
+        }
+        Ok(acc)
+    }
+
+    /// Explicit options version of [`par_fold_array_subset`](Array::par_fold_array_subset).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn par_fold_array_subset_opt<T: Element, Acc: Send>(
+        &self,
+        array_subset: &ArraySubset,
+        identity: impl Fn() -> Acc + Send + Sync,
+        fold: impl Fn(Acc, &T) -> Acc + Send + Sync,
+        reduce: impl Fn(Acc, Acc) -> Acc + Send + Sync,
+        options: &CodecOptions,
+    ) -> Result<Acc, ArrayError> {
+        let chunks = self.chunks_in_array_subset_inbounds(array_subset)?;
+        let num_chunks = chunks.num_elements_usize();
+        let progress = ProgressTracker::new(options, num_chunks);
+        progress.check_cancelled()?;
+
+        // Calculate chunk/codec concurrency
+        let chunk_representation =
+            self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+        let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+        let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+            options.concurrent_target(),
+            num_chunks,
+            options,
+            &codec_concurrency,
+            self.chunk_memory_usage(&chunk_representation)?,
+        );
+
+        let fold_chunk = |chunk_indices: ArrayIndices| {
+            progress.check_cancelled()?;
+            let elements =
+                self.fold_retrieve_chunk_elements::<T>(&chunk_indices, array_subset, &options)?;
+            let acc = elements.iter().fold(identity(), &fold);
+            progress.chunk_completed(std::mem::size_of_val(elements.as_slice()) as u64);
+            Ok::<_, ArrayError>(acc)
+        };
+        let indices = chunks.indices();
+        iter_concurrent_limit!(
+            chunk_concurrent_limit,
+            indices.into_par_iter(),
+            map,
+            fold_chunk
+        )
+        .try_reduce(&identity, |a, b| Ok(reduce(a, b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_fold_array_subset() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::Int16,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0i16),
+        )
+        .build(store, "/array")
+        .unwrap();
+        array
+            .store_array_subset_elements(
+                &ArraySubset::new_with_ranges(&[0..2, 0..6]),
+                (-6..6).collect::<Vec<i16>>(),
+            )
+            .unwrap();
+
+        let subset = ArraySubset::new_with_ranges(&[1..4, 1..5]);
+        let sum = array
+            .fold_array_subset(&subset, 0i64, |acc, element: &i16| {
+                acc + i64::from(*element)
+            })
+            .unwrap();
+        assert_eq!(sum, 1 + 2 + 3 + 4);
+        let (min, max) = array
+            .fold_array_subset(
+                &subset,
+                (i16::MAX, i16::MIN),
+                |(min, max), element: &i16| (min.min(*element), max.max(*element)),
+            )
+            .unwrap();
+        assert_eq!((min, max), (0, 4));
+
+        let count_non_fill = array
+            .par_fold_array_subset(
+                &ArraySubset::new_with_shape(vec![4, 6]),
+                || 0usize,
+                |acc, element: &i16| acc + usize::from(*element != 0),
+                |a, b| a + b,
+            )
+            .unwrap();
+        assert_eq!(count_non_fill, 11);
+        let sum = array
+            .par_fold_array_subset(
+                &subset,
+                || 0i64,
+                |acc, element: &i16| acc + i64::from(*element),
+                |a, b| a + b,
+            )
+            .unwrap();
+        assert_eq!(sum, 10);
+
+        assert!(array
+            .fold_array_subset(
+                &ArraySubset::new_with_ranges(&[0..5, 0..6]),
+                0,
+                |acc, _: &i16| acc
+            )
+            .is_err());
+        assert!(array
+            .fold_array_subset(&subset, 0, |acc, _: &u8| acc)
+            .is_err());
+    }
+}
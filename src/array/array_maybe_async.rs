@@ -0,0 +1,192 @@
+//! [`Array`] methods shared by the sync and async APIs.
+//!
+//! These methods are written once as `async fn`s against the [`MaybeAsyncReadableStorageTraits`] and [`MaybeAsyncWritableStorageTraits`] storage abstraction.
+//! Sync methods run them with [`SyncStorage`] and [`ready`](crate::storage::ready), and async methods await them with [`AsyncStorage`](crate::storage::AsyncStorage).
+//! New functionality which only differs between the sync and async APIs in how it accesses storage should be implemented here.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+use crate::storage::{
+    data_key, statistics_key, MaybeAsyncReadableStorageTraits, MaybeAsyncWritableStorageTraits,
+    ReadableStorageTraits, StorageError, StorageHandle, SyncStorage, WritableStorageTraits,
+};
+
+#[cfg(feature = "async")]
+use crate::storage::{AsyncReadableStorageTraits, AsyncStorage, AsyncWritableStorageTraits};
+
+use super::{
+    array_statistics::{chunk_statistics_from_json, chunk_statistics_to_json},
+    codec::CodecOptions,
+    Array, ArrayError, ChunkStatistics, MaybeBytes,
+};
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
+    /// Return the storage of the array through its storage transformers for reading with the shared methods.
+    pub(super) fn sync_readable_storage(&self) -> SyncStorage<dyn ReadableStorageTraits> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        SyncStorage(
+            self.storage_transformers()
+                .create_readable_transformer(storage_handle),
+        )
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
+    /// Return the storage of the array through its storage transformers for writing with the shared methods.
+    pub(super) fn sync_writable_storage(&self) -> SyncStorage<dyn WritableStorageTraits> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        SyncStorage(
+            self.storage_transformers()
+                .create_writable_transformer(storage_handle),
+        )
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
+    /// Return the storage of the array through its storage transformers for reading with the shared methods.
+    pub(super) fn async_readable_storage(&self) -> AsyncStorage<dyn AsyncReadableStorageTraits> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        AsyncStorage(
+            self.storage_transformers()
+                .create_async_readable_transformer(storage_handle),
+        )
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
+    /// Return the storage of the array through its storage transformers for writing with the shared methods.
+    pub(super) fn async_writable_storage(&self) -> AsyncStorage<dyn AsyncWritableStorageTraits> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        AsyncStorage(
+            self.storage_transformers()
+                .create_async_writable_transformer(storage_handle),
+        )
+    }
+}
+
+impl<TStorage: ?Sized> Array<TStorage> {
+    /// Read the encoded chunk at `chunk_indices` in `storage` if it exists.
+    pub(super) async fn maybe_async_retrieve_encoded_chunk<
+        TMaybeAsyncStorage: MaybeAsyncReadableStorageTraits,
+    >(
+        &self,
+        storage: &TMaybeAsyncStorage,
+        chunk_indices: &[u64],
+    ) -> Result<MaybeBytes, ArrayError> {
+        self.validate_chunk_indices(chunk_indices)?;
+        let key = data_key(self.path(), chunk_indices, self.chunk_key_encoding());
+        Ok(storage.get(&key).await?)
+    }
+
+    /// Read and decode the chunk at `chunk_indices` in `storage` if it exists.
+    pub(super) async fn maybe_async_retrieve_chunk_if_exists_opt<
+        TMaybeAsyncStorage: MaybeAsyncReadableStorageTraits,
+    >(
+        &self,
+        storage: &TMaybeAsyncStorage,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<u8>>, ArrayError> {
+        let chunk_encoded = self
+            .maybe_async_retrieve_encoded_chunk(storage, chunk_indices)
+            .await?;
+        chunk_encoded
+            .map(|chunk_encoded| self.decode_chunk(chunk_encoded, chunk_indices, options))
+            .transpose()
+    }
+
+    /// Read and decode the chunk at `chunk_indices` in `storage`, or return the fill value chunk if it does not exist.
+    pub(super) async fn maybe_async_retrieve_chunk_opt<
+        TMaybeAsyncStorage: MaybeAsyncReadableStorageTraits,
+    >(
+        &self,
+        storage: &TMaybeAsyncStorage,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<Vec<u8>, ArrayError> {
+        let chunk = self
+            .maybe_async_retrieve_chunk_if_exists_opt(storage, chunk_indices, options)
+            .await?;
+        if let Some(chunk) = chunk {
+            Ok(chunk)
+        } else {
+            self.fill_value_chunk(chunk_indices)
+        }
+    }
+
+    /// Return the statistics of the chunk at `chunk_indices` in `storage`.
+    pub(super) async fn maybe_async_chunk_statistics<
+        TMaybeAsyncStorage: MaybeAsyncReadableStorageTraits,
+    >(
+        &self,
+        storage: &TMaybeAsyncStorage,
+        chunk_indices: &[u64],
+    ) -> Result<Option<ChunkStatistics>, ArrayError> {
+        let key = statistics_key(self.path(), chunk_indices, self.chunk_key_encoding());
+        storage
+            .get(&key)
+            .await?
+            .map(|bytes| chunk_statistics_from_json(&key, &bytes))
+            .transpose()
+    }
+
+    /// Store the statistics of the chunk at `chunk_indices` in `storage`, or erase them if [`None`].
+    ///
+    /// This is a no-op if statistics are not computed.
+    pub(super) async fn maybe_async_store_chunk_statistics<
+        TMaybeAsyncStorage: MaybeAsyncWritableStorageTraits,
+    >(
+        &self,
+        storage: &TMaybeAsyncStorage,
+        chunk_indices: &[u64],
+        chunk_statistics: Option<&ChunkStatistics>,
+    ) -> Result<(), StorageError> {
+        if !self.compute_statistics() {
+            return Ok(());
+        }
+        let key = statistics_key(self.path(), chunk_indices, self.chunk_key_encoding());
+        if let Some(chunk_statistics) = chunk_statistics {
+            storage
+                .set(&key, chunk_statistics_to_json(chunk_statistics).into())
+                .await
+        } else {
+            storage.erase(&key).await
+        }
+    }
+
+    /// Store the encoded chunk `encoded_chunk` at `chunk_indices` in `storage` and its statistics, or erase its statistics if [`None`].
+    pub(super) async fn maybe_async_store_encoded_chunk<
+        TMaybeAsyncStorage: MaybeAsyncWritableStorageTraits,
+    >(
+        &self,
+        storage: &TMaybeAsyncStorage,
+        chunk_indices: &[u64],
+        encoded_chunk: Bytes,
+        chunk_statistics: Option<&ChunkStatistics>,
+    ) -> Result<(), ArrayError> {
+        self.validate_chunk_indices(chunk_indices)?;
+        let key = data_key(self.path(), chunk_indices, self.chunk_key_encoding());
+        storage.set(&key, encoded_chunk).await?;
+        self.maybe_async_store_chunk_statistics(storage, chunk_indices, chunk_statistics)
+            .await?;
+        Ok(())
+    }
+
+    /// Erase the chunk at `chunk_indices` in `storage` and its statistics.
+    pub(super) async fn maybe_async_erase_chunk<
+        TMaybeAsyncStorage: MaybeAsyncWritableStorageTraits,
+    >(
+        &self,
+        storage: &TMaybeAsyncStorage,
+        chunk_indices: &[u64],
+    ) -> Result<(), StorageError> {
+        let key = data_key(self.path(), chunk_indices, self.chunk_key_encoding());
+        storage.erase(&key).await?;
+        self.maybe_async_store_chunk_statistics(storage, chunk_indices, None)
+            .await
+    }
+}
@@ -10,14 +10,17 @@ use crate::{
     metadata::{AdditionalFields, Metadata},
 };
 
-use super::{ArrayShape, DimensionName};
+use super::{ArrayMetadataV2, ArrayShape, DimensionName};
 
 /// Zarr array metadata.
+#[allow(clippy::large_enum_variant)]
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Display, From)]
 #[serde(untagged)]
 pub enum ArrayMetadata {
     /// Version 3.0.
     V3(ArrayMetadataV3),
+    /// Version 2.0.
+    V2(ArrayMetadataV2),
 }
 
 /// Zarr array metadata (storage specification v3).
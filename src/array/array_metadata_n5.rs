@@ -0,0 +1,328 @@
+//! N5 dataset metadata.
+//!
+//! See <https://github.com/saalfeldlab/n5#file-system-specification>.
+//!
+//! N5 datasets are supported for reading by converting their metadata to the Zarr V3 model with [`array_metadata_n5_to_v3`].
+
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    array::{
+        chunk_grid::RegularChunkGridConfiguration,
+        codec::array_to_bytes::n5::{N5CodecConfiguration, N5CodecConfigurationV1},
+        DataType, FillValue,
+    },
+    metadata::{AdditionalFields, Metadata, MetadataConfiguration},
+};
+
+use super::{ArrayMetadataV3, ArrayShape, ChunkShape};
+
+/// N5 dataset metadata.
+///
+/// An example `JSON` document for an N5 dataset (`attributes.json`):
+/// ```json
+/// {
+///     "dimensions": [10000, 1000],
+///     "blockSize": [100, 100],
+///     "dataType": "uint16",
+///     "compression": {
+///         "type": "gzip",
+///         "level": -1
+///     },
+///     "foo": 42
+/// }
+/// ```
+///
+/// The dimensions are ordered from fastest to slowest varying (column-major).
+/// Any fields other than the dataset attributes are user defined attributes.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Display)]
+#[serde(rename_all = "camelCase")]
+#[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
+pub struct ArrayMetadataN5 {
+    /// The length of each dimension of the dataset, fastest varying first.
+    pub dimensions: ArrayShape,
+    /// The length of each dimension of a block, fastest varying first.
+    pub block_size: ChunkShape,
+    /// The data type of the dataset (e.g. `"uint16"`).
+    pub data_type: String,
+    /// A JSON object identifying the compression `type` and its parameters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<serde_json::Map<String, serde_json::Value>>,
+    /// The compression type in N5 versions prior to 1.0.0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression_type: Option<String>,
+    /// User defined attributes.
+    #[serde(flatten)]
+    pub attributes: serde_json::Map<String, serde_json::Value>,
+}
+
+/// An error converting N5 dataset metadata to Zarr V3 array metadata.
+#[derive(Debug, Error)]
+pub enum ArrayMetadataN5ToV3ConversionError {
+    /// The dimensionality of the dataset and block size do not match.
+    #[error("the block size dimensionality {0} does not match the dataset dimensionality {1}")]
+    InvalidBlockSize(usize, usize),
+    /// Unsupported data type.
+    #[error("unsupported n5 data type {0}")]
+    UnsupportedDataType(String),
+    /// Invalid or unsupported compression.
+    #[error("invalid or unsupported n5 compression {0}")]
+    InvalidCompression(serde_json::Value),
+    /// A serialisation error.
+    #[error(transparent)]
+    SerdeError(#[from] serde_json::Error),
+}
+
+/// Convert an N5 `dataType` to a [`DataType`].
+///
+/// # Errors
+/// Returns [`ArrayMetadataN5ToV3ConversionError::UnsupportedDataType`] if the data type is unsupported.
+pub fn data_type_metadata_n5_to_v3(
+    data_type: &str,
+) -> Result<DataType, ArrayMetadataN5ToV3ConversionError> {
+    match data_type {
+        "int8" => Ok(DataType::Int8),
+        "int16" => Ok(DataType::Int16),
+        "int32" => Ok(DataType::Int32),
+        "int64" => Ok(DataType::Int64),
+        "uint8" => Ok(DataType::UInt8),
+        "uint16" => Ok(DataType::UInt16),
+        "uint32" => Ok(DataType::UInt32),
+        "uint64" => Ok(DataType::UInt64),
+        "float32" => Ok(DataType::Float32),
+        "float64" => Ok(DataType::Float64),
+        _ => Err(ArrayMetadataN5ToV3ConversionError::UnsupportedDataType(
+            data_type.to_string(),
+        )),
+    }
+}
+
+/// Convert N5 `compression` metadata to the metadata of a Zarr V3 bytes to bytes codec.
+///
+/// The `raw` compression has no equivalent codec and is mapped to [`None`].
+/// The `gzip`, `bzip2`, `zstd`, and `blosc` compressions are mapped to the `gzip`, `bz2`, `zstd`, and `blosc` codecs respectively.
+///
+/// # Errors
+/// Returns [`ArrayMetadataN5ToV3ConversionError::InvalidCompression`] if the compression is invalid or unsupported (e.g. `xz`, `lz4`, or `gzip` with `useZlib`).
+pub fn codec_metadata_n5_to_v3(
+    compression: &serde_json::Map<String, serde_json::Value>,
+    data_type: &DataType,
+) -> Result<Option<Metadata>, ArrayMetadataN5ToV3ConversionError> {
+    let err = || {
+        ArrayMetadataN5ToV3ConversionError::InvalidCompression(serde_json::Value::Object(
+            compression.clone(),
+        ))
+    };
+    let field = |name: &str| compression.get(name).cloned();
+    let field_i64 = |name: &str, default: i64| match field(name) {
+        Some(value) => value.as_i64().ok_or_else(err),
+        None => Ok(default),
+    };
+    let mut configuration = MetadataConfiguration::new();
+    let name = match compression.get("type").and_then(|t| t.as_str()) {
+        Some("raw") => return Ok(None),
+        Some("gzip") => {
+            if field("useZlib").and_then(|use_zlib| use_zlib.as_bool()) == Some(true) {
+                return Err(err());
+            }
+            // A level of -1 is the default compression level of zlib
+            let level = match field_i64("level", -1)? {
+                -1 => 6,
+                level => level,
+            };
+            configuration.insert("level".to_string(), level.into());
+            "gzip"
+        }
+        Some("bzip2") => {
+            configuration.insert("level".to_string(), field_i64("blockSize", 9)?.into());
+            "bz2"
+        }
+        Some("zstd") => {
+            configuration.insert("level".to_string(), field_i64("level", 3)?.into());
+            configuration.insert("checksum".to_string(), false.into());
+            "zstd"
+        }
+        Some("blosc") => {
+            let shuffle = match field_i64("shuffle", 1)? {
+                0 => "noshuffle",
+                1 => "shuffle",
+                2 => "bitshuffle",
+                _ => return Err(err()),
+            };
+            let blocksize = field("blocksize")
+                .and_then(|blocksize| blocksize.as_u64())
+                .filter(|blocksize| *blocksize > 0);
+            configuration.insert("cname".to_string(), field("cname").ok_or_else(err)?);
+            configuration.insert("clevel".to_string(), field("clevel").ok_or_else(err)?);
+            configuration.insert("shuffle".to_string(), shuffle.into());
+            configuration.insert("typesize".to_string(), data_type.size().into());
+            configuration.insert("blocksize".to_string(), blocksize.into());
+            "blosc"
+        }
+        _ => return Err(err()),
+    };
+    Ok(Some(Metadata::new_with_configuration(name, configuration)))
+}
+
+/// Convert N5 dataset metadata to Zarr V3 array metadata.
+///
+/// The resultant array metadata has:
+///  - the N5 dimensions in reverse order, such that the last dimension is the fastest varying,
+///  - a `regular` chunk grid with the reversed N5 block size,
+///  - an `n5` chunk key encoding,
+///  - an `n5` codec encapsulating the N5 compression, and
+///  - a fill value of zero.
+///
+/// # Errors
+/// Returns [`ArrayMetadataN5ToV3ConversionError`] if the metadata is invalid or uses an unsupported data type or compression.
+pub fn array_metadata_n5_to_v3(
+    metadata: &ArrayMetadataN5,
+) -> Result<ArrayMetadataV3, ArrayMetadataN5ToV3ConversionError> {
+    if metadata.dimensions.len() != metadata.block_size.len() {
+        return Err(ArrayMetadataN5ToV3ConversionError::InvalidBlockSize(
+            metadata.block_size.len(),
+            metadata.dimensions.len(),
+        ));
+    }
+
+    let data_type = data_type_metadata_n5_to_v3(&metadata.data_type)?;
+
+    let shape: ArrayShape = metadata.dimensions.iter().rev().copied().collect();
+    let chunk_shape: ChunkShape = metadata
+        .block_size
+        .iter()
+        .rev()
+        .copied()
+        .collect::<Vec<_>>()
+        .into();
+    let chunk_grid = Metadata::new_with_serializable_configuration(
+        "regular",
+        &RegularChunkGridConfiguration { chunk_shape },
+    )?;
+
+    let chunk_key_encoding = Metadata::new("n5");
+
+    let compression = match (&metadata.compression, &metadata.compression_type) {
+        (Some(compression), _) => codec_metadata_n5_to_v3(compression, &data_type)?,
+        (None, Some(compression_type)) => {
+            let mut compression = serde_json::Map::new();
+            compression.insert("type".to_string(), compression_type.clone().into());
+            codec_metadata_n5_to_v3(&compression, &data_type)?
+        }
+        (None, None) => None,
+    };
+    let codecs = vec![Metadata::new_with_serializable_configuration(
+        "n5",
+        &N5CodecConfiguration::V1(N5CodecConfigurationV1 { compression }),
+    )?];
+
+    let fill_value = data_type.metadata_fill_value(&FillValue::new(vec![0; data_type.size()]));
+
+    Ok(ArrayMetadataV3::new(
+        shape,
+        data_type.metadata(),
+        chunk_grid,
+        chunk_key_encoding,
+        fill_value,
+        codecs,
+        metadata.attributes.clone(),
+        vec![],
+        None,
+        AdditionalFields::default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_metadata_n5_gzip() {
+        let metadata: ArrayMetadataN5 = serde_json::from_str(
+            r#"{
+            "dimensions": [100, 200, 300],
+            "blockSize": [10, 20, 30],
+            "dataType": "uint16",
+            "compression": {
+                "type": "gzip",
+                "level": -1
+            },
+            "foo": "bar"
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            metadata.attributes.get("foo"),
+            Some(&serde_json::Value::String("bar".to_string()))
+        );
+        let metadata = array_metadata_n5_to_v3(&metadata).unwrap();
+        assert_eq!(metadata.shape, vec![300, 200, 100]);
+        assert_eq!(
+            serde_json::to_value(&metadata.chunk_grid).unwrap(),
+            serde_json::json!({"name": "regular", "configuration": {"chunk_shape": [30, 20, 10]}})
+        );
+        assert_eq!(
+            serde_json::to_value(&metadata.codecs).unwrap(),
+            serde_json::json!([
+                {"name": "n5", "configuration": {"compression": {"name": "gzip", "configuration": {"level": 6}}}},
+            ])
+        );
+        assert_eq!(metadata.attributes.len(), 1);
+    }
+
+    #[test]
+    fn array_metadata_n5_legacy_compression_type() {
+        let metadata: ArrayMetadataN5 = serde_json::from_str(
+            r#"{
+            "dimensions": [100],
+            "blockSize": [10],
+            "dataType": "float64",
+            "compressionType": "raw"
+        }"#,
+        )
+        .unwrap();
+        let metadata = array_metadata_n5_to_v3(&metadata).unwrap();
+        assert_eq!(
+            serde_json::to_value(&metadata.codecs).unwrap(),
+            serde_json::json!([{"name": "n5", "configuration": {"compression": null}}])
+        );
+        assert!(metadata.attributes.is_empty());
+    }
+
+    #[test]
+    fn array_metadata_n5_unsupported() {
+        let metadata: ArrayMetadataN5 = serde_json::from_str(
+            r#"{
+            "dimensions": [100],
+            "blockSize": [10],
+            "dataType": "object"
+        }"#,
+        )
+        .unwrap();
+        assert!(array_metadata_n5_to_v3(&metadata).is_err());
+
+        let metadata: ArrayMetadataN5 = serde_json::from_str(
+            r#"{
+            "dimensions": [100],
+            "blockSize": [10],
+            "dataType": "uint8",
+            "compression": {"type": "gzip", "useZlib": true}
+        }"#,
+        )
+        .unwrap();
+        assert!(array_metadata_n5_to_v3(&metadata).is_err());
+
+        let metadata: ArrayMetadataN5 = serde_json::from_str(
+            r#"{
+            "dimensions": [100],
+            "blockSize": [10],
+            "dataType": "uint8",
+            "compression": {"type": "xz"}
+        }"#,
+        )
+        .unwrap();
+        assert!(array_metadata_n5_to_v3(&metadata).is_err());
+    }
+}
@@ -0,0 +1,421 @@
+//! Zarr V2 array metadata.
+//!
+//! See <https://zarr-specs.readthedocs.io/en/latest/v2/v2.0.html#metadata>.
+//!
+//! Zarr V2 arrays are supported for reading by converting their metadata to the Zarr V3 model with [`array_metadata_v2_to_v3`].
+
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    array::{
+        chunk_grid::RegularChunkGridConfiguration,
+        chunk_key_encoding::{ChunkKeySeparator, V2ChunkKeyEncodingConfiguration},
+        codec::{array_to_bytes::bytes::Endianness, BytesCodecConfigurationV1},
+        data_type::TimeUnit,
+        DataType, FillValue, FillValueMetadata,
+    },
+    metadata::{AdditionalFields, Metadata, MetadataConfiguration, MetadataV2},
+};
+
+use super::{ArrayMetadataV3, ArrayShape, ChunkShape};
+
+/// Zarr array metadata (storage specification v2).
+///
+/// An example `JSON` document for a v2 array (`.zarray`):
+/// ```json
+/// {
+///     "chunks": [1000, 1000],
+///     "compressor": {
+///         "id": "blosc",
+///         "cname": "lz4",
+///         "clevel": 5,
+///         "shuffle": 1
+///     },
+///     "dtype": "<f8",
+///     "fill_value": "NaN",
+///     "filters": null,
+///     "order": "C",
+///     "shape": [10000, 10000],
+///     "zarr_format": 2
+/// }
+/// ```
+///
+/// The `attributes` are stored separately in `.zattrs`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Display)]
+#[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
+pub struct ArrayMetadataV2 {
+    /// An integer defining the version of the storage specification to which the array store adheres. Must be `2`.
+    pub zarr_format: usize,
+    /// An array of integers providing the length of each dimension of the Zarr array.
+    pub shape: ArrayShape,
+    /// A list of integers defining the length of each dimension of a chunk of the array.
+    pub chunks: ChunkShape,
+    /// The data type of the Zarr array, encoded as a `NumPy` type string (e.g. `"<f8"`).
+    pub dtype: String,
+    /// A JSON object identifying the primary compression codec and providing configuration parameters, or null if no compressor is to be used.
+    pub compressor: Option<MetadataV2>,
+    /// A scalar value providing the default value to use for uninitialized portions of the array, or null if no fill value is to be used.
+    pub fill_value: Option<FillValueMetadata>,
+    /// Either `C` or `F`, defining the layout of bytes within each chunk of the array.
+    pub order: ArrayMetadataV2Order,
+    /// A list of JSON objects providing codec configurations, or null if no filters are to be applied.
+    #[serde(default)]
+    pub filters: Option<Vec<MetadataV2>>,
+    /// If present, either `.` or `/`, defining the separator placed between the dimensions of a chunk.
+    #[serde(default = "chunk_key_separator_default_zarr_v2")]
+    pub dimension_separator: ChunkKeySeparator,
+    /// Optional user defined attributes, stored in `.zattrs`.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub attributes: serde_json::Map<String, serde_json::Value>,
+}
+
+const fn chunk_key_separator_default_zarr_v2() -> ChunkKeySeparator {
+    ChunkKeySeparator::Dot
+}
+
+/// The layout of bytes within each chunk of a Zarr V2 array.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug, Display)]
+pub enum ArrayMetadataV2Order {
+    /// Row-major order. The last dimension varies fastest.
+    C,
+    /// Column-major order. The first dimension varies fastest.
+    F,
+}
+
+/// An error converting Zarr V2 array metadata to Zarr V3 array metadata.
+#[derive(Debug, Error)]
+pub enum ArrayMetadataV2ToV3ConversionError {
+    /// Invalid zarr format.
+    #[error("invalid zarr format {0}, expected 2")]
+    InvalidZarrFormat(usize),
+    /// Unsupported data type.
+    #[error("unsupported zarr v2 data type {0}")]
+    UnsupportedDataType(String),
+    /// Invalid codec metadata.
+    #[error("invalid or unsupported zarr v2 codec metadata {0}")]
+    InvalidCodec(MetadataV2),
+    /// A serialisation error.
+    #[error(transparent)]
+    SerdeError(#[from] serde_json::Error),
+}
+
+/// Convert a Zarr V2 `dtype` to a [`DataType`] and its [`Endianness`].
+///
+/// The endianness is [`None`] for single byte and raw data types.
+///
+/// # Errors
+/// Returns [`ArrayMetadataV2ToV3ConversionError::UnsupportedDataType`] if the data type is unsupported.
+pub fn data_type_metadata_v2_to_v3(
+    dtype: &str,
+) -> Result<(DataType, Option<Endianness>), ArrayMetadataV2ToV3ConversionError> {
+    let err = || ArrayMetadataV2ToV3ConversionError::UnsupportedDataType(dtype.to_string());
+    let mut chars = dtype.chars();
+    let endianness = match chars.next() {
+        Some('<') => Some(Endianness::Little),
+        Some('>') => Some(Endianness::Big),
+        Some('|') => None,
+        #[cfg(target_endian = "big")]
+        Some('=') => Some(Endianness::Big),
+        #[cfg(target_endian = "little")]
+        Some('=') => Some(Endianness::Little),
+        _ => return Err(err()),
+    };
+    let data_type = match chars.as_str() {
+        "b1" => DataType::Bool,
+        "i1" => DataType::Int8,
+        "i2" => DataType::Int16,
+        "i4" => DataType::Int32,
+        "i8" => DataType::Int64,
+        "u1" => DataType::UInt8,
+        "u2" => DataType::UInt16,
+        "u4" => DataType::UInt32,
+        "u8" => DataType::UInt64,
+        "f2" => DataType::Float16,
+        "f4" => DataType::Float32,
+        "f8" => DataType::Float64,
+        "c8" => DataType::Complex64,
+        "c16" => DataType::Complex128,
+        datetime if datetime.starts_with("M8[") || datetime.starts_with("m8[") => {
+            let unit = datetime[3..]
+                .strip_suffix(']')
+                .and_then(|unit| unit.parse::<TimeUnit>().ok())
+                .ok_or_else(err)?;
+            if datetime.starts_with('M') {
+                DataType::DateTime64(unit)
+            } else {
+                DataType::TimeDelta64(unit)
+            }
+        }
+        raw => {
+            let size = raw
+                .strip_prefix('V')
+                .and_then(|size| size.parse::<usize>().ok())
+                .filter(|size| *size > 0)
+                .ok_or_else(err)?;
+            return Ok((DataType::RawBits(size), None));
+        }
+    };
+    let endianness = if data_type.size() > 1 {
+        Some(endianness.ok_or_else(err)?)
+    } else {
+        None
+    };
+    Ok((data_type, endianness))
+}
+
+/// Convert Zarr V2 codec metadata (a compressor or filter) to Zarr V3 codec metadata.
+///
+/// The `blosc`, `gzip`, `zstd`, and `bz2` codecs are mapped to their Zarr V3 equivalents.
+/// Any other codec is mapped to a Zarr V3 codec with a name matching its `id` and the same configuration.
+///
+/// # Errors
+/// Returns [`ArrayMetadataV2ToV3ConversionError::InvalidCodec`] if the metadata of a known codec is invalid.
+pub fn codec_metadata_v2_to_v3(
+    metadata: &MetadataV2,
+    data_type: &DataType,
+) -> Result<Metadata, ArrayMetadataV2ToV3ConversionError> {
+    let err = || ArrayMetadataV2ToV3ConversionError::InvalidCodec(metadata.clone());
+    let configuration = metadata.configuration();
+    let field = |name: &str| configuration.get(name).cloned();
+    let mut configuration_v3 = MetadataConfiguration::new();
+    match metadata.id() {
+        "blosc" => {
+            // The numcodecs blosc shuffle: -1 (auto), 0 (noshuffle), 1 (shuffle), 2 (bitshuffle)
+            let shuffle = match field("shuffle").and_then(|shuffle| shuffle.as_i64()) {
+                Some(0) => "noshuffle",
+                Some(1) | None => "shuffle",
+                Some(2) => "bitshuffle",
+                Some(-1) => {
+                    if data_type.size() == 1 {
+                        "bitshuffle"
+                    } else {
+                        "shuffle"
+                    }
+                }
+                Some(_) => return Err(err()),
+            };
+            let blocksize = field("blocksize")
+                .and_then(|blocksize| blocksize.as_u64())
+                .filter(|blocksize| *blocksize > 0);
+            configuration_v3.insert("cname".to_string(), field("cname").ok_or_else(err)?);
+            configuration_v3.insert("clevel".to_string(), field("clevel").ok_or_else(err)?);
+            configuration_v3.insert("shuffle".to_string(), shuffle.into());
+            configuration_v3.insert("typesize".to_string(), data_type.size().into());
+            configuration_v3.insert("blocksize".to_string(), blocksize.into());
+        }
+        "gzip" | "bz2" => {
+            configuration_v3.insert("level".to_string(), field("level").ok_or_else(err)?);
+        }
+        "zstd" => {
+            configuration_v3.insert("level".to_string(), field("level").ok_or_else(err)?);
+            configuration_v3.insert(
+                "checksum".to_string(),
+                field("checksum").unwrap_or(serde_json::Value::Bool(false)),
+            );
+        }
+        _ => {
+            configuration_v3.clone_from(configuration);
+        }
+    }
+    Ok(Metadata::new_with_configuration(
+        metadata.id(),
+        configuration_v3,
+    ))
+}
+
+/// Convert Zarr V2 array metadata to Zarr V3 array metadata.
+///
+/// The resultant array metadata has:
+///  - a `regular` chunk grid with the chunk shape of the V2 array,
+///  - a `v2` chunk key encoding with the V2 dimension separator,
+///  - a `transpose` codec if the V2 array has `F` order and more than one dimension,
+///  - the V2 filters, followed by a `bytes` codec with the V2 endianness, followed by the V2 compressor, and
+///  - a fill value of zero if the V2 fill value is null.
+///
+/// # Errors
+/// Returns [`ArrayMetadataV2ToV3ConversionError`] if the metadata is invalid or uses an unsupported data type or codec.
+pub fn array_metadata_v2_to_v3(
+    metadata: &ArrayMetadataV2,
+) -> Result<ArrayMetadataV3, ArrayMetadataV2ToV3ConversionError> {
+    if metadata.zarr_format != 2 {
+        return Err(ArrayMetadataV2ToV3ConversionError::InvalidZarrFormat(
+            metadata.zarr_format,
+        ));
+    }
+
+    let (data_type, endianness) = data_type_metadata_v2_to_v3(&metadata.dtype)?;
+
+    let chunk_grid = Metadata::new_with_serializable_configuration(
+        "regular",
+        &RegularChunkGridConfiguration {
+            chunk_shape: metadata.chunks.clone(),
+        },
+    )?;
+
+    let chunk_key_encoding = Metadata::new_with_serializable_configuration(
+        "v2",
+        &V2ChunkKeyEncodingConfiguration {
+            separator: metadata.dimension_separator,
+        },
+    )?;
+
+    let mut codecs = Vec::new();
+    if metadata.order == ArrayMetadataV2Order::F && metadata.shape.len() > 1 {
+        let order: Vec<usize> = (0..metadata.shape.len()).rev().collect();
+        let mut configuration = MetadataConfiguration::new();
+        configuration.insert("order".to_string(), order.into());
+        codecs.push(Metadata::new_with_configuration("transpose", configuration));
+    }
+    if let Some(filters) = &metadata.filters {
+        for filter in filters {
+            codecs.push(codec_metadata_v2_to_v3(filter, &data_type)?);
+        }
+    }
+    codecs.push(Metadata::new_with_serializable_configuration(
+        "bytes",
+        &BytesCodecConfigurationV1::new(endianness),
+    )?);
+    if let Some(compressor) = &metadata.compressor {
+        codecs.push(codec_metadata_v2_to_v3(compressor, &data_type)?);
+    }
+
+    let fill_value = metadata.fill_value.clone().unwrap_or_else(|| {
+        data_type.metadata_fill_value(&FillValue::new(vec![0; data_type.size()]))
+    });
+
+    Ok(ArrayMetadataV3::new(
+        metadata.shape.clone(),
+        data_type.metadata(),
+        chunk_grid,
+        chunk_key_encoding,
+        fill_value,
+        codecs,
+        metadata.attributes.clone(),
+        vec![],
+        None,
+        AdditionalFields::default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_metadata_v2_blosc_f_order() {
+        let metadata: ArrayMetadataV2 = serde_json::from_str(
+            r#"{
+            "chunks": [1000, 1000],
+            "compressor": {
+                "id": "blosc",
+                "cname": "lz4",
+                "clevel": 5,
+                "shuffle": 1
+            },
+            "dtype": "<f8",
+            "fill_value": "NaN",
+            "filters": null,
+            "order": "F",
+            "shape": [10000, 10000],
+            "zarr_format": 2
+        }"#,
+        )
+        .unwrap();
+        let metadata = array_metadata_v2_to_v3(&metadata).unwrap();
+        assert_eq!(
+            serde_json::to_value(&metadata.codecs).unwrap(),
+            serde_json::json!([
+                {"name": "transpose", "configuration": {"order": [1, 0]}},
+                {"name": "bytes", "configuration": {"endian": "little"}},
+                {"name": "blosc", "configuration": {
+                    "cname": "lz4",
+                    "clevel": 5,
+                    "shuffle": "shuffle",
+                    "typesize": 8,
+                    "blocksize": null
+                }}
+            ])
+        );
+        assert_eq!(
+            serde_json::to_value(&metadata.chunk_key_encoding).unwrap(),
+            serde_json::json!({"name": "v2", "configuration": {"separator": "."}})
+        );
+        assert_eq!(metadata.data_type.name(), "float64");
+    }
+
+    #[test]
+    fn array_metadata_v2_null_fill_value() {
+        let metadata: ArrayMetadataV2 = serde_json::from_str(
+            r#"{
+            "chunks": [5],
+            "compressor": null,
+            "dtype": ">u2",
+            "fill_value": null,
+            "order": "C",
+            "shape": [10],
+            "zarr_format": 2,
+            "dimension_separator": "/"
+        }"#,
+        )
+        .unwrap();
+        let metadata = array_metadata_v2_to_v3(&metadata).unwrap();
+        assert_eq!(metadata.fill_value, FillValueMetadata::UInt(0));
+        assert_eq!(
+            serde_json::to_value(&metadata.codecs).unwrap(),
+            serde_json::json!([{"name": "bytes", "configuration": {"endian": "big"}}])
+        );
+    }
+
+    #[test]
+    fn array_metadata_v2_data_types() {
+        assert_eq!(
+            data_type_metadata_v2_to_v3("|b1").unwrap(),
+            (DataType::Bool, None)
+        );
+        assert_eq!(
+            data_type_metadata_v2_to_v3("<c16").unwrap(),
+            (DataType::Complex128, Some(Endianness::Little))
+        );
+        assert_eq!(
+            data_type_metadata_v2_to_v3("|V6").unwrap(),
+            (DataType::RawBits(6), None)
+        );
+        assert!(data_type_metadata_v2_to_v3("|f8").is_err());
+        assert_eq!(
+            data_type_metadata_v2_to_v3("<M8[ns]").unwrap(),
+            (
+                DataType::DateTime64(TimeUnit::Nanosecond),
+                Some(Endianness::Little)
+            )
+        );
+        assert_eq!(
+            data_type_metadata_v2_to_v3(">m8[D]").unwrap(),
+            (DataType::TimeDelta64(TimeUnit::Day), Some(Endianness::Big))
+        );
+        assert!(data_type_metadata_v2_to_v3("<M8[x]").is_err());
+        assert!(data_type_metadata_v2_to_v3("<M8").is_err());
+        assert!(data_type_metadata_v2_to_v3("").is_err());
+    }
+
+    #[test]
+    fn array_metadata_v2_invalid_format() {
+        let metadata: ArrayMetadataV2 = serde_json::from_str(
+            r#"{
+            "chunks": [5],
+            "compressor": null,
+            "dtype": "|u1",
+            "fill_value": 0,
+            "order": "C",
+            "shape": [10],
+            "zarr_format": 3
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            array_metadata_v2_to_v3(&metadata).unwrap_err().to_string(),
+            "invalid zarr format 3, expected 2"
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use std::ops::Range;
+
+use crate::{array_subset::ArraySubset, storage::ReadableStorageTraits};
+
+#[cfg(feature = "async")]
+use crate::storage::AsyncReadableStorageTraits;
+
+use super::{codec::CodecOptions, Array, ArrayError, Element, IncompatibleDimensionalityError};
+
+/// The attribute holding the origin of an array in world coordinates.
+pub(crate) const ORIGIN_ATTRIBUTE: &str = "_zarrs_origin";
+
+impl<TStorage: ?Sized> Array<TStorage> {
+    /// Return the origin of the array in world coordinates.
+    ///
+    /// The origin is the world coordinate of the element at array indices `[0, 0, ...]`, and is stored in the `_zarrs_origin` attribute.
+    /// An array with no origin attribute has an origin of zero.
+    ///
+    /// # Errors
+    /// Returns [`ArrayError::InvalidOrigin`] if the origin attribute is not a list of integers with the dimensionality of the array.
+    pub fn origin(&self) -> Result<Vec<i64>, ArrayError> {
+        let origin = self
+            .attribute::<Vec<i64>>(ORIGIN_ATTRIBUTE)
+            .map_err(|err| ArrayError::InvalidOrigin(err.to_string()))?;
+        match origin {
+            Some(origin) if origin.len() == self.dimensionality() => Ok(origin),
+            Some(origin) => Err(ArrayError::InvalidOrigin(format!(
+                "origin {origin:?} does not match the array dimensionality {}",
+                self.dimensionality()
+            ))),
+            None => Ok(vec![0; self.dimensionality()]),
+        }
+    }
+
+    /// Set the origin of the array in world coordinates, or remove it if [`None`].
+    ///
+    /// This does **not** write to the store, use [`store_attributes`](Array::store_attributes) or [`store_metadata`](Array::store_metadata) to write the origin to the store.
+    ///
+    /// # Errors
+    /// Returns an [`IncompatibleDimensionalityError`] if `origin` does not match the dimensionality of the array.
+    pub fn set_origin(
+        &mut self,
+        origin: Option<Vec<i64>>,
+    ) -> Result<(), IncompatibleDimensionalityError> {
+        if let Some(origin) = origin {
+            if origin.len() != self.dimensionality() {
+                return Err(IncompatibleDimensionalityError::new(
+                    origin.len(),
+                    self.dimensionality(),
+                ));
+            }
+            self.attributes_mut()
+                .insert(ORIGIN_ATTRIBUTE.to_string(), origin.into());
+        } else {
+            self.attributes_mut().remove(ORIGIN_ATTRIBUTE);
+        }
+        Ok(())
+    }
+
+    /// Translate `world_subset` in world coordinates to an array subset relative to the [`origin`](Array::origin) of the array.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the origin attribute is invalid,
+    ///  - the dimensionality of `world_subset` does not match the array, or
+    ///  - `world_subset` starts before the origin of the array.
+    pub fn world_subset_to_array_subset(
+        &self,
+        world_subset: &[Range<i64>],
+    ) -> Result<ArraySubset, ArrayError> {
+        let origin = self.origin()?;
+        let invalid_world_subset = || {
+            ArrayError::InvalidWorldSubset(
+                world_subset.to_vec(),
+                origin.clone(),
+                self.shape().to_vec(),
+            )
+        };
+        if world_subset.len() != origin.len() {
+            return Err(invalid_world_subset());
+        }
+        let ranges = std::iter::zip(world_subset, &origin)
+            .map(|(range, &origin)| {
+                let start = u64::try_from(range.start.checked_sub(origin)?).ok()?;
+                let end = u64::try_from(range.end.checked_sub(origin)?).ok()?;
+                Some(start..end.max(start))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid_world_subset)?;
+        Ok(ArraySubset::new_with_ranges(&ranges))
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
+    /// Read and decode the `world_subset` in world coordinates into its bytes.
+    ///
+    /// `world_subset` is translated to an array subset relative to the [`origin`](Array::origin) of the array, and retrieved with [`retrieve_array_subset`](Array::retrieve_array_subset).
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - `world_subset` cannot be translated to an array subset (see [`world_subset_to_array_subset`](Array::world_subset_to_array_subset)),
+    ///  - there is a codec decoding error, or
+    ///  - an underlying store error.
+    pub fn retrieve_world_subset(
+        &self,
+        world_subset: &[Range<i64>],
+    ) -> Result<Vec<u8>, ArrayError> {
+        self.retrieve_world_subset_opt(world_subset, &CodecOptions::default())
+    }
+
+    /// Read and decode the `world_subset` in world coordinates into a vector of its elements.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the size of `T` does not match the data type size or a [`retrieve_world_subset`](Array::retrieve_world_subset) error condition is met.
+    pub fn retrieve_world_subset_elements<T: Element>(
+        &self,
+        world_subset: &[Range<i64>],
+    ) -> Result<Vec<T>, ArrayError> {
+        self.retrieve_world_subset_elements_opt(world_subset, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`retrieve_world_subset`](Array::retrieve_world_subset).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_world_subset_opt(
+        &self,
+        world_subset: &[Range<i64>],
+        options: &CodecOptions,
+    ) -> Result<Vec<u8>, ArrayError> {
+        let array_subset = self.world_subset_to_array_subset(world_subset)?;
+        self.retrieve_array_subset_opt(&array_subset, options)
+    }
+
+    /// Explicit options version of [`retrieve_world_subset_elements`](Array::retrieve_world_subset_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_world_subset_elements_opt<T: Element>(
+        &self,
+        world_subset: &[Range<i64>],
+        options: &CodecOptions,
+    ) -> Result<Vec<T>, ArrayError> {
+        let array_subset = self.world_subset_to_array_subset(world_subset)?;
+        self.retrieve_array_subset_elements_opt(&array_subset, options)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
+    /// Async variant of [`retrieve_world_subset`](Array::retrieve_world_subset).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_world_subset(
+        &self,
+        world_subset: &[Range<i64>],
+    ) -> Result<Vec<u8>, ArrayError> {
+        self.async_retrieve_world_subset_opt(world_subset, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`retrieve_world_subset_elements`](Array::retrieve_world_subset_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_world_subset_elements<T: Element + Send + Sync>(
+        &self,
+        world_subset: &[Range<i64>],
+    ) -> Result<Vec<T>, ArrayError> {
+        self.async_retrieve_world_subset_elements_opt(world_subset, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`retrieve_world_subset_opt`](Array::retrieve_world_subset_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_world_subset_opt(
+        &self,
+        world_subset: &[Range<i64>],
+        options: &CodecOptions,
+    ) -> Result<Vec<u8>, ArrayError> {
+        let array_subset = self.world_subset_to_array_subset(world_subset)?;
+        self.async_retrieve_array_subset_opt(&array_subset, options)
+            .await
+    }
+
+    /// Async variant of [`retrieve_world_subset_elements_opt`](Array::retrieve_world_subset_elements_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_world_subset_elements_opt<T: Element + Send + Sync>(
+        &self,
+        world_subset: &[Range<i64>],
+        options: &CodecOptions,
+    ) -> Result<Vec<T>, ArrayError> {
+        let array_subset = self.world_subset_to_array_subset(world_subset)?;
+        self.async_retrieve_array_subset_elements_opt(&array_subset, options)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_origin() {
+        let store = Arc::new(MemoryStore::new());
+        let mut array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        assert_eq!(array.origin().unwrap(), vec![0, 0]);
+        assert!(array.set_origin(Some(vec![1])).is_err());
+        array.set_origin(Some(vec![-10, 100])).unwrap();
+        array.store_metadata().unwrap();
+        array
+            .store_array_subset_elements(
+                &ArraySubset::new_with_shape(vec![4, 4]),
+                (0..16).collect::<Vec<u8>>(),
+            )
+            .unwrap();
+
+        let array = Array::new(store, "/array").unwrap();
+        assert_eq!(array.origin().unwrap(), vec![-10, 100]);
+        assert_eq!(
+            array
+                .world_subset_to_array_subset(&[-9..-7, 101..104])
+                .unwrap(),
+            ArraySubset::new_with_ranges(&[1..3, 1..4])
+        );
+        assert_eq!(
+            array
+                .retrieve_world_subset_elements::<u8>(&[-9..-7, 101..104])
+                .unwrap(),
+            vec![5, 6, 7, 9, 10, 11]
+        );
+        // Beyond the end of the array is the fill value, but before the origin is an error
+        assert_eq!(
+            array
+                .retrieve_world_subset_elements::<u8>(&[-7..-5, 103..104])
+                .unwrap(),
+            vec![15, 0]
+        );
+        assert!(array.retrieve_world_subset(&[-11..-9, 100..101]).is_err());
+        assert!(array.retrieve_world_subset(&[-10..-9]).is_err());
+
+        let mut array = array;
+        array.set_origin(None).unwrap();
+        assert_eq!(array.origin().unwrap(), vec![0, 0]);
+        array
+            .attributes_mut()
+            .insert(ORIGIN_ATTRIBUTE.to_string(), "invalid".into());
+        assert!(array.origin().is_err());
+    }
+}
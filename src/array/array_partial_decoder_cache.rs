@@ -0,0 +1,212 @@
+use std::{collections::HashMap, num::NonZeroUsize, sync::Arc};
+
+use parking_lot::Mutex;
+
+use crate::storage::ReadableStorageTraits;
+
+use super::{
+    codec::{ArrayPartialDecoderTraits, CodecOptions},
+    Array, ArrayError, ArrayIndices,
+};
+
+type CachedPartialDecoder<'a> = Arc<dyn ArrayPartialDecoderTraits + 'a>;
+
+/// A least recently used cache of the partial decoders of the chunks of an array.
+///
+/// [`Array::partial_decoder`] initialises a new partial decoder on each call.
+/// This is costly if many small reads are made from the same chunks, as initialisation may involve store requests (e.g. retrieving the shard index of a chunk with the sharding codec).
+///
+/// A [`PartialDecoderCache`] instead holds up to `capacity` partial decoders, and returns the cached partial decoder of a chunk on subsequent calls to [`cached_partial_decoder`](PartialDecoderCache::cached_partial_decoder).
+/// The least recently used partial decoder is evicted when the capacity is exceeded.
+///
+/// A cached partial decoder may hold state retrieved from the store on initialisation, so it is not guaranteed to reflect changes to its chunk made after it was cached.
+/// Use [`clear`](PartialDecoderCache::clear) to discard cached partial decoders after writing to the array.
+pub struct PartialDecoderCache<'a, TStorage: ?Sized> {
+    array: &'a Array<TStorage>,
+    capacity: NonZeroUsize,
+    partial_decoders: Mutex<PartialDecoders<'a>>,
+}
+
+#[derive(Default)]
+struct PartialDecoders<'a> {
+    entries: HashMap<ArrayIndices, (CachedPartialDecoder<'a>, u64)>,
+    counter: u64,
+}
+
+impl<'a, TStorage: ?Sized + ReadableStorageTraits + 'static> PartialDecoderCache<'a, TStorage> {
+    /// Create a new partial decoder cache for `array` holding up to `capacity` partial decoders.
+    #[must_use]
+    pub fn new(array: &'a Array<TStorage>, capacity: NonZeroUsize) -> Self {
+        Self {
+            array,
+            capacity,
+            partial_decoders: Mutex::default(),
+        }
+    }
+
+    /// Return the array.
+    #[must_use]
+    pub const fn array(&self) -> &Array<TStorage> {
+        self.array
+    }
+
+    /// Return the maximum number of cached partial decoders.
+    #[must_use]
+    pub const fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
+    /// Return the number of cached partial decoders.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.partial_decoders.lock().entries.len()
+    }
+
+    /// Returns true if there are no cached partial decoders.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Discard all cached partial decoders.
+    pub fn clear(&self) {
+        *self.partial_decoders.lock() = PartialDecoders::default();
+    }
+
+    /// Return the partial decoder for the chunk at `chunk_indices`, initialising and caching it if it is not cached.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if initialisation of the partial decoder fails.
+    pub fn cached_partial_decoder(
+        &self,
+        chunk_indices: &[u64],
+    ) -> Result<CachedPartialDecoder<'a>, ArrayError> {
+        self.cached_partial_decoder_opt(chunk_indices, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`cached_partial_decoder`](PartialDecoderCache::cached_partial_decoder).
+    ///
+    /// `options` are only used if the partial decoder is initialised.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn cached_partial_decoder_opt(
+        &self,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<CachedPartialDecoder<'a>, ArrayError> {
+        {
+            let mut partial_decoders = self.partial_decoders.lock();
+            let PartialDecoders { entries, counter } = &mut *partial_decoders;
+            if let Some((partial_decoder, last_used)) = entries.get_mut(chunk_indices) {
+                *counter += 1;
+                *last_used = *counter;
+                return Ok(partial_decoder.clone());
+            }
+        }
+
+        // Initialise the partial decoder without holding the lock, so other chunks can be accessed concurrently
+        let partial_decoder: CachedPartialDecoder<'a> = self
+            .array
+            .partial_decoder_opt(chunk_indices, options)?
+            .into();
+
+        let mut partial_decoders = self.partial_decoders.lock();
+        let PartialDecoders { entries, counter } = &mut *partial_decoders;
+        *counter += 1;
+        let (partial_decoder, _) = entries
+            .entry(chunk_indices.to_vec())
+            .or_insert((partial_decoder, *counter))
+            .clone();
+        if entries.len() > self.capacity.get() {
+            if let Some(chunk_indices_lru) = entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(chunk_indices, _)| chunk_indices.clone())
+            {
+                entries.remove(&chunk_indices_lru);
+            }
+        }
+        Ok(partial_decoder)
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
+    /// Create a [`PartialDecoderCache`] for the array holding up to `capacity` partial decoders.
+    #[must_use]
+    pub fn partial_decoder_cache(
+        &self,
+        capacity: NonZeroUsize,
+    ) -> PartialDecoderCache<'_, TStorage> {
+        PartialDecoderCache::new(self, capacity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        array_subset::ArraySubset,
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_partial_decoder_cache() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .shard(vec![2, 2].try_into().unwrap())
+        .build(store, "/array")
+        .unwrap();
+        let elements: Vec<u8> = (0..64).collect();
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_shape(vec![8, 8]), elements)
+            .unwrap();
+
+        let cache = array.partial_decoder_cache(NonZeroUsize::new(2).unwrap());
+        assert!(cache.is_empty());
+        let partial_decoder = cache.cached_partial_decoder(&[0, 1]).unwrap();
+        assert_eq!(
+            partial_decoder
+                .partial_decode(&[ArraySubset::new_with_ranges(&[1..2, 2..4])])
+                .unwrap(),
+            vec![vec![14, 15]]
+        );
+        assert!(Arc::ptr_eq(
+            &partial_decoder,
+            &cache.cached_partial_decoder(&[0, 1]).unwrap()
+        ));
+        assert_eq!(cache.len(), 1);
+
+        // The least recently used partial decoder is evicted
+        cache.cached_partial_decoder(&[1, 0]).unwrap();
+        cache.cached_partial_decoder(&[0, 1]).unwrap();
+        let partial_decoder_1_1 = cache.cached_partial_decoder(&[1, 1]).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert!(Arc::ptr_eq(
+            &partial_decoder,
+            &cache.cached_partial_decoder(&[0, 1]).unwrap()
+        ));
+        assert!(Arc::ptr_eq(
+            &partial_decoder_1_1,
+            &cache.cached_partial_decoder(&[1, 1]).unwrap()
+        ));
+        assert_eq!(
+            partial_decoder_1_1
+                .partial_decode(&[ArraySubset::new_with_ranges(&[3..4, 3..4])])
+                .unwrap(),
+            vec![vec![63]]
+        );
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(!Arc::ptr_eq(
+            &partial_decoder,
+            &cache.cached_partial_decoder(&[0, 1]).unwrap()
+        ));
+    }
+}
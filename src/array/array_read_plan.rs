@@ -0,0 +1,385 @@
+use std::sync::Arc;
+
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+use crate::{
+    array_subset::ArraySubset,
+    byte_range::ByteRange,
+    storage::{data_key, ReadableStorageTraits, StorageHandle, StoreKey},
+};
+
+#[cfg(feature = "async")]
+use crate::storage::AsyncReadableStorageTraits;
+
+use super::{
+    codec::{ArrayToBytesCodecTraits, CodecOptions, StoragePartialDecoder},
+    Array, ArrayError, ArrayIndices,
+};
+
+#[cfg(feature = "async")]
+use super::codec::AsyncStoragePartialDecoder;
+
+/// The store key and byte ranges of a chunk retrieved by a planned read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChunkReadPlan {
+    chunk_indices: ArrayIndices,
+    key: StoreKey,
+    byte_ranges: Vec<ByteRange>,
+}
+
+impl ChunkReadPlan {
+    /// Return the indices of the chunk.
+    #[must_use]
+    pub fn chunk_indices(&self) -> &[u64] {
+        &self.chunk_indices
+    }
+
+    /// Return the store key of the chunk.
+    #[must_use]
+    pub const fn key(&self) -> &StoreKey {
+        &self.key
+    }
+
+    /// Return the byte ranges of the chunk that would be retrieved.
+    ///
+    /// An entirely retrieved chunk has the single byte range `ByteRange::FromStart(0, None)`.
+    #[must_use]
+    pub fn byte_ranges(&self) -> &[ByteRange] {
+        &self.byte_ranges
+    }
+
+    /// Returns true if the entire chunk would be retrieved.
+    #[must_use]
+    pub fn is_entire_chunk(&self) -> bool {
+        self.byte_ranges == [ByteRange::FromStart(0, None)]
+    }
+
+    /// Return the number of bytes that would be retrieved, or [`None`] if any byte range is unbounded.
+    #[must_use]
+    pub fn num_bytes(&self) -> Option<u64> {
+        self.byte_ranges
+            .iter()
+            .map(|byte_range| match byte_range {
+                ByteRange::FromStart(_, length) | ByteRange::FromEnd(_, length) => *length,
+            })
+            .sum()
+    }
+}
+
+/// The plan of a read of an array subset returned by [`Array::plan_retrieve`].
+///
+/// A read plan lists the store keys and byte ranges that would be retrieved to read an array subset.
+/// It can be used to prefetch, batch, or estimate the cost of a read before it is executed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReadPlan {
+    chunks: Vec<ChunkReadPlan>,
+}
+
+impl ReadPlan {
+    /// Return the read plans of the chunks intersecting the array subset, in C order.
+    #[must_use]
+    pub fn chunks(&self) -> &[ChunkReadPlan] {
+        &self.chunks
+    }
+
+    /// Returns true if no chunks would be retrieved.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Return the total number of byte ranges that would be retrieved.
+    #[must_use]
+    pub fn num_byte_ranges(&self) -> usize {
+        self.chunks
+            .iter()
+            .map(|chunk| chunk.byte_ranges.len())
+            .sum()
+    }
+
+    /// Return the total number of bytes that would be retrieved, or [`None`] if any byte range is unbounded.
+    ///
+    /// Entirely retrieved chunks have an unbounded byte range, use [`ReadableStorageTraits::size_key`] to get their size.
+    #[must_use]
+    pub fn num_bytes(&self) -> Option<u64> {
+        self.chunks.iter().map(ChunkReadPlan::num_bytes).sum()
+    }
+
+    fn new(mut chunks: Vec<ChunkReadPlan>) -> Self {
+        chunks.sort_by(|a, b| a.chunk_indices.cmp(&b.chunk_indices));
+        Self { chunks }
+    }
+}
+
+impl<TStorage: ?Sized> Array<TStorage> {
+    /// Return the chunks intersecting `array_subset` and the subset of each chunk that would be decoded.
+    fn plan_chunk_subsets(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<Vec<(ArrayIndices, ArraySubset)>, ArrayError> {
+        if array_subset.dimensionality() != self.dimensionality() {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        }
+        let Some(chunks) = self.chunks_in_array_subset(array_subset)? else {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        };
+        chunks
+            .indices()
+            .into_iter()
+            .map(|chunk_indices| {
+                let chunk_subset = self.chunk_subset(&chunk_indices)?;
+                let overlap = unsafe { chunk_subset.overlap_unchecked(array_subset) };
+                let chunk_subset = unsafe { overlap.relative_to_unchecked(chunk_subset.start()) };
+                Ok((chunk_indices, chunk_subset))
+            })
+            .collect()
+    }
+
+    /// Returns true if `chunk_subset` covers the entire chunk at `chunk_indices`, in which case the chunk is retrieved in full.
+    fn plan_is_entire_chunk(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+    ) -> Result<bool, ArrayError> {
+        let chunk_representation = self.chunk_array_representation(chunk_indices)?;
+        Ok(chunk_subset.shape() == chunk_representation.shape_u64())
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
+    /// Plan the retrieval of `array_subset` without retrieving it.
+    ///
+    /// The returned [`ReadPlan`] lists the store key and byte ranges of each chunk that [`retrieve_array_subset`](Array::retrieve_array_subset) would retrieve.
+    /// Chunks entirely within `array_subset` are retrieved in full.
+    /// Otherwise, the byte ranges are determined by the codecs with [`ArrayToBytesCodecTraits::partial_decode_byte_ranges`].
+    ///
+    /// Planning may itself retrieve some bytes, such as the shard index of each shard of an array with the `sharding` codec.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - `array_subset` is incompatible with the array,
+    ///  - the codecs fail to determine byte ranges, or
+    ///  - there is an underlying store error.
+    pub fn plan_retrieve(&self, array_subset: &ArraySubset) -> Result<ReadPlan, ArrayError> {
+        self.plan_retrieve_opt(array_subset, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`plan_retrieve`](Array::plan_retrieve).
+    ///
+    /// A shard index retrieved during planning is inserted into the shard index cache of `options` (if any), so a subsequent read with the same `options` will not retrieve it again.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn plan_retrieve_opt(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<ReadPlan, ArrayError> {
+        let chunk_subsets = self.plan_chunk_subsets(array_subset)?;
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_readable_transformer(storage_handle);
+
+        let plan_chunk = |(chunk_indices, chunk_subset): (ArrayIndices, ArraySubset)| {
+            let key = data_key(self.path(), &chunk_indices, self.chunk_key_encoding());
+            let byte_ranges = if self.plan_is_entire_chunk(&chunk_indices, &chunk_subset)? {
+                None
+            } else {
+                let input_handle =
+                    StoragePartialDecoder::new(storage_transformer.clone(), key.clone());
+                self.codecs().partial_decode_byte_ranges(
+                    &input_handle,
+                    &self.chunk_array_representation(&chunk_indices)?,
+                    &[chunk_subset],
+                    options,
+                )?
+            };
+            Ok::<_, ArrayError>(ChunkReadPlan {
+                chunk_indices,
+                key,
+                byte_ranges: byte_ranges.unwrap_or_else(|| vec![ByteRange::FromStart(0, None)]),
+            })
+        };
+        let chunks = iter_concurrent_limit!(
+            options.concurrent_target(),
+            chunk_subsets.into_par_iter(),
+            map,
+            plan_chunk
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(ReadPlan::new(chunks))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
+    /// Async variant of [`plan_retrieve`](Array::plan_retrieve).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_plan_retrieve(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<ReadPlan, ArrayError> {
+        self.async_plan_retrieve_opt(array_subset, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`plan_retrieve_opt`](Array::plan_retrieve_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_plan_retrieve_opt(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<ReadPlan, ArrayError> {
+        use futures::{StreamExt, TryStreamExt};
+
+        let chunk_subsets = self.plan_chunk_subsets(array_subset)?;
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_readable_transformer(storage_handle);
+
+        let storage_transformer = &storage_transformer;
+        let plan_chunk = |(chunk_indices, chunk_subset): (ArrayIndices, ArraySubset)| async move {
+            let key = data_key(self.path(), &chunk_indices, self.chunk_key_encoding());
+            let byte_ranges = if self.plan_is_entire_chunk(&chunk_indices, &chunk_subset)? {
+                None
+            } else {
+                let input_handle =
+                    AsyncStoragePartialDecoder::new(storage_transformer.clone(), key.clone());
+                self.codecs()
+                    .async_partial_decode_byte_ranges(
+                        &input_handle,
+                        &self.chunk_array_representation(&chunk_indices)?,
+                        &[chunk_subset],
+                        options,
+                    )
+                    .await?
+            };
+            Ok::<_, ArrayError>(ChunkReadPlan {
+                chunk_indices,
+                key,
+                byte_ranges: byte_ranges.unwrap_or_else(|| vec![ByteRange::FromStart(0, None)]),
+            })
+        };
+        let chunks = futures::stream::iter(chunk_subsets.into_iter().map(plan_chunk))
+            .buffered(options.concurrent_target().max(1))
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(ReadPlan::new(chunks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_plan_retrieve() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/array")
+        .unwrap();
+        array
+            .store_array_subset_elements(
+                &ArraySubset::new_with_ranges(&[0..8, 0..8]),
+                (0..64).collect::<Vec<u8>>(),
+            )
+            .unwrap();
+
+        let plan = array
+            .plan_retrieve(&ArraySubset::new_with_ranges(&[1..4, 2..6]))
+            .unwrap();
+        assert_eq!(plan.chunks().len(), 2);
+        assert_eq!(plan.chunks()[0].chunk_indices(), &[0, 0]);
+        assert_eq!(
+            plan.chunks()[0].key(),
+            &data_key(array.path(), &[0, 0], array.chunk_key_encoding())
+        );
+        assert_eq!(
+            plan.chunks()[0].byte_ranges(),
+            &[
+                ByteRange::FromStart(6, Some(2)),
+                ByteRange::FromStart(10, Some(2)),
+                ByteRange::FromStart(14, Some(2))
+            ]
+        );
+        assert_eq!(plan.chunks()[1].chunk_indices(), &[0, 1]);
+        assert_eq!(plan.num_byte_ranges(), 6);
+        assert_eq!(plan.num_bytes(), Some(12));
+
+        let plan = array
+            .plan_retrieve(&ArraySubset::new_with_ranges(&[0..4, 0..8]))
+            .unwrap();
+        assert_eq!(plan.chunks().len(), 2);
+        assert!(plan.chunks().iter().all(ChunkReadPlan::is_entire_chunk));
+        assert_eq!(plan.num_bytes(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "sharding")]
+    fn array_plan_retrieve_sharded() {
+        use crate::array::codec::{
+            array_to_bytes::sharding::ShardingCodecBuilder, ShardIndexCache,
+        };
+
+        let store = Arc::new(MemoryStore::new());
+        let sharding_codec = ShardingCodecBuilder::new(vec![2, 2].try_into().unwrap()).build();
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .array_to_bytes_codec(Box::new(sharding_codec))
+        .build(store, "/array")
+        .unwrap();
+        array
+            .store_array_subset_elements(
+                &ArraySubset::new_with_ranges(&[0..2, 0..4]),
+                (1..=8).collect::<Vec<u8>>(),
+            )
+            .unwrap();
+
+        // Inner chunk [0, 1] of shard [0, 0] is stored, inner chunk [1, 1] is the fill value
+        let plan = array
+            .plan_retrieve(&ArraySubset::new_with_ranges(&[1..3, 3..4]))
+            .unwrap();
+        assert_eq!(plan.chunks().len(), 1);
+        let byte_ranges = plan.chunks()[0].byte_ranges();
+        assert_eq!(byte_ranges.len(), 2);
+        // The shard index is at the end of the shard, and has 4 inner chunks with an offset and size (+ the crc32c checksum)
+        assert!(matches!(byte_ranges[0], ByteRange::FromEnd(0, Some(_))));
+        assert!(matches!(byte_ranges[1], ByteRange::FromStart(_, Some(1))));
+
+        // A shard index in the cache is not retrieved again
+        let options = CodecOptions::builder()
+            .shard_index_cache(ShardIndexCache::new(
+                std::num::NonZeroUsize::new(8).unwrap(),
+            ))
+            .build();
+        let subset = ArraySubset::new_with_ranges(&[0..1, 0..1]);
+        let plan = array.plan_retrieve_opt(&subset, &options).unwrap();
+        assert_eq!(plan.num_byte_ranges(), 2);
+        let plan = array.plan_retrieve_opt(&subset, &options).unwrap();
+        assert_eq!(plan.num_byte_ranges(), 1);
+        assert_eq!(plan.num_bytes(), Some(1));
+    }
+}
@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+use crate::storage::{ListableStorageTraits, ReadableWritableStorageTraits, StorageHandle};
+
+#[cfg(feature = "async")]
+use crate::storage::{AsyncListableStorageTraits, AsyncReadableWritableStorageTraits};
+
+use super::{
+    codec::{options::ProgressTracker, ArrayCodecTraits, CodecChain, CodecOptions},
+    concurrency::concurrency_chunks_and_codec,
+    Array, ArrayError, ArrayIndices,
+};
+
+impl<TStorage: ?Sized + ReadableWritableStorageTraits + ListableStorageTraits + 'static>
+    Array<TStorage>
+{
+    /// Re-encode all stored chunks with `codecs`, set the codecs of the array, and store the updated metadata.
+    ///
+    /// This migrates an existing array to a new codec chain, e.g. to change its compressor.
+    /// Each stored chunk is decoded with the current codecs and encoded with `codecs` in place, and the metadata is stored once all chunks have been rewritten.
+    /// The array should not be accessed concurrently, as the stored chunks are a mix of the current and new codecs until this completes.
+    /// Chunks are processed in parallel, subject to the concurrency target of the codec options.
+    ///
+    /// Use [`set_codecs`](Array::set_codecs) instead to change the codecs without rewriting stored chunks (e.g. for an array with no stored chunks).
+    /// Returns the number of recompressed chunks.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - `codecs` are incompatible with the data type or chunk grid of the array,
+    ///  - there is a codec encoding or decoding error, or
+    ///  - an underlying store error.
+    pub fn recompress_chunks(&mut self, codecs: CodecChain) -> Result<usize, ArrayError> {
+        self.recompress_chunks_opt(codecs, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`recompress_chunks`](Array::recompress_chunks).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn recompress_chunks_opt(
+        &mut self,
+        codecs: CodecChain,
+        options: &CodecOptions,
+    ) -> Result<usize, ArrayError> {
+        let chunks = self.list_stored_chunks()?;
+        let num_chunks = chunks.len();
+        let progress = ProgressTracker::new(options, num_chunks);
+        progress.check_cancelled()?;
+
+        // Calculate chunk/codec concurrency
+        let chunk_representation =
+            self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+        let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+        let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+            options.concurrent_target(),
+            num_chunks,
+            options,
+            &codec_concurrency,
+            self.chunk_memory_usage(&chunk_representation)?,
+        );
+
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_writable_transformer(storage_handle);
+        let recompress_chunk = |chunk_indices: ArrayIndices| {
+            progress.check_cancelled()?;
+            let chunk_bytes = self.retrieve_chunk_opt(&chunk_indices, &options)?;
+            let chunk_bytes_len = chunk_bytes.len() as u64;
+            let chunk_representation = self.chunk_array_representation(&chunk_indices)?;
+            let chunk_encoded = codecs
+                .encode(chunk_bytes, &chunk_representation, &options)
+                .map_err(ArrayError::CodecError)?;
+            crate::storage::store_chunk(
+                &*storage_transformer,
+                self.path(),
+                &chunk_indices,
+                self.chunk_key_encoding(),
+                &chunk_encoded,
+            )?;
+            progress.chunk_completed(chunk_bytes_len);
+            Ok::<_, ArrayError>(())
+        };
+        iter_concurrent_limit!(
+            chunk_concurrent_limit,
+            chunks.into_par_iter(),
+            try_for_each,
+            recompress_chunk
+        )?;
+
+        self.set_codecs(codecs);
+        self.store_metadata()?;
+        Ok(num_chunks)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<
+        TStorage: ?Sized + AsyncReadableWritableStorageTraits + AsyncListableStorageTraits + 'static,
+    > Array<TStorage>
+{
+    /// Async variant of [`recompress_chunks`](Array::recompress_chunks).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_recompress_chunks(
+        &mut self,
+        codecs: CodecChain,
+    ) -> Result<usize, ArrayError> {
+        self.async_recompress_chunks_opt(codecs, &CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`recompress_chunks_opt`](Array::recompress_chunks_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_recompress_chunks_opt(
+        &mut self,
+        codecs: CodecChain,
+        options: &CodecOptions,
+    ) -> Result<usize, ArrayError> {
+        let chunks = self.async_list_stored_chunks().await?;
+        let num_chunks = chunks.len();
+        let progress = ProgressTracker::new(options, num_chunks);
+
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_writable_transformer(storage_handle);
+        for chunk_indices in &chunks {
+            progress.check_cancelled()?;
+            let chunk_bytes = self
+                .async_retrieve_chunk_opt(chunk_indices, options)
+                .await?;
+            let chunk_bytes_len = chunk_bytes.len() as u64;
+            let chunk_representation = self.chunk_array_representation(chunk_indices)?;
+            let chunk_encoded = codecs
+                .encode(chunk_bytes, &chunk_representation, options)
+                .map_err(ArrayError::CodecError)?;
+            crate::storage::async_store_chunk(
+                &*storage_transformer,
+                self.path(),
+                chunk_indices,
+                self.chunk_key_encoding(),
+                chunk_encoded.into(),
+            )
+            .await?;
+            progress.chunk_completed(chunk_bytes_len);
+        }
+
+        self.set_codecs(codecs);
+        self.async_store_metadata().await?;
+        Ok(num_chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{codec::BytesCodec, ArrayBuilder, DataType, FillValue},
+        array_subset::ArraySubset,
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_recompress_chunks() {
+        let store = Arc::new(MemoryStore::new());
+        let mut array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt16,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        let elements: Vec<u16> = (0..48).collect();
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_ranges(&[0..6, 0..8]), elements)
+            .unwrap();
+
+        let codecs = CodecChain::new(vec![], Box::new(BytesCodec::big()), vec![]);
+        assert_eq!(array.recompress_chunks(codecs).unwrap(), 4);
+        let chunk_encoded = array.retrieve_encoded_chunk(&[0, 1]).unwrap().unwrap();
+        assert_eq!(
+            chunk_encoded,
+            [4u16, 5, 6, 7, 12, 13, 14, 15, 20, 21, 22, 23, 28, 29, 30, 31]
+                .iter()
+                .flat_map(|element| element.to_be_bytes())
+                .collect::<Vec<u8>>()
+        );
+
+        let array = Array::new(store, "/array").unwrap();
+        assert_eq!(
+            array.codecs().create_metadatas(),
+            CodecChain::new(vec![], Box::new(BytesCodec::big()), vec![]).create_metadatas()
+        );
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<u16>(&ArraySubset::new_with_shape(vec![8, 8]))
+                .unwrap(),
+            [(0..48).collect::<Vec<u16>>(), vec![0; 16]].concat()
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::Mutex;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+use crate::{array_subset::ArraySubset, storage::ReadableWritableStorageTraits};
+
+use super::{
+    codec::CodecOptions, concurrency::concurrency_chunks_and_codec, Array, ArrayError,
+    ArrayIndices, Element,
+};
+
+type StagedChunk = Arc<Mutex<Vec<u8>>>;
+
+/// A write-back staging layer for storing array subsets.
+///
+/// [`Array::store_array_subset`] decodes, updates, encodes, and stores every chunk intersecting the array subset on each call.
+/// This is costly if subsets are stored incrementally and many of them intersect the same chunk, such as the outer chunks (shards) of an array with the sharding codec.
+///
+/// A [`StagedArrayWriter`] instead holds the decoded bytes of each chunk written to in memory.
+/// A chunk is retrieved and decoded the first time it is written to, and subsequent writes update the staged chunk in place.
+/// Staged chunks are only encoded and stored on [`flush`](StagedArrayWriter::flush).
+///
+/// Staged writes are not visible to readers of the array until they are flushed.
+/// Staged chunks that are not flushed are discarded when the [`StagedArrayWriter`] is dropped.
+/// Flushing a staged chunk overwrites any changes made to that chunk in the store since it was staged.
+pub struct StagedArrayWriter<'a, TStorage: ?Sized> {
+    array: &'a Array<TStorage>,
+    staged_chunks: Mutex<HashMap<ArrayIndices, StagedChunk>>,
+}
+
+impl<'a, TStorage: ?Sized + ReadableWritableStorageTraits + 'static>
+    StagedArrayWriter<'a, TStorage>
+{
+    /// Create a new staged writer for `array`.
+    #[must_use]
+    pub fn new(array: &'a Array<TStorage>) -> Self {
+        Self {
+            array,
+            staged_chunks: Mutex::default(),
+        }
+    }
+
+    /// Return the array.
+    #[must_use]
+    pub const fn array(&self) -> &Array<TStorage> {
+        self.array
+    }
+
+    /// Return the number of staged chunks.
+    #[must_use]
+    pub fn num_staged_chunks(&self) -> usize {
+        self.staged_chunks.lock().len()
+    }
+
+    /// Return the staged chunk at `chunk_indices`, retrieving it from the array if it is not yet staged.
+    fn staged_chunk(
+        &self,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<StagedChunk, ArrayError> {
+        if let Some(staged_chunk) = self.staged_chunks.lock().get(chunk_indices) {
+            return Ok(staged_chunk.clone());
+        }
+        let chunk_bytes = self.array.retrieve_chunk_opt(chunk_indices, options)?;
+        Ok(self
+            .staged_chunks
+            .lock()
+            .entry(chunk_indices.to_vec())
+            .or_insert_with(|| Arc::new(Mutex::new(chunk_bytes)))
+            .clone())
+    }
+
+    /// Stage `subset_bytes` for storing in `array_subset` with default codec options.
+    ///
+    /// Use [`store_array_subset_opt`](StagedArrayWriter::store_array_subset_opt) to control codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the dimensionality of `array_subset` does not match the chunk grid dimensionality,
+    ///  - the length of `subset_bytes` does not match the expected length governed by the shape of the array subset and the data type size,
+    ///  - there is a codec decoding error, or
+    ///  - an underlying store error.
+    pub fn store_array_subset(
+        &self,
+        array_subset: &ArraySubset,
+        subset_bytes: Vec<u8>,
+    ) -> Result<(), ArrayError> {
+        self.store_array_subset_opt(array_subset, subset_bytes, &CodecOptions::default())
+    }
+
+    /// Stage `subset_elements` for storing in `array_subset` with default codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the size of `T` does not match the data type size or a [`store_array_subset`](StagedArrayWriter::store_array_subset) error condition is met.
+    pub fn store_array_subset_elements<T: Element>(
+        &self,
+        array_subset: &ArraySubset,
+        subset_elements: Vec<T>,
+    ) -> Result<(), ArrayError> {
+        self.store_array_subset_elements_opt(
+            array_subset,
+            subset_elements,
+            &CodecOptions::default(),
+        )
+    }
+
+    /// Explicit options version of [`store_array_subset`](StagedArrayWriter::store_array_subset).
+    #[allow(clippy::missing_errors_doc, clippy::needless_pass_by_value)]
+    pub fn store_array_subset_opt(
+        &self,
+        array_subset: &ArraySubset,
+        subset_bytes: Vec<u8>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let array = self.array;
+
+        // Validation
+        if array_subset.dimensionality() != array.dimensionality() {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                array.shape().to_vec(),
+            ));
+        }
+        let element_size = array.data_type().size();
+        let expected_size = array_subset.num_elements() * element_size as u64;
+        if subset_bytes.len() as u64 != expected_size {
+            return Err(ArrayError::InvalidBytesInputSize(
+                subset_bytes.len(),
+                expected_size,
+            ));
+        }
+
+        // Find the chunks intersecting this array subset
+        let Some(chunks) = array.chunks_in_array_subset(array_subset)? else {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                array.shape().to_vec(),
+            ));
+        };
+        let num_chunks = chunks.num_elements_usize();
+
+        // Calculate chunk/codec concurrency
+        let chunk_representation =
+            array.chunk_array_representation(&vec![0; array.dimensionality()])?;
+        let codec_concurrency = array.recommended_codec_concurrency(&chunk_representation)?;
+        let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+            options.concurrent_target(),
+            num_chunks,
+            options,
+            &codec_concurrency,
+            array.chunk_memory_usage(&chunk_representation)?,
+        );
+
+        let stage_chunk = |chunk_indices: ArrayIndices| -> Result<(), ArrayError> {
+            let chunk_subset_in_array = array.chunk_subset(&chunk_indices)?;
+            let overlap = unsafe { array_subset.overlap_unchecked(&chunk_subset_in_array) };
+            let chunk_subset_in_array_subset =
+                unsafe { overlap.relative_to_unchecked(array_subset.start()) };
+            let array_subset_in_chunk_subset =
+                unsafe { overlap.relative_to_unchecked(chunk_subset_in_array.start()) };
+            let chunk_subset_bytes = unsafe {
+                chunk_subset_in_array_subset.extract_bytes_unchecked(
+                    &subset_bytes,
+                    array_subset.shape(),
+                    element_size,
+                )
+            };
+
+            let staged_chunk = self.staged_chunk(&chunk_indices, &options)?;
+            let mut chunk_bytes = staged_chunk.lock();
+            unsafe {
+                array_subset_in_chunk_subset.store_bytes_unchecked(
+                    &chunk_subset_bytes,
+                    &mut chunk_bytes,
+                    chunk_subset_in_array.shape(),
+                    element_size,
+                );
+            }
+            Ok(())
+        };
+        let indices = chunks.indices();
+        iter_concurrent_limit!(
+            chunk_concurrent_limit,
+            indices.into_par_iter(),
+            try_for_each,
+            stage_chunk
+        )
+    }
+
+    /// Explicit options version of [`store_array_subset_elements`](StagedArrayWriter::store_array_subset_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_array_subset_elements_opt<T: Element>(
+        &self,
+        array_subset: &ArraySubset,
+        subset_elements: Vec<T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        if self.array.data_type().size() != std::mem::size_of::<T>() {
+            return Err(ArrayError::IncompatibleElementSize(
+                self.array.data_type().size(),
+                std::mem::size_of::<T>(),
+            ));
+        }
+        let subset_bytes = super::transmute_to_bytes_vec(subset_elements);
+        self.store_array_subset_opt(array_subset, subset_bytes, options)
+    }
+
+    /// Encode and store all staged chunks with default codec options.
+    ///
+    /// Staged chunks are cleared even if an error occurs.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if there is a codec encoding error or an underlying store error.
+    pub fn flush(&self) -> Result<(), ArrayError> {
+        self.flush_opt(&CodecOptions::default())
+    }
+
+    /// Explicit options version of [`flush`](StagedArrayWriter::flush).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn flush_opt(&self, options: &CodecOptions) -> Result<(), ArrayError> {
+        let staged_chunks: Vec<_> = std::mem::take(&mut *self.staged_chunks.lock())
+            .into_iter()
+            .collect();
+        let num_chunks = staged_chunks.len();
+        if num_chunks == 0 {
+            return Ok(());
+        }
+
+        // Calculate chunk/codec concurrency
+        let array = self.array;
+        let chunk_representation =
+            array.chunk_array_representation(&vec![0; array.dimensionality()])?;
+        let codec_concurrency = array.recommended_codec_concurrency(&chunk_representation)?;
+        let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+            options.concurrent_target(),
+            num_chunks,
+            options,
+            &codec_concurrency,
+            array.chunk_memory_usage(&chunk_representation)?,
+        );
+
+        let store_chunk = |(chunk_indices, staged_chunk): (ArrayIndices, StagedChunk)| {
+            let chunk_bytes = std::mem::take(&mut *staged_chunk.lock());
+            array.store_chunk_opt(&chunk_indices, chunk_bytes, &options)
+        };
+        iter_concurrent_limit!(
+            chunk_concurrent_limit,
+            staged_chunks.into_par_iter(),
+            try_for_each,
+            store_chunk
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "sharding")]
+    fn array_staged_writer() {
+        use crate::array::codec::array_to_bytes::sharding::ShardingCodecBuilder;
+
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 8].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .array_to_bytes_codec(Box::new(
+            ShardingCodecBuilder::new(vec![2, 2].try_into().unwrap()).build(),
+        ))
+        .build(store, "/")
+        .unwrap();
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_ranges(&[0..1, 0..8]), vec![1u8; 8])
+            .unwrap();
+
+        let writer = StagedArrayWriter::new(&array);
+        assert_eq!(writer.array().shape(), &[8, 8]);
+        for i in 0..8 {
+            writer
+                .store_array_subset_elements(
+                    &ArraySubset::new_with_ranges(&[i..i + 1, 2..6]),
+                    vec![2u8; 4],
+                )
+                .unwrap();
+        }
+        assert_eq!(writer.num_staged_chunks(), 2);
+        assert!(writer
+            .store_array_subset_elements(&ArraySubset::new_with_ranges(&[0..1, 0..1]), vec![0u16])
+            .is_err());
+
+        // Staged writes are not visible until flushed
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<u8>(&ArraySubset::new_with_ranges(&[1..2, 0..8]))
+                .unwrap(),
+            vec![0; 8]
+        );
+        writer.flush().unwrap();
+        assert_eq!(writer.num_staged_chunks(), 0);
+
+        let elements = array
+            .retrieve_array_subset_elements::<u8>(&ArraySubset::new_with_shape(vec![8, 8]))
+            .unwrap();
+        assert_eq!(&elements[0..8], &[1, 1, 2, 2, 2, 2, 1, 1]);
+        for i in 1..8 {
+            assert_eq!(&elements[i * 8..(i + 1) * 8], &[0, 0, 2, 2, 2, 2, 0, 0]);
+        }
+    }
+}
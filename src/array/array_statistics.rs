@@ -0,0 +1,515 @@
+use std::{num::NonZeroUsize, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{
+    ready, statistics_prefix, ListableStorageTraits, ReadableStorageTraits, StorageError,
+    StorageHandle, StoreKey,
+};
+
+#[cfg(feature = "async")]
+use crate::storage::{AsyncListableStorageTraits, AsyncReadableStorageTraits};
+
+use super::{Array, ArrayError, DataType};
+
+/// The attribute holding the aggregate array statistics in array metadata.
+pub(crate) const STATISTICS_ATTRIBUTE: &str = "_zarrs_statistics";
+
+/// Equal width histogram bins for array statistics and [`Array::stored_chunk_size_histogram`](super::Array::stored_chunk_size_histogram).
+///
+/// The bins span the half-open interval `[start, end)`, except the last bin which also includes `end`.
+/// Elements outside of this interval are not counted in the histogram.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HistogramBins {
+    start: f64,
+    end: f64,
+    num_bins: NonZeroUsize,
+}
+
+impl HistogramBins {
+    /// Create histogram bins with `num_bins` bins spanning `start` to `end`.
+    ///
+    /// Returns [`None`] if `start` or `end` are not finite, or if `start` is not less than `end`.
+    #[must_use]
+    pub fn new(start: f64, end: f64, num_bins: NonZeroUsize) -> Option<Self> {
+        if start.is_finite() && end.is_finite() && start < end {
+            Some(Self {
+                start,
+                end,
+                num_bins,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Return the start of the first bin.
+    #[must_use]
+    pub const fn start(&self) -> f64 {
+        self.start
+    }
+
+    /// Return the end of the last bin.
+    #[must_use]
+    pub const fn end(&self) -> f64 {
+        self.end
+    }
+
+    /// Return the number of bins.
+    #[must_use]
+    pub const fn num_bins(&self) -> NonZeroUsize {
+        self.num_bins
+    }
+
+    /// Return the index of the bin containing `value`, or [`None`] if it is outside of the bins.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub(super) fn bin(&self, value: f64) -> Option<usize> {
+        if value < self.start || value > self.end {
+            return None;
+        }
+        let num_bins = self.num_bins.get();
+        let bin = ((value - self.start) / (self.end - self.start) * num_bins as f64) as usize;
+        Some(bin.min(num_bins - 1))
+    }
+}
+
+/// Statistics of the elements of a chunk.
+///
+/// `NaN` elements and elements of edge chunks outside of the array shape are excluded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkStatistics {
+    count: u64,
+    min: Option<f64>,
+    max: Option<f64>,
+    sum: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    histogram: Option<Vec<u64>>,
+}
+
+impl ChunkStatistics {
+    /// Compute the statistics of `bytes` with data type `data_type`.
+    ///
+    /// Returns [`None`] if statistics are not supported for `data_type`.
+    /// Statistics are supported for integer and floating point data types.
+    pub(crate) fn compute(
+        bytes: &[u8],
+        data_type: &DataType,
+        histogram_bins: Option<&HistogramBins>,
+    ) -> Option<Self> {
+        macro_rules! values {
+            ( $t:ty ) => {
+                bytes
+                    .chunks_exact(std::mem::size_of::<$t>())
+                    .map(|element| <$t>::from_ne_bytes(element.try_into().unwrap()))
+            };
+        }
+        #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+        let statistics = match data_type {
+            DataType::Int8 => Self::from_values(values!(i8).map(|v| v as f64), histogram_bins),
+            DataType::Int16 => Self::from_values(values!(i16).map(|v| v as f64), histogram_bins),
+            DataType::Int32 => Self::from_values(values!(i32).map(|v| v as f64), histogram_bins),
+            DataType::Int64 => Self::from_values(values!(i64).map(|v| v as f64), histogram_bins),
+            DataType::UInt8 => Self::from_values(values!(u8).map(|v| v as f64), histogram_bins),
+            DataType::UInt16 => Self::from_values(values!(u16).map(|v| v as f64), histogram_bins),
+            DataType::UInt32 => Self::from_values(values!(u32).map(|v| v as f64), histogram_bins),
+            DataType::UInt64 => Self::from_values(values!(u64).map(|v| v as f64), histogram_bins),
+            DataType::Float16 => {
+                Self::from_values(values!(half::f16).map(half::f16::to_f64), histogram_bins)
+            }
+            DataType::BFloat16 => {
+                Self::from_values(values!(half::bf16).map(half::bf16::to_f64), histogram_bins)
+            }
+            DataType::Float32 => Self::from_values(values!(f32).map(f64::from), histogram_bins),
+            DataType::Float64 => Self::from_values(values!(f64), histogram_bins),
+            _ => return None,
+        };
+        Some(statistics)
+    }
+
+    fn from_values(
+        values: impl Iterator<Item = f64>,
+        histogram_bins: Option<&HistogramBins>,
+    ) -> Self {
+        let mut statistics = Self {
+            count: 0,
+            min: None,
+            max: None,
+            sum: 0.0,
+            histogram: histogram_bins.map(|bins| vec![0; bins.num_bins().get()]),
+        };
+        for value in values.filter(|value| !value.is_nan()) {
+            statistics.count += 1;
+            statistics.min = Some(statistics.min.map_or(value, |min| min.min(value)));
+            statistics.max = Some(statistics.max.map_or(value, |max| max.max(value)));
+            statistics.sum += value;
+            if let (Some(histogram), Some(bin)) = (
+                statistics.histogram.as_mut(),
+                histogram_bins.and_then(|bins| bins.bin(value)),
+            ) {
+                histogram[bin] += 1;
+            }
+        }
+        statistics
+    }
+
+    /// Return the number of elements, excluding `NaN` elements.
+    #[must_use]
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Return the minimum element, or [`None`] if there are no elements.
+    #[must_use]
+    pub const fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    /// Return the maximum element, or [`None`] if there are no elements.
+    #[must_use]
+    pub const fn max(&self) -> Option<f64> {
+        self.max
+    }
+
+    /// Return the sum of the elements.
+    #[must_use]
+    pub const fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Return the mean of the elements, or [`None`] if there are no elements.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+
+    /// Return the histogram of the elements, or [`None`] if histogram bins are not configured.
+    #[must_use]
+    pub fn histogram(&self) -> Option<&[u64]> {
+        self.histogram.as_deref()
+    }
+}
+
+/// Statistics of the elements of an array.
+///
+/// Array statistics are aggregated from the [`ChunkStatistics`] of each stored chunk by [`Array::update_statistics`].
+/// Elements of chunks which are not stored (i.e. chunks entirely composed of the fill value) are excluded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArrayStatistics {
+    num_chunks: usize,
+    count: u64,
+    min: Option<f64>,
+    max: Option<f64>,
+    sum: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    histogram_bins: Option<HistogramBins>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    histogram: Option<Vec<u64>>,
+}
+
+impl ArrayStatistics {
+    /// Create empty array statistics with optional histogram bins.
+    pub(crate) fn new(histogram_bins: Option<HistogramBins>) -> Self {
+        Self {
+            num_chunks: 0,
+            count: 0,
+            min: None,
+            max: None,
+            sum: 0.0,
+            histogram_bins,
+            histogram: histogram_bins.map(|bins| vec![0; bins.num_bins().get()]),
+        }
+    }
+
+    /// Parse array statistics from the value of the statistics attribute.
+    pub(crate) fn from_value(value: serde_json::Value) -> Option<Self> {
+        serde_json::from_value(value).ok()
+    }
+
+    /// Serialise array statistics to the value of the statistics attribute.
+    pub(crate) fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// Add the statistics of a chunk.
+    fn add_chunk(&mut self, chunk: &ChunkStatistics) {
+        self.num_chunks += 1;
+        self.count += chunk.count;
+        self.sum += chunk.sum;
+        if let Some(min) = chunk.min {
+            self.min = Some(self.min.map_or(min, |m| m.min(min)));
+        }
+        if let Some(max) = chunk.max {
+            self.max = Some(self.max.map_or(max, |m| m.max(max)));
+        }
+        if let (Some(histogram), Some(chunk_histogram)) =
+            (self.histogram.as_mut(), chunk.histogram.as_ref())
+        {
+            for (count, chunk_count) in std::iter::zip(histogram.iter_mut(), chunk_histogram) {
+                *count += chunk_count;
+            }
+        }
+    }
+
+    /// Return the number of chunks with statistics.
+    #[must_use]
+    pub const fn num_chunks(&self) -> usize {
+        self.num_chunks
+    }
+
+    /// Return the number of elements, excluding `NaN` elements.
+    #[must_use]
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Return the minimum element, or [`None`] if there are no elements.
+    #[must_use]
+    pub const fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    /// Return the maximum element, or [`None`] if there are no elements.
+    #[must_use]
+    pub const fn max(&self) -> Option<f64> {
+        self.max
+    }
+
+    /// Return the sum of the elements.
+    #[must_use]
+    pub const fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Return the mean of the elements, or [`None`] if there are no elements.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+
+    /// Return the histogram bins, or [`None`] if histogram bins are not configured.
+    #[must_use]
+    pub const fn histogram_bins(&self) -> Option<&HistogramBins> {
+        self.histogram_bins.as_ref()
+    }
+
+    /// Return the histogram of the elements, or [`None`] if histogram bins are not configured.
+    #[must_use]
+    pub fn histogram(&self) -> Option<&[u64]> {
+        self.histogram.as_deref()
+    }
+}
+
+/// Deserialise the chunk statistics at `key`.
+pub(super) fn chunk_statistics_from_json(
+    key: &StoreKey,
+    bytes: &[u8],
+) -> Result<ChunkStatistics, ArrayError> {
+    serde_json::from_slice(bytes)
+        .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()).into())
+}
+
+/// Serialise chunk statistics.
+pub(super) fn chunk_statistics_to_json(chunk_statistics: &ChunkStatistics) -> Vec<u8> {
+    serde_json::to_vec(chunk_statistics).unwrap_or_default()
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
+    /// Return the statistics of the chunk at `chunk_indices`.
+    ///
+    /// Returns [`None`] if the chunk has no statistics (e.g. it has not been stored by an array computing statistics, or the data type does not support statistics).
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the chunk statistics are invalid or there is an underlying store error.
+    pub fn chunk_statistics(
+        &self,
+        chunk_indices: &[u64],
+    ) -> Result<Option<ChunkStatistics>, ArrayError> {
+        ready(self.maybe_async_chunk_statistics(&self.sync_readable_storage(), chunk_indices))
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits + 'static> Array<TStorage> {
+    /// Update the [`statistics`](Array::statistics) of the array by aggregating the statistics of each stored chunk.
+    ///
+    /// The updated statistics are written to the array metadata by [`store_metadata`](Array::store_metadata).
+    /// This is a no-op if statistics are not computed.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if any chunk statistics are invalid or there is an underlying store error.
+    pub fn update_statistics(&mut self) -> Result<(), ArrayError> {
+        let Some(statistics) = &self.statistics else {
+            return Ok(());
+        };
+        let mut statistics = ArrayStatistics::new(statistics.histogram_bins);
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_readable_listable_transformer(storage_handle);
+        for key in storage_transformer.list_prefix(&statistics_prefix(self.path()))? {
+            // Chunk statistics may be erased after they are listed
+            if let Some(bytes) = storage_transformer.get(&key)? {
+                statistics.add_chunk(&chunk_statistics_from_json(&key, &bytes)?);
+            }
+        }
+        self.statistics = Some(statistics);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
+    /// Async variant of [`chunk_statistics`](Array::chunk_statistics).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_chunk_statistics(
+        &self,
+        chunk_indices: &[u64],
+    ) -> Result<Option<ChunkStatistics>, ArrayError> {
+        self.maybe_async_chunk_statistics(&self.async_readable_storage(), chunk_indices)
+            .await
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits + 'static>
+    Array<TStorage>
+{
+    /// Async variant of [`update_statistics`](Array::update_statistics).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_update_statistics(&mut self) -> Result<(), ArrayError> {
+        let Some(statistics) = &self.statistics else {
+            return Ok(());
+        };
+        let mut statistics = ArrayStatistics::new(statistics.histogram_bins);
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_readable_listable_transformer(storage_handle);
+        for key in storage_transformer
+            .list_prefix(&statistics_prefix(self.path()))
+            .await?
+        {
+            // Chunk statistics may be erased after they are listed
+            if let Some(bytes) = storage_transformer.get(&key).await? {
+                statistics.add_chunk(&chunk_statistics_from_json(&key, &bytes)?);
+            }
+        }
+        self.statistics = Some(statistics);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{Array, ArrayBuilder, FillValue},
+        array_subset::ArraySubset,
+        storage::{store::MemoryStore, ReadableStorageTraits},
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_statistics() {
+        let store = Arc::new(MemoryStore::new());
+        let bins = HistogramBins::new(0.0, 10.0, NonZeroUsize::new(2).unwrap()).unwrap();
+        assert!(HistogramBins::new(1.0, 1.0, NonZeroUsize::new(2).unwrap()).is_none());
+        let mut array = ArrayBuilder::new(
+            vec![3, 4],
+            DataType::Float32,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0.0f32),
+        )
+        .compute_statistics(true)
+        .statistics_histogram_bins(Some(bins))
+        .build(store.clone(), "/array")
+        .unwrap();
+        assert_eq!(array.statistics().unwrap().num_chunks(), 0);
+
+        array
+            .store_array_subset_elements::<f32>(
+                &ArraySubset::new_with_shape(vec![3, 4]),
+                vec![
+                    1.0,
+                    2.0,
+                    0.0,
+                    0.0, //
+                    3.0,
+                    f32::NAN,
+                    0.0,
+                    0.0, //
+                    4.0,
+                    5.0,
+                    6.0,
+                    20.0,
+                ],
+            )
+            .unwrap();
+        let chunk_statistics = array.chunk_statistics(&[0, 0]).unwrap().unwrap();
+        assert_eq!(chunk_statistics.count(), 3);
+        assert_eq!(chunk_statistics.mean(), Some(2.0));
+        assert!(array.chunk_statistics(&[0, 1]).unwrap().is_none()); // fill value chunk not stored
+        let chunk_statistics = array.chunk_statistics(&[1, 1]).unwrap().unwrap();
+        assert_eq!(chunk_statistics.count(), 2); // excludes elements outside the array
+        assert!(store
+            .get(&StoreKey::new("array/zarrs_statistics/c/0/0.json").unwrap())
+            .unwrap()
+            .is_some());
+
+        // Array statistics are aggregated from the stored chunk statistics on request
+        assert_eq!(array.statistics().unwrap().num_chunks(), 0);
+        array.update_statistics().unwrap();
+        let statistics = array.statistics().unwrap().clone();
+        assert_eq!(statistics.num_chunks(), 3);
+        assert_eq!(statistics.count(), 7);
+        assert_eq!(statistics.min(), Some(1.0));
+        assert_eq!(statistics.max(), Some(20.0));
+        assert_eq!(statistics.mean(), Some(41.0 / 7.0));
+        assert_eq!(statistics.histogram_bins(), Some(&bins));
+        assert_eq!(statistics.histogram(), Some([4, 2].as_slice()));
+
+        // Only the aggregate statistics are stored in the attributes and restored on opening the array
+        array.store_metadata().unwrap();
+        let metadata: serde_json::Value = serde_json::from_slice(
+            &store
+                .get(&StoreKey::new("array/zarr.json").unwrap())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(metadata["attributes"][STATISTICS_ATTRIBUTE]
+            .get("chunks")
+            .is_none());
+        let mut array_opened = Array::new(store.clone(), "/array").unwrap();
+        assert!(array_opened.compute_statistics());
+        assert!(!array_opened.attributes().contains_key(STATISTICS_ATTRIBUTE));
+        assert_eq!(array_opened.statistics(), Some(&statistics));
+
+        array_opened
+            .store_chunk_elements::<f32>(&[1, 1], vec![0.0; 4])
+            .unwrap();
+        array_opened.erase_chunk(&[0, 0]).unwrap();
+        assert!(array_opened.chunk_statistics(&[0, 0]).unwrap().is_none());
+        array_opened.update_statistics().unwrap();
+        assert_eq!(array_opened.statistics().unwrap().num_chunks(), 1);
+
+        let array_unsupported = ArrayBuilder::new(
+            vec![2],
+            DataType::Bool,
+            vec![2].try_into().unwrap(),
+            FillValue::from(false),
+        )
+        .compute_statistics(true)
+        .build(store, "/bool")
+        .unwrap();
+        array_unsupported.store_chunk(&[0], vec![1, 0]).unwrap();
+        assert!(array_unsupported.chunk_statistics(&[0]).unwrap().is_none());
+    }
+}
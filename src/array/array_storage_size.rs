@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use crate::storage::{
+    data_key, ListableStorageTraits, ReadableStorageTraits, StorageError, StorageHandle,
+    StorePrefix,
+};
+
+#[cfg(feature = "async")]
+use crate::storage::{AsyncListableStorageTraits, AsyncReadableStorageTraits};
+
+use super::{Array, ArrayError, ArrayIndices, HistogramBins};
+
+impl<TStorage: ?Sized> Array<TStorage> {
+    /// Return the histogram of the sizes in bytes of `chunk_sizes` with `bins`.
+    #[allow(clippy::cast_precision_loss)]
+    fn chunk_size_histogram(chunk_sizes: &[(ArrayIndices, u64)], bins: &HistogramBins) -> Vec<u64> {
+        let mut histogram = vec![0; bins.num_bins().get()];
+        for (_, size) in chunk_sizes {
+            if let Some(bin) = bins.bin(*size as f64) {
+                histogram[bin] += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Return the ratio of the decoded size to the stored size of `chunk_sizes`.
+    #[allow(clippy::cast_precision_loss)]
+    fn chunk_compression_ratio(
+        &self,
+        chunk_sizes: &[(ArrayIndices, u64)],
+    ) -> Result<Option<f64>, ArrayError> {
+        let mut size_decoded = 0;
+        let mut size_stored = 0;
+        for (chunk_indices, size) in chunk_sizes {
+            size_decoded += self.chunk_array_representation(chunk_indices)?.size();
+            size_stored += size;
+        }
+        Ok((size_stored > 0).then(|| size_decoded as f64 / size_stored as f64))
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
+    /// Return the size in bytes of all keys under the array prefix.
+    ///
+    /// This includes the stored chunks and the array metadata, as reported by [`size_prefix`](ReadableStorageTraits::size_prefix) of the store.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the store does not support size queries or there is an underlying store error.
+    pub fn size_on_disk(&self) -> Result<u64, ArrayError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_readable_transformer(storage_handle);
+        let prefix = StorePrefix::try_from(self.path()).map_err(StorageError::StorePrefixError)?;
+        Ok(storage_transformer.size_prefix(&prefix)?)
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits + 'static> Array<TStorage> {
+    /// Return the indices and sizes in bytes of the chunks that are stored, in C order.
+    ///
+    /// The sizes are those of the encoded chunks in the store, as reported by [`size_key`](ReadableStorageTraits::size_key).
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the chunk grid shape cannot be determined or there is an underlying store error.
+    pub fn stored_chunk_sizes(&self) -> Result<Vec<(ArrayIndices, u64)>, ArrayError> {
+        let chunks = self.list_stored_chunks()?;
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_readable_transformer(storage_handle);
+        let chunk_sizes = chunks
+            .into_par_iter()
+            .map(|chunk_indices| {
+                let key = data_key(self.path(), &chunk_indices, self.chunk_key_encoding());
+                let size = storage_transformer.size_key(&key)?;
+                Ok::<_, StorageError>(size.map(|size| (chunk_indices, size)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(chunk_sizes.into_iter().flatten().collect())
+    }
+
+    /// Return the histogram of the sizes in bytes of the chunks that are stored with `bins`.
+    ///
+    /// Chunks with a size outside of `bins` are not counted.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the chunk grid shape cannot be determined or there is an underlying store error.
+    pub fn stored_chunk_size_histogram(
+        &self,
+        bins: &HistogramBins,
+    ) -> Result<Vec<u64>, ArrayError> {
+        let chunk_sizes = self.stored_chunk_sizes()?;
+        Ok(Self::chunk_size_histogram(&chunk_sizes, bins))
+    }
+
+    /// Return the compression ratio of the chunks that are stored.
+    ///
+    /// This is the ratio of the decoded size of the stored chunks to their size in the store.
+    /// Returns [`None`] if no chunks are stored.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the chunk grid shape cannot be determined or there is an underlying store error.
+    pub fn compression_ratio(&self) -> Result<Option<f64>, ArrayError> {
+        let chunk_sizes = self.stored_chunk_sizes()?;
+        self.chunk_compression_ratio(&chunk_sizes)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
+    /// Async variant of [`size_on_disk`](Array::size_on_disk).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_size_on_disk(&self) -> Result<u64, ArrayError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_readable_transformer(storage_handle);
+        let prefix = StorePrefix::try_from(self.path()).map_err(StorageError::StorePrefixError)?;
+        Ok(storage_transformer.size_prefix(&prefix).await?)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits + 'static>
+    Array<TStorage>
+{
+    /// Async variant of [`stored_chunk_sizes`](Array::stored_chunk_sizes).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_stored_chunk_sizes(&self) -> Result<Vec<(ArrayIndices, u64)>, ArrayError> {
+        let chunks = self.async_list_stored_chunks().await?;
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_readable_transformer(storage_handle);
+        let mut chunk_sizes = Vec::with_capacity(chunks.len());
+        for chunk_indices in chunks {
+            let key = data_key(self.path(), &chunk_indices, self.chunk_key_encoding());
+            if let Some(size) = storage_transformer.size_key(&key).await? {
+                chunk_sizes.push((chunk_indices, size));
+            }
+        }
+        Ok(chunk_sizes)
+    }
+
+    /// Async variant of [`stored_chunk_size_histogram`](Array::stored_chunk_size_histogram).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_stored_chunk_size_histogram(
+        &self,
+        bins: &HistogramBins,
+    ) -> Result<Vec<u64>, ArrayError> {
+        let chunk_sizes = self.async_stored_chunk_sizes().await?;
+        Ok(Self::chunk_size_histogram(&chunk_sizes, bins))
+    }
+
+    /// Async variant of [`compression_ratio`](Array::compression_ratio).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_compression_ratio(&self) -> Result<Option<f64>, ArrayError> {
+        let chunk_sizes = self.async_stored_chunk_sizes().await?;
+        self.chunk_compression_ratio(&chunk_sizes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        array_subset::ArraySubset,
+        storage::{store::MemoryStore, StoreKey},
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_storage_size() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt16,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        let metadata_size = store
+            .size_key(&StoreKey::new("array/zarr.json").unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(array.size_on_disk().unwrap(), metadata_size);
+        assert_eq!(array.stored_chunk_count().unwrap(), 0);
+        assert!(array.stored_chunk_sizes().unwrap().is_empty());
+        assert_eq!(array.compression_ratio().unwrap(), None);
+
+        let elements: Vec<u16> = (1..=32).collect();
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_ranges(&[0..4, 0..8]), elements)
+            .unwrap();
+        assert_eq!(array.size_on_disk().unwrap(), metadata_size + 2 * 32);
+        assert_eq!(array.stored_chunk_count().unwrap(), 2);
+        assert_eq!(
+            array.stored_chunk_sizes().unwrap(),
+            vec![(vec![0, 0], 32), (vec![0, 1], 32)]
+        );
+        let bins = HistogramBins::new(0.0, 64.0, NonZeroUsize::new(4).unwrap()).unwrap();
+        assert_eq!(
+            array.stored_chunk_size_histogram(&bins).unwrap(),
+            vec![0, 0, 2, 0]
+        );
+        assert_eq!(array.compression_ratio().unwrap(), Some(1.0));
+    }
+}
@@ -0,0 +1,145 @@
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{
+    array_subset::ArraySubset,
+    storage::{
+        data_key, ListableStorageTraits, StorageError, StorageHandle, StoreKey, StorePrefix,
+    },
+};
+
+use super::{Array, ArrayError, ArrayIndices};
+
+impl<TStorage: ?Sized> Array<TStorage> {
+    /// Return the chunks of the chunk grid, erroring if it cannot be determined.
+    pub(super) fn chunk_grid_subset(&self) -> Result<ArraySubset, ArrayError> {
+        let chunk_grid_shape = self
+            .chunk_grid_shape()
+            .ok_or_else(|| ArrayError::UnknownChunkGridShape(self.shape().to_vec()))?;
+        Ok(ArraySubset::new_with_shape(chunk_grid_shape))
+    }
+
+    /// Return whether each of the chunks at `chunks` is in `stored_keys`, in C order.
+    pub(super) fn chunks_exist_in_keys(
+        &self,
+        chunks: &ArraySubset,
+        stored_keys: &HashSet<StoreKey>,
+    ) -> Result<Vec<bool>, ArrayError> {
+        let chunk_grid = self.chunk_grid_subset()?;
+        if chunks.dimensionality() != self.dimensionality() || !chunks.inbounds(chunk_grid.shape())
+        {
+            return Err(ArrayError::InvalidArraySubset(
+                chunks.clone(),
+                chunk_grid.shape().to_vec(),
+            ));
+        }
+        Ok(chunks
+            .indices()
+            .into_iter()
+            .map(|chunk_indices| {
+                stored_keys.contains(&data_key(
+                    self.path(),
+                    &chunk_indices,
+                    self.chunk_key_encoding(),
+                ))
+            })
+            .collect())
+    }
+
+    /// Return the indices of the chunks in `stored_keys`, in C order.
+    ///
+    /// The stored keys are decoded into chunk indices if supported by the chunk key encoding.
+    /// Otherwise, the key of every chunk of the chunk grid is checked.
+    pub(super) fn stored_chunks_in_keys(
+        &self,
+        stored_keys: &HashSet<StoreKey>,
+    ) -> Result<Vec<ArrayIndices>, ArrayError> {
+        let chunk_grid = self.chunk_grid_subset()?;
+        if stored_keys.is_empty() {
+            return Ok(vec![]);
+        }
+        if self.chunk_key_encoding().decodes_keys() {
+            let prefix =
+                StorePrefix::try_from(self.path()).map_err(StorageError::StorePrefixError)?;
+            let mut stored_chunks: Vec<ArrayIndices> = stored_keys
+                .iter()
+                .filter_map(|key| {
+                    let key = key.as_str().strip_prefix(prefix.as_str())?;
+                    let key = StoreKey::new(key).ok()?;
+                    self.chunk_key_encoding()
+                        .decode(&key, self.dimensionality())
+                        .filter(|chunk_indices| {
+                            std::iter::zip(chunk_indices, chunk_grid.shape())
+                                .all(|(index, size)| index < size)
+                        })
+                })
+                .collect();
+            stored_chunks.sort_unstable();
+            Ok(stored_chunks)
+        } else {
+            Ok(chunk_grid
+                .indices()
+                .into_iter()
+                .filter(|chunk_indices| {
+                    stored_keys.contains(&data_key(
+                        self.path(),
+                        chunk_indices,
+                        self.chunk_key_encoding(),
+                    ))
+                })
+                .collect())
+        }
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits + 'static> Array<TStorage> {
+    /// List the keys in the store under the array prefix.
+    pub(super) fn stored_keys(&self) -> Result<HashSet<StoreKey>, ArrayError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_listable_transformer(storage_handle);
+        let prefix = StorePrefix::try_from(self.path()).map_err(StorageError::StorePrefixError)?;
+        Ok(storage_transformer
+            .list_prefix(&prefix)?
+            .into_iter()
+            .collect())
+    }
+
+    /// Return whether each of the chunks at `chunks` is stored, in C order.
+    ///
+    /// A chunk that is not stored is implicitly composed entirely of the fill value.
+    /// Chunks composed entirely of the fill value are not stored by [`Array::store_chunk`] and similar methods, so these are reported as not stored.
+    ///
+    /// The keys under the array prefix are listed once, so this is more efficient than checking each chunk individually for many chunks.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - `chunks` is not within the bounds of the chunk grid, or
+    ///  - there is an underlying store error.
+    pub fn chunks_exist(&self, chunks: &ArraySubset) -> Result<Vec<bool>, ArrayError> {
+        let stored_keys = self.stored_keys()?;
+        self.chunks_exist_in_keys(chunks, &stored_keys)
+    }
+
+    /// Return the indices of the chunks that are stored, in C order.
+    ///
+    /// The keys under the array prefix are listed once and decoded into chunk indices, so the cost scales with the number of stored keys rather than the size of the chunk grid.
+    /// If the chunk key encoding does not support [decoding](crate::array::chunk_key_encoding::ChunkKeyEncodingTraits::decodes_keys) keys, every chunk of the chunk grid is visited instead.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the chunk grid shape cannot be determined or there is an underlying store error.
+    pub fn list_stored_chunks(&self) -> Result<Vec<ArrayIndices>, ArrayError> {
+        let stored_keys = self.stored_keys()?;
+        self.stored_chunks_in_keys(&stored_keys)
+    }
+
+    /// Return the number of chunks that are stored.
+    ///
+    /// This is equivalent to the length of [`list_stored_chunks`](Array::list_stored_chunks).
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the chunk grid shape cannot be determined or there is an underlying store error.
+    pub fn stored_chunk_count(&self) -> Result<usize, ArrayError> {
+        Ok(self.list_stored_chunks()?.len())
+    }
+}
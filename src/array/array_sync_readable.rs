@@ -1,45 +1,100 @@
 use std::sync::Arc;
 
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 use rayon_iter_concurrent_limit::iter_concurrent_limit;
 
 use crate::{
     array_subset::ArraySubset,
     node::NodePath,
-    storage::{data_key, meta_key, ReadableStorageTraits, StorageError, StorageHandle},
+    storage::{
+        data_key, meta_key, meta_key_v2_array, meta_key_v2_attributes, ready,
+        ReadableStorageTraits, StorageError, StorageHandle,
+    },
 };
 
 use super::{
+    bounding_array_subset,
     codec::{
-        options::CodecOptions, ArrayCodecTraits, ArrayPartialDecoderTraits,
-        ArrayToBytesCodecTraits, CodecError, StoragePartialDecoder,
+        options::{CodecOptions, ProgressTracker},
+        ArrayPartialDecoderTraits, ArrayToBytesCodecTraits, CodecError, PrefetchPattern,
+        PrefetchingPartialDecoder, StoragePartialDecoder,
     },
     concurrency::concurrency_chunks_and_codec,
-    transmute_from_bytes_vec,
+    convert_elements_into_subset, copy_subsets_bytes_into_outputs, elements_array_view,
+    elements_from_bytes_vec, unravel_index,
     unsafe_cell_slice::UnsafeCellSlice,
-    validate_element_size, Array, ArrayCreateError, ArrayError, ArrayMetadata, ArrayView,
+    validate_element_size, Array, ArrayChunksIter, ArrayCreateError, ArrayError, ArrayIndices,
+    ArrayMetadata, ArrayMetadataV2, ArraySubsetElementsIter, ArrayView, ArrayViewMut, Element,
+    MaybeBytes,
+};
+
+#[cfg(feature = "n5")]
+use crate::{
+    array::{array_metadata_n5_to_v3, ArrayMetadataN5},
+    storage::meta_key_n5,
 };
 
 #[cfg(feature = "ndarray")]
-use super::elements_to_ndarray;
+use super::{elements_to_ndarray, validate_ndarray_shape};
 
 impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     /// Create an array in `storage` at `path`. The metadata is read from the store.
     ///
+    /// If `zarr.json` is absent, Zarr V2 array metadata is read from `.zarray` and `.zattrs` and converted to Zarr V3 metadata.
+    /// See [`array_metadata_v2_to_v3`](crate::array::array_metadata_v2_to_v3).
+    ///
+    /// With the `n5` feature, if `.zarray` is also absent, N5 dataset metadata is read from `attributes.json` and converted to Zarr V3 metadata.
+    /// See [`array_metadata_n5_to_v3`](crate::array::array_metadata_n5_to_v3).
+    ///
     /// # Errors
     /// Returns [`ArrayCreateError`] if there is a storage error or any metadata is invalid.
     pub fn new(storage: Arc<TStorage>, path: &str) -> Result<Self, ArrayCreateError> {
         let node_path = NodePath::new(path)?;
         let key = meta_key(&node_path);
-        let metadata: ArrayMetadata = serde_json::from_slice(
-            &storage
-                .get(&key)?
-                .ok_or(ArrayCreateError::MissingMetadata)?,
-        )
-        .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
+        let metadata: ArrayMetadata =
+            if let Some((metadata, generation)) = storage.get_with_generation(&key)? {
+                let metadata = serde_json::from_slice(&metadata)
+                    .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
+                let array = Self::new_with_metadata(storage, path, metadata)?;
+                array.set_metadata_generation(generation);
+                return Ok(array);
+            } else {
+                let key = meta_key_v2_array(&node_path);
+                let metadata_v2 = storage.get(&key)?;
+                #[cfg(feature = "n5")]
+                if metadata_v2.is_none() {
+                    let key = meta_key_n5(&node_path);
+                    if let Some(metadata) = storage.get(&key)? {
+                        let metadata: ArrayMetadataN5 = serde_json::from_slice(&metadata)
+                            .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
+                        let metadata = array_metadata_n5_to_v3(&metadata)?;
+                        return Self::new_with_metadata(storage, path, metadata.into());
+                    }
+                }
+                let mut metadata: ArrayMetadataV2 =
+                    serde_json::from_slice(&metadata_v2.ok_or(ArrayCreateError::MissingMetadata)?)
+                        .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
+                let key = meta_key_v2_attributes(&node_path);
+                if let Some(attributes) = storage.get(&key)? {
+                    metadata.attributes = serde_json::from_slice(&attributes)
+                        .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
+                }
+                metadata.into()
+            };
         Self::new_with_metadata(storage, path, metadata)
     }
 
+    /// Read the encoded chunk at `chunk_indices` if it exists.
+    ///
+    /// The bytes are retrieved through the storage transformers of the array, but are not decoded by the codec chain.
+    /// This returns the exact serialised representation of the chunk, e.g. for store-to-store copies or caching layers.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if `chunk_indices` are invalid or there is an underlying store error.
+    pub fn retrieve_encoded_chunk(&self, chunk_indices: &[u64]) -> Result<MaybeBytes, ArrayError> {
+        ready(self.maybe_async_retrieve_encoded_chunk(&self.sync_readable_storage(), chunk_indices))
+    }
+
     /// Read and decode the chunk at `chunk_indices` into its bytes if it exists with default codec options.
     ///
     /// # Errors
@@ -66,7 +121,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     ///  - `chunk_indices` are invalid,
     ///  - there is a codec decoding error, or
     ///  - an underlying store error.
-    pub fn retrieve_chunk_elements_if_exists<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_elements_if_exists<T: Element>(
         &self,
         chunk_indices: &[u64],
     ) -> Result<Option<Vec<T>>, ArrayError> {
@@ -86,7 +141,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     ///
     /// # Panics
     /// Will panic if a chunk dimension is larger than `usize::MAX`.
-    pub fn retrieve_chunk_ndarray_if_exists<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_ndarray_if_exists<T: Element>(
         &self,
         chunk_indices: &[u64],
     ) -> Result<Option<ndarray::ArrayD<T>>, ArrayError> {
@@ -116,7 +171,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     ///  - `chunk_indices` are invalid,
     ///  - there is a codec decoding error, or
     ///  - an underlying store error.
-    pub fn retrieve_chunk_elements<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_elements<T: Element>(
         &self,
         chunk_indices: &[u64],
     ) -> Result<Vec<T>, ArrayError> {
@@ -136,7 +191,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     ///
     /// # Panics
     /// Will panic if a chunk dimension is larger than `usize::MAX`.
-    pub fn retrieve_chunk_ndarray<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_ndarray<T: Element>(
         &self,
         chunk_indices: &[u64],
     ) -> Result<ndarray::ArrayD<T>, ArrayError> {
@@ -159,6 +214,27 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         self.retrieve_chunk_into_array_view_opt(chunk_indices, array_view, &CodecOptions::default())
     }
 
+    /// Retrieve a chunk into a mutable array view.
+    ///
+    /// This is a safe alternative to [`retrieve_chunk_into_array_view`](Array::retrieve_chunk_into_array_view) for decoding into disjoint regions of an output buffer in parallel, see [`ArrayViewMut::split_disjoint`].
+    ///
+    /// # Errors
+    /// See [`Array::retrieve_chunk_into_array_view`].
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_chunk_into_array_view_mut(
+        &self,
+        chunk_indices: &[u64],
+        array_view: &mut ArrayViewMut,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunk_into_array_view_mut_opt(
+            chunk_indices,
+            array_view,
+            &CodecOptions::default(),
+        )
+    }
+
     /// Read and decode the chunks at `chunks` into their bytes.
     ///
     /// # Errors
@@ -180,7 +256,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     ///
     /// # Panics
     /// Panics if the number of array elements in the chunks exceeds `usize::MAX`.
-    pub fn retrieve_chunks_elements<T: bytemuck::Pod>(
+    pub fn retrieve_chunks_elements<T: Element>(
         &self,
         chunks: &ArraySubset,
     ) -> Result<Vec<T>, ArrayError> {
@@ -195,7 +271,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     ///
     /// # Panics
     /// Panics if the number of array elements in the chunks exceeds `usize::MAX`.
-    pub fn retrieve_chunks_ndarray<T: bytemuck::Pod>(
+    pub fn retrieve_chunks_ndarray<T: Element>(
         &self,
         chunks: &ArraySubset,
     ) -> Result<ndarray::ArrayD<T>, ArrayError> {
@@ -218,6 +294,23 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         self.retrieve_chunks_into_array_view_opt(chunks, array_view, &CodecOptions::default())
     }
 
+    /// Retrieve chunks into a mutable array view.
+    ///
+    /// This is a safe alternative to [`retrieve_chunks_into_array_view`](Array::retrieve_chunks_into_array_view) for decoding into disjoint regions of an output buffer in parallel, see [`ArrayViewMut::split_disjoint`].
+    ///
+    /// # Errors
+    /// See [`Array::retrieve_chunks_into_array_view`].
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_chunks_into_array_view_mut(
+        &self,
+        chunks: &ArraySubset,
+        array_view: &mut ArrayViewMut,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunks_into_array_view_mut_opt(chunks, array_view, &CodecOptions::default())
+    }
+
     /// Read and decode the `chunk_subset` of the chunk at `chunk_indices` into its bytes.
     ///
     /// # Errors
@@ -245,7 +338,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     ///  - the chunk subset is invalid,
     ///  - there is a codec decoding error, or
     ///  - an underlying store error.
-    pub fn retrieve_chunk_subset_elements<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_subset_elements<T: Element>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -269,7 +362,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     ///
     /// # Panics
     /// Will panic if the number of elements in `chunk_subset` is `usize::MAX` or larger.
-    pub fn retrieve_chunk_subset_ndarray<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_subset_ndarray<T: Element>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -304,6 +397,29 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         )
     }
 
+    /// Retrieve a subset of a chunk into a mutable array view.
+    ///
+    /// This is a safe alternative to [`retrieve_chunk_subset_into_array_view`](Array::retrieve_chunk_subset_into_array_view) for decoding into disjoint regions of an output buffer in parallel, see [`ArrayViewMut::split_disjoint`].
+    ///
+    /// # Errors
+    /// See [`Array::retrieve_chunk_subset_into_array_view`].
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_chunk_subset_into_array_view_mut(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        array_view: &mut ArrayViewMut,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunk_subset_into_array_view_mut_opt(
+            chunk_indices,
+            chunk_subset,
+            array_view,
+            &CodecOptions::default(),
+        )
+    }
+
     /// Read and decode the `array_subset` of array into its bytes.
     ///
     /// Out-of-bounds elements will have the fill value.
@@ -330,13 +446,81 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     ///  - an array subset is invalid or out of bounds of the array,
     ///  - there is a codec decoding error, or
     ///  - an underlying store error.
-    pub fn retrieve_array_subset_elements<T: bytemuck::Pod>(
+    pub fn retrieve_array_subset_elements<T: Element>(
         &self,
         array_subset: &ArraySubset,
     ) -> Result<Vec<T>, ArrayError> {
         self.retrieve_array_subset_elements_opt(array_subset, &CodecOptions::default())
     }
 
+    /// Read and decode the `array_subset` of array into a vector of its elements converted from `TSrc` to `TDst` with `convert`.
+    ///
+    /// `TSrc` must match the size of the array data type.
+    /// Elements are converted chunk-by-chunk as chunks are decoded, so the array subset is not held in memory as both `TSrc` and `TDst`.
+    /// For example, a `uint16` array can be read as scaled [`f32`] values with `|x: u16| f32::from(x) * scale + offset`.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if a [`retrieve_array_subset_elements`](Array::retrieve_array_subset_elements) error condition is met.
+    pub fn retrieve_array_subset_elements_as<TSrc: Element, TDst: Element + Send + Sync>(
+        &self,
+        array_subset: &ArraySubset,
+        convert: impl Fn(TSrc) -> TDst + Sync,
+    ) -> Result<Vec<TDst>, ArrayError> {
+        self.retrieve_array_subset_elements_as_opt(array_subset, convert, &CodecOptions::default())
+    }
+
+    /// Read and decode multiple `array_subsets` of the array into a vector of bytes for each array subset.
+    ///
+    /// Chunk access is planned across all of the array subsets, so each chunk intersecting any array subset is retrieved and decoded once.
+    /// This is more efficient than calling [`retrieve_array_subset`](Array::retrieve_array_subset) for each array subset if many array subsets share chunks (e.g. extracting many small regions of interest).
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if:
+    ///  - any array subset is invalid or out of bounds of the array,
+    ///  - there is a codec decoding error, or
+    ///  - an underlying store error.
+    ///
+    /// # Panics
+    /// Panics if attempting to reference a byte beyond `usize::MAX`.
+    pub fn retrieve_array_subsets(
+        &self,
+        array_subsets: &[ArraySubset],
+    ) -> Result<Vec<Vec<u8>>, ArrayError> {
+        self.retrieve_array_subsets_opt(array_subsets, &CodecOptions::default())
+    }
+
+    /// Read and decode multiple `array_subsets` of the array into a vector of elements for each array subset.
+    ///
+    /// See [`retrieve_array_subsets`](Array::retrieve_array_subsets).
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the size of `T` does not match the data type size or a [`retrieve_array_subsets`](Array::retrieve_array_subsets) error condition is met.
+    pub fn retrieve_array_subsets_elements<T: Element>(
+        &self,
+        array_subsets: &[ArraySubset],
+    ) -> Result<Vec<Vec<T>>, ArrayError> {
+        self.retrieve_array_subsets_elements_opt(array_subsets, &CodecOptions::default())
+    }
+
+    /// Read and decode the elements at `array_indices` into a vector of elements in the same order.
+    ///
+    /// The elements are grouped by chunk, and each chunk is partially decoded to retrieve only the requested elements where supported by the codecs (e.g. the `sharding_indexed` codec).
+    /// This is suited to sampling sparse points from an array (e.g. nearest-neighbour lookups).
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if:
+    ///  - the size of `T` does not match the data type size,
+    ///  - the decoded bytes cannot be transmuted,
+    ///  - any array indices are not within the bounds of the array,
+    ///  - there is a codec decoding error, or
+    ///  - an underlying store error.
+    pub fn retrieve_elements_at<T: Element>(
+        &self,
+        array_indices: &[ArrayIndices],
+    ) -> Result<Vec<T>, ArrayError> {
+        self.retrieve_elements_at_opt(array_indices, &CodecOptions::default())
+    }
+
     #[cfg(feature = "ndarray")]
     /// Read and decode the `array_subset` of array into an [`ndarray::ArrayD`].
     ///
@@ -348,13 +532,30 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     ///
     /// # Panics
     /// Will panic if any dimension in `chunk_subset` is `usize::MAX` or larger.
-    pub fn retrieve_array_subset_ndarray<T: bytemuck::Pod>(
+    pub fn retrieve_array_subset_ndarray<T: Element>(
         &self,
         array_subset: &ArraySubset,
     ) -> Result<ndarray::ArrayD<T>, ArrayError> {
         self.retrieve_array_subset_ndarray_opt(array_subset, &CodecOptions::default())
     }
 
+    #[cfg(feature = "arrow")]
+    /// Read and decode the `array_subset` of array into an [`arrow_array::PrimitiveArray`].
+    ///
+    /// The elements of `array_subset` are in C order and the decoded elements are not copied.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if a [`retrieve_array_subset_elements`](Array::retrieve_array_subset_elements) error condition is met.
+    pub fn retrieve_array_subset_arrow<T: arrow_array::ArrowPrimitiveType>(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<arrow_array::PrimitiveArray<T>, ArrayError>
+    where
+        T::Native: Element,
+    {
+        self.retrieve_array_subset_arrow_opt(array_subset, &CodecOptions::default())
+    }
+
     /// Retrieve an array subset into an array view.
     ///
     /// # Errors
@@ -375,6 +576,244 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         )
     }
 
+    /// Retrieve an array subset into a mutable array view.
+    ///
+    /// This is a safe alternative to [`retrieve_array_subset_into_array_view`](Array::retrieve_array_subset_into_array_view) for decoding into disjoint regions of an output buffer in parallel, see [`ArrayViewMut::split_disjoint`].
+    ///
+    /// # Errors
+    /// See [`Array::retrieve_array_subset_into_array_view`].
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_array_subset_into_array_view_mut(
+        &self,
+        array_subset: &ArraySubset,
+        array_view: &mut ArrayViewMut,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_array_subset_into_array_view_mut_opt(
+            array_subset,
+            array_view,
+            &CodecOptions::default(),
+        )
+    }
+
+    /// Retrieve a chunk into a preallocated slice of elements.
+    ///
+    /// `elements` must have the number of elements of the chunk shape.
+    /// Unlike [`retrieve_chunk_elements`](Array::retrieve_chunk_elements), this does not allocate an output, so `elements` can be reused across calls.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the size of `T` does not match the data type size,
+    ///  - the number of `elements` does not match the chunk shape, or
+    ///  - a [`retrieve_chunk_into_array_view`](Array::retrieve_chunk_into_array_view) error condition is met.
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_chunk_into<T: bytemuck::Pod>(
+        &self,
+        chunk_indices: &[u64],
+        elements: &mut [T],
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunk_into_opt(chunk_indices, elements, &CodecOptions::default())
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Retrieve a chunk into a preallocated [`ndarray::ArrayViewMutD`].
+    ///
+    /// `array` must have the chunk shape.
+    /// Elements are decoded directly into `array` if it is in standard layout, otherwise they are decoded into a temporary buffer and assigned to `array`.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the shape of `array` does not match the chunk shape or a [`retrieve_chunk_into`](Array::retrieve_chunk_into) error condition is met.
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_chunk_into_ndarray<T: bytemuck::Pod>(
+        &self,
+        chunk_indices: &[u64],
+        array: ndarray::ArrayViewMutD<'_, T>,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunk_into_ndarray_opt(chunk_indices, array, &CodecOptions::default())
+    }
+
+    /// Retrieve the chunks in `chunks` into a preallocated slice of elements.
+    ///
+    /// `elements` must have the number of elements of the shape of the subset of `chunks`.
+    /// Unlike [`retrieve_chunks_elements`](Array::retrieve_chunks_elements), this does not allocate an output, so `elements` can be reused across calls.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the size of `T` does not match the data type size,
+    ///  - the number of `elements` does not match the shape of the subset of `chunks`, or
+    ///  - a [`retrieve_chunks_into_array_view`](Array::retrieve_chunks_into_array_view) error condition is met.
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_chunks_into<T: bytemuck::Pod>(
+        &self,
+        chunks: &ArraySubset,
+        elements: &mut [T],
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunks_into_opt(chunks, elements, &CodecOptions::default())
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Retrieve the chunks in `chunks` into a preallocated [`ndarray::ArrayViewMutD`].
+    ///
+    /// `array` must have the shape of the subset of `chunks`.
+    /// Elements are decoded directly into `array` if it is in standard layout, otherwise they are decoded into a temporary buffer and assigned to `array`.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the shape of `array` does not match the shape of the subset of `chunks` or a [`retrieve_chunks_into`](Array::retrieve_chunks_into) error condition is met.
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_chunks_into_ndarray<T: bytemuck::Pod>(
+        &self,
+        chunks: &ArraySubset,
+        array: ndarray::ArrayViewMutD<'_, T>,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunks_into_ndarray_opt(chunks, array, &CodecOptions::default())
+    }
+
+    /// Retrieve a subset of a chunk into a preallocated slice of elements.
+    ///
+    /// `elements` must have the number of elements of the shape of `chunk_subset`.
+    /// Unlike [`retrieve_chunk_subset_elements`](Array::retrieve_chunk_subset_elements), this does not allocate an output, so `elements` can be reused across calls.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the size of `T` does not match the data type size,
+    ///  - the number of `elements` does not match the shape of `chunk_subset`, or
+    ///  - a [`retrieve_chunk_subset_into_array_view`](Array::retrieve_chunk_subset_into_array_view) error condition is met.
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_chunk_subset_into<T: bytemuck::Pod>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        elements: &mut [T],
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunk_subset_into_opt(
+            chunk_indices,
+            chunk_subset,
+            elements,
+            &CodecOptions::default(),
+        )
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Retrieve a subset of a chunk into a preallocated [`ndarray::ArrayViewMutD`].
+    ///
+    /// `array` must have the shape of `chunk_subset`.
+    /// Elements are decoded directly into `array` if it is in standard layout, otherwise they are decoded into a temporary buffer and assigned to `array`.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the shape of `array` does not match the shape of `chunk_subset` or a [`retrieve_chunk_subset_into`](Array::retrieve_chunk_subset_into) error condition is met.
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_chunk_subset_into_ndarray<T: bytemuck::Pod>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        array: ndarray::ArrayViewMutD<'_, T>,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunk_subset_into_ndarray_opt(
+            chunk_indices,
+            chunk_subset,
+            array,
+            &CodecOptions::default(),
+        )
+    }
+
+    /// Retrieve an array subset into a preallocated slice of elements.
+    ///
+    /// `elements` must have the number of elements of the shape of `array_subset`.
+    /// Unlike [`retrieve_array_subset_elements`](Array::retrieve_array_subset_elements), this does not allocate an output, so `elements` can be reused across calls.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the size of `T` does not match the data type size,
+    ///  - the number of `elements` does not match the shape of `array_subset`, or
+    ///  - a [`retrieve_array_subset_into_array_view`](Array::retrieve_array_subset_into_array_view) error condition is met.
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_array_subset_into<T: bytemuck::Pod>(
+        &self,
+        array_subset: &ArraySubset,
+        elements: &mut [T],
+    ) -> Result<(), ArrayError> {
+        self.retrieve_array_subset_into_opt(array_subset, elements, &CodecOptions::default())
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Retrieve an array subset into a preallocated [`ndarray::ArrayViewMutD`].
+    ///
+    /// `array` must have the shape of `array_subset`.
+    /// Elements are decoded directly into `array` if it is in standard layout, otherwise they are decoded into a temporary buffer and assigned to `array`.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the shape of `array` does not match the shape of `array_subset` or a [`retrieve_array_subset_into`](Array::retrieve_array_subset_into) error condition is met.
+    ///
+    /// # Panics
+    /// Panics if an offset is larger than `usize::MAX`.
+    pub fn retrieve_array_subset_into_ndarray<T: bytemuck::Pod>(
+        &self,
+        array_subset: &ArraySubset,
+        array: ndarray::ArrayViewMutD<'_, T>,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_array_subset_into_ndarray_opt(array_subset, array, &CodecOptions::default())
+    }
+
+    /// Return an iterator over the decoded chunks intersecting `array_subset`.
+    ///
+    /// Chunks are retrieved and decoded lazily in C order as the iterator is advanced, so arrays larger than memory can be processed chunk-by-chunk.
+    /// Each item is the chunk indices and the decoded bytes of the entire chunk, which may extend beyond `array_subset`.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if `array_subset` is not within the bounds of the array or the intersecting chunks cannot be determined.
+    pub fn chunks_iter(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<ArrayChunksIter<'_, TStorage>, ArrayError> {
+        self.chunks_iter_opt(array_subset, &CodecOptions::default())
+    }
+
+    /// Return an iterator over the indices and values of the elements of `array_subset`.
+    ///
+    /// Chunks are retrieved and decoded lazily as the iterator is advanced, so sparse scans of large array subsets do not need to hold the entire subset in memory.
+    /// Elements are visited chunk-by-chunk rather than in C order over the entire array subset, see [`ArraySubsetElementsIter`].
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if `array_subset` is not within the bounds of the array or the intersecting chunks cannot be determined.
+    pub fn iter_array_subset_elements<T: Element>(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<ArraySubsetElementsIter<'_, TStorage, T>, ArrayError> {
+        self.iter_array_subset_elements_opt(array_subset, &CodecOptions::default())
+    }
+
+    /// Return a parallel iterator over the decoded chunks intersecting `array_subset`.
+    ///
+    /// This is a [`rayon`] variant of [`chunks_iter`](Array::chunks_iter) where chunks are retrieved and decoded in parallel.
+    /// The codec concurrency is reduced based on the number of chunks and the concurrency target of the codec options.
+    ///
+    /// # Errors
+    /// See [`chunks_iter`](Array::chunks_iter).
+    pub fn par_chunks_iter(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<
+        impl IndexedParallelIterator<Item = Result<(ArrayIndices, Vec<u8>), ArrayError>> + '_,
+        ArrayError,
+    > {
+        self.par_chunks_iter_opt(array_subset, &CodecOptions::default())
+    }
+
     /// Initialises a partial decoder for the chunk at `chunk_indices`.
     ///
     /// # Errors
@@ -386,52 +825,41 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         self.partial_decoder_opt(chunk_indices, &CodecOptions::default())
     }
 
+    /// Initialises a partial decoder for the chunk at `chunk_indices` which reads ahead the byte ranges predicted by `pattern`.
+    ///
+    /// This suits repeated partial decoding of a chunk with a predictable access pattern, such as sequential slices along an axis.
+    /// See [`PrefetchingPartialDecoder`].
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if initialisation of the partial decoder fails.
+    pub fn prefetching_partial_decoder<'a>(
+        &'a self,
+        chunk_indices: &[u64],
+        pattern: PrefetchPattern,
+    ) -> Result<Box<dyn ArrayPartialDecoderTraits + 'a>, ArrayError> {
+        self.prefetching_partial_decoder_opt(chunk_indices, pattern, &CodecOptions::default())
+    }
+
     /////////////////////////////////////////////////////////////////////////////
     // Advanced methods
     /////////////////////////////////////////////////////////////////////////////
 
     /// Explicit options version of [`retrieve_chunk_if_exists`](Array::retrieve_chunk_if_exists).
     #[allow(clippy::missing_errors_doc)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(path = %self.path(), chunk_indices = ?chunk_indices))
+    )]
     pub fn retrieve_chunk_if_exists_opt(
         &self,
         chunk_indices: &[u64],
         options: &CodecOptions,
     ) -> Result<Option<Vec<u8>>, ArrayError> {
-        if chunk_indices.len() != self.dimensionality() {
-            return Err(ArrayError::InvalidChunkGridIndicesError(
-                chunk_indices.to_vec(),
-            ));
-        }
-        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-        let storage_transformer = self
-            .storage_transformers()
-            .create_readable_transformer(storage_handle);
-        let chunk_encoded = crate::storage::retrieve_chunk(
-            &*storage_transformer,
-            self.path(),
+        ready(self.maybe_async_retrieve_chunk_if_exists_opt(
+            &self.sync_readable_storage(),
             chunk_indices,
-            self.chunk_key_encoding(),
-        )
-        .map_err(ArrayError::StorageError)?;
-        if let Some(chunk_encoded) = chunk_encoded {
-            let chunk_representation = self.chunk_array_representation(chunk_indices)?;
-            let chunk_decoded = self
-                .codecs()
-                .decode(chunk_encoded, &chunk_representation, options)
-                .map_err(ArrayError::CodecError)?;
-            let chunk_decoded_size =
-                chunk_representation.num_elements_usize() * chunk_representation.data_type().size();
-            if chunk_decoded.len() == chunk_decoded_size {
-                Ok(Some(chunk_decoded))
-            } else {
-                Err(ArrayError::UnexpectedChunkDecodedSize(
-                    chunk_decoded.len(),
-                    chunk_decoded_size,
-                ))
-            }
-        } else {
-            Ok(None)
-        }
+            options,
+        ))
     }
 
     /// Explicit options version of [`retrieve_chunk`](Array::retrieve_chunk).
@@ -441,44 +869,41 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         chunk_indices: &[u64],
         options: &CodecOptions,
     ) -> Result<Vec<u8>, ArrayError> {
-        let chunk = self.retrieve_chunk_if_exists_opt(chunk_indices, options)?;
-        if let Some(chunk) = chunk {
-            Ok(chunk)
-        } else {
-            let chunk_representation = self.chunk_array_representation(chunk_indices)?;
-            let fill_value = chunk_representation.fill_value().as_ne_bytes();
-            Ok(fill_value.repeat(chunk_representation.num_elements_usize()))
-        }
+        ready(self.maybe_async_retrieve_chunk_opt(
+            &self.sync_readable_storage(),
+            chunk_indices,
+            options,
+        ))
     }
 
     /// Explicit options version of [`retrieve_chunk_elements_if_exists`](Array::retrieve_chunk_elements_if_exists).
     #[allow(clippy::missing_errors_doc)]
-    pub fn retrieve_chunk_elements_if_exists_opt<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_elements_if_exists_opt<T: Element>(
         &self,
         chunk_indices: &[u64],
         options: &CodecOptions,
     ) -> Result<Option<Vec<T>>, ArrayError> {
         validate_element_size::<T>(self.data_type())?;
         let bytes = self.retrieve_chunk_if_exists_opt(chunk_indices, options)?;
-        Ok(bytes.map(|bytes| transmute_from_bytes_vec::<T>(bytes)))
+        bytes.map(elements_from_bytes_vec::<T>).transpose()
     }
 
     /// Explicit options version of [`retrieve_chunk_elements`](Array::retrieve_chunk_elements).
     #[allow(clippy::missing_errors_doc)]
-    pub fn retrieve_chunk_elements_opt<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_elements_opt<T: Element>(
         &self,
         chunk_indices: &[u64],
         options: &CodecOptions,
     ) -> Result<Vec<T>, ArrayError> {
         validate_element_size::<T>(self.data_type())?;
         let bytes = self.retrieve_chunk_opt(chunk_indices, options)?;
-        Ok(transmute_from_bytes_vec::<T>(bytes))
+        elements_from_bytes_vec::<T>(bytes)
     }
 
     #[cfg(feature = "ndarray")]
     /// Explicit options version of [`retrieve_chunk_ndarray_if_exists`](Array::retrieve_chunk_ndarray_if_exists).
     #[allow(clippy::missing_errors_doc)]
-    pub fn retrieve_chunk_ndarray_if_exists_opt<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_ndarray_if_exists_opt<T: Element>(
         &self,
         chunk_indices: &[u64],
         options: &CodecOptions,
@@ -499,7 +924,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     #[cfg(feature = "ndarray")]
     /// Explicit options version of [`retrieve_chunk_ndarray`](Array::retrieve_chunk_ndarray).
     #[allow(clippy::missing_errors_doc)]
-    pub fn retrieve_chunk_ndarray_opt<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_ndarray_opt<T: Element>(
         &self,
         chunk_indices: &[u64],
         options: &CodecOptions,
@@ -543,33 +968,23 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
             self.chunk_key_encoding(),
         )
         .map_err(ArrayError::StorageError)?;
-        if let Some(chunk_encoded) = chunk_encoded {
-            self.codecs()
-                .decode_into_array_view(&chunk_encoded, &chunk_representation, array_view, options)
-                .map_err(ArrayError::CodecError)
-        } else {
-            // fill array_view with fill value
-            let contiguous_indices = unsafe {
-                array_view
-                    .subset()
-                    .contiguous_linearised_indices_unchecked(array_view.array_shape())
-            };
-            let element_size = chunk_representation.element_size();
-            let length = contiguous_indices.contiguous_elements_usize() * element_size;
-            let fill = self
-                .fill_value()
-                .as_ne_bytes()
-                .repeat(contiguous_indices.contiguous_elements_usize());
-            // FIXME: Par iteration?
-            let output = unsafe { array_view.bytes_mut() };
-            for (array_subset_element_index, _num_elements) in &contiguous_indices {
-                let output_offset =
-                    usize::try_from(array_subset_element_index).unwrap() * element_size;
-                debug_assert!((output_offset + length) <= output.len());
-                output[output_offset..output_offset + length].copy_from_slice(&fill);
-            }
-            Ok(())
-        }
+        self.decode_chunk_into_array_view(
+            chunk_encoded.as_deref(),
+            chunk_indices,
+            array_view,
+            options,
+        )
+    }
+
+    /// Explicit options version of [`retrieve_chunk_into_array_view_mut`](Array::retrieve_chunk_into_array_view_mut).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_chunk_into_array_view_mut_opt(
+        &self,
+        chunk_indices: &[u64],
+        array_view: &mut ArrayViewMut,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunk_into_array_view_opt(chunk_indices, array_view.array_view(), options)
     }
 
     /// Explicit options version of [`retrieve_chunk_subset_into_array_view`](Array::retrieve_chunk_subset_into_array_view).
@@ -608,6 +1023,23 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         }
     }
 
+    /// Explicit options version of [`retrieve_chunk_subset_into_array_view_mut`](Array::retrieve_chunk_subset_into_array_view_mut).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_chunk_subset_into_array_view_mut_opt(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        array_view: &mut ArrayViewMut,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunk_subset_into_array_view_opt(
+            chunk_indices,
+            chunk_subset,
+            array_view.array_view(),
+            options,
+        )
+    }
+
     /// Explicit options version of [`retrieve_chunks`](Array::retrieve_chunks).
     #[allow(clippy::missing_errors_doc)]
     pub fn retrieve_chunks_opt(
@@ -626,11 +1058,15 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
 
         // Retrieve chunk bytes
         let num_chunks = chunks.num_elements_usize();
+        let progress = ProgressTracker::new(options, num_chunks);
+        progress.check_cancelled()?;
         match num_chunks {
             0 => Ok(vec![]),
             1 => {
                 let chunk_indices = chunks.start();
-                self.retrieve_chunk_opt(chunk_indices, options)
+                let bytes = self.retrieve_chunk_opt(chunk_indices, options)?;
+                progress.chunk_completed(bytes.len() as u64);
+                Ok(bytes)
             }
             _ => {
                 // Calculate chunk/codec concurrency
@@ -643,6 +1079,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                     num_chunks,
                     options,
                     &codec_concurrency,
+                    self.chunk_memory_usage(&chunk_representation)?,
                 );
 
                 // let mut output = vec![0; size_output];
@@ -659,6 +1096,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                         indices.into_par_iter(),
                         try_for_each,
                         |chunk_indices| {
+                            progress.check_cancelled()?;
                             let chunk_subset = self.chunk_subset(&chunk_indices)?;
                             let array_view_subset = unsafe {
                                 chunk_subset.relative_to_unchecked(chunk0_subset.start())
@@ -672,7 +1110,11 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                                 )
                                 .map_err(|err| CodecError::from(err.to_string()))?,
                                 &options,
-                            )
+                            )?;
+                            progress.chunk_completed(
+                                chunk_subset.num_elements() * self.data_type().size() as u64,
+                            );
+                            Ok::<_, ArrayError>(())
                         }
                     )?;
                 }
@@ -684,20 +1126,20 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
 
     /// Explicit options version of [`retrieve_chunks_elements`](Array::retrieve_chunks_elements).
     #[allow(clippy::missing_errors_doc)]
-    pub fn retrieve_chunks_elements_opt<T: bytemuck::Pod>(
+    pub fn retrieve_chunks_elements_opt<T: Element>(
         &self,
         chunks: &ArraySubset,
         options: &CodecOptions,
     ) -> Result<Vec<T>, ArrayError> {
         validate_element_size::<T>(self.data_type())?;
         let bytes = self.retrieve_chunks_opt(chunks, options)?;
-        Ok(transmute_from_bytes_vec::<T>(bytes))
+        elements_from_bytes_vec::<T>(bytes)
     }
 
     #[cfg(feature = "ndarray")]
     /// Explicit options version of [`retrieve_chunks_ndarray`](Array::retrieve_chunks_ndarray).
     #[allow(clippy::missing_errors_doc)]
-    pub fn retrieve_chunks_ndarray_opt<T: bytemuck::Pod>(
+    pub fn retrieve_chunks_ndarray_opt<T: Element>(
         &self,
         chunks: &ArraySubset,
         options: &CodecOptions,
@@ -710,6 +1152,10 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
 
     /// Explicit options version of [`retrieve_array_subset`](Array::retrieve_array_subset).
     #[allow(clippy::missing_errors_doc)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(path = %self.path(), array_subset = %array_subset))
+    )]
     pub fn retrieve_array_subset_opt(
         &self,
         array_subset: &ArraySubset,
@@ -733,6 +1179,8 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
 
         // Retrieve chunk bytes
         let num_chunks = chunks.num_elements_usize();
+        let progress = ProgressTracker::new(options, num_chunks);
+        progress.check_cancelled()?;
         match num_chunks {
             0 => Ok(self
                 .fill_value()
@@ -741,9 +1189,9 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
             1 => {
                 let chunk_indices = chunks.start();
                 let chunk_subset = self.chunk_subset(chunk_indices)?;
-                if &chunk_subset == array_subset {
+                let bytes = if &chunk_subset == array_subset {
                     // Single chunk fast path if the array subset domain matches the chunk domain
-                    self.retrieve_chunk_opt(chunk_indices, options)
+                    self.retrieve_chunk_opt(chunk_indices, options)?
                 } else {
                     let array_subset_in_chunk_subset =
                         unsafe { array_subset.relative_to_unchecked(chunk_subset.start()) };
@@ -751,8 +1199,10 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                         chunk_indices,
                         &array_subset_in_chunk_subset,
                         options,
-                    )
-                }
+                    )?
+                };
+                progress.chunk_completed(bytes.len() as u64);
+                Ok(bytes)
             }
             _ => {
                 // Allocate the output
@@ -769,11 +1219,13 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                     num_chunks,
                     options,
                     &codec_concurrency,
+                    self.chunk_memory_usage(&chunk_representation)?,
                 );
 
                 {
                     let output = UnsafeCellSlice::new_from_vec_with_spare_capacity(&mut output);
                     let retrieve_chunk = |chunk_indices: Vec<u64>| {
+                        progress.check_cancelled()?;
                         let chunk_subset = self.chunk_subset(&chunk_indices)?;
                         let chunk_subset_in_array_subset =
                             unsafe { chunk_subset.overlap_unchecked(array_subset) };
@@ -794,7 +1246,11 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                             &chunk_subset,
                             &array_view,
                             &options,
-                        )
+                        )?;
+                        progress.chunk_completed(
+                            chunk_subset.num_elements() * self.data_type().size() as u64,
+                        );
+                        Ok::<_, ArrayError>(())
                     };
                     let indices = chunks.indices();
                     iter_concurrent_limit!(
@@ -847,7 +1303,10 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                 num_chunks,
                 options,
                 &codec_concurrency,
+                self.chunk_memory_usage(&chunk_representation)?,
             );
+            let progress = ProgressTracker::new(&options, num_chunks);
+            progress.check_cancelled()?;
 
             {
                 let indices = chunks.indices();
@@ -856,6 +1315,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                     indices.into_par_iter(),
                     try_for_each,
                     |chunk_indices| {
+                        progress.check_cancelled()?;
                         let chunk_subset = self.chunk_subset(&chunk_indices)?;
                         let array_view_subset =
                             unsafe { chunk_subset.relative_to_unchecked(array_subset.start()) };
@@ -864,7 +1324,11 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                             &unsafe { array_view.subset_view(&array_view_subset) }
                                 .map_err(|err| CodecError::from(err.to_string()))?,
                             &options,
-                        )
+                        )?;
+                        progress.chunk_completed(
+                            chunk_subset.num_elements() * self.data_type().size() as u64,
+                        );
+                        Ok::<_, ArrayError>(())
                     }
                 )?;
             }
@@ -872,6 +1336,17 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         }
     }
 
+    /// Explicit options version of [`retrieve_chunks_into_array_view_mut`](Array::retrieve_chunks_into_array_view_mut).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_chunks_into_array_view_mut_opt(
+        &self,
+        chunks: &ArraySubset,
+        array_view: &mut ArrayViewMut,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_chunks_into_array_view_opt(chunks, array_view.array_view(), options)
+    }
+
     /// Explicit options version of [`retrieve_array_subset_into_array_view`](Array::retrieve_array_subset_into_array_view).
     #[allow(clippy::missing_errors_doc)]
     pub fn retrieve_array_subset_into_array_view_opt(
@@ -898,6 +1373,8 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
 
         // Retrieve chunk bytes
         let num_chunks = chunks.num_elements_usize();
+        let progress = ProgressTracker::new(options, num_chunks);
+        progress.check_cancelled()?;
         match num_chunks {
             0 => Ok(()),
             1 => {
@@ -929,7 +1406,10 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                             .map_err(|err| CodecError::from(err.to_string()))?,
                         options,
                     )
-                }
+                }?;
+                progress
+                    .chunk_completed(array_subset.num_elements() * self.data_type().size() as u64);
+                Ok(())
             }
             _ => {
                 // Calculate chunk/codec concurrency
@@ -942,6 +1422,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                     num_chunks,
                     options,
                     &codec_concurrency,
+                    self.chunk_memory_usage(&chunk_representation)?,
                 );
 
                 {
@@ -951,6 +1432,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                         indices.into_par_iter(),
                         try_for_each,
                         |chunk_indices| {
+                            progress.check_cancelled()?;
                             let chunk_subset = self.chunk_subset(&chunk_indices)?;
                             let chunk_subset_in_array_subset =
                                 unsafe { chunk_subset.overlap_unchecked(array_subset) };
@@ -968,7 +1450,11 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                                 &unsafe { array_view.subset_view(&array_view_subset) }
                                     .map_err(|err| CodecError::from(err.to_string()))?,
                                 &options,
-                            )
+                            )?;
+                            progress.chunk_completed(
+                                chunk_subset.num_elements() * self.data_type().size() as u64,
+                            );
+                            Ok::<_, ArrayError>(())
                         }
                     )?;
                 }
@@ -977,22 +1463,417 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         }
     }
 
+    /// Explicit options version of [`retrieve_array_subset_into_array_view_mut`](Array::retrieve_array_subset_into_array_view_mut).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_array_subset_into_array_view_mut_opt(
+        &self,
+        array_subset: &ArraySubset,
+        array_view: &mut ArrayViewMut,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        self.retrieve_array_subset_into_array_view_opt(
+            array_subset,
+            array_view.array_view(),
+            options,
+        )
+    }
+
+    /// Explicit options version of [`retrieve_chunk_into`](Array::retrieve_chunk_into).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_chunk_into_opt<T: bytemuck::Pod>(
+        &self,
+        chunk_indices: &[u64],
+        elements: &mut [T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = self.chunk_subset(chunk_indices)?.shape().to_vec();
+        let array_view = elements_array_view(self.data_type(), elements, &shape)?;
+        self.retrieve_chunk_into_array_view_opt(chunk_indices, &array_view, options)
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Explicit options version of [`retrieve_chunk_into_ndarray`](Array::retrieve_chunk_into_ndarray).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_chunk_into_ndarray_opt<T: bytemuck::Pod>(
+        &self,
+        chunk_indices: &[u64],
+        mut array: ndarray::ArrayViewMutD<'_, T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = self.chunk_subset(chunk_indices)?.shape().to_vec();
+        validate_ndarray_shape(&array, &shape)?;
+        if let Some(elements) = array.as_slice_mut() {
+            self.retrieve_chunk_into_opt(chunk_indices, elements, options)
+        } else {
+            let elements = self.retrieve_chunk_elements_opt::<T>(chunk_indices, options)?;
+            array.assign(&elements_to_ndarray(&shape, elements)?);
+            Ok(())
+        }
+    }
+
+    /// Explicit options version of [`retrieve_chunks_into`](Array::retrieve_chunks_into).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_chunks_into_opt<T: bytemuck::Pod>(
+        &self,
+        chunks: &ArraySubset,
+        elements: &mut [T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = self.chunks_subset(chunks)?.shape().to_vec();
+        let array_view = elements_array_view(self.data_type(), elements, &shape)?;
+        self.retrieve_chunks_into_array_view_opt(chunks, &array_view, options)
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Explicit options version of [`retrieve_chunks_into_ndarray`](Array::retrieve_chunks_into_ndarray).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_chunks_into_ndarray_opt<T: bytemuck::Pod>(
+        &self,
+        chunks: &ArraySubset,
+        mut array: ndarray::ArrayViewMutD<'_, T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = self.chunks_subset(chunks)?.shape().to_vec();
+        validate_ndarray_shape(&array, &shape)?;
+        if let Some(elements) = array.as_slice_mut() {
+            self.retrieve_chunks_into_opt(chunks, elements, options)
+        } else {
+            let elements = self.retrieve_chunks_elements_opt::<T>(chunks, options)?;
+            array.assign(&elements_to_ndarray(&shape, elements)?);
+            Ok(())
+        }
+    }
+
+    /// Explicit options version of [`retrieve_chunk_subset_into`](Array::retrieve_chunk_subset_into).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_chunk_subset_into_opt<T: bytemuck::Pod>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        elements: &mut [T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = chunk_subset.shape().to_vec();
+        let array_view = elements_array_view(self.data_type(), elements, &shape)?;
+        self.retrieve_chunk_subset_into_array_view_opt(
+            chunk_indices,
+            chunk_subset,
+            &array_view,
+            options,
+        )
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Explicit options version of [`retrieve_chunk_subset_into_ndarray`](Array::retrieve_chunk_subset_into_ndarray).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_chunk_subset_into_ndarray_opt<T: bytemuck::Pod>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_subset: &ArraySubset,
+        mut array: ndarray::ArrayViewMutD<'_, T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = chunk_subset.shape().to_vec();
+        validate_ndarray_shape(&array, &shape)?;
+        if let Some(elements) = array.as_slice_mut() {
+            self.retrieve_chunk_subset_into_opt(chunk_indices, chunk_subset, elements, options)
+        } else {
+            let elements =
+                self.retrieve_chunk_subset_elements_opt::<T>(chunk_indices, chunk_subset, options)?;
+            array.assign(&elements_to_ndarray(&shape, elements)?);
+            Ok(())
+        }
+    }
+
+    /// Explicit options version of [`retrieve_array_subset_into`](Array::retrieve_array_subset_into).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_array_subset_into_opt<T: bytemuck::Pod>(
+        &self,
+        array_subset: &ArraySubset,
+        elements: &mut [T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = array_subset.shape().to_vec();
+        let array_view = elements_array_view(self.data_type(), elements, &shape)?;
+        self.retrieve_array_subset_into_array_view_opt(array_subset, &array_view, options)
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Explicit options version of [`retrieve_array_subset_into_ndarray`](Array::retrieve_array_subset_into_ndarray).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_array_subset_into_ndarray_opt<T: bytemuck::Pod>(
+        &self,
+        array_subset: &ArraySubset,
+        mut array: ndarray::ArrayViewMutD<'_, T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let shape = array_subset.shape().to_vec();
+        validate_ndarray_shape(&array, &shape)?;
+        if let Some(elements) = array.as_slice_mut() {
+            self.retrieve_array_subset_into_opt(array_subset, elements, options)
+        } else {
+            let elements = self.retrieve_array_subset_elements_opt::<T>(array_subset, options)?;
+            array.assign(&elements_to_ndarray(&shape, elements)?);
+            Ok(())
+        }
+    }
+
     /// Explicit options version of [`retrieve_array_subset_elements`](Array::retrieve_array_subset_elements).
     #[allow(clippy::missing_errors_doc)]
-    pub fn retrieve_array_subset_elements_opt<T: bytemuck::Pod>(
+    pub fn retrieve_array_subset_elements_opt<T: Element>(
         &self,
         array_subset: &ArraySubset,
         options: &CodecOptions,
     ) -> Result<Vec<T>, ArrayError> {
         validate_element_size::<T>(self.data_type())?;
         let bytes = self.retrieve_array_subset_opt(array_subset, options)?;
-        Ok(transmute_from_bytes_vec::<T>(bytes))
+        elements_from_bytes_vec::<T>(bytes)
+    }
+
+    /// Explicit options version of [`retrieve_array_subset_elements_as`](Array::retrieve_array_subset_elements_as).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_array_subset_elements_as_opt<TSrc: Element, TDst: Element + Send + Sync>(
+        &self,
+        array_subset: &ArraySubset,
+        convert: impl Fn(TSrc) -> TDst + Sync,
+        options: &CodecOptions,
+    ) -> Result<Vec<TDst>, ArrayError> {
+        validate_element_size::<TSrc>(self.data_type())?;
+        if array_subset.dimensionality() != self.dimensionality() {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        }
+
+        // Find the chunks intersecting this array subset
+        let Some(chunks) = self.chunks_in_array_subset(array_subset)? else {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        };
+
+        let num_elements = array_subset.num_elements_usize();
+        let mut output = Vec::with_capacity(num_elements);
+        if num_elements > 0 {
+            // Calculate chunk/codec concurrency
+            let num_chunks = chunks.num_elements_usize();
+            let chunk_representation =
+                self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+            let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+            let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+                options.concurrent_target(),
+                num_chunks,
+                options,
+                &codec_concurrency,
+                self.chunk_memory_usage(&chunk_representation)?,
+            );
+
+            let output_slice = UnsafeCellSlice::new_from_vec_with_spare_capacity(&mut output);
+            let retrieve_chunk = |chunk_indices: Vec<u64>| {
+                let chunk_subset = self.chunk_subset(&chunk_indices)?;
+                let chunk_subset_in_array_subset =
+                    unsafe { chunk_subset.overlap_unchecked(array_subset) };
+                let elements = self.retrieve_chunk_subset_elements_opt::<TSrc>(
+                    &chunk_indices,
+                    &unsafe {
+                        chunk_subset_in_array_subset.relative_to_unchecked(chunk_subset.start())
+                    },
+                    &options,
+                )?;
+                convert_elements_into_subset(
+                    elements,
+                    &unsafe {
+                        chunk_subset_in_array_subset.relative_to_unchecked(array_subset.start())
+                    },
+                    unsafe { output_slice.get() },
+                    array_subset.shape(),
+                    &convert,
+                );
+                Ok::<_, ArrayError>(())
+            };
+            let indices = chunks.indices();
+            iter_concurrent_limit!(
+                chunk_concurrent_limit,
+                indices.into_par_iter(),
+                try_for_each,
+                retrieve_chunk
+            )?;
+        }
+        unsafe { output.set_len(num_elements) };
+        Ok(output)
+    }
+
+    /// Explicit options version of [`retrieve_array_subsets`](Array::retrieve_array_subsets).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn retrieve_array_subsets_opt(
+        &self,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<u8>>, ArrayError> {
+        // Find the chunks intersecting the array subsets
+        let chunks_subsets = self.chunks_in_array_subsets(array_subsets)?;
+
+        // Allocate the outputs
+        let element_size = self.data_type().size();
+        let mut outputs: Vec<Vec<u8>> = array_subsets
+            .iter()
+            .map(|array_subset| {
+                Vec::with_capacity(array_subset.num_elements_usize() * element_size)
+            })
+            .collect();
+
+        if !chunks_subsets.is_empty() {
+            // Calculate chunk/codec concurrency
+            let num_chunks = chunks_subsets.len();
+            let chunk_representation =
+                self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+            let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+            let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+                options.concurrent_target(),
+                num_chunks,
+                options,
+                &codec_concurrency,
+                self.chunk_memory_usage(&chunk_representation)?,
+            );
+
+            let output_slices: Vec<UnsafeCellSlice<u8>> = outputs
+                .iter_mut()
+                .map(UnsafeCellSlice::new_from_vec_with_spare_capacity)
+                .collect();
+            let retrieve_chunk = |(chunk_indices, indices): (ArrayIndices, Vec<usize>)| {
+                let chunk_subset = self.chunk_subset(&chunk_indices)?;
+                let subsets: Vec<(usize, ArraySubset)> = indices
+                    .into_iter()
+                    .map(|index| {
+                        (index, unsafe {
+                            chunk_subset.overlap_unchecked(&array_subsets[index])
+                        })
+                    })
+                    .collect();
+
+                // Only retrieve the region of the chunk intersecting the array subsets
+                let bytes_subset = bounding_array_subset(
+                    &subsets
+                        .iter()
+                        .map(|(_, subset)| subset.clone())
+                        .collect::<Vec<_>>(),
+                );
+                let bytes = if bytes_subset == chunk_subset {
+                    self.retrieve_chunk_opt(&chunk_indices, &options)?
+                } else {
+                    self.retrieve_chunk_subset_opt(
+                        &chunk_indices,
+                        &unsafe { bytes_subset.relative_to_unchecked(chunk_subset.start()) },
+                        &options,
+                    )?
+                };
+                copy_subsets_bytes_into_outputs(
+                    &bytes,
+                    &bytes_subset,
+                    &subsets,
+                    array_subsets,
+                    &output_slices,
+                    element_size,
+                );
+                Ok::<_, ArrayError>(())
+            };
+            let chunks_subsets: Vec<_> = chunks_subsets.into_iter().collect();
+            iter_concurrent_limit!(
+                chunk_concurrent_limit,
+                chunks_subsets.into_par_iter(),
+                try_for_each,
+                retrieve_chunk
+            )?;
+        }
+        for (output, array_subset) in std::iter::zip(&mut outputs, array_subsets) {
+            unsafe { output.set_len(array_subset.num_elements_usize() * element_size) };
+        }
+        Ok(outputs)
+    }
+
+    /// Explicit options version of [`retrieve_array_subsets_elements`](Array::retrieve_array_subsets_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_array_subsets_elements_opt<T: Element>(
+        &self,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<T>>, ArrayError> {
+        validate_element_size::<T>(self.data_type())?;
+        self.retrieve_array_subsets_opt(array_subsets, options)?
+            .into_iter()
+            .map(elements_from_bytes_vec::<T>)
+            .collect()
+    }
+
+    /// Explicit options version of [`retrieve_elements_at`](Array::retrieve_elements_at).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_elements_at_opt<T: Element>(
+        &self,
+        array_indices: &[ArrayIndices],
+        options: &CodecOptions,
+    ) -> Result<Vec<T>, ArrayError> {
+        validate_element_size::<T>(self.data_type())?;
+
+        // Group the elements by chunk
+        let chunks_elements = self.chunks_elements(array_indices)?;
+
+        let element_size = self.data_type().size();
+        let size_output = array_indices.len() * element_size;
+        let mut output = Vec::with_capacity(size_output);
+        if size_output > 0 {
+            // Calculate chunk/codec concurrency
+            let num_chunks = chunks_elements.len();
+            let chunk_representation =
+                self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+            let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+            let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+                options.concurrent_target(),
+                num_chunks,
+                options,
+                &codec_concurrency,
+                self.chunk_memory_usage(&chunk_representation)?,
+            );
+
+            let output_slice = UnsafeCellSlice::new_from_vec_with_spare_capacity(&mut output);
+            let retrieve_chunk_elements =
+                |(chunk_indices, elements): (ArrayIndices, Vec<(usize, ArrayIndices)>)| {
+                    let element_subsets: Vec<ArraySubset> = elements
+                        .iter()
+                        .map(|(_, indices)| unsafe {
+                            ArraySubset::new_with_start_shape_unchecked(
+                                indices.clone(),
+                                vec![1; indices.len()],
+                            )
+                        })
+                        .collect();
+                    let decoded = self
+                        .partial_decoder_opt(&chunk_indices, &options)?
+                        .partial_decode_opt(&element_subsets, &options)?;
+                    let output = unsafe { output_slice.get() };
+                    for ((index, _), bytes) in std::iter::zip(elements, decoded) {
+                        output[index * element_size..(index + 1) * element_size]
+                            .copy_from_slice(&bytes);
+                    }
+                    Ok::<_, ArrayError>(())
+                };
+            let chunks_elements: Vec<_> = chunks_elements.into_iter().collect();
+            iter_concurrent_limit!(
+                chunk_concurrent_limit,
+                chunks_elements.into_par_iter(),
+                try_for_each,
+                retrieve_chunk_elements
+            )?;
+        }
+        unsafe { output.set_len(size_output) };
+        elements_from_bytes_vec::<T>(output)
     }
 
     #[cfg(feature = "ndarray")]
     /// Explicit options version of [`retrieve_array_subset_ndarray`](Array::retrieve_array_subset_ndarray).
     #[allow(clippy::missing_errors_doc)]
-    pub fn retrieve_array_subset_ndarray_opt<T: bytemuck::Pod>(
+    pub fn retrieve_array_subset_ndarray_opt<T: Element>(
         &self,
         array_subset: &ArraySubset,
         options: &CodecOptions,
@@ -1002,6 +1883,22 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         elements_to_ndarray(array_subset.shape(), elements)
     }
 
+    #[cfg(feature = "arrow")]
+    /// Explicit options version of [`retrieve_array_subset_arrow`](Array::retrieve_array_subset_arrow).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_array_subset_arrow_opt<T: arrow_array::ArrowPrimitiveType>(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<arrow_array::PrimitiveArray<T>, ArrayError>
+    where
+        T::Native: Element,
+    {
+        let elements =
+            self.retrieve_array_subset_elements_opt::<T::Native>(array_subset, options)?;
+        Ok(arrow_array::PrimitiveArray::new(elements.into(), None))
+    }
+
     /// Explicit options version of [`retrieve_chunk_subset`](Array::retrieve_chunk_subset).
     #[allow(clippy::missing_errors_doc)]
     pub fn retrieve_chunk_subset_opt(
@@ -1056,7 +1953,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
 
     /// Explicit options version of [`retrieve_chunk_subset_elements`](Array::retrieve_chunk_subset_elements).
     #[allow(clippy::missing_errors_doc)]
-    pub fn retrieve_chunk_subset_elements_opt<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_subset_elements_opt<T: Element>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -1064,13 +1961,13 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     ) -> Result<Vec<T>, ArrayError> {
         validate_element_size::<T>(self.data_type())?;
         let bytes = self.retrieve_chunk_subset_opt(chunk_indices, chunk_subset, options)?;
-        Ok(transmute_from_bytes_vec::<T>(bytes))
+        elements_from_bytes_vec::<T>(bytes)
     }
 
     #[cfg(feature = "ndarray")]
     /// Explicit options version of [`retrieve_chunk_subset_ndarray`](Array::retrieve_chunk_subset_ndarray).
     #[allow(clippy::missing_errors_doc)]
-    pub fn retrieve_chunk_subset_ndarray_opt<T: bytemuck::Pod>(
+    pub fn retrieve_chunk_subset_ndarray_opt<T: Element>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -1082,6 +1979,86 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         elements_to_ndarray(chunk_subset.shape(), elements)
     }
 
+    /// Return the chunks intersecting `array_subset`, which must be within the bounds of the array.
+    pub(super) fn chunks_in_array_subset_inbounds(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<ArraySubset, ArrayError> {
+        if array_subset.dimensionality() != self.dimensionality()
+            || !array_subset.inbounds(self.shape())
+        {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        }
+        self.chunks_in_array_subset(array_subset)?.ok_or_else(|| {
+            ArrayError::InvalidArraySubset(array_subset.clone(), self.shape().to_vec())
+        })
+    }
+
+    /// Explicit options version of [`chunks_iter`](Array::chunks_iter).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn chunks_iter_opt(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<ArrayChunksIter<'_, TStorage>, ArrayError> {
+        let chunks = self.chunks_in_array_subset_inbounds(array_subset)?;
+        Ok(ArrayChunksIter::new(self, chunks, options.clone()))
+    }
+
+    /// Explicit options version of [`iter_array_subset_elements`](Array::iter_array_subset_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn iter_array_subset_elements_opt<T: Element>(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<ArraySubsetElementsIter<'_, TStorage, T>, ArrayError> {
+        let chunks = self.chunks_in_array_subset_inbounds(array_subset)?;
+        Ok(ArraySubsetElementsIter::new(
+            self,
+            array_subset.clone(),
+            chunks,
+            options.clone(),
+        ))
+    }
+
+    /// Explicit options version of [`par_chunks_iter`](Array::par_chunks_iter).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn par_chunks_iter_opt(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<
+        impl IndexedParallelIterator<Item = Result<(ArrayIndices, Vec<u8>), ArrayError>> + '_,
+        ArrayError,
+    > {
+        let chunks = self.chunks_in_array_subset_inbounds(array_subset)?;
+        let num_chunks = chunks.num_elements_usize();
+
+        // Calculate chunk/codec concurrency
+        let chunk_representation =
+            self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+        let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+        let (_chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+            options.concurrent_target(),
+            num_chunks,
+            options,
+            &codec_concurrency,
+            self.chunk_memory_usage(&chunk_representation)?,
+        );
+
+        let retrieve_chunk = move |index: usize| {
+            let mut chunk_indices = unravel_index(index as u64, chunks.shape());
+            std::iter::zip(chunk_indices.iter_mut(), chunks.start())
+                .for_each(|(chunk_index, start)| *chunk_index += start);
+            let chunk_bytes = self.retrieve_chunk_opt(&chunk_indices, &options)?;
+            Ok((chunk_indices, chunk_bytes))
+        };
+        Ok((0..num_chunks).into_par_iter().map(retrieve_chunk))
+    }
+
     /// Explicit options version of [`partial_decoder`](Array::partial_decoder).
     #[allow(clippy::missing_errors_doc)]
     pub fn partial_decoder_opt<'a>(
@@ -1102,4 +2079,27 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
             .codecs()
             .partial_decoder(input_handle, &chunk_representation, options)?)
     }
+
+    /// Explicit options version of [`prefetching_partial_decoder`](Array::prefetching_partial_decoder).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn prefetching_partial_decoder_opt<'a>(
+        &'a self,
+        chunk_indices: &[u64],
+        pattern: PrefetchPattern,
+        options: &CodecOptions,
+    ) -> Result<Box<dyn ArrayPartialDecoderTraits + 'a>, ArrayError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_readable_transformer(storage_handle);
+        let input_handle = Box::new(PrefetchingPartialDecoder::new(
+            storage_transformer,
+            data_key(self.path(), chunk_indices, self.chunk_key_encoding()),
+            pattern,
+        ));
+        let chunk_representation = self.chunk_array_representation(chunk_indices)?;
+        Ok(self
+            .codecs()
+            .partial_decoder(input_handle, &chunk_representation, options)?)
+    }
 }
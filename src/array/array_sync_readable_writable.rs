@@ -2,18 +2,38 @@ use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use crate::{
     array_subset::ArraySubset,
-    storage::{data_key, ReadableWritableStorageTraits},
+    storage::{
+        data_key, update_attributes, ReadableWritableStorageTraits, StorageError, StorageHandle,
+    },
 };
 
 use super::{
-    codec::options::CodecOptions, concurrency::concurrency_chunks_and_codec, Array, ArrayError,
+    codec::options::{CodecOptions, ProgressTracker},
+    concurrency::concurrency_chunks_and_codec,
+    update_chunk_elements, validate_element_size, Array, ArrayError, ArrayIndices, ArrayShape,
+    Element,
 };
 
+#[cfg(feature = "arrow")]
+use super::validate_arrow_nulls;
+
 impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage> {
+    /// Store the attributes in the existing array metadata.
+    ///
+    /// Unlike [`store_metadata`](Array::store_metadata), only the attributes of the stored array metadata are updated.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the array metadata does not exist in the store or there is an underlying store error.
+    pub fn store_attributes(&self) -> Result<(), StorageError> {
+        let storage_handle = StorageHandle::new(self.storage.clone());
+        update_attributes(&storage_handle, self.path(), &self.metadata_attributes())
+    }
+
     /// Encode `chunk_subset_bytes` and store in `chunk_subset` of the chunk at `chunk_indices` with default codec options.
     ///
     /// Use [`store_chunk_subset_opt`](Array::store_chunk_subset_opt) to control codec options.
     /// Prefer to use [`store_chunk`](Array::store_chunk) where possible, since this function may decode the chunk before updating it and reencoding it.
+    /// If the chunk does not exist and `chunk_subset_bytes` is entirely the fill value, nothing is stored.
     ///
     /// # Errors
     /// Returns an [`ArrayError`] if
@@ -48,7 +68,7 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
     ///  - the size of  `T` does not match the data type size, or
     ///  - a [`store_chunk_subset`](Array::store_chunk_subset) error condition is met.
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub fn store_chunk_subset_elements<T: bytemuck::Pod>(
+    pub fn store_chunk_subset_elements<T: Element>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -71,7 +91,7 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
     /// # Errors
     /// Returns an [`ArrayError`] if a [`store_chunk_subset_elements`](Array::store_chunk_subset_elements) error condition is met.
     pub fn store_chunk_subset_ndarray<
-        T: bytemuck::Pod,
+        T: Element,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -92,6 +112,10 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
     ///
     /// Use [`store_array_subset_opt`](Array::store_array_subset_opt) to control codec options.
     /// Prefer to use [`store_chunk`](Array::store_chunk) or [`store_chunks`](Array::store_chunks) where possible, since this will decode and encode each chunk intersecting `array_subset`.
+    /// If `array_subset` is aligned with chunk boundaries, chunks are stored directly without being retrieved or locked.
+    /// Chunks that do not exist and would only be updated with the fill value (such as edge chunks of a sparse write) are skipped.
+    /// Use [`store_array_subset_exact`](Array::store_array_subset_exact) to guarantee this.
+    /// Use a [`StagedArrayWriter`](crate::array::StagedArrayWriter) to coalesce many subset writes to the same chunks, such as the outer chunks of a sharded array.
     ///
     /// # Errors
     /// Returns an [`ArrayError`] if
@@ -118,7 +142,7 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
     ///  - the size of `T` does not match the data type size, or
     ///  - a [`store_array_subset`](Array::store_array_subset) error condition is met.
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub fn store_array_subset_elements<T: bytemuck::Pod>(
+    pub fn store_array_subset_elements<T: Element>(
         &self,
         array_subset: &ArraySubset,
         subset_elements: Vec<T>,
@@ -140,7 +164,7 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
     /// Returns an [`ArrayError`] if a [`store_array_subset_elements`](Array::store_array_subset_elements) error condition is met.
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub fn store_array_subset_ndarray<
-        T: bytemuck::Pod,
+        T: Element,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -151,6 +175,177 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
         self.store_array_subset_ndarray_opt(subset_start, subset_array, &CodecOptions::default())
     }
 
+    #[cfg(feature = "arrow")]
+    /// Encode `subset_array` and store in `array_subset`.
+    ///
+    /// The elements of `subset_array` are in C order.
+    ///
+    /// Use [`store_array_subset_arrow_opt`](Array::store_array_subset_arrow_opt) to control codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - `subset_array` has null elements, or
+    ///  - a [`store_array_subset_elements`](Array::store_array_subset_elements) error condition is met.
+    pub fn store_array_subset_arrow<T: arrow_array::ArrowPrimitiveType>(
+        &self,
+        array_subset: &ArraySubset,
+        subset_array: &arrow_array::PrimitiveArray<T>,
+    ) -> Result<(), ArrayError>
+    where
+        T::Native: Element,
+    {
+        self.store_array_subset_arrow_opt(array_subset, subset_array, &CodecOptions::default())
+    }
+
+    /// Update the elements of `array_subset` in place with `f`.
+    ///
+    /// Each chunk intersecting `array_subset` is locked, retrieved, updated by calling `f` with the array indices and a mutable reference of each element in the intersection, and stored.
+    /// This avoids separately retrieving and storing `array_subset`, and the chunk lock is held for the entire read-modify-write.
+    /// Chunks are updated sequentially in C order, and the elements of each chunk are visited in C order.
+    ///
+    /// Use [`update_array_subset_with_opt`](Array::update_array_subset_with_opt) to control codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the dimensionality of `array_subset` does not match the chunk grid dimensionality,
+    ///  - the size of `T` does not match the data type size,
+    ///  - a retrieved element is not a valid `T`,
+    ///  - there is a codec decoding or encoding error, or
+    ///  - an underlying store error.
+    pub fn update_array_subset_with<T: Element>(
+        &self,
+        array_subset: &ArraySubset,
+        f: impl FnMut(&ArrayIndices, &mut T),
+    ) -> Result<(), ArrayError> {
+        self.update_array_subset_with_opt(array_subset, f, &CodecOptions::default())
+    }
+
+    /// Resize the array to `new_shape` with default codec options.
+    ///
+    /// Unlike [`set_shape`](Array::set_shape), this cleans up chunks affected by shrinking the array:
+    ///  - chunks entirely outside of `new_shape` are erased, and
+    ///  - elements of chunks partially outside of `new_shape` are set to the fill value.
+    ///
+    /// This ensures that regions revealed by subsequently growing the array hold the fill value.
+    /// The array metadata is not written to the store, use [`store_metadata`](Array::store_metadata) to persist the new shape.
+    ///
+    /// Use [`resize_opt`](Array::resize_opt) to control codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the dimensionality of `new_shape` does not match the array dimensionality,
+    ///  - there is a codec decoding or encoding error, or
+    ///  - an underlying store error.
+    pub fn resize(&mut self, new_shape: ArrayShape) -> Result<(), ArrayError> {
+        self.resize_opt(new_shape, &CodecOptions::default())
+    }
+
+    /// Encode `append_bytes` and append it to the end of the array along `axis` with default codec options.
+    ///
+    /// The shape of the appended data is the array shape with the length along `axis` inferred from the length of `append_bytes`.
+    /// The array is grown with [`resize`](Array::resize) before the data is stored.
+    /// The array metadata is not written to the store, use [`store_metadata`](Array::store_metadata) to persist the new shape.
+    ///
+    /// Use [`append_opt`](Array::append_opt) to control codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - `axis` is not less than the array dimensionality,
+    ///  - the length of `append_bytes` is not a multiple of the size of an array slice along `axis`, or
+    ///  - a [`store_array_subset`](Array::store_array_subset) error condition is met.
+    pub fn append(&mut self, axis: usize, append_bytes: Vec<u8>) -> Result<(), ArrayError> {
+        self.append_opt(axis, append_bytes, &CodecOptions::default())
+    }
+
+    /// Encode `append_elements` and append it to the end of the array along `axis` with default codec options.
+    ///
+    /// Use [`append_elements_opt`](Array::append_elements_opt) to control codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the size of `T` does not match the data type size, or
+    ///  - an [`append`](Array::append) error condition is met.
+    pub fn append_elements<T: Element>(
+        &mut self,
+        axis: usize,
+        append_elements: Vec<T>,
+    ) -> Result<(), ArrayError> {
+        self.append_elements_opt(axis, append_elements, &CodecOptions::default())
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Encode `append_array` and append it to the end of the array along `axis` with default codec options.
+    ///
+    /// Use [`append_ndarray_opt`](Array::append_ndarray_opt) to control codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the shape of `append_array` does not match the array shape excluding `axis`, or
+    ///  - an [`append_elements`](Array::append_elements) error condition is met.
+    pub fn append_ndarray<T: Element, TArray: Into<ndarray::Array<T, D>>, D: ndarray::Dimension>(
+        &mut self,
+        axis: usize,
+        append_array: TArray,
+    ) -> Result<(), ArrayError> {
+        self.append_ndarray_opt(axis, append_array, &CodecOptions::default())
+    }
+
+    /// Encode `append_bytes` and append it to the end of the array along `axis` as a new chunk with default codec options.
+    ///
+    /// Unlike [`append`](Array::append), the length of the appended data along `axis` does not need to match the chunk shape.
+    /// The array must have a `rectangular` chunk grid, and the size of the new chunk is pushed to the chunk sizes of `axis`.
+    /// If `axis` has a fixed chunk size, the appended length must equal the chunk size and the array shape along `axis` must be a multiple of it.
+    /// Existing chunks are not rewritten.
+    /// The array metadata is not written to the store, use [`store_metadata`](Array::store_metadata) to persist the new shape and chunk grid.
+    ///
+    /// Use [`append_ragged_opt`](Array::append_ragged_opt) to control codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the array does not have a `rectangular` chunk grid,
+    ///  - the chunk sizes of `axis` do not cover the array shape, or `append_bytes` is empty or does not fit the chunk grid,
+    ///  - an [`append`](Array::append) error condition is met.
+    pub fn append_ragged(&mut self, axis: usize, append_bytes: Vec<u8>) -> Result<(), ArrayError> {
+        self.append_ragged_opt(axis, append_bytes, &CodecOptions::default())
+    }
+
+    /// Encode `append_elements` and append it to the end of the array along `axis` as a new chunk with default codec options.
+    ///
+    /// Use [`append_ragged_elements_opt`](Array::append_ragged_elements_opt) to control codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the size of `T` does not match the data type size, or
+    ///  - an [`append_ragged`](Array::append_ragged) error condition is met.
+    pub fn append_ragged_elements<T: Element>(
+        &mut self,
+        axis: usize,
+        append_elements: Vec<T>,
+    ) -> Result<(), ArrayError> {
+        self.append_ragged_elements_opt(axis, append_elements, &CodecOptions::default())
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Encode `append_array` and append it to the end of the array along `axis` as a new chunk with default codec options.
+    ///
+    /// Use [`append_ragged_ndarray_opt`](Array::append_ragged_ndarray_opt) to control codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the shape of `append_array` does not match the array shape excluding `axis`, or
+    ///  - an [`append_ragged_elements`](Array::append_ragged_elements) error condition is met.
+    pub fn append_ragged_ndarray<
+        T: Element,
+        TArray: Into<ndarray::Array<T, D>>,
+        D: ndarray::Dimension,
+    >(
+        &mut self,
+        axis: usize,
+        append_array: TArray,
+    ) -> Result<(), ArrayError> {
+        self.append_ragged_ndarray_opt(axis, append_array, &CodecOptions::default())
+    }
+
     /////////////////////////////////////////////////////////////////////////////
     // Advanced methods
     /////////////////////////////////////////////////////////////////////////////
@@ -192,11 +387,30 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
         } else {
             // Lock the chunk
             let key = data_key(self.path(), chunk_indices, self.chunk_key_encoding());
-            let mutex = self.storage.mutex(&key)?;
-            let _lock = mutex.lock();
+            let mutex = match &self.store_locks {
+                Some(store_locks) => store_locks.mutex(&key),
+                None => self.storage.mutex(&key)?,
+            };
+            let _lock = mutex.lock()?;
 
             // Decode the entire chunk
-            let mut chunk_bytes = self.retrieve_chunk_opt(chunk_indices, options)?;
+            let chunk_bytes = self.retrieve_chunk_if_exists_opt(chunk_indices, options)?;
+            let mut chunk_bytes = match chunk_bytes {
+                Some(chunk_bytes) => chunk_bytes,
+                None if !options.store_empty_chunks()
+                    && self.fill_value().equals_all(&chunk_subset_bytes) =>
+                {
+                    // The chunk does not exist and would remain entirely fill value, so skip storing it
+                    return Ok(());
+                }
+                None => {
+                    let chunk_representation = self.chunk_array_representation(chunk_indices)?;
+                    chunk_representation
+                        .fill_value()
+                        .as_ne_bytes()
+                        .repeat(chunk_representation.num_elements_usize())
+                }
+            };
 
             // Update the intersecting subset of the chunk
             let element_size = self.data_type().size();
@@ -221,7 +435,7 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
 
     /// Explicit options version of [`store_chunk_subset_elements`](Array::store_chunk_subset_elements).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub fn store_chunk_subset_elements_opt<T: bytemuck::Pod>(
+    pub fn store_chunk_subset_elements_opt<T: Element>(
         &self,
         chunk_indices: &[u64],
         chunk_subset: &ArraySubset,
@@ -239,7 +453,7 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
     /// Explicit options version of [`store_chunk_subset_ndarray`](Array::store_chunk_subset_ndarray).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub fn store_chunk_subset_ndarray_opt<
-        T: bytemuck::Pod,
+        T: Element,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -266,7 +480,15 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
     }
 
     /// Explicit options version of [`store_array_subset`](Array::store_array_subset).
-    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    #[allow(
+        clippy::missing_errors_doc,
+        clippy::missing_panics_doc,
+        clippy::too_many_lines
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(path = %self.path(), array_subset = %array_subset))
+    )]
     pub fn store_array_subset_opt(
         &self,
         array_subset: &ArraySubset,
@@ -296,7 +518,20 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
                 self.shape().to_vec(),
             ));
         };
+        if self.chunks_subset(&chunks)? == *array_subset {
+            // A fast path if the array subset is aligned with chunk boundaries
+            // This skips retrieving and locking chunks in store_chunk_subset
+            let existing_chunks = self.write_journal_existing_chunks(&chunks, options)?;
+            return self.store_chunks_journaled_opt(
+                &chunks,
+                subset_bytes,
+                existing_chunks,
+                options,
+            );
+        }
         let num_chunks = chunks.num_elements_usize();
+        let progress = ProgressTracker::new(options, num_chunks);
+        progress.check_cancelled()?;
         if num_chunks == 1 {
             let chunk_indices = chunks.start();
             let chunk_subset_in_array = unsafe {
@@ -304,33 +539,31 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
                     .subset_unchecked(chunk_indices, self.shape())
                     .unwrap()
             };
-            if array_subset == &chunk_subset_in_array {
-                // A fast path if the array subset matches the chunk subset
-                // This skips the internal decoding occurring in store_chunk_subset
-                self.store_chunk_opt(chunk_indices, subset_bytes, options)?;
-            } else {
-                let overlap = unsafe { array_subset.overlap_unchecked(&chunk_subset_in_array) };
-                let chunk_subset_in_array_subset =
-                    unsafe { overlap.relative_to_unchecked(array_subset.start()) };
-                let chunk_subset_bytes = unsafe {
-                    chunk_subset_in_array_subset.extract_bytes_unchecked(
-                        &subset_bytes,
-                        array_subset.shape(),
-                        self.data_type().size(),
-                    )
-                };
+            let overlap = unsafe { array_subset.overlap_unchecked(&chunk_subset_in_array) };
+            let chunk_subset_in_array_subset =
+                unsafe { overlap.relative_to_unchecked(array_subset.start()) };
+            let chunk_subset_bytes = unsafe {
+                chunk_subset_in_array_subset.extract_bytes_unchecked(
+                    &subset_bytes,
+                    array_subset.shape(),
+                    self.data_type().size(),
+                )
+            };
 
-                // Store the chunk subset
-                let array_subset_in_chunk_subset =
-                    unsafe { overlap.relative_to_unchecked(chunk_subset_in_array.start()) };
-                self.store_chunk_subset_opt(
-                    chunk_indices,
-                    &array_subset_in_chunk_subset,
-                    chunk_subset_bytes,
-                    options,
-                )?;
-            }
+            // Store the chunk subset
+            let array_subset_in_chunk_subset =
+                unsafe { overlap.relative_to_unchecked(chunk_subset_in_array.start()) };
+            self.store_chunk_subset_opt(
+                chunk_indices,
+                &array_subset_in_chunk_subset,
+                chunk_subset_bytes,
+                options,
+            )?;
+            progress.chunk_completed(subset_bytes.len() as u64);
         } else {
+            let existing_chunks = self.write_journal_existing_chunks(&chunks, options)?;
+            let write_journal = self.begin_write_journal(&chunks, existing_chunks, options)?;
+
             // Calculate chunk/codec concurrency
             let chunk_representation =
                 self.chunk_array_representation(&vec![0; self.dimensionality()])?;
@@ -340,9 +573,12 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
                 num_chunks,
                 options,
                 &codec_concurrency,
+                self.chunk_memory_usage(&chunk_representation)?,
             );
 
+            let completed_chunks = parking_lot::Mutex::new(Vec::new());
             let store_chunk = |chunk_indices: Vec<u64>| -> Result<(), ArrayError> {
+                progress.check_cancelled()?;
                 let chunk_subset_in_array = unsafe {
                     self.chunk_grid()
                         .subset_unchecked(&chunk_indices, self.shape())
@@ -360,28 +596,78 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
                         self.data_type().size(),
                     )
                 };
+                let num_bytes = chunk_subset_bytes.len() as u64;
                 self.store_chunk_subset_opt(
                     &chunk_indices,
                     &array_subset_in_chunk_subset,
                     chunk_subset_bytes,
                     &options,
-                )
+                )?;
+                progress.chunk_completed(num_bytes);
+                completed_chunks.lock().push(chunk_indices);
+                Ok(())
             };
 
             let indices = chunks.indices();
-            rayon_iter_concurrent_limit::iter_concurrent_limit!(
+            let result = rayon_iter_concurrent_limit::iter_concurrent_limit!(
                 chunk_concurrent_limit,
                 indices.into_par_iter(),
                 try_for_each,
                 store_chunk
-            )?;
+            );
+            self.finish_write_journal(write_journal, result, completed_chunks.into_inner())?;
+        }
+        Ok(())
+    }
+
+    /// Explicit options version of [`update_array_subset_with`](Array::update_array_subset_with).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn update_array_subset_with_opt<T: Element>(
+        &self,
+        array_subset: &ArraySubset,
+        mut f: impl FnMut(&ArrayIndices, &mut T),
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        validate_element_size::<T>(self.data_type())?;
+        if array_subset.dimensionality() != self.dimensionality() {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        }
+        let Some(chunks) = self.chunks_in_array_subset(array_subset)? else {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        };
+        let progress = ProgressTracker::new(options, chunks.num_elements_usize());
+        for chunk_indices in &chunks.indices() {
+            progress.check_cancelled()?;
+
+            // Lock the chunk
+            let key = data_key(self.path(), &chunk_indices, self.chunk_key_encoding());
+            let mutex = match &self.store_locks {
+                Some(store_locks) => store_locks.mutex(&key),
+                None => self.storage.mutex(&key)?,
+            };
+            let _lock = mutex.lock()?;
+
+            // Retrieve, update, and store the chunk
+            let chunk_subset = self.chunk_subset(&chunk_indices)?;
+            let overlap = unsafe { chunk_subset.overlap_unchecked(array_subset) };
+            let chunk_bytes = self.retrieve_chunk_opt(&chunk_indices, options)?;
+            let chunk_bytes = update_chunk_elements(chunk_bytes, &chunk_subset, &overlap, &mut f)?;
+            let num_bytes = chunk_bytes.len() as u64;
+            self.store_chunk_opt(&chunk_indices, chunk_bytes, options)?;
+            progress.chunk_completed(num_bytes);
         }
         Ok(())
     }
 
     /// Explicit options version of [`store_array_subset_elements`](Array::store_array_subset_elements).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub fn store_array_subset_elements_opt<T: bytemuck::Pod>(
+    pub fn store_array_subset_elements_opt<T: Element>(
         &self,
         array_subset: &ArraySubset,
         subset_elements: Vec<T>,
@@ -398,7 +684,7 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
     /// Explicit options version of [`store_array_subset_ndarray`](Array::store_array_subset_ndarray).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub fn store_array_subset_ndarray_opt<
-        T: bytemuck::Pod,
+        T: Element,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -418,4 +704,152 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
             store_array_subset_elements_opt(&subset, subset_array, options)
         )
     }
+
+    #[cfg(feature = "arrow")]
+    /// Explicit options version of [`store_array_subset_arrow`](Array::store_array_subset_arrow).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_array_subset_arrow_opt<T: arrow_array::ArrowPrimitiveType>(
+        &self,
+        array_subset: &ArraySubset,
+        subset_array: &arrow_array::PrimitiveArray<T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError>
+    where
+        T::Native: Element,
+    {
+        validate_arrow_nulls(subset_array)?;
+        self.store_array_subset_elements_opt(array_subset, subset_array.values().to_vec(), options)
+    }
+
+    /// Explicit options version of [`resize`](Array::resize).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn resize_opt(
+        &mut self,
+        new_shape: ArrayShape,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let chunks_outside = self.chunks_outside_shape(&new_shape)?;
+
+        // Erase chunks outside of the new shape, and fill elements of chunks partially outside of the new shape
+        let resize_chunk = |chunk_indices: ArrayIndices| -> Result<(), ArrayError> {
+            let chunk_subset = self
+                .chunk_grid()
+                .subset(&chunk_indices, self.shape())?
+                .ok_or_else(|| ArrayError::InvalidChunkGridIndicesError(chunk_indices.clone()))?;
+            if std::iter::zip(chunk_subset.start(), &new_shape).any(|(start, size)| start >= size) {
+                self.erase_chunk(&chunk_indices)?;
+            } else if let Some(chunk_bytes) =
+                self.retrieve_chunk_if_exists_opt(&chunk_indices, options)?
+            {
+                let chunk_bytes = self.chunk_bytes_bounded(&chunk_subset, &chunk_bytes, &new_shape);
+                self.store_chunk_opt(&chunk_indices, chunk_bytes, options)?;
+            }
+            Ok(())
+        };
+        chunks_outside.into_par_iter().try_for_each(resize_chunk)?;
+
+        self.set_shape(new_shape);
+        Ok(())
+    }
+
+    /// Explicit options version of [`append`](Array::append).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn append_opt(
+        &mut self,
+        axis: usize,
+        append_bytes: Vec<u8>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let (append_subset, new_shape) = self.append_subset(axis, append_bytes.len())?;
+        self.resize_opt(new_shape, options)?;
+        self.store_array_subset_opt(&append_subset, append_bytes, options)
+    }
+
+    /// Explicit options version of [`append_elements`](Array::append_elements).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn append_elements_opt<T: Element>(
+        &mut self,
+        axis: usize,
+        append_elements: Vec<T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        array_store_elements!(
+            self,
+            append_elements,
+            append_opt(axis, append_elements, options)
+        )
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Explicit options version of [`append_ndarray`](Array::append_ndarray).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn append_ndarray_opt<
+        T: Element,
+        TArray: Into<ndarray::Array<T, D>>,
+        D: ndarray::Dimension,
+    >(
+        &mut self,
+        axis: usize,
+        append_array: TArray,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let append_array: ndarray::Array<T, D> = append_array.into();
+        self.validate_append_ndarray_shape(axis, append_array.shape())?;
+        array_store_ndarray!(
+            self,
+            append_array,
+            append_elements_opt(axis, append_array, options)
+        )
+    }
+
+    /// Explicit options version of [`append_ragged`](Array::append_ragged).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn append_ragged_opt(
+        &mut self,
+        axis: usize,
+        append_bytes: Vec<u8>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let (append_subset, new_shape) = self.append_subset(axis, append_bytes.len())?;
+        self.chunk_grid = self.append_ragged_chunk_grid(axis, &append_subset)?;
+        self.set_shape(new_shape);
+        self.store_array_subset_opt(&append_subset, append_bytes, options)
+    }
+
+    /// Explicit options version of [`append_ragged_elements`](Array::append_ragged_elements).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn append_ragged_elements_opt<T: Element>(
+        &mut self,
+        axis: usize,
+        append_elements: Vec<T>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        array_store_elements!(
+            self,
+            append_elements,
+            append_ragged_opt(axis, append_elements, options)
+        )
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Explicit options version of [`append_ragged_ndarray`](Array::append_ragged_ndarray).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub fn append_ragged_ndarray_opt<
+        T: Element,
+        TArray: Into<ndarray::Array<T, D>>,
+        D: ndarray::Dimension,
+    >(
+        &mut self,
+        axis: usize,
+        append_array: TArray,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let append_array: ndarray::Array<T, D> = append_array.into();
+        self.validate_append_ndarray_shape(axis, append_array.shape())?;
+        array_store_ndarray!(
+            self,
+            append_array,
+            append_ragged_elements_opt(axis, append_array, options)
+        )
+    }
 }
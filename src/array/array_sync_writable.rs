@@ -1,36 +1,57 @@
 use std::sync::Arc;
 
+use bytes::Bytes;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use rayon_iter_concurrent_limit::iter_concurrent_limit;
 
 use crate::{
     array_subset::ArraySubset,
-    storage::{StorageError, StorageHandle, WritableStorageTraits},
+    storage::{ready, StorageError, StorageHandle, WritableStorageTraits},
 };
 
 use super::{
-    codec::{options::CodecOptions, ArrayCodecTraits},
+    codec::options::{CodecOptions, ProgressTracker},
     concurrency::concurrency_chunks_and_codec,
-    Array, ArrayError,
+    Array, ArrayError, ArrayIndices, Element,
 };
 
 impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
     /// Store metadata.
     ///
+    /// If the [`metadata_generation`](Array::metadata_generation) is known (e.g. the array was opened from a store that supports conditional writes), the metadata is only stored if it has not been modified in the store since it was read or last stored.
+    /// This prevents concurrent writers revising the array metadata from silently overwriting each other.
+    ///
     /// # Errors
-    /// Returns [`StorageError`] if there is an underlying store error.
+    /// Returns [`StorageError::PreconditionFailed`] if the metadata was modified concurrently, or [`StorageError`] if there is an underlying store error.
     pub fn store_metadata(&self) -> Result<(), StorageError> {
         let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
         let storage_transformer = self
             .storage_transformers()
             .create_writable_transformer(storage_handle);
+        let mut metadata_generation = self.metadata_generation.lock();
+        if let Some(generation) = metadata_generation.as_ref() {
+            match crate::storage::create_array_if_match(
+                &*storage_transformer,
+                self.path(),
+                &self.metadata(),
+                Some(generation),
+            ) {
+                Ok(generation) => {
+                    *metadata_generation = generation;
+                    return Ok(());
+                }
+                Err(StorageError::Unsupported(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        *metadata_generation = None;
         crate::storage::create_array(&*storage_transformer, self.path(), &self.metadata())
     }
 
     /// Encode `chunk_bytes` and store at `chunk_indices`.
     ///
     /// Use [`store_chunk_opt`](Array::store_chunk_opt) to control codec options.
-    /// A chunk composed entirely of the fill value will not be written to the store.
+    /// A chunk composed entirely of the fill value will not be written to the store (and is erased if it exists), unless [`CodecOptions::store_empty_chunks`] is enabled.
     ///
     /// # Errors
     /// Returns an [`ArrayError`] if
@@ -49,13 +70,13 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
     /// Encode `chunk_elements` and store at `chunk_indices`.
     ///
     /// Use [`store_chunk_elements_opt`](Array::store_chunk_elements_opt) to control codec options.
-    /// A chunk composed entirely of the fill value will not be written to the store.
+    /// A chunk composed entirely of the fill value will not be written to the store (and is erased if it exists), unless [`CodecOptions::store_empty_chunks`] is enabled.
     ///
     /// # Errors
     /// Returns an [`ArrayError`] if
     ///  - the size of  `T` does not match the data type size, or
     ///  - a [`store_chunk`](Array::store_chunk) error condition is met.
-    pub fn store_chunk_elements<T: bytemuck::Pod>(
+    pub fn store_chunk_elements<T: Element>(
         &self,
         chunk_indices: &[u64],
         chunk_elements: Vec<T>,
@@ -74,7 +95,7 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
     ///  - a [`store_chunk_elements`](Array::store_chunk_elements) error condition is met.
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub fn store_chunk_ndarray<
-        T: bytemuck::Pod,
+        T: Element,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -88,7 +109,7 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
     /// Encode `chunks_bytes` and store at the chunks with indices represented by the `chunks` array subset.
     ///
     /// Use [`store_chunks_opt`](Array::store_chunks_opt) to control codec options.
-    /// A chunk composed entirely of the fill value will not be written to the store.
+    /// A chunk composed entirely of the fill value will not be written to the store (and is erased if it exists), unless [`CodecOptions::store_empty_chunks`] is enabled.
     ///
     /// # Errors
     /// Returns an [`ArrayError`] if
@@ -113,7 +134,7 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
     ///  - the size of  `T` does not match the data type size, or
     ///  - a [`store_chunks`](Array::store_chunks) error condition is met.
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-    pub fn store_chunks_elements<T: bytemuck::Pod>(
+    pub fn store_chunks_elements<T: Element>(
         &self,
         chunks: &ArraySubset,
         chunks_elements: Vec<T>,
@@ -130,7 +151,7 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
     ///  - a [`store_chunks_elements`](Array::store_chunks_elements) error condition is met.
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub fn store_chunks_ndarray<
-        T: bytemuck::Pod,
+        T: Element,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -141,6 +162,45 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
         self.store_chunks_ndarray_opt(chunks, chunks_array, &CodecOptions::default())
     }
 
+    /// Encode `subset_bytes` and store in `array_subset`, which must be aligned with chunk boundaries.
+    ///
+    /// Use [`store_array_subset_exact_opt`](Array::store_array_subset_exact_opt) to control codec options.
+    /// Unlike [`store_array_subset`](Array::store_array_subset), this never retrieves or locks chunks for a read-modify-write.
+    /// Each chunk intersecting `array_subset` is encoded and stored as if by [`store_chunks`](Array::store_chunks).
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - `array_subset` does not exactly cover the chunks it intersects ([`ArrayError::UnalignedArraySubset`]), or
+    ///  - a [`store_chunks`](Array::store_chunks) error condition is met.
+    pub fn store_array_subset_exact(
+        &self,
+        array_subset: &ArraySubset,
+        subset_bytes: Vec<u8>,
+    ) -> Result<(), ArrayError> {
+        self.store_array_subset_exact_opt(array_subset, subset_bytes, &CodecOptions::default())
+    }
+
+    /// Store the encoded chunk `encoded_chunk` at `chunk_indices`.
+    ///
+    /// The bytes are stored through the storage transformers of the array, but are not encoded by the codec chain.
+    /// `encoded_chunk` must be the exact serialised representation of the chunk (e.g. from [`retrieve_encoded_chunk`](Array::retrieve_encoded_chunk) of an array with the same codecs), as it is not validated.
+    /// The statistics of the chunk are discarded.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if `chunk_indices` are invalid or there is an underlying store error.
+    pub fn store_encoded_chunk(
+        &self,
+        chunk_indices: &[u64],
+        encoded_chunk: &[u8],
+    ) -> Result<(), ArrayError> {
+        ready(self.maybe_async_store_encoded_chunk(
+            &self.sync_writable_storage(),
+            chunk_indices,
+            Bytes::copy_from_slice(encoded_chunk),
+            None,
+        ))
+    }
+
     /// Erase the chunk at `chunk_indices`.
     ///
     /// Succeeds if the chunk does not exist.
@@ -148,16 +208,7 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
     /// # Errors
     /// Returns a [`StorageError`] if there is an underlying store error.
     pub fn erase_chunk(&self, chunk_indices: &[u64]) -> Result<(), StorageError> {
-        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-        let storage_transformer = self
-            .storage_transformers()
-            .create_writable_transformer(storage_handle);
-        crate::storage::erase_chunk(
-            &*storage_transformer,
-            self.path(),
-            chunk_indices,
-            self.chunk_key_encoding(),
-        )
+        ready(self.maybe_async_erase_chunk(&self.sync_writable_storage(), chunk_indices))
     }
 
     /// Erase the chunks in `chunks`.
@@ -165,20 +216,26 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
     /// # Errors
     /// Returns a [`StorageError`] if there is an underlying store error.
     pub fn erase_chunks(&self, chunks: &ArraySubset) -> Result<(), StorageError> {
+        let storage = self.sync_writable_storage();
+        let erase_chunk =
+            |chunk_indices: Vec<u64>| ready(self.maybe_async_erase_chunk(&storage, &chunk_indices));
+
+        chunks.indices().into_par_iter().try_for_each(erase_chunk)
+    }
+
+    /// Erase the array, including its metadata and all of its chunks.
+    ///
+    /// All keys under the array path are erased, so this should only be used for arrays that are not the root node of a hierarchy with other nodes.
+    /// Succeeds if the array does not exist in the store.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an underlying store error.
+    pub fn erase(&self) -> Result<(), StorageError> {
         let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
         let storage_transformer = self
             .storage_transformers()
             .create_writable_transformer(storage_handle);
-        let erase_chunk = |chunk_indices: Vec<u64>| {
-            crate::storage::erase_chunk(
-                &*storage_transformer,
-                self.path(),
-                &chunk_indices,
-                self.chunk_key_encoding(),
-            )
-        };
-
-        chunks.indices().into_par_iter().try_for_each(erase_chunk)
+        crate::storage::erase_node(&*storage_transformer, self.path())
     }
 
     /////////////////////////////////////////////////////////////////////////////
@@ -187,48 +244,35 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
 
     /// Explicit options version of [`store_chunk`](Array::store_chunk).
     #[allow(clippy::missing_errors_doc)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(path = %self.path(), chunk_indices = ?chunk_indices))
+    )]
     pub fn store_chunk_opt(
         &self,
         chunk_indices: &[u64],
         chunk_bytes: Vec<u8>,
         options: &CodecOptions,
     ) -> Result<(), ArrayError> {
-        // Validation
-        let chunk_array_representation = self.chunk_array_representation(chunk_indices)?;
-        if chunk_bytes.len() as u64 != chunk_array_representation.size() {
-            return Err(ArrayError::InvalidBytesInputSize(
-                chunk_bytes.len(),
-                chunk_array_representation.size(),
-            ));
-        }
-
-        let all_fill_value = self.fill_value().equals_all(&chunk_bytes);
-        if all_fill_value {
-            self.erase_chunk(chunk_indices)?;
-            Ok(())
-        } else {
-            let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-            let storage_transformer = self
-                .storage_transformers()
-                .create_writable_transformer(storage_handle);
-            let chunk_encoded: Vec<u8> = self
-                .codecs()
-                .encode(chunk_bytes, &chunk_array_representation, options)
-                .map_err(ArrayError::CodecError)?;
-            crate::storage::store_chunk(
-                &*storage_transformer,
-                self.path(),
+        if let Some((chunk_encoded, chunk_statistics)) =
+            self.encode_chunk(chunk_indices, chunk_bytes, options)?
+        {
+            ready(self.maybe_async_store_encoded_chunk(
+                &self.sync_writable_storage(),
                 chunk_indices,
-                self.chunk_key_encoding(),
-                &chunk_encoded,
-            )
-            .map_err(ArrayError::StorageError)
+                Bytes::from(chunk_encoded),
+                chunk_statistics.as_ref(),
+            ))?;
+        } else {
+            self.erase_chunk(chunk_indices)?;
         }
+        self.invalidate_cached_shard_index(chunk_indices, options);
+        Ok(())
     }
 
     /// Explicit options version of [`store_chunk_elements`](Array::store_chunk_elements).
     #[allow(clippy::missing_errors_doc)]
-    pub fn store_chunk_elements_opt<T: bytemuck::Pod>(
+    pub fn store_chunk_elements_opt<T: Element>(
         &self,
         chunk_indices: &[u64],
         chunk_elements: Vec<T>,
@@ -245,7 +289,7 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
     /// Explicit options version of [`store_chunk_ndarray`](Array::store_chunk_ndarray).
     #[allow(clippy::missing_errors_doc)]
     pub fn store_chunk_ndarray_opt<
-        T: bytemuck::Pod,
+        T: Element,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -278,13 +322,29 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
         chunks: &ArraySubset,
         chunks_bytes: Vec<u8>,
         options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        self.store_chunks_journaled_opt(chunks, chunks_bytes, None, options)
+    }
+
+    /// Store `chunks` with the chunks that were stored before (`existing_chunks`) recorded in the write journal, if it is enabled.
+    #[allow(clippy::similar_names)]
+    pub(super) fn store_chunks_journaled_opt(
+        &self,
+        chunks: &ArraySubset,
+        chunks_bytes: Vec<u8>,
+        existing_chunks: Option<Vec<ArrayIndices>>,
+        options: &CodecOptions,
     ) -> Result<(), ArrayError> {
         let num_chunks = chunks.num_elements_usize();
+        let progress = ProgressTracker::new(options, num_chunks);
+        progress.check_cancelled()?;
         match num_chunks {
             0 => {}
             1 => {
                 let chunk_indices = chunks.start();
+                let num_bytes = chunks_bytes.len() as u64;
                 self.store_chunk_opt(chunk_indices, chunks_bytes, options)?;
+                progress.chunk_completed(num_bytes);
             }
             _ => {
                 let array_subset = self.chunks_subset(chunks)?;
@@ -297,6 +357,8 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
                     ));
                 }
 
+                let write_journal = self.begin_write_journal(chunks, existing_chunks, options)?;
+
                 // Calculate chunk/codec concurrency
                 let chunk_representation =
                     self.chunk_array_representation(&vec![0; self.dimensionality()])?;
@@ -307,9 +369,12 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
                     num_chunks,
                     options,
                     &codec_concurrency,
+                    self.chunk_memory_usage(&chunk_representation)?,
                 );
 
+                let completed_chunks = parking_lot::Mutex::new(Vec::new());
                 let store_chunk = |chunk_indices: Vec<u64>| -> Result<(), ArrayError> {
+                    progress.check_cancelled()?;
                     let chunk_subset_in_array = unsafe {
                         self.chunk_grid()
                             .subset_unchecked(&chunk_indices, self.shape())
@@ -334,15 +399,20 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
                         chunk_subset_in_array_subset.num_elements()
                     );
 
-                    self.store_chunk_opt(&chunk_indices, chunk_bytes, &options)
+                    let num_bytes = chunk_bytes.len() as u64;
+                    self.store_chunk_opt(&chunk_indices, chunk_bytes, &options)?;
+                    progress.chunk_completed(num_bytes);
+                    completed_chunks.lock().push(chunk_indices);
+                    Ok(())
                 };
                 let indices = chunks.indices();
-                iter_concurrent_limit!(
+                let result = iter_concurrent_limit!(
                     chunk_concurrent_limit,
                     indices.into_par_iter(),
                     try_for_each,
                     store_chunk
-                )?;
+                );
+                self.finish_write_journal(write_journal, result, completed_chunks.into_inner())?;
             }
         }
 
@@ -351,7 +421,7 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
 
     /// Explicit options version of [`store_chunks_elements`](Array::store_chunks_elements).
     #[allow(clippy::missing_errors_doc)]
-    pub fn store_chunks_elements_opt<T: bytemuck::Pod>(
+    pub fn store_chunks_elements_opt<T: Element>(
         &self,
         chunks: &ArraySubset,
         chunks_elements: Vec<T>,
@@ -368,7 +438,7 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
     /// Explicit options version of [`store_chunks_ndarray`](Array::store_chunks_ndarray).
     #[allow(clippy::missing_errors_doc)]
     pub fn store_chunks_ndarray_opt<
-        T: bytemuck::Pod,
+        T: Element,
         TArray: Into<ndarray::Array<T, D>>,
         D: ndarray::Dimension,
     >(
@@ -393,4 +463,16 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
             ))
         }
     }
+
+    /// Explicit options version of [`store_array_subset_exact`](Array::store_array_subset_exact).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_array_subset_exact_opt(
+        &self,
+        array_subset: &ArraySubset,
+        subset_bytes: Vec<u8>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let chunks = self.chunks_in_aligned_array_subset(array_subset)?;
+        self.store_chunks_opt(&chunks, subset_bytes, options)
+    }
 }
@@ -0,0 +1,420 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use bytes::Bytes;
+use parking_lot::Mutex;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::{ByteRange, InvalidByteRangeError},
+    storage::{
+        meta_key,
+        store_lock::{DefaultStoreLocks, StoreKeyMutex, StoreLocks},
+        store_set_partial_values, ReadableStorageTraits, ReadableWritableStorageTraits,
+        StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue, StorePrefix,
+        WritableStorageTraits,
+    },
+};
+
+use super::{Array, ArrayCreateError, ArrayError};
+
+/// The storage of an [`ArrayTransaction`].
+///
+/// Values written to the storage are held in memory, and values that have not been written are read from the underlying storage.
+/// Erased values are recorded and read as absent.
+#[derive(Debug)]
+pub struct TransactionStorage<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    staged: Mutex<BTreeMap<StoreKey, MaybeBytes>>,
+    locks: StoreLocks,
+}
+
+impl<TStorage: ?Sized> TransactionStorage<TStorage> {
+    fn new(storage: Arc<TStorage>) -> Self {
+        Self {
+            storage,
+            staged: Mutex::default(),
+            locks: Arc::new(DefaultStoreLocks::default()),
+        }
+    }
+
+    /// Return the number of staged values (including erased values).
+    #[must_use]
+    pub fn num_staged_values(&self) -> usize {
+        self.staged.lock().len()
+    }
+
+    /// Return the staged value at `key`, or [`None`] if `key` has not been written or erased.
+    fn staged(&self, key: &StoreKey) -> Option<MaybeBytes> {
+        self.staged.lock().get(key).cloned()
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for TransactionStorage<TStorage>
+{
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        match self.staged(key) {
+            Some(value) => Ok(value),
+            None => self.storage.get(key),
+        }
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        match self.staged(key) {
+            Some(Some(value)) => {
+                let mut out = Vec::with_capacity(byte_ranges.len());
+                for byte_range in byte_ranges {
+                    let size = value.len() as u64;
+                    let (start, end) = (byte_range.start(size), byte_range.end(size));
+                    if end > size {
+                        return Err(InvalidByteRangeError::new(*byte_range, size).into());
+                    }
+                    out.push(
+                        value.slice(usize::try_from(start).unwrap()..usize::try_from(end).unwrap()),
+                    );
+                }
+                Ok(Some(out))
+            }
+            Some(None) => Ok(None),
+            None => self.storage.get_partial_values_key(key, byte_ranges),
+        }
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges)
+    }
+
+    fn size_prefix(&self, _prefix: &StorePrefix) -> Result<u64, StorageError> {
+        Err(StorageError::Unsupported(
+            "prefix operations are not supported in an array transaction".to_string(),
+        ))
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        match self.staged(key) {
+            Some(value) => Ok(value.map(|value| value.len() as u64)),
+            None => self.storage.size_key(key),
+        }
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + Send + Sync> WritableStorageTraits
+    for TransactionStorage<TStorage>
+{
+    fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        self.staged
+            .lock()
+            .insert(key.clone(), Some(Bytes::copy_from_slice(value)));
+        Ok(())
+    }
+
+    fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        store_set_partial_values(self, key_start_values)
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.staged.lock().insert(key.clone(), None);
+        Ok(())
+    }
+
+    fn erase_prefix(&self, _prefix: &StorePrefix) -> Result<(), StorageError> {
+        Err(StorageError::Unsupported(
+            "prefix operations are not supported in an array transaction".to_string(),
+        ))
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + Send + Sync> ReadableWritableStorageTraits
+    for TransactionStorage<TStorage>
+{
+    fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+        Ok(self.locks.mutex(key))
+    }
+}
+
+/// A write transaction on an [`Array`].
+///
+/// An [`ArrayTransaction`] holds an array ([`array`](ArrayTransaction::array)) with the same metadata as the transaction array, which is backed by a [`TransactionStorage`].
+/// Chunks and array subsets stored to this array (e.g. with [`Array::store_chunk`] or [`Array::store_array_subset`]) are encoded and held in memory, and are visible to reads of this array but not to readers of the underlying store.
+/// The metadata of the array can be revised with [`array_mut`](ArrayTransaction::array_mut) and staged with [`Array::store_metadata`].
+///
+/// On [`commit`](ArrayTransaction::commit), staged chunks are written to the store before staged metadata.
+/// If a write fails, the values that were already written are restored to their state before the commit (on a best-effort basis).
+/// A transaction that is dropped without committing is rolled back.
+///
+/// A commit is not atomic with respect to concurrent readers of the store, and it overwrites any changes made in the store since values were staged.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use zarrs::array::{ArrayBuilder, ArrayTransaction, DataType, FillValue};
+/// # use zarrs::array_subset::ArraySubset;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let store = Arc::new(zarrs::storage::store::MemoryStore::new());
+/// let array = ArrayBuilder::new(vec![4, 4], DataType::UInt8, vec![2, 2].try_into()?, FillValue::from(0u8))
+///     .build(store, "/array")?;
+/// let mut transaction = ArrayTransaction::new(&array)?;
+/// transaction.array().store_array_subset_elements::<u8>(&ArraySubset::new_with_ranges(&[0..4, 0..2]), vec![1; 8])?;
+/// transaction.array_mut().attributes_mut().insert("ingested".to_string(), true.into());
+/// transaction.array().store_metadata()?;
+/// assert!(array.list_stored_chunks()?.is_empty());
+/// transaction.commit()?;
+/// assert_eq!(array.list_stored_chunks()?.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ArrayTransaction<'a, TStorage: ?Sized> {
+    array: &'a Array<TStorage>,
+    transaction_array: Array<TransactionStorage<TStorage>>,
+}
+
+impl<'a, TStorage: ?Sized + ReadableWritableStorageTraits + 'static>
+    ArrayTransaction<'a, TStorage>
+{
+    /// Create a new transaction on `array`.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayCreateError`] if the transaction array cannot be created from the metadata of `array`.
+    pub fn new(array: &'a Array<TStorage>) -> Result<Self, ArrayCreateError> {
+        let storage = Arc::new(TransactionStorage::new(array.storage.clone()));
+        let mut transaction_array =
+            Array::new_with_metadata(storage, array.path().as_str(), array.metadata())?;
+        transaction_array.set_include_zarrs_metadata(array.include_zarrs_metadata);
+        Ok(Self {
+            array,
+            transaction_array,
+        })
+    }
+
+    /// Return the array which stages writes in the transaction.
+    #[must_use]
+    pub const fn array(&self) -> &Array<TransactionStorage<TStorage>> {
+        &self.transaction_array
+    }
+
+    /// Mutably borrow the array which stages writes in the transaction (e.g. to revise its metadata).
+    #[must_use]
+    pub fn array_mut(&mut self) -> &mut Array<TransactionStorage<TStorage>> {
+        &mut self.transaction_array
+    }
+
+    /// Return the number of staged values (including erased values).
+    #[must_use]
+    pub fn num_staged_values(&self) -> usize {
+        self.transaction_array.storage.num_staged_values()
+    }
+
+    /// Commit the transaction.
+    ///
+    /// Staged chunks are written to the store first, followed by staged metadata.
+    /// If a write fails, the values written by the commit are restored to their previous state on a best-effort basis.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if there is an underlying store error.
+    pub fn commit(self) -> Result<(), ArrayError> {
+        let storage = &self.array.storage;
+        let staged = std::mem::take(&mut *self.transaction_array.storage.staged.lock());
+        let meta_key = meta_key(self.array.path());
+        let (metadata, chunks): (Vec<_>, Vec<_>) =
+            staged.into_iter().partition(|(key, _)| *key == meta_key);
+
+        let mut previous: Vec<(StoreKey, MaybeBytes)> = Vec::new();
+        let mut write = |key: StoreKey, value: MaybeBytes| -> Result<(), StorageError> {
+            let previous_value = storage.get(&key)?;
+            previous.push((key.clone(), previous_value));
+            match value {
+                Some(value) => storage.set(&key, &value),
+                None => storage.erase(&key),
+            }
+        };
+        let result = chunks
+            .into_iter()
+            .chain(metadata)
+            .try_for_each(|(key, value)| write(key, value));
+
+        if let Err(err) = result {
+            // Roll back in reverse order, ignoring errors since the original error takes precedence
+            for (key, value) in previous.into_iter().rev() {
+                let _ = match value {
+                    Some(value) => storage.set(&key, &value),
+                    None => storage.erase(&key),
+                };
+            }
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    /// Roll back the transaction, discarding all staged values.
+    ///
+    /// This is equivalent to dropping the transaction.
+    pub fn rollback(self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        array_subset::ArraySubset,
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    /// A store which fails to write values with a key ending in `fail_suffix`.
+    #[derive(Debug)]
+    struct FailingStore {
+        store: MemoryStore,
+        fail_suffix: &'static str,
+    }
+
+    impl ReadableStorageTraits for FailingStore {
+        fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+            self.store.get(key)
+        }
+
+        fn get_partial_values_key(
+            &self,
+            key: &StoreKey,
+            byte_ranges: &[ByteRange],
+        ) -> Result<Option<Vec<Bytes>>, StorageError> {
+            self.store.get_partial_values_key(key, byte_ranges)
+        }
+
+        fn get_partial_values(
+            &self,
+            key_ranges: &[StoreKeyRange],
+        ) -> Result<Vec<MaybeBytes>, StorageError> {
+            self.store.get_partial_values(key_ranges)
+        }
+
+        fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+            self.store.size_prefix(prefix)
+        }
+
+        fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+            self.store.size_key(key)
+        }
+    }
+
+    impl WritableStorageTraits for FailingStore {
+        fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+            if key.as_str().ends_with(self.fail_suffix) {
+                Err(StorageError::Other("write failed".to_string()))
+            } else {
+                self.store.set(key, value)
+            }
+        }
+
+        fn set_partial_values(
+            &self,
+            key_start_values: &[StoreKeyStartValue],
+        ) -> Result<(), StorageError> {
+            store_set_partial_values(self, key_start_values)
+        }
+
+        fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+            self.store.erase(key)
+        }
+
+        fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+            self.store.erase_prefix(prefix)
+        }
+    }
+
+    impl ReadableWritableStorageTraits for FailingStore {
+        fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+            self.store.mutex(key)
+        }
+    }
+
+    fn array<TStorage: ?Sized>(store: Arc<TStorage>) -> Array<TStorage> {
+        ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/array")
+        .unwrap()
+    }
+
+    #[test]
+    fn array_transaction_commit() {
+        let store = Arc::new(MemoryStore::new());
+        let array = array(store.clone());
+        array.store_metadata().unwrap();
+        array
+            .store_chunk_elements::<u8>(&[1, 1], vec![9; 4])
+            .unwrap();
+
+        let mut transaction = ArrayTransaction::new(&array).unwrap();
+        transaction
+            .array()
+            .store_array_subset_elements::<u8>(
+                &ArraySubset::new_with_ranges(&[0..2, 0..4]),
+                vec![1; 8],
+            )
+            .unwrap();
+        transaction
+            .array()
+            .store_chunk_elements::<u8>(&[1, 1], vec![0; 4])
+            .unwrap();
+        transaction.array_mut().set_shape(vec![4, 8]);
+        transaction.array().store_metadata().unwrap();
+        assert_eq!(
+            transaction
+                .array()
+                .retrieve_chunk_elements::<u8>(&[0, 1])
+                .unwrap(),
+            vec![1; 4]
+        );
+        assert_eq!(transaction.num_staged_values(), 4);
+
+        // Nothing is visible before commit
+        assert_eq!(array.list_stored_chunks().unwrap(), vec![vec![1, 1]]);
+        transaction.commit().unwrap();
+        assert_eq!(
+            array.list_stored_chunks().unwrap(),
+            vec![vec![0, 0], vec![0, 1]]
+        );
+        assert_eq!(Array::new(store, "/array").unwrap().shape(), &[4, 8]);
+    }
+
+    #[test]
+    fn array_transaction_rollback() {
+        let store = Arc::new(FailingStore {
+            store: MemoryStore::new(),
+            fail_suffix: "c/1/1",
+        });
+        let array = array(store.clone());
+        array
+            .store_chunk_elements::<u8>(&[0, 0], vec![9; 4])
+            .unwrap();
+
+        let transaction = ArrayTransaction::new(&array).unwrap();
+        transaction
+            .array()
+            .store_chunk_elements::<u8>(&[0, 0], vec![1; 4])
+            .unwrap();
+        transaction
+            .array()
+            .store_chunk_elements::<u8>(&[1, 1], vec![1; 4])
+            .unwrap();
+        assert!(transaction.commit().is_err());
+        assert_eq!(
+            array.retrieve_chunk_elements::<u8>(&[0, 0]).unwrap(),
+            vec![9; 4]
+        );
+        assert!(array.retrieve_chunk_if_exists(&[1, 1]).unwrap().is_none());
+    }
+}
@@ -0,0 +1,277 @@
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
+
+use crate::storage::{data_key, ListableStorageTraits, ReadableStorageTraits, StoreKey};
+
+#[cfg(feature = "async")]
+use crate::storage::{AsyncListableStorageTraits, AsyncReadableStorageTraits};
+
+use super::{
+    codec::{options::ProgressTracker, CodecOptions},
+    concurrency::concurrency_chunks_and_codec,
+    Array, ArrayError, ArrayIndices,
+};
+
+/// A stored chunk which failed verification.
+#[derive(Debug)]
+pub struct CorruptChunk {
+    chunk_indices: ArrayIndices,
+    key: StoreKey,
+    error: ArrayError,
+}
+
+impl CorruptChunk {
+    /// Return the indices of the chunk.
+    #[must_use]
+    pub fn chunk_indices(&self) -> &[u64] {
+        &self.chunk_indices
+    }
+
+    /// Return the store key of the chunk.
+    #[must_use]
+    pub const fn key(&self) -> &StoreKey {
+        &self.key
+    }
+
+    /// Return the error encountered when decoding the chunk.
+    #[must_use]
+    pub const fn error(&self) -> &ArrayError {
+        &self.error
+    }
+}
+
+/// The report of [`Array::verify`].
+#[derive(Debug, Default)]
+pub struct ArrayVerifyReport {
+    chunks_verified: usize,
+    corrupt_chunks: Vec<CorruptChunk>,
+}
+
+impl ArrayVerifyReport {
+    /// Return the number of stored chunks that were verified.
+    #[must_use]
+    pub const fn chunks_verified(&self) -> usize {
+        self.chunks_verified
+    }
+
+    /// Return the stored chunks that failed verification, in C order.
+    #[must_use]
+    pub fn corrupt_chunks(&self) -> &[CorruptChunk] {
+        &self.corrupt_chunks
+    }
+
+    /// Returns true if no stored chunks failed verification.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.corrupt_chunks.is_empty()
+    }
+
+    fn new(chunks_verified: usize, mut corrupt_chunks: Vec<CorruptChunk>) -> Self {
+        corrupt_chunks.sort_by(|a, b| a.chunk_indices.cmp(&b.chunk_indices));
+        Self {
+            chunks_verified,
+            corrupt_chunks,
+        }
+    }
+}
+
+impl<TStorage: ?Sized> Array<TStorage> {
+    /// Classify the result of decoding the chunk at `chunk_indices`.
+    ///
+    /// Storage errors and cancellation are returned as errors, other errors indicate a corrupt chunk.
+    fn verify_chunk_result<T>(
+        &self,
+        chunk_indices: ArrayIndices,
+        result: Result<T, ArrayError>,
+    ) -> Result<Option<CorruptChunk>, ArrayError> {
+        match result {
+            Ok(_) => Ok(None),
+            Err(err @ (ArrayError::StorageError(_) | ArrayError::Cancelled)) => Err(err),
+            Err(error) => Ok(Some(CorruptChunk {
+                key: data_key(self.path(), &chunk_indices, self.chunk_key_encoding()),
+                chunk_indices,
+                error,
+            })),
+        }
+    }
+
+    /// Return the options and chunk concurrent limit for verifying `num_chunks` chunks.
+    fn verify_options(
+        &self,
+        num_chunks: usize,
+        options: &CodecOptions,
+    ) -> Result<(usize, CodecOptions), ArrayError> {
+        let chunk_representation =
+            self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+        let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+        let (chunk_concurrent_limit, mut options) = concurrency_chunks_and_codec(
+            options.concurrent_target(),
+            num_chunks,
+            options,
+            &codec_concurrency,
+            self.chunk_memory_usage(&chunk_representation)?,
+        );
+        options.set_validate_checksums(true);
+        Ok((chunk_concurrent_limit, options))
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits + 'static> Array<TStorage> {
+    /// Verify the integrity of all stored chunks.
+    ///
+    /// Each stored chunk is retrieved and decoded with checksum validation enabled (regardless of `options`), and its decoded size is checked against the chunk shape.
+    /// Chunks which fail to decode are reported in the returned [`ArrayVerifyReport`] rather than returned as an error.
+    /// Chunks are verified in parallel, subject to the concurrency target of `options`.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the stored chunks cannot be listed,
+    ///  - there is an underlying store error when retrieving a chunk, or
+    ///  - the operation is cancelled.
+    pub fn verify(&self, options: &CodecOptions) -> Result<ArrayVerifyReport, ArrayError> {
+        let chunks = self.list_stored_chunks()?;
+        let num_chunks = chunks.len();
+        let (chunk_concurrent_limit, options) = self.verify_options(num_chunks, options)?;
+        let progress = ProgressTracker::new(&options, num_chunks);
+        progress.check_cancelled()?;
+
+        let verify_chunk = |chunk_indices: ArrayIndices| {
+            progress.check_cancelled()?;
+            let result = self.retrieve_chunk_if_exists_opt(&chunk_indices, &options);
+            let num_bytes = result
+                .as_ref()
+                .ok()
+                .and_then(Option::as_ref)
+                .map_or(0, |bytes| bytes.len() as u64);
+            let corrupt_chunk = self.verify_chunk_result(chunk_indices, result)?;
+            progress.chunk_completed(num_bytes);
+            Ok::<_, ArrayError>(corrupt_chunk)
+        };
+        let corrupt_chunks = iter_concurrent_limit!(
+            chunk_concurrent_limit,
+            chunks.into_par_iter(),
+            map,
+            verify_chunk
+        )
+        .filter_map(Result::transpose)
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(ArrayVerifyReport::new(num_chunks, corrupt_chunks))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits + 'static>
+    Array<TStorage>
+{
+    /// Async variant of [`verify`](Array::verify).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_verify(
+        &self,
+        options: &CodecOptions,
+    ) -> Result<ArrayVerifyReport, ArrayError> {
+        use futures::StreamExt;
+
+        let chunks = self.async_list_stored_chunks().await?;
+        let num_chunks = chunks.len();
+        let (chunk_concurrent_limit, options) = self.verify_options(num_chunks, options)?;
+        let progress = ProgressTracker::new(&options, num_chunks);
+        progress.check_cancelled()?;
+
+        let progress = &progress;
+        let options = &options;
+        let verify_chunk = |chunk_indices: ArrayIndices| async move {
+            progress.check_cancelled()?;
+            let result = self
+                .async_retrieve_chunk_if_exists_opt(&chunk_indices, options)
+                .await;
+            let num_bytes = result
+                .as_ref()
+                .ok()
+                .and_then(Option::as_ref)
+                .map_or(0, |bytes| bytes.len() as u64);
+            let corrupt_chunk = self.verify_chunk_result(chunk_indices, result)?;
+            progress.chunk_completed(num_bytes);
+            Ok::<_, ArrayError>(corrupt_chunk)
+        };
+        let mut stream = futures::stream::iter(chunks.into_iter().map(verify_chunk))
+            .buffer_unordered(chunk_concurrent_limit.max(1));
+        let mut corrupt_chunks = Vec::new();
+        while let Some(item) = stream.next().await {
+            if let Some(corrupt_chunk) = item? {
+                corrupt_chunks.push(corrupt_chunk);
+            }
+        }
+        Ok(ArrayVerifyReport::new(num_chunks, corrupt_chunks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{codec::Crc32cCodec, ArrayBuilder, DataType, FillValue},
+        array_subset::ArraySubset,
+        storage::{store::MemoryStore, ReadableStorageTraits, WritableStorageTraits},
+    };
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "crc32c")]
+    fn array_verify() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 6],
+            DataType::UInt16,
+            vec![2, 3].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .bytes_to_bytes_codecs(vec![Box::new(Crc32cCodec::new())])
+        .build(store.clone(), "/array")
+        .unwrap();
+        array
+            .store_array_subset_elements(
+                &ArraySubset::new_with_ranges(&[0..4, 0..6]),
+                (1..=24).collect::<Vec<u16>>(),
+            )
+            .unwrap();
+
+        let report = array.verify(&CodecOptions::default()).unwrap();
+        assert_eq!(report.chunks_verified(), 4);
+        assert!(report.is_ok());
+
+        // Flip a byte of one chunk, so its checksum fails
+        let key = data_key(array.path(), &[1, 0], array.chunk_key_encoding());
+        let mut bytes = store.get(&key).unwrap().unwrap().to_vec();
+        bytes[0] ^= 0xFF;
+        store.set(&key, &bytes).unwrap();
+        // Truncate another chunk
+        let key_truncated = data_key(array.path(), &[0, 1], array.chunk_key_encoding());
+        let bytes = store.get(&key_truncated).unwrap().unwrap();
+        store
+            .set(&key_truncated, &bytes[..bytes.len() - 6])
+            .unwrap();
+
+        let report = array
+            .verify(
+                &CodecOptions::builder()
+                    .validate_checksums(false)
+                    .concurrent_target(1)
+                    .build(),
+            )
+            .unwrap();
+        assert_eq!(report.chunks_verified(), 4);
+        assert!(!report.is_ok());
+        let corrupt_chunks = report.corrupt_chunks();
+        assert_eq!(corrupt_chunks.len(), 2);
+        assert_eq!(corrupt_chunks[0].chunk_indices(), &[0, 1]);
+        assert_eq!(corrupt_chunks[0].key(), &key_truncated);
+        assert_eq!(corrupt_chunks[1].chunk_indices(), &[1, 0]);
+        assert_eq!(corrupt_chunks[1].key(), &key);
+        assert!(matches!(
+            corrupt_chunks[1].error(),
+            ArrayError::CodecError(_)
+        ));
+    }
+}
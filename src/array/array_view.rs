@@ -7,6 +7,9 @@ use super::{unsafe_cell_slice::UnsafeCellSlice, ArrayShape};
 /// A view of a subset of an array.
 ///
 /// This class has various *hidden* unsafe functions which are used internally.
+///
+/// An [`ArrayView`] can be cloned and shared between threads, so writing to it from multiple threads is the responsibility of the caller.
+/// [`ArrayViewMut`] is a safe alternative for decoding into disjoint regions of an output buffer in parallel.
 // TODO: Element size as well for bytes/shape validation? But how to handle variable sized elements in the future?
 #[derive(Clone)]
 pub struct ArrayView<'a> {
@@ -25,6 +28,9 @@ pub enum ArrayViewCreateError {
     /// The subset has an incompatible dimensionality to the array shape.
     #[error(transparent)]
     SubsetIncompatibleDimensionality(#[from] IncompatibleDimensionalityError),
+    /// Array subsets of a split array view overlap.
+    #[error("the array subsets {_0} and {_1} overlap")]
+    OverlappingSubsets(ArraySubset, ArraySubset),
 }
 
 impl<'a> ArrayView<'a> {
@@ -109,3 +115,78 @@ impl<'a> ArrayView<'a> {
         }
     }
 }
+
+/// A mutable view of a subset of an array.
+///
+/// This is a safe alternative to [`ArrayView`] for decoding into an output buffer from multiple threads.
+/// An [`ArrayViewMut`] exclusively borrows its output and cannot be cloned.
+/// It can be split into multiple views of disjoint subsets with [`split_disjoint`](ArrayViewMut::split_disjoint), which can then be written to in parallel.
+///
+/// See methods with the `_into_array_view_mut` suffix of [`Array`](crate::array::Array), such as [`retrieve_chunk_into_array_view_mut`](crate::array::Array::retrieve_chunk_into_array_view_mut).
+pub struct ArrayViewMut<'a> {
+    view: ArrayView<'a>,
+}
+
+impl<'a> ArrayViewMut<'a> {
+    /// Create a new [`ArrayViewMut`].
+    ///
+    /// # Errors
+    /// Returns an error if the subset is out-of-bounds of the array or the dimensionality of `shape` and `subset` does not match.
+    pub fn new(
+        bytes: &'a mut [u8],
+        shape: &'a [u64],
+        subset: ArraySubset,
+    ) -> Result<Self, ArrayViewCreateError> {
+        Ok(Self {
+            view: ArrayView::new(bytes, shape, subset)?,
+        })
+    }
+
+    /// Return the subset of the array view.
+    #[must_use]
+    pub fn subset(&self) -> &ArraySubset {
+        self.view.subset()
+    }
+
+    /// Return the array shape of the array view.
+    #[must_use]
+    pub fn array_shape(&self) -> &[u64] {
+        self.view.array_shape()
+    }
+
+    /// Split the view into views of `subsets`, which are relative to the subset of this view.
+    ///
+    /// The returned views borrow this view, so they can be written to in parallel (e.g. with [`rayon`]) but this view cannot be used until they are dropped.
+    /// Checking that `subsets` are disjoint is quadratic in the number of subsets.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayViewCreateError`] if any subset has an incompatible dimensionality, extends beyond the bounds of this view, or overlaps another subset.
+    pub fn split_disjoint(
+        &mut self,
+        subsets: &[ArraySubset],
+    ) -> Result<Vec<ArrayViewMut<'_>>, ArrayViewCreateError> {
+        for (i, subset) in subsets.iter().enumerate() {
+            for subset_other in &subsets[..i] {
+                if !subset.overlap(subset_other)?.is_empty() {
+                    return Err(ArrayViewCreateError::OverlappingSubsets(
+                        subset_other.clone(),
+                        subset.clone(),
+                    ));
+                }
+            }
+        }
+        subsets
+            .iter()
+            .map(|subset| {
+                // SAFETY: The views reference disjoint regions of the array and borrow `self` mutably.
+                let view = unsafe { self.view.subset_view(subset) }?;
+                Ok(ArrayViewMut { view })
+            })
+            .collect()
+    }
+
+    /// Return the underlying [`ArrayView`].
+    pub(crate) fn array_view(&mut self) -> &ArrayView<'a> {
+        &self.view
+    }
+}
@@ -0,0 +1,547 @@
+use std::{collections::HashSet, sync::Arc};
+
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    array_subset::ArraySubset,
+    storage::{
+        data_key, write_journal_key, write_journal_prefix, ListableStorageTraits,
+        ReadableStorageTraits, StorageError, StorageHandle, StoreKey, WritableStorageTraits,
+    },
+};
+
+#[cfg(feature = "async")]
+use crate::storage::{
+    AsyncListableStorageTraits, AsyncReadableStorageTraits, AsyncWritableStorageTraits,
+};
+
+use super::{codec::CodecOptions, Array, ArrayError, ArrayIndices, ArrayShape};
+
+/// The write journal of an operation storing multiple chunks of an array which is in progress or was interrupted.
+///
+/// If [`CodecOptions::write_journal`] is enabled, [`Array::store_chunks`] and [`Array::store_array_subset`] (and their variants) store a write journal under a unique key before storing multiple chunks, and erase it once all chunks have been stored.
+/// A write journal that remains after an operation has finished indicates that the operation failed or was interrupted, and that any of its chunks may not have been stored.
+/// Concurrent operations store separate write journals, which are listed by [`Array::write_journals`].
+///
+/// A write journal records:
+///  - the chunks that existed before the operation, if the array storage is readable when the write journal is stored (e.g. [`Array::store_array_subset`]), and
+///  - the chunks that were stored by the operation, if it failed or was cancelled rather than interrupted.
+///
+/// An interrupted operation can be resumed by repeating it and then calling [`Array::clear_write_journal`], or rolled back with [`Array::erase_write_journal_chunks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteJournal {
+    id: String,
+    chunks: ArraySubset,
+    existing_chunks: Option<Vec<ArrayIndices>>,
+    completed_chunks: Option<Vec<ArrayIndices>>,
+}
+
+/// The stored representation of a [`WriteJournal`].
+#[derive(Serialize, Deserialize)]
+struct WriteJournalMetadata {
+    chunks_start: ArrayIndices,
+    chunks_shape: ArrayShape,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    existing_chunks: Option<Vec<ArrayIndices>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    completed_chunks: Option<Vec<ArrayIndices>>,
+}
+
+impl WriteJournal {
+    /// Return the identifier of the write journal, which is unique to the journaled operation.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Return the chunks written by the journaled operation.
+    #[must_use]
+    pub const fn chunks(&self) -> &ArraySubset {
+        &self.chunks
+    }
+
+    /// Return the chunks of [`chunks`](WriteJournal::chunks) that were stored before the journaled operation.
+    ///
+    /// Returns [`None`] if they are unknown, which is the case if the operation only had write access to the array storage (e.g. [`Array::store_chunks`]).
+    #[must_use]
+    pub fn existing_chunks(&self) -> Option<&[ArrayIndices]> {
+        self.existing_chunks.as_deref()
+    }
+
+    /// Return the chunks stored by the journaled operation before it failed or was cancelled.
+    ///
+    /// Returns [`None`] if they are unknown, which is the case if the operation is in progress or was interrupted (e.g. the process terminated).
+    #[must_use]
+    pub fn completed_chunks(&self) -> Option<&[ArrayIndices]> {
+        self.completed_chunks.as_deref()
+    }
+
+    /// Return the chunks that may have been created by the journaled operation.
+    ///
+    /// These are the completed chunks (or all chunks if they are unknown) that were not stored before the operation.
+    /// Returns [`None`] if the chunks stored before the operation are unknown.
+    #[must_use]
+    pub fn created_chunks(&self) -> Option<Vec<ArrayIndices>> {
+        let existing_chunks = self.existing_chunks.as_ref()?;
+        let existing_chunks: HashSet<&ArrayIndices> = existing_chunks.iter().collect();
+        let is_created = |chunk_indices: &ArrayIndices| !existing_chunks.contains(chunk_indices);
+        Some(match &self.completed_chunks {
+            Some(completed_chunks) => completed_chunks
+                .iter()
+                .filter(|chunk_indices| is_created(chunk_indices))
+                .cloned()
+                .collect(),
+            None => self
+                .chunks
+                .indices()
+                .into_iter()
+                .filter(is_created)
+                .collect(),
+        })
+    }
+
+    fn to_json(&self, key: &StoreKey) -> Result<Vec<u8>, StorageError> {
+        let metadata = WriteJournalMetadata {
+            chunks_start: self.chunks.start().to_vec(),
+            chunks_shape: self.chunks.shape().to_vec(),
+            existing_chunks: self.existing_chunks.clone(),
+            completed_chunks: self.completed_chunks.clone(),
+        };
+        serde_json::to_vec(&metadata)
+            .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))
+    }
+
+    fn from_json(id: String, key: &StoreKey, bytes: &[u8]) -> Result<Self, StorageError> {
+        let metadata: WriteJournalMetadata = serde_json::from_slice(bytes)
+            .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
+        let chunks =
+            ArraySubset::new_with_start_shape(metadata.chunks_start, metadata.chunks_shape)
+                .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
+        Ok(Self {
+            id,
+            chunks,
+            existing_chunks: metadata.existing_chunks,
+            completed_chunks: metadata.completed_chunks,
+        })
+    }
+}
+
+impl<TStorage: ?Sized> Array<TStorage> {
+    /// Return true if a write journal should be stored for storing `chunks` with `options`.
+    fn is_write_journaled(chunks: &ArraySubset, options: &CodecOptions) -> bool {
+        options.write_journal() && chunks.num_elements() > 1
+    }
+
+    /// Return the write journal identifier in a write journal key, or [`None`] if it is not a write journal key.
+    fn write_journal_id_from_key(&self, key: &StoreKey) -> Option<String> {
+        key.as_str()
+            .strip_prefix(write_journal_prefix(self.path()).as_str())?
+            .strip_suffix(".json")
+            .filter(|id| !id.contains('/'))
+            .map(ToString::to_string)
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
+    /// Store a write journal for storing `chunks` if it is enabled in `options` and there are multiple chunks.
+    ///
+    /// Returns the write journal if it was stored.
+    pub(super) fn begin_write_journal(
+        &self,
+        chunks: &ArraySubset,
+        existing_chunks: Option<Vec<ArrayIndices>>,
+        options: &CodecOptions,
+    ) -> Result<Option<WriteJournal>, StorageError> {
+        if !Self::is_write_journaled(chunks, options) {
+            return Ok(None);
+        }
+        let write_journal = WriteJournal {
+            id: crate::random_id(),
+            chunks: chunks.clone(),
+            existing_chunks,
+            completed_chunks: None,
+        };
+        self.store_write_journal(&write_journal)?;
+        Ok(Some(write_journal))
+    }
+
+    /// Erase the write journal if the journaled operation succeeded, otherwise record its completed chunks in the write journal.
+    ///
+    /// Returns `result`.
+    pub(super) fn finish_write_journal(
+        &self,
+        write_journal: Option<WriteJournal>,
+        result: Result<(), ArrayError>,
+        completed_chunks: Vec<ArrayIndices>,
+    ) -> Result<(), ArrayError> {
+        let Some(mut write_journal) = write_journal else {
+            return result;
+        };
+        if result.is_ok() {
+            self.clear_write_journal(&write_journal)?;
+        } else {
+            // The original error takes precedence over an error recording the completed chunks
+            write_journal.completed_chunks = Some(completed_chunks);
+            let _ = self.store_write_journal(&write_journal);
+        }
+        result
+    }
+
+    fn store_write_journal(&self, write_journal: &WriteJournal) -> Result<(), StorageError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_writable_transformer(storage_handle);
+        let key = write_journal_key(self.path(), write_journal.id());
+        storage_transformer.set(&key, &write_journal.to_json(&key)?)
+    }
+
+    /// Erase `write_journal`.
+    ///
+    /// Succeeds if the write journal does not exist.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an underlying store error.
+    pub fn clear_write_journal(&self, write_journal: &WriteJournal) -> Result<(), StorageError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_writable_transformer(storage_handle);
+        storage_transformer.erase(&write_journal_key(self.path(), write_journal.id()))
+    }
+
+    /// Erase the chunks created by the operation of `write_journal`, then erase the write journal.
+    ///
+    /// This rolls back an interrupted operation to the state of the array before it, with the exception of chunks that existed before the operation, which are not erased.
+    /// The erased chunks are those of [`WriteJournal::created_chunks`].
+    /// A chunk created concurrently by another operation that is also a chunk of `write_journal` is erased.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the chunks stored before the operation are unknown (see [`WriteJournal::existing_chunks`]) or there is an underlying store error.
+    pub fn erase_write_journal_chunks(
+        &self,
+        write_journal: &WriteJournal,
+    ) -> Result<(), ArrayError> {
+        let created_chunks = write_journal
+            .created_chunks()
+            .ok_or_else(|| unknown_existing_chunks_error(write_journal))?;
+        created_chunks
+            .into_par_iter()
+            .try_for_each(|chunk_indices| self.erase_chunk(&chunk_indices))?;
+        self.clear_write_journal(write_journal)?;
+        Ok(())
+    }
+}
+
+fn unknown_existing_chunks_error(write_journal: &WriteJournal) -> StorageError {
+    StorageError::Unsupported(format!(
+        "the chunks stored before the operation of write journal {} are unknown",
+        write_journal.id()
+    ))
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
+    /// Return the chunks of `chunks` that are stored if a write journal is enabled in `options` and there are multiple chunks.
+    pub(super) fn write_journal_existing_chunks(
+        &self,
+        chunks: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<ArrayIndices>>, StorageError> {
+        if !Self::is_write_journaled(chunks, options) {
+            return Ok(None);
+        }
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_readable_transformer(storage_handle);
+        let existing_chunks = chunks
+            .indices()
+            .into_par_iter()
+            .map(|chunk_indices| {
+                let key = data_key(self.path(), &chunk_indices, self.chunk_key_encoding());
+                Ok::<_, StorageError>(storage_transformer.size_key(&key)?.map(|_| chunk_indices))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(existing_chunks.into_iter().flatten().collect()))
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits + 'static> Array<TStorage> {
+    /// Return the write journals of operations storing multiple chunks which are in progress or were interrupted.
+    ///
+    /// See [`WriteJournal`].
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if a write journal is invalid or there is an underlying store error.
+    pub fn write_journals(&self) -> Result<Vec<WriteJournal>, ArrayError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_readable_listable_transformer(storage_handle);
+        let mut write_journals = Vec::new();
+        for key in storage_transformer.list_prefix(&write_journal_prefix(self.path()))? {
+            let Some(id) = self.write_journal_id_from_key(&key) else {
+                continue;
+            };
+            // A write journal may be erased after it is listed
+            if let Some(bytes) = storage_transformer.get(&key)? {
+                write_journals.push(WriteJournal::from_json(id, &key, &bytes)?);
+            }
+        }
+        Ok(write_journals)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
+    /// Async variant of [`begin_write_journal`](Array::begin_write_journal).
+    pub(super) async fn async_begin_write_journal(
+        &self,
+        chunks: &ArraySubset,
+        existing_chunks: Option<Vec<ArrayIndices>>,
+        options: &CodecOptions,
+    ) -> Result<Option<WriteJournal>, StorageError> {
+        if !Self::is_write_journaled(chunks, options) {
+            return Ok(None);
+        }
+        let write_journal = WriteJournal {
+            id: crate::random_id(),
+            chunks: chunks.clone(),
+            existing_chunks,
+            completed_chunks: None,
+        };
+        self.async_store_write_journal(&write_journal).await?;
+        Ok(Some(write_journal))
+    }
+
+    /// Async variant of [`finish_write_journal`](Array::finish_write_journal).
+    pub(super) async fn async_finish_write_journal(
+        &self,
+        write_journal: Option<WriteJournal>,
+        result: Result<(), ArrayError>,
+        completed_chunks: Vec<ArrayIndices>,
+    ) -> Result<(), ArrayError> {
+        let Some(mut write_journal) = write_journal else {
+            return result;
+        };
+        if result.is_ok() {
+            self.async_clear_write_journal(&write_journal).await?;
+        } else {
+            // The original error takes precedence over an error recording the completed chunks
+            write_journal.completed_chunks = Some(completed_chunks);
+            let _ = self.async_store_write_journal(&write_journal).await;
+        }
+        result
+    }
+
+    async fn async_store_write_journal(
+        &self,
+        write_journal: &WriteJournal,
+    ) -> Result<(), StorageError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_writable_transformer(storage_handle);
+        let key = write_journal_key(self.path(), write_journal.id());
+        storage_transformer
+            .set(&key, write_journal.to_json(&key)?.into())
+            .await
+    }
+
+    /// Async variant of [`clear_write_journal`](Array::clear_write_journal).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_clear_write_journal(
+        &self,
+        write_journal: &WriteJournal,
+    ) -> Result<(), StorageError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_writable_transformer(storage_handle);
+        storage_transformer
+            .erase(&write_journal_key(self.path(), write_journal.id()))
+            .await
+    }
+
+    /// Async variant of [`erase_write_journal_chunks`](Array::erase_write_journal_chunks).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_erase_write_journal_chunks(
+        &self,
+        write_journal: &WriteJournal,
+    ) -> Result<(), ArrayError> {
+        let created_chunks = write_journal
+            .created_chunks()
+            .ok_or_else(|| unknown_existing_chunks_error(write_journal))?;
+        for chunk_indices in created_chunks {
+            self.async_erase_chunk(&chunk_indices).await?;
+        }
+        self.async_clear_write_journal(write_journal).await?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
+    /// Async variant of [`write_journal_existing_chunks`](Array::write_journal_existing_chunks).
+    pub(super) async fn async_write_journal_existing_chunks(
+        &self,
+        chunks: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<ArrayIndices>>, StorageError> {
+        if !Self::is_write_journaled(chunks, options) {
+            return Ok(None);
+        }
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_readable_transformer(storage_handle);
+        let mut existing_chunks = Vec::new();
+        for chunk_indices in &chunks.indices() {
+            let key = data_key(self.path(), &chunk_indices, self.chunk_key_encoding());
+            if storage_transformer.size_key(&key).await?.is_some() {
+                existing_chunks.push(chunk_indices);
+            }
+        }
+        Ok(Some(existing_chunks))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits + 'static>
+    Array<TStorage>
+{
+    /// Async variant of [`write_journals`](Array::write_journals).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_write_journals(&self) -> Result<Vec<WriteJournal>, ArrayError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_async_readable_listable_transformer(storage_handle);
+        let mut write_journals = Vec::new();
+        for key in storage_transformer
+            .list_prefix(&write_journal_prefix(self.path()))
+            .await?
+        {
+            let Some(id) = self.write_journal_id_from_key(&key) else {
+                continue;
+            };
+            // A write journal may be erased after it is listed
+            if let Some(bytes) = storage_transformer.get(&key).await? {
+                write_journals.push(WriteJournal::from_json(id, &key, &bytes)?);
+            }
+        }
+        Ok(write_journals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{
+            codec::options::{CancellationToken, ProgressReporter},
+            ArrayBuilder, DataType, FillValue,
+        },
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn array_write_journal() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        let options = CodecOptions::builder().write_journal(true).build();
+        let subset = ArraySubset::new_with_ranges(&[0..3, 0..2]);
+
+        // The write journal is erased once all chunks are stored
+        array
+            .store_array_subset_elements_opt::<u8>(&subset, vec![1; 6], &options)
+            .unwrap();
+        assert_eq!(array.write_journals().unwrap(), vec![]);
+        assert_eq!(array.stored_chunk_count().unwrap(), 2);
+
+        // An operation interrupted after storing a chunk leaves the write journal
+        let cancellation_token = CancellationToken::new();
+        let options_interrupted = options
+            .into_builder()
+            .concurrent_target(1)
+            .cancellation_token(cancellation_token.clone())
+            .progress_reporter(ProgressReporter::new(move |_| cancellation_token.cancel()))
+            .build();
+        let subset = ArraySubset::new_with_ranges(&[0..4, 0..4]);
+        assert!(matches!(
+            array.store_array_subset_elements_opt::<u8>(&subset, vec![2; 16], &options_interrupted),
+            Err(ArrayError::Cancelled)
+        ));
+        let write_journals = array.write_journals().unwrap();
+        assert_eq!(write_journals.len(), 1);
+        let write_journal = &write_journals[0];
+        assert_eq!(
+            write_journal.chunks(),
+            &ArraySubset::new_with_shape(vec![2, 2])
+        );
+        assert_eq!(
+            write_journal.existing_chunks(),
+            Some([vec![0, 0], vec![1, 0]].as_slice())
+        );
+        assert_eq!(
+            write_journal.completed_chunks(),
+            Some([vec![0, 0]].as_slice())
+        );
+        assert_eq!(write_journal.created_chunks(), Some(vec![]));
+
+        // A concurrent or interrupted operation on chunks that did not exist
+        let chunks = ArraySubset::new_with_ranges(&[0..2, 1..2]);
+        let existing_chunks = array
+            .write_journal_existing_chunks(&chunks, &options)
+            .unwrap();
+        let write_journal_new = array
+            .begin_write_journal(&chunks, existing_chunks, &options)
+            .unwrap()
+            .unwrap();
+        assert_ne!(write_journal.id(), write_journal_new.id());
+        array
+            .store_chunks_elements::<u8>(&chunks, vec![3; 8])
+            .unwrap();
+        assert_eq!(array.write_journals().unwrap().len(), 2);
+        assert_eq!(
+            write_journal_new.created_chunks(),
+            Some(vec![vec![0, 1], vec![1, 1]])
+        );
+
+        // Roll back the interrupted operations, which only erases the chunks they created
+        array.erase_write_journal_chunks(write_journal).unwrap();
+        array
+            .erase_write_journal_chunks(&write_journal_new)
+            .unwrap();
+        assert_eq!(array.write_journals().unwrap(), vec![]);
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<u8>(&ArraySubset::new_with_shape(vec![4, 4]))
+                .unwrap(),
+            vec![2, 2, 0, 0, 2, 2, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0]
+        );
+
+        // The chunks stored before an operation with write access only are unknown
+        let write_journal_write_only = array
+            .begin_write_journal(&chunks, None, &options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(write_journal_write_only.created_chunks(), None);
+        assert!(array
+            .erase_write_journal_chunks(&write_journal_write_only)
+            .is_err());
+        array
+            .clear_write_journal(&write_journal_write_only)
+            .unwrap();
+        assert_eq!(array.write_journals().unwrap(), vec![]);
+    }
+}
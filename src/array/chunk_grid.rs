@@ -3,18 +3,24 @@
 //! See <https://zarr-specs.readthedocs.io/en/latest/v3/core/v3.0.html#chunk-grids>.
 //!
 //! A [`ChunkGrid`] is a [`Box`] wrapped chunk grid which implements [`ChunkGridTraits`].
-//! Chunk grids are zarr extension points and they can be registered through [`inventory`] as a [`ChunkGridPlugin`].
+//! Chunk grids are zarr extension points and they can be registered through [`inventory`] as a [`ChunkGridPlugin`], or at runtime with the [`ChunkGridRegistry`].
+//! See [`ChunkGridTraits`] for implementing a custom chunk grid.
 //!
 //! Includes a [`RegularChunkGrid`] and [`RectangularChunkGrid`] implementation.
 //!
 //! A regular chunk grid can be created [from a `Vec<usize>` chunk shape](./type.ChunkGrid.html#method.from).
 
+mod chunk_grid_registry;
 mod rectangular;
 mod regular;
 
 use std::num::NonZeroU64;
 
-pub use rectangular::{RectangularChunkGrid, RectangularChunkGridConfiguration};
+pub use chunk_grid_registry::{ChunkGridCreateFn, ChunkGridRegistry};
+pub use rectangular::{
+    RectangularChunkGrid, RectangularChunkGridConfiguration,
+    RectangularChunkGridDimensionConfiguration, RectangularChunkGridPushError,
+};
 pub use regular::{RegularChunkGrid, RegularChunkGridConfiguration};
 
 use derive_more::{Deref, From};
@@ -46,8 +52,11 @@ impl ChunkGrid {
     ///
     /// # Errors
     ///
-    /// Returns a [`PluginCreateError`] if the metadata is invalid or not associated with a registered chunk grid plugin.
+    /// Returns a [`PluginCreateError`] if the metadata is invalid or not associated with a chunk grid registered with the [`ChunkGridRegistry`] or a registered chunk grid plugin.
     pub fn from_metadata(metadata: &Metadata) -> Result<Self, PluginCreateError> {
+        if let Some(chunk_grid) = ChunkGridRegistry::create(metadata) {
+            return chunk_grid;
+        }
         for plugin in inventory::iter::<ChunkGridPlugin> {
             if plugin.match_name(metadata.name()) {
                 return plugin.create(metadata);
@@ -121,6 +130,17 @@ impl TryFrom<ArrayShape> for ChunkGrid {
 }
 
 /// Chunk grid traits.
+///
+/// A chunk grid partitions an array into chunks which are hyperrectangles aligned with the array axes.
+/// An implementation must uphold the following, which [`Array`](crate::array::Array) relies on to map array subsets to chunks:
+///  - the chunk indices of an element are non-decreasing along each dimension as its array indices increase, so the chunks intersecting an array subset are the chunks between the chunk indices of its first and last element, and
+///  - the chunks tile the array without gaps or overlaps.
+///
+/// Custom chunk grids implement the `_unchecked` methods, [`create_metadata`](ChunkGridTraits::create_metadata), and [`dimensionality`](ChunkGridTraits::dimensionality).
+/// The remaining methods validate their inputs and have default implementations.
+/// A chunk grid can be created from array metadata if it is registered as a [`ChunkGridPlugin`] or with the [`ChunkGridRegistry`].
+///
+/// See the `custom_chunk_grid` example for a complete implementation.
 pub trait ChunkGridTraits: dyn_clone::DynClone + core::fmt::Debug + Send + Sync {
     /// Create metadata.
     fn create_metadata(&self) -> Metadata;
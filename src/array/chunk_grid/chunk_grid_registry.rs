@@ -0,0 +1,119 @@
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+use crate::{metadata::Metadata, plugin::PluginCreateError};
+
+use super::ChunkGrid;
+
+/// A function which creates a [`ChunkGrid`] from metadata.
+pub type ChunkGridCreateFn = fn(metadata: &Metadata) -> Result<ChunkGrid, PluginCreateError>;
+
+static CHUNK_GRID_REGISTRY: OnceLock<RwLock<HashMap<String, ChunkGridCreateFn>>> = OnceLock::new();
+
+fn chunk_grid_registry() -> &'static RwLock<HashMap<String, ChunkGridCreateFn>> {
+    CHUNK_GRID_REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// A runtime registry of chunk grids.
+///
+/// Chunk grids are usually registered at compile time as a [`ChunkGridPlugin`](super::ChunkGridPlugin) with the [inventory] crate.
+/// The chunk grid registry is an alternative for applications on platforms where [inventory] is unreliable (e.g. some embedded and WASM targets, or miri).
+///
+/// Chunk grids registered at runtime are resolved by [`ChunkGrid::from_metadata`] (and therefore when opening an array) before any chunk grid plugins.
+/// A registered chunk grid can thus replace the implementation of a chunk grid plugin with the same name.
+///
+/// ```
+/// # use zarrs::array::chunk_grid::{ChunkGrid, ChunkGridRegistry};
+/// # use zarrs::metadata::Metadata;
+/// fn create_chunk_grid(_metadata: &Metadata) -> Result<ChunkGrid, zarrs::plugin::PluginCreateError> {
+///     Ok(vec![2, 2].try_into()?)
+/// }
+/// ChunkGridRegistry::register("example.chunk_grid", create_chunk_grid);
+/// assert!(ChunkGrid::from_metadata(&Metadata::new("example.chunk_grid")).is_ok());
+/// ```
+pub struct ChunkGridRegistry;
+
+impl ChunkGridRegistry {
+    /// Register a chunk grid with `name` created by `create_fn`.
+    ///
+    /// Returns the previously registered create function for `name`, if any.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    pub fn register(
+        name: impl Into<String>,
+        create_fn: ChunkGridCreateFn,
+    ) -> Option<ChunkGridCreateFn> {
+        chunk_grid_registry()
+            .write()
+            .unwrap()
+            .insert(name.into(), create_fn)
+    }
+
+    /// Unregister the chunk grid with `name`.
+    ///
+    /// Returns the previously registered create function for `name`, if any.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    #[allow(clippy::must_use_candidate)]
+    pub fn unregister(name: &str) -> Option<ChunkGridCreateFn> {
+        chunk_grid_registry().write().unwrap().remove(name)
+    }
+
+    /// Returns true if a chunk grid with `name` is registered.
+    ///
+    /// This does not consider chunk grid plugins registered with [inventory].
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    #[must_use]
+    pub fn is_registered(name: &str) -> bool {
+        chunk_grid_registry().read().unwrap().contains_key(name)
+    }
+
+    /// Create a chunk grid from `metadata` if a chunk grid with its name is registered.
+    pub(crate) fn create(metadata: &Metadata) -> Option<Result<ChunkGrid, PluginCreateError>> {
+        let create_fn = chunk_grid_registry()
+            .read()
+            .unwrap()
+            .get(metadata.name())
+            .copied();
+        create_fn.map(|create_fn| create_fn(metadata))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_chunk_grid_test(metadata: &Metadata) -> Result<ChunkGrid, PluginCreateError> {
+        if metadata.configuration().is_some() {
+            return Err(PluginCreateError::from("unexpected configuration"));
+        }
+        vec![3, 4].try_into()
+    }
+
+    #[test]
+    fn chunk_grid_registry() {
+        let name = "zarrs.test_chunk_grid_registry";
+        let metadata = Metadata::new(name);
+        assert!(!ChunkGridRegistry::is_registered(name));
+        assert!(ChunkGrid::from_metadata(&metadata).is_err());
+
+        assert!(ChunkGridRegistry::register(name, create_chunk_grid_test).is_none());
+        assert!(ChunkGridRegistry::is_registered(name));
+        let chunk_grid = ChunkGrid::from_metadata(&metadata).unwrap();
+        assert_eq!(chunk_grid.dimensionality(), 2);
+        assert_eq!(
+            chunk_grid.chunk_shape_u64(&[0, 0], &[6, 8]).unwrap(),
+            Some(vec![3, 4])
+        );
+
+        assert!(ChunkGridRegistry::unregister(name).is_some());
+        assert!(!ChunkGridRegistry::is_registered(name));
+        assert!(ChunkGrid::from_metadata(&metadata).is_err());
+    }
+}
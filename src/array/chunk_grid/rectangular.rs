@@ -13,6 +13,7 @@ use crate::{
 use derive_more::{Display, From};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use super::{ChunkGrid, ChunkGridTraits};
 
@@ -45,10 +46,13 @@ pub struct RectangularChunkGridConfiguration {
     pub chunk_shape: Vec<RectangularChunkGridDimensionConfiguration>,
 }
 
+/// The chunk sizes of a dimension of a `rectangular` chunk grid configuration.
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, From)]
 #[serde(untagged)]
 pub enum RectangularChunkGridDimensionConfiguration {
+    /// A fixed chunk size.
     Fixed(NonZeroU64),
+    /// A varying chunk size, with the size of each chunk.
     Varying(ChunkShape),
 }
 
@@ -155,6 +159,85 @@ impl RectangularChunkGrid {
             .collect();
         Self { chunks }
     }
+
+    /// Create a rectangular chunk grid from `rectangular` chunk grid metadata.
+    ///
+    /// Returns [`None`] if `metadata` is not valid `rectangular` chunk grid metadata.
+    pub(crate) fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        if metadata.name() == IDENTIFIER {
+            let configuration: RectangularChunkGridConfiguration =
+                metadata.to_configuration().ok()?;
+            Some(Self::new(&configuration.chunk_shape))
+        } else {
+            None
+        }
+    }
+
+    /// Append a chunk with size `size` to the end of dimension `dim`.
+    ///
+    /// This grows the extent of a dimension with varying chunk sizes by `size`, so the chunk grid can follow an array that grows along `dim`.
+    /// A dimension with a fixed chunk size already extends indefinitely, so `size` must equal its chunk size.
+    ///
+    /// # Errors
+    /// Returns a [`RectangularChunkGridPushError`] if `dim` is out of bounds or `size` does not match the chunk size of a fixed dimension.
+    pub fn push_chunk_size(
+        &mut self,
+        dim: usize,
+        size: NonZeroU64,
+    ) -> Result<(), RectangularChunkGridPushError> {
+        let dimensionality = self.dimensionality();
+        match self.chunks.get_mut(dim) {
+            Some(RectangularChunkGridDimension::Fixed(chunk_size)) => {
+                if *chunk_size == size {
+                    Ok(())
+                } else {
+                    Err(RectangularChunkGridPushError::IncompatibleFixedChunkSize(
+                        dim,
+                        size,
+                        *chunk_size,
+                    ))
+                }
+            }
+            Some(RectangularChunkGridDimension::Varying(offsets_sizes)) => {
+                let offset = offsets_sizes
+                    .last()
+                    .map_or(0, |last| last.offset + last.size.get());
+                offsets_sizes.push(OffsetSize { offset, size });
+                Ok(())
+            }
+            None => Err(RectangularChunkGridPushError::InvalidDimension(
+                dim,
+                dimensionality,
+            )),
+        }
+    }
+
+    /// Return the extent of dimension `dim` covered by its chunks.
+    ///
+    /// This is the sum of the chunk sizes of a dimension with varying chunk sizes, which must match the array shape along `dim`.
+    /// Returns [`None`] if `dim` is out of bounds or has a fixed chunk size.
+    #[must_use]
+    pub fn dimension_extent(&self, dim: usize) -> Option<u64> {
+        match self.chunks.get(dim)? {
+            RectangularChunkGridDimension::Fixed(_) => None,
+            RectangularChunkGridDimension::Varying(offsets_sizes) => Some(
+                offsets_sizes
+                    .last()
+                    .map_or(0, |last| last.offset + last.size.get()),
+            ),
+        }
+    }
+}
+
+/// A [`RectangularChunkGrid::push_chunk_size`] error.
+#[derive(Debug, Error)]
+pub enum RectangularChunkGridPushError {
+    /// The dimension is out of bounds.
+    #[error("dimension {_0} is out of bounds of a chunk grid with dimensionality {_1}")]
+    InvalidDimension(usize, usize),
+    /// The chunk size does not match the chunk size of a fixed dimension.
+    #[error("chunk size {_1} does not match the fixed chunk size {_2} of dimension {_0}")]
+    IncompatibleFixedChunkSize(usize, NonZeroU64, NonZeroU64),
 }
 
 impl ChunkGridTraits for RectangularChunkGrid {
@@ -461,4 +544,50 @@ mod tests {
         let chunk_indices: ArrayShape = vec![6, 123];
         assert!(chunk_grid.chunk_indices_inbounds(&chunk_indices, &array_shape));
     }
+
+    #[test]
+    fn chunk_grid_rectangular_push_chunk_size() {
+        let chunk_shapes: Vec<RectangularChunkGridDimensionConfiguration> =
+            vec![[5, 15].try_into().unwrap(), 10.try_into().unwrap()];
+        let mut chunk_grid = RectangularChunkGrid::new(&chunk_shapes);
+        assert_eq!(chunk_grid.dimension_extent(0), Some(20));
+        assert_eq!(chunk_grid.dimension_extent(1), None);
+
+        chunk_grid
+            .push_chunk_size(0, NonZeroU64::new(7).unwrap())
+            .unwrap();
+        assert_eq!(chunk_grid.dimension_extent(0), Some(27));
+        let array_shape: ArrayShape = vec![27, 100];
+        assert_eq!(
+            chunk_grid.grid_shape(&array_shape).unwrap(),
+            Some(vec![3, 10])
+        );
+        assert_eq!(
+            chunk_grid.chunk_indices(&[21, 0], &array_shape).unwrap(),
+            Some(vec![2, 0])
+        );
+        assert_eq!(
+            chunk_grid.create_metadata(),
+            serde_json::from_str(
+                r#"{"name":"rectangular","configuration":{"chunk_shape":[[5,15,7],10]}}"#
+            )
+            .unwrap()
+        );
+
+        assert!(chunk_grid
+            .push_chunk_size(1, NonZeroU64::new(10).unwrap())
+            .is_ok());
+        assert!(matches!(
+            chunk_grid.push_chunk_size(1, NonZeroU64::new(5).unwrap()),
+            Err(RectangularChunkGridPushError::IncompatibleFixedChunkSize(
+                1,
+                _,
+                _
+            ))
+        ));
+        assert!(matches!(
+            chunk_grid.push_chunk_size(2, NonZeroU64::new(5).unwrap()),
+            Err(RectangularChunkGridPushError::InvalidDimension(2, 2))
+        ));
+    }
 }
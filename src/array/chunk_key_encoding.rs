@@ -1,14 +1,28 @@
 //! Zarr chunk key encodings. Includes a [default](default::DefaultChunkKeyEncoding) and [v2](v2::V2ChunkKeyEncoding) implementation.
 //!
+//! An [n5](n5::N5ChunkKeyEncoding) implementation for reading N5 datasets is included with the `n5` feature.
+//!
+//! The non-standard [radix](radix::RadixChunkKeyEncoding) chunk key encoding splits the chunk index of each dimension into nested components, to limit the number of entries of a directory (or prefix) in a store.
+//!
+//! Custom chunk key encodings can be implemented with [`ChunkKeyEncodingTraits`] and registered as a [`ChunkKeyEncodingPlugin`].
+//! See the `custom_chunk_key_encoding` example.
+//!
 //! See <https://zarr-specs.readthedocs.io/en/latest/v3/core/v3.0.html#chunk-key-encoding>.
 
 mod default;
+#[cfg(feature = "n5")]
+mod n5;
+mod radix;
 mod v2;
 
 pub use default::{DefaultChunkKeyEncoding, DefaultChunkKeyEncodingConfiguration};
+#[cfg(feature = "n5")]
+pub use n5::N5ChunkKeyEncoding;
+pub use radix::{RadixChunkKeyEncoding, RadixChunkKeyEncodingConfiguration};
 pub use v2::{V2ChunkKeyEncoding, V2ChunkKeyEncodingConfiguration};
 
 use crate::{
+    array::ArrayIndices,
     metadata::Metadata,
     plugin::{Plugin, PluginCreateError},
     storage::StoreKey,
@@ -52,6 +66,13 @@ impl ChunkKeyEncoding {
                 v2::IDENTIFIER => {
                     return v2::create_chunk_key_encoding_v2(metadata);
                 }
+                radix::IDENTIFIER => {
+                    return radix::create_chunk_key_encoding_radix(metadata);
+                }
+                #[cfg(feature = "n5")]
+                n5::IDENTIFIER => {
+                    return n5::create_chunk_key_encoding_n5(metadata);
+                }
                 _ => {}
             }
         }
@@ -78,6 +99,38 @@ pub trait ChunkKeyEncodingTraits: dyn_clone::DynClone + core::fmt::Debug + Send
 
     /// Encode chunk grid indices (grid cell coordinates) into a store key.
     fn encode(&self, chunk_grid_indices: &[u64]) -> StoreKey;
+
+    /// Decode a store key into chunk grid indices with `dimensionality` dimensions.
+    ///
+    /// This is the inverse of [`encode`](ChunkKeyEncodingTraits::encode).
+    /// Returns [`None`] if `key` is not the encoding of any chunk grid indices with `dimensionality` dimensions.
+    ///
+    /// The default implementation returns [`None`] for every key, and should be overridden if [`decodes_keys`](ChunkKeyEncodingTraits::decodes_keys) is true.
+    fn decode(&self, _key: &StoreKey, _dimensionality: usize) -> Option<ArrayIndices> {
+        None
+    }
+
+    /// Returns true if the chunk key encoding implements [`decode`](ChunkKeyEncodingTraits::decode).
+    ///
+    /// If false, listing the stored chunks of an array (e.g. [`Array::list_stored_chunks`](crate::array::Array::list_stored_chunks)) checks the key of every chunk of the chunk grid.
+    fn decodes_keys(&self) -> bool {
+        false
+    }
+}
+
+/// Decode the components of a chunk key, separated by `separator`, into chunk grid indices.
+///
+/// Returns [`None`] if the number of components is not `dimensionality` or any component is not an integer.
+fn decode_components(
+    components: &str,
+    separator: ChunkKeySeparator,
+    dimensionality: usize,
+) -> Option<ArrayIndices> {
+    let indices = components
+        .split(separator.as_char())
+        .map(|component| component.parse::<u64>().ok())
+        .collect::<Option<ArrayIndices>>()?;
+    (indices.len() == dimensionality).then_some(indices)
 }
 
 dyn_clone::clone_trait_object!(ChunkKeyEncodingTraits);
@@ -93,6 +146,16 @@ pub enum ChunkKeySeparator {
     Dot,
 }
 
+impl ChunkKeySeparator {
+    /// Return the separator character.
+    const fn as_char(self) -> char {
+        match self {
+            Self::Slash => '/',
+            Self::Dot => '.',
+        }
+    }
+}
+
 impl TryFrom<char> for ChunkKeySeparator {
     type Error = char;
 
@@ -5,12 +5,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     array::chunk_key_encoding::{ChunkKeyEncodingPlugin, ChunkKeySeparator},
+    array::ArrayIndices,
     metadata::Metadata,
     plugin::{PluginCreateError, PluginMetadataInvalidError},
     storage::StoreKey,
 };
 
-use super::{ChunkKeyEncoding, ChunkKeyEncodingTraits};
+use super::{decode_components, ChunkKeyEncoding, ChunkKeyEncodingTraits};
 
 /// The identifier for the `default` chunk key encoding.
 pub const IDENTIFIER: &str = "default";
@@ -115,6 +116,22 @@ impl ChunkKeyEncodingTraits for DefaultChunkKeyEncoding {
         }
         unsafe { StoreKey::new_unchecked(key) }
     }
+
+    fn decode(&self, key: &StoreKey, dimensionality: usize) -> Option<ArrayIndices> {
+        let components = key.as_str().strip_prefix('c')?;
+        let chunk_grid_indices = if dimensionality == 0 {
+            components.is_empty().then(Vec::new)?
+        } else {
+            let components = components.strip_prefix(self.separator.as_char())?;
+            decode_components(components, self.separator, dimensionality)?
+        };
+        // Reject keys which are not canonical (e.g. with leading zeros)
+        (self.encode(&chunk_grid_indices) == *key).then_some(chunk_grid_indices)
+    }
+
+    fn decodes_keys(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -162,4 +179,23 @@ mod tests {
         );
         assert_eq!(key, StoreKey::new("c").unwrap());
     }
+
+    #[test]
+    fn decode() {
+        for chunk_key_encoding in [
+            DefaultChunkKeyEncoding::new_slash(),
+            DefaultChunkKeyEncoding::new_dot(),
+        ] {
+            let key = chunk_key_encoding.encode(&[1, 23, 45]);
+            assert_eq!(chunk_key_encoding.decode(&key, 3), Some(vec![1, 23, 45]));
+            assert_eq!(chunk_key_encoding.decode(&key, 2), None);
+            let key = chunk_key_encoding.encode(&[]);
+            assert_eq!(chunk_key_encoding.decode(&key, 0), Some(vec![]));
+        }
+        let chunk_key_encoding = DefaultChunkKeyEncoding::new_slash();
+        for key in ["c/01/2", "c/+1/2", "c.1.2", "d/1/2", "c/1", "zarr.json"] {
+            let key = StoreKey::new(key).unwrap();
+            assert_eq!(chunk_key_encoding.decode(&key, 2), None);
+        }
+    }
 }
@@ -0,0 +1,127 @@
+//! The n5 chunk key encoding.
+
+use crate::{
+    array::chunk_key_encoding::ChunkKeyEncodingPlugin,
+    array::ArrayIndices,
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+    storage::StoreKey,
+};
+
+use super::{decode_components, ChunkKeyEncoding, ChunkKeyEncodingTraits, ChunkKeySeparator};
+
+/// The identifier for the `n5` chunk key encoding.
+pub const IDENTIFIER: &str = "n5";
+
+// Register the chunk key encoding.
+inventory::submit! {
+    ChunkKeyEncodingPlugin::new(IDENTIFIER, is_name_n5, create_chunk_key_encoding_n5)
+}
+
+fn is_name_n5(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+}
+
+pub fn create_chunk_key_encoding_n5(
+    metadata: &Metadata,
+) -> Result<ChunkKeyEncoding, PluginCreateError> {
+    if !metadata.configuration_is_none_or_empty() {
+        return Err(PluginMetadataInvalidError::new(
+            IDENTIFIER,
+            "chunk key encoding",
+            metadata.clone(),
+        )
+        .into());
+    }
+    Ok(ChunkKeyEncoding::new(N5ChunkKeyEncoding))
+}
+
+/// An `n5` chunk key encoding.
+///
+/// The identifier for a chunk is formed by joining the ASCII decimal string representation of the chunk indices in reverse order with the `/` character.
+/// The chunk indices are reversed because N5 dimensions are ordered from fastest to slowest varying, whereas Zarr dimensions are ordered from slowest to fastest varying.
+///
+/// See <https://github.com/saalfeldlab/n5#file-system-specification>.
+#[derive(Debug, Clone, Default)]
+pub struct N5ChunkKeyEncoding;
+
+impl N5ChunkKeyEncoding {
+    /// Create a new n5 chunk key encoding.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl ChunkKeyEncodingTraits for N5ChunkKeyEncoding {
+    fn create_metadata(&self) -> Metadata {
+        Metadata::new(IDENTIFIER)
+    }
+
+    fn encode(&self, chunk_grid_indices: &[u64]) -> StoreKey {
+        let key = if chunk_grid_indices.is_empty() {
+            "0".to_string()
+        } else {
+            chunk_grid_indices
+                .iter()
+                .rev()
+                .map(std::string::ToString::to_string)
+                .collect::<Vec<String>>()
+                .join("/")
+        };
+        unsafe { StoreKey::new_unchecked(key) }
+    }
+
+    fn decode(&self, key: &StoreKey, dimensionality: usize) -> Option<ArrayIndices> {
+        let chunk_grid_indices = if dimensionality == 0 {
+            (key.as_str() == "0").then(Vec::new)?
+        } else {
+            let mut chunk_grid_indices =
+                decode_components(key.as_str(), ChunkKeySeparator::Slash, dimensionality)?;
+            chunk_grid_indices.reverse();
+            chunk_grid_indices
+        };
+        // Reject keys which are not canonical (e.g. with leading zeros)
+        (self.encode(&chunk_grid_indices) == *key).then_some(chunk_grid_indices)
+    }
+
+    fn decodes_keys(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{node::NodePath, storage::data_key};
+
+    use super::*;
+
+    #[test]
+    fn n5_nd() {
+        let key = data_key(
+            &NodePath::new("/dataset").unwrap(),
+            &[1, 23, 45],
+            &N5ChunkKeyEncoding::new().into(),
+        );
+        assert_eq!(key, StoreKey::new("dataset/45/23/1").unwrap());
+    }
+
+    #[test]
+    fn n5_metadata() {
+        let chunk_key_encoding =
+            ChunkKeyEncoding::from_metadata(&Metadata::new(IDENTIFIER)).unwrap();
+        assert_eq!(
+            chunk_key_encoding.create_metadata(),
+            Metadata::new(IDENTIFIER)
+        );
+    }
+
+    #[test]
+    fn n5_decode() {
+        let chunk_key_encoding = N5ChunkKeyEncoding::new();
+        let key = chunk_key_encoding.encode(&[1, 23, 45]);
+        assert_eq!(chunk_key_encoding.decode(&key, 3), Some(vec![1, 23, 45]));
+        let key = StoreKey::new("attributes.json").unwrap();
+        assert_eq!(chunk_key_encoding.decode(&key, 1), None);
+    }
+}
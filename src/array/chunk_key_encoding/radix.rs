@@ -0,0 +1,225 @@
+//! The radix chunk key encoding.
+
+use std::num::NonZeroU64;
+
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    array::chunk_key_encoding::{ChunkKeyEncodingPlugin, ChunkKeySeparator},
+    array::ArrayIndices,
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+    storage::StoreKey,
+};
+
+use super::{decode_components, ChunkKeyEncoding, ChunkKeyEncodingTraits};
+
+/// The identifier for the `radix` chunk key encoding.
+pub const IDENTIFIER: &str = "radix";
+
+// Register the chunk key encoding.
+inventory::submit! {
+    ChunkKeyEncodingPlugin::new(IDENTIFIER, is_name_radix, create_chunk_key_encoding_radix)
+}
+
+fn is_name_radix(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+}
+
+pub fn create_chunk_key_encoding_radix(
+    metadata: &Metadata,
+) -> Result<ChunkKeyEncoding, PluginCreateError> {
+    let configuration: RadixChunkKeyEncodingConfiguration =
+        metadata.to_configuration().map_err(|_| {
+            PluginMetadataInvalidError::new(IDENTIFIER, "chunk key encoding", metadata.clone())
+        })?;
+    let radix = RadixChunkKeyEncoding::new(configuration.radix, configuration.separator);
+    Ok(ChunkKeyEncoding::new(radix))
+}
+
+/// A `radix` chunk key encoding configuration.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
+pub struct RadixChunkKeyEncodingConfiguration {
+    /// The radix of the chunk index in each dimension.
+    ///
+    /// A dimension with a `null` radix is not split.
+    pub radix: Vec<Option<NonZeroU64>>,
+    /// The chunk key separator.
+    #[serde(default = "radix_separator")]
+    pub separator: ChunkKeySeparator,
+}
+
+const fn radix_separator() -> ChunkKeySeparator {
+    ChunkKeySeparator::Slash
+}
+
+/// A `radix` chunk key encoding.
+///
+/// The key for a chunk with grid index (k, j, i, …) is formed by taking the initial prefix c, and appending for each dimension:
+/// - the separator character, followed by,
+/// - if the dimension has a radix r, the ASCII decimal string representation of the chunk index divided by r, the separator character, and the chunk index modulo r, or otherwise
+/// - the ASCII decimal string representation of the chunk index within that dimension.
+///
+/// For example, the chunk with grid index (12345, 6) has the key `c/12/345/6` with a radix of 1000 in the first dimension and no radix in the second dimension.
+///
+/// With the `/` separator, the radix bounds the number of entries of a directory (or prefix) in a store, which avoids very large flat prefixes that are slow to list or rate-limited in object stores.
+/// Dimensions beyond the length of the radix configuration are not split.
+#[derive(Debug, Clone)]
+pub struct RadixChunkKeyEncoding {
+    radix: Vec<Option<NonZeroU64>>,
+    separator: ChunkKeySeparator,
+}
+
+impl RadixChunkKeyEncoding {
+    /// Create a new radix chunk key encoding with a radix for each dimension and separator `separator`.
+    #[must_use]
+    pub fn new(radix: Vec<Option<NonZeroU64>>, separator: ChunkKeySeparator) -> Self {
+        Self { radix, separator }
+    }
+
+    /// Create a new radix chunk key encoding with a radix for each dimension and separator `/`.
+    #[must_use]
+    pub fn new_slash(radix: Vec<Option<NonZeroU64>>) -> Self {
+        Self::new(radix, ChunkKeySeparator::Slash)
+    }
+
+    /// Return the radix of each dimension.
+    #[must_use]
+    pub fn radix(&self) -> &[Option<NonZeroU64>] {
+        &self.radix
+    }
+}
+
+impl ChunkKeyEncodingTraits for RadixChunkKeyEncoding {
+    fn create_metadata(&self) -> Metadata {
+        let configuration = RadixChunkKeyEncodingConfiguration {
+            radix: self.radix.clone(),
+            separator: self.separator,
+        };
+        Metadata::new_with_serializable_configuration(IDENTIFIER, &configuration).unwrap()
+    }
+
+    fn encode(&self, chunk_grid_indices: &[u64]) -> StoreKey {
+        let separator = self.separator.to_string();
+        let mut key = "c".to_string();
+        for (dim, index) in chunk_grid_indices.iter().enumerate() {
+            key.push_str(&separator);
+            match self.radix.get(dim).copied().flatten() {
+                Some(radix) => {
+                    key.push_str(&(index / radix.get()).to_string());
+                    key.push_str(&separator);
+                    key.push_str(&(index % radix.get()).to_string());
+                }
+                None => key.push_str(&index.to_string()),
+            }
+        }
+        unsafe { StoreKey::new_unchecked(key) }
+    }
+
+    fn decode(&self, key: &StoreKey, dimensionality: usize) -> Option<ArrayIndices> {
+        let components = key.as_str().strip_prefix('c')?;
+        let chunk_grid_indices = if dimensionality == 0 {
+            components.is_empty().then(Vec::new)?
+        } else {
+            let radixes = (0..dimensionality)
+                .map(|dim| self.radix.get(dim).copied().flatten())
+                .collect::<Vec<_>>();
+            let num_components = dimensionality + radixes.iter().flatten().count();
+            let components = components.strip_prefix(self.separator.as_char())?;
+            let mut components =
+                decode_components(components, self.separator, num_components)?.into_iter();
+            radixes
+                .iter()
+                .map(|radix| {
+                    let index = components.next()?;
+                    match radix {
+                        Some(radix) => index
+                            .checked_mul(radix.get())?
+                            .checked_add(components.next()?),
+                        None => Some(index),
+                    }
+                })
+                .collect::<Option<ArrayIndices>>()?
+        };
+        // Reject keys which are not canonical (e.g. with leading zeros or a remainder exceeding the radix)
+        (self.encode(&chunk_grid_indices) == *key).then_some(chunk_grid_indices)
+    }
+
+    fn decodes_keys(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{node::NodePath, storage::data_key};
+
+    use super::*;
+
+    #[test]
+    fn radix_nd() {
+        let chunk_key_encoding: ChunkKeyEncoding = RadixChunkKeyEncoding::new_slash(vec![
+            NonZeroU64::new(1000),
+            None,
+            NonZeroU64::new(10),
+        ])
+        .into();
+        let key = data_key(&NodePath::root(), &[12345, 6, 7], &chunk_key_encoding);
+        assert_eq!(key, StoreKey::new("c/12/345/6/0/7").unwrap());
+        let key = data_key(&NodePath::root(), &[1, 2, 3, 4], &chunk_key_encoding);
+        assert_eq!(key, StoreKey::new("c/0/1/2/0/3/4").unwrap());
+        let key = data_key(&NodePath::root(), &[], &chunk_key_encoding);
+        assert_eq!(key, StoreKey::new("c").unwrap());
+    }
+
+    #[test]
+    fn radix_dot() {
+        let key = data_key(
+            &NodePath::root(),
+            &[123, 45],
+            &RadixChunkKeyEncoding::new(
+                vec![NonZeroU64::new(100), NonZeroU64::new(100)],
+                ChunkKeySeparator::Dot,
+            )
+            .into(),
+        );
+        assert_eq!(key, StoreKey::new("c.1.23.0.45").unwrap());
+    }
+
+    #[test]
+    fn radix_metadata() {
+        let metadata: Metadata = serde_json::from_str(
+            r#"{"name":"radix","configuration":{"radix":[1000,null],"separator":"/"}}"#,
+        )
+        .unwrap();
+        let chunk_key_encoding = ChunkKeyEncoding::from_metadata(&metadata).unwrap();
+        assert_eq!(chunk_key_encoding.create_metadata(), metadata);
+        assert_eq!(
+            chunk_key_encoding.encode(&[2024, 3]),
+            StoreKey::new("c/2/24/3").unwrap()
+        );
+
+        let metadata: Metadata =
+            serde_json::from_str(r#"{"name":"radix","configuration":{"radix":[0]}}"#).unwrap();
+        assert!(ChunkKeyEncoding::from_metadata(&metadata).is_err());
+    }
+
+    #[test]
+    fn radix_decode() {
+        let chunk_key_encoding = RadixChunkKeyEncoding::new_slash(vec![
+            NonZeroU64::new(1000),
+            None,
+            NonZeroU64::new(10),
+        ]);
+        let key = chunk_key_encoding.encode(&[12345, 6, 7]);
+        assert_eq!(chunk_key_encoding.decode(&key, 3), Some(vec![12345, 6, 7]));
+        let key = chunk_key_encoding.encode(&[1, 2, 3, 4]);
+        assert_eq!(chunk_key_encoding.decode(&key, 4), Some(vec![1, 2, 3, 4]));
+        assert_eq!(chunk_key_encoding.decode(&key, 3), None);
+        let key = StoreKey::new("c/12/1345/6/0/7").unwrap();
+        assert_eq!(chunk_key_encoding.decode(&key, 3), None);
+    }
+}
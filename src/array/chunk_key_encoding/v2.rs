@@ -5,12 +5,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     array::chunk_key_encoding::ChunkKeyEncodingPlugin,
+    array::ArrayIndices,
     metadata::Metadata,
     plugin::{PluginCreateError, PluginMetadataInvalidError},
     storage::StoreKey,
 };
 
-use super::{ChunkKeyEncoding, ChunkKeyEncodingTraits, ChunkKeySeparator};
+use super::{decode_components, ChunkKeyEncoding, ChunkKeyEncodingTraits, ChunkKeySeparator};
 
 /// The identifier for the `v2` chunk key encoding.
 pub const IDENTIFIER: &str = "v2";
@@ -114,6 +115,20 @@ impl ChunkKeyEncodingTraits for V2ChunkKeyEncoding {
         };
         unsafe { StoreKey::new_unchecked(key) }
     }
+
+    fn decode(&self, key: &StoreKey, dimensionality: usize) -> Option<ArrayIndices> {
+        let chunk_grid_indices = if dimensionality == 0 {
+            (key.as_str() == "0").then(Vec::new)?
+        } else {
+            decode_components(key.as_str(), self.separator, dimensionality)?
+        };
+        // Reject keys which are not canonical (e.g. with leading zeros)
+        (self.encode(&chunk_grid_indices) == *key).then_some(chunk_grid_indices)
+    }
+
+    fn decodes_keys(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -161,4 +176,21 @@ mod tests {
         );
         assert_eq!(key, StoreKey::new("0").unwrap());
     }
+
+    #[test]
+    fn decode() {
+        for chunk_key_encoding in [
+            V2ChunkKeyEncoding::new_slash(),
+            V2ChunkKeyEncoding::new_dot(),
+        ] {
+            let key = chunk_key_encoding.encode(&[1, 23, 45]);
+            assert_eq!(chunk_key_encoding.decode(&key, 3), Some(vec![1, 23, 45]));
+            assert_eq!(chunk_key_encoding.decode(&key, 2), None);
+            let key = chunk_key_encoding.encode(&[]);
+            assert_eq!(chunk_key_encoding.decode(&key, 0), Some(vec![]));
+            assert_eq!(chunk_key_encoding.decode(&key, 1), Some(vec![0]));
+        }
+        let key = StoreKey::new(".zarray").unwrap();
+        assert_eq!(V2ChunkKeyEncoding::new_dot().decode(&key, 1), None);
+    }
 }
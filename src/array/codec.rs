@@ -15,19 +15,36 @@ pub mod array_to_bytes;
 pub mod bytes_to_bytes;
 pub mod options;
 
-pub use options::{CodecOptions, CodecOptionsBuilder};
+pub use options::{
+    CancellationToken, CodecOptions, CodecOptionsBuilder, Progress, ProgressReporter,
+    ShardIndexCache,
+};
 
 // Array to array
 #[cfg(feature = "bitround")]
 pub use array_to_array::bitround::{
     BitroundCodec, BitroundCodecConfiguration, BitroundCodecConfigurationV1,
 };
+#[cfg(feature = "fixedscaleoffset")]
+pub use array_to_array::fixedscaleoffset::{
+    FixedScaleOffsetCodec, FixedScaleOffsetCodecConfiguration, FixedScaleOffsetCodecConfigurationV1,
+};
 #[cfg(feature = "transpose")]
 pub use array_to_array::transpose::{
     TransposeCodec, TransposeCodecConfiguration, TransposeCodecConfigurationV1,
 };
 
 // Array to bytes
+#[cfg(feature = "blosc2")]
+pub use array_to_bytes::blosc2_nd::{
+    Blosc2NdCodec, Blosc2NdCodecConfiguration, Blosc2NdCodecConfigurationV1,
+};
+#[cfg(feature = "n5")]
+pub use array_to_bytes::n5::{N5Codec, N5CodecConfiguration, N5CodecConfigurationV1};
+#[cfg(feature = "packbits")]
+pub use array_to_bytes::packbits::{
+    PackBitsCodec, PackBitsCodecConfiguration, PackBitsCodecConfigurationV1,
+};
 #[cfg(feature = "sharding")]
 pub use array_to_bytes::sharding::{
     ShardingCodec, ShardingCodecConfiguration, ShardingCodecConfigurationV1,
@@ -42,15 +59,32 @@ pub use array_to_bytes::{
 // Bytes to bytes
 #[cfg(feature = "blosc")]
 pub use bytes_to_bytes::blosc::{BloscCodec, BloscCodecConfiguration, BloscCodecConfigurationV1};
+#[cfg(feature = "blosc2")]
+pub use bytes_to_bytes::blosc2::{
+    Blosc2Codec, Blosc2CodecConfiguration, Blosc2CodecConfigurationV1,
+};
+#[cfg(feature = "bz2")]
+pub use bytes_to_bytes::bz2::{
+    Bz2Codec, Bz2CodecConfiguration, Bz2CodecConfigurationV1, Bz2CompressionLevel,
+};
 #[cfg(feature = "crc32c")]
 pub use bytes_to_bytes::crc32c::{
     Crc32cCodec, Crc32cCodecConfiguration, Crc32cCodecConfigurationV1,
 };
 #[cfg(feature = "gzip")]
 pub use bytes_to_bytes::gzip::{GzipCodec, GzipCodecConfiguration, GzipCodecConfigurationV1};
+#[cfg(feature = "shuffle")]
+pub use bytes_to_bytes::shuffle::{
+    ShuffleCodec, ShuffleCodecConfiguration, ShuffleCodecConfigurationV1,
+};
+#[cfg(feature = "snappy")]
+pub use bytes_to_bytes::snappy::{
+    SnappyCodec, SnappyCodecConfiguration, SnappyCodecConfigurationV1,
+};
 #[cfg(feature = "zstd")]
 pub use bytes_to_bytes::zstd::{ZstdCodec, ZstdCodecConfiguration, ZstdCodecConfigurationV1};
 
+use bytes::Bytes;
 use itertools::Itertools;
 use thiserror::Error;
 
@@ -60,7 +94,13 @@ pub use array_partial_decoder_cache::ArrayPartialDecoderCache;
 pub use bytes_partial_decoder_cache::BytesPartialDecoderCache;
 
 mod byte_interval_partial_decoder;
+mod codec_registry;
+mod prefetching_partial_decoder;
 pub use byte_interval_partial_decoder::ByteIntervalPartialDecoder;
+pub use codec_registry::{CodecCreateFn, CodecRegistry};
+#[cfg(feature = "async")]
+pub use prefetching_partial_decoder::{AsyncPrefetchingPartialDecoder, AsyncSpawn};
+pub use prefetching_partial_decoder::{PrefetchPattern, PrefetchingPartialDecoder};
 
 #[cfg(feature = "async")]
 pub use byte_interval_partial_decoder::AsyncByteIntervalPartialDecoder;
@@ -105,8 +145,11 @@ impl Codec {
     /// Create a codec from metadata.
     ///
     /// # Errors
-    /// Returns [`PluginCreateError`] if the metadata is invalid or not associated with a registered codec plugin.
+    /// Returns [`PluginCreateError`] if the metadata is invalid or not associated with a codec registered with the [`CodecRegistry`] or a registered codec plugin.
     pub fn from_metadata(metadata: &Metadata) -> Result<Self, PluginCreateError> {
+        if let Some(codec) = CodecRegistry::create(metadata) {
+            return codec;
+        }
         for plugin in inventory::iter::<CodecPlugin> {
             if plugin.match_name(metadata.name()) {
                 return plugin.create(metadata);
@@ -124,9 +167,27 @@ impl Codec {
                 array_to_array::bitround::IDENTIFIER => {
                     return array_to_array::bitround::create_codec_bitround(metadata);
                 }
+                #[cfg(feature = "fixedscaleoffset")]
+                array_to_array::fixedscaleoffset::IDENTIFIER => {
+                    return array_to_array::fixedscaleoffset::create_codec_fixedscaleoffset(
+                        metadata,
+                    );
+                }
                 array_to_bytes::bytes::IDENTIFIER => {
                     return array_to_bytes::bytes::create_codec_bytes(metadata);
                 }
+                #[cfg(feature = "blosc2")]
+                array_to_bytes::blosc2_nd::IDENTIFIER => {
+                    return array_to_bytes::blosc2_nd::create_codec_blosc2_nd(metadata);
+                }
+                #[cfg(feature = "n5")]
+                array_to_bytes::n5::IDENTIFIER => {
+                    return array_to_bytes::n5::create_codec_n5(metadata);
+                }
+                #[cfg(feature = "packbits")]
+                array_to_bytes::packbits::IDENTIFIER => {
+                    return array_to_bytes::packbits::create_codec_packbits(metadata);
+                }
                 #[cfg(feature = "pcodec")]
                 array_to_bytes::pcodec::IDENTIFIER => {
                     return array_to_bytes::pcodec::create_codec_pcodec(metadata);
@@ -143,6 +204,10 @@ impl Codec {
                 bytes_to_bytes::blosc::IDENTIFIER => {
                     return bytes_to_bytes::blosc::create_codec_blosc(metadata);
                 }
+                #[cfg(feature = "blosc2")]
+                bytes_to_bytes::blosc2::IDENTIFIER => {
+                    return bytes_to_bytes::blosc2::create_codec_blosc2(metadata);
+                }
                 #[cfg(feature = "bz2")]
                 bytes_to_bytes::bz2::IDENTIFIER => {
                     return bytes_to_bytes::bz2::create_codec_bz2(metadata);
@@ -155,6 +220,14 @@ impl Codec {
                 bytes_to_bytes::gzip::IDENTIFIER => {
                     return bytes_to_bytes::gzip::create_codec_gzip(metadata);
                 }
+                #[cfg(feature = "shuffle")]
+                bytes_to_bytes::shuffle::IDENTIFIER => {
+                    return bytes_to_bytes::shuffle::create_codec_shuffle(metadata);
+                }
+                #[cfg(feature = "snappy")]
+                bytes_to_bytes::snappy::IDENTIFIER => {
+                    return bytes_to_bytes::snappy::create_codec_snappy(metadata);
+                }
                 #[cfg(feature = "zstd")]
                 bytes_to_bytes::zstd::IDENTIFIER => {
                     return bytes_to_bytes::zstd::create_codec_zstd(metadata);
@@ -209,11 +282,13 @@ pub trait ArrayCodecTraits: CodecTraits {
 
     /// Decode a chunk.
     ///
+    /// `encoded_value` is a reference counted [`Bytes`] buffer, so an encoded chunk can be passed from a store without copying.
+    ///
     /// # Errors
     /// Returns [`CodecError`] if a codec fails or the decoded output is incompatible with `decoded_representation`.
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         decoded_representation: &ChunkRepresentation,
         options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError>;
@@ -232,29 +307,59 @@ pub trait ArrayCodecTraits: CodecTraits {
         array_view: &ArrayView,
         options: &CodecOptions,
     ) -> Result<(), CodecError> {
-        let decoded_bytes = self.decode(encoded_value.to_vec(), decoded_representation, options)?;
-        let contiguous_indices = unsafe {
-            array_view
-                .subset()
-                .contiguous_linearised_indices_unchecked(array_view.array_shape())
-        };
-        let element_size = decoded_representation.element_size();
-        let length = contiguous_indices.contiguous_elements_usize() * element_size;
-        let mut decoded_offset = 0;
-        // FIXME: Par iteration?
-        let output = unsafe { array_view.bytes_mut() };
-        for (array_subset_element_index, _num_elements) in &contiguous_indices {
-            let output_offset = usize::try_from(array_subset_element_index).unwrap() * element_size;
-            debug_assert!((output_offset + length) <= output.len());
-            debug_assert!((decoded_offset + length) <= decoded_bytes.len());
-            output[output_offset..output_offset + length]
-                .copy_from_slice(&decoded_bytes[decoded_offset..decoded_offset + length]);
-            decoded_offset += length;
-        }
-        Ok(())
+        let decoded_bytes = self.decode(
+            Bytes::copy_from_slice(encoded_value),
+            decoded_representation,
+            options,
+        )?;
+        copy_into_array_view(&decoded_bytes, decoded_representation, array_view)
+    }
+
+    /// Returns true if the codec is a passthrough for `decoded_representation`.
+    ///
+    /// A passthrough codec encodes and decodes without changing any bytes, so its decode can reuse the input buffer without copying.
+    /// The default implementation returns false.
+    fn is_passthrough(&self, _decoded_representation: &ChunkRepresentation) -> bool {
+        false
     }
 }
 
+/// Copy the bytes of a decoded chunk into the subset of an array.
+///
+/// # Errors
+/// Returns [`CodecError::UnexpectedChunkDecodedSize`] if the length of `decoded_bytes` does not match `decoded_representation`.
+fn copy_into_array_view(
+    decoded_bytes: &[u8],
+    decoded_representation: &ChunkRepresentation,
+    array_view: &ArrayView,
+) -> Result<(), CodecError> {
+    if decoded_bytes.len() as u64 != decoded_representation.size() {
+        return Err(CodecError::UnexpectedChunkDecodedSize(
+            decoded_bytes.len(),
+            decoded_representation.size(),
+        ));
+    }
+    let contiguous_indices = unsafe {
+        array_view
+            .subset()
+            .contiguous_linearised_indices_unchecked(array_view.array_shape())
+    };
+    let element_size = decoded_representation.element_size();
+    let length = contiguous_indices.contiguous_elements_usize() * element_size;
+    let mut decoded_offset = 0;
+    // FIXME: Par iteration?
+    let output = unsafe { array_view.bytes_mut() };
+    for (array_subset_element_index, _num_elements) in &contiguous_indices {
+        let output_offset = usize::try_from(array_subset_element_index).unwrap() * element_size;
+        debug_assert!((output_offset + length) <= output.len());
+        debug_assert!((decoded_offset + length) <= decoded_bytes.len());
+        output[output_offset..output_offset + length]
+            .copy_from_slice(&decoded_bytes[decoded_offset..decoded_offset + length]);
+        decoded_offset += length;
+    }
+    Ok(())
+}
+
 /// Partial bytes decoder traits.
 pub trait BytesPartialDecoderTraits: Send + Sync {
     /// Partially decode bytes.
@@ -267,7 +372,7 @@ pub trait BytesPartialDecoderTraits: Send + Sync {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError>;
+    ) -> Result<Option<Vec<Bytes>>, CodecError>;
 
     /// Decode all bytes.
     ///
@@ -280,6 +385,13 @@ pub trait BytesPartialDecoderTraits: Send + Sync {
             .partial_decode(&[ByteRange::FromStart(0, None)], options)?
             .map(|mut v| v.remove(0)))
     }
+
+    /// Return the store key of the value if the bytes are read directly from a store without any decoding.
+    ///
+    /// The default implementation returns [`None`].
+    fn store_key(&self) -> Option<&StoreKey> {
+        None
+    }
 }
 
 #[cfg(feature = "async")]
@@ -296,7 +408,7 @@ pub trait AsyncBytesPartialDecoderTraits: Send + Sync {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError>;
+    ) -> Result<Option<Vec<Bytes>>, CodecError>;
 
     /// Decode all bytes.
     ///
@@ -310,6 +422,13 @@ pub trait AsyncBytesPartialDecoderTraits: Send + Sync {
             .await?
             .map(|mut v| v.remove(0)))
     }
+
+    /// Return the store key of the value if the bytes are read directly from a store without any decoding.
+    ///
+    /// The default implementation returns [`None`].
+    fn store_key(&self) -> Option<&StoreKey> {
+        None
+    }
 }
 
 /// Partial array decoder traits.
@@ -495,11 +614,15 @@ impl BytesPartialDecoderTraits for StoragePartialDecoder {
         &self,
         decoded_regions: &[ByteRange],
         _options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
         Ok(self
             .storage
             .get_partial_values_key(&self.key, decoded_regions)?)
     }
+
+    fn store_key(&self) -> Option<&StoreKey> {
+        Some(&self.key)
+    }
 }
 
 #[cfg(feature = "async")]
@@ -524,12 +647,16 @@ impl AsyncBytesPartialDecoderTraits for AsyncStoragePartialDecoder {
         &self,
         decoded_regions: &[ByteRange],
         _options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
         Ok(self
             .storage
             .get_partial_values_key(&self.key, decoded_regions)
             .await?)
     }
+
+    fn store_key(&self) -> Option<&StoreKey> {
+        Some(&self.key)
+    }
 }
 
 /// Traits for array to array codecs.
@@ -615,10 +742,67 @@ pub trait ArrayToBytesCodecTraits:
         decoded_representation: &ChunkRepresentation,
         options: &CodecOptions,
     ) -> Result<Box<dyn AsyncArrayPartialDecoderTraits + 'a>, CodecError>;
+
+    /// Return the byte ranges of the encoded input that a partial decoder would retrieve to decode `array_subsets`.
+    ///
+    /// Returns [`None`] if the partial decoder would retrieve the entire input, which is the default.
+    /// A codec may retrieve bytes from `input_handle` if they are needed to plan the read (e.g. the `sharding` codec retrieves the shard index).
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if the byte ranges cannot be determined.
+    fn partial_decode_byte_ranges(
+        &self,
+        _input_handle: &dyn BytesPartialDecoderTraits,
+        _decoded_representation: &ChunkRepresentation,
+        _array_subsets: &[ArraySubset],
+        _options: &CodecOptions,
+    ) -> Result<Option<Vec<ByteRange>>, CodecError> {
+        Ok(None)
+    }
+
+    #[cfg(feature = "async")]
+    /// Asynchronous variant of [`partial_decode_byte_ranges`](ArrayToBytesCodecTraits::partial_decode_byte_ranges).
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if the byte ranges cannot be determined.
+    async fn async_partial_decode_byte_ranges(
+        &self,
+        _input_handle: &dyn AsyncBytesPartialDecoderTraits,
+        _decoded_representation: &ChunkRepresentation,
+        _array_subsets: &[ArraySubset],
+        _options: &CodecOptions,
+    ) -> Result<Option<Vec<ByteRange>>, CodecError> {
+        Ok(None)
+    }
+
+    /// Encode a chunk as a sequence of byte segments which concatenate to the encoded chunk.
+    ///
+    /// Segments are encoded as the iterator is consumed, so a codec which produces its output incrementally (e.g. the `sharding` codec) can override this to avoid holding the entire encoded chunk in memory.
+    /// This allows an encoded chunk to be streamed into a store with `AsyncWritableStorageTraits::set_stream`.
+    /// The default implementation yields the output of [`encode`](ArrayCodecTraits::encode) as a single segment.
+    ///
+    /// # Errors
+    /// Returns [`CodecError`] if the decoded value is incompatible with the decoded representation.
+    /// Each segment is an error if a codec fails.
+    fn encode_segments<'a>(
+        &'a self,
+        decoded_value: Vec<u8>,
+        decoded_representation: &ChunkRepresentation,
+        options: &CodecOptions,
+    ) -> Result<EncodedSegments<'a>, CodecError> {
+        Ok(Box::new(std::iter::once(self.encode(
+            decoded_value,
+            decoded_representation,
+            options,
+        ))))
+    }
 }
 
 dyn_clone::clone_trait_object!(ArrayToBytesCodecTraits);
 
+/// The segments of an encoded chunk, returned by [`ArrayToBytesCodecTraits::encode_segments`].
+pub type EncodedSegments<'a> = Box<dyn Iterator<Item = Result<Vec<u8>, CodecError>> + Send + 'a>;
+
 /// Traits for bytes to bytes codecs.
 #[cfg_attr(feature = "async", async_trait::async_trait)]
 pub trait BytesToBytesCodecTraits: CodecTraits + dyn_clone::DynClone + core::fmt::Debug {
@@ -645,12 +829,14 @@ pub trait BytesToBytesCodecTraits: CodecTraits + dyn_clone::DynClone + core::fmt
         -> Result<Vec<u8>, CodecError>;
 
     /// Decode chunk bytes.
-    //
+    ///
+    /// `encoded_value` is a reference counted [`Bytes`] buffer, so an encoded chunk can be passed from a store without copying.
+    ///
     /// # Errors
     /// Returns [`CodecError`] if a codec fails.
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         decoded_representation: &BytesRepresentation,
         options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError>;
@@ -686,11 +872,13 @@ impl BytesPartialDecoderTraits for std::io::Cursor<&[u8]> {
         &self,
         decoded_regions: &[ByteRange],
         _parallel: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
-        Ok(Some(extract_byte_ranges_read_seek(
-            &mut self.clone(),
-            decoded_regions,
-        )?))
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        Ok(Some(
+            extract_byte_ranges_read_seek(&mut self.clone(), decoded_regions)?
+                .into_iter()
+                .map(Bytes::from)
+                .collect(),
+        ))
     }
 }
 
@@ -699,11 +887,13 @@ impl BytesPartialDecoderTraits for std::io::Cursor<Vec<u8>> {
         &self,
         decoded_regions: &[ByteRange],
         _parallel: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
-        Ok(Some(extract_byte_ranges_read_seek(
-            &mut self.clone(),
-            decoded_regions,
-        )?))
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        Ok(Some(
+            extract_byte_ranges_read_seek(&mut self.clone(), decoded_regions)?
+                .into_iter()
+                .map(Bytes::from)
+                .collect(),
+        ))
     }
 }
 
@@ -714,11 +904,13 @@ impl AsyncBytesPartialDecoderTraits for std::io::Cursor<&[u8]> {
         &self,
         decoded_regions: &[ByteRange],
         _parallel: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
-        Ok(Some(extract_byte_ranges_read_seek(
-            &mut self.clone(),
-            decoded_regions,
-        )?))
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        Ok(Some(
+            extract_byte_ranges_read_seek(&mut self.clone(), decoded_regions)?
+                .into_iter()
+                .map(Bytes::from)
+                .collect(),
+        ))
     }
 }
 
@@ -729,11 +921,13 @@ impl AsyncBytesPartialDecoderTraits for std::io::Cursor<Vec<u8>> {
         &self,
         decoded_regions: &[ByteRange],
         _parallel: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
-        Ok(Some(extract_byte_ranges_read_seek(
-            &mut self.clone(),
-            decoded_regions,
-        )?))
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        Ok(Some(
+            extract_byte_ranges_read_seek(&mut self.clone(), decoded_regions)?
+                .into_iter()
+                .map(Bytes::from)
+                .collect(),
+        ))
     }
 }
 
@@ -801,7 +995,7 @@ pub fn extract_byte_ranges_read_seek<T: Read + Seek>(
         let data: Vec<u8> = match byte_range {
             ByteRange::FromStart(offset, None) => {
                 bytes.seek(SeekFrom::Start(*offset))?;
-                let length = usize::try_from(len).unwrap();
+                let length = usize::try_from(len - offset).unwrap();
                 let mut data = vec![0; length];
                 bytes.read_exact(&mut data)?;
                 data
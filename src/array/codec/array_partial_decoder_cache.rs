@@ -78,6 +78,11 @@ impl<'a> ArrayPartialDecoderTraits for ArrayPartialDecoderCache<'a> {
         decoded_regions: &[ArraySubset],
         _options: &CodecOptions,
     ) -> Result<Vec<Vec<u8>>, CodecError> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            regions = decoded_regions.len(),
+            "array partial decoder cache hit"
+        );
         let mut out: Vec<Vec<u8>> = Vec::with_capacity(decoded_regions.len());
         let array_shape = self.decoded_representation.shape_u64();
         let element_size = self.decoded_representation.element_size();
@@ -2,5 +2,7 @@
 
 #[cfg(feature = "bitround")]
 pub mod bitround;
+#[cfg(feature = "fixedscaleoffset")]
+pub mod fixedscaleoffset;
 #[cfg(feature = "transpose")]
 pub mod transpose;
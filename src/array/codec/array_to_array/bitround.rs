@@ -215,7 +215,7 @@ mod tests {
             .unwrap();
         let decoded = codec
             .decode(
-                encoded.clone(),
+                encoded.clone().into(),
                 &chunk_representation,
                 &CodecOptions::default(),
             )
@@ -248,7 +248,7 @@ mod tests {
             .unwrap();
         let decoded = codec
             .decode(
-                encoded.clone(),
+                encoded.clone().into(),
                 &chunk_representation,
                 &CodecOptions::default(),
             )
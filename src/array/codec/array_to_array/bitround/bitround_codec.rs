@@ -1,3 +1,5 @@
+use bytes::Bytes;
+
 use crate::{
     array::{
         codec::{
@@ -83,11 +85,11 @@ impl ArrayCodecTraits for BitroundCodec {
 
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         _decoded_representation: &ChunkRepresentation,
         _options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
-        Ok(encoded_value)
+        Ok(Vec::from(encoded_value))
     }
 }
 
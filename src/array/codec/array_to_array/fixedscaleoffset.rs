@@ -0,0 +1,339 @@
+//! The `fixedscaleoffset` array to array codec.
+//!
+//! Quantises values with a fixed scale and offset and stores them in a (typically narrower) data type.
+//! This is the `numcodecs` [`FixedScaleOffset`](https://numcodecs.readthedocs.io/en/stable/fixedscaleoffset.html) filter.
+//!
+//! Values are encoded as `round((x - offset) * scale)` in the `astype` data type, and decoded as `x / scale + offset` in the `dtype` data type.
+//! Rounding is half to even and conversions to an integer data type saturate.
+//! Integer and floating point data types are supported.
+//!
+//! This codec requires the `fixedscaleoffset` feature, which is disabled by default.
+//!
+//! See [`FixedScaleOffsetCodecConfigurationV1`] for example `JSON` metadata.
+//!
+
+mod fixedscaleoffset_codec;
+mod fixedscaleoffset_configuration;
+mod fixedscaleoffset_partial_decoder;
+
+pub use fixedscaleoffset_codec::FixedScaleOffsetCodec;
+pub use fixedscaleoffset_configuration::{
+    FixedScaleOffsetCodecConfiguration, FixedScaleOffsetCodecConfigurationV1,
+};
+
+use crate::{
+    array::{
+        codec::{Codec, CodecError, CodecPlugin},
+        DataType,
+    },
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+/// The identifier for the `fixedscaleoffset` codec.
+pub const IDENTIFIER: &str = "fixedscaleoffset";
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_fixedscaleoffset, create_codec_fixedscaleoffset)
+}
+
+fn is_name_fixedscaleoffset(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+}
+
+pub(crate) fn create_codec_fixedscaleoffset(
+    metadata: &Metadata,
+) -> Result<Codec, PluginCreateError> {
+    let configuration: FixedScaleOffsetCodecConfiguration = metadata
+        .to_configuration()
+        .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?;
+    let codec = Box::new(FixedScaleOffsetCodec::new_with_configuration(
+        &configuration,
+    )?);
+    Ok(Codec::ArrayToArray(codec))
+}
+
+/// Returns true if `data_type` is supported by the `fixedscaleoffset` codec.
+const fn is_supported_data_type(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float16
+            | DataType::BFloat16
+            | DataType::Float32
+            | DataType::Float64
+    )
+}
+
+/// Returns the `NumPy` type string of a supported data type in native endianness.
+fn data_type_to_numpy(data_type: &DataType) -> Option<String> {
+    let kind = match data_type {
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => 'i',
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => 'u',
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => 'f',
+        _ => return None,
+    };
+    let endianness = if data_type.size() == 1 {
+        '|'
+    } else if cfg!(target_endian = "big") {
+        '>'
+    } else {
+        '<'
+    };
+    Some(format!("{endianness}{kind}{}", data_type.size()))
+}
+
+/// Round half to even, matching [`numpy.around`](https://numpy.org/doc/stable/reference/generated/numpy.around.html).
+#[allow(clippy::float_cmp)]
+fn round_half_to_even(value: f64) -> f64 {
+    let rounded = value.round();
+    if (value - value.trunc()).abs() == 0.5 {
+        2.0 * (value / 2.0).round()
+    } else {
+        rounded
+    }
+}
+
+macro_rules! bytes_to_f64 {
+    ( $bytes:expr, $t:ty, $convert:expr ) => {
+        $bytes
+            .chunks_exact(core::mem::size_of::<$t>())
+            .map(|bytes| $convert(<$t>::from_ne_bytes(bytes.try_into().unwrap())))
+            .collect()
+    };
+}
+
+macro_rules! f64_to_bytes {
+    ( $values:expr, $convert:expr ) => {
+        $values
+            .into_iter()
+            .flat_map(|value| $convert(value).to_ne_bytes())
+            .collect()
+    };
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn to_f64(bytes: &[u8], data_type: &DataType) -> Result<Vec<f64>, CodecError> {
+    Ok(match data_type {
+        DataType::Int8 => bytes_to_f64!(bytes, i8, f64::from),
+        DataType::Int16 => bytes_to_f64!(bytes, i16, f64::from),
+        DataType::Int32 => bytes_to_f64!(bytes, i32, f64::from),
+        DataType::Int64 => bytes_to_f64!(bytes, i64, |x| x as f64),
+        DataType::UInt8 => bytes_to_f64!(bytes, u8, f64::from),
+        DataType::UInt16 => bytes_to_f64!(bytes, u16, f64::from),
+        DataType::UInt32 => bytes_to_f64!(bytes, u32, f64::from),
+        DataType::UInt64 => bytes_to_f64!(bytes, u64, |x| x as f64),
+        DataType::Float16 => bytes_to_f64!(bytes, half::f16, half::f16::to_f64),
+        DataType::BFloat16 => bytes_to_f64!(bytes, half::bf16, half::bf16::to_f64),
+        DataType::Float32 => bytes_to_f64!(bytes, f32, f64::from),
+        DataType::Float64 => bytes_to_f64!(bytes, f64, |x| x),
+        _ => {
+            return Err(CodecError::UnsupportedDataType(
+                data_type.clone(),
+                IDENTIFIER.to_string(),
+            ))
+        }
+    })
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_f64(values: Vec<f64>, data_type: &DataType) -> Result<Vec<u8>, CodecError> {
+    Ok(match data_type {
+        DataType::Int8 => f64_to_bytes!(values, |x| x as i8),
+        DataType::Int16 => f64_to_bytes!(values, |x| x as i16),
+        DataType::Int32 => f64_to_bytes!(values, |x| x as i32),
+        DataType::Int64 => f64_to_bytes!(values, |x| x as i64),
+        DataType::UInt8 => f64_to_bytes!(values, |x| x as u8),
+        DataType::UInt16 => f64_to_bytes!(values, |x| x as u16),
+        DataType::UInt32 => f64_to_bytes!(values, |x| x as u32),
+        DataType::UInt64 => f64_to_bytes!(values, |x| x as u64),
+        DataType::Float16 => f64_to_bytes!(values, half::f16::from_f64),
+        DataType::BFloat16 => f64_to_bytes!(values, half::bf16::from_f64),
+        DataType::Float32 => f64_to_bytes!(values, |x| x as f32),
+        DataType::Float64 => f64_to_bytes!(values, |x: f64| x),
+        _ => {
+            return Err(CodecError::UnsupportedDataType(
+                data_type.clone(),
+                IDENTIFIER.to_string(),
+            ))
+        }
+    })
+}
+
+/// Encode `bytes` of `dtype` to bytes of `astype`.
+fn encode_bytes(
+    bytes: &[u8],
+    offset: f64,
+    scale: f64,
+    dtype: &DataType,
+    astype: &DataType,
+) -> Result<Vec<u8>, CodecError> {
+    let mut values = to_f64(bytes, dtype)?;
+    for value in &mut values {
+        *value = round_half_to_even((*value - offset) * scale);
+    }
+    from_f64(values, astype)
+}
+
+/// Decode `bytes` of `astype` to bytes of `dtype`.
+fn decode_bytes(
+    bytes: &[u8],
+    offset: f64,
+    scale: f64,
+    dtype: &DataType,
+    astype: &DataType,
+) -> Result<Vec<u8>, CodecError> {
+    let mut values = to_f64(bytes, astype)?;
+    for value in &mut values {
+        *value = *value / scale + offset;
+    }
+    from_f64(values, dtype)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use crate::{
+        array::{
+            codec::{
+                ArrayCodecTraits, ArrayToArrayCodecTraits, ArrayToBytesCodecTraits, BytesCodec,
+                CodecOptions,
+            },
+            transmute_from_bytes_vec, transmute_to_bytes_vec, ChunkRepresentation, DataType,
+            FillValue,
+        },
+        array_subset::ArraySubset,
+    };
+
+    use super::*;
+
+    const JSON_VALID: &str = r#"{
+        "offset": 1000,
+        "scale": 10,
+        "dtype": "<f8",
+        "astype": "<u1"
+    }"#;
+
+    #[test]
+    fn codec_fixedscaleoffset_round_half_to_even() {
+        for (value, rounded) in [
+            (0.5, 0.0),
+            (1.5, 2.0),
+            (2.5, 2.0),
+            (-2.5, -2.0),
+            (2.6, 3.0),
+            (-2.4, -2.0),
+        ] {
+            assert!((round_half_to_even(value) - rounded).abs() < f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn codec_fixedscaleoffset_round_trip() {
+        let elements: Vec<f64> = vec![1000.0, 1000.11, 1000.22, 1000.33, 1020.0, 1025.5];
+        let chunk_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(elements.len() as u64).unwrap()],
+            DataType::Float64,
+            FillValue::from(1000.0f64),
+        )
+        .unwrap();
+        let bytes = transmute_to_bytes_vec(elements);
+
+        let configuration: FixedScaleOffsetCodecConfiguration =
+            serde_json::from_str(JSON_VALID).unwrap();
+        let codec = FixedScaleOffsetCodec::new_with_configuration(&configuration).unwrap();
+
+        let encoded_representation = codec.compute_encoded_size(&chunk_representation).unwrap();
+        assert_eq!(encoded_representation.data_type(), &DataType::UInt8);
+        assert_eq!(encoded_representation.fill_value(), &FillValue::from(0u8));
+
+        let encoded = codec
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        assert_eq!(encoded, vec![0, 1, 2, 3, 200, 255]);
+        let decoded = codec
+            .decode(
+                encoded.into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_elements = transmute_from_bytes_vec::<f64>(decoded);
+        let expected = [1000.0, 1000.1, 1000.2, 1000.3, 1020.0, 1025.5];
+        for (decoded, expected) in decoded_elements.iter().zip(expected) {
+            assert!((decoded - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn codec_fixedscaleoffset_invalid() {
+        let configuration: FixedScaleOffsetCodecConfiguration = serde_json::from_str(
+            r#"{
+                "offset": 0,
+                "scale": 1,
+                "dtype": "<c8"
+            }"#,
+        )
+        .unwrap();
+        assert!(FixedScaleOffsetCodec::new_with_configuration(&configuration).is_err());
+
+        let codec =
+            FixedScaleOffsetCodec::new(0.0, 1.0, DataType::Float32, DataType::Int16).unwrap();
+        let chunk_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(2).unwrap()],
+            DataType::Float64,
+            FillValue::from(0.0f64),
+        )
+        .unwrap();
+        assert!(codec.compute_encoded_size(&chunk_representation).is_err());
+    }
+
+    #[test]
+    fn codec_fixedscaleoffset_partial_decode() {
+        let elements: Vec<f32> = (0..16u8).map(|i| f32::from(i) * 0.25 - 1.0).collect();
+        let chunk_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(4).unwrap(), NonZeroU64::new(4).unwrap()],
+            DataType::Float32,
+            FillValue::from(0.0f32),
+        )
+        .unwrap();
+        let bytes = transmute_to_bytes_vec(elements);
+
+        let codec =
+            FixedScaleOffsetCodec::new(-1.0, 4.0, DataType::Float32, DataType::Int16).unwrap();
+        let encoded = codec
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        let decoded_regions = [ArraySubset::new_with_ranges(&[1..3, 1..2])];
+        let encoded_representation = codec.compute_encoded_size(&chunk_representation).unwrap();
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let bytes_codec = BytesCodec::default();
+        let input_handle = bytes_codec
+            .partial_decoder(
+                input_handle,
+                &encoded_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder.partial_decode(&decoded_regions).unwrap();
+        let decoded_partial_chunk: Vec<f32> =
+            transmute_from_bytes_vec(decoded_partial_chunk.into_iter().flatten().collect());
+        assert_eq!(decoded_partial_chunk, vec![0.25, 1.25]);
+    }
+}
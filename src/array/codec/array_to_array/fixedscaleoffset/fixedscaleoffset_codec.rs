@@ -0,0 +1,220 @@
+use bytes::Bytes;
+
+use crate::{
+    array::{
+        codec::{
+            options::CodecOptions, ArrayCodecTraits, ArrayPartialDecoderTraits,
+            ArrayToArrayCodecTraits, CodecError, CodecTraits, RecommendedConcurrency,
+        },
+        data_type_metadata_v2_to_v3, ChunkRepresentation, DataType, FillValue,
+    },
+    metadata::Metadata,
+    plugin::PluginCreateError,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncArrayPartialDecoderTraits;
+
+use super::{
+    data_type_to_numpy, decode_bytes, encode_bytes, fixedscaleoffset_partial_decoder,
+    is_supported_data_type, FixedScaleOffsetCodecConfiguration,
+    FixedScaleOffsetCodecConfigurationV1, IDENTIFIER,
+};
+
+/// A `fixedscaleoffset` codec implementation.
+#[derive(Clone, Debug)]
+pub struct FixedScaleOffsetCodec {
+    offset: f64,
+    scale: f64,
+    dtype: DataType,
+    astype: DataType,
+}
+
+impl FixedScaleOffsetCodec {
+    /// Create a new `fixedscaleoffset` codec.
+    ///
+    /// Elements of the `dtype` data type are encoded as `round((x - offset) * scale)` in the `astype` data type.
+    ///
+    /// # Errors
+    /// Returns [`PluginCreateError`] if `dtype` or `astype` is not an integer or floating point data type, or `scale` is zero.
+    pub fn new(
+        offset: f64,
+        scale: f64,
+        dtype: DataType,
+        astype: DataType,
+    ) -> Result<Self, PluginCreateError> {
+        for data_type in [&dtype, &astype] {
+            if !is_supported_data_type(data_type) {
+                return Err(PluginCreateError::Other(format!(
+                    "{IDENTIFIER} codec does not support data type {data_type}"
+                )));
+            }
+        }
+        if scale == 0.0 || !scale.is_finite() || !offset.is_finite() {
+            return Err(PluginCreateError::Other(format!(
+                "{IDENTIFIER} codec scale {scale} and offset {offset} are invalid"
+            )));
+        }
+        Ok(Self {
+            offset,
+            scale,
+            dtype,
+            astype,
+        })
+    }
+
+    /// Create a new `fixedscaleoffset` codec from a configuration.
+    ///
+    /// # Errors
+    /// Returns [`PluginCreateError`] if the configuration is invalid or has an unsupported data type.
+    pub fn new_with_configuration(
+        configuration: &FixedScaleOffsetCodecConfiguration,
+    ) -> Result<Self, PluginCreateError> {
+        let FixedScaleOffsetCodecConfiguration::V1(configuration) = configuration;
+        let parse_data_type = |dtype: &str| {
+            data_type_metadata_v2_to_v3(dtype)
+                .map(|(data_type, _endianness)| data_type)
+                .map_err(|err| PluginCreateError::Other(err.to_string()))
+        };
+        let dtype = parse_data_type(&configuration.dtype)?;
+        let astype = configuration
+            .astype
+            .as_deref()
+            .map_or_else(|| Ok(dtype.clone()), parse_data_type)?;
+        Self::new(configuration.offset, configuration.scale, dtype, astype)
+    }
+}
+
+impl CodecTraits for FixedScaleOffsetCodec {
+    fn create_metadata(&self) -> Option<Metadata> {
+        let configuration = FixedScaleOffsetCodecConfigurationV1 {
+            offset: self.offset,
+            scale: self.scale,
+            dtype: data_type_to_numpy(&self.dtype)?,
+            astype: Some(data_type_to_numpy(&self.astype)?),
+        };
+        Some(Metadata::new_with_serializable_configuration(IDENTIFIER, &configuration).unwrap())
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        false
+    }
+}
+
+impl ArrayCodecTraits for FixedScaleOffsetCodec {
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &ChunkRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+
+    fn encode(
+        &self,
+        decoded_value: Vec<u8>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        self.validate_data_type(decoded_representation.data_type())?;
+        encode_bytes(
+            &decoded_value,
+            self.offset,
+            self.scale,
+            &self.dtype,
+            &self.astype,
+        )
+    }
+
+    fn decode(
+        &self,
+        encoded_value: Bytes,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        self.validate_data_type(decoded_representation.data_type())?;
+        decode_bytes(
+            &encoded_value,
+            self.offset,
+            self.scale,
+            &self.dtype,
+            &self.astype,
+        )
+    }
+}
+
+impl FixedScaleOffsetCodec {
+    fn validate_data_type(&self, data_type: &DataType) -> Result<(), CodecError> {
+        if data_type == &self.dtype {
+            Ok(())
+        } else {
+            Err(CodecError::UnsupportedDataType(
+                data_type.clone(),
+                IDENTIFIER.to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl ArrayToArrayCodecTraits for FixedScaleOffsetCodec {
+    fn partial_decoder<'a>(
+        &'a self,
+        input_handle: Box<dyn ArrayPartialDecoderTraits + 'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn ArrayPartialDecoderTraits + 'a>, CodecError> {
+        self.validate_data_type(decoded_representation.data_type())?;
+        Ok(Box::new(
+            fixedscaleoffset_partial_decoder::FixedScaleOffsetPartialDecoder::new(
+                input_handle,
+                self.offset,
+                self.scale,
+                self.dtype.clone(),
+                self.astype.clone(),
+            ),
+        ))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder<'a>(
+        &'a self,
+        input_handle: Box<dyn AsyncArrayPartialDecoderTraits + 'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn AsyncArrayPartialDecoderTraits + 'a>, CodecError> {
+        self.validate_data_type(decoded_representation.data_type())?;
+        Ok(Box::new(
+            fixedscaleoffset_partial_decoder::AsyncFixedScaleOffsetPartialDecoder::new(
+                input_handle,
+                self.offset,
+                self.scale,
+                self.dtype.clone(),
+                self.astype.clone(),
+            ),
+        ))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        decoded_representation: &ChunkRepresentation,
+    ) -> Result<ChunkRepresentation, CodecError> {
+        self.validate_data_type(decoded_representation.data_type())?;
+        let fill_value = FillValue::new(encode_bytes(
+            decoded_representation.fill_value().as_ne_bytes(),
+            self.offset,
+            self.scale,
+            &self.dtype,
+            &self.astype,
+        )?);
+        ChunkRepresentation::new(
+            decoded_representation.shape().to_vec(),
+            self.astype.clone(),
+            fill_value,
+        )
+        .map_err(|err| CodecError::Other(err.to_string()))
+    }
+}
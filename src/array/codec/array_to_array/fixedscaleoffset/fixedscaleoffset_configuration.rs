@@ -0,0 +1,79 @@
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+/// A wrapper to handle various versions of `fixedscaleoffset` codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum FixedScaleOffsetCodecConfiguration {
+    /// Version 1.0 draft.
+    V1(FixedScaleOffsetCodecConfigurationV1),
+}
+
+/// `fixedscaleoffset` codec configuration parameters (version 1.0 draft).
+///
+/// The configuration matches the `numcodecs` `FixedScaleOffset` filter configuration.
+/// Data types are `NumPy` type strings (e.g. `"<f8"`), the endianness is ignored.
+///
+/// ### Example: Store values between 1000 and 1025.5 to one decimal place in an 8-bit unsigned integer
+/// ```rust
+/// # let JSON = r#"
+/// {
+///     "offset": 1000,
+///     "scale": 10,
+///     "dtype": "<f8",
+///     "astype": "|u1"
+/// }
+/// # "#;
+/// # let configuration: zarrs::array::codec::FixedScaleOffsetCodecConfigurationV1 = serde_json::from_str(JSON).unwrap();
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
+pub struct FixedScaleOffsetCodecConfigurationV1 {
+    /// The value subtracted from the data before scaling.
+    pub offset: f64,
+    /// The value the offset data is multiplied by before rounding.
+    pub scale: f64,
+    /// The decoded data type.
+    pub dtype: String,
+    /// The encoded data type. Defaults to `dtype` if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub astype: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::metadata::Metadata;
+
+    use super::*;
+
+    #[test]
+    fn codec_fixedscaleoffset_metadata() {
+        serde_json::from_str::<Metadata>(
+            r#"{
+            "name": "fixedscaleoffset",
+            "configuration": {
+                "offset": 1000,
+                "scale": 10,
+                "dtype": "<f8",
+                "astype": "|u1"
+            }
+        }"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn codec_fixedscaleoffset_config() {
+        let configuration = serde_json::from_str::<FixedScaleOffsetCodecConfiguration>(
+            r#"{
+                "offset": 0.5,
+                "scale": 2,
+                "dtype": "<f4"
+            }"#,
+        )
+        .unwrap();
+        let FixedScaleOffsetCodecConfiguration::V1(configuration) = configuration;
+        assert_eq!(configuration.astype, None);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    array::{
+        codec::{ArrayPartialDecoderTraits, CodecError, CodecOptions},
+        DataType,
+    },
+    array_subset::ArraySubset,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncArrayPartialDecoderTraits;
+
+use super::decode_bytes;
+
+/// Partial decoder for the `fixedscaleoffset` codec.
+pub struct FixedScaleOffsetPartialDecoder<'a> {
+    input_handle: Box<dyn ArrayPartialDecoderTraits + 'a>,
+    offset: f64,
+    scale: f64,
+    dtype: DataType,
+    astype: DataType,
+}
+
+impl<'a> FixedScaleOffsetPartialDecoder<'a> {
+    /// Create a new partial decoder for the `fixedscaleoffset` codec.
+    pub fn new(
+        input_handle: Box<dyn ArrayPartialDecoderTraits + 'a>,
+        offset: f64,
+        scale: f64,
+        dtype: DataType,
+        astype: DataType,
+    ) -> Self {
+        Self {
+            input_handle,
+            offset,
+            scale,
+            dtype,
+            astype,
+        }
+    }
+}
+
+impl ArrayPartialDecoderTraits for FixedScaleOffsetPartialDecoder<'_> {
+    fn element_size(&self) -> usize {
+        self.dtype.size()
+    }
+
+    fn partial_decode_opt(
+        &self,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<u8>>, CodecError> {
+        self.input_handle
+            .partial_decode_opt(array_subsets, options)?
+            .iter()
+            .map(|bytes| decode_bytes(bytes, self.offset, self.scale, &self.dtype, &self.astype))
+            .collect()
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `fixedscaleoffset` codec.
+pub struct AsyncFixedScaleOffsetPartialDecoder<'a> {
+    input_handle: Box<dyn AsyncArrayPartialDecoderTraits + 'a>,
+    offset: f64,
+    scale: f64,
+    dtype: DataType,
+    astype: DataType,
+}
+
+#[cfg(feature = "async")]
+impl<'a> AsyncFixedScaleOffsetPartialDecoder<'a> {
+    /// Create a new partial decoder for the `fixedscaleoffset` codec.
+    pub fn new(
+        input_handle: Box<dyn AsyncArrayPartialDecoderTraits + 'a>,
+        offset: f64,
+        scale: f64,
+        dtype: DataType,
+        astype: DataType,
+    ) -> Self {
+        Self {
+            input_handle,
+            offset,
+            scale,
+            dtype,
+            astype,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncArrayPartialDecoderTraits for AsyncFixedScaleOffsetPartialDecoder<'_> {
+    fn element_size(&self) -> usize {
+        self.dtype.size()
+    }
+
+    async fn partial_decode_opt(
+        &self,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<u8>>, CodecError> {
+        self.input_handle
+            .partial_decode_opt(array_subsets, options)
+            .await?
+            .iter()
+            .map(|bytes| decode_bytes(bytes, self.offset, self.scale, &self.dtype, &self.astype))
+            .collect()
+    }
+}
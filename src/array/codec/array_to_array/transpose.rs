@@ -71,6 +71,16 @@ fn calculate_order_decode(order: &TransposeOrder, array_dimensions: usize) -> Ve
     permutation_decode
 }
 
+/// Return `shape` with an additional innermost dimension of `bytes_per_element`.
+fn shape_with_element_dimension(shape: &[u64], bytes_per_element: usize) -> Vec<usize> {
+    let mut shape_n = Vec::with_capacity(shape.len() + 1);
+    for size in shape {
+        shape_n.push(usize::try_from(*size).unwrap());
+    }
+    shape_n.push(bytes_per_element);
+    shape_n
+}
+
 fn transpose_array(
     transpose_order: &[usize],
     untransposed_shape: &[u64],
@@ -78,11 +88,7 @@ fn transpose_array(
     data: Vec<u8>,
 ) -> Result<Vec<u8>, ndarray::ShapeError> {
     // Create an array view of the data
-    let mut shape_n = Vec::with_capacity(untransposed_shape.len() + 1);
-    for size in untransposed_shape {
-        shape_n.push(usize::try_from(*size).unwrap());
-    }
-    shape_n.push(bytes_per_element);
+    let shape_n = shape_with_element_dimension(untransposed_shape, bytes_per_element);
     let array = ndarray::ArrayD::<u8>::from_shape_vec(shape_n, data)?;
 
     // Transpose the data
@@ -146,7 +152,11 @@ mod tests {
             )
             .unwrap();
         let decoded = codec
-            .decode(encoded, &chunk_representation, &CodecOptions::default())
+            .decode(
+                encoded.into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
             .unwrap();
         assert_eq!(bytes, decoded);
 
@@ -178,6 +188,62 @@ mod tests {
         codec_transpose_round_trip_impl(JSON, DataType::UInt16, FillValue::from(0u16));
     }
 
+    #[test]
+    fn codec_transpose_decode_into_array_view() {
+        use crate::array::{codec::CodecChain, ArrayView};
+
+        let chunk_representation = ChunkRepresentation::new(
+            vec![
+                NonZeroU64::new(2).unwrap(),
+                NonZeroU64::new(3).unwrap(),
+                NonZeroU64::new(4).unwrap(),
+            ],
+            DataType::UInt16,
+            FillValue::from(0u16),
+        )
+        .unwrap();
+        let elements: Vec<u16> = (0..24).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        for array_to_array in [
+            vec![Box::new(TransposeCodec::new(
+                TransposeOrder::new(&[2, 0, 1]).unwrap(),
+            )) as _],
+            vec![
+                Box::new(TransposeCodec::new(
+                    TransposeOrder::new(&[2, 0, 1]).unwrap(),
+                )) as _,
+                Box::new(TransposeCodec::new(
+                    TransposeOrder::new(&[1, 0, 2]).unwrap(),
+                )) as _,
+            ],
+        ] {
+            let codec = CodecChain::new(array_to_array, Box::new(BytesCodec::little()), vec![]);
+            let encoded = codec
+                .encode(
+                    bytes.clone(),
+                    &chunk_representation,
+                    &CodecOptions::default(),
+                )
+                .unwrap();
+
+            let array_shape = vec![4, 3, 6];
+            let subset = ArraySubset::new_with_ranges(&[1..3, 0..3, 2..6]);
+            let mut output = vec![0u8; 4 * 3 * 6 * 2];
+            let array_view = ArrayView::new(&mut output, &array_shape, subset.clone()).unwrap();
+            codec
+                .decode_into_array_view(
+                    &encoded,
+                    &chunk_representation,
+                    &array_view,
+                    &CodecOptions::default(),
+                )
+                .unwrap();
+            let output_subset = unsafe { subset.extract_bytes_unchecked(&output, &array_shape, 2) };
+            assert_eq!(output_subset, bytes);
+        }
+    }
+
     #[test]
     fn codec_transpose_partial_decode() {
         let codec = TransposeCodec::new(TransposeOrder::new(&[1, 0]).unwrap());
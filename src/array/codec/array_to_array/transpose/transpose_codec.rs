@@ -1,3 +1,4 @@
+use bytes::Bytes;
 use derive_more::From;
 use thiserror::Error;
 
@@ -7,7 +8,7 @@ use crate::{
             options::CodecOptions, ArrayCodecTraits, ArrayPartialDecoderTraits,
             ArrayToArrayCodecTraits, CodecError, CodecTraits, RecommendedConcurrency,
         },
-        ChunkRepresentation,
+        ArrayView, ChunkRepresentation,
     },
     metadata::Metadata,
     plugin::PluginCreateError,
@@ -17,9 +18,9 @@ use crate::{
 use crate::array::codec::AsyncArrayPartialDecoderTraits;
 
 use super::{
-    calculate_order_decode, calculate_order_encode, permute, transpose_array,
-    transpose_configuration::TransposeCodecConfigurationV1, TransposeCodecConfiguration,
-    TransposeOrder, IDENTIFIER,
+    calculate_order_decode, calculate_order_encode, permute, shape_with_element_dimension,
+    transpose_array, transpose_configuration::TransposeCodecConfigurationV1,
+    TransposeCodecConfiguration, TransposeOrder, IDENTIFIER,
 };
 
 /// A Transpose codec implementation.
@@ -153,7 +154,7 @@ impl ArrayCodecTraits for TransposeCodec {
 
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         decoded_representation: &ChunkRepresentation,
         _options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
@@ -165,8 +166,62 @@ impl ArrayCodecTraits for TransposeCodec {
             &order_decode,
             &transposed_shape,
             decoded_representation.element_size(),
-            encoded_value,
+            Vec::from(encoded_value),
         )
         .map_err(|_| CodecError::UnexpectedChunkDecodedSize(len, decoded_representation.size()))
     }
+
+    fn decode_into_array_view(
+        &self,
+        encoded_value: &[u8],
+        decoded_representation: &ChunkRepresentation,
+        array_view: &ArrayView,
+        _options: &CodecOptions,
+    ) -> Result<(), CodecError> {
+        if encoded_value.len() as u64 != decoded_representation.size() {
+            return Err(CodecError::UnexpectedChunkDecodedSize(
+                encoded_value.len(),
+                decoded_representation.size(),
+            ));
+        }
+        let subset = array_view.subset();
+        if subset.shape() != decoded_representation.shape_u64() {
+            return Err(CodecError::from(format!(
+                "array view subset {subset} does not match the decoded shape {:?}",
+                decoded_representation.shape_u64()
+            )));
+        }
+        let element_size = decoded_representation.element_size();
+
+        // Transposed view of the encoded value
+        let order_decode =
+            calculate_order_decode(&self.order, decoded_representation.shape().len());
+        let transposed_shape = permute(&decoded_representation.shape_u64(), &self.order);
+        let decoded = ndarray::ArrayViewD::<u8>::from_shape(
+            shape_with_element_dimension(&transposed_shape, element_size),
+            encoded_value,
+        )
+        .map_err(|err| CodecError::from(err.to_string()))?
+        .permuted_axes(order_decode);
+
+        // Write directly into the array view subset
+        let output = unsafe { array_view.bytes_mut() };
+        let mut output = ndarray::ArrayViewMutD::<u8>::from_shape(
+            shape_with_element_dimension(array_view.array_shape(), element_size),
+            output,
+        )
+        .map_err(|err| CodecError::from(err.to_string()))?;
+        let mut output = output.slice_each_axis_mut(|axis| {
+            let i = axis.axis.index();
+            if i < subset.dimensionality() {
+                let start = usize::try_from(subset.start()[i]).unwrap();
+                let end = start + usize::try_from(subset.shape()[i]).unwrap();
+                ndarray::Slice::from(start..end)
+            } else {
+                ndarray::Slice::from(..)
+            }
+        });
+        output.assign(&decoded);
+        Ok(())
+    }
 }
@@ -3,6 +3,12 @@
 pub mod bytes;
 pub mod codec_chain;
 
+#[cfg(feature = "blosc2")]
+pub mod blosc2_nd;
+#[cfg(feature = "n5")]
+pub mod n5;
+#[cfg(feature = "packbits")]
+pub mod packbits;
 #[cfg(feature = "pcodec")]
 pub mod pcodec;
 #[cfg(feature = "sharding")]
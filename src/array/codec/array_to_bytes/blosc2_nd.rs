@@ -0,0 +1,615 @@
+//! The `blosc2_nd` array to bytes codec.
+//!
+//! It uses the multidimensional [blosc2](https://www.blosc.org/) `b2nd` format.
+//!
+//! This codec requires the `blosc2` feature, which is disabled by default.
+//!
+//! Each encoded chunk is a `b2nd` array partitioned into multidimensional blocks of shape `blockshape`, which are compressed independently.
+//! Combined with the [`sharding`](crate::array::codec::array_to_bytes::sharding) codec, this gives a three level partitioning (shard, inner chunk, block).
+//!
+//! The partial decoder only decompresses the blocks intersecting the requested array subsets, rather than the entire chunk.
+//! Unlike the [`blosc2`](crate::array::codec::bytes_to_bytes::blosc2) bytes to bytes codec, whose blocks are contiguous byte ranges of the decoded chunk, a small region of interest intersects few blocks regardless of the dimension it spans.
+//! A smaller `blockshape` therefore reduces the cost of reading a small region of a chunk at the expense of compression ratio.
+//!
+//! Elements are encoded in little endian.
+//! The `blockshape` is clamped to the chunk shape, and a chunk must have at most 8 dimensions.
+//!
+//! The codec is not yet standardised, so the configuration and identifier may change.
+//!
+//! ### Codec `configuration` Example - [`Blosc2NdCodecConfiguration`]:
+//! ```rust
+//! # let JSON = r#"
+//! {
+//!     "cname": "zstd",
+//!     "clevel": 5,
+//!     "shuffle": "shuffle",
+//!     "blockshape": [16, 16, 16]
+//! }
+//! # "#;
+//! # let configuration: zarrs::array::codec::Blosc2NdCodecConfiguration = serde_json::from_str(JSON).unwrap();
+//! ```
+
+mod blosc2_nd_codec;
+mod blosc2_nd_configuration;
+mod blosc2_nd_partial_decoder;
+
+use std::{
+    ffi::{c_char, c_int, c_void},
+    marker::PhantomData,
+};
+
+pub use blosc2_nd_codec::Blosc2NdCodec;
+pub use blosc2_nd_configuration::{Blosc2NdCodecConfiguration, Blosc2NdCodecConfigurationV1};
+use blosc2_sys::{
+    blosc2_get_blosc2_storage_defaults, blosc2_metalayer, blosc2_schunk, blosc2_storage,
+};
+
+use crate::{
+    array::{
+        codec::{
+            bytes_to_bytes::blosc2::{
+                blosc2_compression_params, blosc2_decompression_params, Blosc2CompressionLevel,
+                Blosc2Compressor, Blosc2Error, Blosc2ShuffleMode,
+            },
+            Codec, CodecPlugin,
+        },
+        ArrayShape,
+    },
+    array_subset::ArraySubset,
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+/// The identifier for the `blosc2_nd` codec.
+pub const IDENTIFIER: &str = "blosc2_nd";
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_blosc2_nd, create_codec_blosc2_nd)
+}
+
+fn is_name_blosc2_nd(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+}
+
+pub(crate) fn create_codec_blosc2_nd(metadata: &Metadata) -> Result<Codec, PluginCreateError> {
+    let configuration: Blosc2NdCodecConfiguration = metadata
+        .to_configuration()
+        .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?;
+    let codec = Box::new(Blosc2NdCodec::new_with_configuration(&configuration)?);
+    Ok(Codec::ArrayToBytes(codec))
+}
+
+/// The maximum dimensionality of a `b2nd` array.
+const B2ND_MAX_DIM: usize = 8;
+
+/// An opaque `b2nd` context.
+#[repr(C)]
+struct b2nd_context_t {
+    _private: [u8; 0],
+}
+
+/// The leading fields of a `b2nd` array.
+///
+/// `blosc2-sys` does not generate bindings for `b2nd.h`, but `b2nd` is part of the `blosc2` library.
+/// Arrays are only accessed through pointers returned by `b2nd`, so the trailing fields are omitted.
+#[repr(C)]
+struct b2nd_array_t {
+    sc: *mut blosc2_schunk,
+    shape: [i64; B2ND_MAX_DIM],
+    chunkshape: [i32; B2ND_MAX_DIM],
+    extshape: [i64; B2ND_MAX_DIM],
+    blockshape: [i32; B2ND_MAX_DIM],
+    extchunkshape: [i64; B2ND_MAX_DIM],
+    nitems: i64,
+    chunknitems: i32,
+    extnitems: i64,
+    blocknitems: i32,
+    extchunknitems: i64,
+    ndim: i8,
+}
+
+extern "C" {
+    fn b2nd_create_ctx(
+        b2_storage: *const blosc2_storage,
+        ndim: i8,
+        shape: *const i64,
+        chunkshape: *const i32,
+        blockshape: *const i32,
+        dtype: *const c_char,
+        dtype_format: i8,
+        metalayers: *const blosc2_metalayer,
+        nmetalayers: i32,
+    ) -> *mut b2nd_context_t;
+
+    fn b2nd_free_ctx(ctx: *mut b2nd_context_t) -> c_int;
+
+    fn b2nd_from_cbuffer(
+        ctx: *mut b2nd_context_t,
+        array: *mut *mut b2nd_array_t,
+        buffer: *const c_void,
+        buffersize: i64,
+    ) -> c_int;
+
+    fn b2nd_to_cframe(
+        array: *const b2nd_array_t,
+        cframe: *mut *mut u8,
+        cframe_len: *mut i64,
+        needs_free: *mut bool,
+    ) -> c_int;
+
+    fn b2nd_from_cframe(
+        cframe: *mut u8,
+        cframe_len: i64,
+        copy: bool,
+        array: *mut *mut b2nd_array_t,
+    ) -> c_int;
+
+    fn b2nd_get_slice_cbuffer(
+        array: *const b2nd_array_t,
+        start: *const i64,
+        stop: *const i64,
+        buffer: *mut c_void,
+        buffershape: *const i64,
+        buffersize: i64,
+    ) -> c_int;
+
+    fn b2nd_free(array: *mut b2nd_array_t) -> c_int;
+
+    fn free(ptr: *mut c_void);
+}
+
+/// A `b2nd` context, freed on drop.
+struct B2ndContext(*mut b2nd_context_t);
+
+impl Drop for B2ndContext {
+    fn drop(&mut self) {
+        unsafe {
+            b2nd_free_ctx(self.0);
+        }
+    }
+}
+
+/// A `b2nd` array, freed on drop.
+///
+/// An array created from a frame without copying borrows the frame for `'a`.
+struct B2ndArray<'a>(*mut b2nd_array_t, PhantomData<&'a [u8]>);
+
+impl B2ndArray<'_> {
+    fn null() -> Self {
+        Self(std::ptr::null_mut(), PhantomData)
+    }
+
+    fn shape(&self) -> Vec<u64> {
+        let array = unsafe { &*self.0 };
+        #[allow(clippy::cast_sign_loss)]
+        array.shape[..array.ndim as usize]
+            .iter()
+            .map(|&size| size as u64)
+            .collect()
+    }
+
+    fn typesize(&self) -> usize {
+        let typesize = unsafe { (*(*self.0).sc).typesize };
+        usize::try_from(typesize).unwrap_or_default()
+    }
+}
+
+impl Drop for B2ndArray<'_> {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe {
+                b2nd_free(self.0);
+            }
+        }
+    }
+}
+
+fn to_i64(values: &[u64]) -> Result<Vec<i64>, Blosc2Error> {
+    values
+        .iter()
+        .map(|&value| {
+            i64::try_from(value).map_err(|_| Blosc2Error::from(format!("{value} is too large")))
+        })
+        .collect()
+}
+
+fn to_i32(values: &[u64]) -> Result<Vec<i32>, Blosc2Error> {
+    values
+        .iter()
+        .map(|&value| {
+            i32::try_from(value).map_err(|_| Blosc2Error::from(format!("{value} is too large")))
+        })
+        .collect()
+}
+
+/// Encode `src` with shape `shape` as a `b2nd` frame partitioned into blocks of shape `blockshape`.
+///
+/// `blockshape` is clamped to `shape`.
+fn blosc2_nd_encode(
+    src: &[u8],
+    shape: &[u64],
+    blockshape: &[u64],
+    typesize: usize,
+    clevel: Blosc2CompressionLevel,
+    shuffle_mode: Blosc2ShuffleMode,
+    compressor: Blosc2Compressor,
+) -> Result<Vec<u8>, Blosc2Error> {
+    if shape.is_empty() || shape.len() > B2ND_MAX_DIM {
+        return Err(Blosc2Error::from(format!(
+            "blosc2_nd supports 1 to {B2ND_MAX_DIM} dimensions, got {}",
+            shape.len()
+        )));
+    }
+    if shape.len() != blockshape.len() {
+        return Err(Blosc2Error::from(format!(
+            "blockshape {blockshape:?} is incompatible with chunk shape {shape:?}"
+        )));
+    }
+    let blockshape: Vec<u64> = std::iter::zip(blockshape, shape)
+        .map(|(&block, &size)| block.min(size))
+        .collect();
+
+    let mut cparams = blosc2_compression_params(clevel, shuffle_mode, typesize, compressor, 0)?;
+    let mut dparams = blosc2_decompression_params();
+    let mut storage = unsafe { blosc2_get_blosc2_storage_defaults() };
+    storage.contiguous = true;
+    storage.cparams = std::ptr::addr_of_mut!(cparams);
+    storage.dparams = std::ptr::addr_of_mut!(dparams);
+
+    let shape_i64 = to_i64(shape)?;
+    let chunkshape = to_i32(shape)?;
+    let blockshape = to_i32(&blockshape)?;
+    let context = B2ndContext(unsafe {
+        b2nd_create_ctx(
+            std::ptr::addr_of!(storage),
+            i8::try_from(shape.len()).unwrap(),
+            shape_i64.as_ptr(),
+            chunkshape.as_ptr(),
+            blockshape.as_ptr(),
+            std::ptr::null(),
+            0,
+            std::ptr::null(),
+            0,
+        )
+    });
+    if context.0.is_null() {
+        return Err(Blosc2Error::from("b2nd_create_ctx failed"));
+    }
+
+    let mut array = B2ndArray::null();
+    let rc = unsafe {
+        b2nd_from_cbuffer(
+            context.0,
+            std::ptr::addr_of_mut!(array.0),
+            src.as_ptr().cast::<c_void>(),
+            i64::try_from(src.len()).unwrap(),
+        )
+    };
+    if rc < 0 {
+        return Err(Blosc2Error::from(format!(
+            "b2nd_from_cbuffer(shape: {shape:?}, blockshape: {blockshape:?}, typesize: {typesize}) -> {rc} (failure)"
+        )));
+    }
+
+    let mut cframe: *mut u8 = std::ptr::null_mut();
+    let mut cframe_len: i64 = 0;
+    let mut needs_free = false;
+    let rc = unsafe {
+        b2nd_to_cframe(
+            array.0,
+            std::ptr::addr_of_mut!(cframe),
+            std::ptr::addr_of_mut!(cframe_len),
+            std::ptr::addr_of_mut!(needs_free),
+        )
+    };
+    if rc < 0 {
+        return Err(Blosc2Error::from(format!(
+            "b2nd_to_cframe -> {rc} (failure)"
+        )));
+    }
+    let encoded = unsafe {
+        std::slice::from_raw_parts(cframe, usize::try_from(cframe_len).unwrap()).to_vec()
+    };
+    if needs_free {
+        unsafe {
+            free(cframe.cast::<c_void>());
+        }
+    }
+    Ok(encoded)
+}
+
+/// Open the `b2nd` frame `src`, checking that it has shape `shape` and type size `typesize`.
+///
+/// The frame is not copied, so the array borrows `src`.
+fn blosc2_nd_open<'a>(
+    src: &'a [u8],
+    shape: &[u64],
+    typesize: usize,
+) -> Result<B2ndArray<'a>, Blosc2Error> {
+    let mut array = B2ndArray::null();
+    // The frame is only read, since it is not copied into a writable frame
+    let rc = unsafe {
+        b2nd_from_cframe(
+            src.as_ptr().cast_mut(),
+            i64::try_from(src.len()).unwrap(),
+            false,
+            std::ptr::addr_of_mut!(array.0),
+        )
+    };
+    if rc < 0 || array.0.is_null() {
+        return Err(Blosc2Error::from("blosc2_nd encoded value is invalid"));
+    }
+    if array.shape() != shape || array.typesize() != typesize {
+        return Err(Blosc2Error::from(format!(
+            "blosc2_nd encoded value has shape {:?} and type size {}, expected {shape:?} and {typesize}",
+            array.shape(),
+            array.typesize()
+        )));
+    }
+    Ok(array)
+}
+
+/// Decode the `array_subset` of a `b2nd` array, only decompressing the blocks intersecting it.
+///
+/// The array subset must be within the bounds of the array.
+fn blosc2_nd_decode_subset(
+    array: &B2ndArray,
+    array_subset: &ArraySubset,
+    typesize: usize,
+) -> Result<Vec<u8>, Blosc2Error> {
+    let destsize = array_subset.num_elements_usize() * typesize;
+    if destsize == 0 {
+        return Ok(vec![]);
+    }
+    let start = to_i64(array_subset.start())?;
+    let stop = to_i64(&array_subset.end_exc())?;
+    let buffershape = to_i64(array_subset.shape())?;
+    let mut dest: Vec<u8> = Vec::with_capacity(destsize);
+    let rc = unsafe {
+        b2nd_get_slice_cbuffer(
+            array.0,
+            start.as_ptr(),
+            stop.as_ptr(),
+            dest.as_mut_ptr().cast::<c_void>(),
+            buffershape.as_ptr(),
+            i64::try_from(destsize).unwrap(),
+        )
+    };
+    if rc < 0 {
+        Err(Blosc2Error::from(format!(
+            "b2nd_get_slice_cbuffer(start: {start:?}, stop: {stop:?}) -> {rc} (failure)"
+        )))
+    } else {
+        unsafe {
+            dest.set_len(destsize);
+        }
+        Ok(dest)
+    }
+}
+
+/// Decode the `b2nd` frame `src` with shape `shape` and type size `typesize`.
+fn blosc2_nd_decode(
+    src: &[u8],
+    shape: &ArrayShape,
+    typesize: usize,
+) -> Result<Vec<u8>, Blosc2Error> {
+    let array = blosc2_nd_open(src, shape, typesize)?;
+    blosc2_nd_decode_subset(
+        &array,
+        &ArraySubset::new_with_shape(shape.clone()),
+        typesize,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use crate::array::{
+        codec::{ArrayCodecTraits, ArrayToBytesCodecTraits, CodecOptions},
+        ChunkRepresentation, DataType, FillValue,
+    };
+
+    use super::*;
+
+    const JSON_VALID: &str = r#"
+{
+    "cname": "lz4",
+    "clevel": 5,
+    "shuffle": "shuffle",
+    "blockshape": [2, 4, 4]
+}"#;
+
+    fn chunk_representation() -> ChunkRepresentation {
+        ChunkRepresentation::new(
+            vec![
+                NonZeroU64::new(8).unwrap(),
+                NonZeroU64::new(8).unwrap(),
+                NonZeroU64::new(8).unwrap(),
+            ],
+            DataType::UInt16,
+            FillValue::from(0u16),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc2_nd_round_trip() {
+        let chunk_representation = chunk_representation();
+        let elements: Vec<u16> = (0..512).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec_configuration: Blosc2NdCodecConfiguration =
+            serde_json::from_str(JSON_VALID).unwrap();
+        let codec = Blosc2NdCodec::new_with_configuration(&codec_configuration).unwrap();
+
+        let encoded = codec
+            .encode(
+                bytes.clone(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded = codec
+            .decode(
+                encoded.into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc2_nd_blockshape_clamped() {
+        let chunk_representation = chunk_representation();
+        let elements: Vec<u16> = (0..512).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec = Blosc2NdCodec::new(
+            Blosc2Compressor::Zstd,
+            Blosc2CompressionLevel::try_from(5).unwrap(),
+            Blosc2ShuffleMode::BitShuffle,
+            vec![
+                NonZeroU64::new(3).unwrap(),
+                NonZeroU64::new(100).unwrap(),
+                NonZeroU64::new(5).unwrap(),
+            ],
+        )
+        .unwrap();
+        let encoded = codec
+            .encode(
+                bytes.clone(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded = codec
+            .decode(
+                encoded.into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc2_nd_incompatible() {
+        let chunk_representation = chunk_representation();
+        let bytes = vec![0; chunk_representation.size_usize()];
+        let codec = Blosc2NdCodec::new(
+            Blosc2Compressor::Zstd,
+            Blosc2CompressionLevel::try_from(5).unwrap(),
+            Blosc2ShuffleMode::Shuffle,
+            vec![NonZeroU64::new(4).unwrap(); 2],
+        )
+        .unwrap();
+        assert!(codec
+            .encode(
+                bytes.clone(),
+                &chunk_representation,
+                &CodecOptions::default()
+            )
+            .is_err());
+
+        // The encoded shape must match the decoded representation
+        let codec_configuration: Blosc2NdCodecConfiguration =
+            serde_json::from_str(JSON_VALID).unwrap();
+        let codec = Blosc2NdCodec::new_with_configuration(&codec_configuration).unwrap();
+        let encoded = codec
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        let other_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(512).unwrap()],
+            DataType::UInt16,
+            FillValue::from(0u16),
+        )
+        .unwrap();
+        assert!(codec
+            .decode(
+                encoded.into(),
+                &other_representation,
+                &CodecOptions::default()
+            )
+            .is_err());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc2_nd_partial_decode() {
+        let chunk_representation = chunk_representation();
+        let elements: Vec<u16> = (0..512).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec_configuration: Blosc2NdCodecConfiguration =
+            serde_json::from_str(JSON_VALID).unwrap();
+        let codec = Blosc2NdCodec::new_with_configuration(&codec_configuration).unwrap();
+
+        let encoded = codec
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        let decoded_regions = [
+            ArraySubset::new_with_ranges(&[2..4, 1..3, 5..7]),
+            ArraySubset::new_with_ranges(&[0..1, 0..0, 0..8]),
+        ];
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded = partial_decoder.partial_decode(&decoded_regions).unwrap();
+
+        let decoded_region: Vec<u16> = crate::array::transmute_from_bytes_vec(decoded[0].clone());
+        let answer: Vec<u16> = [2u16, 3]
+            .iter()
+            .flat_map(|z| [1u16, 2].map(|y| (z, y)))
+            .flat_map(|(z, y)| [5u16, 6].map(|x| z * 64 + y * 8 + x))
+            .collect();
+        assert_eq!(answer, decoded_region);
+        assert!(decoded[1].is_empty());
+
+        assert!(partial_decoder
+            .partial_decode(&[ArraySubset::new_with_ranges(&[6..10, 0..1, 0..1])])
+            .is_err());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn codec_blosc2_nd_async_partial_decode() {
+        let chunk_representation = chunk_representation();
+        let elements: Vec<u16> = (0..512).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec_configuration: Blosc2NdCodecConfiguration =
+            serde_json::from_str(JSON_VALID).unwrap();
+        let codec = Blosc2NdCodec::new_with_configuration(&codec_configuration).unwrap();
+
+        let encoded = codec
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .async_partial_decoder(
+                input_handle,
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .await
+            .unwrap();
+        let decoded = partial_decoder
+            .partial_decode(&[ArraySubset::new_with_ranges(&[7..8, 7..8, 6..8])])
+            .await
+            .unwrap();
+        let decoded: Vec<u16> = crate::array::transmute_from_bytes_vec(decoded[0].clone());
+        assert_eq!(decoded, vec![510, 511]);
+    }
+}
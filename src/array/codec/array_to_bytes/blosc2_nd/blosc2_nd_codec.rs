@@ -0,0 +1,192 @@
+use std::num::NonZeroU64;
+
+use bytes::Bytes;
+
+use crate::{
+    array::{
+        codec::{
+            array_to_bytes::bytes::reverse_endianness,
+            bytes_to_bytes::blosc2::{
+                blosc2_compressor_is_supported, Blosc2CompressionLevel, Blosc2Compressor,
+                Blosc2ShuffleMode,
+            },
+            ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayToBytesCodecTraits,
+            BytesPartialDecoderTraits, CodecError, CodecOptions, CodecTraits,
+            RecommendedConcurrency,
+        },
+        BytesRepresentation, ChunkRepresentation,
+    },
+    metadata::Metadata,
+    plugin::PluginCreateError,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::{AsyncArrayPartialDecoderTraits, AsyncBytesPartialDecoderTraits};
+
+use super::{
+    blosc2_nd_decode, blosc2_nd_encode, blosc2_nd_partial_decoder, Blosc2NdCodecConfiguration,
+    Blosc2NdCodecConfigurationV1, IDENTIFIER,
+};
+
+/// A `blosc2_nd` codec implementation.
+#[derive(Clone, Debug)]
+pub struct Blosc2NdCodec {
+    configuration: Blosc2NdCodecConfigurationV1,
+}
+
+impl Blosc2NdCodec {
+    /// Create a new `blosc2_nd` codec.
+    ///
+    /// `blockshape` is clamped to the chunk shape when encoding.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PluginCreateError`] if the compressor is not supported.
+    pub fn new(
+        cname: Blosc2Compressor,
+        clevel: Blosc2CompressionLevel,
+        shuffle_mode: Blosc2ShuffleMode,
+        blockshape: Vec<NonZeroU64>,
+    ) -> Result<Self, PluginCreateError> {
+        if !blosc2_compressor_is_supported(cname) {
+            return Err(PluginCreateError::from(format!(
+                "compressor {cname:?} is not supported."
+            )));
+        }
+
+        let configuration = Blosc2NdCodecConfigurationV1 {
+            cname,
+            clevel,
+            shuffle: shuffle_mode,
+            blockshape,
+        };
+
+        Ok(Self { configuration })
+    }
+
+    /// Create a new `blosc2_nd` codec from configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PluginCreateError`] if the configuration is not supported.
+    pub fn new_with_configuration(
+        configuration: &Blosc2NdCodecConfiguration,
+    ) -> Result<Self, PluginCreateError> {
+        let Blosc2NdCodecConfiguration::V1(configuration) = configuration;
+        Self::new(
+            configuration.cname,
+            configuration.clevel,
+            configuration.shuffle,
+            configuration.blockshape.clone(),
+        )
+    }
+}
+
+impl CodecTraits for Blosc2NdCodec {
+    fn create_metadata(&self) -> Option<Metadata> {
+        Some(
+            Metadata::new_with_serializable_configuration(IDENTIFIER, &self.configuration).unwrap(),
+        )
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        false
+    }
+}
+
+impl ArrayCodecTraits for Blosc2NdCodec {
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &ChunkRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+
+    fn encode(
+        &self,
+        mut decoded_value: Vec<u8>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        if cfg!(target_endian = "big") {
+            reverse_endianness(&mut decoded_value, decoded_representation.data_type());
+        }
+        let blockshape: Vec<u64> = self
+            .configuration
+            .blockshape
+            .iter()
+            .map(|size| size.get())
+            .collect();
+        blosc2_nd_encode(
+            &decoded_value,
+            &decoded_representation.shape_u64(),
+            &blockshape,
+            decoded_representation.element_size(),
+            self.configuration.clevel,
+            self.configuration.shuffle,
+            self.configuration.cname,
+        )
+        .map_err(|err| CodecError::Other(err.to_string()))
+    }
+
+    fn decode(
+        &self,
+        encoded_value: Bytes,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        let mut decoded_value = blosc2_nd_decode(
+            &encoded_value,
+            &decoded_representation.shape_u64(),
+            decoded_representation.element_size(),
+        )
+        .map_err(|err| CodecError::Other(err.to_string()))?;
+        if cfg!(target_endian = "big") {
+            reverse_endianness(&mut decoded_value, decoded_representation.data_type());
+        }
+        Ok(decoded_value)
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl ArrayToBytesCodecTraits for Blosc2NdCodec {
+    fn partial_decoder<'a>(
+        &self,
+        input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn ArrayPartialDecoderTraits + 'a>, CodecError> {
+        Ok(Box::new(
+            blosc2_nd_partial_decoder::Blosc2NdPartialDecoder::new(
+                input_handle,
+                decoded_representation.clone(),
+            ),
+        ))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder<'a>(
+        &'a self,
+        input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn AsyncArrayPartialDecoderTraits + 'a>, CodecError> {
+        Ok(Box::new(
+            blosc2_nd_partial_decoder::AsyncBlosc2NdPartialDecoder::new(
+                input_handle,
+                decoded_representation.clone(),
+            ),
+        ))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        _decoded_representation: &ChunkRepresentation,
+    ) -> Result<BytesRepresentation, CodecError> {
+        Ok(BytesRepresentation::UnboundedSize)
+    }
+}
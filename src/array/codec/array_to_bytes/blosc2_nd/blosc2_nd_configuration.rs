@@ -0,0 +1,64 @@
+use std::num::NonZeroU64;
+
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+use crate::array::codec::bytes_to_bytes::blosc2::{
+    Blosc2CompressionLevel, Blosc2Compressor, Blosc2ShuffleMode,
+};
+
+/// A wrapper to handle various versions of `blosc2_nd` codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum Blosc2NdCodecConfiguration {
+    /// Version 1.0.
+    V1(Blosc2NdCodecConfigurationV1),
+}
+
+/// Configuration parameters for the `blosc2_nd` codec (version 1.0).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
+pub struct Blosc2NdCodecConfigurationV1 {
+    /// The compressor.
+    pub cname: Blosc2Compressor,
+    /// The compression level.
+    pub clevel: Blosc2CompressionLevel,
+    /// The shuffle mode.
+    pub shuffle: Blosc2ShuffleMode,
+    /// The shape of the compression blocks, clamped to the chunk shape.
+    ///
+    /// Blocks are the unit of partial decompression.
+    pub blockshape: Vec<NonZeroU64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec_blosc2_nd_valid() {
+        serde_json::from_str::<Blosc2NdCodecConfiguration>(
+            r#"
+        {
+            "cname": "zstd",
+            "clevel": 5,
+            "shuffle": "bitshuffle",
+            "blockshape": [16, 16, 16]
+        }"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn codec_blosc2_nd_invalid() {
+        for json in [
+            r#"{"cname": "snappy", "clevel": 5, "shuffle": "shuffle", "blockshape": [4]}"#,
+            r#"{"cname": "lz4", "clevel": 10, "shuffle": "shuffle", "blockshape": [4]}"#,
+            r#"{"cname": "lz4", "clevel": 5, "shuffle": "shuffle", "blockshape": [0, 4]}"#,
+            r#"{"cname": "lz4", "clevel": 5, "shuffle": "shuffle"}"#,
+        ] {
+            assert!(serde_json::from_str::<Blosc2NdCodecConfiguration>(json).is_err());
+        }
+    }
+}
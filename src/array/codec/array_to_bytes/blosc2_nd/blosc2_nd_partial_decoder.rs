@@ -0,0 +1,131 @@
+use crate::{
+    array::{
+        codec::{
+            array_to_bytes::bytes::reverse_endianness, ArrayPartialDecoderTraits, ArraySubset,
+            BytesPartialDecoderTraits, CodecError, CodecOptions,
+        },
+        ChunkRepresentation, MaybeBytes,
+    },
+    array_subset::IncompatibleArraySubsetAndShapeError,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::{AsyncArrayPartialDecoderTraits, AsyncBytesPartialDecoderTraits};
+
+use super::{blosc2_nd_decode_subset, blosc2_nd_open};
+
+/// Decode the `decoded_regions` of a `blosc2_nd` encoded value, only decompressing the blocks intersecting each region.
+fn do_partial_decode(
+    encoded_value: MaybeBytes,
+    decoded_regions: &[ArraySubset],
+    decoded_representation: &ChunkRepresentation,
+) -> Result<Vec<Vec<u8>>, CodecError> {
+    let chunk_shape = decoded_representation.shape_u64();
+    for array_subset in decoded_regions {
+        if !array_subset.inbounds(&chunk_shape) {
+            return Err(IncompatibleArraySubsetAndShapeError::from((
+                array_subset.clone(),
+                chunk_shape,
+            ))
+            .into());
+        }
+    }
+
+    let Some(encoded_value) = encoded_value else {
+        return Ok(decoded_regions
+            .iter()
+            .map(|array_subset| {
+                decoded_representation
+                    .fill_value()
+                    .as_ne_bytes()
+                    .repeat(array_subset.num_elements_usize())
+            })
+            .collect());
+    };
+
+    let element_size = decoded_representation.element_size();
+    let array = blosc2_nd_open(&encoded_value, &chunk_shape, element_size)
+        .map_err(|err| CodecError::Other(err.to_string()))?;
+    let mut decoded_bytes = Vec::with_capacity(decoded_regions.len());
+    for array_subset in decoded_regions {
+        let mut bytes_subset = blosc2_nd_decode_subset(&array, array_subset, element_size)
+            .map_err(|err| CodecError::Other(err.to_string()))?;
+        if cfg!(target_endian = "big") {
+            reverse_endianness(&mut bytes_subset, decoded_representation.data_type());
+        }
+        decoded_bytes.push(bytes_subset);
+    }
+    Ok(decoded_bytes)
+}
+
+/// Partial decoder for the `blosc2_nd` codec.
+pub struct Blosc2NdPartialDecoder<'a> {
+    input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+    decoded_representation: ChunkRepresentation,
+}
+
+impl<'a> Blosc2NdPartialDecoder<'a> {
+    /// Create a new partial decoder for the `blosc2_nd` codec.
+    pub fn new(
+        input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+        decoded_representation: ChunkRepresentation,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+        }
+    }
+}
+
+impl ArrayPartialDecoderTraits for Blosc2NdPartialDecoder<'_> {
+    fn element_size(&self) -> usize {
+        self.decoded_representation.element_size()
+    }
+
+    fn partial_decode_opt(
+        &self,
+        decoded_regions: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<u8>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options)?;
+        do_partial_decode(encoded_value, decoded_regions, &self.decoded_representation)
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `blosc2_nd` codec.
+pub struct AsyncBlosc2NdPartialDecoder<'a> {
+    input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+    decoded_representation: ChunkRepresentation,
+}
+
+#[cfg(feature = "async")]
+impl<'a> AsyncBlosc2NdPartialDecoder<'a> {
+    /// Create a new partial decoder for the `blosc2_nd` codec.
+    pub fn new(
+        input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+        decoded_representation: ChunkRepresentation,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncArrayPartialDecoderTraits for AsyncBlosc2NdPartialDecoder<'_> {
+    fn element_size(&self) -> usize {
+        self.decoded_representation.element_size()
+    }
+
+    async fn partial_decode_opt(
+        &self,
+        decoded_regions: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<u8>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options).await?;
+        do_partial_decode(encoded_value, decoded_regions, &self.decoded_representation)
+    }
+}
@@ -89,9 +89,13 @@ const NATIVE_ENDIAN: Endianness = Endianness::Big;
 #[cfg(target_endian = "little")]
 const NATIVE_ENDIAN: Endianness = Endianness::Little;
 
-fn reverse_endianness(v: &mut [u8], data_type: &DataType) {
+pub(crate) fn reverse_endianness(v: &mut [u8], data_type: &DataType) {
     match data_type {
-        DataType::Bool | DataType::Int8 | DataType::UInt8 | DataType::RawBits(_) => {}
+        DataType::Bool
+        | DataType::Int8
+        | DataType::UInt8
+        | DataType::RawBits(_)
+        | DataType::Extension(_) => {}
         DataType::Int16 | DataType::UInt16 | DataType::Float16 | DataType::BFloat16 => {
             let swap = |chunk: &mut [u8]| {
                 let bytes = u16::from_ne_bytes(chunk.try_into().unwrap());
@@ -106,7 +110,12 @@ fn reverse_endianness(v: &mut [u8], data_type: &DataType) {
             };
             v.chunks_exact_mut(4).for_each(swap);
         }
-        DataType::Int64 | DataType::UInt64 | DataType::Float64 | DataType::Complex128 => {
+        DataType::Int64
+        | DataType::UInt64
+        | DataType::Float64
+        | DataType::Complex128
+        | DataType::DateTime64(_)
+        | DataType::TimeDelta64(_) => {
             let swap = |chunk: &mut [u8]| {
                 let bytes = u64::from_ne_bytes(chunk.try_into().unwrap());
                 chunk.copy_from_slice(bytes.swap_bytes().to_ne_bytes().as_slice());
@@ -123,7 +132,7 @@ mod tests {
     use crate::{
         array::{
             codec::{ArrayCodecTraits, ArrayToBytesCodecTraits, CodecOptions, CodecTraits},
-            ChunkRepresentation, ChunkShape, FillValue,
+            ArrayView, ChunkRepresentation, ChunkShape, FillValue,
         },
         array_subset::ArraySubset,
     };
@@ -183,7 +192,11 @@ mod tests {
             &CodecOptions::default(),
         )?;
         let decoded = codec
-            .decode(encoded, &chunk_representation, &CodecOptions::default())
+            .decode(
+                encoded.into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
             .unwrap();
         assert_eq!(bytes, decoded);
         Ok(())
@@ -299,6 +312,76 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn codec_bytes_decode_passthrough() {
+        let chunk_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(4).unwrap(); 2],
+            DataType::UInt16,
+            FillValue::from(0u16),
+        )
+        .unwrap();
+        let codec = BytesCodec::new(Some(NATIVE_ENDIAN));
+        assert!(codec.is_passthrough(&chunk_representation));
+        let non_native = match NATIVE_ENDIAN {
+            Endianness::Little => Endianness::Big,
+            Endianness::Big => Endianness::Little,
+        };
+        assert!(!BytesCodec::new(Some(non_native)).is_passthrough(&chunk_representation));
+        assert!(!BytesCodec::new(None).is_passthrough(&chunk_representation));
+
+        // The input buffer is reused
+        let bytes: Vec<u8> = (0..32).collect();
+        let ptr = bytes.as_ptr();
+        let decoded = codec
+            .decode(
+                bytes.clone().into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(decoded, bytes);
+        let decoded = codec
+            .decode(
+                bytes.into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(decoded.as_ptr(), ptr);
+
+        // The encoded size is validated
+        assert!(codec
+            .decode(
+                vec![0u8; 3].into(),
+                &chunk_representation,
+                &CodecOptions::default()
+            )
+            .is_err());
+
+        // Decoding into an array view
+        let encoded: Vec<u8> = (0..32).collect();
+        let mut output = vec![0u8; 6 * 6 * 2];
+        {
+            let array_view = ArrayView::new(
+                &mut output,
+                &[6, 6],
+                ArraySubset::new_with_ranges(&[1..5, 2..6]),
+            )
+            .unwrap();
+            codec
+                .decode_into_array_view(
+                    &encoded,
+                    &chunk_representation,
+                    &array_view,
+                    &CodecOptions::default(),
+                )
+                .unwrap();
+        }
+        assert_eq!(&output[16..24], &encoded[0..8]);
+        assert_eq!(&output[52..60], &encoded[24..32]);
+        assert_eq!(&output[0..16], &[0u8; 16]);
+    }
+
     #[test]
     fn codec_bytes_partial_decode() {
         let chunk_shape: ChunkShape = vec![4, 4].try_into().unwrap();
@@ -1,14 +1,18 @@
 // Note: No validation that this codec is created *without* a specified endianness for multi-byte data types.
 
+use bytes::Bytes;
+
 use crate::{
     array::{
         codec::{
-            ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayToBytesCodecTraits,
-            BytesPartialDecoderTraits, CodecError, CodecOptions, CodecTraits,
-            RecommendedConcurrency,
+            copy_into_array_view, ArrayCodecTraits, ArrayPartialDecoderTraits,
+            ArrayToBytesCodecTraits, BytesPartialDecoderTraits, CodecError, CodecOptions,
+            CodecTraits, RecommendedConcurrency,
         },
-        BytesRepresentation, ChunkRepresentation,
+        ArrayView, BytesRepresentation, ChunkRepresentation,
     },
+    array_subset::{ArraySubset, IncompatibleArraySubsetAndShapeError},
+    byte_range::ByteRange,
     metadata::Metadata,
 };
 
@@ -135,11 +139,45 @@ impl ArrayCodecTraits for BytesCodec {
 
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         decoded_representation: &ChunkRepresentation,
         _options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
-        self.do_encode_or_decode(encoded_value, decoded_representation)
+        if self.is_passthrough(decoded_representation) {
+            // The buffer of a uniquely owned `Bytes` is reused without copying
+            if encoded_value.len() as u64 != decoded_representation.size() {
+                return Err(CodecError::UnexpectedChunkDecodedSize(
+                    encoded_value.len(),
+                    decoded_representation.size(),
+                ));
+            }
+            Ok(Vec::from(encoded_value))
+        } else {
+            self.do_encode_or_decode(Vec::from(encoded_value), decoded_representation)
+        }
+    }
+
+    fn decode_into_array_view(
+        &self,
+        encoded_value: &[u8],
+        decoded_representation: &ChunkRepresentation,
+        array_view: &ArrayView,
+        options: &CodecOptions,
+    ) -> Result<(), CodecError> {
+        if self.is_passthrough(decoded_representation) {
+            copy_into_array_view(encoded_value, decoded_representation, array_view)
+        } else {
+            let decoded_value = self.decode(
+                Bytes::copy_from_slice(encoded_value),
+                decoded_representation,
+                options,
+            )?;
+            copy_into_array_view(&decoded_value, decoded_representation, array_view)
+        }
+    }
+
+    fn is_passthrough(&self, decoded_representation: &ChunkRepresentation) -> bool {
+        decoded_representation.element_size() == 1 || self.endian.is_some_and(Endianness::is_native)
     }
 }
 
@@ -182,4 +220,55 @@ impl ArrayToBytesCodecTraits for BytesCodec {
             decoded_representation.num_elements() * decoded_representation.element_size() as u64,
         ))
     }
+
+    fn partial_decode_byte_ranges(
+        &self,
+        _input_handle: &dyn BytesPartialDecoderTraits,
+        decoded_representation: &ChunkRepresentation,
+        array_subsets: &[ArraySubset],
+        _options: &CodecOptions,
+    ) -> Result<Option<Vec<ByteRange>>, CodecError> {
+        Ok(Some(Self::byte_ranges(
+            decoded_representation,
+            array_subsets,
+        )?))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decode_byte_ranges(
+        &self,
+        _input_handle: &dyn AsyncBytesPartialDecoderTraits,
+        decoded_representation: &ChunkRepresentation,
+        array_subsets: &[ArraySubset],
+        _options: &CodecOptions,
+    ) -> Result<Option<Vec<ByteRange>>, CodecError> {
+        Ok(Some(Self::byte_ranges(
+            decoded_representation,
+            array_subsets,
+        )?))
+    }
+}
+
+impl BytesCodec {
+    /// Return the byte ranges of the encoded chunk holding the elements of `array_subsets`.
+    fn byte_ranges(
+        decoded_representation: &ChunkRepresentation,
+        array_subsets: &[ArraySubset],
+    ) -> Result<Vec<ByteRange>, CodecError> {
+        let chunk_shape = decoded_representation.shape_u64();
+        let mut byte_ranges = Vec::new();
+        for array_subset in array_subsets {
+            byte_ranges.extend(
+                array_subset
+                    .byte_ranges(&chunk_shape, decoded_representation.element_size())
+                    .map_err(|_| {
+                        IncompatibleArraySubsetAndShapeError::from((
+                            array_subset.clone(),
+                            chunk_shape.clone(),
+                        ))
+                    })?,
+            );
+        }
+        Ok(byte_ranges)
+    }
 }
@@ -1,16 +1,20 @@
 //! An array to bytes codec formed by joining an array to array sequence, array to bytes, and bytes to bytes sequence of codecs.
 
+use bytes::Bytes;
+
 use crate::{
     array::{
         codec::{
             ArrayCodecTraits, ArrayPartialDecoderCache, ArrayPartialDecoderTraits,
             ArrayToArrayCodecTraits, ArrayToBytesCodecTraits, BytesPartialDecoderCache,
             BytesPartialDecoderTraits, BytesToBytesCodecTraits, Codec, CodecError, CodecOptions,
-            CodecTraits,
+            CodecTraits, EncodedSegments,
         },
         concurrency::RecommendedConcurrency,
         ArrayView, BytesRepresentation, ChunkRepresentation,
     },
+    array_subset::ArraySubset,
+    byte_range::ByteRange,
     metadata::Metadata,
     plugin::PluginCreateError,
 };
@@ -371,6 +375,84 @@ impl ArrayToBytesCodecTraits for CodecChain {
 
         Ok(bytes_representation)
     }
+
+    /// Returns [`None`] unless the codec chain is only composed of an array to bytes codec.
+    ///
+    /// Array to array and bytes to bytes codecs change the mapping between decoded elements and encoded bytes.
+    fn partial_decode_byte_ranges(
+        &self,
+        input_handle: &dyn BytesPartialDecoderTraits,
+        decoded_representation: &ChunkRepresentation,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<ByteRange>>, CodecError> {
+        if self.array_to_array.is_empty() && self.bytes_to_bytes.is_empty() {
+            self.array_to_bytes.partial_decode_byte_ranges(
+                input_handle,
+                decoded_representation,
+                array_subsets,
+                options,
+            )
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decode_byte_ranges(
+        &self,
+        input_handle: &dyn AsyncBytesPartialDecoderTraits,
+        decoded_representation: &ChunkRepresentation,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<ByteRange>>, CodecError> {
+        if self.array_to_array.is_empty() && self.bytes_to_bytes.is_empty() {
+            self.array_to_bytes
+                .async_partial_decode_byte_ranges(
+                    input_handle,
+                    decoded_representation,
+                    array_subsets,
+                    options,
+                )
+                .await
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the segments of the array to bytes codec if there are no bytes to bytes codecs, otherwise the encoded chunk is a single segment.
+    ///
+    /// Bytes to bytes codecs encode their entire input at once.
+    fn encode_segments<'a>(
+        &'a self,
+        decoded_value: Vec<u8>,
+        decoded_representation: &ChunkRepresentation,
+        options: &CodecOptions,
+    ) -> Result<EncodedSegments<'a>, CodecError> {
+        if !self.bytes_to_bytes.is_empty() {
+            return Ok(Box::new(std::iter::once(self.encode(
+                decoded_value,
+                decoded_representation,
+                options,
+            ))));
+        }
+
+        if decoded_value.len() as u64 != decoded_representation.size() {
+            return Err(CodecError::UnexpectedChunkDecodedSize(
+                decoded_value.len(),
+                decoded_representation.size(),
+            ));
+        }
+
+        let mut decoded_representation = decoded_representation.clone();
+        let mut value = decoded_value;
+        for codec in &self.array_to_array {
+            value = codec.encode(value, &decoded_representation, options)?;
+            decoded_representation = codec.compute_encoded_size(&decoded_representation)?;
+        }
+        self.array_to_bytes
+            .encode_segments(value, &decoded_representation, options)
+    }
 }
 
 impl ArrayCodecTraits for CodecChain {
@@ -420,6 +502,10 @@ impl ArrayCodecTraits for CodecChain {
         Ok(recommended_concurrency)
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all, fields(decoded_size = decoded_value.len()))
+    )]
     fn encode(
         &self,
         decoded_value: Vec<u8>,
@@ -456,12 +542,18 @@ impl ArrayCodecTraits for CodecChain {
             decoded_representation = codec.compute_encoded_size(&decoded_representation);
         }
 
+        #[cfg(feature = "tracing")]
+        tracing::trace!(encoded_size = value.len(), "encoded chunk");
         Ok(value)
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all, fields(encoded_size = encoded_value.len()))
+    )]
     fn decode(
         &self,
-        mut encoded_value: Vec<u8>,
+        mut encoded_value: Bytes,
         decoded_representation: &ChunkRepresentation,
         options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
@@ -475,11 +567,13 @@ impl ArrayCodecTraits for CodecChain {
             self.bytes_to_bytes.iter().rev(),
             bytes_representations.iter().rev().skip(1),
         ) {
-            encoded_value = codec.decode(encoded_value, bytes_representation, options)?;
+            encoded_value = codec
+                .decode(encoded_value, bytes_representation, options)?
+                .into();
         }
 
         // bytes->array
-        encoded_value = self.array_to_bytes.decode(
+        let mut decoded_value = self.array_to_bytes.decode(
             encoded_value,
             array_representations.last().unwrap(),
             options,
@@ -490,19 +584,25 @@ impl ArrayCodecTraits for CodecChain {
             self.array_to_array.iter().rev(),
             array_representations.iter().rev().skip(1),
         ) {
-            encoded_value = codec.decode(encoded_value, array_representation, options)?;
+            decoded_value = codec.decode(decoded_value.into(), array_representation, options)?;
         }
 
-        if encoded_value.len() as u64 != decoded_representation.size() {
+        if decoded_value.len() as u64 != decoded_representation.size() {
             return Err(CodecError::UnexpectedChunkDecodedSize(
-                encoded_value.len(),
+                decoded_value.len(),
                 decoded_representation.size(),
             ));
         }
 
-        Ok(encoded_value)
+        #[cfg(feature = "tracing")]
+        tracing::trace!(decoded_size = decoded_value.len(), "decoded chunk");
+        Ok(decoded_value)
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all, fields(encoded_size = encoded_value.len()))
+    )]
     fn decode_into_array_view(
         &self,
         encoded_value: &[u8],
@@ -527,14 +627,16 @@ impl ArrayCodecTraits for CodecChain {
         }
 
         // Default path
-        let mut encoded_value = encoded_value.to_vec();
+        let mut encoded_value = Bytes::copy_from_slice(encoded_value);
 
         // bytes->bytes
         for (codec, bytes_representation) in std::iter::zip(
             self.bytes_to_bytes.iter().rev(),
             bytes_representations.iter().rev().skip(1),
         ) {
-            encoded_value = codec.decode(encoded_value, bytes_representation, options)?;
+            encoded_value = codec
+                .decode(encoded_value, bytes_representation, options)?
+                .into();
         }
 
         if self.array_to_array.is_empty() {
@@ -547,50 +649,29 @@ impl ArrayCodecTraits for CodecChain {
             )
         } else {
             // bytes->array
-            encoded_value = self.array_to_bytes.decode(
+            let mut decoded_value = self.array_to_bytes.decode(
                 encoded_value,
                 array_representations.last().unwrap(),
                 options,
             )?;
 
-            // array->array
+            // array->array, except the last array->array codec
             for (codec, array_representation) in std::iter::zip(
-                self.array_to_array.iter().rev(),
-                array_representations.iter().rev().skip(1),
+                self.array_to_array.iter().skip(1).rev(),
+                array_representations.iter().skip(1).rev().skip(1),
             ) {
-                encoded_value = codec.decode(encoded_value, array_representation, options)?;
+                decoded_value =
+                    codec.decode(decoded_value.into(), array_representation, options)?;
             }
 
-            if encoded_value.len() as u64 != decoded_representation.size() {
-                return Err(CodecError::UnexpectedChunkDecodedSize(
-                    encoded_value.len(),
-                    decoded_representation.size(),
-                ));
-            }
-
-            // FIXME: the last array to array can decode into array_view
-            //        Could also identify which filters are passthrough (e.g. bytes if endianness is native/none, transpose in C order, etc.)
-            let decoded_value = encoded_value;
-            let contiguous_indices = unsafe {
-                array_view
-                    .subset()
-                    .contiguous_linearised_indices_unchecked(array_view.array_shape())
-            };
-            let element_size = decoded_representation.element_size();
-            let length = contiguous_indices.contiguous_elements_usize() * element_size;
-            let mut decoded_offset = 0;
-            // FIXME: Par iteration?
-            let output = unsafe { array_view.bytes_mut() };
-            for (array_subset_element_index, _num_elements) in &contiguous_indices {
-                let output_offset =
-                    usize::try_from(array_subset_element_index).unwrap() * element_size;
-                debug_assert!((output_offset + length) <= output.len());
-                debug_assert!((decoded_offset + length) <= decoded_value.len());
-                output[output_offset..output_offset + length]
-                    .copy_from_slice(&decoded_value[decoded_offset..decoded_offset + length]);
-                decoded_offset += length;
-            }
-            Ok(())
+            // The last array->array codec decodes into the array view
+            // FIXME: Could also skip codecs which are passthrough (see ArrayCodecTraits::is_passthrough)
+            self.array_to_array[0].decode_into_array_view(
+                &decoded_value,
+                decoded_representation,
+                array_view,
+                options,
+            )
         }
     }
 }
@@ -715,7 +796,7 @@ mod tests {
             .unwrap();
         let decoded = codec
             .decode(
-                encoded.clone(),
+                encoded.clone().into(),
                 &chunk_representation,
                 &CodecOptions::default(),
             )
@@ -0,0 +1,297 @@
+//! The `n5` array to bytes codec.
+//!
+//! Encodes chunks as [N5](https://github.com/saalfeldlab/n5#file-system-specification) blocks for reading N5 datasets through the [`Array`](crate::array::Array) API.
+//! An N5 block consists of a header holding the block shape, followed by the big endian elements of the block compressed with an optional bytes to bytes codec.
+//! See [`N5CodecConfigurationV1`] for example `JSON` metadata.
+//!
+//! N5 blocks at the edge of a dataset are truncated to the dataset shape.
+//! Truncated blocks are padded with the fill value when decoded, whereas blocks are always encoded with the full chunk shape.
+//!
+//! This codec requires the `n5` feature, which is disabled by default.
+//! It is not part of the Zarr V3 core specification, and is automatically used by arrays created from N5 metadata.
+//! See [`array_metadata_n5_to_v3`](crate::array::array_metadata_n5_to_v3).
+
+mod n5_codec;
+mod n5_configuration;
+mod n5_partial_decoder;
+
+pub use n5_configuration::{N5CodecConfiguration, N5CodecConfigurationV1};
+
+pub use n5_codec::N5Codec;
+
+use crate::{
+    array::{
+        codec::{Codec, CodecError, CodecPlugin},
+        ArrayShape, DataType,
+    },
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+/// The identifier for the `n5` codec.
+pub const IDENTIFIER: &str = "n5";
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_n5, create_codec_n5)
+}
+
+fn is_name_n5(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+}
+
+pub(crate) fn create_codec_n5(metadata: &Metadata) -> Result<Codec, PluginCreateError> {
+    let configuration = if metadata.configuration_is_none_or_empty() {
+        N5CodecConfiguration::V1(N5CodecConfigurationV1::default())
+    } else {
+        metadata
+            .to_configuration()
+            .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?
+    };
+    let codec = Box::new(N5Codec::new_with_configuration(&configuration)?);
+    Ok(Codec::ArrayToBytes(codec))
+}
+
+/// The default block mode.
+const MODE_DEFAULT: u16 = 0;
+
+/// The varlength block mode, where the number of elements follows the block shape in the header.
+const MODE_VARLENGTH: u16 = 1;
+
+/// Validate that `data_type` is supported by the `n5` codec.
+fn validate_data_type(data_type: &DataType) -> Result<(), CodecError> {
+    match data_type {
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64
+        | DataType::Float32
+        | DataType::Float64 => Ok(()),
+        _ => Err(CodecError::UnsupportedDataType(
+            data_type.clone(),
+            IDENTIFIER.to_string(),
+        )),
+    }
+}
+
+/// Encode the header of a block with `chunk_shape`.
+fn encode_header(chunk_shape: &[u64]) -> Result<Vec<u8>, CodecError> {
+    let dimensionality = u16::try_from(chunk_shape.len())
+        .map_err(|_| CodecError::from("n5 blocks support at most 65535 dimensions"))?;
+    let mut header = Vec::with_capacity(4 + 4 * chunk_shape.len());
+    header.extend_from_slice(&MODE_DEFAULT.to_be_bytes());
+    header.extend_from_slice(&dimensionality.to_be_bytes());
+    for size in chunk_shape.iter().rev() {
+        let size = u32::try_from(*size)
+            .map_err(|_| CodecError::from("n5 block dimensions must not exceed u32::MAX"))?;
+        header.extend_from_slice(&size.to_be_bytes());
+    }
+    Ok(header)
+}
+
+/// Decode the header of an encoded block.
+///
+/// Returns the shape of the block with the N5 dimensions reversed and the offset of the block data.
+fn decode_header(encoded: &[u8], dimensionality: usize) -> Result<(ArrayShape, usize), CodecError> {
+    let err = || CodecError::from("n5 block header is invalid");
+    let read_u16 = |offset: usize| -> Result<u16, CodecError> {
+        let bytes = encoded.get(offset..offset + 2).ok_or_else(err)?;
+        Ok(u16::from_be_bytes(bytes.try_into().unwrap()))
+    };
+    let read_u32 = |offset: usize| -> Result<u32, CodecError> {
+        let bytes = encoded.get(offset..offset + 4).ok_or_else(err)?;
+        Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+    };
+
+    let mode = read_u16(0)?;
+    if mode != MODE_DEFAULT && mode != MODE_VARLENGTH {
+        return Err(CodecError::Other(format!(
+            "n5 block mode {mode} is not supported"
+        )));
+    }
+    if usize::from(read_u16(2)?) != dimensionality {
+        return Err(err());
+    }
+    let mut block_shape = (0..dimensionality)
+        .map(|i| read_u32(4 + 4 * i).map(u64::from))
+        .collect::<Result<ArrayShape, _>>()?;
+    block_shape.reverse();
+    let mut offset = 4 + 4 * dimensionality;
+    if mode == MODE_VARLENGTH {
+        let num_elements = u64::from(read_u32(offset)?);
+        if num_elements != block_shape.iter().product::<u64>() {
+            return Err(CodecError::from(
+                "n5 varlength blocks with a number of elements not matching the block shape are not supported",
+            ));
+        }
+        offset += 4;
+    }
+    Ok((block_shape, offset))
+}
+
+/// Reverse the bytes of each element of `bytes` on little endian targets, converting between big endian and native endian.
+fn reverse_endianness_if_little(bytes: &mut [u8], element_size: usize) {
+    if cfg!(target_endian = "little") && element_size > 1 {
+        for element in bytes.chunks_exact_mut(element_size) {
+            element.reverse();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use crate::{
+        array::{
+            codec::{ArrayCodecTraits, ArrayToBytesCodecTraits, CodecOptions, CodecTraits},
+            BytesRepresentation, ChunkRepresentation, FillValue,
+        },
+        array_subset::ArraySubset,
+    };
+
+    use super::*;
+
+    fn chunk_representation() -> ChunkRepresentation {
+        ChunkRepresentation::new(
+            vec![NonZeroU64::new(2).unwrap(), NonZeroU64::new(3).unwrap()],
+            DataType::UInt16,
+            FillValue::from(0u16),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn codec_n5_round_trip() {
+        let chunk_representation = chunk_representation();
+        let elements: Vec<u16> = (0..6).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec = N5Codec::new(None);
+        let encoded = codec
+            .encode(
+                bytes.clone(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(
+            encoded,
+            vec![
+                0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 2, // header
+                0, 0, 0, 1, 0, 2, 0, 3, 0, 4, 0, 5, // data
+            ]
+        );
+        let decoded = codec
+            .decode(
+                encoded.into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn codec_n5_round_trip_gzip() {
+        let chunk_representation = chunk_representation();
+        let elements: Vec<u16> = (0..6).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let configuration: N5CodecConfiguration =
+            serde_json::from_str(r#"{"compression":{"name":"gzip","configuration":{"level":5}}}"#)
+                .unwrap();
+        let codec = N5Codec::new_with_configuration(&configuration).unwrap();
+        assert_eq!(
+            codec.create_metadata().unwrap().configuration().unwrap(),
+            serde_json::to_value(&configuration)
+                .unwrap()
+                .as_object()
+                .unwrap()
+        );
+        let encoded = codec
+            .encode(
+                bytes.clone(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(&encoded[..12], &[0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 2]);
+        let decoded = codec
+            .decode(
+                encoded.into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded);
+    }
+
+    #[test]
+    fn codec_n5_truncated_block() {
+        let chunk_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(2).unwrap(), NonZeroU64::new(3).unwrap()],
+            DataType::UInt16,
+            FillValue::from(9u16),
+        )
+        .unwrap();
+        // A varlength block with N5 shape [2, 1] (zarrs shape [1, 2])
+        let encoded: Vec<u8> = vec![
+            0, 1, 0, 2, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, // header
+            0, 1, 0, 2, // data
+        ];
+        let codec = N5Codec::new(None);
+        let decoded = codec
+            .decode(
+                encoded.clone().into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded: Vec<u16> = crate::array::transmute_from_bytes_vec(decoded);
+        assert_eq!(decoded, vec![1, 2, 9, 9, 9, 9]);
+
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded = partial_decoder
+            .partial_decode(&[ArraySubset::new_with_ranges(&[0..2, 1..2])])
+            .unwrap();
+        let decoded: Vec<u16> = crate::array::transmute_from_bytes_vec(decoded[0].clone());
+        assert_eq!(decoded, vec![2, 9]);
+    }
+
+    #[test]
+    fn codec_n5_invalid() {
+        let chunk_representation = chunk_representation();
+        let codec = N5Codec::new(None);
+        assert!(codec
+            .decode(
+                vec![0, 2, 0, 2, 0, 0, 0, 3, 0, 0, 0, 2].into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .is_err());
+        assert!(codec
+            .decode(
+                vec![0, 0, 0, 2, 0, 0, 0, 3].into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .is_err());
+        assert_eq!(
+            codec.compute_encoded_size(&chunk_representation).unwrap(),
+            BytesRepresentation::FixedSize(24)
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use bytes::Bytes;
+
+use crate::{
+    array::{
+        codec::{
+            ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayToBytesCodecTraits,
+            BytesPartialDecoderTraits, BytesToBytesCodecTraits, Codec, CodecError, CodecOptions,
+            CodecTraits, RecommendedConcurrency,
+        },
+        BytesRepresentation, ChunkRepresentation,
+    },
+    array_subset::ArraySubset,
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::{AsyncArrayPartialDecoderTraits, AsyncBytesPartialDecoderTraits};
+
+use super::{
+    decode_header, encode_header, n5_partial_decoder, reverse_endianness_if_little,
+    validate_data_type, N5CodecConfiguration, N5CodecConfigurationV1, IDENTIFIER,
+};
+
+/// An `n5` codec implementation.
+#[derive(Debug, Clone)]
+pub struct N5Codec {
+    compression: Option<Box<dyn BytesToBytesCodecTraits>>,
+}
+
+impl N5Codec {
+    /// Create a new `n5` codec with an optional `compression` codec.
+    #[must_use]
+    pub fn new(compression: Option<Box<dyn BytesToBytesCodecTraits>>) -> Self {
+        Self { compression }
+    }
+
+    /// Create a new `n5` codec from configuration.
+    ///
+    /// # Errors
+    /// Returns a [`PluginCreateError`] if the compression codec is not supported or is not a bytes to bytes codec.
+    pub fn new_with_configuration(
+        configuration: &N5CodecConfiguration,
+    ) -> Result<Self, PluginCreateError> {
+        let N5CodecConfiguration::V1(configuration) = configuration;
+        let compression = match &configuration.compression {
+            Some(metadata) => match Codec::from_metadata(metadata)? {
+                Codec::BytesToBytes(codec) => Some(codec),
+                _ => {
+                    return Err(PluginMetadataInvalidError::new(
+                        IDENTIFIER,
+                        "codec",
+                        metadata.clone(),
+                    )
+                    .into())
+                }
+            },
+            None => None,
+        };
+        Ok(Self::new(compression))
+    }
+}
+
+impl CodecTraits for N5Codec {
+    fn create_metadata(&self) -> Option<Metadata> {
+        let configuration = N5CodecConfigurationV1 {
+            compression: self
+                .compression
+                .as_ref()
+                .and_then(|compression| compression.create_metadata()),
+        };
+        Some(Metadata::new_with_serializable_configuration(IDENTIFIER, &configuration).unwrap())
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        true
+    }
+}
+
+impl ArrayCodecTraits for N5Codec {
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &ChunkRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+
+    fn encode(
+        &self,
+        mut decoded_value: Vec<u8>,
+        decoded_representation: &ChunkRepresentation,
+        options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        validate_data_type(decoded_representation.data_type())?;
+        if decoded_value.len() as u64 != decoded_representation.size() {
+            return Err(CodecError::UnexpectedChunkDecodedSize(
+                decoded_value.len(),
+                decoded_representation.size(),
+            ));
+        }
+        let mut encoded = encode_header(&decoded_representation.shape_u64())?;
+        reverse_endianness_if_little(&mut decoded_value, decoded_representation.element_size());
+        match &self.compression {
+            Some(compression) => encoded.extend(compression.encode(decoded_value, options)?),
+            None => encoded.extend(decoded_value),
+        }
+        Ok(encoded)
+    }
+
+    fn decode(
+        &self,
+        encoded_value: Bytes,
+        decoded_representation: &ChunkRepresentation,
+        options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        validate_data_type(decoded_representation.data_type())?;
+        let (block_shape, offset) =
+            decode_header(&encoded_value, decoded_representation.dimensionality())?;
+        let element_size = decoded_representation.element_size();
+        let block_size = block_shape.iter().product::<u64>() * element_size as u64;
+        let encoded_block = encoded_value.slice(offset..);
+        let mut block = match &self.compression {
+            Some(compression) => compression.decode(
+                encoded_block,
+                &BytesRepresentation::FixedSize(block_size),
+                options,
+            )?,
+            None => encoded_block.to_vec(),
+        };
+        if block.len() as u64 != block_size {
+            return Err(CodecError::UnexpectedChunkDecodedSize(
+                block.len(),
+                block_size,
+            ));
+        }
+        reverse_endianness_if_little(&mut block, element_size);
+
+        let chunk_shape = decoded_representation.shape_u64();
+        if block_shape == chunk_shape {
+            return Ok(block);
+        }
+
+        // The block is truncated (or extends) beyond the chunk, so copy the overlapping region into a chunk filled with the fill value
+        let mut decoded = decoded_representation
+            .fill_value()
+            .as_ne_bytes()
+            .repeat(decoded_representation.num_elements_usize());
+        let overlap_shape: Vec<u64> = std::iter::zip(&block_shape, &chunk_shape)
+            .map(|(block, chunk)| std::cmp::min(*block, *chunk))
+            .collect();
+        let overlap = ArraySubset::new_with_shape(overlap_shape);
+        let overlap_bytes = overlap
+            .extract_bytes(&block, &block_shape, element_size)
+            .map_err(|err| CodecError::Other(err.to_string()))?;
+        overlap
+            .store_bytes(&overlap_bytes, &mut decoded, &chunk_shape, element_size)
+            .map_err(|err| CodecError::Other(err.to_string()))?;
+        Ok(decoded)
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl ArrayToBytesCodecTraits for N5Codec {
+    fn partial_decoder<'a>(
+        &self,
+        input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn ArrayPartialDecoderTraits + 'a>, CodecError> {
+        Ok(Box::new(n5_partial_decoder::N5PartialDecoder::new(
+            input_handle,
+            decoded_representation.clone(),
+            self.clone(),
+        )))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder<'a>(
+        &'a self,
+        input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn AsyncArrayPartialDecoderTraits + 'a>, CodecError> {
+        Ok(Box::new(n5_partial_decoder::AsyncN5PartialDecoder::new(
+            input_handle,
+            decoded_representation.clone(),
+            self.clone(),
+        )))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        decoded_representation: &ChunkRepresentation,
+    ) -> Result<BytesRepresentation, CodecError> {
+        let header_size = 4 + 4 * decoded_representation.dimensionality() as u64;
+        let data_size = BytesRepresentation::FixedSize(decoded_representation.size());
+        let data_size = match &self.compression {
+            Some(compression) => compression.compute_encoded_size(&data_size),
+            None => data_size,
+        };
+        Ok(match data_size {
+            BytesRepresentation::FixedSize(size) => {
+                BytesRepresentation::FixedSize(header_size + size)
+            }
+            BytesRepresentation::BoundedSize(size) => {
+                BytesRepresentation::BoundedSize(header_size + size)
+            }
+            BytesRepresentation::UnboundedSize => BytesRepresentation::UnboundedSize,
+        })
+    }
+}
@@ -0,0 +1,62 @@
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+use crate::metadata::Metadata;
+
+/// A wrapper to handle various versions of `n5` codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum N5CodecConfiguration {
+    /// Version 1.0 draft.
+    V1(N5CodecConfigurationV1),
+}
+
+/// Configuration parameters for the `n5` codec (version 1.0 draft).
+///
+/// ### Example: encode with gzip compression
+/// ```rust
+/// # let JSON = r#"
+/// {
+///     "compression": {
+///         "name": "gzip",
+///         "configuration": {
+///             "level": 6
+///         }
+///     }
+/// }
+/// # "#;
+/// # let configuration: zarrs::array::codec::N5CodecConfigurationV1 = serde_json::from_str(JSON).unwrap();
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Display, Default)]
+#[serde(deny_unknown_fields)]
+#[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
+pub struct N5CodecConfigurationV1 {
+    /// The metadata of the bytes to bytes codec compressing the block data, or null if the block data is uncompressed (the N5 `raw` compression).
+    #[serde(default)]
+    pub compression: Option<Metadata>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec_n5_configuration_valid() {
+        assert_eq!(
+            serde_json::from_str::<N5CodecConfiguration>("{}").unwrap(),
+            N5CodecConfiguration::V1(N5CodecConfigurationV1::default())
+        );
+        serde_json::from_str::<N5CodecConfiguration>(
+            r#"{"compression":{"name":"zstd","configuration":{"level":3,"checksum":false}}}"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn codec_n5_configuration_invalid() {
+        assert!(
+            serde_json::from_str::<N5CodecConfiguration>(r#"{"compression":null,"mode":0}"#)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    array::{
+        codec::{
+            ArrayCodecTraits, ArrayPartialDecoderTraits, ArraySubset, BytesPartialDecoderTraits,
+            CodecError, CodecOptions,
+        },
+        ChunkRepresentation, MaybeBytes,
+    },
+    array_subset::IncompatibleArraySubsetAndShapeError,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::{AsyncArrayPartialDecoderTraits, AsyncBytesPartialDecoderTraits};
+
+use super::N5Codec;
+
+/// Partial decoder for the `n5` codec.
+pub struct N5PartialDecoder<'a> {
+    input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+    decoded_representation: ChunkRepresentation,
+    codec: N5Codec,
+}
+
+impl<'a> N5PartialDecoder<'a> {
+    /// Create a new partial decoder for the `n5` codec.
+    pub fn new(
+        input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+        decoded_representation: ChunkRepresentation,
+        codec: N5Codec,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+            codec,
+        }
+    }
+}
+
+fn do_partial_decode(
+    codec: &N5Codec,
+    encoded: MaybeBytes,
+    decoded_regions: &[ArraySubset],
+    decoded_representation: &ChunkRepresentation,
+    options: &CodecOptions,
+) -> Result<Vec<Vec<u8>>, CodecError> {
+    let mut decoded_bytes = Vec::with_capacity(decoded_regions.len());
+    match encoded {
+        None => {
+            for array_subset in decoded_regions {
+                let bytes_subset = decoded_representation
+                    .fill_value()
+                    .as_ne_bytes()
+                    .repeat(array_subset.num_elements_usize());
+                decoded_bytes.push(bytes_subset);
+            }
+        }
+        Some(encoded) => {
+            let decoded_chunk = codec.decode(encoded, decoded_representation, options)?;
+            let chunk_shape = decoded_representation.shape_u64();
+            for array_subset in decoded_regions {
+                let bytes_subset = array_subset
+                    .extract_bytes(
+                        &decoded_chunk,
+                        &chunk_shape,
+                        decoded_representation.element_size(),
+                    )
+                    .map_err(|_| {
+                        IncompatibleArraySubsetAndShapeError::from((
+                            array_subset.clone(),
+                            chunk_shape.clone(),
+                        ))
+                    })?;
+                decoded_bytes.push(bytes_subset);
+            }
+        }
+    }
+    Ok(decoded_bytes)
+}
+
+impl ArrayPartialDecoderTraits for N5PartialDecoder<'_> {
+    fn element_size(&self) -> usize {
+        self.decoded_representation.element_size()
+    }
+
+    fn partial_decode_opt(
+        &self,
+        decoded_regions: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<u8>>, CodecError> {
+        let encoded = self.input_handle.decode(options)?;
+        do_partial_decode(
+            &self.codec,
+            encoded,
+            decoded_regions,
+            &self.decoded_representation,
+            options,
+        )
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `n5` codec.
+pub struct AsyncN5PartialDecoder<'a> {
+    input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+    decoded_representation: ChunkRepresentation,
+    codec: N5Codec,
+}
+
+#[cfg(feature = "async")]
+impl<'a> AsyncN5PartialDecoder<'a> {
+    /// Create a new asynchronous partial decoder for the `n5` codec.
+    pub fn new(
+        input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+        decoded_representation: ChunkRepresentation,
+        codec: N5Codec,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+            codec,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncArrayPartialDecoderTraits for AsyncN5PartialDecoder<'_> {
+    fn element_size(&self) -> usize {
+        self.decoded_representation.element_size()
+    }
+
+    async fn partial_decode_opt(
+        &self,
+        decoded_regions: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<u8>>, CodecError> {
+        let encoded = self.input_handle.decode(options).await?;
+        do_partial_decode(
+            &self.codec,
+            encoded,
+            decoded_regions,
+            &self.decoded_representation,
+            options,
+        )
+    }
+}
@@ -0,0 +1,262 @@
+//! The `packbits` array to bytes codec.
+//!
+//! Packs the elements of a `bool` array into single bits, reducing the encoded size by a factor of 8 relative to the `bytes` codec.
+//! Elements are packed in lexicographical order from the least significant bit to the most significant bit of each byte.
+//!
+//! The number of padding bits in the last byte (0-7) can optionally be encoded in an additional byte at the start or end of the encoded value.
+//! See [`PackBitsPaddingEncoding`].
+//!
+//! This codec requires the `packbits` feature, which is disabled by default.
+//! It is an experimental codec that is not part of the Zarr V3 core specification.
+
+mod packbits_codec;
+mod packbits_configuration;
+mod packbits_partial_decoder;
+
+pub use packbits_configuration::{PackBitsCodecConfiguration, PackBitsCodecConfigurationV1};
+
+pub use packbits_codec::PackBitsCodec;
+
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    array::codec::{Codec, CodecError, CodecPlugin},
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+/// The identifier for the `packbits` codec.
+pub const IDENTIFIER: &str = "packbits";
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_packbits, create_codec_packbits)
+}
+
+fn is_name_packbits(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+}
+
+pub(crate) fn create_codec_packbits(metadata: &Metadata) -> Result<Codec, PluginCreateError> {
+    let configuration = if metadata.configuration_is_none_or_empty() {
+        PackBitsCodecConfiguration::default()
+    } else {
+        metadata
+            .to_configuration()
+            .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?
+    };
+    let codec = Box::new(PackBitsCodec::new_with_configuration(&configuration));
+    Ok(Codec::ArrayToBytes(codec))
+}
+
+/// The encoding of the number of padding bits in the last byte of `packbits` encoded values.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug, Display, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PackBitsPaddingEncoding {
+    /// The number of padding bits is not encoded.
+    #[default]
+    None,
+    /// The number of padding bits is encoded in a byte at the start of the encoded value.
+    StartByte,
+    /// The number of padding bits is encoded in a byte at the end of the encoded value.
+    EndByte,
+}
+
+/// Return the number of bytes holding packed bits and the number of padding bits for `num_elements` elements.
+const fn packed_size(num_elements: u64) -> (u64, u8) {
+    let num_bytes = (num_elements + 7) / 8;
+    #[allow(clippy::cast_possible_truncation)]
+    let padding_bits = (num_bytes * 8 - num_elements) as u8;
+    (num_bytes, padding_bits)
+}
+
+/// Pack `decoded` elements into bits, treating any non-zero element as `true`.
+fn pack_bits(decoded: &[u8]) -> Vec<u8> {
+    decoded
+        .chunks(8)
+        .map(|elements| {
+            elements
+                .iter()
+                .enumerate()
+                .fold(0u8, |byte, (bit, &element)| {
+                    byte | (u8::from(element != 0) << bit)
+                })
+        })
+        .collect()
+}
+
+/// Unpack `num_elements` elements from `packed` starting at `first_bit`, and append them to `decoded`.
+fn unpack_bits(packed: &[u8], first_bit: usize, num_elements: usize, decoded: &mut Vec<u8>) {
+    decoded.extend(
+        (first_bit..first_bit + num_elements).map(|bit| (packed[bit / 8] >> (bit % 8)) & 1),
+    );
+}
+
+/// Validate the padding byte of a `packbits` encoded value.
+fn validate_padding_byte(padding_byte: u8, padding_bits: u8) -> Result<(), CodecError> {
+    if padding_byte == padding_bits {
+        Ok(())
+    } else {
+        Err(CodecError::Other(format!(
+            "packbits encoded value has {padding_byte} padding bits, expected {padding_bits}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use crate::{
+        array::{
+            codec::{ArrayCodecTraits, ArrayToBytesCodecTraits, CodecOptions},
+            BytesRepresentation, ChunkRepresentation, DataType, FillValue,
+        },
+        array_subset::ArraySubset,
+    };
+
+    use super::*;
+
+    fn chunk_representation() -> ChunkRepresentation {
+        ChunkRepresentation::new(
+            vec![NonZeroU64::new(3).unwrap(), NonZeroU64::new(5).unwrap()],
+            DataType::Bool,
+            FillValue::from(false),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn codec_packbits_configuration() {
+        let configuration: PackBitsCodecConfiguration =
+            serde_json::from_str(r#"{"padding_encoding":"start_byte"}"#).unwrap();
+        let codec = PackBitsCodec::new_with_configuration(&configuration);
+        assert_eq!(codec.padding_encoding(), PackBitsPaddingEncoding::StartByte);
+        assert!(serde_json::from_str::<PackBitsCodecConfiguration>(
+            r#"{"padding_encoding":"middle_byte"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn codec_packbits_round_trip() {
+        let chunk_representation = chunk_representation();
+        let bytes: Vec<u8> = (0..15).map(|i| u8::from(i % 3 == 0)).collect();
+        for (padding_encoding, expected_size) in [
+            (PackBitsPaddingEncoding::None, 2),
+            (PackBitsPaddingEncoding::StartByte, 3),
+            (PackBitsPaddingEncoding::EndByte, 3),
+        ] {
+            let codec = PackBitsCodec::new(padding_encoding);
+            assert_eq!(
+                codec.compute_encoded_size(&chunk_representation).unwrap(),
+                BytesRepresentation::FixedSize(expected_size)
+            );
+            let encoded = codec
+                .encode(
+                    bytes.clone(),
+                    &chunk_representation,
+                    &CodecOptions::default(),
+                )
+                .unwrap();
+            assert_eq!(encoded.len() as u64, expected_size);
+            let decoded = codec
+                .decode(
+                    encoded.into(),
+                    &chunk_representation,
+                    &CodecOptions::default(),
+                )
+                .unwrap();
+            assert_eq!(bytes, decoded);
+        }
+
+        let encoded = PackBitsCodec::new(PackBitsPaddingEncoding::EndByte)
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        assert_eq!(encoded, vec![0b0100_1001, 0b0001_0010, 1]);
+        assert!(PackBitsCodec::new(PackBitsPaddingEncoding::StartByte)
+            .decode(
+                encoded.into(),
+                &chunk_representation,
+                &CodecOptions::default()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn codec_packbits_unsupported_data_type() {
+        let chunk_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(8).unwrap()],
+            DataType::UInt8,
+            FillValue::from(0u8),
+        )
+        .unwrap();
+        assert!(PackBitsCodec::default()
+            .encode(vec![0; 8], &chunk_representation, &CodecOptions::default())
+            .is_err());
+    }
+
+    #[test]
+    fn codec_packbits_partial_decode() {
+        let chunk_representation = chunk_representation();
+        let bytes: Vec<u8> = (0..15).map(|i| u8::from(i % 2 == 0)).collect();
+        let codec = PackBitsCodec::new(PackBitsPaddingEncoding::StartByte);
+        let encoded = codec
+            .encode(
+                bytes.clone(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_regions = [
+            ArraySubset::new_with_ranges(&[1..3, 2..5]),
+            ArraySubset::new_with_ranges(&[0..1, 0..5]),
+        ];
+        let decoded = partial_decoder.partial_decode(&decoded_regions).unwrap();
+        assert_eq!(decoded[0], vec![0, 1, 0, 1, 0, 1]);
+        assert_eq!(decoded[1], vec![1, 0, 1, 0, 1]);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn codec_packbits_async_partial_decode() {
+        let chunk_representation = chunk_representation();
+        let bytes: Vec<u8> = (0..15).map(|i| u8::from(i % 2 == 0)).collect();
+        let codec = PackBitsCodec::new(PackBitsPaddingEncoding::EndByte);
+        let encoded = codec
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .async_partial_decoder(
+                input_handle,
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .await
+            .unwrap();
+        let decoded = partial_decoder
+            .partial_decode(&[ArraySubset::new_with_ranges(&[2..3, 1..4])])
+            .await
+            .unwrap();
+        assert_eq!(decoded[0], vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn packbits_pack_unpack() {
+        let packed = pack_bits(&[1, 0, 2, 0, 0, 0, 0, 1, 1]);
+        assert_eq!(packed, vec![0b1000_0101, 0b0000_0001]);
+        let mut decoded = vec![];
+        unpack_bits(&packed, 2, 7, &mut decoded);
+        assert_eq!(decoded, vec![1, 0, 0, 0, 0, 1, 1]);
+    }
+}
@@ -0,0 +1,200 @@
+use bytes::Bytes;
+
+use crate::{
+    array::{
+        codec::{
+            ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayToBytesCodecTraits,
+            BytesPartialDecoderTraits, CodecError, CodecOptions, CodecTraits,
+            RecommendedConcurrency,
+        },
+        BytesRepresentation, ChunkRepresentation, DataType,
+    },
+    metadata::Metadata,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::{AsyncArrayPartialDecoderTraits, AsyncBytesPartialDecoderTraits};
+
+use super::{
+    pack_bits, packbits_partial_decoder, packed_size, unpack_bits, validate_padding_byte,
+    PackBitsCodecConfiguration, PackBitsCodecConfigurationV1, PackBitsPaddingEncoding, IDENTIFIER,
+};
+
+/// A `packbits` codec implementation.
+#[derive(Debug, Clone, Default)]
+pub struct PackBitsCodec {
+    padding_encoding: PackBitsPaddingEncoding,
+}
+
+impl PackBitsCodec {
+    /// Create a new `packbits` codec.
+    #[must_use]
+    pub const fn new(padding_encoding: PackBitsPaddingEncoding) -> Self {
+        Self { padding_encoding }
+    }
+
+    /// Create a new `packbits` codec from configuration.
+    #[must_use]
+    pub const fn new_with_configuration(configuration: &PackBitsCodecConfiguration) -> Self {
+        let PackBitsCodecConfiguration::V1(configuration) = configuration;
+        Self::new(configuration.padding_encoding)
+    }
+
+    /// Return the padding encoding.
+    #[must_use]
+    pub const fn padding_encoding(&self) -> PackBitsPaddingEncoding {
+        self.padding_encoding
+    }
+}
+
+/// Validate that the data type of `decoded_representation` is supported by the `packbits` codec.
+fn validate_data_type(decoded_representation: &ChunkRepresentation) -> Result<(), CodecError> {
+    match decoded_representation.data_type() {
+        DataType::Bool => Ok(()),
+        data_type => Err(CodecError::UnsupportedDataType(
+            data_type.clone(),
+            IDENTIFIER.to_string(),
+        )),
+    }
+}
+
+impl CodecTraits for PackBitsCodec {
+    fn create_metadata(&self) -> Option<Metadata> {
+        let configuration = PackBitsCodecConfigurationV1::new(self.padding_encoding);
+        Some(Metadata::new_with_serializable_configuration(IDENTIFIER, &configuration).unwrap())
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        false
+    }
+}
+
+impl ArrayCodecTraits for PackBitsCodec {
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &ChunkRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+
+    fn encode(
+        &self,
+        decoded_value: Vec<u8>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        validate_data_type(decoded_representation)?;
+        if decoded_value.len() as u64 != decoded_representation.size() {
+            return Err(CodecError::UnexpectedChunkDecodedSize(
+                decoded_value.len(),
+                decoded_representation.size(),
+            ));
+        }
+
+        let (_, padding_bits) = packed_size(decoded_representation.num_elements());
+        let packed = pack_bits(&decoded_value);
+        Ok(match self.padding_encoding {
+            PackBitsPaddingEncoding::None => packed,
+            PackBitsPaddingEncoding::StartByte => {
+                let mut encoded = Vec::with_capacity(packed.len() + 1);
+                encoded.push(padding_bits);
+                encoded.extend(packed);
+                encoded
+            }
+            PackBitsPaddingEncoding::EndByte => {
+                let mut encoded = packed;
+                encoded.push(padding_bits);
+                encoded
+            }
+        })
+    }
+
+    fn decode(
+        &self,
+        encoded_value: Bytes,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        validate_data_type(decoded_representation)?;
+        let encoded_size = self
+            .compute_encoded_size(decoded_representation)?
+            .size()
+            .unwrap_or_default();
+        if encoded_value.len() as u64 != encoded_size {
+            return Err(CodecError::UnexpectedChunkDecodedSize(
+                encoded_value.len(),
+                encoded_size,
+            ));
+        }
+
+        let (_, padding_bits) = packed_size(decoded_representation.num_elements());
+        let packed = match self.padding_encoding {
+            PackBitsPaddingEncoding::None => &encoded_value[..],
+            PackBitsPaddingEncoding::StartByte => {
+                validate_padding_byte(encoded_value[0], padding_bits)?;
+                &encoded_value[1..]
+            }
+            PackBitsPaddingEncoding::EndByte => {
+                let (padding_byte, packed) = encoded_value.split_last().unwrap(); // safe, the encoded size is non-zero
+                validate_padding_byte(*padding_byte, padding_bits)?;
+                packed
+            }
+        };
+        let num_elements = usize::try_from(decoded_representation.num_elements()).unwrap();
+        let mut decoded = Vec::with_capacity(num_elements);
+        unpack_bits(packed, 0, num_elements, &mut decoded);
+        Ok(decoded)
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl ArrayToBytesCodecTraits for PackBitsCodec {
+    fn partial_decoder<'a>(
+        &self,
+        input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn ArrayPartialDecoderTraits + 'a>, CodecError> {
+        validate_data_type(decoded_representation)?;
+        Ok(Box::new(
+            packbits_partial_decoder::PackBitsPartialDecoder::new(
+                input_handle,
+                decoded_representation.clone(),
+                self.padding_encoding,
+            ),
+        ))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder<'a>(
+        &'a self,
+        input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn AsyncArrayPartialDecoderTraits + 'a>, CodecError> {
+        validate_data_type(decoded_representation)?;
+        Ok(Box::new(
+            packbits_partial_decoder::AsyncPackBitsPartialDecoder::new(
+                input_handle,
+                decoded_representation.clone(),
+                self.padding_encoding,
+            ),
+        ))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        decoded_representation: &ChunkRepresentation,
+    ) -> Result<BytesRepresentation, CodecError> {
+        let (num_bytes, _) = packed_size(decoded_representation.num_elements());
+        let padding_byte = match self.padding_encoding {
+            PackBitsPaddingEncoding::None => 0,
+            PackBitsPaddingEncoding::StartByte | PackBitsPaddingEncoding::EndByte => 1,
+        };
+        Ok(BytesRepresentation::FixedSize(num_bytes + padding_byte))
+    }
+}
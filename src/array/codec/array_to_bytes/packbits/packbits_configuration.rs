@@ -0,0 +1,65 @@
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+use super::PackBitsPaddingEncoding;
+
+/// A wrapper to handle various versions of `packbits` codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum PackBitsCodecConfiguration {
+    /// Version 1.0 draft.
+    V1(PackBitsCodecConfigurationV1),
+}
+
+impl Default for PackBitsCodecConfiguration {
+    fn default() -> Self {
+        Self::V1(PackBitsCodecConfigurationV1::default())
+    }
+}
+
+/// Configuration parameters for the `packbits` codec (version 1.0 draft).
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Debug, Display, Default)]
+#[serde(deny_unknown_fields)]
+#[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
+pub struct PackBitsCodecConfigurationV1 {
+    /// The encoding of the number of padding bits in the last byte.
+    ///
+    /// The default is [`PackBitsPaddingEncoding::None`].
+    #[serde(default)]
+    pub padding_encoding: PackBitsPaddingEncoding,
+}
+
+impl PackBitsCodecConfigurationV1 {
+    /// Create a new `packbits` codec configuration given a [`PackBitsPaddingEncoding`].
+    #[must_use]
+    pub const fn new(padding_encoding: PackBitsPaddingEncoding) -> Self {
+        Self { padding_encoding }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec_packbits_valid_empty() {
+        assert_eq!(
+            serde_json::from_str::<PackBitsCodecConfiguration>("{}").unwrap(),
+            PackBitsCodecConfiguration::default()
+        );
+    }
+
+    #[test]
+    fn codec_packbits_valid() {
+        serde_json::from_str::<PackBitsCodecConfiguration>(r#"{"padding_encoding":"end_byte"}"#)
+            .unwrap();
+    }
+
+    #[test]
+    fn codec_packbits_invalid() {
+        assert!(serde_json::from_str::<PackBitsCodecConfiguration>(
+            r#"{"padding_encoding":"none","first_bit":0}"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use bytes::Bytes;
+
+use crate::{
+    array::{
+        codec::{
+            ArrayPartialDecoderTraits, ArraySubset, BytesPartialDecoderTraits, CodecError,
+            CodecOptions,
+        },
+        ChunkRepresentation,
+    },
+    array_subset::IncompatibleArraySubsetAndShapeError,
+    byte_range::ByteRange,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::{AsyncArrayPartialDecoderTraits, AsyncBytesPartialDecoderTraits};
+
+use super::{unpack_bits, PackBitsPaddingEncoding};
+
+/// Return the byte ranges of the packed bits of each contiguous run of elements in `array_subset`.
+///
+/// Also returns the first bit of each run within its byte range and the number of elements in each run.
+fn packed_byte_ranges(
+    array_subset: &ArraySubset,
+    decoded_representation: &ChunkRepresentation,
+    padding_encoding: PackBitsPaddingEncoding,
+) -> Result<(Vec<ByteRange>, Vec<usize>, usize), CodecError> {
+    let contiguous_indices = array_subset
+        .contiguous_linearised_indices(&decoded_representation.shape_u64())
+        .map_err(|_| {
+            IncompatibleArraySubsetAndShapeError::from((
+                array_subset.clone(),
+                decoded_representation.shape_u64(),
+            ))
+        })?;
+    let offset = match padding_encoding {
+        PackBitsPaddingEncoding::StartByte => 1,
+        PackBitsPaddingEncoding::None | PackBitsPaddingEncoding::EndByte => 0,
+    };
+    let contiguous_elements = contiguous_indices.contiguous_elements();
+    let mut byte_ranges = Vec::new();
+    let mut first_bits = Vec::new();
+    for (index, _) in &contiguous_indices {
+        let byte_start = index / 8;
+        let byte_end = (index + contiguous_elements + 7) / 8;
+        byte_ranges.push(ByteRange::FromStart(
+            offset + byte_start,
+            Some(byte_end - byte_start),
+        ));
+        first_bits.push(usize::try_from(index % 8).unwrap());
+    }
+    Ok((
+        byte_ranges,
+        first_bits,
+        usize::try_from(contiguous_elements).unwrap(),
+    ))
+}
+
+/// Unpack the elements of each contiguous run of elements from its packed bytes.
+fn unpack_runs(packed_runs: &[Bytes], first_bits: &[usize], contiguous_elements: usize) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(packed_runs.len() * contiguous_elements);
+    for (packed, &first_bit) in std::iter::zip(packed_runs, first_bits) {
+        unpack_bits(packed, first_bit, contiguous_elements, &mut decoded);
+    }
+    decoded
+}
+
+/// Partial decoder for the `packbits` codec.
+pub struct PackBitsPartialDecoder<'a> {
+    input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+    decoded_representation: ChunkRepresentation,
+    padding_encoding: PackBitsPaddingEncoding,
+}
+
+impl<'a> PackBitsPartialDecoder<'a> {
+    /// Create a new partial decoder for the `packbits` codec.
+    pub fn new(
+        input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+        decoded_representation: ChunkRepresentation,
+        padding_encoding: PackBitsPaddingEncoding,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+            padding_encoding,
+        }
+    }
+}
+
+impl ArrayPartialDecoderTraits for PackBitsPartialDecoder<'_> {
+    fn element_size(&self) -> usize {
+        self.decoded_representation.element_size()
+    }
+
+    fn partial_decode_opt(
+        &self,
+        decoded_regions: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<u8>>, CodecError> {
+        let mut bytes = Vec::with_capacity(decoded_regions.len());
+        for array_subset in decoded_regions {
+            let (byte_ranges, first_bits, contiguous_elements) = packed_byte_ranges(
+                array_subset,
+                &self.decoded_representation,
+                self.padding_encoding,
+            )?;
+            let bytes_subset = match self.input_handle.partial_decode(&byte_ranges, options)? {
+                Some(packed_runs) => unpack_runs(&packed_runs, &first_bits, contiguous_elements),
+                None => self
+                    .decoded_representation
+                    .fill_value()
+                    .as_ne_bytes()
+                    .repeat(array_subset.num_elements_usize()),
+            };
+            bytes.push(bytes_subset);
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `packbits` codec.
+pub struct AsyncPackBitsPartialDecoder<'a> {
+    input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+    decoded_representation: ChunkRepresentation,
+    padding_encoding: PackBitsPaddingEncoding,
+}
+
+#[cfg(feature = "async")]
+impl<'a> AsyncPackBitsPartialDecoder<'a> {
+    /// Create a new partial decoder for the `packbits` codec.
+    pub fn new(
+        input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+        decoded_representation: ChunkRepresentation,
+        padding_encoding: PackBitsPaddingEncoding,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+            padding_encoding,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncArrayPartialDecoderTraits for AsyncPackBitsPartialDecoder<'_> {
+    fn element_size(&self) -> usize {
+        self.decoded_representation.element_size()
+    }
+
+    async fn partial_decode_opt(
+        &self,
+        decoded_regions: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<Vec<u8>>, CodecError> {
+        let mut bytes = Vec::with_capacity(decoded_regions.len());
+        for array_subset in decoded_regions {
+            let (byte_ranges, first_bits, contiguous_elements) = packed_byte_ranges(
+                array_subset,
+                &self.decoded_representation,
+                self.padding_encoding,
+            )?;
+            let bytes_subset = match self
+                .input_handle
+                .partial_decode(&byte_ranges, options)
+                .await?
+            {
+                Some(packed_runs) => unpack_runs(&packed_runs, &first_bits, contiguous_elements),
+                None => self
+                    .decoded_representation
+                    .fill_value()
+                    .as_ne_bytes()
+                    .repeat(array_subset.num_elements_usize()),
+            };
+            bytes.push(bytes_subset);
+        }
+        Ok(bytes)
+    }
+}
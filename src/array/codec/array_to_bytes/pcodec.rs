@@ -1,12 +1,18 @@
 //! The `pcodec` array to bytes codec.
+//!
+//! The compression level, delta encoding order, and mode (int/float mult) hints can be tuned to trade off compression ratio and speed.
+//! See [`PcodecCodecConfigurationV1`] for example `JSON` metadata.
+//! The [`PcodecCodecBuilder`] can help with creating a [`PcodecCodec`].
 
 mod pcodec_codec;
+mod pcodec_codec_builder;
 mod pcodec_configuration;
 mod pcodec_partial_decoder;
 
 pub use pcodec_configuration::{PcodecCodecConfiguration, PcodecCodecConfigurationV1};
 
 pub use pcodec_codec::PcodecCodec;
+pub use pcodec_codec_builder::PcodecCodecBuilder;
 
 use serde::{Deserialize, Deserializer, Serialize};
 
@@ -194,7 +200,7 @@ mod tests {
 
     use crate::{
         array::{
-            codec::{ArrayCodecTraits, ArrayToBytesCodecTraits, CodecOptions},
+            codec::{ArrayCodecTraits, ArrayToBytesCodecTraits, CodecOptions, CodecTraits},
             transmute_to_bytes_vec, ChunkRepresentation, ChunkShape, DataType, FillValue,
         },
         array_subset::ArraySubset,
@@ -217,6 +223,39 @@ mod tests {
         let _ = PcodecCodec::new_with_configuration(&codec_configuration);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_pcodec_builder() {
+        let codec = PcodecCodecBuilder::new()
+            .level(PcodecCompressionLevel::new(4u8).unwrap())
+            .delta_encoding_order(Some(PcodecDeltaEncodingOrder::new(1u8).unwrap()))
+            .int_mult_spec(false)
+            .float_mult_spec(false)
+            .max_page_n(1024)
+            .build();
+        let configuration: PcodecCodecConfiguration =
+            codec.create_metadata().unwrap().to_configuration().unwrap();
+        assert_eq!(
+            configuration,
+            PcodecCodecConfiguration::V1(PcodecCodecConfigurationV1 {
+                level: PcodecCompressionLevel::new(4u8).unwrap(),
+                delta_encoding_order: Some(PcodecDeltaEncodingOrder::new(1u8).unwrap()),
+                int_mult_spec: false,
+                float_mult_spec: false,
+                max_page_n: 1024,
+            })
+        );
+        codec_pcodec_round_trip_impl(&codec, DataType::Float64, FillValue::from(0f64)).unwrap();
+
+        let configuration: PcodecCodecConfiguration = PcodecCodecBuilder::new()
+            .build()
+            .create_metadata()
+            .unwrap()
+            .to_configuration()
+            .unwrap();
+        assert_eq!(configuration, PcodecCodecConfiguration::default());
+    }
+
     fn codec_pcodec_round_trip_impl(
         codec: &PcodecCodec,
         data_type: DataType,
@@ -233,7 +272,11 @@ mod tests {
             &CodecOptions::default(),
         )?;
         let decoded = codec
-            .decode(encoded, &chunk_representation, &CodecOptions::default())
+            .decode(
+                encoded.into(),
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
             .unwrap();
         assert_eq!(bytes, decoded);
         Ok(())
@@ -1,3 +1,4 @@
+use bytes::Bytes;
 use pco::{ChunkConfig, FloatMultSpec, IntMultSpec, PagingSpec};
 
 use crate::{
@@ -140,14 +141,14 @@ impl ArrayCodecTraits for PcodecCodec {
 
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         decoded_representation: &ChunkRepresentation,
         _options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
         let data_type = decoded_representation.data_type();
         macro_rules! pcodec_decode {
             ( $t:ty ) => {
-                pco::standalone::auto_decompress(encoded_value.as_slice())
+                pco::standalone::auto_decompress(&encoded_value[..])
                     .map(|bytes| transmute_to_bytes_vec::<$t>(bytes))
                     .map_err(|err| CodecError::Other(err.to_string()))
             };
@@ -0,0 +1,73 @@
+use super::{
+    PcodecCodec, PcodecCodecConfiguration, PcodecCodecConfigurationV1, PcodecCompressionLevel,
+    PcodecDeltaEncodingOrder,
+};
+
+/// A [`PcodecCodec`] builder.
+///
+/// By default, the compression level is 8, the delta encoding order is inferred automatically, int and float mult modes are considered, and pages hold up to `1 << 18` values.
+/// This matches the default [`PcodecCodecConfigurationV1`].
+///
+/// Use the methods in the `pcodec` codec builder to change the configuration away from these defaults, and then build the `pcodec` codec with [`build`](PcodecCodecBuilder::build).
+#[derive(Debug, Clone, Default)]
+pub struct PcodecCodecBuilder {
+    configuration: PcodecCodecConfigurationV1,
+}
+
+impl PcodecCodecBuilder {
+    /// Create a new `pcodec` codec builder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the compression level.
+    ///
+    /// Higher levels compress more but take longer.
+    /// If left unmodified, the compression level is 8.
+    pub fn level(&mut self, level: PcodecCompressionLevel) -> &mut Self {
+        self.configuration.level = level;
+        self
+    }
+
+    /// Set the delta encoding order.
+    ///
+    /// If left unmodified or set to [`None`], pcodec will try to infer the optimal delta encoding order for each chunk.
+    pub fn delta_encoding_order(
+        &mut self,
+        delta_encoding_order: Option<PcodecDeltaEncodingOrder>,
+    ) -> &mut Self {
+        self.configuration.delta_encoding_order = delta_encoding_order;
+        self
+    }
+
+    /// Set whether pcodec will consider int mult mode for integer data types.
+    ///
+    /// If left unmodified, int mult mode is considered.
+    pub fn int_mult_spec(&mut self, int_mult_spec: bool) -> &mut Self {
+        self.configuration.int_mult_spec = int_mult_spec;
+        self
+    }
+
+    /// Set whether pcodec will consider float mult mode for float data types.
+    ///
+    /// If left unmodified, float mult mode is considered.
+    pub fn float_mult_spec(&mut self, float_mult_spec: bool) -> &mut Self {
+        self.configuration.float_mult_spec = float_mult_spec;
+        self
+    }
+
+    /// Set the maximum number of values to encode per pcodec page.
+    ///
+    /// If left unmodified, pages hold up to `1 << 18` values.
+    pub fn max_page_n(&mut self, max_page_n: usize) -> &mut Self {
+        self.configuration.max_page_n = max_page_n;
+        self
+    }
+
+    /// Build into a [`PcodecCodec`].
+    #[must_use]
+    pub fn build(&self) -> PcodecCodec {
+        PcodecCodec::new_with_configuration(&PcodecCodecConfiguration::V1(self.configuration))
+    }
+}
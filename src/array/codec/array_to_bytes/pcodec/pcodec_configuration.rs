@@ -18,6 +18,22 @@ impl Default for PcodecCodecConfiguration {
 }
 
 /// Configuration parameters for the `pcodec` codec (version 1.0 draft).
+///
+/// All parameters are optional and fall back to their defaults.
+/// The `int_mult_spec` and `float_mult_spec` parameters control which modes pcodec considers in addition to its classic mode.
+///
+/// ### Example: encode with a compression level of 12, 1st order delta encoding, and classic mode only
+/// ```rust
+/// # let JSON = r#"
+/// {
+///     "level": 12,
+///     "delta_encoding_order": 1,
+///     "int_mult_spec": false,
+///     "float_mult_spec": false
+/// }
+/// # "#;
+/// # let configuration: zarrs::array::codec::array_to_bytes::pcodec::PcodecCodecConfigurationV1 = serde_json::from_str(JSON).unwrap();
+/// ```
 #[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Debug, Display)]
 #[serde(deny_unknown_fields)]
 #[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
@@ -9,20 +9,30 @@
 //!
 //! See [`ShardingCodecConfigurationV1`] for example `JSON` metadata.
 //! The [`ShardingCodecBuilder`] can help with creating a [`ShardingCodec`].
+//!
+//! A [`ShardWriter`] can incrementally write inner chunks to a shard without assembling the entire shard in memory.
+//!
+//! A [`ShardIndex`] holds the byte offset and size of each inner chunk of a shard, and can be read from a store without retrieving any inner chunks.
 
+mod shard_index;
 mod sharding_codec;
 mod sharding_codec_builder;
 mod sharding_configuration;
 mod sharding_partial_decoder;
+mod sharding_writer;
 
 use std::num::NonZeroU64;
 
+use bytes::Bytes;
+
 pub use sharding_configuration::{
     ShardingCodecConfiguration, ShardingCodecConfigurationV1, ShardingIndexLocation,
 };
 
+pub use shard_index::ShardIndex;
 pub use sharding_codec::ShardingCodec;
 pub use sharding_codec_builder::ShardingCodecBuilder;
+pub use sharding_writer::ShardWriter;
 use thiserror::Error;
 
 use crate::{
@@ -30,6 +40,7 @@ use crate::{
         codec::{ArrayToBytesCodecTraits, Codec, CodecError, CodecOptions, CodecPlugin},
         BytesRepresentation, ChunkRepresentation, ChunkShape, DataType, FillValue,
     },
+    byte_range::ByteRange,
     metadata::Metadata,
     plugin::{PluginCreateError, PluginMetadataInvalidError},
 };
@@ -71,7 +82,7 @@ fn calculate_chunks_per_shard(
         .map(|(s, c)| {
             let s = s.get();
             let c = c.get();
-            if s.is_multiple_of(&c) {
+            if Integer::is_multiple_of(&s, &c) {
                 Ok(unsafe { NonZeroU64::new_unchecked(s / c) })
             } else {
                 Err(ChunksPerShardError {
@@ -104,8 +115,27 @@ fn compute_index_encoded_size(
     }
 }
 
+/// Return the decoded representation and the byte range of the encoded shard index of a shard with `shard_shape`.
+fn shard_index_representation_and_byte_range(
+    index_codecs: &dyn ArrayToBytesCodecTraits,
+    index_location: ShardingIndexLocation,
+    shard_shape: &[NonZeroU64],
+    chunk_shape: &[NonZeroU64],
+) -> Result<(ChunkRepresentation, ByteRange), CodecError> {
+    let chunks_per_shard = calculate_chunks_per_shard(shard_shape, chunk_shape)
+        .map_err(|e| CodecError::Other(e.to_string()))?;
+    let index_array_representation =
+        sharding_index_decoded_representation(chunks_per_shard.as_slice());
+    let index_encoded_size = compute_index_encoded_size(index_codecs, &index_array_representation)?;
+    let index_byte_range = match index_location {
+        ShardingIndexLocation::Start => ByteRange::FromStart(0, Some(index_encoded_size)),
+        ShardingIndexLocation::End => ByteRange::FromEnd(0, Some(index_encoded_size)),
+    };
+    Ok((index_array_representation, index_byte_range))
+}
+
 fn decode_shard_index(
-    encoded_shard_index: Vec<u8>,
+    encoded_shard_index: Bytes,
     index_array_representation: &ChunkRepresentation,
     index_codecs: &dyn ArrayToBytesCodecTraits,
     options: &CodecOptions,
@@ -124,7 +154,7 @@ mod tests {
     use crate::{
         array::codec::{
             bytes_to_bytes::test_unbounded::TestUnboundedCodec, ArrayCodecTraits,
-            BytesToBytesCodecTraits, CodecOptionsBuilder,
+            BytesToBytesCodecTraits, CodecChain, CodecOptionsBuilder, CodecTraits,
         },
         array_subset::ArraySubset,
         config::global_config,
@@ -132,6 +162,53 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn codec_sharding_builder_codec_chains() {
+        let inner_codecs = CodecChain::new(
+            vec![],
+            Box::new(crate::array::codec::BytesCodec::big()),
+            vec![Box::new(TestUnboundedCodec::new())],
+        );
+        let index_codecs = CodecChain::new(
+            vec![],
+            Box::new(crate::array::codec::BytesCodec::little()),
+            vec![],
+        );
+        let codec = ShardingCodecBuilder::new(vec![2, 2].try_into().unwrap())
+            .inner_codecs(&inner_codecs)
+            .index_codecs(&index_codecs)
+            .index_location(ShardingIndexLocation::Start)
+            .build();
+        let ShardingCodecConfiguration::V1(configuration) =
+            codec.create_metadata().unwrap().to_configuration().unwrap();
+        assert_eq!(configuration.codecs, inner_codecs.create_metadatas());
+        assert_eq!(configuration.index_codecs, index_codecs.create_metadatas());
+        assert_eq!(configuration.index_location, ShardingIndexLocation::Start);
+
+        let shard_representation = ChunkRepresentation::new(
+            ChunkShape::try_from(vec![4, 4]).unwrap().into(),
+            DataType::UInt16,
+            FillValue::from(0u16),
+        )
+        .unwrap();
+        let bytes = crate::array::transmute_to_bytes_vec((0..16).collect::<Vec<u16>>());
+        let encoded = codec
+            .encode(
+                bytes.clone(),
+                &shard_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded = codec
+            .decode(
+                encoded.into(),
+                &shard_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded);
+    }
+
     fn get_concurrent_target(parallel: bool) -> usize {
         if parallel {
             global_config().codec_concurrent_target()
@@ -224,7 +301,7 @@ mod tests {
             .encode(bytes.clone(), &chunk_representation, options)
             .unwrap();
         let decoded = codec
-            .decode(encoded.clone(), &chunk_representation, options)
+            .decode(encoded.clone().into(), &chunk_representation, options)
             .unwrap();
         assert_ne!(encoded, decoded);
         assert_eq!(bytes, decoded);
@@ -318,7 +395,7 @@ mod tests {
             .encode(bytes.clone(), &chunk_representation, options)
             .unwrap();
         let decoded = codec
-            .decode(encoded.clone(), &chunk_representation, options)
+            .decode(encoded.clone().into(), &chunk_representation, options)
             .unwrap();
         assert_ne!(encoded, decoded);
         assert_eq!(bytes, decoded);
@@ -600,4 +677,140 @@ mod tests {
         let answer: Vec<u8> = vec![4, 8];
         assert_eq!(answer, decoded_partial_chunk);
     }
+
+    fn shard_writer_chunk_bytes(
+        chunk_representation: &ChunkRepresentation,
+        shard_elements: &[u16],
+        chunk_indices: &[u64],
+    ) -> Vec<u8> {
+        let chunk_shape = chunk_representation.shape_u64();
+        let chunk_subset = ArraySubset::new_with_start_shape(
+            std::iter::zip(chunk_indices, &chunk_shape)
+                .map(|(i, s)| i * s)
+                .collect(),
+            chunk_shape,
+        )
+        .unwrap();
+        unsafe {
+            chunk_subset.extract_bytes_unchecked(
+                bytemuck::cast_slice(shard_elements),
+                &[4, 4],
+                std::mem::size_of::<u16>(),
+            )
+        }
+    }
+
+    #[test]
+    fn codec_sharding_shard_writer() {
+        let shard_representation = ChunkRepresentation::new(
+            ChunkShape::try_from(vec![4, 4]).unwrap().into(),
+            DataType::UInt16,
+            FillValue::from(0u16),
+        )
+        .unwrap();
+        let options = CodecOptions::default();
+        for index_location in [ShardingIndexLocation::Start, ShardingIndexLocation::End] {
+            let codec = ShardingCodecBuilder::new(vec![2, 2].try_into().unwrap())
+                .index_location(index_location)
+                .build();
+
+            // The lower right inner chunk is entirely the fill value
+            let mut elements: Vec<u16> = (0..16).collect();
+            for i in [10, 11, 14, 15] {
+                elements[i] = 0;
+            }
+            let mut writer =
+                ShardWriter::new(&codec, &shard_representation, std::io::Cursor::new(vec![]))
+                    .unwrap();
+            for chunk_indices in [[1, 0], [0, 0], [0, 1], [1, 1]] {
+                let chunk_bytes = shard_writer_chunk_bytes(
+                    writer.chunk_representation(),
+                    &elements,
+                    &chunk_indices,
+                );
+                writer
+                    .append_chunk(&chunk_indices, chunk_bytes, &options)
+                    .unwrap();
+            }
+            assert!(writer.append_chunk(&[2, 0], vec![0; 8], &options).is_err());
+            assert!(writer.append_chunk(&[0, 0], vec![0; 2], &options).is_err());
+            let encoded = writer.finish(&options).unwrap().into_inner();
+            let decoded = codec
+                .decode(encoded.clone().into(), &shard_representation, &options)
+                .unwrap();
+            assert_eq!(
+                decoded,
+                crate::array::transmute_to_bytes_vec(elements.clone())
+            );
+
+            // Append to the existing shard, replacing inner chunks
+            for i in [0, 1, 4, 5, 10, 11, 14, 15] {
+                elements[i] = 100 + u16::try_from(i).unwrap();
+            }
+            let mut writer = ShardWriter::new_append(
+                &codec,
+                &shard_representation,
+                std::io::Cursor::new(encoded),
+                &options,
+            )
+            .unwrap();
+            for chunk_indices in [[1, 1], [0, 0]] {
+                let chunk_bytes = shard_writer_chunk_bytes(
+                    writer.chunk_representation(),
+                    &elements,
+                    &chunk_indices,
+                );
+                writer
+                    .append_chunk(&chunk_indices, chunk_bytes, &options)
+                    .unwrap();
+            }
+            let encoded = writer.finish(&options).unwrap().into_inner();
+            let decoded = codec
+                .decode(encoded.into(), &shard_representation, &options)
+                .unwrap();
+            assert_eq!(decoded, crate::array::transmute_to_bytes_vec(elements));
+        }
+    }
+
+    #[test]
+    fn codec_sharding_encode_segments() {
+        let shard_representation = ChunkRepresentation::new(
+            ChunkShape::try_from(vec![4, 4]).unwrap().into(),
+            DataType::UInt16,
+            FillValue::from(0u16),
+        )
+        .unwrap();
+        let options = CodecOptionsBuilder::new().concurrent_target(1).build();
+        let mut elements: Vec<u16> = (0..16).collect();
+        elements[0] = 1;
+        for i in [10, 11, 14, 15] {
+            elements[i] = 0;
+        }
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        for (index_location, expected_segments) in [
+            (ShardingIndexLocation::Start, 1),
+            (ShardingIndexLocation::End, 4),
+        ] {
+            let codec = ShardingCodecBuilder::new(vec![2, 2].try_into().unwrap())
+                .index_location(index_location)
+                .build();
+            let segments = codec
+                .encode_segments(bytes.clone(), &shard_representation, &options)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(segments.len(), expected_segments);
+            let encoded = segments.concat();
+            assert_eq!(
+                encoded,
+                codec
+                    .encode(bytes.clone(), &shard_representation, &options)
+                    .unwrap()
+            );
+            let decoded = codec
+                .decode(encoded.into(), &shard_representation, &options)
+                .unwrap();
+            assert_eq!(decoded, bytes);
+        }
+    }
 }
@@ -0,0 +1,284 @@
+use std::num::NonZeroU64;
+
+use crate::{
+    array::{
+        codec::{CodecError, CodecOptions},
+        ravel_indices, unravel_index, ArrayIndices, ArrayShape, ChunkRepresentation,
+    },
+    storage::{ReadableStorageTraits, StoreKey},
+};
+
+#[cfg(feature = "async")]
+use crate::storage::AsyncReadableStorageTraits;
+
+use super::{decode_shard_index, shard_index_representation_and_byte_range, ShardingCodec};
+
+/// The decoded index of a shard.
+///
+/// The shard index holds the byte offset and size of each inner chunk of a shard.
+/// An inner chunk with an offset and size of [`u64::MAX`] is empty and is not stored in the shard.
+///
+/// A [`ShardIndex`] can be retrieved from a store with [`ShardIndex::read`] or decoded from an encoded shard with [`ShardingCodec::shard_index`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardIndex {
+    chunks_per_shard: ArrayShape,
+    offsets_and_sizes: Vec<u64>,
+}
+
+impl ShardIndex {
+    /// Create a shard index from the decoded index array representation and its elements.
+    pub(super) fn new(
+        index_array_representation: &ChunkRepresentation,
+        offsets_and_sizes: Vec<u64>,
+    ) -> Self {
+        let index_shape = index_array_representation.shape_u64();
+        let chunks_per_shard = index_shape[..index_shape.len() - 1].to_vec();
+        Self {
+            chunks_per_shard,
+            offsets_and_sizes,
+        }
+    }
+
+    /// Read the shard index of the shard at `key` in `storage` without retrieving any inner chunks.
+    ///
+    /// `shard_shape` is the shape of the shard (the chunk shape of the array) and `sharding_codec` is the sharding codec of the array.
+    /// Returns [`None`] if the shard does not exist.
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if there is an underlying store error, the shard shape is incompatible with `sharding_codec`, or the shard index cannot be decoded.
+    pub fn read<TStorage: ?Sized + ReadableStorageTraits>(
+        storage: &TStorage,
+        key: &StoreKey,
+        sharding_codec: &ShardingCodec,
+        shard_shape: &[NonZeroU64],
+        options: &CodecOptions,
+    ) -> Result<Option<Self>, CodecError> {
+        let (index_array_representation, index_byte_range) =
+            shard_index_representation_and_byte_range(
+                &sharding_codec.index_codecs,
+                sharding_codec.index_location,
+                shard_shape,
+                sharding_codec.chunk_shape.as_slice(),
+            )?;
+        let Some(mut encoded_shard_index) =
+            storage.get_partial_values_key(key, &[index_byte_range])?
+        else {
+            return Ok(None);
+        };
+        let offsets_and_sizes = decode_shard_index(
+            encoded_shard_index.remove(0),
+            &index_array_representation,
+            &sharding_codec.index_codecs,
+            options,
+        )?;
+        Ok(Some(Self::new(
+            &index_array_representation,
+            offsets_and_sizes,
+        )))
+    }
+
+    #[cfg(feature = "async")]
+    /// Async variant of [`read`](ShardIndex::read).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_read<TStorage: ?Sized + AsyncReadableStorageTraits>(
+        storage: &TStorage,
+        key: &StoreKey,
+        sharding_codec: &ShardingCodec,
+        shard_shape: &[NonZeroU64],
+        options: &CodecOptions,
+    ) -> Result<Option<Self>, CodecError> {
+        let (index_array_representation, index_byte_range) =
+            shard_index_representation_and_byte_range(
+                &sharding_codec.index_codecs,
+                sharding_codec.index_location,
+                shard_shape,
+                sharding_codec.chunk_shape.as_slice(),
+            )?;
+        let Some(mut encoded_shard_index) = storage
+            .get_partial_values_key(key, &[index_byte_range])
+            .await?
+        else {
+            return Ok(None);
+        };
+        let offsets_and_sizes = decode_shard_index(
+            encoded_shard_index.remove(0),
+            &index_array_representation,
+            &sharding_codec.index_codecs,
+            options,
+        )?;
+        Ok(Some(Self::new(
+            &index_array_representation,
+            offsets_and_sizes,
+        )))
+    }
+
+    /// Return the number of inner chunks along each dimension of the shard.
+    #[must_use]
+    pub fn chunks_per_shard(&self) -> &[u64] {
+        &self.chunks_per_shard
+    }
+
+    /// Return the number of inner chunks in the shard, including empty inner chunks.
+    #[must_use]
+    pub fn num_chunks(&self) -> usize {
+        self.offsets_and_sizes.len() / 2
+    }
+
+    /// Return the number of empty inner chunks in the shard.
+    #[must_use]
+    pub fn num_empty_chunks(&self) -> usize {
+        self.offsets_and_sizes
+            .chunks_exact(2)
+            .filter(|offset_size| offset_size == &[u64::MAX, u64::MAX])
+            .count()
+    }
+
+    /// Return the byte offset and size of the inner chunk at `chunk_indices` within the shard.
+    ///
+    /// Returns [`None`] if the inner chunk is empty.
+    ///
+    /// # Panics
+    /// Panics if `chunk_indices` does not match the dimensionality of the shard or is out of bounds.
+    #[must_use]
+    pub fn chunk_offset_size(&self, chunk_indices: &[u64]) -> Option<(u64, u64)> {
+        assert!(
+            chunk_indices.len() == self.chunks_per_shard.len()
+                && std::iter::zip(chunk_indices, &self.chunks_per_shard).all(|(i, n)| i < n),
+            "inner chunk indices {chunk_indices:?} are out of bounds of {:?}",
+            self.chunks_per_shard
+        );
+        let chunk_index =
+            usize::try_from(ravel_indices(chunk_indices, &self.chunks_per_shard)).unwrap();
+        Self::offset_size(&self.offsets_and_sizes[chunk_index * 2..chunk_index * 2 + 2])
+    }
+
+    /// Return an iterator over the indices of each inner chunk and its byte offset and size, in C order.
+    ///
+    /// The offset and size is [`None`] if the inner chunk is empty.
+    pub fn iter(&self) -> impl Iterator<Item = (ArrayIndices, Option<(u64, u64)>)> + '_ {
+        self.offsets_and_sizes
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(chunk_index, offset_size)| {
+                (
+                    unravel_index(chunk_index as u64, &self.chunks_per_shard),
+                    Self::offset_size(offset_size),
+                )
+            })
+    }
+
+    /// Return the decoded shard index, which holds the offset and size of each inner chunk in C order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[u64] {
+        &self.offsets_and_sizes
+    }
+
+    fn offset_size(offset_size: &[u64]) -> Option<(u64, u64)> {
+        match *offset_size {
+            [u64::MAX, u64::MAX] => None,
+            [offset, size] => Some((offset, size)),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{
+            codec::{
+                array_to_bytes::sharding::{ShardingCodecBuilder, ShardingIndexLocation},
+                ArrayCodecTraits,
+            },
+            DataType, FillValue,
+        },
+        byte_range::ByteRange,
+        storage::{store::MemoryStore, WritableStorageTraits},
+    };
+
+    use super::*;
+
+    #[test]
+    fn shard_index_read() {
+        let shard_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(4).unwrap(); 2],
+            DataType::UInt16,
+            FillValue::from(0u16),
+        )
+        .unwrap();
+        // Only the first inner chunk is not the fill value
+        let mut elements = vec![0u16; 16];
+        elements[0] = 1;
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        for index_location in [ShardingIndexLocation::Start, ShardingIndexLocation::End] {
+            let codec = ShardingCodecBuilder::new(vec![2, 2].try_into().unwrap())
+                .index_location(index_location)
+                .build();
+            let encoded = codec
+                .encode(
+                    bytes.clone(),
+                    &shard_representation,
+                    &CodecOptions::default(),
+                )
+                .unwrap();
+
+            let store = MemoryStore::new();
+            let key = StoreKey::new("shard").unwrap();
+            store.set(&key, &encoded).unwrap();
+
+            let shard_index = ShardIndex::read(
+                &store,
+                &key,
+                &codec,
+                shard_representation.shape(),
+                &CodecOptions::default(),
+            )
+            .unwrap()
+            .unwrap();
+            assert_eq!(
+                shard_index,
+                codec
+                    .shard_index(
+                        &encoded,
+                        shard_representation.shape(),
+                        &CodecOptions::default()
+                    )
+                    .unwrap()
+            );
+            assert_eq!(shard_index.chunks_per_shard(), &[2, 2]);
+            assert_eq!(shard_index.num_chunks(), 4);
+            assert_eq!(shard_index.num_empty_chunks(), 3);
+            let offset = match codec
+                .shard_index_byte_range(shard_representation.shape())
+                .unwrap()
+            {
+                ByteRange::FromStart(0, Some(index_encoded_size)) => index_encoded_size,
+                ByteRange::FromEnd(0, Some(_)) => 0,
+                _ => unreachable!(),
+            };
+            assert_eq!(shard_index.chunk_offset_size(&[0, 0]), Some((offset, 8)));
+            assert_eq!(shard_index.chunk_offset_size(&[1, 1]), None);
+            assert_eq!(
+                shard_index.iter().collect::<Vec<_>>(),
+                vec![
+                    (vec![0, 0], Some((offset, 8))),
+                    (vec![0, 1], None),
+                    (vec![1, 0], None),
+                    (vec![1, 1], None),
+                ]
+            );
+
+            let missing_key = StoreKey::new("missing").unwrap();
+            assert!(ShardIndex::read(
+                &store,
+                &missing_key,
+                &codec,
+                shard_representation.shape(),
+                &CodecOptions::default(),
+            )
+            .unwrap()
+            .is_none());
+        }
+    }
+}
@@ -1,19 +1,22 @@
 use std::{num::NonZeroU64, sync::atomic::AtomicUsize};
 
+use bytes::Bytes;
+
 use crate::{
     array::{
         chunk_shape_to_array_shape,
         codec::{
             ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayToBytesCodecTraits,
             BytesPartialDecoderTraits, CodecChain, CodecError, CodecOptions, CodecTraits,
-            RecommendedConcurrency,
+            EncodedSegments, RecommendedConcurrency,
         },
         concurrency::calc_concurrency_outer_inner,
         transmute_to_bytes_vec, unravel_index,
         unsafe_cell_slice::UnsafeCellSlice,
-        ArrayView, BytesRepresentation, ChunkRepresentation, ChunkShape,
+        ArrayShape, ArrayView, BytesRepresentation, ChunkRepresentation, ChunkShape,
     },
     array_subset::ArraySubset,
+    byte_range::ByteRange,
     metadata::Metadata,
     plugin::PluginCreateError,
 };
@@ -23,8 +26,9 @@ use crate::array::codec::{AsyncArrayPartialDecoderTraits, AsyncBytesPartialDecod
 
 use super::{
     calculate_chunks_per_shard, compute_index_encoded_size, decode_shard_index,
-    sharding_configuration::ShardingIndexLocation, sharding_index_decoded_representation,
-    sharding_partial_decoder, ShardingCodecConfiguration, ShardingCodecConfigurationV1, IDENTIFIER,
+    shard_index_representation_and_byte_range, sharding_configuration::ShardingIndexLocation,
+    sharding_index_decoded_representation, sharding_partial_decoder, ShardIndex,
+    ShardingCodecConfiguration, ShardingCodecConfigurationV1, IDENTIFIER,
 };
 
 use rayon::prelude::*;
@@ -33,13 +37,13 @@ use rayon::prelude::*;
 #[derive(Clone, Debug)]
 pub struct ShardingCodec {
     /// An array of integers specifying the shape of the inner chunks in a shard along each dimension of the outer array.
-    chunk_shape: ChunkShape,
+    pub(super) chunk_shape: ChunkShape,
     /// The codecs used to encode and decode inner chunks.
-    inner_codecs: CodecChain,
+    pub(super) inner_codecs: CodecChain,
     /// The codecs used to encode and decode the shard index.
-    index_codecs: CodecChain,
+    pub(super) index_codecs: CodecChain,
     /// Specifies whether the shard index is located at the beginning or end of the file.
-    index_location: ShardingIndexLocation,
+    pub(super) index_location: ShardingIndexLocation,
 }
 
 impl ShardingCodec {
@@ -77,6 +81,43 @@ impl ShardingCodec {
             configuration.index_location,
         ))
     }
+
+    /// Return the byte range of the encoded shard index within a shard with shape `shard_shape`.
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if the shard shape is incompatible with the inner chunk shape or the index codecs have a variable size output.
+    pub fn shard_index_byte_range(
+        &self,
+        shard_shape: &[NonZeroU64],
+    ) -> Result<ByteRange, CodecError> {
+        let (_, index_byte_range) = shard_index_representation_and_byte_range(
+            &self.index_codecs,
+            self.index_location,
+            shard_shape,
+            self.chunk_shape.as_slice(),
+        )?;
+        Ok(index_byte_range)
+    }
+
+    /// Decode the shard index of an encoded shard with shape `shard_shape` without decoding any inner chunks.
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if the shard shape is incompatible with the inner chunk shape or the shard index cannot be decoded.
+    pub fn shard_index(
+        &self,
+        encoded_shard: &[u8],
+        shard_shape: &[NonZeroU64],
+        options: &CodecOptions,
+    ) -> Result<ShardIndex, CodecError> {
+        let chunks_per_shard = calculate_chunks_per_shard(shard_shape, self.chunk_shape.as_slice())
+            .map_err(|e| CodecError::Other(e.to_string()))?;
+        let offsets_and_sizes =
+            self.decode_index(encoded_shard, chunks_per_shard.as_slice(), options)?;
+        Ok(ShardIndex::new(
+            &sharding_index_decoded_representation(chunks_per_shard.as_slice()),
+            offsets_and_sizes,
+        ))
+    }
 }
 
 impl CodecTraits for ShardingCodec {
@@ -145,7 +186,7 @@ impl ArrayCodecTraits for ShardingCodec {
 
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         decoded_representation: &ChunkRepresentation,
         options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
@@ -288,6 +329,31 @@ impl ArrayCodecTraits for ShardingCodec {
 
 #[cfg_attr(feature = "async", async_trait::async_trait)]
 impl ArrayToBytesCodecTraits for ShardingCodec {
+    /// Returns the encoded inner chunks in batches followed by the shard index if the index is located at the end of the shard.
+    ///
+    /// Each batch is encoded as the segments are consumed, so the encoded shard is not held in memory.
+    /// If the index is located at the start of the shard, the encoded shard is a single segment since the index depends on the size of every encoded inner chunk.
+    fn encode_segments<'a>(
+        &'a self,
+        decoded_value: Vec<u8>,
+        shard_representation: &ChunkRepresentation,
+        options: &CodecOptions,
+    ) -> Result<EncodedSegments<'a>, CodecError> {
+        match self.index_location {
+            ShardingIndexLocation::Start => Ok(Box::new(std::iter::once(self.encode(
+                decoded_value,
+                shard_representation,
+                options,
+            )))),
+            ShardingIndexLocation::End => Ok(Box::new(ShardSegments::new(
+                self,
+                decoded_value,
+                shard_representation,
+                options,
+            )?)),
+        }
+    }
+
     fn partial_decoder<'a>(
         &'a self,
         input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
@@ -332,6 +398,10 @@ impl ArrayToBytesCodecTraits for ShardingCodec {
         &self,
         decoded_representation: &ChunkRepresentation,
     ) -> Result<BytesRepresentation, CodecError> {
+        let chunks_per_shard =
+            calculate_chunks_per_shard(decoded_representation.shape(), self.chunk_shape.as_slice())
+                .map_err(|e| CodecError::Other(e.to_string()))?;
+
         // Get the maximum size of encoded chunks
         let chunk_representation = unsafe {
             ChunkRepresentation::new_unchecked(
@@ -346,11 +416,6 @@ impl ArrayToBytesCodecTraits for ShardingCodec {
 
         match chunk_bytes_representation {
             BytesRepresentation::BoundedSize(size) | BytesRepresentation::FixedSize(size) => {
-                let chunks_per_shard = calculate_chunks_per_shard(
-                    decoded_representation.shape(),
-                    self.chunk_shape.as_slice(),
-                )
-                .map_err(|e| CodecError::Other(e.to_string()))?;
                 let index_decoded_representation =
                     sharding_index_decoded_representation(chunks_per_shard.as_slice());
                 let index_encoded_size =
@@ -365,6 +430,48 @@ impl ArrayToBytesCodecTraits for ShardingCodec {
             BytesRepresentation::UnboundedSize => Ok(BytesRepresentation::UnboundedSize),
         }
     }
+
+    fn partial_decode_byte_ranges(
+        &self,
+        input_handle: &dyn BytesPartialDecoderTraits,
+        decoded_representation: &ChunkRepresentation,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<ByteRange>>, CodecError> {
+        Ok(Some(sharding_partial_decoder::partial_decode_byte_ranges(
+            input_handle,
+            decoded_representation,
+            self.chunk_shape.as_slice(),
+            &self.inner_codecs,
+            &self.index_codecs,
+            self.index_location,
+            array_subsets,
+            options,
+        )?))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decode_byte_ranges(
+        &self,
+        input_handle: &dyn AsyncBytesPartialDecoderTraits,
+        decoded_representation: &ChunkRepresentation,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<ByteRange>>, CodecError> {
+        Ok(Some(
+            sharding_partial_decoder::async_partial_decode_byte_ranges(
+                input_handle,
+                decoded_representation,
+                self.chunk_shape.as_slice(),
+                &self.inner_codecs,
+                &self.index_codecs,
+                self.index_location,
+                array_subsets,
+                options,
+            )
+            .await?,
+        ))
+    }
 }
 
 impl ShardingCodec {
@@ -701,10 +808,175 @@ impl ShardingCodec {
 
         // Decode the shard index
         decode_shard_index(
-            encoded_shard_index.to_vec(),
+            Bytes::copy_from_slice(encoded_shard_index),
             &index_array_representation,
             &self.index_codecs,
             options,
         )
     }
 }
+
+/// The segments of a shard with its index located at the end, returned by [`ShardingCodec::encode_segments`].
+///
+/// Inner chunks are encoded in batches as the segments are consumed, and the shard index is the last segment.
+struct ShardSegments<'a> {
+    codec: &'a ShardingCodec,
+    decoded_value: Vec<u8>,
+    shard_shape: ArrayShape,
+    element_size: usize,
+    chunk_representation: ChunkRepresentation,
+    chunks_per_shard: ChunkShape,
+    index_representation: ChunkRepresentation,
+    shard_index: Vec<u64>,
+    /// The offset of the next encoded inner chunk in the shard.
+    offset: u64,
+    next_chunk_index: usize,
+    num_chunks: usize,
+    batch_size: usize,
+    options: CodecOptions,
+    options_inner: CodecOptions,
+    finished: bool,
+}
+
+impl<'a> ShardSegments<'a> {
+    fn new(
+        codec: &'a ShardingCodec,
+        decoded_value: Vec<u8>,
+        shard_representation: &ChunkRepresentation,
+        options: &CodecOptions,
+    ) -> Result<Self, CodecError> {
+        if decoded_value.len() as u64 != shard_representation.size() {
+            return Err(CodecError::UnexpectedChunkDecodedSize(
+                decoded_value.len(),
+                shard_representation.size(),
+            ));
+        }
+
+        let chunk_representation = unsafe {
+            ChunkRepresentation::new_unchecked(
+                codec.chunk_shape.as_slice().to_vec(),
+                shard_representation.data_type().clone(),
+                shard_representation.fill_value().clone(),
+            )
+        };
+        let chunks_per_shard =
+            calculate_chunks_per_shard(shard_representation.shape(), chunk_representation.shape())
+                .map_err(|e| CodecError::Other(e.to_string()))?;
+        let index_representation =
+            sharding_index_decoded_representation(chunks_per_shard.as_slice());
+        let num_chunks = chunks_per_shard.num_elements_usize();
+
+        // Each batch encodes as many inner chunks as are encoded concurrently
+        let (shard_concurrent_limit, concurrency_limit_inner_chunks) = calc_concurrency_outer_inner(
+            options.concurrent_target(),
+            &codec.recommended_concurrency(shard_representation)?,
+            &codec
+                .inner_codecs
+                .recommended_concurrency(&chunk_representation)?,
+        );
+        let options_inner = options
+            .into_builder()
+            .concurrent_target(concurrency_limit_inner_chunks)
+            .build();
+
+        Ok(Self {
+            codec,
+            decoded_value,
+            shard_shape: shard_representation.shape_u64(),
+            element_size: shard_representation.element_size(),
+            chunk_representation,
+            shard_index: vec![u64::MAX; index_representation.num_elements_usize()],
+            chunks_per_shard,
+            index_representation,
+            offset: 0,
+            next_chunk_index: 0,
+            num_chunks,
+            batch_size: shard_concurrent_limit.max(1),
+            options: options.clone(),
+            options_inner,
+            finished: false,
+        })
+    }
+
+    /// Encode the inner chunks in `chunk_indices` that are not entirely the fill value.
+    fn encode_batch(
+        &self,
+        chunk_indices: std::ops::Range<usize>,
+    ) -> Result<Vec<(usize, Vec<u8>)>, CodecError> {
+        rayon_iter_concurrent_limit::iter_concurrent_limit!(
+            self.batch_size,
+            chunk_indices.into_par_iter(),
+            filter_map,
+            |chunk_index| {
+                let chunk_subset = self
+                    .codec
+                    .chunk_index_to_subset(chunk_index as u64, self.chunks_per_shard.as_slice());
+                let bytes = unsafe {
+                    chunk_subset.extract_bytes_unchecked(
+                        &self.decoded_value,
+                        &self.shard_shape,
+                        self.element_size,
+                    )
+                };
+                if self.chunk_representation.fill_value().equals_all(&bytes) {
+                    None
+                } else {
+                    Some(
+                        self.codec
+                            .inner_codecs
+                            .encode(bytes, &self.chunk_representation, &self.options_inner)
+                            .map(|encoded_chunk| (chunk_index, encoded_chunk)),
+                    )
+                }
+            }
+        )
+        .collect()
+    }
+}
+
+impl Iterator for ShardSegments<'_> {
+    type Item = Result<Vec<u8>, CodecError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        while self.next_chunk_index < self.num_chunks {
+            let chunk_indices = self.next_chunk_index
+                ..std::cmp::min(self.next_chunk_index + self.batch_size, self.num_chunks);
+            self.next_chunk_index = chunk_indices.end;
+            let encoded_chunks = match self.encode_batch(chunk_indices) {
+                Ok(encoded_chunks) => encoded_chunks,
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+            };
+            if encoded_chunks.is_empty() {
+                continue;
+            }
+
+            let mut segment = Vec::with_capacity(
+                encoded_chunks
+                    .iter()
+                    .map(|(_, encoded_chunk)| encoded_chunk.len())
+                    .sum(),
+            );
+            for (chunk_index, encoded_chunk) in encoded_chunks {
+                self.shard_index[chunk_index * 2] = self.offset;
+                self.shard_index[chunk_index * 2 + 1] = encoded_chunk.len() as u64;
+                self.offset += encoded_chunk.len() as u64;
+                segment.extend_from_slice(&encoded_chunk);
+            }
+            return Some(Ok(segment));
+        }
+
+        self.finished = true;
+        Some(self.codec.index_codecs.encode(
+            transmute_to_bytes_vec(std::mem::take(&mut self.shard_index)),
+            &self.index_representation,
+            &self.options,
+        ))
+    }
+}
@@ -97,6 +97,26 @@ impl ShardingCodecBuilder {
         self
     }
 
+    /// Set the inner chunk codecs.
+    ///
+    /// This is equivalent to setting the [array to array](ShardingCodecBuilder::array_to_array_codecs), [array to bytes](ShardingCodecBuilder::array_to_bytes_codec), and [bytes to bytes](ShardingCodecBuilder::bytes_to_bytes_codecs) codecs of the inner chunks.
+    pub fn inner_codecs(&mut self, inner_codecs: &CodecChain) -> &mut Self {
+        self.array_to_array_codecs = inner_codecs.array_to_array_codecs().to_vec();
+        self.array_to_bytes_codec = inner_codecs.array_to_bytes_codec().clone();
+        self.bytes_to_bytes_codecs = inner_codecs.bytes_to_bytes_codecs().to_vec();
+        self
+    }
+
+    /// Set the index codecs.
+    ///
+    /// This is equivalent to setting the [index array to bytes](ShardingCodecBuilder::index_array_to_bytes_codec) and [index bytes to bytes](ShardingCodecBuilder::index_bytes_to_bytes_codecs) codecs.
+    /// The index codecs must not include array to array codecs, and must produce a fixed size output.
+    pub fn index_codecs(&mut self, index_codecs: &CodecChain) -> &mut Self {
+        self.index_array_to_bytes_codec = index_codecs.array_to_bytes_codec().clone();
+        self.index_bytes_to_bytes_codecs = index_codecs.bytes_to_bytes_codecs().to_vec();
+        self
+    }
+
     /// Set the index location.
     ///
     /// If left unmodified, defaults to the end of the shard.
@@ -106,6 +126,8 @@ impl ShardingCodecBuilder {
     }
 
     /// Build into a [`ShardingCodec`].
+    ///
+    /// The inner chunk shape is validated against the chunk shape of an array when the array is built with [`ArrayBuilder::build`](crate::array::ArrayBuilder::build).
     #[must_use]
     pub fn build(&self) -> ShardingCodec {
         let inner_codecs = CodecChain::new(
@@ -1,23 +1,24 @@
-use std::num::NonZeroU64;
+use std::{num::NonZeroU64, sync::Arc};
 
 use rayon::prelude::*;
 
-use crate::{
-    array::{
-        chunk_grid::RegularChunkGrid,
-        chunk_shape_to_array_shape,
-        codec::{
-            ArrayCodecTraits, ArrayPartialDecoderTraits, ArraySubset, ArrayToBytesCodecTraits,
-            ByteIntervalPartialDecoder, BytesPartialDecoderTraits, CodecChain, CodecError,
-            CodecOptions,
-        },
-        concurrency::{calc_concurrency_outer_inner, RecommendedConcurrency},
-        ravel_indices,
-        unsafe_cell_slice::UnsafeCellSlice,
-        ChunkRepresentation, ChunkShape,
+use crate::array::{
+    chunk_grid::RegularChunkGrid,
+    chunk_shape_to_array_shape,
+    codec::{
+        ArrayCodecTraits, ArrayPartialDecoderTraits, ArraySubset, ArrayToBytesCodecTraits,
+        ByteIntervalPartialDecoder, BytesPartialDecoderTraits, CodecChain, CodecError,
+        CodecOptions,
     },
-    byte_range::ByteRange,
+    concurrency::{calc_concurrency_outer_inner, RecommendedConcurrency},
+    ravel_indices,
+    unsafe_cell_slice::UnsafeCellSlice,
+    ChunkRepresentation, ChunkShape,
 };
+use crate::{byte_range::ByteRange, storage::StoreKey};
+
+#[cfg(feature = "async")]
+use futures::StreamExt;
 
 #[cfg(feature = "async")]
 use crate::array::codec::{
@@ -26,8 +27,8 @@ use crate::array::codec::{
 };
 
 use super::{
-    calculate_chunks_per_shard, compute_index_encoded_size, decode_shard_index,
-    sharding_configuration::ShardingIndexLocation, sharding_index_decoded_representation,
+    calculate_chunks_per_shard, decode_shard_index, shard_index_representation_and_byte_range,
+    sharding_configuration::ShardingIndexLocation,
 };
 
 /// Partial decoder for the sharding codec.
@@ -36,7 +37,7 @@ pub struct ShardingPartialDecoder<'a> {
     decoded_representation: ChunkRepresentation,
     chunk_grid: RegularChunkGrid,
     inner_codecs: &'a CodecChain,
-    shard_index: Option<Vec<u64>>,
+    shard_index: Option<Arc<Vec<u64>>>,
 }
 
 impl<'a> ShardingPartialDecoder<'a> {
@@ -50,7 +51,7 @@ impl<'a> ShardingPartialDecoder<'a> {
         index_location: ShardingIndexLocation,
         options: &CodecOptions,
     ) -> Result<Self, CodecError> {
-        let shard_index = Self::decode_shard_index(
+        let shard_index = retrieve_shard_index(
             &*input_handle,
             index_codecs,
             index_location,
@@ -76,32 +77,13 @@ impl<'a> ShardingPartialDecoder<'a> {
         decoded_representation: &ChunkRepresentation,
         options: &CodecOptions,
     ) -> Result<Option<Vec<u64>>, CodecError> {
-        let shard_shape = decoded_representation.shape();
-        let chunk_representation = unsafe {
-            ChunkRepresentation::new_unchecked(
-                chunk_shape.to_vec(),
-                decoded_representation.data_type().clone(),
-                decoded_representation.fill_value().clone(),
-            )
-        };
-
-        // Calculate chunks per shard
-        let chunks_per_shard =
-            calculate_chunks_per_shard(shard_shape, chunk_representation.shape())
-                .map_err(|e| CodecError::Other(e.to_string()))?;
-
-        // Get index array representation and encoded size
-        let index_array_representation =
-            sharding_index_decoded_representation(chunks_per_shard.as_slice());
-        let index_encoded_size =
-            compute_index_encoded_size(index_codecs, &index_array_representation)
-                .map_err(|e| CodecError::Other(e.to_string()))?;
-
-        // Decode the shard index
-        let index_byte_range = match index_location {
-            ShardingIndexLocation::Start => ByteRange::FromStart(0, Some(index_encoded_size)),
-            ShardingIndexLocation::End => ByteRange::FromEnd(0, Some(index_encoded_size)),
-        };
+        let (index_array_representation, index_byte_range) =
+            shard_index_representation_and_byte_range(
+                index_codecs,
+                index_location,
+                decoded_representation.shape(),
+                chunk_shape,
+            )?;
 
         let encoded_shard_index = input_handle
             .partial_decode(&[index_byte_range], options)?
@@ -119,6 +101,156 @@ impl<'a> ShardingPartialDecoder<'a> {
     }
 }
 
+/// Retrieve and decode the shard index, or get it from the shard index cache of `options`.
+///
+/// Returns `None` if there is no shard.
+fn retrieve_shard_index(
+    input_handle: &dyn BytesPartialDecoderTraits,
+    index_codecs: &CodecChain,
+    index_location: ShardingIndexLocation,
+    chunk_shape: &[NonZeroU64],
+    decoded_representation: &ChunkRepresentation,
+    options: &CodecOptions,
+) -> Result<Option<Arc<Vec<u64>>>, CodecError> {
+    let cache = options.shard_index_cache().zip(input_handle.store_key());
+    if let Some(shard_index) = cache.and_then(|(cache, key)| cache.get(key)) {
+        return Ok(Some(shard_index));
+    }
+    let shard_index = ShardingPartialDecoder::decode_shard_index(
+        input_handle,
+        index_codecs,
+        index_location,
+        chunk_shape,
+        decoded_representation,
+        options,
+    )?
+    .map(Arc::new);
+    if let (Some((cache, key)), Some(shard_index)) = (cache, &shard_index) {
+        cache.insert(key.clone(), shard_index.clone());
+    }
+    Ok(shard_index)
+}
+
+/// Returns true if the shard index of the shard at `store_key` is in the shard index cache of `options`.
+fn shard_index_is_cached(store_key: Option<&StoreKey>, options: &CodecOptions) -> bool {
+    options
+        .shard_index_cache()
+        .zip(store_key)
+        .is_some_and(|(cache, key)| cache.get(key).is_some())
+}
+
+/// Convert a byte range of the inner chunk at the byte interval `offset..offset + size` of a shard to a byte range of the shard.
+fn inner_chunk_byte_range_in_shard(byte_range: &ByteRange, offset: u64, size: u64) -> ByteRange {
+    ByteRange::FromStart(
+        offset + byte_range.start(size),
+        Some(byte_range.length(size)),
+    )
+}
+
+/// Validate `array_subsets` and return the inner chunk representation and the number of chunks per shard along each dimension.
+fn inner_chunk_representation_and_chunks_per_shard(
+    decoded_representation: &ChunkRepresentation,
+    chunk_shape: &[NonZeroU64],
+    array_subsets: &[ArraySubset],
+) -> Result<(ChunkRepresentation, Vec<u64>), CodecError> {
+    for array_subset in array_subsets {
+        if array_subset.dimensionality() != decoded_representation.dimensionality() {
+            return Err(CodecError::InvalidArraySubsetDimensionalityError(
+                array_subset.clone(),
+                decoded_representation.dimensionality(),
+            ));
+        }
+    }
+    let chunk_representation = unsafe {
+        ChunkRepresentation::new_unchecked(
+            chunk_shape.to_vec(),
+            decoded_representation.data_type().clone(),
+            decoded_representation.fill_value().clone(),
+        )
+    };
+    let chunks_per_shard = calculate_chunks_per_shard(decoded_representation.shape(), chunk_shape)
+        .map_err(|e| CodecError::Other(e.to_string()))?;
+    Ok((
+        chunk_representation,
+        chunk_shape_to_array_shape(chunks_per_shard.as_slice()),
+    ))
+}
+
+/// Return the byte ranges of a shard that a [`ShardingPartialDecoder`] would retrieve to decode `array_subsets`.
+///
+/// The shard index byte range is included unless the shard index is cached.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn partial_decode_byte_ranges(
+    input_handle: &dyn BytesPartialDecoderTraits,
+    decoded_representation: &ChunkRepresentation,
+    chunk_shape: &[NonZeroU64],
+    inner_codecs: &CodecChain,
+    index_codecs: &CodecChain,
+    index_location: ShardingIndexLocation,
+    array_subsets: &[ArraySubset],
+    options: &CodecOptions,
+) -> Result<Vec<ByteRange>, CodecError> {
+    let (chunk_representation, chunks_per_shard) = inner_chunk_representation_and_chunks_per_shard(
+        decoded_representation,
+        chunk_shape,
+        array_subsets,
+    )?;
+
+    let mut byte_ranges = Vec::new();
+    if !shard_index_is_cached(input_handle.store_key(), options) {
+        let (_, index_byte_range) = shard_index_representation_and_byte_range(
+            index_codecs,
+            index_location,
+            decoded_representation.shape(),
+            chunk_shape,
+        )?;
+        byte_ranges.push(index_byte_range);
+    }
+    let Some(shard_index) = retrieve_shard_index(
+        input_handle,
+        index_codecs,
+        index_location,
+        chunk_shape,
+        decoded_representation,
+        options,
+    )?
+    else {
+        return Ok(byte_ranges);
+    };
+
+    for array_subset in array_subsets {
+        let chunks = unsafe { array_subset.chunks_unchecked(chunk_representation.shape()) };
+        for (chunk_indices, chunk_subset) in &chunks {
+            let shard_index_idx =
+                usize::try_from(ravel_indices(&chunk_indices, &chunks_per_shard) * 2).unwrap();
+            let offset = shard_index[shard_index_idx];
+            let size = shard_index[shard_index_idx + 1];
+            if offset == u64::MAX && size == u64::MAX {
+                // The chunk is just the fill value
+                continue;
+            }
+            let overlap = unsafe { array_subset.overlap_unchecked(&chunk_subset) };
+            let array_subset_in_chunk_subset =
+                unsafe { overlap.relative_to_unchecked(chunk_subset.start()) };
+            let inner_byte_ranges = inner_codecs.partial_decode_byte_ranges(
+                &ByteIntervalPartialDecoder::new(input_handle, offset, size),
+                &chunk_representation,
+                &[array_subset_in_chunk_subset],
+                options,
+            )?;
+            match inner_byte_ranges {
+                Some(inner_byte_ranges) => {
+                    byte_ranges.extend(inner_byte_ranges.iter().map(|byte_range| {
+                        inner_chunk_byte_range_in_shard(byte_range, offset, size)
+                    }));
+                }
+                None => byte_ranges.push(ByteRange::FromStart(offset, Some(size))),
+            }
+        }
+    }
+    Ok(byte_ranges)
+}
+
 impl ArrayPartialDecoderTraits for ShardingPartialDecoder<'_> {
     fn element_size(&self) -> usize {
         self.decoded_representation.element_size()
@@ -266,7 +398,7 @@ pub struct AsyncShardingPartialDecoder<'a> {
     decoded_representation: ChunkRepresentation,
     chunk_grid: RegularChunkGrid,
     inner_codecs: &'a CodecChain,
-    shard_index: Option<Vec<u64>>,
+    shard_index: Option<Arc<Vec<u64>>>,
 }
 
 #[cfg(feature = "async")]
@@ -281,7 +413,7 @@ impl<'a> AsyncShardingPartialDecoder<'a> {
         index_location: ShardingIndexLocation,
         options: &CodecOptions,
     ) -> Result<AsyncShardingPartialDecoder<'a>, CodecError> {
-        let shard_index = Self::decode_shard_index(
+        let shard_index = async_retrieve_shard_index(
             &*input_handle,
             index_codecs,
             index_location,
@@ -308,32 +440,13 @@ impl<'a> AsyncShardingPartialDecoder<'a> {
         decoded_representation: &ChunkRepresentation,
         options: &CodecOptions,
     ) -> Result<Option<Vec<u64>>, CodecError> {
-        let shard_shape = decoded_representation.shape();
-        let chunk_representation = unsafe {
-            ChunkRepresentation::new_unchecked(
-                chunk_shape.to_vec(),
-                decoded_representation.data_type().clone(),
-                decoded_representation.fill_value().clone(),
-            )
-        };
-
-        // Calculate chunks per shard
-        let chunks_per_shard =
-            calculate_chunks_per_shard(shard_shape, chunk_representation.shape())
-                .map_err(|e| CodecError::Other(e.to_string()))?;
-
-        // Get index array representation and encoded size
-        let index_array_representation =
-            sharding_index_decoded_representation(chunks_per_shard.as_slice());
-        let index_encoded_size =
-            compute_index_encoded_size(index_codecs, &index_array_representation)
-                .map_err(|e| CodecError::Other(e.to_string()))?;
-
-        // Decode the shard index
-        let index_byte_range = match index_location {
-            ShardingIndexLocation::Start => ByteRange::FromStart(0, Some(index_encoded_size)),
-            ShardingIndexLocation::End => ByteRange::FromEnd(0, Some(index_encoded_size)),
-        };
+        let (index_array_representation, index_byte_range) =
+            shard_index_representation_and_byte_range(
+                index_codecs,
+                index_location,
+                decoded_representation.shape(),
+                chunk_shape,
+            )?;
 
         let encoded_shard_index = input_handle
             .partial_decode(&[index_byte_range], options)
@@ -352,6 +465,117 @@ impl<'a> AsyncShardingPartialDecoder<'a> {
     }
 }
 
+#[cfg(feature = "async")]
+/// Asynchronously retrieve and decode the shard index, or get it from the shard index cache of `options`.
+///
+/// Returns `None` if there is no shard.
+async fn async_retrieve_shard_index(
+    input_handle: &dyn AsyncBytesPartialDecoderTraits,
+    index_codecs: &CodecChain,
+    index_location: ShardingIndexLocation,
+    chunk_shape: &[NonZeroU64],
+    decoded_representation: &ChunkRepresentation,
+    options: &CodecOptions,
+) -> Result<Option<Arc<Vec<u64>>>, CodecError> {
+    let cache = options.shard_index_cache().zip(input_handle.store_key());
+    if let Some(shard_index) = cache.and_then(|(cache, key)| cache.get(key)) {
+        return Ok(Some(shard_index));
+    }
+    let shard_index = AsyncShardingPartialDecoder::decode_shard_index(
+        input_handle,
+        index_codecs,
+        index_location,
+        chunk_shape,
+        decoded_representation,
+        options,
+    )
+    .await?
+    .map(Arc::new);
+    if let (Some((cache, key)), Some(shard_index)) = (cache, &shard_index) {
+        cache.insert(key.clone(), shard_index.clone());
+    }
+    Ok(shard_index)
+}
+
+#[cfg(feature = "async")]
+/// Return the byte ranges of a shard that an [`AsyncShardingPartialDecoder`] would retrieve to decode `array_subsets`.
+///
+/// The shard index byte range is included unless the shard index is cached.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn async_partial_decode_byte_ranges(
+    input_handle: &dyn AsyncBytesPartialDecoderTraits,
+    decoded_representation: &ChunkRepresentation,
+    chunk_shape: &[NonZeroU64],
+    inner_codecs: &CodecChain,
+    index_codecs: &CodecChain,
+    index_location: ShardingIndexLocation,
+    array_subsets: &[ArraySubset],
+    options: &CodecOptions,
+) -> Result<Vec<ByteRange>, CodecError> {
+    let (chunk_representation, chunks_per_shard) = inner_chunk_representation_and_chunks_per_shard(
+        decoded_representation,
+        chunk_shape,
+        array_subsets,
+    )?;
+
+    let mut byte_ranges = Vec::new();
+    if !shard_index_is_cached(input_handle.store_key(), options) {
+        let (_, index_byte_range) = shard_index_representation_and_byte_range(
+            index_codecs,
+            index_location,
+            decoded_representation.shape(),
+            chunk_shape,
+        )?;
+        byte_ranges.push(index_byte_range);
+    }
+    let Some(shard_index) = async_retrieve_shard_index(
+        input_handle,
+        index_codecs,
+        index_location,
+        chunk_shape,
+        decoded_representation,
+        options,
+    )
+    .await?
+    else {
+        return Ok(byte_ranges);
+    };
+
+    for array_subset in array_subsets {
+        let chunks = unsafe { array_subset.chunks_unchecked(chunk_representation.shape()) };
+        for (chunk_indices, chunk_subset) in &chunks {
+            let shard_index_idx =
+                usize::try_from(ravel_indices(&chunk_indices, &chunks_per_shard) * 2).unwrap();
+            let offset = shard_index[shard_index_idx];
+            let size = shard_index[shard_index_idx + 1];
+            if offset == u64::MAX && size == u64::MAX {
+                // The chunk is just the fill value
+                continue;
+            }
+            let overlap = unsafe { array_subset.overlap_unchecked(&chunk_subset) };
+            let array_subset_in_chunk_subset =
+                unsafe { overlap.relative_to_unchecked(chunk_subset.start()) };
+            let inner_byte_ranges = inner_codecs
+                .async_partial_decode_byte_ranges(
+                    &AsyncByteIntervalPartialDecoder::new(input_handle, offset, size),
+                    &chunk_representation,
+                    &[array_subset_in_chunk_subset],
+                    options,
+                )
+                .await?;
+            match inner_byte_ranges {
+                Some(inner_byte_ranges) => {
+                    byte_ranges.extend(inner_byte_ranges.iter().map(|byte_range| {
+                        inner_chunk_byte_range_in_shard(byte_range, offset, size)
+                    }));
+                }
+                None => byte_ranges.push(ByteRange::FromStart(offset, Some(size))),
+            }
+        }
+    }
+    Ok(byte_ranges)
+}
+
 #[cfg(feature = "async")]
 #[async_trait::async_trait]
 impl AsyncArrayPartialDecoderTraits for AsyncShardingPartialDecoder<'_> {
@@ -392,10 +616,34 @@ impl AsyncArrayPartialDecoderTraits for AsyncShardingPartialDecoder<'_> {
         )
         .map_err(|e| CodecError::Other(e.to_string()))?;
         let chunks_per_shard = chunk_shape_to_array_shape(chunks_per_shard.as_slice());
+        let num_chunks = usize::try_from(chunks_per_shard.iter().product::<u64>()).unwrap();
+
+        let chunk_representation = unsafe {
+            ChunkRepresentation::new_unchecked(
+                self.chunk_grid.chunk_shape().to_vec(),
+                self.decoded_representation.data_type().clone(),
+                self.decoded_representation.fill_value().clone(),
+            )
+        };
+
+        // Calculate inner chunk/codec concurrency
+        let (inner_chunk_concurrent_limit, concurrency_limit_codec) = calc_concurrency_outer_inner(
+            options.concurrent_target(),
+            &RecommendedConcurrency::new_maximum(std::cmp::min(
+                options.concurrent_target(),
+                num_chunks,
+            )),
+            &self
+                .inner_codecs
+                .recommended_concurrency(&chunk_representation)?,
+        );
+        let codec_options = options
+            .into_builder()
+            .concurrent_target(concurrency_limit_codec)
+            .build();
 
         let element_size = self.decoded_representation.element_size();
         let mut out = Vec::with_capacity(array_subsets.len());
-        // FIXME: Could go parallel here
         for array_subset in array_subsets {
             // shard (subset)
             let shard_size = array_subset.num_elements_usize() * element_size;
@@ -424,35 +672,24 @@ impl AsyncArrayPartialDecoderTraits for AsyncShardingPartialDecoder<'_> {
                     .collect::<Vec<_>>();
 
             // Decode unfilled chunks
-            let results = futures::future::join_all(
-                chunk_info
-                    .iter()
-                    .filter_map(|(chunk_subset, offset_size)| {
-                        offset_size
-                            .as_ref()
-                            .map(|offset_size| (chunk_subset, offset_size))
-                    })
-                    .map(|(chunk_subset, (offset, size))| async move {
-                        let chunk_representation = unsafe {
-                            ChunkRepresentation::new_unchecked(
-                                self.chunk_grid.chunk_shape().to_vec(),
-                                self.decoded_representation.data_type().clone(),
-                                self.decoded_representation.fill_value().clone(),
-                            )
-                        };
+            let partial_decode_chunk =
+                |(chunk_subset, (offset, size)): (ArraySubset, (usize, usize))| {
+                    let chunk_representation = &chunk_representation;
+                    let codec_options = &codec_options;
+                    async move {
                         let partial_decoder = self
                             .inner_codecs
                             .async_partial_decoder(
                                 Box::new(AsyncByteIntervalPartialDecoder::new(
                                     &*self.input_handle,
-                                    u64::try_from(*offset).unwrap(),
-                                    u64::try_from(*size).unwrap(),
+                                    u64::try_from(offset).unwrap(),
+                                    u64::try_from(size).unwrap(),
                                 )),
-                                &chunk_representation,
-                                options, // FIXME: Adjust options for partial decoding
+                                chunk_representation,
+                                codec_options,
                             )
                             .await?;
-                        let overlap = unsafe { array_subset.overlap_unchecked(chunk_subset) };
+                        let overlap = unsafe { array_subset.overlap_unchecked(&chunk_subset) };
                         let array_subset_in_chunk_subset =
                             unsafe { overlap.relative_to_unchecked(chunk_subset.start()) };
                         // Partial decoding is actually really slow with the blosc codec! Assume sharded chunks are small, and just decode the whole thing and extract bytes
@@ -464,8 +701,8 @@ impl AsyncArrayPartialDecoderTraits for AsyncShardingPartialDecoder<'_> {
                         let decoded_chunk = partial_decoder
                             .partial_decode_opt(
                                 &[ArraySubset::new_with_shape(chunk_subset.shape().to_vec())],
-                                options,
-                            ) // FIXME: Adjust options for partial decoding
+                                codec_options,
+                            )
                             .await?
                             .remove(0);
                         let decoded_chunk = array_subset_in_chunk_subset
@@ -474,10 +711,19 @@ impl AsyncArrayPartialDecoderTraits for AsyncShardingPartialDecoder<'_> {
                         let chunk_subset_in_array_subset =
                             unsafe { overlap.relative_to_unchecked(array_subset.start()) };
                         Ok::<_, CodecError>((chunk_subset_in_array_subset, decoded_chunk))
-                    }),
-            )
-            .await;
-            // FIXME: Concurrency limit for futures
+                    }
+                };
+            let unfilled_chunks = chunk_info
+                .iter()
+                .filter_map(|(chunk_subset, offset_size)| {
+                    offset_size.map(|offset_size| (chunk_subset.clone(), offset_size))
+                })
+                .collect::<Vec<_>>();
+            let results =
+                futures::stream::iter(unfilled_chunks.into_iter().map(partial_decode_chunk))
+                    .buffer_unordered(inner_chunk_concurrent_limit)
+                    .collect::<Vec<_>>()
+                    .await;
 
             if !results.is_empty() {
                 rayon_iter_concurrent_limit::iter_concurrent_limit!(
@@ -0,0 +1,252 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use crate::array::{
+    chunk_shape_to_array_shape,
+    codec::{ArrayCodecTraits, CodecError, CodecOptions},
+    ravel_indices, transmute_to_bytes_vec, ChunkRepresentation, ChunkShape,
+};
+
+use super::{
+    calculate_chunks_per_shard, compute_index_encoded_size, decode_shard_index,
+    sharding_index_decoded_representation, ShardingCodec, ShardingIndexLocation,
+};
+
+/// An incremental writer for a shard encoded with a [`ShardingCodec`].
+///
+/// Inner chunks are encoded and written to the underlying writer as they are appended, and the shard index is only encoded and written on [`finish`](ShardWriter::finish).
+/// This avoids holding the entire decoded or encoded shard in memory.
+///
+/// An inner chunk that is appended more than once is written again and the shard index is updated to reference the latest encoded chunk.
+/// The bytes of the earlier encoded chunk remain in the shard but are no longer referenced.
+pub struct ShardWriter<'a, W: Write + Seek> {
+    codec: &'a ShardingCodec,
+    chunk_representation: ChunkRepresentation,
+    chunks_per_shard: ChunkShape,
+    index_representation: ChunkRepresentation,
+    index_encoded_size: u64,
+    shard_index: Vec<u64>,
+    writer: W,
+    /// The position of the start of the shard in the writer.
+    start: u64,
+    /// The offset of the next inner chunk relative to the start of the shard.
+    offset: u64,
+}
+
+impl<'a, W: Write + Seek> ShardWriter<'a, W> {
+    /// Create a new shard writer for an empty shard.
+    ///
+    /// The shard is written to `writer` starting at its current position.
+    /// If the index is located at the start of the shard, space is reserved for it.
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if
+    ///  - the inner chunk shape of `codec` does not evenly divide the shard shape,
+    ///  - the index codecs of `codec` do not have a fixed size output, or
+    ///  - there is an underlying IO error.
+    pub fn new(
+        codec: &'a ShardingCodec,
+        shard_representation: &ChunkRepresentation,
+        mut writer: W,
+    ) -> Result<Self, CodecError> {
+        let start = writer.stream_position()?;
+        let mut shard_writer = Self::new_empty(codec, shard_representation, writer, start)?;
+        if codec.index_location == ShardingIndexLocation::Start {
+            shard_writer.offset = shard_writer.index_encoded_size;
+            shard_writer
+                .writer
+                .seek(SeekFrom::Start(start + shard_writer.offset))?;
+        }
+        Ok(shard_writer)
+    }
+
+    fn new_empty(
+        codec: &'a ShardingCodec,
+        shard_representation: &ChunkRepresentation,
+        writer: W,
+        start: u64,
+    ) -> Result<Self, CodecError> {
+        let chunks_per_shard =
+            calculate_chunks_per_shard(shard_representation.shape(), codec.chunk_shape.as_slice())
+                .map_err(|e| CodecError::Other(e.to_string()))?;
+        let chunk_representation = unsafe {
+            ChunkRepresentation::new_unchecked(
+                codec.chunk_shape.as_slice().to_vec(),
+                shard_representation.data_type().clone(),
+                shard_representation.fill_value().clone(),
+            )
+        };
+        let index_representation =
+            sharding_index_decoded_representation(chunks_per_shard.as_slice());
+        let index_encoded_size =
+            compute_index_encoded_size(&codec.index_codecs, &index_representation)?;
+        let shard_index = vec![u64::MAX; index_representation.num_elements_usize()];
+        Ok(Self {
+            codec,
+            chunk_representation,
+            chunks_per_shard,
+            index_representation,
+            index_encoded_size,
+            shard_index,
+            writer,
+            start,
+            offset: 0,
+        })
+    }
+
+    /// Return the number of inner chunks along each dimension of the shard.
+    #[must_use]
+    pub fn chunks_per_shard(&self) -> &ChunkShape {
+        &self.chunks_per_shard
+    }
+
+    /// Return the representation of the inner chunks of the shard.
+    #[must_use]
+    pub fn chunk_representation(&self) -> &ChunkRepresentation {
+        &self.chunk_representation
+    }
+
+    /// Encode an inner chunk and append it to the shard.
+    ///
+    /// If `chunk_bytes` is entirely the fill value, nothing is written and the inner chunk is marked as empty in the shard index.
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if
+    ///  - `chunk_indices` are invalid for the shard,
+    ///  - the length of `chunk_bytes` does not match the size of an inner chunk,
+    ///  - the inner codecs fail to encode the chunk, or
+    ///  - there is an underlying IO error.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn append_chunk(
+        &mut self,
+        chunk_indices: &[u64],
+        chunk_bytes: Vec<u8>,
+        options: &CodecOptions,
+    ) -> Result<(), CodecError> {
+        if chunk_indices.len() != self.chunks_per_shard.len()
+            || std::iter::zip(chunk_indices, self.chunks_per_shard.as_slice())
+                .any(|(&i, n)| i >= n.get())
+        {
+            return Err(CodecError::Other(format!(
+                "invalid inner chunk indices {chunk_indices:?} for a shard with {:?} inner chunks",
+                self.chunks_per_shard.as_slice()
+            )));
+        }
+        if chunk_bytes.len() as u64 != self.chunk_representation.size() {
+            return Err(CodecError::UnexpectedChunkDecodedSize(
+                chunk_bytes.len(),
+                self.chunk_representation.size(),
+            ));
+        }
+
+        let chunk_index = usize::try_from(ravel_indices(
+            chunk_indices,
+            &chunk_shape_to_array_shape(self.chunks_per_shard.as_slice()),
+        ))
+        .unwrap(); // safe, validated against chunks per shard
+        if self
+            .chunk_representation
+            .fill_value()
+            .equals_all(&chunk_bytes)
+        {
+            self.shard_index[chunk_index * 2] = u64::MAX;
+            self.shard_index[chunk_index * 2 + 1] = u64::MAX;
+        } else {
+            let chunk_encoded =
+                self.codec
+                    .inner_codecs
+                    .encode(chunk_bytes, &self.chunk_representation, options)?;
+            self.writer.write_all(&chunk_encoded)?;
+            self.shard_index[chunk_index * 2] = self.offset;
+            self.shard_index[chunk_index * 2 + 1] = chunk_encoded.len() as u64;
+            self.offset += chunk_encoded.len() as u64;
+        }
+        Ok(())
+    }
+
+    /// Encode and write the shard index, then flush and return the underlying writer.
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if the index codecs fail to encode the shard index or there is an underlying IO error.
+    pub fn finish(mut self, options: &CodecOptions) -> Result<W, CodecError> {
+        let encoded_shard_index = self.codec.index_codecs.encode(
+            transmute_to_bytes_vec(self.shard_index),
+            &self.index_representation,
+            options,
+        )?;
+        match self.codec.index_location {
+            ShardingIndexLocation::Start => {
+                self.writer.seek(SeekFrom::Start(self.start))?;
+                self.writer.write_all(&encoded_shard_index)?;
+                self.writer
+                    .seek(SeekFrom::Start(self.start + self.offset))?;
+            }
+            ShardingIndexLocation::End => {
+                self.writer.write_all(&encoded_shard_index)?;
+            }
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<'a, W: Read + Write + Seek> ShardWriter<'a, W> {
+    /// Create a new shard writer that appends to an existing encoded shard.
+    ///
+    /// The existing shard spans from the current position of `writer` to its end.
+    /// If it is empty, this is equivalent to [`new`](ShardWriter::new).
+    /// Otherwise, the existing shard index is read and inner chunks are appended without re-encoding the existing inner chunks.
+    /// If the index is located at the end of the shard, the existing index is overwritten by appended inner chunks or the new index.
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if
+    ///  - the inner chunk shape of `codec` does not evenly divide the shard shape,
+    ///  - the index codecs of `codec` do not have a fixed size output,
+    ///  - the existing shard index cannot be decoded, or
+    ///  - there is an underlying IO error.
+    ///
+    /// # Panics
+    /// Panics if the encoded size of the shard index exceeds [`usize::MAX`].
+    pub fn new_append(
+        codec: &'a ShardingCodec,
+        shard_representation: &ChunkRepresentation,
+        mut writer: W,
+        options: &CodecOptions,
+    ) -> Result<Self, CodecError> {
+        let start = writer.stream_position()?;
+        let end = writer.seek(SeekFrom::End(0))?;
+        let shard_size = end.saturating_sub(start);
+        if shard_size == 0 {
+            writer.seek(SeekFrom::Start(start))?;
+            return Self::new(codec, shard_representation, writer);
+        }
+
+        let mut shard_writer = Self::new_empty(codec, shard_representation, writer, start)?;
+        let index_encoded_size = shard_writer.index_encoded_size;
+        if shard_size < index_encoded_size {
+            return Err(CodecError::Other(
+                "The encoded shard is smaller than the expected size of its index.".to_string(),
+            ));
+        }
+
+        // Read and decode the existing shard index
+        let (index_position, offset) = match codec.index_location {
+            ShardingIndexLocation::Start => (start, shard_size),
+            ShardingIndexLocation::End => {
+                (end - index_encoded_size, shard_size - index_encoded_size)
+            }
+        };
+        let mut encoded_shard_index = vec![0; usize::try_from(index_encoded_size).unwrap()];
+        shard_writer.writer.seek(SeekFrom::Start(index_position))?;
+        shard_writer.writer.read_exact(&mut encoded_shard_index)?;
+        shard_writer.shard_index = decode_shard_index(
+            encoded_shard_index.into(),
+            &shard_writer.index_representation,
+            &codec.index_codecs,
+            options,
+        )?;
+
+        shard_writer.offset = offset;
+        shard_writer.writer.seek(SeekFrom::Start(start + offset))?;
+        Ok(shard_writer)
+    }
+}
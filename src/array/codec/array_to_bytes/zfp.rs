@@ -4,7 +4,17 @@
 //!
 //! This codec requires the `zfp` feature, which is disabled by default.
 //!
+//! The fixed rate, fixed precision, fixed accuracy, reversible, and expert modes of zfp are supported.
 //! See [`ZfpCodecConfigurationV1`] for example `JSON` metadata.
+//!
+//! In fixed rate mode (or expert mode where `minbits` equals `maxbits`), every 4^d block is encoded with the same number of bits.
+//! The partial decoder then only decodes the blocks intersecting the requested array subsets, rather than the entire chunk.
+//!
+//! ### Fixed rate mode migration
+//! `zarrs` 0.12 and earlier computed the number of bits per block in fixed rate mode as if every chunk was 3D (`rate * 4^3`), regardless of the chunk dimensionality.
+//! The number of bits per block is now computed from the chunk dimensionality (`rate * 4^d`), so non-3D chunks encoded in fixed rate mode by earlier versions cannot be decoded with the same configuration.
+//! Such data remains readable by setting `"legacy_3d_rate": true` in the fixed rate codec configuration (see [`ZfpFixedRateConfiguration`]), or with [`ZfpCodec::new_fixed_rate_legacy`].
+//! This is not needed for 3D chunks, which are encoded identically with either setting.
 
 mod zfp_bitstream;
 mod zfp_codec;
@@ -13,6 +23,8 @@ mod zfp_field;
 mod zfp_partial_decoder;
 mod zfp_stream;
 
+use std::num::NonZeroU64;
+
 use serde::{Deserialize, Serialize};
 
 pub use zfp_codec::ZfpCodec;
@@ -22,16 +34,22 @@ pub use zfp_configuration::{
 };
 
 use zfp_sys::{
-    zfp_decompress, zfp_exec_policy_zfp_exec_omp, zfp_stream_rewind, zfp_stream_set_bit_stream,
-    zfp_stream_set_execution, zfp_type, zfp_type_zfp_type_double, zfp_type_zfp_type_float,
-    zfp_type_zfp_type_int32, zfp_type_zfp_type_int64,
+    stream_rseek, zfp_decode_block_double_1, zfp_decode_block_double_2, zfp_decode_block_double_3,
+    zfp_decode_block_double_4, zfp_decode_block_float_1, zfp_decode_block_float_2,
+    zfp_decode_block_float_3, zfp_decode_block_float_4, zfp_decode_block_int32_1,
+    zfp_decode_block_int32_2, zfp_decode_block_int32_3, zfp_decode_block_int32_4,
+    zfp_decode_block_int64_1, zfp_decode_block_int64_2, zfp_decode_block_int64_3,
+    zfp_decode_block_int64_4, zfp_decompress, zfp_exec_policy_zfp_exec_omp, zfp_stream_rewind,
+    zfp_stream_set_bit_stream, zfp_stream_set_execution, zfp_type, zfp_type_zfp_type_double,
+    zfp_type_zfp_type_float, zfp_type_zfp_type_int32, zfp_type_zfp_type_int64,
 };
 
 use crate::{
     array::{
         codec::{Codec, CodecError, CodecPlugin},
-        ChunkRepresentation, DataType,
+        ravel_indices, ChunkRepresentation, DataType,
     },
+    array_subset::{ArraySubset, IncompatibleArraySubsetAndShapeError},
     metadata::Metadata,
     plugin::{PluginCreateError, PluginMetadataInvalidError},
 };
@@ -65,6 +83,10 @@ pub enum ZfpMode {
     Expert(ZfpExpertParams),
     /// Fixed rate mode.
     FixedRate(f64),
+    /// Fixed rate mode with the number of bits per block of a 3D array, regardless of the chunk dimensionality.
+    ///
+    /// This is compatible with data encoded in fixed rate mode by `zarrs` 0.12 and earlier.
+    FixedRateLegacy(f64),
     /// Fixed precision mode.
     FixedPrecision(u32),
     /// Fixed accuracy mode.
@@ -73,6 +95,21 @@ pub enum ZfpMode {
     Reversible,
 }
 
+impl ZfpMode {
+    /// Returns true if every 4^d block is encoded with the same number of bits.
+    ///
+    /// This is the case in fixed rate mode, or in expert mode if `minbits` equals `maxbits`.
+    /// Blocks can then be decoded independently without decoding preceding blocks.
+    #[must_use]
+    pub const fn has_fixed_size_blocks(&self) -> bool {
+        match self {
+            Self::FixedRate(_) | Self::FixedRateLegacy(_) => true,
+            Self::Expert(expert) => expert.minbits == expert.maxbits,
+            Self::FixedPrecision(_) | Self::FixedAccuracy(_) | Self::Reversible => false,
+        }
+    }
+}
+
 /// `zfp` expert parameters.
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
 pub struct ZfpExpertParams {
@@ -128,7 +165,8 @@ fn zfp_decode(
     ) else {
         return Err(CodecError::from("failed to create zfp field"));
     };
-    let Some(zfp) = ZfpStream::new(zfp_mode, zfp_type) else {
+    let Some(zfp) = ZfpStream::new(zfp_mode, zfp_type, decoded_representation.dimensionality())
+    else {
         return Err(CodecError::from("failed to create zfp stream"));
     };
 
@@ -155,13 +193,144 @@ fn zfp_decode(
     }
 }
 
+/// The edge length of a `zfp` block.
+const ZFP_BLOCK_EDGE: u64 = 4;
+
+/// Decode the next 4^d block of a `zfp` stream into `block`.
+///
+/// Returns the number of bits consumed, or zero if `zfp_type` or `dims` is unsupported.
+///
+/// # Safety
+/// `block` must point to at least 4^`dims` aligned elements of `zfp_type`.
+#[allow(non_upper_case_globals)]
+unsafe fn zfp_decode_block(
+    zfp: *mut zfp_sys::zfp_stream,
+    zfp_type: zfp_type,
+    dims: usize,
+    block: *mut std::ffi::c_void,
+) -> usize {
+    match (zfp_type, dims) {
+        (zfp_type_zfp_type_int32, 1) => zfp_decode_block_int32_1(zfp, block.cast()),
+        (zfp_type_zfp_type_int32, 2) => zfp_decode_block_int32_2(zfp, block.cast()),
+        (zfp_type_zfp_type_int32, 3) => zfp_decode_block_int32_3(zfp, block.cast()),
+        (zfp_type_zfp_type_int32, 4) => zfp_decode_block_int32_4(zfp, block.cast()),
+        (zfp_type_zfp_type_int64, 1) => zfp_decode_block_int64_1(zfp, block.cast()),
+        (zfp_type_zfp_type_int64, 2) => zfp_decode_block_int64_2(zfp, block.cast()),
+        (zfp_type_zfp_type_int64, 3) => zfp_decode_block_int64_3(zfp, block.cast()),
+        (zfp_type_zfp_type_int64, 4) => zfp_decode_block_int64_4(zfp, block.cast()),
+        (zfp_type_zfp_type_float, 1) => zfp_decode_block_float_1(zfp, block.cast()),
+        (zfp_type_zfp_type_float, 2) => zfp_decode_block_float_2(zfp, block.cast()),
+        (zfp_type_zfp_type_float, 3) => zfp_decode_block_float_3(zfp, block.cast()),
+        (zfp_type_zfp_type_float, 4) => zfp_decode_block_float_4(zfp, block.cast()),
+        (zfp_type_zfp_type_double, 1) => zfp_decode_block_double_1(zfp, block.cast()),
+        (zfp_type_zfp_type_double, 2) => zfp_decode_block_double_2(zfp, block.cast()),
+        (zfp_type_zfp_type_double, 3) => zfp_decode_block_double_3(zfp, block.cast()),
+        (zfp_type_zfp_type_double, 4) => zfp_decode_block_double_4(zfp, block.cast()),
+        _ => 0,
+    }
+}
+
+/// Decode `decoded_regions` of a `zfp` encoded value by only decoding the 4^d blocks intersecting each region.
+///
+/// This is only supported if `zfp_mode` encodes blocks with a fixed number of bits (see [`ZfpMode::has_fixed_size_blocks`]).
+/// Otherwise, blocks cannot be located without decoding all preceding blocks.
+fn zfp_partial_decode(
+    zfp_mode: &ZfpMode,
+    zfp_type: zfp_type,
+    mut encoded_value: Vec<u8>,
+    decoded_representation: &ChunkRepresentation,
+    decoded_regions: &[ArraySubset],
+) -> Result<Vec<Vec<u8>>, CodecError> {
+    let shape = decoded_representation.shape_u64();
+    for decoded_region in decoded_regions {
+        if !decoded_region.inbounds(&shape) {
+            return Err(CodecError::InvalidArraySubsetError(
+                IncompatibleArraySubsetAndShapeError::new(decoded_region.clone(), shape.clone()),
+            ));
+        }
+    }
+
+    let dims = decoded_representation.dimensionality();
+    let Some(zfp) = ZfpStream::new(zfp_mode, zfp_type, dims) else {
+        return Err(CodecError::from("failed to create zfp stream"));
+    };
+    let Some(block_bits) = zfp.fixed_block_bits() else {
+        return Err(CodecError::from(
+            "zfp blocks are not encoded with a fixed number of bits",
+        ));
+    };
+    let Some(stream) = ZfpBitstream::new(&mut encoded_value) else {
+        return Err(CodecError::from("failed to create zfp field"));
+    };
+    unsafe {
+        zfp_stream_set_bit_stream(zfp.as_zfp_stream(), stream.as_bitstream());
+    }
+
+    let element_size = decoded_representation.element_size();
+    let block_shape = vec![ZFP_BLOCK_EDGE; dims];
+    let block_shape_nonzero = vec![NonZeroU64::new(ZFP_BLOCK_EDGE).unwrap(); dims];
+    let block_grid_shape = shape
+        .iter()
+        .map(|size| (size + ZFP_BLOCK_EDGE - 1) / ZFP_BLOCK_EDGE)
+        .collect::<Vec<_>>();
+    let block_len = block_shape.iter().product::<u64>();
+    // The block is u64 backed so that it is aligned for all zfp types
+    let mut block = vec![0u64; usize::try_from(block_len).unwrap()];
+
+    let mut out = Vec::with_capacity(decoded_regions.len());
+    for decoded_region in decoded_regions {
+        let mut decoded = vec![0u8; decoded_region.num_elements_usize() * element_size];
+        if decoded_region.is_empty() {
+            out.push(decoded);
+            continue;
+        }
+        for (block_indices, block_subset) in
+            &unsafe { decoded_region.chunks_unchecked(&block_shape_nonzero) }
+        {
+            let block_index = ravel_indices(&block_indices, &block_grid_shape);
+            let bits = unsafe {
+                stream_rseek(stream.as_bitstream(), block_index * block_bits);
+                zfp_decode_block(
+                    zfp.as_zfp_stream(),
+                    zfp_type,
+                    dims,
+                    block.as_mut_ptr().cast(),
+                )
+            };
+            if bits == 0 {
+                return Err(CodecError::from("zfp block decompression failed"));
+            }
+
+            let overlap = unsafe { decoded_region.overlap_unchecked(&block_subset) };
+            let block_bytes = &bytemuck::cast_slice::<u64, u8>(&block)
+                [..usize::try_from(block_len).unwrap() * element_size];
+            let overlap_bytes = unsafe {
+                overlap
+                    .relative_to_unchecked(block_subset.start())
+                    .extract_bytes_unchecked(block_bytes, &block_shape, element_size)
+            };
+            unsafe {
+                overlap
+                    .relative_to_unchecked(decoded_region.start())
+                    .store_bytes_unchecked(
+                        &overlap_bytes,
+                        &mut decoded,
+                        decoded_region.shape(),
+                        element_size,
+                    );
+            }
+        }
+        out.push(decoded);
+    }
+    Ok(out)
+}
+
 #[cfg(test)]
 mod tests {
     use std::num::NonZeroU64;
 
-    use crate::{
-        array::codec::{ArrayCodecTraits, ArrayToBytesCodecTraits, CodecOptions},
-        array_subset::ArraySubset,
+    use crate::array::codec::{
+        ArrayCodecTraits, ArrayToBytesCodecTraits, CodecOptions, CodecTraits,
     };
 
     use super::*;
@@ -196,7 +365,7 @@ mod tests {
             .unwrap();
         let decoded = codec
             .decode(
-                encoded.clone(),
+                encoded.into(),
                 &chunk_representation,
                 &CodecOptions::default(),
             )
@@ -206,6 +375,128 @@ mod tests {
         assert_eq!(elements, decoded_elements);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_zfp_modes_round_trip() {
+        let chunk_shape = vec![NonZeroU64::new(7).unwrap(), NonZeroU64::new(9).unwrap()];
+        let chunk_representation =
+            ChunkRepresentation::new(chunk_shape, DataType::Float64, 0.0f64.into()).unwrap();
+        let elements: Vec<f64> = (0..63).map(|i| f64::from(i) / 10.0).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements.clone());
+
+        for (codec, tolerance) in [
+            (ZfpCodec::new_fixed_rate(32.0), 1e-3),
+            (ZfpCodec::new_fixed_rate_legacy(32.0), 1e-3),
+            (ZfpCodec::new_fixed_precision(32), 1e-3),
+            (ZfpCodec::new_fixed_accuracy(1e-3), 1e-3),
+            (ZfpCodec::new_reversible(), 0.0),
+            (
+                ZfpCodec::new_expert(ZfpExpertParams {
+                    minbits: 1,
+                    maxbits: 16658,
+                    maxprec: 64,
+                    minexp: -1074,
+                }),
+                1e-12,
+            ),
+        ] {
+            let configuration: ZfpCodecConfiguration =
+                codec.create_metadata().unwrap().to_configuration().unwrap();
+            let codec = ZfpCodec::new_with_configuration(&configuration);
+            let encoded = codec
+                .encode(
+                    bytes.clone(),
+                    &chunk_representation,
+                    &CodecOptions::default(),
+                )
+                .unwrap();
+            let decoded = codec
+                .decode(
+                    encoded.into(),
+                    &chunk_representation,
+                    &CodecOptions::default(),
+                )
+                .unwrap();
+            let decoded_elements = crate::array::transmute_from_bytes_vec::<f64>(decoded);
+            for (element, decoded_element) in std::iter::zip(&elements, &decoded_elements) {
+                assert!((element - decoded_element).abs() <= tolerance);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_zfp_fixed_rate_partial_decode_blocks() {
+        let chunk_shape = vec![NonZeroU64::new(10).unwrap(), NonZeroU64::new(7).unwrap()];
+        let chunk_representation =
+            ChunkRepresentation::new(chunk_shape, DataType::Float32, 0.0f32.into()).unwrap();
+        let elements: Vec<f32> = (0..70u8).map(f32::from).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        for codec in [
+            ZfpCodec::new_fixed_rate(12.0),
+            ZfpCodec::new_fixed_rate_legacy(12.0),
+            ZfpCodec::new_expert(ZfpExpertParams {
+                minbits: 256,
+                maxbits: 256,
+                maxprec: 32,
+                minexp: -149,
+            }),
+        ] {
+            assert!(!codec.partial_decoder_decodes_all());
+            let encoded = codec
+                .encode(
+                    bytes.clone(),
+                    &chunk_representation,
+                    &CodecOptions::default(),
+                )
+                .unwrap();
+            let decoded = codec
+                .decode(
+                    encoded.clone().into(),
+                    &chunk_representation,
+                    &CodecOptions::default(),
+                )
+                .unwrap();
+
+            let decoded_regions = [
+                ArraySubset::new_with_ranges(&[0..10, 0..7]),
+                ArraySubset::new_with_ranges(&[3..9, 2..7]),
+                ArraySubset::new_with_ranges(&[8..10, 6..7]),
+                ArraySubset::new_with_ranges(&[5..5, 0..7]),
+            ];
+            let input_handle = Box::new(std::io::Cursor::new(encoded));
+            let partial_decoder = codec
+                .partial_decoder(
+                    input_handle,
+                    &chunk_representation,
+                    &CodecOptions::default(),
+                )
+                .unwrap();
+            let decoded_partial_chunk = partial_decoder
+                .partial_decode_opt(&decoded_regions, &CodecOptions::default())
+                .unwrap();
+            for (decoded_region, decoded_partial) in
+                std::iter::zip(&decoded_regions, decoded_partial_chunk)
+            {
+                assert_eq!(
+                    decoded_region
+                        .extract_bytes(&decoded, &[10, 7], std::mem::size_of::<f32>())
+                        .unwrap(),
+                    decoded_partial
+                );
+            }
+
+            assert!(partial_decoder
+                .partial_decode_opt(
+                    &[ArraySubset::new_with_ranges(&[8..11, 0..7])],
+                    &CodecOptions::default()
+                )
+                .is_err());
+        }
+        assert!(ZfpCodec::new_fixed_precision(12).partial_decoder_decodes_all());
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn codec_zfp_partial_decode() {
@@ -1,3 +1,4 @@
+use bytes::Bytes;
 use zfp_sys::{
     zfp_compress,
     zfp_stream_maximum_size,
@@ -57,6 +58,16 @@ impl ZfpCodec {
         }
     }
 
+    /// Create a new `Zfp` codec in fixed rate mode with the number of bits per block of a 3D array, regardless of the chunk dimensionality.
+    ///
+    /// This is compatible with data encoded in fixed rate mode by `zarrs` 0.12 and earlier.
+    #[must_use]
+    pub const fn new_fixed_rate_legacy(rate: f64) -> Self {
+        Self {
+            mode: ZfpMode::FixedRateLegacy(rate),
+        }
+    }
+
     /// Create a new `Zfp` codec in fixed precision mode.
     #[must_use]
     pub const fn new_fixed_precision(precision: u32) -> Self {
@@ -88,7 +99,13 @@ impl ZfpCodec {
         let ZfpCodecConfiguration::V1(configuration) = configuration;
         match configuration {
             V1::Expert(cfg) => Self::new_expert(*cfg),
-            V1::FixedRate(cfg) => Self::new_fixed_rate(cfg.rate),
+            V1::FixedRate(cfg) => {
+                if cfg.legacy_3d_rate {
+                    Self::new_fixed_rate_legacy(cfg.rate)
+                } else {
+                    Self::new_fixed_rate(cfg.rate)
+                }
+            }
             V1::FixedPrecision(cfg) => Self::new_fixed_precision(cfg.precision),
             V1::FixedAccuracy(cfg) => Self::new_fixed_accuracy(cfg.tolerance),
             V1::Reversible => Self::new_reversible(),
@@ -101,7 +118,16 @@ impl CodecTraits for ZfpCodec {
         let configuration = match self.mode {
             ZfpMode::Expert(expert) => ZfpCodecConfigurationV1::Expert(expert),
             ZfpMode::FixedRate(rate) => {
-                ZfpCodecConfigurationV1::FixedRate(ZfpFixedRateConfiguration { rate })
+                ZfpCodecConfigurationV1::FixedRate(ZfpFixedRateConfiguration {
+                    rate,
+                    legacy_3d_rate: false,
+                })
+            }
+            ZfpMode::FixedRateLegacy(rate) => {
+                ZfpCodecConfigurationV1::FixedRate(ZfpFixedRateConfiguration {
+                    rate,
+                    legacy_3d_rate: true,
+                })
             }
             ZfpMode::FixedPrecision(precision) => {
                 ZfpCodecConfigurationV1::FixedPrecision(ZfpFixedPrecisionConfiguration {
@@ -121,7 +147,7 @@ impl CodecTraits for ZfpCodec {
     }
 
     fn partial_decoder_decodes_all(&self) -> bool {
-        true
+        !self.mode.has_fixed_size_blocks()
     }
 }
 
@@ -157,7 +183,11 @@ impl ArrayCodecTraits for ZfpCodec {
         ) else {
             return Err(CodecError::from("failed to create zfp field"));
         };
-        let Some(zfp) = ZfpStream::new(&self.mode, zfp_type) else {
+        let Some(zfp) = ZfpStream::new(
+            &self.mode,
+            zfp_type,
+            decoded_representation.dimensionality(),
+        ) else {
             return Err(CodecError::from("failed to create zfp stream"));
         };
 
@@ -192,7 +222,7 @@ impl ArrayCodecTraits for ZfpCodec {
 
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         decoded_representation: &ChunkRepresentation,
         _options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
@@ -205,7 +235,7 @@ impl ArrayCodecTraits for ZfpCodec {
         zfp_decode(
             &self.mode,
             zfp_type,
-            encoded_value,
+            Vec::from(encoded_value),
             decoded_representation,
             false, // FIXME
         )
@@ -28,6 +28,18 @@ pub enum ZfpCodecConfiguration {
 /// # let configuration: zarrs::array::codec::ZfpCodecConfigurationV1 = serde_json::from_str(JSON).unwrap();
 /// ```
 ///
+/// ### Decode data encoded in fixed rate mode by `zarrs` 0.12 and earlier
+/// ```rust
+/// # let JSON = r#"
+/// {
+///     "mode": "fixedrate",
+///     "rate": 10.5,
+///     "legacy_3d_rate": true
+/// }
+/// # "#;
+/// # let configuration: zarrs::array::codec::ZfpCodecConfigurationV1 = serde_json::from_str(JSON).unwrap();
+/// ```
+///
 /// ### Encode in fixed precision mode with 19 uncompressed bits per value
 /// ```rust
 /// # let JSON = r#"
@@ -97,9 +109,16 @@ pub struct ZfpExpertConfiguration {
 
 /// The `zfp` configuration for fixed rate mode.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Display)]
+#[display(fmt = "{}", rate)]
 pub struct ZfpFixedRateConfiguration {
     /// The rate is the number of compressed bits per value.
     pub rate: f64,
+    /// If true, the number of bits per block is that of a 3D array (`rate * 4^3`), regardless of the chunk dimensionality.
+    ///
+    /// This is compatible with data encoded in fixed rate mode by `zarrs` 0.12 and earlier, which always used the number of bits per block of a 3D array.
+    /// Defaults to false, where the number of bits per block is `rate * 4^d` for a chunk with `d` dimensions.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub legacy_3d_rate: bool,
 }
 
 /// The `zfp` configuration for fixed precision mode.
@@ -141,6 +160,21 @@ mod tests {
         serde_json::from_str::<ZfpCodecConfiguration>(JSON).unwrap();
     }
 
+    #[test]
+    fn codec_zfp_configuration_fixed_rate_legacy() {
+        const JSON: &'static str = r#"{
+        "mode": "fixedrate",
+        "rate": 12,
+        "legacy_3d_rate": true
+    }"#;
+        let ZfpCodecConfiguration::V1(ZfpCodecConfigurationV1::FixedRate(configuration)) =
+            serde_json::from_str::<ZfpCodecConfiguration>(JSON).unwrap()
+        else {
+            panic!()
+        };
+        assert!(configuration.legacy_3d_rate);
+    }
+
     #[test]
     fn codec_zfp_configuration_fixed_precision() {
         const JSON: &'static str = r#"{
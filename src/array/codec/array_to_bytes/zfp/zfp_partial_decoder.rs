@@ -12,7 +12,7 @@ use crate::{
 #[cfg(feature = "async")]
 use crate::array::codec::{AsyncArrayPartialDecoderTraits, AsyncBytesPartialDecoderTraits};
 
-use super::{zarr_data_type_to_zfp_data_type, zfp_decode, ZfpMode};
+use super::{zarr_data_type_to_zfp_data_type, zfp_decode, zfp_partial_decode, ZfpMode};
 
 /// Partial decoder for the `zfp` codec.
 pub struct ZfpPartialDecoder<'a> {
@@ -70,11 +70,21 @@ impl ArrayPartialDecoderTraits for ZfpPartialDecoder<'_> {
         let mut out = Vec::with_capacity(decoded_regions.len());
         let chunk_shape = self.decoded_representation.shape_u64();
         match encoded_value {
+            Some(encoded_value) if self.mode.has_fixed_size_blocks() => {
+                // Only decode the blocks intersecting the decoded regions
+                out.extend(zfp_partial_decode(
+                    &self.mode,
+                    self.zfp_type,
+                    Vec::from(encoded_value),
+                    &self.decoded_representation,
+                    decoded_regions,
+                )?);
+            }
             Some(encoded_value) => {
                 let decoded_value = zfp_decode(
                     &self.mode,
                     self.zfp_type,
-                    encoded_value,
+                    Vec::from(encoded_value),
                     &self.decoded_representation,
                     false, // FIXME
                 )?;
@@ -164,11 +174,21 @@ impl AsyncArrayPartialDecoderTraits for AsyncZfpPartialDecoder<'_> {
         let chunk_shape = self.decoded_representation.shape_u64();
         let mut out = Vec::with_capacity(decoded_regions.len());
         match encoded_value {
+            Some(encoded_value) if self.mode.has_fixed_size_blocks() => {
+                // Only decode the blocks intersecting the decoded regions
+                out.extend(zfp_partial_decode(
+                    &self.mode,
+                    self.zfp_type,
+                    Vec::from(encoded_value),
+                    &self.decoded_representation,
+                    decoded_regions,
+                )?);
+            }
             Some(encoded_value) => {
                 let decoded_value = zfp_decode(
                     &self.mode,
                     self.zfp_type,
-                    encoded_value,
+                    Vec::from(encoded_value),
                     &self.decoded_representation,
                     false, // FIXME
                 )?;
@@ -20,7 +20,7 @@ impl Drop for ZfpStream {
 }
 
 impl ZfpStream {
-    pub fn new(mode: &ZfpMode, type_: zfp_type) -> Option<Self> {
+    pub fn new(mode: &ZfpMode, type_: zfp_type, dims: usize) -> Option<Self> {
         let zfp = unsafe { zfp_stream_open(std::ptr::null_mut()) };
         match mode {
             ZfpMode::Expert(expert) => {
@@ -35,6 +35,10 @@ impl ZfpStream {
                 };
             }
             ZfpMode::FixedRate(rate) => {
+                let dims = u32::try_from(dims).ok()?;
+                unsafe { zfp_stream_set_rate(zfp, *rate, type_, dims, 0) };
+            }
+            ZfpMode::FixedRateLegacy(rate) => {
                 unsafe { zfp_stream_set_rate(zfp, *rate, type_, 3, 0) };
             }
             ZfpMode::FixedPrecision(precision) => unsafe {
@@ -57,4 +61,12 @@ impl ZfpStream {
     pub const fn as_zfp_stream(&self) -> *mut zfp_stream {
         self.0.as_ptr()
     }
+
+    /// Return the number of bits of every encoded block if blocks are encoded with a fixed number of bits.
+    ///
+    /// This is the case in fixed rate mode, or in expert mode if `minbits` equals `maxbits`.
+    pub fn fixed_block_bits(&self) -> Option<u64> {
+        let zfp = unsafe { self.0.as_ref() };
+        (zfp.minbits == zfp.maxbits).then_some(u64::from(zfp.maxbits))
+    }
 }
@@ -2,9 +2,11 @@
 
 use std::marker::PhantomData;
 
+use bytes::Bytes;
+
 use crate::{
     array::MaybeBytes,
-    byte_range::{extract_byte_ranges, ByteRange},
+    byte_range::{extract_byte_ranges_bytes, ByteRange},
 };
 
 use super::{BytesPartialDecoderTraits, CodecError, CodecOptions};
@@ -61,10 +63,15 @@ impl BytesPartialDecoderTraits for BytesPartialDecoderCache<'_> {
         &self,
         decoded_regions: &[ByteRange],
         _options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            byte_ranges = decoded_regions.len(),
+            "bytes partial decoder cache hit"
+        );
         Ok(match &self.cache {
             Some(bytes) => Some(
-                extract_byte_ranges(bytes, decoded_regions)
+                extract_byte_ranges_bytes(bytes, decoded_regions)
                     .map_err(CodecError::InvalidByteRangeError)?,
             ),
             None => None,
@@ -79,7 +86,7 @@ impl AsyncBytesPartialDecoderTraits for BytesPartialDecoderCache<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
         BytesPartialDecoderTraits::partial_decode(self, decoded_regions, options)
     }
 }
@@ -2,12 +2,18 @@
 
 #[cfg(feature = "blosc")]
 pub mod blosc;
+#[cfg(feature = "blosc2")]
+pub mod blosc2;
 #[cfg(feature = "bz2")]
 pub mod bz2;
 #[cfg(feature = "crc32c")]
 pub mod crc32c;
 #[cfg(feature = "gzip")]
 pub mod gzip;
+#[cfg(feature = "shuffle")]
+pub mod shuffle;
+#[cfg(feature = "snappy")]
+pub mod snappy;
 #[cfg(feature = "zstd")]
 pub mod zstd;
 
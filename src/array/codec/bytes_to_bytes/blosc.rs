@@ -347,7 +347,11 @@ mod tests {
             .encode(bytes.clone(), &CodecOptions::default())
             .unwrap();
         let decoded = codec
-            .decode(encoded, &bytes_representation, &CodecOptions::default())
+            .decode(
+                encoded.into(),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
             .unwrap();
         assert_eq!(bytes, decoded);
     }
@@ -367,7 +371,11 @@ mod tests {
             .encode(bytes.clone(), &CodecOptions::default())
             .unwrap();
         let decoded = codec
-            .decode(encoded, &bytes_representation, &CodecOptions::default())
+            .decode(
+                encoded.into(),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
             .unwrap();
         assert_eq!(bytes, decoded);
     }
@@ -1,6 +1,7 @@
 use std::ffi::c_char;
 
 use blosc_sys::{blosc_get_complib_info, BLOSC_MAX_OVERHEAD};
+use bytes::Bytes;
 
 use crate::{
     array::{
@@ -162,7 +163,7 @@ impl BytesToBytesCodecTraits for BloscCodec {
 
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         _decoded_representation: &BytesRepresentation,
         _options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
@@ -1,3 +1,5 @@
+use bytes::Bytes;
+
 use crate::{
     array::codec::{
         bytes_to_bytes::blosc::blosc_nbytes, BytesPartialDecoderTraits, CodecError, CodecOptions,
@@ -26,7 +28,7 @@ impl BytesPartialDecoderTraits for BloscPartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
         let encoded_value = self.input_handle.decode(options)?;
         let Some(encoded_value) = encoded_value else {
             return Ok(None);
@@ -40,7 +42,7 @@ impl BytesPartialDecoderTraits for BloscPartialDecoder<'_> {
                 for byte_range in decoded_regions {
                     let start = usize::try_from(byte_range.start(nbytes as u64)).unwrap();
                     let end = usize::try_from(byte_range.end(nbytes as u64)).unwrap();
-                    decoded_byte_ranges.push(
+                    decoded_byte_ranges.push(Bytes::from(
                         blosc_decompress_bytes_partial(
                             &encoded_value,
                             start,
@@ -48,7 +50,7 @@ impl BytesPartialDecoderTraits for BloscPartialDecoder<'_> {
                             typesize,
                         )
                         .map_err(|err| CodecError::from(err.to_string()))?,
-                    );
+                    ));
                 }
                 return Ok(Some(decoded_byte_ranges));
             }
@@ -77,7 +79,7 @@ impl AsyncBytesPartialDecoderTraits for AsyncBloscPartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
         let encoded_value = self.input_handle.decode(options).await?;
         let Some(encoded_value) = encoded_value else {
             return Ok(None);
@@ -91,7 +93,7 @@ impl AsyncBytesPartialDecoderTraits for AsyncBloscPartialDecoder<'_> {
                 for byte_range in decoded_regions {
                     let start = usize::try_from(byte_range.start(nbytes as u64)).unwrap();
                     let end = usize::try_from(byte_range.end(nbytes as u64)).unwrap();
-                    decoded_byte_ranges.push(
+                    decoded_byte_ranges.push(Bytes::from(
                         blosc_decompress_bytes_partial(
                             &encoded_value,
                             start,
@@ -99,7 +101,7 @@ impl AsyncBytesPartialDecoderTraits for AsyncBloscPartialDecoder<'_> {
                             typesize,
                         )
                         .map_err(|err| CodecError::from(err.to_string()))?,
-                    );
+                    ));
                 }
                 return Ok(Some(decoded_byte_ranges));
             }
@@ -0,0 +1,565 @@
+//! The `blosc2` bytes to bytes codec.
+//!
+//! It uses the [blosc2](https://www.blosc.org/) chunk format.
+//!
+//! This codec requires the `blosc2` feature, which is disabled by default.
+//!
+//! Blosc2 partitions each encoded chunk into blocks which are compressed independently.
+//! The partial decoder only decompresses the blocks intersecting the requested byte ranges, rather than the entire chunk.
+//! A smaller `blocksize` therefore reduces the cost of reading a small region of a chunk at the expense of compression ratio.
+//! Combined with the [`sharding`](crate::array::codec::array_to_bytes::sharding) codec, this gives a three level partitioning (shard, inner chunk, block).
+//!
+//! Blocks are contiguous byte ranges of the decoded chunk, so a region of a multidimensional chunk may intersect many blocks.
+//! The [`blosc2_nd`](crate::array::codec::array_to_bytes::blosc2_nd) array to bytes codec instead partitions chunks into multidimensional blocks with the `b2nd` API of blosc2.
+//!
+//! The codec is not yet standardised, so the configuration and identifier may change.
+//!
+//! ### Codec `configuration` Example - [`Blosc2CodecConfiguration`]:
+//! ```rust
+//! # let JSON = r#"
+//! {
+//!     "cname": "zstd",
+//!     "clevel": 5,
+//!     "shuffle": "shuffle",
+//!     "typesize": 4,
+//!     "blocksize": 16384
+//! }
+//! # "#;
+//! # let configuration: zarrs::array::codec::Blosc2CodecConfiguration = serde_json::from_str(JSON).unwrap();
+//! ```
+
+mod blosc2_codec;
+mod blosc2_configuration;
+mod blosc2_partial_decoder;
+
+use std::ffi::{c_char, c_int, c_void};
+
+pub use blosc2_codec::Blosc2Codec;
+pub use blosc2_configuration::{Blosc2CodecConfiguration, Blosc2CodecConfigurationV1};
+use blosc2_sys::{
+    blosc1_cbuffer_metainfo, blosc1_cbuffer_validate, blosc2_compress_ctx, blosc2_context,
+    blosc2_cparams, blosc2_create_cctx, blosc2_create_dctx, blosc2_decompress_ctx, blosc2_dparams,
+    blosc2_free_ctx, blosc2_get_blosc2_cparams_defaults, blosc2_get_blosc2_dparams_defaults,
+    blosc2_get_complib_info, blosc2_getitem_ctx, BLOSC2_MAX_BUFFERSIZE, BLOSC2_MAX_FILTERS,
+    BLOSC2_MAX_OVERHEAD, BLOSC_BITSHUFFLE, BLOSC_BLOSCLZ, BLOSC_BLOSCLZ_COMPNAME, BLOSC_LZ4,
+    BLOSC_LZ4HC, BLOSC_LZ4HC_COMPNAME, BLOSC_LZ4_COMPNAME, BLOSC_NOSHUFFLE, BLOSC_SHUFFLE,
+    BLOSC_ZLIB, BLOSC_ZLIB_COMPNAME, BLOSC_ZSTD, BLOSC_ZSTD_COMPNAME,
+};
+use derive_more::From;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    array::codec::{Codec, CodecPlugin},
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+/// The identifier for the `blosc2` codec.
+pub const IDENTIFIER: &str = "blosc2";
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_blosc2, create_codec_blosc2)
+}
+
+fn is_name_blosc2(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+}
+
+pub(crate) fn create_codec_blosc2(metadata: &Metadata) -> Result<Codec, PluginCreateError> {
+    let configuration: Blosc2CodecConfiguration = metadata
+        .to_configuration()
+        .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?;
+    let codec = Box::new(Blosc2Codec::new_with_configuration(&configuration)?);
+    Ok(Codec::BytesToBytes(codec))
+}
+
+#[derive(Debug, Error, From)]
+#[error("{0}")]
+pub(crate) struct Blosc2Error(String);
+
+impl From<&str> for Blosc2Error {
+    fn from(err: &str) -> Self {
+        Self(err.to_string())
+    }
+}
+
+/// An integer from 0 to 9 controlling the compression level
+///
+/// A level of 1 is the fastest compression method and produces the least compressions, while 9 is slowest and produces the most compression.
+/// Compression is turned off when the compression level is 0.
+#[derive(Serialize, Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Blosc2CompressionLevel(u8);
+
+impl TryFrom<u8> for Blosc2CompressionLevel {
+    type Error = u8;
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        if level <= 9 {
+            Ok(Self(level))
+        } else {
+            Err(level)
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Blosc2CompressionLevel {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let level = u8::deserialize(d)?;
+        if level <= 9 {
+            Ok(Self(level))
+        } else {
+            Err(serde::de::Error::custom("clevel must be between 0 and 9"))
+        }
+    }
+}
+
+/// The `blosc2` shuffle mode.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+#[repr(u32)]
+pub enum Blosc2ShuffleMode {
+    /// No shuffling.
+    NoShuffle = BLOSC_NOSHUFFLE,
+    /// Byte-wise shuffling.
+    Shuffle = BLOSC_SHUFFLE,
+    /// Bit-wise shuffling.
+    BitShuffle = BLOSC_BITSHUFFLE,
+}
+
+/// The `blosc2` compressor.
+///
+/// See <https://www.blosc.org/pages/>.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Blosc2Compressor {
+    /// [BloscLZ](https://github.com/Blosc/c-blosc2/blob/main/blosc/blosclz.h): blosc default compressor, heavily based on [FastLZ](http://fastlz.org/).
+    BloscLZ,
+    /// [LZ4](http://fastcompression.blogspot.com/p/lz4.html): a compact, very popular and fast compressor.
+    LZ4,
+    /// [LZ4HC](http://fastcompression.blogspot.com/p/lz4.html): a tweaked version of LZ4, produces better compression ratios at the expense of speed.
+    LZ4HC,
+    /// [Zlib](http://www.zlib.net/): a classic; somewhat slower than the previous ones, but achieving better compression ratios.
+    Zlib,
+    /// [Zstd](http://www.zstd.net/): an extremely well balanced codec; it provides the best compression ratios among the others above, and at reasonably fast speed.
+    Zstd,
+}
+
+impl Blosc2Compressor {
+    const fn as_cstr(&self) -> *const u8 {
+        match self {
+            Self::BloscLZ => BLOSC_BLOSCLZ_COMPNAME.as_ptr(),
+            Self::LZ4 => BLOSC_LZ4_COMPNAME.as_ptr(),
+            Self::LZ4HC => BLOSC_LZ4HC_COMPNAME.as_ptr(),
+            Self::Zlib => BLOSC_ZLIB_COMPNAME.as_ptr(),
+            Self::Zstd => BLOSC_ZSTD_COMPNAME.as_ptr(),
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    const fn compcode(self) -> u8 {
+        (match self {
+            Self::BloscLZ => BLOSC_BLOSCLZ,
+            Self::LZ4 => BLOSC_LZ4,
+            Self::LZ4HC => BLOSC_LZ4HC,
+            Self::Zlib => BLOSC_ZLIB,
+            Self::Zstd => BLOSC_ZSTD,
+        }) as u8
+    }
+}
+
+/// Returns single-threaded `blosc2` compression parameters.
+///
+/// The block size is chosen automatically if `blocksize` is zero.
+pub(crate) fn blosc2_compression_params(
+    clevel: Blosc2CompressionLevel,
+    shuffle_mode: Blosc2ShuffleMode,
+    typesize: usize,
+    compressor: Blosc2Compressor,
+    blocksize: usize,
+) -> Result<blosc2_cparams, Blosc2Error> {
+    let mut cparams = unsafe { blosc2_get_blosc2_cparams_defaults() };
+    cparams.compcode = compressor.compcode();
+    cparams.clevel = clevel.0;
+    cparams.typesize = i32::try_from(typesize.max(1))
+        .map_err(|_| Blosc2Error::from(format!("typesize {typesize} is too large")))?;
+    cparams.blocksize = i32::try_from(blocksize)
+        .map_err(|_| Blosc2Error::from(format!("blocksize {blocksize} is too large")))?;
+    cparams.nthreads = 1;
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        cparams.filters[BLOSC2_MAX_FILTERS as usize - 1] = shuffle_mode as u8;
+    }
+    Ok(cparams)
+}
+
+/// Returns single-threaded `blosc2` decompression parameters.
+pub(crate) fn blosc2_decompression_params() -> blosc2_dparams {
+    let mut dparams = unsafe { blosc2_get_blosc2_dparams_defaults() };
+    dparams.nthreads = 1;
+    dparams
+}
+
+/// A `blosc2` compression or decompression context, freed on drop.
+struct Blosc2Context(*mut blosc2_context);
+
+impl Blosc2Context {
+    fn new_compression(
+        clevel: Blosc2CompressionLevel,
+        shuffle_mode: Blosc2ShuffleMode,
+        typesize: usize,
+        compressor: Blosc2Compressor,
+        blocksize: usize,
+    ) -> Result<Self, Blosc2Error> {
+        let cparams =
+            blosc2_compression_params(clevel, shuffle_mode, typesize, compressor, blocksize)?;
+        let context = unsafe { blosc2_create_cctx(cparams) };
+        if context.is_null() {
+            Err(Blosc2Error::from("blosc2_create_cctx failed"))
+        } else {
+            Ok(Self(context))
+        }
+    }
+
+    fn new_decompression() -> Result<Self, Blosc2Error> {
+        let context = unsafe { blosc2_create_dctx(blosc2_decompression_params()) };
+        if context.is_null() {
+            Err(Blosc2Error::from("blosc2_create_dctx failed"))
+        } else {
+            Ok(Self(context))
+        }
+    }
+}
+
+impl Drop for Blosc2Context {
+    fn drop(&mut self) {
+        unsafe {
+            blosc2_free_ctx(self.0);
+        }
+    }
+}
+
+fn blosc2_compress_bytes(
+    src: &[u8],
+    clevel: Blosc2CompressionLevel,
+    shuffle_mode: Blosc2ShuffleMode,
+    typesize: usize,
+    compressor: Blosc2Compressor,
+    blocksize: usize,
+) -> Result<Vec<u8>, Blosc2Error> {
+    if src.len() > BLOSC2_MAX_BUFFERSIZE as usize {
+        return Err(Blosc2Error::from(format!(
+            "blosc2 cannot encode {} bytes, the maximum is {BLOSC2_MAX_BUFFERSIZE}",
+            src.len()
+        )));
+    }
+    let context =
+        Blosc2Context::new_compression(clevel, shuffle_mode, typesize, compressor, blocksize)?;
+
+    let destsize = src.len() + BLOSC2_MAX_OVERHEAD as usize;
+    let mut dest: Vec<u8> = Vec::with_capacity(destsize);
+    let destsize = unsafe {
+        blosc2_compress_ctx(
+            context.0,
+            src.as_ptr().cast::<c_void>(),
+            i32::try_from(src.len()).unwrap(),
+            dest.as_mut_ptr().cast::<c_void>(),
+            i32::try_from(destsize).unwrap(),
+        )
+    };
+    if destsize > 0 {
+        unsafe {
+            #[allow(clippy::cast_sign_loss)]
+            dest.set_len(destsize as usize);
+        }
+        dest.shrink_to_fit();
+        Ok(dest)
+    } else {
+        Err(Blosc2Error::from(format!("blosc2_compress_ctx(clevel: {}, shuffle: {shuffle_mode:?}, typesize: {typesize}, nbytes: {}, compressor {compressor:?}, blocksize: {blocksize}) -> {destsize} (failure)", clevel.0, src.len())))
+    }
+}
+
+/// Returns the length of the decompressed bytes of a `blosc2` buffer if it is valid.
+fn blosc2_validate(src: &[u8]) -> Option<usize> {
+    let mut destsize: usize = 0;
+    let valid = unsafe {
+        blosc1_cbuffer_validate(
+            src.as_ptr().cast::<c_void>(),
+            src.len(),
+            std::ptr::addr_of_mut!(destsize),
+        )
+    } == 0;
+    valid.then_some(destsize)
+}
+
+/// # Safety
+///
+/// Validate first
+fn blosc2_typesize(src: &[u8]) -> Option<usize> {
+    let mut typesize: usize = 0;
+    let mut flags: c_int = 0;
+    unsafe {
+        blosc1_cbuffer_metainfo(
+            src.as_ptr().cast::<c_void>(),
+            std::ptr::addr_of_mut!(typesize),
+            std::ptr::addr_of_mut!(flags),
+        );
+    };
+    (typesize != 0).then_some(typesize)
+}
+
+fn blosc2_decompress_bytes(src: &[u8], destsize: usize) -> Result<Vec<u8>, Blosc2Error> {
+    let context = Blosc2Context::new_decompression()?;
+    let mut dest: Vec<u8> = Vec::with_capacity(destsize);
+    let destsize = unsafe {
+        blosc2_decompress_ctx(
+            context.0,
+            src.as_ptr().cast::<c_void>(),
+            i32::try_from(src.len()).unwrap_or(i32::MAX),
+            dest.as_mut_ptr().cast::<c_void>(),
+            i32::try_from(destsize).unwrap(),
+        )
+    };
+    if destsize >= 0 {
+        unsafe {
+            #[allow(clippy::cast_sign_loss)]
+            dest.set_len(destsize as usize);
+        }
+        Ok(dest)
+    } else {
+        Err(Blosc2Error::from("blosc2_decompress_ctx failed"))
+    }
+}
+
+/// Decompress `length` bytes from `offset` in the decoded `blosc2` buffer `src`.
+///
+/// Only the blocks intersecting the byte range are decompressed.
+/// The byte range does not need to be aligned to `typesize`.
+fn blosc2_decompress_bytes_partial(
+    context: &Blosc2Context,
+    src: &[u8],
+    offset: usize,
+    length: usize,
+    typesize: usize,
+) -> Result<Vec<u8>, Blosc2Error> {
+    if length == 0 {
+        return Ok(vec![]);
+    }
+    let item_start = offset / typesize;
+    let item_end = (offset + length + typesize - 1) / typesize;
+    let start = i32::try_from(item_start).unwrap();
+    let nitems = i32::try_from(item_end - item_start).unwrap();
+    let destsize = (item_end - item_start) * typesize;
+    let mut dest: Vec<u8> = Vec::with_capacity(destsize);
+    let destsize = unsafe {
+        blosc2_getitem_ctx(
+            context.0,
+            src.as_ptr().cast::<c_void>(),
+            i32::try_from(src.len()).unwrap_or(i32::MAX),
+            start,
+            nitems,
+            dest.as_mut_ptr().cast::<c_void>(),
+            i32::try_from(destsize).unwrap(),
+        )
+    };
+    if destsize <= 0 {
+        Err(Blosc2Error::from(format!(
+            "blosc2_getitem_ctx(src: len {}, start: {start}, nitems: {nitems}) -> {destsize} (failure)",
+            src.len()
+        )))
+    } else {
+        unsafe {
+            #[allow(clippy::cast_sign_loss)]
+            dest.set_len(destsize as usize);
+        }
+        let skip = offset - item_start * typesize;
+        dest.drain(..skip);
+        dest.truncate(length);
+        Ok(dest)
+    }
+}
+
+/// Returns true if `compressor` is included in the `blosc2` build.
+pub(crate) fn blosc2_compressor_is_supported(compressor: Blosc2Compressor) -> bool {
+    let support = unsafe {
+        blosc2_get_complib_info(
+            compressor.as_cstr().cast::<c_char>(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    support >= 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{
+            codec::{BytesToBytesCodecTraits, CodecOptions},
+            ArrayRepresentation, BytesRepresentation, DataType, FillValue,
+        },
+        array_subset::ArraySubset,
+        byte_range::ByteRange,
+    };
+
+    use super::*;
+
+    const JSON_VALID: &str = r#"
+{
+    "cname": "lz4",
+    "clevel": 5,
+    "shuffle": "shuffle",
+    "typesize": 2,
+    "blocksize": 16
+}"#;
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc2_round_trip() {
+        let elements: Vec<u16> = (0..1024).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec_configuration: Blosc2CodecConfiguration =
+            serde_json::from_str(JSON_VALID).unwrap();
+        let codec = Blosc2Codec::new_with_configuration(&codec_configuration).unwrap();
+
+        let encoded = codec
+            .encode(bytes.clone(), &CodecOptions::default())
+            .unwrap();
+        let decoded = codec
+            .decode(
+                encoded.into(),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc2_partial_decode() {
+        let array_representation =
+            ArrayRepresentation::new(vec![8, 8, 8], DataType::UInt16, FillValue::from(0u16))
+                .unwrap();
+        let bytes_representation = BytesRepresentation::FixedSize(array_representation.size());
+
+        let elements: Vec<u16> = (0..array_representation.num_elements() as u16).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec_configuration: Blosc2CodecConfiguration =
+            serde_json::from_str(JSON_VALID).unwrap();
+        let codec = Blosc2Codec::new_with_configuration(&codec_configuration).unwrap();
+
+        let encoded = codec.encode(bytes, &CodecOptions::default()).unwrap();
+        let decoded_regions: Vec<ByteRange> = ArraySubset::new_with_ranges(&[2..4, 1..3, 5..7])
+            .byte_ranges(
+                array_representation.shape(),
+                array_representation.element_size(),
+            )
+            .unwrap();
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .unwrap()
+            .unwrap();
+
+        let decoded: Vec<u16> = decoded
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .chunks(std::mem::size_of::<u16>())
+            .map(|b| u16::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+
+        let answer: Vec<u16> = [2u16, 3]
+            .iter()
+            .flat_map(|z| [1u16, 2].map(|y| (z, y)))
+            .flat_map(|(z, y)| [5u16, 6].map(|x| z * 64 + y * 8 + x))
+            .collect();
+        assert_eq!(answer, decoded);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc2_partial_decode_unaligned() {
+        let elements: Vec<u32> = (0..256).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec = Blosc2Codec::new(
+            Blosc2Compressor::Zstd,
+            Blosc2CompressionLevel::try_from(5).unwrap(),
+            Some(64),
+            Blosc2ShuffleMode::Shuffle,
+            Some(4),
+        )
+        .unwrap();
+        let encoded = codec
+            .encode(bytes.clone(), &CodecOptions::default())
+            .unwrap();
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_regions = [
+            ByteRange::FromStart(3, Some(70)),
+            ByteRange::FromStart(100, Some(0)),
+            ByteRange::FromEnd(0, Some(5)),
+        ];
+        let decoded = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded[0], bytes[3..73]);
+        assert!(decoded[1].is_empty());
+        assert_eq!(decoded[2], bytes[bytes.len() - 5..]);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn codec_blosc2_async_partial_decode() {
+        let elements: Vec<u16> = (0..1024).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec_configuration: Blosc2CodecConfiguration =
+            serde_json::from_str(JSON_VALID).unwrap();
+        let codec = Blosc2Codec::new_with_configuration(&codec_configuration).unwrap();
+
+        let encoded = codec
+            .encode(bytes.clone(), &CodecOptions::default())
+            .unwrap();
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .async_partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .await
+            .unwrap();
+        let decoded = partial_decoder
+            .partial_decode(
+                &[ByteRange::FromStart(40, Some(100))],
+                &CodecOptions::default(),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded[0], bytes[40..140]);
+    }
+}
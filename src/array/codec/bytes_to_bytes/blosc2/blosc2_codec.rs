@@ -0,0 +1,181 @@
+use bytes::Bytes;
+
+use blosc2_sys::BLOSC2_MAX_OVERHEAD;
+
+use crate::{
+    array::{
+        codec::{
+            BytesPartialDecoderTraits, BytesToBytesCodecTraits, CodecError, CodecOptions,
+            CodecTraits, RecommendedConcurrency,
+        },
+        BytesRepresentation,
+    },
+    metadata::Metadata,
+    plugin::PluginCreateError,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+use super::{
+    blosc2_compress_bytes, blosc2_compressor_is_supported, blosc2_decompress_bytes,
+    blosc2_partial_decoder, blosc2_validate, Blosc2CodecConfiguration, Blosc2CodecConfigurationV1,
+    Blosc2CompressionLevel, Blosc2Compressor, Blosc2Error, Blosc2ShuffleMode, IDENTIFIER,
+};
+
+/// A `blosc2` codec implementation.
+#[derive(Clone, Debug)]
+pub struct Blosc2Codec {
+    configuration: Blosc2CodecConfigurationV1,
+}
+
+impl Blosc2Codec {
+    /// Create a new `blosc2` codec.
+    ///
+    /// The block size is chosen automatically if `blocksize` is none.
+    /// `typesize` must be a positive integer if shuffling is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PluginCreateError`] if
+    ///  - the compressor is not supported, or
+    ///  - `typesize` is [`None`] and shuffling is enabled.
+    pub fn new(
+        cname: Blosc2Compressor,
+        clevel: Blosc2CompressionLevel,
+        blocksize: Option<usize>,
+        shuffle_mode: Blosc2ShuffleMode,
+        typesize: Option<usize>,
+    ) -> Result<Self, PluginCreateError> {
+        if shuffle_mode != Blosc2ShuffleMode::NoShuffle && typesize.is_none() {
+            return Err(PluginCreateError::from(
+                "typesize is a positive integer required if shuffle mode is not none.",
+            ));
+        }
+
+        if !blosc2_compressor_is_supported(cname) {
+            return Err(PluginCreateError::from(format!(
+                "compressor {cname:?} is not supported."
+            )));
+        }
+
+        let configuration = Blosc2CodecConfigurationV1 {
+            cname,
+            clevel,
+            blocksize,
+            shuffle: shuffle_mode,
+            typesize: typesize.unwrap_or_default(),
+        };
+
+        Ok(Self { configuration })
+    }
+
+    /// Create a new `blosc2` codec from configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PluginCreateError`] if the configuration is not supported.
+    pub fn new_with_configuration(
+        configuration: &Blosc2CodecConfiguration,
+    ) -> Result<Self, PluginCreateError> {
+        let Blosc2CodecConfiguration::V1(configuration) = configuration;
+        Self::new(
+            configuration.cname,
+            configuration.clevel,
+            configuration.blocksize,
+            configuration.shuffle,
+            Some(configuration.typesize),
+        )
+    }
+}
+
+impl CodecTraits for Blosc2Codec {
+    fn create_metadata(&self) -> Option<Metadata> {
+        Some(
+            Metadata::new_with_serializable_configuration(IDENTIFIER, &self.configuration).unwrap(),
+        )
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        false
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl BytesToBytesCodecTraits for Blosc2Codec {
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &BytesRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+
+    fn encode(
+        &self,
+        decoded_value: Vec<u8>,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        blosc2_compress_bytes(
+            &decoded_value,
+            self.configuration.clevel,
+            self.configuration.shuffle,
+            self.configuration.typesize,
+            self.configuration.cname,
+            self.configuration.blocksize.unwrap_or(0),
+        )
+        .map_err(|err: Blosc2Error| CodecError::Other(err.to_string()))
+    }
+
+    fn decode(
+        &self,
+        encoded_value: Bytes,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        blosc2_validate(&encoded_value).map_or_else(
+            || Err(CodecError::from("blosc2 encoded value is invalid")),
+            |destsize| {
+                blosc2_decompress_bytes(&encoded_value, destsize)
+                    .map_err(|err| CodecError::from(err.to_string()))
+            },
+        )
+    }
+
+    fn partial_decoder<'a>(
+        &'a self,
+        input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn BytesPartialDecoderTraits + 'a>, CodecError> {
+        Ok(Box::new(blosc2_partial_decoder::Blosc2PartialDecoder::new(
+            input_handle,
+        )))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder<'a>(
+        &'a self,
+        input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn AsyncBytesPartialDecoderTraits + 'a>, CodecError> {
+        Ok(Box::new(
+            blosc2_partial_decoder::AsyncBlosc2PartialDecoder::new(input_handle),
+        ))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        decoded_representation: &BytesRepresentation,
+    ) -> BytesRepresentation {
+        decoded_representation
+            .size()
+            .map_or(BytesRepresentation::UnboundedSize, |size| {
+                BytesRepresentation::BoundedSize(size + u64::from(BLOSC2_MAX_OVERHEAD))
+            })
+    }
+}
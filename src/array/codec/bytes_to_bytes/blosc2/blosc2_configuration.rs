@@ -0,0 +1,62 @@
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+use super::{Blosc2CompressionLevel, Blosc2Compressor, Blosc2ShuffleMode};
+
+/// A wrapper to handle various versions of `blosc2` codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum Blosc2CodecConfiguration {
+    /// Version 1.0.
+    V1(Blosc2CodecConfigurationV1),
+}
+
+/// Configuration parameters for the `blosc2` codec (version 1.0).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
+pub struct Blosc2CodecConfigurationV1 {
+    /// The compressor.
+    pub cname: Blosc2Compressor,
+    /// The compression level.
+    pub clevel: Blosc2CompressionLevel,
+    /// The shuffle mode.
+    pub shuffle: Blosc2ShuffleMode,
+    /// The type size in bytes.
+    pub typesize: usize,
+    /// The compression block size in bytes. Automatically determined if [`None`].
+    ///
+    /// Blocks are the unit of partial decompression.
+    pub blocksize: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec_blosc2_valid() {
+        serde_json::from_str::<Blosc2CodecConfiguration>(
+            r#"
+        {
+            "cname": "zstd",
+            "clevel": 5,
+            "shuffle": "bitshuffle",
+            "typesize": 4,
+            "blocksize": 16384
+        }"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn codec_blosc2_invalid() {
+        for json in [
+            r#"{"cname": "snappy", "clevel": 5, "shuffle": "shuffle", "typesize": 4, "blocksize": 0}"#,
+            r#"{"cname": "lz4", "clevel": 10, "shuffle": "shuffle", "typesize": 4, "blocksize": 0}"#,
+            r#"{"cname": "lz4", "clevel": 5, "shuffle": "", "typesize": 4, "blocksize": 0}"#,
+        ] {
+            assert!(serde_json::from_str::<Blosc2CodecConfiguration>(json).is_err());
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use bytes::Bytes;
+
+use crate::{
+    array::codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
+    byte_range::{ByteRange, InvalidByteRangeError},
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+use super::{blosc2_decompress_bytes_partial, blosc2_typesize, blosc2_validate, Blosc2Context};
+
+/// Decode the `decoded_regions` of a `blosc2` encoded value, only decompressing the blocks intersecting each region.
+fn blosc2_partial_decode(
+    encoded_value: &[u8],
+    decoded_regions: &[ByteRange],
+) -> Result<Vec<Bytes>, CodecError> {
+    let (Some(nbytes), Some(typesize)) = (
+        blosc2_validate(encoded_value),
+        blosc2_typesize(encoded_value),
+    ) else {
+        return Err(CodecError::from("blosc2 encoded value is invalid"));
+    };
+    let context =
+        Blosc2Context::new_decompression().map_err(|err| CodecError::from(err.to_string()))?;
+    let mut decoded_byte_ranges = Vec::with_capacity(decoded_regions.len());
+    for byte_range in decoded_regions {
+        let (ByteRange::FromStart(offset, length) | ByteRange::FromEnd(offset, length)) =
+            byte_range;
+        if offset + length.unwrap_or(0) > nbytes as u64 {
+            return Err(InvalidByteRangeError::new(*byte_range, nbytes as u64).into());
+        }
+        let start = usize::try_from(byte_range.start(nbytes as u64)).unwrap();
+        let end = usize::try_from(byte_range.end(nbytes as u64)).unwrap();
+        decoded_byte_ranges.push(Bytes::from(
+            blosc2_decompress_bytes_partial(&context, encoded_value, start, end - start, typesize)
+                .map_err(|err| CodecError::from(err.to_string()))?,
+        ));
+    }
+    Ok(decoded_byte_ranges)
+}
+
+/// Partial decoder for the `blosc2` codec.
+pub struct Blosc2PartialDecoder<'a> {
+    input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+}
+
+impl<'a> Blosc2PartialDecoder<'a> {
+    pub fn new(input_handle: Box<dyn BytesPartialDecoderTraits + 'a>) -> Self {
+        Self { input_handle }
+    }
+}
+
+impl BytesPartialDecoderTraits for Blosc2PartialDecoder<'_> {
+    fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options)?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+        Ok(Some(blosc2_partial_decode(
+            &encoded_value,
+            decoded_regions,
+        )?))
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `blosc2` codec.
+pub struct AsyncBlosc2PartialDecoder<'a> {
+    input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+}
+
+#[cfg(feature = "async")]
+impl<'a> AsyncBlosc2PartialDecoder<'a> {
+    pub fn new(input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>) -> Self {
+        Self { input_handle }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncBytesPartialDecoderTraits for AsyncBlosc2PartialDecoder<'_> {
+    async fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options).await?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+        Ok(Some(blosc2_partial_decode(
+            &encoded_value,
+            decoded_regions,
+        )?))
+    }
+}
@@ -1,12 +1,29 @@
 //! The `bz2` (bzip2) bytes to bytes codec.
+//!
+//! Applies [bzip2](https://sourceware.org/bzip2/) compression, compatible with [`numcodecs.BZ2`](https://numcodecs.readthedocs.io/en/latest/bz2.html).
+//!
+//! This codec requires the `bz2` feature, which is disabled by default.
+//!
+//! The codec is not yet standardised, so the configuration and identifier may change.
+//!
+//! ### Codec `configuration` Example - [`Bz2CodecConfiguration`]:
+//! ```rust
+//! # let JSON = r#"
+//! {
+//!     "level": 9
+//! }
+//! # "#;
+//! # let configuration: zarrs::array::codec::Bz2CodecConfiguration = serde_json::from_str(JSON).unwrap();
+//! ```
 
 mod bz2_codec;
 mod bz2_configuration;
 mod bz2_partial_decoder;
 
-use derive_more::From;
+pub use bz2_codec::Bz2Codec;
+pub use bz2_configuration::{Bz2CodecConfiguration, Bz2CodecConfigurationV1};
+
 use serde::{Deserialize, Deserializer, Serialize};
-use thiserror::Error;
 
 use crate::{
     array::codec::{Codec, CodecPlugin},
@@ -14,8 +31,6 @@ use crate::{
     plugin::{PluginCreateError, PluginMetadataInvalidError},
 };
 
-use self::{bz2_codec::Bz2Codec, bz2_configuration::Bz2CodecConfiguration};
-
 /// The identifier for the `bz2` codec.
 pub const IDENTIFIER: &str = "bz2";
 
@@ -36,16 +51,6 @@ pub(crate) fn create_codec_bz2(metadata: &Metadata) -> Result<Codec, PluginCreat
     Ok(Codec::BytesToBytes(codec))
 }
 
-#[derive(Debug, Error, From)]
-#[error("{0}")]
-struct Bz2Error(String);
-
-impl From<&str> for Bz2Error {
-    fn from(err: &str) -> Self {
-        Self(err.to_string())
-    }
-}
-
 /// An integer from 0 to 9 controlling the compression level
 ///
 /// A level of 1 is the fastest compression method and produces the least compressions, while 9 is slowest and produces the most compression.
@@ -142,11 +147,44 @@ mod tests {
             .encode(bytes.clone(), &CodecOptions::default())
             .unwrap();
         let decoded = codec
-            .decode(encoded, &bytes_representation, &CodecOptions::default())
+            .decode(
+                encoded.into(),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
             .unwrap();
         assert_eq!(bytes, decoded);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_bz2_encoded_size_bound() {
+        // Pseudo-random bytes are incompressible
+        let mut state = 1u32;
+        let bytes: Vec<u8> = (0..100_000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state.to_ne_bytes()[0]
+            })
+            .collect();
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+        for level in [1u32, 9] {
+            let codec = Bz2Codec::new(Bz2CompressionLevel::new(level).unwrap());
+            let BytesRepresentation::BoundedSize(bound) =
+                codec.compute_encoded_size(&bytes_representation)
+            else {
+                panic!()
+            };
+            let encoded = codec
+                .encode(bytes.clone(), &CodecOptions::default())
+                .unwrap();
+            assert!(encoded.len() as u64 > bytes.len() as u64);
+            assert!(encoded.len() as u64 <= bound);
+        }
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn codec_bz2_partial_decode() {
@@ -241,4 +279,51 @@ mod tests {
         let answer: Vec<u16> = vec![2, 6];
         assert_eq!(answer, decoded);
     }
+
+    #[cfg(feature = "sharding")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_bz2_sharded_array() {
+        use std::sync::Arc;
+
+        use crate::{
+            array::{codec::array_to_bytes::sharding::ShardingCodecBuilder, Array, ArrayBuilder},
+            storage::store::MemoryStore,
+        };
+
+        let store = Arc::new(MemoryStore::new());
+        let mut sharding_codec_builder = ShardingCodecBuilder::new(vec![4, 4].try_into().unwrap());
+        sharding_codec_builder.bytes_to_bytes_codecs(vec![Box::new(Bz2Codec::new(
+            Bz2CompressionLevel::new(9u32).unwrap(),
+        ))]);
+        let array = ArrayBuilder::new(
+            vec![16, 16],
+            DataType::UInt16,
+            vec![8, 8].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .array_to_bytes_codec(Box::new(sharding_codec_builder.build()))
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        let elements: Vec<u16> = (0..256).collect();
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_shape(vec![16, 16]), elements)
+            .unwrap();
+
+        let array = Array::new(store, "/array").unwrap();
+        let subset = ArraySubset::new_with_ranges(&[3..6, 5..7]);
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<u16>(&subset)
+                .unwrap(),
+            vec![53, 54, 69, 70, 85, 86]
+        );
+        let partial_decoder = array.partial_decoder(&[0, 0]).unwrap();
+        let decoded = partial_decoder.partial_decode(&[subset]).unwrap().remove(0);
+        assert_eq!(
+            crate::array::transmute_from_bytes_vec::<u16>(decoded),
+            vec![53, 54, 69, 70, 85, 86]
+        );
+    }
 }
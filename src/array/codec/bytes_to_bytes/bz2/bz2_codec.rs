@@ -1,5 +1,7 @@
 use std::io::Read;
 
+use bytes::Bytes;
+
 use crate::{
     array::{
         codec::{
@@ -27,15 +29,14 @@ pub struct Bz2Codec {
 
 impl Bz2Codec {
     /// Create a new `bz2` codec.
+    #[must_use]
     pub fn new(level: Bz2CompressionLevel) -> Self {
         let compression = bzip2::Compression::new(level.as_u32());
         Self { compression }
     }
 
     /// Create a new `bz2` codec from configuration.
-    ///
-    /// # Errors
-    /// Returns [`PluginCreateError`] if the configuration is not supported.
+    #[must_use]
     pub fn new_with_configuration(configuration: &Bz2CodecConfiguration) -> Self {
         let Bz2CodecConfiguration::V1(configuration) = configuration;
         Self::new(configuration.level)
@@ -82,11 +83,11 @@ impl BytesToBytesCodecTraits for Bz2Codec {
 
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         _decoded_representation: &BytesRepresentation,
         _options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
-        let mut decoder = bzip2::read::BzDecoder::new(encoded_value.as_slice());
+        let mut decoder = bzip2::read::BzDecoder::new(&encoded_value[..]);
         let mut out: Vec<u8> = Vec::new();
         decoder.read_to_end(&mut out)?;
         Ok(out)
@@ -122,9 +123,9 @@ impl BytesToBytesCodecTraits for Bz2Codec {
         decoded_representation
             .size()
             .map_or(BytesRepresentation::UnboundedSize, |size| {
-                // https://en.wikipedia.org/wiki/Bzip2#Implementation
-                // TODO: Below assumes a maximum expansion of 1.25 for the blocks + header (4 byte) + footer (11 byte), but need to read spec
-                BytesRepresentation::BoundedSize(4 + 11 + size + (size + 3) / 4)
+                // The worst case bzip2 expansion is 1% plus 600 bytes
+                // https://sourceware.org/bzip2/manual/manual.html#util-fns
+                BytesRepresentation::BoundedSize(size + (size + 99) / 100 + 600)
             })
     }
 }
@@ -1,8 +1,10 @@
 use std::io::Read;
 
+use bytes::Bytes;
+
 use crate::{
     array::codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
-    byte_range::{extract_byte_ranges, ByteRange},
+    byte_range::{extract_byte_ranges_bytes, ByteRange},
 };
 
 #[cfg(feature = "async")]
@@ -24,18 +26,18 @@ impl BytesPartialDecoderTraits for Bz2PartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
         let encoded_value = self.input_handle.decode(options)?;
         let Some(encoded_value) = encoded_value else {
             return Ok(None);
         };
 
-        let mut decoder = bzip2::read::BzDecoder::new(encoded_value.as_slice());
+        let mut decoder = bzip2::read::BzDecoder::new(&encoded_value[..]);
         let mut decompressed = Vec::new();
         decoder.read_to_end(&mut decompressed)?;
 
         Ok(Some(
-            extract_byte_ranges(&decompressed, decoded_regions)
+            extract_byte_ranges_bytes(&Bytes::from(decompressed), decoded_regions)
                 .map_err(CodecError::InvalidByteRangeError)?,
         ))
     }
@@ -61,18 +63,18 @@ impl AsyncBytesPartialDecoderTraits for AsyncBz2PartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
         let encoded_value = self.input_handle.decode(options).await?;
         let Some(encoded_value) = encoded_value else {
             return Ok(None);
         };
 
-        let mut decoder = bzip2::read::BzDecoder::new(encoded_value.as_slice());
+        let mut decoder = bzip2::read::BzDecoder::new(&encoded_value[..]);
         let mut decompressed = Vec::new();
         decoder.read_to_end(&mut decompressed)?;
 
         Ok(Some(
-            extract_byte_ranges(&decompressed, decoded_regions)
+            extract_byte_ranges_bytes(&Bytes::from(decompressed), decoded_regions)
                 .map_err(CodecError::InvalidByteRangeError)?,
         ))
     }
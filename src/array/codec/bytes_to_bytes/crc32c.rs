@@ -2,6 +2,9 @@
 //!
 //! Appends a CRC32C checksum of the input bytestream.
 //!
+//! Partial decoding retrieves only the requested byte ranges of the encoded value.
+//! The checksum is only validated if a requested byte range covers the entire value.
+//!
 //! See <https://zarr-specs.readthedocs.io/en/latest/v3/codecs/crc32c/v1.0.html>.
 
 mod crc32c_codec;
@@ -46,7 +49,7 @@ const CHECKSUM_SIZE: usize = core::mem::size_of::<u32>();
 mod tests {
     use crate::{
         array::{
-            codec::{BytesToBytesCodecTraits, CodecOptions, CodecTraits},
+            codec::{BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits},
             BytesRepresentation,
         },
         byte_range::ByteRange,
@@ -81,7 +84,7 @@ mod tests {
             .unwrap();
         let decoded = codec
             .decode(
-                encoded.clone(),
+                encoded.clone().into(),
                 &bytes_representation,
                 &CodecOptions::default(),
             )
@@ -124,6 +127,72 @@ mod tests {
         assert_eq!(answer, decoded_partial_chunk);
     }
 
+    #[test]
+    fn codec_crc32c_partial_decode_byte_ranges() {
+        let bytes: Vec<u8> = (0..32).collect();
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+        let codec = Crc32cCodec::new();
+
+        let encoded = codec
+            .encode(bytes.clone(), &CodecOptions::default())
+            .unwrap();
+        let decoded_regions = [
+            ByteRange::FromEnd(0, Some(2)),
+            ByteRange::FromEnd(2, None),
+            ByteRange::FromStart(30, None),
+            ByteRange::FromStart(0, None),
+            ByteRange::FromEnd(0, None),
+        ];
+        let input_handle = Box::new(std::io::Cursor::new(encoded.clone()));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .unwrap()
+            .unwrap();
+        let answer: &[Vec<u8>] = &[
+            vec![30, 31],
+            bytes[..30].to_vec(),
+            vec![30, 31],
+            bytes.clone(),
+            bytes,
+        ];
+        assert_eq!(answer, decoded_partial_chunk);
+
+        // The checksum is validated if the entire value is decoded
+        let mut corrupted = encoded;
+        *corrupted.last_mut().unwrap() ^= 1;
+        let input_handle = Box::new(std::io::Cursor::new(corrupted));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert!(partial_decoder
+            .partial_decode(
+                &[ByteRange::FromStart(3, Some(2))],
+                &CodecOptions::default()
+            )
+            .is_ok());
+        assert!(matches!(
+            partial_decoder
+                .partial_decode(&[ByteRange::FromStart(0, None)], &CodecOptions::default()),
+            Err(CodecError::InvalidChecksum)
+        ));
+        let mut options = CodecOptions::default();
+        options.set_validate_checksums(false);
+        assert!(partial_decoder
+            .partial_decode(&[ByteRange::FromStart(0, None)], &options)
+            .is_ok());
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn codec_crc32c_async_partial_decode() {
@@ -1,3 +1,5 @@
+use bytes::Bytes;
+
 use crate::{
     array::{
         codec::{
@@ -72,7 +74,7 @@ impl BytesToBytesCodecTraits for Crc32cCodec {
 
     fn decode(
         &self,
-        mut encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         _decoded_representation: &BytesRepresentation,
         options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
@@ -84,8 +86,9 @@ impl BytesToBytesCodecTraits for Crc32cCodec {
                     return Err(CodecError::InvalidChecksum);
                 }
             }
-            encoded_value.resize_with(encoded_value.len() - CHECKSUM_SIZE, Default::default);
-            Ok(encoded_value)
+            let mut decoded_value = Vec::from(encoded_value);
+            decoded_value.truncate(decoded_value.len() - CHECKSUM_SIZE);
+            Ok(decoded_value)
         } else {
             Err(CodecError::Other(
                 "CRC32C checksum decoder expects a 32 bit input".to_string(),
@@ -1,3 +1,5 @@
+use bytes::Bytes;
+
 use crate::{
     array::codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
     byte_range::ByteRange,
@@ -8,6 +10,60 @@ use crate::array::codec::AsyncBytesPartialDecoderTraits;
 
 use super::CHECKSUM_SIZE;
 
+/// Return true if `byte_range` covers the entire decoded value.
+fn is_entire_value(byte_range: &ByteRange) -> bool {
+    matches!(
+        byte_range,
+        ByteRange::FromStart(0, None) | ByteRange::FromEnd(0, None)
+    )
+}
+
+/// Map a byte range of the decoded value to a byte range of the encoded value.
+///
+/// Byte ranges from the end are offset by the trailing checksum.
+/// Byte ranges that cover the entire value include the checksum so that it can be validated.
+fn encoded_byte_range(byte_range: &ByteRange) -> ByteRange {
+    if is_entire_value(byte_range) {
+        ByteRange::FromStart(0, None)
+    } else {
+        match *byte_range {
+            ByteRange::FromStart(offset, length) => ByteRange::FromStart(offset, length),
+            ByteRange::FromEnd(offset, length) => {
+                ByteRange::FromEnd(offset + CHECKSUM_SIZE as u64, length)
+            }
+        }
+    }
+}
+
+/// Decode the bytes of an encoded byte range retrieved with [`encoded_byte_range`].
+///
+/// The checksum is validated if `byte_range` covers the entire value and checksum validation is enabled.
+fn decode_byte_range(
+    mut bytes: Bytes,
+    byte_range: &ByteRange,
+    options: &CodecOptions,
+) -> Result<Bytes, CodecError> {
+    match byte_range {
+        ByteRange::FromStart(_, None) | ByteRange::FromEnd(0, None) => {
+            if bytes.len() < CHECKSUM_SIZE {
+                return Err(CodecError::Other(
+                    "CRC32C checksum decoder expects a 32 bit input".to_string(),
+                ));
+            }
+            let length = bytes.len() - CHECKSUM_SIZE;
+            if is_entire_value(byte_range) && options.validate_checksums() {
+                let checksum = crc32c::crc32c(&bytes[..length]).to_le_bytes();
+                if checksum != bytes[length..] {
+                    return Err(CodecError::InvalidChecksum);
+                }
+            }
+            bytes.truncate(length);
+        }
+        ByteRange::FromStart(_, Some(_)) | ByteRange::FromEnd(_, _) => {}
+    }
+    Ok(bytes)
+}
+
 /// Partial decoder for the `CRC32C checksum` codec.
 pub struct Crc32cPartialDecoder<'a> {
     input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
@@ -25,27 +81,20 @@ impl BytesPartialDecoderTraits for Crc32cPartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
-        let bytes = self.input_handle.partial_decode(decoded_regions, options)?;
-        let Some(mut bytes) = bytes else {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        let encoded_regions: Vec<ByteRange> =
+            decoded_regions.iter().map(encoded_byte_range).collect();
+        let bytes = self
+            .input_handle
+            .partial_decode(&encoded_regions, options)?;
+        let Some(bytes) = bytes else {
             return Ok(None);
         };
 
-        // Drop trailing checksum
-        for (bytes, byte_range) in bytes.iter_mut().zip(decoded_regions) {
-            match byte_range {
-                ByteRange::FromStart(_, Some(_)) => {}
-                ByteRange::FromStart(_, None) => {
-                    bytes.resize(bytes.len() - CHECKSUM_SIZE, 0);
-                }
-                ByteRange::FromEnd(offset, _) => {
-                    if *offset < CHECKSUM_SIZE as u64 {
-                        let length = bytes.len() as u64 - (CHECKSUM_SIZE as u64 - offset);
-                        bytes.resize(usize::try_from(length).unwrap(), 0);
-                    }
-                }
-            };
-        }
+        // Validate and drop the trailing checksum
+        let bytes = std::iter::zip(bytes, decoded_regions)
+            .map(|(bytes, byte_range)| decode_byte_range(bytes, byte_range, options))
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Some(bytes))
     }
@@ -72,30 +121,21 @@ impl AsyncBytesPartialDecoderTraits for AsyncCrc32cPartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        let encoded_regions: Vec<ByteRange> =
+            decoded_regions.iter().map(encoded_byte_range).collect();
         let bytes = self
             .input_handle
-            .partial_decode(decoded_regions, options)
+            .partial_decode(&encoded_regions, options)
             .await?;
-        let Some(mut bytes) = bytes else {
+        let Some(bytes) = bytes else {
             return Ok(None);
         };
 
-        // Drop trailing checksum
-        for (bytes, byte_range) in bytes.iter_mut().zip(decoded_regions) {
-            match byte_range {
-                ByteRange::FromStart(_, Some(_)) => {}
-                ByteRange::FromStart(_, None) => {
-                    bytes.resize(bytes.len() - CHECKSUM_SIZE, 0);
-                }
-                ByteRange::FromEnd(offset, _) => {
-                    if *offset < CHECKSUM_SIZE as u64 {
-                        let length = bytes.len() as u64 - (CHECKSUM_SIZE as u64 - offset);
-                        bytes.resize(usize::try_from(length).unwrap(), 0);
-                    }
-                }
-            };
-        }
+        // Validate and drop the trailing checksum
+        let bytes = std::iter::zip(bytes, decoded_regions)
+            .map(|(bytes, byte_range)| decode_byte_range(bytes, byte_range, options))
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Some(bytes))
     }
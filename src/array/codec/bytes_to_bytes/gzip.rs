@@ -89,7 +89,11 @@ mod tests {
             .encode(bytes.clone(), &CodecOptions::default())
             .unwrap();
         let decoded = codec
-            .decode(encoded, &bytes_representation, &CodecOptions::default())
+            .decode(
+                encoded.into(),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
             .unwrap();
         assert_eq!(bytes, decoded);
     }
@@ -1,5 +1,6 @@
 use std::io::{Cursor, Read};
 
+use bytes::Bytes;
 use flate2::bufread::{GzDecoder, GzEncoder};
 
 use crate::{
@@ -90,7 +91,7 @@ impl BytesToBytesCodecTraits for GzipCodec {
 
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         _decoded_representation: &BytesRepresentation,
         _options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
@@ -1,10 +1,11 @@
 use std::io::{Cursor, Read};
 
+use bytes::Bytes;
 use flate2::bufread::GzDecoder;
 
 use crate::{
     array::codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
-    byte_range::{extract_byte_ranges, ByteRange},
+    byte_range::{extract_byte_ranges_bytes, ByteRange},
 };
 
 #[cfg(feature = "async")]
@@ -27,7 +28,7 @@ impl BytesPartialDecoderTraits for GzipPartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
         let encoded_value = self.input_handle.decode(options)?;
         let Some(encoded_value) = encoded_value else {
             return Ok(None);
@@ -38,7 +39,7 @@ impl BytesPartialDecoderTraits for GzipPartialDecoder<'_> {
         decoder.read_to_end(&mut decompressed)?;
 
         Ok(Some(
-            extract_byte_ranges(&decompressed, decoded_regions)
+            extract_byte_ranges_bytes(&Bytes::from(decompressed), decoded_regions)
                 .map_err(CodecError::InvalidByteRangeError)?,
         ))
     }
@@ -65,7 +66,7 @@ impl AsyncBytesPartialDecoderTraits for AsyncGzipPartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
         let encoded_value = self.input_handle.decode(options).await?;
         let Some(encoded_value) = encoded_value else {
             return Ok(None);
@@ -76,7 +77,7 @@ impl AsyncBytesPartialDecoderTraits for AsyncGzipPartialDecoder<'_> {
         decoder.read_to_end(&mut decompressed)?;
 
         Ok(Some(
-            extract_byte_ranges(&decompressed, decoded_regions)
+            extract_byte_ranges_bytes(&Bytes::from(decompressed), decoded_regions)
                 .map_err(CodecError::InvalidByteRangeError)?,
         ))
     }
@@ -0,0 +1,220 @@
+//! The `shuffle` bytes to bytes codec.
+//!
+//! Rearranges the bytes of fixed size elements such that the first byte of every element is stored first, then the second byte of every element, and so on.
+//! This can improve the compression ratio of subsequent compression codecs.
+//! Trailing bytes that do not form a complete element are left unchanged.
+//!
+//! This is a standalone implementation of the byte shuffle filter of `blosc`, compatible with [`numcodecs.Shuffle`](https://numcodecs.readthedocs.io/en/latest/shuffle.html).
+//!
+//! This codec requires the `shuffle` feature, which is disabled by default.
+//!
+//! The codec is not yet standardised, so the configuration and identifier may change.
+//!
+//! ### Codec `configuration` Example - [`ShuffleCodecConfiguration`]:
+//! ```rust
+//! # let JSON = r#"
+//! {
+//!     "elementsize": 4
+//! }
+//! # "#;
+//! # let configuration: zarrs::array::codec::ShuffleCodecConfiguration = serde_json::from_str(JSON).unwrap();
+//! ```
+
+mod shuffle_codec;
+mod shuffle_configuration;
+mod shuffle_partial_decoder;
+
+pub use shuffle_codec::ShuffleCodec;
+pub use shuffle_configuration::{ShuffleCodecConfiguration, ShuffleCodecConfigurationV1};
+
+use crate::{
+    array::codec::{Codec, CodecPlugin},
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+/// The identifier for the `shuffle` codec.
+pub const IDENTIFIER: &str = "shuffle";
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_shuffle, create_codec_shuffle)
+}
+
+fn is_name_shuffle(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+}
+
+pub(crate) fn create_codec_shuffle(metadata: &Metadata) -> Result<Codec, PluginCreateError> {
+    let configuration: ShuffleCodecConfiguration = metadata
+        .to_configuration()
+        .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?;
+    let codec = Box::new(ShuffleCodec::new_with_configuration(&configuration));
+    Ok(Codec::BytesToBytes(codec))
+}
+
+/// Shuffle the bytes of elements with size `element_size`.
+fn shuffle(bytes: &[u8], element_size: usize) -> Vec<u8> {
+    let num_elements = bytes.len() / element_size;
+    let mut shuffled = Vec::with_capacity(bytes.len());
+    for byte in 0..element_size {
+        shuffled.extend((0..num_elements).map(|element| bytes[element * element_size + byte]));
+    }
+    shuffled.extend_from_slice(&bytes[num_elements * element_size..]);
+    shuffled
+}
+
+/// Reverse [`shuffle`].
+fn unshuffle(bytes: &[u8], element_size: usize) -> Vec<u8> {
+    let num_elements = bytes.len() / element_size;
+    let mut unshuffled = Vec::with_capacity(bytes.len());
+    for element in 0..num_elements {
+        unshuffled.extend((0..element_size).map(|byte| bytes[byte * num_elements + element]));
+    }
+    unshuffled.extend_from_slice(&bytes[num_elements * element_size..]);
+    unshuffled
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{
+            codec::{BytesToBytesCodecTraits, CodecOptions},
+            BytesRepresentation,
+        },
+        byte_range::ByteRange,
+    };
+
+    use super::*;
+
+    const JSON_VALID: &str = r#"{
+        "elementsize": 2
+    }"#;
+
+    #[test]
+    fn codec_shuffle_configuration_valid() {
+        assert!(serde_json::from_str::<ShuffleCodecConfiguration>(JSON_VALID).is_ok());
+    }
+
+    #[test]
+    fn codec_shuffle_configuration_invalid() {
+        const JSON_INVALID: &str = r#"{
+        "elementsize": 0
+    }"#;
+        assert!(serde_json::from_str::<ShuffleCodecConfiguration>(JSON_INVALID).is_err());
+    }
+
+    #[test]
+    fn codec_shuffle_bytes() {
+        let bytes = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let shuffled = shuffle(&bytes, 4);
+        assert_eq!(shuffled, vec![0, 4, 1, 5, 2, 6, 3, 7, 8]);
+        assert_eq!(unshuffle(&shuffled, 4), bytes);
+        assert_eq!(shuffle(&bytes, 1), bytes);
+        assert_eq!(shuffle(&bytes, 16), bytes);
+    }
+
+    #[test]
+    fn codec_shuffle_round_trip() {
+        let elements: Vec<u16> = (0..32).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let configuration: ShuffleCodecConfiguration = serde_json::from_str(JSON_VALID).unwrap();
+        let codec = ShuffleCodec::new_with_configuration(&configuration);
+
+        let encoded = codec
+            .encode(bytes.clone(), &CodecOptions::default())
+            .unwrap();
+        assert_ne!(bytes, encoded);
+        let decoded = codec
+            .decode(
+                encoded.into(),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded);
+    }
+
+    #[test]
+    fn codec_shuffle_partial_decode() {
+        let elements: Vec<u16> = (0..8).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let configuration: ShuffleCodecConfiguration = serde_json::from_str(JSON_VALID).unwrap();
+        let codec = ShuffleCodec::new_with_configuration(&configuration);
+
+        let encoded = codec.encode(bytes, &CodecOptions::default()).unwrap();
+        let decoded_regions = [
+            ByteRange::FromStart(4, Some(4)),
+            ByteRange::FromStart(10, Some(2)),
+        ];
+
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .unwrap()
+            .unwrap();
+
+        let decoded_partial_chunk: Vec<u16> = decoded_partial_chunk
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .chunks(std::mem::size_of::<u16>())
+            .map(|b| u16::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+        let answer: Vec<u16> = vec![2, 3, 5];
+        assert_eq!(answer, decoded_partial_chunk);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn codec_shuffle_async_partial_decode() {
+        let elements: Vec<u16> = (0..8).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let configuration: ShuffleCodecConfiguration = serde_json::from_str(JSON_VALID).unwrap();
+        let codec = ShuffleCodec::new_with_configuration(&configuration);
+
+        let encoded = codec.encode(bytes, &CodecOptions::default()).unwrap();
+        let decoded_regions = [
+            ByteRange::FromStart(4, Some(4)),
+            ByteRange::FromStart(10, Some(2)),
+        ];
+
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .async_partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .await
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let decoded_partial_chunk: Vec<u16> = decoded_partial_chunk
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .chunks(std::mem::size_of::<u16>())
+            .map(|b| u16::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+        let answer: Vec<u16> = vec![2, 3, 5];
+        assert_eq!(answer, decoded_partial_chunk);
+    }
+}
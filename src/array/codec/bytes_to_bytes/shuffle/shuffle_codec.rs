@@ -0,0 +1,119 @@
+use std::num::NonZeroUsize;
+
+use bytes::Bytes;
+
+use crate::{
+    array::{
+        codec::{
+            BytesPartialDecoderTraits, BytesToBytesCodecTraits, CodecError, CodecOptions,
+            CodecTraits, RecommendedConcurrency,
+        },
+        BytesRepresentation,
+    },
+    metadata::Metadata,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+use super::{
+    shuffle, shuffle_configuration::ShuffleCodecConfigurationV1, shuffle_partial_decoder,
+    unshuffle, ShuffleCodecConfiguration, IDENTIFIER,
+};
+
+/// A `shuffle` codec implementation.
+#[derive(Clone, Debug)]
+pub struct ShuffleCodec {
+    element_size: NonZeroUsize,
+}
+
+impl ShuffleCodec {
+    /// Create a new `shuffle` codec with an element size in bytes.
+    #[must_use]
+    pub const fn new(element_size: NonZeroUsize) -> Self {
+        Self { element_size }
+    }
+
+    /// Create a new `shuffle` codec from configuration.
+    #[must_use]
+    pub const fn new_with_configuration(configuration: &ShuffleCodecConfiguration) -> Self {
+        let ShuffleCodecConfiguration::V1(configuration) = configuration;
+        Self {
+            element_size: configuration.elementsize,
+        }
+    }
+}
+
+impl CodecTraits for ShuffleCodec {
+    fn create_metadata(&self) -> Option<Metadata> {
+        let configuration = ShuffleCodecConfigurationV1 {
+            elementsize: self.element_size,
+        };
+        Some(Metadata::new_with_serializable_configuration(IDENTIFIER, &configuration).unwrap())
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        true
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl BytesToBytesCodecTraits for ShuffleCodec {
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &BytesRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+
+    fn encode(
+        &self,
+        decoded_value: Vec<u8>,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        Ok(shuffle(&decoded_value, self.element_size.get()))
+    }
+
+    fn decode(
+        &self,
+        encoded_value: Bytes,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        Ok(unshuffle(&encoded_value, self.element_size.get()))
+    }
+
+    fn partial_decoder<'a>(
+        &self,
+        r: Box<dyn BytesPartialDecoderTraits + 'a>,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn BytesPartialDecoderTraits + 'a>, CodecError> {
+        Ok(Box::new(
+            shuffle_partial_decoder::ShufflePartialDecoder::new(r, self.element_size.get()),
+        ))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder<'a>(
+        &'a self,
+        r: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn AsyncBytesPartialDecoderTraits + 'a>, CodecError> {
+        Ok(Box::new(
+            shuffle_partial_decoder::AsyncShufflePartialDecoder::new(r, self.element_size.get()),
+        ))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        decoded_representation: &BytesRepresentation,
+    ) -> BytesRepresentation {
+        *decoded_representation
+    }
+}
@@ -0,0 +1,29 @@
+use std::num::NonZeroUsize;
+
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+/// A wrapper to handle various versions of `shuffle` codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum ShuffleCodecConfiguration {
+    /// Version 1.0 draft.
+    V1(ShuffleCodecConfigurationV1),
+}
+
+/// Configuration parameters for the `shuffle` codec (version 1.0 draft).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
+pub struct ShuffleCodecConfigurationV1 {
+    /// The element size in bytes.
+    pub elementsize: NonZeroUsize,
+}
+
+impl ShuffleCodecConfigurationV1 {
+    /// Create a new `shuffle` codec configuration given an element size in bytes.
+    #[must_use]
+    pub const fn new(elementsize: NonZeroUsize) -> Self {
+        Self { elementsize }
+    }
+}
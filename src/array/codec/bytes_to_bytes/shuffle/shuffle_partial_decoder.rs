@@ -0,0 +1,86 @@
+use bytes::Bytes;
+
+use crate::{
+    array::codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
+    byte_range::{extract_byte_ranges_bytes, ByteRange},
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+/// Partial decoder for the `shuffle` codec.
+pub struct ShufflePartialDecoder<'a> {
+    input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+    element_size: usize,
+}
+
+impl<'a> ShufflePartialDecoder<'a> {
+    pub fn new(input_handle: Box<dyn BytesPartialDecoderTraits + 'a>, element_size: usize) -> Self {
+        Self {
+            input_handle,
+            element_size,
+        }
+    }
+}
+
+impl BytesPartialDecoderTraits for ShufflePartialDecoder<'_> {
+    fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options)?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+
+        let decoded_value = super::unshuffle(&encoded_value, self.element_size);
+
+        Ok(Some(
+            extract_byte_ranges_bytes(&Bytes::from(decoded_value), decoded_regions)
+                .map_err(CodecError::InvalidByteRangeError)?,
+        ))
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `shuffle` codec.
+pub struct AsyncShufflePartialDecoder<'a> {
+    input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+    element_size: usize,
+}
+
+#[cfg(feature = "async")]
+impl<'a> AsyncShufflePartialDecoder<'a> {
+    pub fn new(
+        input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+        element_size: usize,
+    ) -> Self {
+        Self {
+            input_handle,
+            element_size,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncBytesPartialDecoderTraits for AsyncShufflePartialDecoder<'_> {
+    async fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options).await?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+
+        let decoded_value = super::unshuffle(&encoded_value, self.element_size);
+
+        Ok(Some(
+            extract_byte_ranges_bytes(&Bytes::from(decoded_value), decoded_regions)
+                .map_err(CodecError::InvalidByteRangeError)?,
+        ))
+    }
+}
@@ -0,0 +1,193 @@
+//! The `snappy` bytes to bytes codec.
+//!
+//! Applies [snappy](https://github.com/google/snappy) compression in the raw (unframed) snappy format.
+//! Snappy favours compression and decompression speed over compression ratio, and is the default compressor of many Spark and Hadoop pipelines.
+//!
+//! This codec requires the `snappy` feature, which is disabled by default.
+//!
+//! The codec is not yet standardised, so the configuration and identifier may change.
+//!
+//! ### Codec `configuration` Example - [`SnappyCodecConfiguration`]:
+//! ```rust
+//! # let JSON = r#"
+//! {}
+//! # "#;
+//! # let configuration: zarrs::array::codec::SnappyCodecConfiguration = serde_json::from_str(JSON).unwrap();
+//! ```
+
+mod snappy_codec;
+mod snappy_configuration;
+mod snappy_partial_decoder;
+
+pub use snappy_codec::SnappyCodec;
+pub use snappy_configuration::{SnappyCodecConfiguration, SnappyCodecConfigurationV1};
+
+use crate::{
+    array::codec::{Codec, CodecPlugin},
+    metadata::Metadata,
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+/// The identifier for the `snappy` codec.
+pub const IDENTIFIER: &str = "snappy";
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_snappy, create_codec_snappy)
+}
+
+fn is_name_snappy(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+}
+
+pub(crate) fn create_codec_snappy(metadata: &Metadata) -> Result<Codec, PluginCreateError> {
+    if metadata.configuration_is_none_or_empty() {
+        let codec = Box::new(SnappyCodec::new());
+        Ok(Codec::BytesToBytes(codec))
+    } else {
+        Err(PluginCreateError::MetadataInvalid(
+            PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{
+            codec::{BytesToBytesCodecTraits, CodecOptions},
+            BytesRepresentation,
+        },
+        byte_range::ByteRange,
+    };
+
+    use super::*;
+
+    #[test]
+    fn codec_snappy_configuration() {
+        assert!(serde_json::from_str::<SnappyCodecConfiguration>("{}").is_ok());
+        assert!(serde_json::from_str::<SnappyCodecConfiguration>(r#"{"level": 1}"#).is_err());
+        let metadata: Metadata = serde_json::from_str(r#"{"name": "snappy"}"#).unwrap();
+        assert!(create_codec_snappy(&metadata).is_ok());
+        let metadata: Metadata =
+            serde_json::from_str(r#"{"name": "snappy", "configuration": {"level": 1}}"#).unwrap();
+        assert!(create_codec_snappy(&metadata).is_err());
+    }
+
+    #[test]
+    fn codec_snappy_round_trip() {
+        let elements: Vec<u16> = (0..1024).map(|i| i % 16).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec = SnappyCodec::new();
+        let encoded = codec
+            .encode(bytes.clone(), &CodecOptions::default())
+            .unwrap();
+        assert!(encoded.len() < bytes.len());
+        let BytesRepresentation::BoundedSize(bound) =
+            codec.compute_encoded_size(&bytes_representation)
+        else {
+            panic!()
+        };
+        assert!(encoded.len() as u64 <= bound);
+        // The raw snappy format is compatible with other implementations
+        assert_eq!(
+            snap::raw::Decoder::new().decompress_vec(&encoded).unwrap(),
+            bytes
+        );
+        let decoded = codec
+            .decode(
+                encoded.into(),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded);
+
+        assert!(codec
+            .decode(
+                vec![0xff; 8].into(),
+                &bytes_representation,
+                &CodecOptions::default()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn codec_snappy_partial_decode() {
+        let elements: Vec<u16> = (0..8).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec = SnappyCodec::new();
+        let encoded = codec.encode(bytes, &CodecOptions::default()).unwrap();
+        let decoded_regions = [
+            ByteRange::FromStart(4, Some(4)),
+            ByteRange::FromEnd(0, Some(2)),
+        ];
+
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .unwrap()
+            .unwrap();
+
+        let decoded_partial_chunk: Vec<u16> = decoded_partial_chunk
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .chunks(std::mem::size_of::<u16>())
+            .map(|b| u16::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+        let answer: Vec<u16> = vec![2, 3, 7];
+        assert_eq!(answer, decoded_partial_chunk);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn codec_snappy_async_partial_decode() {
+        let elements: Vec<u16> = (0..8).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec = SnappyCodec::new();
+        let encoded = codec.encode(bytes, &CodecOptions::default()).unwrap();
+        let decoded_regions = [
+            ByteRange::FromStart(4, Some(4)),
+            ByteRange::FromEnd(0, Some(2)),
+        ];
+
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .async_partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .await
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let decoded_partial_chunk: Vec<u16> = decoded_partial_chunk
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .chunks(std::mem::size_of::<u16>())
+            .map(|b| u16::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+        let answer: Vec<u16> = vec![2, 3, 7];
+        assert_eq!(answer, decoded_partial_chunk);
+    }
+}
@@ -0,0 +1,125 @@
+use bytes::Bytes;
+
+use crate::{
+    array::{
+        codec::{
+            BytesPartialDecoderTraits, BytesToBytesCodecTraits, CodecError, CodecOptions,
+            CodecTraits, RecommendedConcurrency,
+        },
+        BytesRepresentation,
+    },
+    metadata::Metadata,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+use super::{
+    snappy_configuration::SnappyCodecConfigurationV1, snappy_partial_decoder,
+    SnappyCodecConfiguration, IDENTIFIER,
+};
+
+/// A `snappy` codec implementation.
+#[derive(Clone, Debug, Default)]
+pub struct SnappyCodec;
+
+impl SnappyCodec {
+    /// Create a new `snappy` codec.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    /// Create a new `snappy` codec from configuration.
+    #[must_use]
+    pub const fn new_with_configuration(_configuration: &SnappyCodecConfiguration) -> Self {
+        Self {}
+    }
+}
+
+/// Decompress a raw snappy encoded value.
+pub(super) fn snappy_decompress(encoded_value: &[u8]) -> Result<Vec<u8>, CodecError> {
+    snap::raw::Decoder::new()
+        .decompress_vec(encoded_value)
+        .map_err(|err| CodecError::Other(err.to_string()))
+}
+
+impl CodecTraits for SnappyCodec {
+    fn create_metadata(&self) -> Option<Metadata> {
+        let configuration = SnappyCodecConfigurationV1 {};
+        Some(Metadata::new_with_serializable_configuration(IDENTIFIER, &configuration).unwrap())
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        true
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl BytesToBytesCodecTraits for SnappyCodec {
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &BytesRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        // snappy does not support parallel decode
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+
+    fn encode(
+        &self,
+        decoded_value: Vec<u8>,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        snap::raw::Encoder::new()
+            .compress_vec(&decoded_value)
+            .map_err(|err| CodecError::Other(err.to_string()))
+    }
+
+    fn decode(
+        &self,
+        encoded_value: Bytes,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Vec<u8>, CodecError> {
+        snappy_decompress(&encoded_value)
+    }
+
+    fn partial_decoder<'a>(
+        &'a self,
+        input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn BytesPartialDecoderTraits + 'a>, CodecError> {
+        Ok(Box::new(snappy_partial_decoder::SnappyPartialDecoder::new(
+            input_handle,
+        )))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder<'a>(
+        &'a self,
+        input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Box<dyn AsyncBytesPartialDecoderTraits + 'a>, CodecError> {
+        Ok(Box::new(
+            snappy_partial_decoder::AsyncSnappyPartialDecoder::new(input_handle),
+        ))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        decoded_representation: &BytesRepresentation,
+    ) -> BytesRepresentation {
+        decoded_representation
+            .size()
+            .map_or(BytesRepresentation::UnboundedSize, |size| {
+                // See snap::raw::max_compress_len
+                BytesRepresentation::BoundedSize(32 + size + size / 6)
+            })
+    }
+}
@@ -0,0 +1,18 @@
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+/// A wrapper to handle various versions of `snappy` codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum SnappyCodecConfiguration {
+    /// Version 1.0 draft.
+    V1(SnappyCodecConfigurationV1),
+}
+
+/// Configuration parameters for the `snappy` codec (version 1.0 draft).
+///
+/// The `snappy` codec has no configuration parameters.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
+pub struct SnappyCodecConfigurationV1 {}
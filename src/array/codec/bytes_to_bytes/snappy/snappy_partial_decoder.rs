@@ -0,0 +1,75 @@
+use bytes::Bytes;
+
+use crate::{
+    array::codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
+    byte_range::{extract_byte_ranges_bytes, ByteRange},
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+use super::snappy_codec::snappy_decompress;
+
+/// Partial decoder for the `snappy` codec.
+pub struct SnappyPartialDecoder<'a> {
+    input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+}
+
+impl<'a> SnappyPartialDecoder<'a> {
+    pub fn new(input_handle: Box<dyn BytesPartialDecoderTraits + 'a>) -> Self {
+        Self { input_handle }
+    }
+}
+
+impl BytesPartialDecoderTraits for SnappyPartialDecoder<'_> {
+    fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options)?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+
+        let decompressed = snappy_decompress(&encoded_value)?;
+        Ok(Some(
+            extract_byte_ranges_bytes(&Bytes::from(decompressed), decoded_regions)
+                .map_err(CodecError::InvalidByteRangeError)?,
+        ))
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `snappy` codec.
+pub struct AsyncSnappyPartialDecoder<'a> {
+    input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+}
+
+#[cfg(feature = "async")]
+impl<'a> AsyncSnappyPartialDecoder<'a> {
+    pub fn new(input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>) -> Self {
+        Self { input_handle }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncBytesPartialDecoderTraits for AsyncSnappyPartialDecoder<'_> {
+    async fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options).await?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+
+        let decompressed = snappy_decompress(&encoded_value)?;
+        Ok(Some(
+            extract_byte_ranges_bytes(&Bytes::from(decompressed), decoded_regions)
+                .map_err(CodecError::InvalidByteRangeError)?,
+        ))
+    }
+}
@@ -1,3 +1,5 @@
+use bytes::Bytes;
+
 use crate::{
     array::{
         codec::{
@@ -62,11 +64,11 @@ impl BytesToBytesCodecTraits for TestUnboundedCodec {
 
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         _decoded_representation: &BytesRepresentation,
         _options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
-        Ok(encoded_value)
+        Ok(Vec::from(encoded_value))
     }
 
     fn partial_decoder<'a>(
@@ -1,6 +1,8 @@
+use bytes::Bytes;
+
 use crate::{
     array::codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
-    byte_range::{extract_byte_ranges, ByteRange},
+    byte_range::{extract_byte_ranges_bytes, ByteRange},
 };
 
 #[cfg(feature = "async")]
@@ -23,14 +25,14 @@ impl BytesPartialDecoderTraits for TestUnboundedPartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
         let encoded_value = self.input_handle.decode(options)?;
         let Some(encoded_value) = encoded_value else {
             return Ok(None);
         };
 
         Ok(Some(
-            extract_byte_ranges(&encoded_value, decoded_regions)
+            extract_byte_ranges_bytes(&encoded_value, decoded_regions)
                 .map_err(CodecError::InvalidByteRangeError)?,
         ))
     }
@@ -57,14 +59,14 @@ impl AsyncBytesPartialDecoderTraits for AsyncTestUnboundedPartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
         let encoded_value = self.input_handle.decode(options).await?;
         let Some(encoded_value) = encoded_value else {
             return Ok(None);
         };
 
         Ok(Some(
-            extract_byte_ranges(&encoded_value, decoded_regions)
+            extract_byte_ranges_bytes(&encoded_value, decoded_regions)
                 .map_err(CodecError::InvalidByteRangeError)?,
         ))
     }
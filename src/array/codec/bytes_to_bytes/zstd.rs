@@ -3,18 +3,33 @@
 //! Applies [Zstd](https://tools.ietf.org/html/rfc8878) compression.
 //!
 //! See <https://zarr-specs.readthedocs.io/en/latest/v3/codecs/zstd/v1.0.html>.
+//!
+//! The codec can optionally encode in the `zstd` [seekable format](https://github.com/facebook/zstd/blob/dev/contrib/seekable_format/zstd_seekable_compression_format.md) with [`ZstdCodec::new_seekable`].
+//! The frame size is stored in the `seekable_frame_size` member of the codec configuration, which is a `zarrs` extension of the `zstd` codec configuration that only affects encoding.
+//! A seekable `zstd` stream is a sequence of independently compressed frames followed by a seek table in a skippable frame, so it remains decodable by any `zstd` decoder.
+//! The partial decoder only reads and decodes the frames intersecting requested byte ranges of a seekable `zstd` stream.
+//!
+//! The codec can optionally compress with a trained dictionary with [`ZstdCodec::new_with_dictionary`], which can substantially improve the compression of many small similar chunks (e.g. label maps).
+//! A dictionary can be trained from sample chunks with [`ZstdCodec::train_dictionary`].
+//! The dictionary is stored base64 encoded in the `dictionary` member of the codec configuration.
+//! This is a `zarrs` extension of the `zstd` codec configuration, so other Zarr implementations may be unable to read arrays with a `zstd` dictionary.
 
 mod zstd_codec;
 mod zstd_configuration;
 mod zstd_partial_decoder;
+mod zstd_seekable;
 
 pub use zstd_codec::ZstdCodec;
 pub use zstd_configuration::{
     ZstdCodecConfiguration, ZstdCodecConfigurationV1, ZstdCompressionLevel,
 };
 
+use std::io::{Read, Write};
+
+use zstd::zstd_safe;
+
 use crate::{
-    array::codec::{Codec, CodecPlugin},
+    array::codec::{Codec, CodecError, CodecPlugin},
     metadata::Metadata,
     plugin::{PluginCreateError, PluginMetadataInvalidError},
 };
@@ -39,11 +54,46 @@ pub(crate) fn create_codec_zstd(metadata: &Metadata) -> Result<Codec, PluginCrea
     Ok(Codec::BytesToBytes(codec))
 }
 
+/// Create a `zstd` encoder writing to `writer`, compressing with `dictionary` if [`Some`].
+fn zstd_encoder<W: Write>(
+    writer: W,
+    compression: zstd_safe::CompressionLevel,
+    dictionary: Option<&[u8]>,
+) -> std::io::Result<zstd::Encoder<'static, W>> {
+    match dictionary {
+        Some(dictionary) => zstd::Encoder::with_dictionary(writer, compression, dictionary),
+        None => zstd::Encoder::new(writer, compression),
+    }
+}
+
+/// Decode all frames of `encoded_value`, decompressing with `dictionary` if [`Some`].
+fn zstd_decode(encoded_value: &[u8], dictionary: Option<&[u8]>) -> Result<Vec<u8>, CodecError> {
+    match dictionary {
+        Some(dictionary) => {
+            let mut decoder = zstd::Decoder::with_dictionary(encoded_value, dictionary)?;
+            let mut out = Vec::new();
+            decoder.read_to_end(&mut out)?;
+            Ok(out)
+        }
+        None => zstd::decode_all(encoded_value).map_err(CodecError::IOError),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::{
+        num::NonZeroU32,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
     use crate::{
         array::{
-            codec::{BytesToBytesCodecTraits, CodecOptions},
+            codec::{
+                BytesPartialDecoderTraits, BytesToBytesCodecTraits, CodecOptions, CodecTraits,
+            },
             BytesRepresentation,
         },
         byte_range::ByteRange,
@@ -70,7 +120,11 @@ mod tests {
             .encode(bytes.clone(), &CodecOptions::default())
             .unwrap();
         let decoded = codec
-            .decode(encoded, &bytes_representation, &CodecOptions::default())
+            .decode(
+                encoded.into(),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
             .unwrap();
         assert_eq!(bytes, decoded);
 
@@ -161,4 +215,275 @@ mod tests {
         let answer: Vec<u16> = vec![2, 3, 5];
         assert_eq!(answer, decoded_partial_chunk);
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_zstd_dictionary() {
+        // Small chunks composed of runs of a limited set of pseudo-random labels
+        let mut state = 1u64;
+        let mut random = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let labels: Vec<u64> = (0..32).map(|_| random()).collect();
+        let samples: Vec<Vec<u8>> = (0..1000)
+            .map(|_| {
+                let chunk: Vec<u64> = (0..8)
+                    .flat_map(|_| {
+                        let label = labels[(random() % 32) as usize];
+                        std::iter::repeat(label).take(4)
+                    })
+                    .collect();
+                crate::array::transmute_to_bytes_vec(chunk)
+            })
+            .collect();
+        let dictionary = ZstdCodec::train_dictionary(&samples, 4096).unwrap();
+        assert!(ZstdCodec::train_dictionary(&samples[..1], 4096).is_err());
+
+        let codec = ZstdCodec::new(5, false);
+        let codec_dictionary = ZstdCodec::new_with_dictionary(5, false, dictionary.clone());
+        assert_eq!(codec_dictionary.dictionary(), Some(dictionary.as_slice()));
+        let encoded_size = |codec: &ZstdCodec| -> usize {
+            samples
+                .iter()
+                .map(|sample| {
+                    codec
+                        .encode(sample.clone(), &CodecOptions::default())
+                        .unwrap()
+                        .len()
+                })
+                .sum()
+        };
+        assert!(encoded_size(&codec_dictionary) < encoded_size(&codec));
+
+        // The dictionary is stored in the codec metadata
+        let metadata = codec_dictionary.create_metadata().unwrap();
+        let Codec::BytesToBytes(codec_from_metadata) = create_codec_zstd(&metadata).unwrap() else {
+            panic!()
+        };
+        let bytes = samples[10].clone();
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+        let encoded = codec_dictionary
+            .encode(bytes.clone(), &CodecOptions::default())
+            .unwrap();
+        let decoded = codec_from_metadata
+            .decode(
+                encoded.clone().into(),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded);
+        assert!(codec
+            .decode(
+                encoded.clone().into(),
+                &bytes_representation,
+                &CodecOptions::default()
+            )
+            .is_err());
+
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec_from_metadata
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(
+                &[ByteRange::FromStart(4, Some(8))],
+                &CodecOptions::default(),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded_partial_chunk, vec![bytes[4..12].to_vec()]);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_zstd_seekable_partial_decode() {
+        let elements: Vec<u16> = (0..1000).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec = ZstdCodec::new_seekable(5, true, std::num::NonZeroU32::new(100).unwrap());
+        assert!(!codec.partial_decoder_decodes_all());
+        let encoded = codec
+            .encode(bytes.clone(), &CodecOptions::default())
+            .unwrap();
+        assert!(
+            encoded.len() as u64
+                <= codec
+                    .compute_encoded_size(&bytes_representation)
+                    .size()
+                    .unwrap()
+        );
+
+        // The seekable format is decodable by any zstd decoder
+        assert_eq!(zstd::decode_all(encoded.as_slice()).unwrap(), bytes);
+        let decoded = codec
+            .decode(
+                encoded.clone().into(),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded);
+
+        let decoded_regions = [
+            ByteRange::FromStart(4, Some(4)),
+            ByteRange::FromStart(198, Some(6)),
+            ByteRange::FromEnd(0, Some(2)),
+            ByteRange::FromStart(1990, None),
+        ];
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .unwrap()
+            .unwrap();
+        let decoded_partial_chunk: Vec<u16> = decoded_partial_chunk
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .chunks(std::mem::size_of::<u16>())
+            .map(|b| u16::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+        let answer: Vec<u16> = vec![2, 3, 99, 100, 101, 999, 995, 996, 997, 998, 999];
+        assert_eq!(answer, decoded_partial_chunk);
+
+        assert!(partial_decoder
+            .partial_decode(
+                &[ByteRange::FromStart(1999, Some(2))],
+                &CodecOptions::default()
+            )
+            .is_err());
+
+        // An empty value is encoded as a single empty frame
+        let encoded = codec.encode(vec![], &CodecOptions::default()).unwrap();
+        assert!(zstd::decode_all(encoded.as_slice()).unwrap().is_empty());
+
+        // The seekable frame size is stored in the codec configuration
+        let configuration: ZstdCodecConfiguration =
+            codec.create_metadata().unwrap().to_configuration().unwrap();
+        let codec = ZstdCodec::new_with_configuration(&configuration);
+        assert_eq!(codec.seekable_frame_size(), NonZeroU32::new(100));
+    }
+
+    /// An input handle counting reads, which fail if `fail` is true.
+    struct CountingPartialDecoder {
+        encoded: std::io::Cursor<Vec<u8>>,
+        reads: Arc<AtomicUsize>,
+        fail: bool,
+    }
+
+    impl BytesPartialDecoderTraits for CountingPartialDecoder {
+        fn partial_decode(
+            &self,
+            decoded_regions: &[ByteRange],
+            options: &CodecOptions,
+        ) -> Result<Option<Vec<bytes::Bytes>>, CodecError> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                return Err(CodecError::Other("store unavailable".to_string()));
+            }
+            self.encoded.partial_decode(decoded_regions, options)
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_zstd_seekable_partial_decode_seek_table() {
+        let elements: Vec<u16> = (0..1000).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+        let codec = ZstdCodec::new_seekable(5, false, NonZeroU32::new(100).unwrap());
+        let encoded = codec.encode(bytes, &CodecOptions::default()).unwrap();
+
+        // The seek table is read once and cached
+        let reads = Arc::new(AtomicUsize::new(0));
+        let input_handle = Box::new(CountingPartialDecoder {
+            encoded: std::io::Cursor::new(encoded.clone()),
+            reads: reads.clone(),
+            fail: false,
+        });
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        for _ in 0..3 {
+            let decoded_partial_chunk = partial_decoder
+                .partial_decode(
+                    &[ByteRange::FromStart(198, Some(4))],
+                    &CodecOptions::default(),
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(decoded_partial_chunk, vec![vec![99, 0, 100, 0]]);
+        }
+        // Footer, seek table, then one frame read per partial decode
+        assert_eq!(reads.load(Ordering::SeqCst), 2 + 3);
+
+        // Errors reading the seek table are propagated
+        let input_handle = Box::new(CountingPartialDecoder {
+            encoded: std::io::Cursor::new(encoded),
+            reads: Arc::new(AtomicUsize::new(0)),
+            fail: true,
+        });
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert!(partial_decoder
+            .partial_decode(
+                &[ByteRange::FromStart(198, Some(4))],
+                &CodecOptions::default()
+            )
+            .is_err());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn codec_zstd_seekable_async_partial_decode() {
+        let elements: Vec<u16> = (0..1000).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec = ZstdCodec::new_seekable(5, false, std::num::NonZeroU32::new(100).unwrap());
+        let encoded = codec.encode(bytes, &CodecOptions::default()).unwrap();
+        let input_handle = Box::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .async_partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .await
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(
+                &[ByteRange::FromStart(198, Some(4))],
+                &CodecOptions::default(),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded_partial_chunk, vec![vec![99, 0, 100, 0]]);
+    }
 }
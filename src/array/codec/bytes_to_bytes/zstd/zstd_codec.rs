@@ -1,3 +1,6 @@
+use std::{num::NonZeroU32, sync::Arc};
+
+use bytes::Bytes;
 use zstd::zstd_safe;
 
 use crate::{
@@ -14,13 +17,18 @@ use crate::{
 #[cfg(feature = "async")]
 use crate::array::codec::AsyncBytesPartialDecoderTraits;
 
-use super::{zstd_partial_decoder, ZstdCodecConfiguration, ZstdCodecConfigurationV1, IDENTIFIER};
+use super::{
+    zstd_decode, zstd_encoder, zstd_partial_decoder, zstd_seekable, ZstdCodecConfiguration,
+    ZstdCodecConfigurationV1, IDENTIFIER,
+};
 
 /// A `zstd` codec implementation.
 #[derive(Clone, Debug)]
 pub struct ZstdCodec {
     compression: zstd_safe::CompressionLevel,
     checksum: bool,
+    seekable_frame_size: Option<NonZeroU32>,
+    dictionary: Option<Arc<[u8]>>,
 }
 
 impl ZstdCodec {
@@ -30,9 +38,76 @@ impl ZstdCodec {
         Self {
             compression,
             checksum,
+            seekable_frame_size: None,
+            dictionary: None,
         }
     }
 
+    /// Create a new `Zstd` codec which compresses with a trained `dictionary`.
+    ///
+    /// A dictionary can substantially improve the compression of small values with similar content, such as many small chunks of a label map.
+    /// The same dictionary is required for decoding, so it is stored in the codec metadata.
+    /// Use [`train_dictionary`](ZstdCodec::train_dictionary) to train a dictionary from samples.
+    #[must_use]
+    pub fn new_with_dictionary(
+        compression: zstd_safe::CompressionLevel,
+        checksum: bool,
+        dictionary: Vec<u8>,
+    ) -> Self {
+        Self {
+            compression,
+            checksum,
+            seekable_frame_size: None,
+            dictionary: Some(dictionary.into()),
+        }
+    }
+
+    /// Train a `zstd` dictionary with a maximum size of `max_size` bytes from `samples`.
+    ///
+    /// The samples should be representative of the values to be compressed, e.g. a selection of encoded chunks of an array prior to this codec.
+    /// A dictionary of about 100KB trained from about 100 times as many bytes of samples is typical.
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if the dictionary cannot be trained, e.g. if there are too few samples.
+    pub fn train_dictionary<S: AsRef<[u8]>>(
+        samples: &[S],
+        max_size: usize,
+    ) -> Result<Vec<u8>, CodecError> {
+        zstd::dict::from_samples(samples, max_size).map_err(CodecError::IOError)
+    }
+
+    /// Return the dictionary, or [`None`] if the codec does not compress with a dictionary.
+    #[must_use]
+    pub fn dictionary(&self) -> Option<&[u8]> {
+        self.dictionary.as_deref()
+    }
+
+    /// Create a new `Zstd` codec which encodes in the `zstd` seekable format with frames of `frame_size` decoded bytes.
+    ///
+    /// The seekable format enables the partial decoder to decode byte ranges without decoding the entire encoded value.
+    /// Smaller frames improve partial decoding granularity at the cost of compression ratio.
+    /// The frame size is stored in the `seekable_frame_size` member of the codec configuration, so that chunks written by other handles of the array are also seekable.
+    /// Any `zstd` decoder can decode the seekable format.
+    #[must_use]
+    pub const fn new_seekable(
+        compression: zstd_safe::CompressionLevel,
+        checksum: bool,
+        frame_size: NonZeroU32,
+    ) -> Self {
+        Self {
+            compression,
+            checksum,
+            seekable_frame_size: Some(frame_size),
+            dictionary: None,
+        }
+    }
+
+    /// Return the seekable format frame size, or [`None`] if the codec does not encode in the seekable format.
+    #[must_use]
+    pub const fn seekable_frame_size(&self) -> Option<NonZeroU32> {
+        self.seekable_frame_size
+    }
+
     /// Create a new `Zstd` codec from configuration.
     #[must_use]
     pub fn new_with_configuration(configuration: &ZstdCodecConfiguration) -> Self {
@@ -40,6 +115,8 @@ impl ZstdCodec {
         Self {
             compression: configuration.level.clone().into(),
             checksum: configuration.checksum,
+            seekable_frame_size: configuration.seekable_frame_size,
+            dictionary: configuration.dictionary.clone().map(Into::into),
         }
     }
 }
@@ -49,6 +126,8 @@ impl CodecTraits for ZstdCodec {
         let configuration = ZstdCodecConfigurationV1 {
             level: self.compression.into(),
             checksum: self.checksum,
+            dictionary: self.dictionary.as_deref().map(<[u8]>::to_vec),
+            seekable_frame_size: self.seekable_frame_size,
         };
         Some(Metadata::new_with_serializable_configuration(IDENTIFIER, &configuration).unwrap())
     }
@@ -58,7 +137,7 @@ impl CodecTraits for ZstdCodec {
     }
 
     fn partial_decoder_decodes_all(&self) -> bool {
-        true
+        self.seekable_frame_size.is_none()
     }
 }
 
@@ -77,8 +156,18 @@ impl BytesToBytesCodecTraits for ZstdCodec {
         decoded_value: Vec<u8>,
         _options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
+        if let Some(frame_size) = self.seekable_frame_size {
+            return zstd_seekable::encode_seekable(
+                &decoded_value,
+                self.compression,
+                self.checksum,
+                frame_size,
+                self.dictionary(),
+            );
+        }
+
         let mut result = Vec::<u8>::new();
-        let mut encoder = zstd::Encoder::new(&mut result, self.compression)?;
+        let mut encoder = zstd_encoder(&mut result, self.compression, self.dictionary())?;
         encoder.include_checksum(self.checksum)?;
         // if parallel {
         //     let n_threads = std::thread::available_parallelism().unwrap().get();
@@ -91,20 +180,23 @@ impl BytesToBytesCodecTraits for ZstdCodec {
 
     fn decode(
         &self,
-        encoded_value: Vec<u8>,
+        encoded_value: Bytes,
         _decoded_representation: &BytesRepresentation,
         _options: &CodecOptions,
     ) -> Result<Vec<u8>, CodecError> {
-        zstd::decode_all(encoded_value.as_slice()).map_err(CodecError::IOError)
+        zstd_decode(&encoded_value, self.dictionary())
     }
 
     fn partial_decoder<'a>(
-        &self,
+        &'a self,
         r: Box<dyn BytesPartialDecoderTraits + 'a>,
         _decoded_representation: &BytesRepresentation,
         _options: &CodecOptions,
     ) -> Result<Box<dyn BytesPartialDecoderTraits + 'a>, CodecError> {
-        Ok(Box::new(zstd_partial_decoder::ZstdPartialDecoder::new(r)))
+        Ok(Box::new(zstd_partial_decoder::ZstdPartialDecoder::new(
+            r,
+            self.dictionary(),
+        )))
     }
 
     #[cfg(feature = "async")]
@@ -115,7 +207,7 @@ impl BytesToBytesCodecTraits for ZstdCodec {
         _options: &CodecOptions,
     ) -> Result<Box<dyn AsyncBytesPartialDecoderTraits + 'a>, CodecError> {
         Ok(Box::new(
-            zstd_partial_decoder::AsyncZstdPartialDecoder::new(r),
+            zstd_partial_decoder::AsyncZstdPartialDecoder::new(r, self.dictionary()),
         ))
     }
 
@@ -133,7 +225,25 @@ impl BytesToBytesCodecTraits for ZstdCodec {
                 const BLOCK_OVERHEAD: u64 = 3;
                 let blocks_overhead =
                     BLOCK_OVERHEAD * ((size + MIN_WINDOW_SIZE - 1) / MIN_WINDOW_SIZE);
-                BytesRepresentation::BoundedSize(size + HEADER_TRAILER_OVERHEAD + blocks_overhead)
+                if let Some(frame_size) = self.seekable_frame_size {
+                    // Each frame has a header/trailer and may have a partial block, plus a seek table
+                    const SEEK_TABLE_ENTRY_SIZE: u64 = 8;
+                    const SEEK_TABLE_OVERHEAD: u64 = 8 + 9;
+                    let frame_size = u64::from(frame_size.get());
+                    let num_frames = std::cmp::max((size + frame_size - 1) / frame_size, 1);
+                    BytesRepresentation::BoundedSize(
+                        size + blocks_overhead
+                            + num_frames
+                                * (HEADER_TRAILER_OVERHEAD
+                                    + BLOCK_OVERHEAD
+                                    + SEEK_TABLE_ENTRY_SIZE)
+                            + SEEK_TABLE_OVERHEAD,
+                    )
+                } else {
+                    BytesRepresentation::BoundedSize(
+                        size + HEADER_TRAILER_OVERHEAD + blocks_overhead,
+                    )
+                }
             })
     }
 }
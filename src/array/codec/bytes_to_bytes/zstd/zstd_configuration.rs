@@ -1,3 +1,5 @@
+use std::num::NonZeroU32;
+
 use derive_more::{Display, From};
 use serde::{Deserialize, Serialize};
 use zstd::zstd_safe;
@@ -19,13 +21,61 @@ pub struct ZstdCodecConfigurationV1 {
     pub level: ZstdCompressionLevel,
     /// A boolean that indicates whether to store a checksum when writing that will be verified when reading.
     pub checksum: bool,
+    /// An optional trained dictionary, serialised as a base64 string.
+    ///
+    /// This is a `zarrs` extension of the `zstd` codec configuration.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "dictionary_base64"
+    )]
+    pub dictionary: Option<Vec<u8>>,
+    /// An optional frame size in decoded bytes to encode in the `zstd` seekable format.
+    ///
+    /// This is a `zarrs` extension of the `zstd` codec configuration.
+    /// It only affects encoding, since any `zstd` decoder can decode the seekable format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seekable_frame_size: Option<NonZeroU32>,
 }
 
 impl ZstdCodecConfigurationV1 {
     /// Create a new `zstd` codec configuration given a [`ZstdCompressionLevel`].
     #[must_use]
     pub const fn new(level: ZstdCompressionLevel, checksum: bool) -> Self {
-        Self { level, checksum }
+        Self {
+            level,
+            checksum,
+            dictionary: None,
+            seekable_frame_size: None,
+        }
+    }
+}
+
+mod dictionary_base64 {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(
+        dictionary: &Option<Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match dictionary {
+            Some(dictionary) => serializer.serialize_str(&STANDARD.encode(dictionary)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<u8>>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|dictionary| {
+                STANDARD
+                    .decode(dictionary)
+                    .map_err(serde::de::Error::custom)
+            })
+            .transpose()
     }
 }
 
@@ -84,6 +134,28 @@ mod tests {
         serde_json::from_str::<ZstdCodecConfiguration>(JSON_VALID).unwrap();
     }
 
+    #[test]
+    fn codec_zstd_configuration_dictionary() {
+        const JSON_VALID: &str = r#"{"level":1,"checksum":false,"dictionary":"AAEC/w=="}"#;
+        const JSON_INVALID: &str = r#"{"level":1,"checksum":false,"dictionary":"not base64"}"#;
+        let configuration = serde_json::from_str::<ZstdCodecConfiguration>(JSON_VALID).unwrap();
+        let ZstdCodecConfiguration::V1(configuration_v1) = &configuration;
+        assert_eq!(configuration_v1.dictionary, Some(vec![0, 1, 2, 255]));
+        assert_eq!(serde_json::to_string(&configuration).unwrap(), JSON_VALID);
+        assert!(serde_json::from_str::<ZstdCodecConfiguration>(JSON_INVALID).is_err());
+    }
+
+    #[test]
+    fn codec_zstd_configuration_seekable() {
+        const JSON_VALID: &str = r#"{"level":1,"checksum":false,"seekable_frame_size":1024}"#;
+        const JSON_INVALID: &str = r#"{"level":1,"checksum":false,"seekable_frame_size":0}"#;
+        let configuration = serde_json::from_str::<ZstdCodecConfiguration>(JSON_VALID).unwrap();
+        let ZstdCodecConfiguration::V1(configuration_v1) = &configuration;
+        assert_eq!(configuration_v1.seekable_frame_size, NonZeroU32::new(1024));
+        assert_eq!(serde_json::to_string(&configuration).unwrap(), JSON_VALID);
+        assert!(serde_json::from_str::<ZstdCodecConfiguration>(JSON_INVALID).is_err());
+    }
+
     #[test]
     fn codec_zstd_configuration_invalid1() {
         const JSON_INVALID1: &str = r#"{
@@ -1,20 +1,84 @@
+use std::sync::OnceLock;
+
+use bytes::Bytes;
+
 use crate::{
     array::codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
-    byte_range::{extract_byte_ranges, ByteRange},
+    byte_range::{extract_byte_ranges_bytes, ByteRange},
+};
+
+use super::{
+    zstd_decode,
+    zstd_seekable::{parse_seek_table_footer, SeekTable, SEEK_TABLE_FOOTER_SIZE},
 };
 
 #[cfg(feature = "async")]
 use crate::array::codec::AsyncBytesPartialDecoderTraits;
 
+/// The byte range of the seek table footer of a seekable `zstd` stream.
+const SEEK_TABLE_FOOTER: ByteRange = ByteRange::FromEnd(0, Some(SEEK_TABLE_FOOTER_SIZE));
+
+/// Return the seek table byte range and entry size from the seek table footer of a `zstd` stream.
+///
+/// Returns [`None`] if the encoded value is missing or not in the seekable format.
+fn seek_table_footer(footer: Option<Vec<Bytes>>) -> Option<(ByteRange, usize)> {
+    parse_seek_table_footer(&footer?.concat())
+}
+
+/// Parse the seek table of a seekable `zstd` stream.
+///
+/// Returns [`None`] if the encoded value is missing or the seek table is invalid, in which case the encoded value is decoded in its entirety.
+fn seek_table_parse(seek_table: Option<Vec<Bytes>>, entry_size: usize) -> Option<SeekTable> {
+    SeekTable::parse(&seek_table?.concat(), entry_size)
+}
+
 /// Partial decoder for the `zstd` codec.
+///
+/// If the encoded value is in the `zstd` seekable format, only the frames intersecting the decoded regions are read and decoded.
+/// Otherwise, the entire encoded value is decoded.
+///
+/// The seek table is read on the first partial decode and cached for subsequent partial decodes.
 pub struct ZstdPartialDecoder<'a> {
     input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+    dictionary: Option<&'a [u8]>,
+    seek_table: OnceLock<Option<SeekTable>>,
 }
 
 impl<'a> ZstdPartialDecoder<'a> {
-    /// Create a new partial decoder for the `zstd` codec.
-    pub fn new(input_handle: Box<dyn BytesPartialDecoderTraits + 'a>) -> Self {
-        Self { input_handle }
+    /// Create a new partial decoder for the `zstd` codec, decompressing with `dictionary` if [`Some`].
+    pub fn new(
+        input_handle: Box<dyn BytesPartialDecoderTraits + 'a>,
+        dictionary: Option<&'a [u8]>,
+    ) -> Self {
+        Self {
+            input_handle,
+            dictionary,
+            seek_table: OnceLock::new(),
+        }
+    }
+}
+
+impl ZstdPartialDecoder<'_> {
+    /// Read the seek table of a seekable `zstd` stream, or return the cached seek table if it has already been read.
+    ///
+    /// Returns [`None`] if the encoded value is not in the seekable format.
+    fn seek_table(&self, options: &CodecOptions) -> Result<Option<&SeekTable>, CodecError> {
+        if let Some(seek_table) = self.seek_table.get() {
+            return Ok(seek_table.as_ref());
+        }
+        let footer = self
+            .input_handle
+            .partial_decode(&[SEEK_TABLE_FOOTER], options)?;
+        let seek_table =
+            if let Some((seek_table_byte_range, entry_size)) = seek_table_footer(footer) {
+                let seek_table = self
+                    .input_handle
+                    .partial_decode(&[seek_table_byte_range], options)?;
+                seek_table_parse(seek_table, entry_size)
+            } else {
+                None
+            };
+        Ok(self.seek_table.get_or_init(|| seek_table).as_ref())
     }
 }
 
@@ -23,17 +87,32 @@ impl BytesPartialDecoderTraits for ZstdPartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        if let Some(seek_table) = self.seek_table(options)? {
+            let frames = seek_table.frames_in_regions(decoded_regions)?;
+            let Some(frames_encoded) = self
+                .input_handle
+                .partial_decode(&seek_table.frames_byte_ranges(&frames), options)?
+            else {
+                return Ok(None);
+            };
+            return Ok(Some(seek_table.decode_regions(
+                &frames,
+                &frames_encoded,
+                decoded_regions,
+                self.dictionary,
+            )?));
+        }
+
         let encoded_value = self.input_handle.decode(options)?;
         let Some(encoded_value) = encoded_value else {
             return Ok(None);
         };
 
-        let decompressed =
-            zstd::decode_all(encoded_value.as_slice()).map_err(CodecError::IOError)?;
+        let decompressed = zstd_decode(&encoded_value, self.dictionary)?;
 
         Ok(Some(
-            extract_byte_ranges(&decompressed, decoded_regions)
+            extract_byte_ranges_bytes(&Bytes::from(decompressed), decoded_regions)
                 .map_err(CodecError::InvalidByteRangeError)?,
         ))
     }
@@ -43,13 +122,49 @@ impl BytesPartialDecoderTraits for ZstdPartialDecoder<'_> {
 /// Asynchronous partial decoder for the `zstd` codec.
 pub struct AsyncZstdPartialDecoder<'a> {
     input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+    dictionary: Option<&'a [u8]>,
+    seek_table: OnceLock<Option<SeekTable>>,
 }
 
 #[cfg(feature = "async")]
 impl<'a> AsyncZstdPartialDecoder<'a> {
-    /// Create a new partial decoder for the `zstd` codec.
-    pub fn new(input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>) -> Self {
-        Self { input_handle }
+    /// Create a new partial decoder for the `zstd` codec, decompressing with `dictionary` if [`Some`].
+    pub fn new(
+        input_handle: Box<dyn AsyncBytesPartialDecoderTraits + 'a>,
+        dictionary: Option<&'a [u8]>,
+    ) -> Self {
+        Self {
+            input_handle,
+            dictionary,
+            seek_table: OnceLock::new(),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncZstdPartialDecoder<'_> {
+    /// Read the seek table of a seekable `zstd` stream, or return the cached seek table if it has already been read.
+    ///
+    /// Returns [`None`] if the encoded value is not in the seekable format.
+    async fn seek_table(&self, options: &CodecOptions) -> Result<Option<&SeekTable>, CodecError> {
+        if let Some(seek_table) = self.seek_table.get() {
+            return Ok(seek_table.as_ref());
+        }
+        let footer = self
+            .input_handle
+            .partial_decode(&[SEEK_TABLE_FOOTER], options)
+            .await?;
+        let seek_table =
+            if let Some((seek_table_byte_range, entry_size)) = seek_table_footer(footer) {
+                let seek_table = self
+                    .input_handle
+                    .partial_decode(&[seek_table_byte_range], options)
+                    .await?;
+                seek_table_parse(seek_table, entry_size)
+            } else {
+                None
+            };
+        Ok(self.seek_table.get_or_init(|| seek_table).as_ref())
     }
 }
 
@@ -60,17 +175,33 @@ impl AsyncBytesPartialDecoderTraits for AsyncZstdPartialDecoder<'_> {
         &self,
         decoded_regions: &[ByteRange],
         options: &CodecOptions,
-    ) -> Result<Option<Vec<Vec<u8>>>, CodecError> {
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        if let Some(seek_table) = self.seek_table(options).await? {
+            let frames = seek_table.frames_in_regions(decoded_regions)?;
+            let Some(frames_encoded) = self
+                .input_handle
+                .partial_decode(&seek_table.frames_byte_ranges(&frames), options)
+                .await?
+            else {
+                return Ok(None);
+            };
+            return Ok(Some(seek_table.decode_regions(
+                &frames,
+                &frames_encoded,
+                decoded_regions,
+                self.dictionary,
+            )?));
+        }
+
         let encoded_value = self.input_handle.decode(options).await?;
         let Some(encoded_value) = encoded_value else {
             return Ok(None);
         };
 
-        let decompressed =
-            zstd::decode_all(encoded_value.as_slice()).map_err(CodecError::IOError)?;
+        let decompressed = zstd_decode(&encoded_value, self.dictionary)?;
 
         Ok(Some(
-            extract_byte_ranges(&decompressed, decoded_regions)
+            extract_byte_ranges_bytes(&Bytes::from(decompressed), decoded_regions)
                 .map_err(CodecError::InvalidByteRangeError)?,
         ))
     }
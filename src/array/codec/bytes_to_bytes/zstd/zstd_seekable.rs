@@ -0,0 +1,236 @@
+//! Support for the `zstd` [seekable format](https://github.com/facebook/zstd/blob/dev/contrib/seekable_format/zstd_seekable_compression_format.md).
+//!
+//! A seekable `zstd` stream is composed of independently compressed frames followed by a seek table in a skippable frame.
+//! It can be decoded by any `zstd` decoder, since skippable frames are ignored when decoding.
+
+use std::{num::NonZeroU32, ops::Range};
+
+use bytes::Bytes;
+use zstd::zstd_safe;
+
+use crate::{
+    array::codec::CodecError,
+    byte_range::{ByteRange, InvalidByteRangeError},
+};
+
+use super::{zstd_decode, zstd_encoder};
+
+const SKIPPABLE_FRAME_MAGIC: u32 = 0x184D_2A5E;
+const SEEKABLE_MAGIC: u32 = 0x8F92_EAB1;
+const SKIPPABLE_FRAME_HEADER_SIZE: usize = 8;
+const SEEK_TABLE_ENTRY_SIZE: usize = 8;
+const SEEK_TABLE_ENTRY_CHECKSUM_SIZE: usize = 12;
+const SEEK_TABLE_CHECKSUM_FLAG: u8 = 1 << 7;
+
+const SEEK_TABLE_FOOTER_LENGTH: usize = 9;
+
+/// The size of the seek table footer.
+pub const SEEK_TABLE_FOOTER_SIZE: u64 = SEEK_TABLE_FOOTER_LENGTH as u64;
+
+/// Encode `decoded_value` in the seekable format with frames of `frame_size` decoded bytes, compressing with `dictionary` if [`Some`].
+pub fn encode_seekable(
+    decoded_value: &[u8],
+    compression: zstd_safe::CompressionLevel,
+    checksum: bool,
+    frame_size: NonZeroU32,
+    dictionary: Option<&[u8]>,
+) -> Result<Vec<u8>, CodecError> {
+    let to_u32 = |size: usize| {
+        u32::try_from(size)
+            .map_err(|_| CodecError::from("zstd seekable frame exceeds the maximum size"))
+    };
+
+    let mut result = Vec::<u8>::new();
+    let mut seek_table = Vec::<u8>::new();
+    let mut num_frames: u32 = 0;
+    let frames: Vec<&[u8]> = if decoded_value.is_empty() {
+        vec![decoded_value]
+    } else {
+        decoded_value.chunks(frame_size.get() as usize).collect()
+    };
+    for frame in frames {
+        let offset = result.len();
+        let mut encoder = zstd_encoder(&mut result, compression, dictionary)?;
+        encoder.include_checksum(checksum)?;
+        encoder.set_pledged_src_size(Some(frame.len() as u64))?;
+        std::io::copy(&mut &*frame, &mut encoder)?;
+        encoder.finish()?;
+        seek_table.extend(to_u32(result.len() - offset)?.to_le_bytes());
+        seek_table.extend(to_u32(frame.len())?.to_le_bytes());
+        num_frames += 1;
+    }
+
+    // Seek table skippable frame
+    result.extend(SKIPPABLE_FRAME_MAGIC.to_le_bytes());
+    result.extend(to_u32(seek_table.len() + SEEK_TABLE_FOOTER_LENGTH)?.to_le_bytes());
+    result.extend(seek_table);
+    result.extend(num_frames.to_le_bytes());
+    result.push(0); // seek table descriptor, no checksums
+    result.extend(SEEKABLE_MAGIC.to_le_bytes());
+    Ok(result)
+}
+
+/// Parse the seek table footer.
+///
+/// Returns the byte range of the seek table skippable frame excluding the footer and the size of each seek table entry, or [`None`] if `footer` is not a seek table footer.
+pub fn parse_seek_table_footer(footer: &[u8]) -> Option<(ByteRange, usize)> {
+    let footer: &[u8; 9] = footer.try_into().ok()?;
+    let num_frames = u32::from_le_bytes([footer[0], footer[1], footer[2], footer[3]]);
+    let descriptor = footer[4];
+    let magic = u32::from_le_bytes([footer[5], footer[6], footer[7], footer[8]]);
+    if magic != SEEKABLE_MAGIC {
+        return None;
+    }
+    let entry_size = if descriptor & SEEK_TABLE_CHECKSUM_FLAG == 0 {
+        SEEK_TABLE_ENTRY_SIZE
+    } else {
+        SEEK_TABLE_ENTRY_CHECKSUM_SIZE
+    };
+    let length = SKIPPABLE_FRAME_HEADER_SIZE as u64 + u64::from(num_frames) * entry_size as u64;
+    Some((
+        ByteRange::FromEnd(SEEK_TABLE_FOOTER_SIZE, Some(length)),
+        entry_size,
+    ))
+}
+
+/// A frame in a seek table.
+struct SeekTableFrame {
+    compressed: Range<u64>,
+    decompressed: Range<u64>,
+}
+
+/// A `zstd` seek table.
+pub struct SeekTable {
+    frames: Vec<SeekTableFrame>,
+}
+
+impl SeekTable {
+    /// Parse a seek table from its skippable frame excluding the footer.
+    ///
+    /// Returns [`None`] if `seek_table` is not a valid seek table.
+    pub fn parse(seek_table: &[u8], entry_size: usize) -> Option<Self> {
+        if seek_table.len() < SKIPPABLE_FRAME_HEADER_SIZE {
+            return None;
+        }
+        let (header, entries) = seek_table.split_at(SKIPPABLE_FRAME_HEADER_SIZE);
+        let magic = u32::from_le_bytes(header[0..4].try_into().ok()?);
+        let frame_size = u32::from_le_bytes(header[4..8].try_into().ok()?);
+        if magic != SKIPPABLE_FRAME_MAGIC
+            || u64::from(frame_size) != entries.len() as u64 + SEEK_TABLE_FOOTER_SIZE
+            || entries.len() % entry_size != 0
+        {
+            return None;
+        }
+
+        let mut frames = Vec::with_capacity(entries.len() / entry_size);
+        let (mut compressed_offset, mut decompressed_offset) = (0u64, 0u64);
+        for entry in entries.chunks_exact(entry_size) {
+            let compressed_size = u32::from_le_bytes(entry[0..4].try_into().ok()?);
+            let decompressed_size = u32::from_le_bytes(entry[4..8].try_into().ok()?);
+            let compressed_end = compressed_offset + u64::from(compressed_size);
+            let decompressed_end = decompressed_offset + u64::from(decompressed_size);
+            frames.push(SeekTableFrame {
+                compressed: compressed_offset..compressed_end,
+                decompressed: decompressed_offset..decompressed_end,
+            });
+            compressed_offset = compressed_end;
+            decompressed_offset = decompressed_end;
+        }
+        Some(Self { frames })
+    }
+
+    /// Return the decoded size.
+    fn decoded_size(&self) -> u64 {
+        self.frames.last().map_or(0, |frame| frame.decompressed.end)
+    }
+
+    /// Return the indices of the frames intersecting `decoded_regions`, in increasing order.
+    ///
+    /// # Errors
+    /// Returns an [`InvalidByteRangeError`] if a decoded region exceeds the decoded size.
+    pub fn frames_in_regions(
+        &self,
+        decoded_regions: &[ByteRange],
+    ) -> Result<Vec<usize>, InvalidByteRangeError> {
+        let decoded_size = self.decoded_size();
+        let mut frames = Vec::new();
+        for decoded_region in decoded_regions {
+            let (ByteRange::FromStart(offset, length) | ByteRange::FromEnd(offset, length)) =
+                decoded_region;
+            if offset + length.unwrap_or(0) > decoded_size {
+                return Err(InvalidByteRangeError::new(*decoded_region, decoded_size));
+            }
+            let range = decoded_region.to_range(decoded_size);
+            let first = self
+                .frames
+                .partition_point(|frame| frame.decompressed.end <= range.start);
+            let last = self
+                .frames
+                .partition_point(|frame| frame.decompressed.start < range.end);
+            frames.extend(first..last);
+        }
+        frames.sort_unstable();
+        frames.dedup();
+        Ok(frames)
+    }
+
+    /// Return the encoded byte ranges of `frames`.
+    pub fn frames_byte_ranges(&self, frames: &[usize]) -> Vec<ByteRange> {
+        frames
+            .iter()
+            .map(|&frame| {
+                let compressed = &self.frames[frame].compressed;
+                ByteRange::FromStart(compressed.start, Some(compressed.end - compressed.start))
+            })
+            .collect()
+    }
+
+    /// Decompress the encoded `frames_encoded` of `frames` with `dictionary` if [`Some`] and extract `decoded_regions`.
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if a frame cannot be decompressed or a decoded region is invalid.
+    ///
+    /// # Panics
+    /// Panics if a decoded region exceeds [`usize::MAX`].
+    pub fn decode_regions(
+        &self,
+        frames: &[usize],
+        frames_encoded: &[Bytes],
+        decoded_regions: &[ByteRange],
+        dictionary: Option<&[u8]>,
+    ) -> Result<Vec<Bytes>, CodecError> {
+        let frames_decoded = std::iter::zip(frames, frames_encoded)
+            .map(|(&frame, frame_encoded)| {
+                let decompressed = &self.frames[frame].decompressed;
+                let frame_decoded = zstd_decode(frame_encoded, dictionary)?;
+                if frame_decoded.len() as u64 == decompressed.end - decompressed.start {
+                    Ok(frame_decoded)
+                } else {
+                    Err(CodecError::UnexpectedChunkDecodedSize(
+                        frame_decoded.len(),
+                        decompressed.end - decompressed.start,
+                    ))
+                }
+            })
+            .collect::<Result<Vec<_>, CodecError>>()?;
+
+        let decoded_size = self.decoded_size();
+        let mut out = Vec::with_capacity(decoded_regions.len());
+        for decoded_region in decoded_regions {
+            let range = decoded_region.to_range(decoded_size);
+            let mut bytes = Vec::with_capacity(usize::try_from(range.end - range.start).unwrap());
+            for (&frame, frame_decoded) in std::iter::zip(frames, &frames_decoded) {
+                let decompressed = &self.frames[frame].decompressed;
+                let start = range.start.max(decompressed.start);
+                let end = range.end.min(decompressed.end);
+                if start < end {
+                    let start = usize::try_from(start - decompressed.start).unwrap();
+                    let end = usize::try_from(end - decompressed.start).unwrap();
+                    bytes.extend_from_slice(&frame_decoded[start..end]);
+                }
+            }
+            out.push(Bytes::from(bytes));
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+use crate::{metadata::Metadata, plugin::PluginCreateError};
+
+use super::Codec;
+
+/// A function which creates a [`Codec`] from metadata.
+pub type CodecCreateFn = fn(metadata: &Metadata) -> Result<Codec, PluginCreateError>;
+
+static CODEC_REGISTRY: OnceLock<RwLock<HashMap<String, CodecCreateFn>>> = OnceLock::new();
+
+fn codec_registry() -> &'static RwLock<HashMap<String, CodecCreateFn>> {
+    CODEC_REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// A runtime registry of codecs.
+///
+/// Codecs are usually registered at compile time as a [`CodecPlugin`](super::CodecPlugin) with the [inventory] crate.
+/// The codec registry is an alternative for applications on platforms where [inventory] is unreliable (e.g. some embedded and WASM targets, or miri).
+///
+/// Codecs registered at runtime are resolved by [`Codec::from_metadata`] (and therefore [`CodecChain::from_metadata`](super::CodecChain::from_metadata)) before any codec plugins.
+/// A registered codec can thus replace the implementation of a codec plugin with the same name.
+///
+/// ```
+/// # use zarrs::array::codec::{BytesCodec, Codec, CodecRegistry};
+/// # use zarrs::metadata::Metadata;
+/// fn create_codec(_metadata: &Metadata) -> Result<Codec, zarrs::plugin::PluginCreateError> {
+///     Ok(Codec::ArrayToBytes(Box::new(BytesCodec::little())))
+/// }
+/// CodecRegistry::register("example.codec", create_codec);
+/// assert!(Codec::from_metadata(&Metadata::new("example.codec")).is_ok());
+/// ```
+pub struct CodecRegistry;
+
+impl CodecRegistry {
+    /// Register a codec with `name` created by `create_fn`.
+    ///
+    /// Returns the previously registered create function for `name`, if any.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    pub fn register(name: impl Into<String>, create_fn: CodecCreateFn) -> Option<CodecCreateFn> {
+        codec_registry()
+            .write()
+            .unwrap()
+            .insert(name.into(), create_fn)
+    }
+
+    /// Unregister the codec with `name`.
+    ///
+    /// Returns the previously registered create function for `name`, if any.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    #[allow(clippy::must_use_candidate)]
+    pub fn unregister(name: &str) -> Option<CodecCreateFn> {
+        codec_registry().write().unwrap().remove(name)
+    }
+
+    /// Returns true if a codec with `name` is registered.
+    ///
+    /// This does not consider codec plugins registered with [inventory].
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    #[must_use]
+    pub fn is_registered(name: &str) -> bool {
+        codec_registry().read().unwrap().contains_key(name)
+    }
+
+    /// Create a codec from `metadata` if a codec with its name is registered.
+    pub(crate) fn create(metadata: &Metadata) -> Option<Result<Codec, PluginCreateError>> {
+        let create_fn = codec_registry()
+            .read()
+            .unwrap()
+            .get(metadata.name())
+            .copied();
+        create_fn.map(|create_fn| create_fn(metadata))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::{
+        codec::{ArrayCodecTraits, BytesCodec, CodecChain, CodecOptions, CodecTraits},
+        ChunkRepresentation, DataType, FillValue,
+    };
+
+    use super::*;
+
+    fn create_codec_test(metadata: &Metadata) -> Result<Codec, PluginCreateError> {
+        if metadata.configuration().is_some() {
+            return Err(PluginCreateError::from("unexpected configuration"));
+        }
+        Ok(Codec::ArrayToBytes(Box::new(BytesCodec::big())))
+    }
+
+    #[test]
+    fn codec_registry() {
+        let name = "zarrs.test_codec_registry";
+        let metadata = Metadata::new(name);
+        assert!(!CodecRegistry::is_registered(name));
+        assert!(Codec::from_metadata(&metadata).is_err());
+
+        assert!(CodecRegistry::register(name, create_codec_test).is_none());
+        assert!(CodecRegistry::is_registered(name));
+        let Codec::ArrayToBytes(codec) = Codec::from_metadata(&metadata).unwrap() else {
+            panic!()
+        };
+        assert_eq!(codec.create_metadata(), BytesCodec::big().create_metadata());
+
+        let codec_chain = CodecChain::from_metadata(std::slice::from_ref(&metadata)).unwrap();
+        let decoded_representation = ChunkRepresentation::new(
+            vec![2.try_into().unwrap()],
+            DataType::UInt16,
+            FillValue::from(0u16),
+        )
+        .unwrap();
+        let decoded_value: Vec<u8> = [1u16, 2].iter().flat_map(|v| v.to_ne_bytes()).collect();
+        let encoded = codec_chain
+            .encode(
+                decoded_value,
+                &decoded_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(encoded, vec![0, 1, 0, 2]);
+
+        assert!(CodecRegistry::unregister(name).is_some());
+        assert!(!CodecRegistry::is_registered(name));
+        assert!(Codec::from_metadata(&metadata).is_err());
+    }
+}
@@ -1,12 +1,249 @@
 //! Codec options for encoding and decoding.
 
-use crate::config::global_config;
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use parking_lot::Mutex;
+
+use crate::{array::ArrayError, config::global_config, storage::StoreKey};
+
+/// The progress of an operation spanning multiple chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    chunks_completed: usize,
+    chunks_total: usize,
+    bytes_processed: u64,
+}
+
+impl Progress {
+    /// Return the number of chunks that have been completed.
+    #[must_use]
+    pub const fn chunks_completed(&self) -> usize {
+        self.chunks_completed
+    }
+
+    /// Return the total number of chunks of the operation.
+    #[must_use]
+    pub const fn chunks_total(&self) -> usize {
+        self.chunks_total
+    }
+
+    /// Return the number of decoded bytes retrieved or stored by the completed chunks.
+    #[must_use]
+    pub const fn bytes_processed(&self) -> u64 {
+        self.bytes_processed
+    }
+}
+
+/// A progress callback for operations spanning multiple chunks.
+///
+/// The callback is called after each chunk of an operation is completed, potentially from multiple threads concurrently.
+/// It is called by the multi-chunk retrieve and store methods of an [`Array`](crate::array::Array), such as [`retrieve_array_subset_opt`](crate::array::Array::retrieve_array_subset_opt) and [`store_chunks_opt`](crate::array::Array::store_chunks_opt).
+#[derive(Clone)]
+pub struct ProgressReporter(Arc<dyn Fn(Progress) + Send + Sync>);
+
+impl core::fmt::Debug for ProgressReporter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ProgressReporter")
+    }
+}
+
+impl ProgressReporter {
+    /// Create a new progress reporter from a callback.
+    pub fn new(callback: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    /// Report `progress`.
+    pub fn report(&self, progress: Progress) {
+        (self.0)(progress);
+    }
+}
+
+/// A token for cancelling operations spanning multiple chunks.
+///
+/// Clones of a token share the same cancellation state, so a token can be cancelled from another thread while an operation is in progress.
+/// Cancellation is checked between chunks, so chunks that are already being retrieved or stored are completed.
+/// A cancelled operation returns [`ArrayError::Cancelled`].
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a new cancellation token.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel operations using this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if the token has been cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A least recently used cache of decoded shard indexes, keyed by the store key of each shard.
+///
+/// The cache holds up to `capacity` shard indexes, and the least recently used shard index is evicted when the capacity is exceeded.
+/// If set in [`CodecOptions`], the `sharding_indexed` partial decoder reuses a cached shard index rather than retrieving and decoding it, so repeated partial reads of the same shard only fetch the shard index once.
+/// Shard indexes are only cached if the sharding codec reads directly from a store (i.e. it is not preceded by bytes to bytes codecs).
+///
+/// Clones of a cache share the same entries.
+/// [`Array::store_chunk_opt`](crate::array::Array::store_chunk_opt) (and the methods that use it) invalidates the shard index of a stored chunk in the cache of its options.
+/// If shards are modified by any other means, the affected entries must be [invalidated](ShardIndexCache::invalidate) or the cache [cleared](ShardIndexCache::clear).
+/// A cache should not be shared between arrays in different stores.
+#[derive(Debug, Clone)]
+pub struct ShardIndexCache {
+    capacity: NonZeroUsize,
+    shard_indexes: Arc<Mutex<ShardIndexes>>,
+}
+
+#[derive(Debug, Default)]
+struct ShardIndexes {
+    entries: HashMap<StoreKey, (Arc<Vec<u64>>, u64)>,
+    counter: u64,
+}
+
+impl ShardIndexCache {
+    /// Create a new empty shard index cache holding up to `capacity` shard indexes.
+    #[must_use]
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            capacity,
+            shard_indexes: Arc::default(),
+        }
+    }
+
+    /// Return the maximum number of cached shard indexes.
+    #[must_use]
+    pub const fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
+    /// Return the cached shard index of the shard at `key`.
+    ///
+    /// The shard index holds the offset and size of each inner chunk.
+    #[must_use]
+    pub fn get(&self, key: &StoreKey) -> Option<Arc<Vec<u64>>> {
+        let mut shard_indexes = self.shard_indexes.lock();
+        let ShardIndexes { entries, counter } = &mut *shard_indexes;
+        let (shard_index, last_used) = entries.get_mut(key)?;
+        *counter += 1;
+        *last_used = *counter;
+        Some(shard_index.clone())
+    }
+
+    /// Insert the shard index of the shard at `key`, evicting the least recently used shard index if the capacity is exceeded.
+    #[cfg(feature = "sharding")]
+    pub(crate) fn insert(&self, key: StoreKey, shard_index: Arc<Vec<u64>>) {
+        let mut shard_indexes = self.shard_indexes.lock();
+        let ShardIndexes { entries, counter } = &mut *shard_indexes;
+        *counter += 1;
+        entries.insert(key, (shard_index, *counter));
+        if entries.len() > self.capacity.get() {
+            if let Some(key_lru) = entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone())
+            {
+                entries.remove(&key_lru);
+            }
+        }
+    }
+
+    /// Remove the cached shard index of the shard at `key`.
+    pub fn invalidate(&self, key: &StoreKey) {
+        self.shard_indexes.lock().entries.remove(key);
+    }
+
+    /// Remove all cached shard indexes.
+    pub fn clear(&self) {
+        *self.shard_indexes.lock() = ShardIndexes::default();
+    }
+
+    /// Return the number of cached shard indexes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shard_indexes.lock().entries.len()
+    }
+
+    /// Returns true if the cache is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Tracks the progress and cancellation of an operation spanning multiple chunks.
+pub(crate) struct ProgressTracker {
+    reporter: Option<ProgressReporter>,
+    cancellation_token: Option<CancellationToken>,
+    chunks_total: usize,
+    chunks_completed: AtomicUsize,
+    bytes_processed: AtomicU64,
+}
+
+impl ProgressTracker {
+    /// Create a new progress tracker for an operation with `chunks_total` chunks.
+    pub(crate) fn new(options: &CodecOptions, chunks_total: usize) -> Self {
+        Self {
+            reporter: options.progress_reporter().cloned(),
+            cancellation_token: options.cancellation_token().cloned(),
+            chunks_total,
+            chunks_completed: AtomicUsize::new(0),
+            bytes_processed: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns [`ArrayError::Cancelled`] if the operation has been cancelled.
+    pub(crate) fn check_cancelled(&self) -> Result<(), ArrayError> {
+        if self
+            .cancellation_token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            Err(ArrayError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Record the completion of a chunk with `bytes` decoded bytes retrieved or stored.
+    pub(crate) fn chunk_completed(&self, bytes: u64) {
+        if let Some(reporter) = &self.reporter {
+            let chunks_completed = self.chunks_completed.fetch_add(1, Ordering::Relaxed) + 1;
+            let bytes_processed = self.bytes_processed.fetch_add(bytes, Ordering::Relaxed) + bytes;
+            reporter.report(Progress {
+                chunks_completed,
+                chunks_total: self.chunks_total,
+                bytes_processed,
+            });
+        }
+    }
+}
 
 /// Codec options for encoding/decoding.
 #[derive(Debug, Clone)]
 pub struct CodecOptions {
     validate_checksums: bool,
     concurrent_target: usize,
+    store_empty_chunks: bool,
+    progress_reporter: Option<ProgressReporter>,
+    cancellation_token: Option<CancellationToken>,
+    max_memory: Option<u64>,
+    shard_index_cache: Option<ShardIndexCache>,
+    write_journal: bool,
 }
 
 impl Default for CodecOptions {
@@ -14,6 +251,12 @@ impl Default for CodecOptions {
         Self {
             validate_checksums: global_config().validate_checksums(),
             concurrent_target: global_config().codec_concurrent_target(),
+            store_empty_chunks: global_config().store_empty_chunks(),
+            progress_reporter: None,
+            cancellation_token: None,
+            max_memory: None,
+            shard_index_cache: None,
+            write_journal: false,
         }
     }
 }
@@ -31,6 +274,12 @@ impl CodecOptions {
         CodecOptionsBuilder {
             validate_checksums: self.validate_checksums,
             concurrent_target: self.concurrent_target,
+            store_empty_chunks: self.store_empty_chunks,
+            progress_reporter: self.progress_reporter.clone(),
+            cancellation_token: self.cancellation_token.clone(),
+            max_memory: self.max_memory,
+            shard_index_cache: self.shard_index_cache.clone(),
+            write_journal: self.write_journal,
         }
     }
 
@@ -55,6 +304,80 @@ impl CodecOptions {
     pub fn set_concurrent_target(&mut self, concurrent_target: usize) {
         self.concurrent_target = concurrent_target;
     }
+
+    /// Return the store empty chunks setting.
+    ///
+    /// If false, chunks that are entirely the fill value are erased rather than stored.
+    #[must_use]
+    pub fn store_empty_chunks(&self) -> bool {
+        self.store_empty_chunks
+    }
+
+    /// Set whether or not to store chunks that are entirely the fill value.
+    pub fn set_store_empty_chunks(&mut self, store_empty_chunks: bool) {
+        self.store_empty_chunks = store_empty_chunks;
+    }
+
+    /// Return the progress reporter.
+    #[must_use]
+    pub fn progress_reporter(&self) -> Option<&ProgressReporter> {
+        self.progress_reporter.as_ref()
+    }
+
+    /// Set the progress reporter of operations spanning multiple chunks.
+    pub fn set_progress_reporter(&mut self, progress_reporter: Option<ProgressReporter>) {
+        self.progress_reporter = progress_reporter;
+    }
+
+    /// Return the cancellation token.
+    #[must_use]
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    /// Set the cancellation token of operations spanning multiple chunks.
+    pub fn set_cancellation_token(&mut self, cancellation_token: Option<CancellationToken>) {
+        self.cancellation_token = cancellation_token;
+    }
+
+    /// Return the memory budget (in bytes) of operations spanning multiple chunks.
+    ///
+    /// If set, the number of chunks processed concurrently is limited such that their estimated decoded and encoded size does not exceed this budget.
+    /// At least one chunk is always processed, and the memory of the output of an operation is not included in the budget.
+    #[must_use]
+    pub fn max_memory(&self) -> Option<u64> {
+        self.max_memory
+    }
+
+    /// Set the memory budget (in bytes) of operations spanning multiple chunks.
+    pub fn set_max_memory(&mut self, max_memory: Option<u64>) {
+        self.max_memory = max_memory;
+    }
+
+    /// Return the shard index cache.
+    #[must_use]
+    pub fn shard_index_cache(&self) -> Option<&ShardIndexCache> {
+        self.shard_index_cache.as_ref()
+    }
+
+    /// Set the shard index cache of the `sharding_indexed` partial decoder.
+    pub fn set_shard_index_cache(&mut self, shard_index_cache: Option<ShardIndexCache>) {
+        self.shard_index_cache = shard_index_cache;
+    }
+
+    /// Return the write journal setting.
+    ///
+    /// If true, operations storing multiple chunks record the chunks they write in a write journal while they are in progress.
+    /// See [`WriteJournal`](crate::array::WriteJournal).
+    #[must_use]
+    pub fn write_journal(&self) -> bool {
+        self.write_journal
+    }
+
+    /// Set whether or not to record a write journal for operations storing multiple chunks.
+    pub fn set_write_journal(&mut self, write_journal: bool) {
+        self.write_journal = write_journal;
+    }
 }
 
 /// Builder for [`CodecOptions`].
@@ -62,6 +385,12 @@ impl CodecOptions {
 pub struct CodecOptionsBuilder {
     validate_checksums: bool,
     concurrent_target: usize,
+    store_empty_chunks: bool,
+    progress_reporter: Option<ProgressReporter>,
+    cancellation_token: Option<CancellationToken>,
+    max_memory: Option<u64>,
+    shard_index_cache: Option<ShardIndexCache>,
+    write_journal: bool,
 }
 
 impl Default for CodecOptionsBuilder {
@@ -77,6 +406,12 @@ impl CodecOptionsBuilder {
         Self {
             validate_checksums: global_config().validate_checksums(),
             concurrent_target: global_config().codec_concurrent_target(),
+            store_empty_chunks: global_config().store_empty_chunks(),
+            progress_reporter: None,
+            cancellation_token: None,
+            max_memory: None,
+            shard_index_cache: None,
+            write_journal: false,
         }
     }
 
@@ -86,6 +421,12 @@ impl CodecOptionsBuilder {
         CodecOptions {
             validate_checksums: self.validate_checksums,
             concurrent_target: self.concurrent_target,
+            store_empty_chunks: self.store_empty_chunks,
+            progress_reporter: self.progress_reporter.clone(),
+            cancellation_token: self.cancellation_token.clone(),
+            max_memory: self.max_memory,
+            shard_index_cache: self.shard_index_cache.clone(),
+            write_journal: self.write_journal,
         }
     }
 
@@ -102,4 +443,52 @@ impl CodecOptionsBuilder {
         self.concurrent_target = concurrent_target;
         self
     }
+
+    /// Set whether or not to store chunks that are entirely the fill value.
+    #[must_use]
+    pub fn store_empty_chunks(mut self, store_empty_chunks: bool) -> Self {
+        self.store_empty_chunks = store_empty_chunks;
+        self
+    }
+
+    /// Set the progress reporter of operations spanning multiple chunks.
+    #[must_use]
+    pub fn progress_reporter(mut self, progress_reporter: ProgressReporter) -> Self {
+        self.progress_reporter = Some(progress_reporter);
+        self
+    }
+
+    /// Set the cancellation token of operations spanning multiple chunks.
+    #[must_use]
+    pub fn cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+
+    /// Set the memory budget (in bytes) of operations spanning multiple chunks.
+    ///
+    /// See [`CodecOptions::max_memory`].
+    #[must_use]
+    pub fn max_memory(mut self, max_memory: u64) -> Self {
+        self.max_memory = Some(max_memory);
+        self
+    }
+
+    /// Set the shard index cache of the `sharding_indexed` partial decoder.
+    ///
+    /// See [`ShardIndexCache`].
+    #[must_use]
+    pub fn shard_index_cache(mut self, shard_index_cache: ShardIndexCache) -> Self {
+        self.shard_index_cache = Some(shard_index_cache);
+        self
+    }
+
+    /// Set whether or not to record a write journal for operations storing multiple chunks.
+    ///
+    /// See [`CodecOptions::write_journal`].
+    #[must_use]
+    pub fn write_journal(mut self, write_journal: bool) -> Self {
+        self.write_journal = write_journal;
+        self
+    }
 }
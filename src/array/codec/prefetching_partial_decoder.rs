@@ -0,0 +1,584 @@
+//! A prefetching partial decoder.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use bytes::Bytes;
+use parking_lot::{Condvar, Mutex};
+
+use crate::{
+    byte_range::ByteRange,
+    storage::{ReadableStorage, StorageError, StoreKey},
+};
+
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin};
+
+#[cfg(feature = "async")]
+use futures::{channel::oneshot, future::AbortHandle};
+
+#[cfg(feature = "async")]
+use crate::storage::AsyncReadableStorage;
+
+#[cfg(feature = "async")]
+use super::AsyncBytesPartialDecoderTraits;
+
+use super::{BytesPartialDecoderTraits, CodecError, CodecOptions};
+
+/// The predicted access pattern of a [`PrefetchingPartialDecoder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrefetchPattern {
+    /// Predict the next request by repeating the byte offset delta between the two most recent requests.
+    ///
+    /// Sequential slices along any axis of a chunk encoded with the `bytes` codec are requested as byte ranges with identical lengths offset by a constant delta.
+    Strided,
+    /// Predict the next request by offsetting the most recent request by a fixed number of bytes.
+    Offset(i64),
+}
+
+type PrefetchResult = Result<Option<Vec<Bytes>>, StorageError>;
+
+/// The progress of a read-ahead.
+enum PrefetchSlot {
+    /// The read-ahead has not started.
+    Pending,
+    /// The read-ahead is in progress.
+    Running,
+    /// The read-ahead was cancelled or its result was taken.
+    Cancelled,
+    /// The read-ahead is complete.
+    Done(PrefetchResult),
+}
+
+/// An in-flight read-ahead.
+///
+/// A read-ahead which has not started when it is cancelled is skipped.
+struct Prefetch {
+    byte_ranges: Vec<ByteRange>,
+    slot: Arc<(Mutex<PrefetchSlot>, Condvar)>,
+}
+
+impl Prefetch {
+    /// Spawn a read-ahead of `byte_ranges` of the value at `key` in `storage` on the [`rayon`] global thread pool.
+    fn spawn(storage: ReadableStorage, key: StoreKey, byte_ranges: Vec<ByteRange>) -> Self {
+        let slot = Arc::new((Mutex::new(PrefetchSlot::Pending), Condvar::new()));
+        let prefetch = Self {
+            byte_ranges: byte_ranges.clone(),
+            slot: slot.clone(),
+        };
+        rayon::spawn(move || {
+            let (slot, condvar) = &*slot;
+            {
+                let mut slot = slot.lock();
+                if !matches!(*slot, PrefetchSlot::Pending) {
+                    return;
+                }
+                *slot = PrefetchSlot::Running;
+            }
+            let result = storage.get_partial_values_key(&key, &byte_ranges);
+            let mut slot = slot.lock();
+            if matches!(*slot, PrefetchSlot::Running) {
+                *slot = PrefetchSlot::Done(result);
+            }
+            condvar.notify_all();
+        });
+        prefetch
+    }
+
+    /// Return the result of the read-ahead, waiting for it if it is in progress.
+    ///
+    /// Returns [`None`] if the read-ahead has not started, in which case it is cancelled.
+    fn take(&self) -> Option<PrefetchResult> {
+        let (slot, condvar) = &*self.slot;
+        let mut slot = slot.lock();
+        loop {
+            match std::mem::replace(&mut *slot, PrefetchSlot::Cancelled) {
+                PrefetchSlot::Pending | PrefetchSlot::Cancelled => return None,
+                PrefetchSlot::Running => {
+                    *slot = PrefetchSlot::Running;
+                    condvar.wait(&mut slot);
+                }
+                PrefetchSlot::Done(result) => return Some(result),
+            }
+        }
+    }
+}
+
+impl Drop for Prefetch {
+    fn drop(&mut self) {
+        // Skip the read-ahead if it has not started, and discard its result if it is in progress
+        *self.slot.0.lock() = PrefetchSlot::Cancelled;
+    }
+}
+
+#[derive(Default)]
+struct PrefetchState {
+    previous: Option<Vec<ByteRange>>,
+    prefetch: Option<Prefetch>,
+}
+
+/// A [`ReadableStorage`] partial decoder which reads ahead the predicted next byte ranges.
+///
+/// After each request, the byte ranges of the next request are predicted from a [`PrefetchPattern`] and retrieved on the [`rayon`] global thread pool while the current request is decoded.
+/// At most one read-ahead is in flight per decoder.
+/// If the next request matches the prediction, it is served by the read-ahead.
+/// Otherwise the read-ahead is cancelled and the request is retrieved from the store.
+/// A read-ahead which has not started by the time it is requested (e.g. if the thread pool is busy, or without threads on `wasm32`) is cancelled and retrieved on the calling thread instead.
+///
+/// Only requests composed of byte ranges from the start with a length are used for prediction, so other requests (e.g. of a shard index at the end of a shard) do not disrupt the access pattern.
+/// Values modified in the store after a read-ahead was issued may be returned stale.
+pub struct PrefetchingPartialDecoder {
+    storage: ReadableStorage,
+    key: StoreKey,
+    pattern: PrefetchPattern,
+    state: Mutex<PrefetchState>,
+    prefetch_hits: AtomicUsize,
+}
+
+impl PrefetchingPartialDecoder {
+    /// Create a new prefetching partial decoder of the value at `key` with a predicted access `pattern`.
+    #[must_use]
+    pub fn new(storage: ReadableStorage, key: StoreKey, pattern: PrefetchPattern) -> Self {
+        Self {
+            storage,
+            key,
+            pattern,
+            state: Mutex::default(),
+            prefetch_hits: AtomicUsize::default(),
+        }
+    }
+
+    /// Return the predicted access pattern.
+    #[must_use]
+    pub const fn pattern(&self) -> PrefetchPattern {
+        self.pattern
+    }
+
+    /// Return the number of requests that matched the byte ranges of a read-ahead.
+    #[must_use]
+    pub fn prefetch_hits(&self) -> usize {
+        self.prefetch_hits.load(Ordering::Relaxed)
+    }
+}
+
+/// Return the byte offset delta between `previous` and `byte_ranges` if it is identical for all byte ranges.
+fn stride(previous: &[ByteRange], byte_ranges: &[ByteRange]) -> Option<i64> {
+    if previous.len() != byte_ranges.len() {
+        return None;
+    }
+    let mut stride = None;
+    for (previous, byte_range) in std::iter::zip(previous, byte_ranges) {
+        let (
+            ByteRange::FromStart(previous_offset, Some(previous_length)),
+            ByteRange::FromStart(offset, Some(length)),
+        ) = (previous, byte_range)
+        else {
+            return None;
+        };
+        if previous_length != length {
+            return None;
+        }
+        let delta = i64::try_from(*offset).ok()? - i64::try_from(*previous_offset).ok()?;
+        if stride.is_some_and(|stride| stride != delta) {
+            return None;
+        }
+        stride = Some(delta);
+    }
+    stride.filter(|stride| *stride != 0)
+}
+
+/// Return the byte ranges predicted by `pattern` of the request following `byte_ranges`.
+fn predict(
+    pattern: PrefetchPattern,
+    previous: Option<&[ByteRange]>,
+    byte_ranges: &[ByteRange],
+) -> Option<Vec<ByteRange>> {
+    let delta = match pattern {
+        PrefetchPattern::Strided => stride(previous?, byte_ranges)?,
+        PrefetchPattern::Offset(delta) => delta,
+    };
+    byte_ranges
+        .iter()
+        .map(|byte_range| match byte_range {
+            ByteRange::FromStart(offset, length) => Some(ByteRange::FromStart(
+                offset.checked_add_signed(delta)?,
+                *length,
+            )),
+            ByteRange::FromEnd(..) => None,
+        })
+        .collect()
+}
+
+/// Returns true if all byte ranges are from the start with a length.
+fn is_bounded_from_start(byte_ranges: &[ByteRange]) -> bool {
+    !byte_ranges.is_empty()
+        && byte_ranges
+            .iter()
+            .all(|byte_range| matches!(byte_range, ByteRange::FromStart(_, Some(_))))
+}
+
+impl BytesPartialDecoderTraits for PrefetchingPartialDecoder {
+    fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        _options: &CodecOptions,
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        let prefetch = self
+            .state
+            .lock()
+            .prefetch
+            .take()
+            .filter(|prefetch| prefetch.byte_ranges == decoded_regions);
+        let prefetched = prefetch.and_then(|prefetch| {
+            self.prefetch_hits.fetch_add(1, Ordering::Relaxed);
+            prefetch.take()
+        });
+        let bytes = if let Some(bytes) = prefetched {
+            bytes?
+        } else {
+            self.storage
+                .get_partial_values_key(&self.key, decoded_regions)?
+        };
+
+        if is_bounded_from_start(decoded_regions) {
+            let mut state = self.state.lock();
+            if let Some(byte_ranges) =
+                predict(self.pattern, state.previous.as_deref(), decoded_regions)
+            {
+                state.prefetch = Some(Prefetch::spawn(
+                    self.storage.clone(),
+                    self.key.clone(),
+                    byte_ranges,
+                ));
+            }
+            state.previous = Some(decoded_regions.to_vec());
+        }
+
+        Ok(bytes)
+    }
+
+    fn store_key(&self) -> Option<&StoreKey> {
+        Some(&self.key)
+    }
+}
+
+/// An asynchronous task spawner.
+///
+/// This crate is async runtime-agnostic, so the spawner must be provided.
+/// For example, `Arc::new(|future| { tokio::spawn(future); })`.
+#[cfg(feature = "async")]
+pub type AsyncSpawn = Arc<dyn Fn(Pin<Box<dyn Future<Output = ()> + Send>>) + Send + Sync>;
+
+/// An in-flight asynchronous read-ahead, which is aborted when dropped.
+#[cfg(feature = "async")]
+struct AsyncPrefetch {
+    byte_ranges: Vec<ByteRange>,
+    receiver: oneshot::Receiver<PrefetchResult>,
+    abort: AbortHandle,
+}
+
+#[cfg(feature = "async")]
+impl AsyncPrefetch {
+    /// Spawn a read-ahead of `byte_ranges` of the value at `key` in `storage` with `spawn`.
+    fn spawn(
+        spawn: &AsyncSpawn,
+        storage: AsyncReadableStorage,
+        key: StoreKey,
+        byte_ranges: Vec<ByteRange>,
+    ) -> Self {
+        let (sender, receiver) = oneshot::channel();
+        let byte_ranges_prefetch = byte_ranges.clone();
+        let (future, abort) = futures::future::abortable(async move {
+            let result = storage
+                .get_partial_values_key(&key, &byte_ranges_prefetch)
+                .await;
+            let _ = sender.send(result);
+        });
+        spawn(Box::pin(async move {
+            let _ = future.await;
+        }));
+        Self {
+            byte_ranges,
+            receiver,
+            abort,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl Drop for AsyncPrefetch {
+    fn drop(&mut self) {
+        self.abort.abort();
+    }
+}
+
+#[cfg(feature = "async")]
+#[derive(Default)]
+struct AsyncPrefetchState {
+    previous: Option<Vec<ByteRange>>,
+    prefetch: Option<AsyncPrefetch>,
+}
+
+/// An [`AsyncReadableStorage`] partial decoder which reads ahead the predicted next byte ranges.
+///
+/// Async variant of [`PrefetchingPartialDecoder`].
+/// The read-ahead is a future spawned with an [`AsyncSpawn`] while the current request is decoded, and it is aborted if the next request does not match the prediction.
+#[cfg(feature = "async")]
+pub struct AsyncPrefetchingPartialDecoder {
+    storage: AsyncReadableStorage,
+    key: StoreKey,
+    pattern: PrefetchPattern,
+    spawn: AsyncSpawn,
+    state: Mutex<AsyncPrefetchState>,
+    prefetch_hits: AtomicUsize,
+}
+
+#[cfg(feature = "async")]
+impl AsyncPrefetchingPartialDecoder {
+    /// Create a new asynchronous prefetching partial decoder of the value at `key` with a predicted access `pattern`.
+    ///
+    /// Read-aheads are spawned with `spawn`.
+    #[must_use]
+    pub fn new(
+        storage: AsyncReadableStorage,
+        key: StoreKey,
+        pattern: PrefetchPattern,
+        spawn: AsyncSpawn,
+    ) -> Self {
+        Self {
+            storage,
+            key,
+            pattern,
+            spawn,
+            state: Mutex::default(),
+            prefetch_hits: AtomicUsize::default(),
+        }
+    }
+
+    /// Return the predicted access pattern.
+    #[must_use]
+    pub const fn pattern(&self) -> PrefetchPattern {
+        self.pattern
+    }
+
+    /// Return the number of requests that matched the byte ranges of a read-ahead.
+    #[must_use]
+    pub fn prefetch_hits(&self) -> usize {
+        self.prefetch_hits.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncBytesPartialDecoderTraits for AsyncPrefetchingPartialDecoder {
+    async fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        _options: &CodecOptions,
+    ) -> Result<Option<Vec<Bytes>>, CodecError> {
+        let prefetch = self
+            .state
+            .lock()
+            .prefetch
+            .take()
+            .filter(|prefetch| prefetch.byte_ranges == decoded_regions);
+        let prefetched = if let Some(mut prefetch) = prefetch {
+            self.prefetch_hits.fetch_add(1, Ordering::Relaxed);
+            // The receiver is cancelled if the spawned future was dropped without completing
+            (&mut prefetch.receiver).await.ok()
+        } else {
+            None
+        };
+        let bytes = if let Some(bytes) = prefetched {
+            bytes?
+        } else {
+            self.storage
+                .get_partial_values_key(&self.key, decoded_regions)
+                .await?
+        };
+
+        if is_bounded_from_start(decoded_regions) {
+            let mut state = self.state.lock();
+            if let Some(byte_ranges) =
+                predict(self.pattern, state.previous.as_deref(), decoded_regions)
+            {
+                state.prefetch = Some(AsyncPrefetch::spawn(
+                    &self.spawn,
+                    self.storage.clone(),
+                    self.key.clone(),
+                    byte_ranges,
+                ));
+            }
+            state.previous = Some(decoded_regions.to_vec());
+        }
+
+        Ok(bytes)
+    }
+
+    fn store_key(&self) -> Option<&StoreKey> {
+        Some(&self.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::storage::{store::MemoryStore, WritableStorageTraits};
+
+    use super::*;
+
+    #[test]
+    fn prefetching_partial_decoder_strided() {
+        let store = Arc::new(MemoryStore::new());
+        let key = StoreKey::new("a").unwrap();
+        store.set(&key, &(0..64).collect::<Vec<u8>>()).unwrap();
+        let decoder =
+            PrefetchingPartialDecoder::new(store.clone(), key.clone(), PrefetchPattern::Strided);
+        let options = CodecOptions::default();
+
+        // Slices along the last axis of an 8x8 chunk of u8
+        let slice = |i: u64| -> Vec<ByteRange> {
+            (0..8)
+                .map(|j| ByteRange::FromStart(j * 8 + i, Some(1)))
+                .collect()
+        };
+        for i in 0..4 {
+            let bytes = decoder
+                .partial_decode(&slice(i), &options)
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                bytes.concat(),
+                (0..8).map(|j| j * 8 + i as u8).collect::<Vec<u8>>()
+            );
+        }
+        // The stride is detected after the second request
+        assert_eq!(decoder.prefetch_hits(), 2);
+
+        // A mispredicted request is retrieved from the store, and the shard index style request does not reset the stride
+        let bytes = decoder
+            .partial_decode(&[ByteRange::FromEnd(0, Some(2))], &options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(bytes.concat(), vec![62, 63]);
+        decoder.partial_decode(&slice(4), &options).unwrap();
+        assert_eq!(decoder.prefetch_hits(), 2);
+        decoder.partial_decode(&slice(5), &options).unwrap();
+        assert_eq!(decoder.prefetch_hits(), 3);
+    }
+
+    #[test]
+    fn prefetching_partial_decoder_offset() {
+        let store = Arc::new(MemoryStore::new());
+        let key = StoreKey::new("a").unwrap();
+        store.set(&key, &(0..64).collect::<Vec<u8>>()).unwrap();
+        let decoder = PrefetchingPartialDecoder::new(store, key, PrefetchPattern::Offset(-16));
+        let options = CodecOptions::default();
+
+        decoder
+            .partial_decode(&[ByteRange::FromStart(48, Some(16))], &options)
+            .unwrap();
+        let bytes = decoder
+            .partial_decode(&[ByteRange::FromStart(32, Some(16))], &options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(bytes.concat(), (32..48).collect::<Vec<u8>>());
+        assert_eq!(decoder.prefetch_hits(), 1);
+
+        // The value is missing
+        let decoder = PrefetchingPartialDecoder::new(
+            Arc::new(MemoryStore::new()),
+            StoreKey::new("a").unwrap(),
+            PrefetchPattern::Offset(1),
+        );
+        assert!(decoder
+            .partial_decode(&[ByteRange::FromStart(0, Some(1))], &options)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn array_prefetching_partial_decoder() {
+        use crate::array::{ArrayBuilder, DataType, FillValue};
+        use crate::array_subset::ArraySubset;
+
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt16,
+            vec![8, 8].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store, "/array")
+        .unwrap();
+        array
+            .store_chunk_elements(&[0, 0], (0..64).collect::<Vec<u16>>())
+            .unwrap();
+
+        let partial_decoder = array
+            .prefetching_partial_decoder(&[0, 0], PrefetchPattern::Strided)
+            .unwrap();
+        for i in 0..8 {
+            let bytes = partial_decoder
+                .partial_decode(&[ArraySubset::new_with_ranges(&[0..8, i..i + 1])])
+                .unwrap()
+                .remove(0);
+            let elements: Vec<u16> = bytemuck::pod_collect_to_vec(&bytes);
+            assert_eq!(
+                elements,
+                (0..8).map(|j| j * 8 + i as u16).collect::<Vec<u16>>()
+            );
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn async_prefetching_partial_decoder_strided() {
+        use crate::storage::{store::AsyncObjectStore, AsyncWritableStorageTraits};
+
+        let store = Arc::new(AsyncObjectStore::new(object_store::memory::InMemory::new()));
+        let key = StoreKey::new("a").unwrap();
+        store
+            .set(&key, (0..64).collect::<Vec<u8>>().into())
+            .await
+            .unwrap();
+        let spawn: AsyncSpawn = Arc::new(|future| {
+            tokio::spawn(future);
+        });
+        let decoder =
+            AsyncPrefetchingPartialDecoder::new(store, key, PrefetchPattern::Strided, spawn);
+        let options = CodecOptions::default();
+
+        let slice = |i: u64| -> Vec<ByteRange> {
+            (0..8)
+                .map(|j| ByteRange::FromStart(j * 8 + i, Some(1)))
+                .collect()
+        };
+        for i in 0..4 {
+            let bytes = decoder
+                .partial_decode(&slice(i), &options)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                bytes.concat(),
+                (0..8).map(|j| j * 8 + i as u8).collect::<Vec<u8>>()
+            );
+        }
+        assert_eq!(decoder.prefetch_hits(), 2);
+
+        // A mispredicted read-ahead is aborted
+        let bytes = decoder
+            .partial_decode(&[ByteRange::FromEnd(0, Some(2))], &options)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(bytes.concat(), vec![62, 63]);
+        decoder.partial_decode(&slice(4), &options).await.unwrap();
+        decoder.partial_decode(&slice(5), &options).await.unwrap();
+        assert_eq!(decoder.prefetch_hits(), 3);
+    }
+}
@@ -115,22 +115,42 @@ pub fn calc_concurrency_outer_inner(
 }
 
 /// Calculate the outer concurrency and inner options for a codec.
+///
+/// `chunk_memory` is the estimated memory usage (in bytes) of processing a single chunk.
+/// If the [`max_memory`](CodecOptions::max_memory) of `codec_options` is set, the outer (chunk) concurrency is limited such that the estimated memory usage of concurrently processed chunks does not exceed it.
+/// The inner (codec) concurrency is then increased towards the concurrency target where the codec recommended concurrency allows.
 #[must_use]
 pub fn concurrency_chunks_and_codec(
     concurrency_target: usize,
     num_chunks: usize,
     codec_options: &CodecOptions,
     codec_concurrency: &RecommendedConcurrency,
+    chunk_memory: u64,
 ) -> (usize, CodecOptions) {
     // core::cmp::minmax https://github.com/rust-lang/rust/issues/115939
     let chunk_concurrent_minimum = global_config().chunk_concurrent_minimum();
     let min_concurrent_chunks = std::cmp::min(chunk_concurrent_minimum, num_chunks);
     let max_concurrent_chunks = std::cmp::max(chunk_concurrent_minimum, num_chunks);
-    let (self_concurrent_limit, codec_concurrent_limit) = calc_concurrency_outer_inner(
+    let (mut self_concurrent_limit, mut codec_concurrent_limit) = calc_concurrency_outer_inner(
         concurrency_target,
         &RecommendedConcurrency::new(min_concurrent_chunks..max_concurrent_chunks),
         codec_concurrency,
     );
+    if let Some(max_memory) = codec_options.max_memory() {
+        let memory_concurrent_limit = usize::try_from(max_memory / std::cmp::max(chunk_memory, 1))
+            .unwrap_or(usize::MAX)
+            .max(1);
+        if self_concurrent_limit > memory_concurrent_limit {
+            self_concurrent_limit = memory_concurrent_limit;
+            codec_concurrent_limit = std::cmp::max(
+                codec_concurrent_limit,
+                std::cmp::min(
+                    (concurrency_target + self_concurrent_limit - 1) / self_concurrent_limit,
+                    codec_concurrency.max(),
+                ),
+            );
+        }
+    }
     let codec_options = codec_options
         .into_builder()
         .concurrent_target(codec_concurrent_limit)
@@ -174,4 +194,27 @@ mod tests {
         );
         assert_eq!((self_limit, inner_limit), (2, 14));
     }
+
+    #[test]
+    fn concurrent_limits_max_memory() {
+        let target = 32;
+        let codec_concurrency = RecommendedConcurrency::new(1..16);
+
+        let options = CodecOptions::default();
+        let (self_limit, options) =
+            concurrency_chunks_and_codec(target, 100, &options, &codec_concurrency, 1000);
+        assert_eq!((self_limit, options.concurrent_target()), (4, 8));
+
+        // Fewer chunks and more codec concurrency
+        let options = CodecOptions::builder().max_memory(2500).build();
+        let (self_limit, options) =
+            concurrency_chunks_and_codec(target, 100, &options, &codec_concurrency, 1000);
+        assert_eq!((self_limit, options.concurrent_target()), (2, 16));
+
+        // At least one chunk is processed
+        let options = CodecOptions::builder().max_memory(10).build();
+        let (self_limit, _) =
+            concurrency_chunks_and_codec(target, 100, &options, &codec_concurrency, 1000);
+        assert_eq!(self_limit, 1);
+    }
 }
@@ -1,6 +1,22 @@
 //! Zarr data types.
 //!
 //! See <https://zarr-specs.readthedocs.io/en/latest/v3/core/v3.0.html#data-types>.
+//!
+//! The [`DateTime64`](DataType::DateTime64) and [`TimeDelta64`](DataType::TimeDelta64) extension data types correspond to the `NumPy` `datetime64` and `timedelta64` data types.
+//! Their elements are [`i64`], and can be converted to and from [`chrono`] types with the `chrono` feature.
+
+mod data_type_registry;
+mod opaque;
+mod time_unit;
+
+pub use data_type_registry::{DataTypeCreateFn, DataTypeRegistry};
+pub use opaque::{OpaqueDataType, OPAQUE_DATA_TYPE_SIZE_CONFIGURATION};
+#[cfg(feature = "chrono")]
+pub use time_unit::{
+    chrono_to_datetime64_elements, chrono_to_timedelta64_elements, datetime64_elements_to_chrono,
+    timedelta64_elements_to_chrono,
+};
+pub use time_unit::{TimeUnit, NAT};
 
 use derive_more::From;
 use half::{bf16, f16};
@@ -8,9 +24,11 @@ use thiserror::Error;
 
 use crate::{
     array::{ZARR_NAN_BF16, ZARR_NAN_F16, ZARR_NAN_F32, ZARR_NAN_F64},
-    metadata::Metadata,
+    metadata::{Metadata, MetadataConfiguration},
 };
 
+use self::time_unit::TimeUnitConfiguration;
+
 use super::{
     fill_value_metadata::{
         FillValueFloat, FillValueFloatStringNonFinite, FillValueMetadata, HexString,
@@ -49,14 +67,28 @@ pub enum DataType {
     /// `bfloat16` brain floating point data type: sign bit, 5 bits exponent, 10 bits mantissa.
     BFloat16,
     /// `complex64` real and complex components are each IEEE 754 single-precision floating point.
+    ///
+    /// Elements correspond to [`num::complex::Complex32`].
     Complex64,
     /// `complex128` real and complex components are each IEEE 754 double-precision floating point.
+    ///
+    /// Elements correspond to [`num::complex::Complex64`].
     Complex128,
     /// `r*` raw bits, variable size given by *, limited to be a multiple of 8.
     RawBits(usize), // the stored usize is the size in bytes
-
-                    // /// An extension data type.
-                    // Extension(Box<dyn DataTypeExtension>),
+    /// `datetime64` extension data type: a signed 64-bit integer count of [`TimeUnit`]s since the unix epoch.
+    ///
+    /// The minimum [`i64`] value represents "not a time" ([`NAT`]).
+    DateTime64(TimeUnit),
+    /// `timedelta64` extension data type: a signed 64-bit integer count of [`TimeUnit`]s.
+    ///
+    /// The minimum [`i64`] value represents "not a time" ([`NAT`]).
+    TimeDelta64(TimeUnit),
+    /// An extension data type.
+    ///
+    /// Extension data types are registered at runtime with the [`DataTypeRegistry`], or are [`OpaqueDataType`]s preserved from annotated metadata.
+    /// Codecs treat the elements of an extension data type as raw bytes, so they are not byte swapped by the `bytes` codec.
+    Extension(Box<dyn DataTypeExtension>),
 }
 
 /// An unsupported data type error.
@@ -81,6 +113,14 @@ impl Eq for DataType {}
 #[error("incompatible fill value {1} for data type {0}")]
 pub struct IncompatibleFillValueErrorMetadataError(String, FillValueMetadata);
 
+impl IncompatibleFillValueErrorMetadataError {
+    /// Create a new incompatible fill value metadata error.
+    #[must_use]
+    pub const fn new(data_type_name: String, fill_value_metadata: FillValueMetadata) -> Self {
+        Self(data_type_name, fill_value_metadata)
+    }
+}
+
 /// A fill value incompatibility error.
 #[derive(Debug, Error)]
 #[error("incompatible fill value {1} for data type {0}")]
@@ -128,7 +168,7 @@ dyn_clone::clone_trait_object!(DataTypeExtension);
 impl DataType {
     /// Returns the identifier.
     #[must_use]
-    pub const fn identifier(&self) -> &'static str {
+    pub fn identifier(&self) -> &'static str {
         match self {
             Self::Bool => "bool",
             Self::Int8 => "int8",
@@ -146,7 +186,9 @@ impl DataType {
             Self::Complex64 => "complex64",
             Self::Complex128 => "complex128",
             Self::RawBits(_usize) => "r*",
-            // Self::Extension(extension) => extension.identifier(),
+            Self::DateTime64(_unit) => "datetime64",
+            Self::TimeDelta64(_unit) => "timedelta64",
+            Self::Extension(extension) => extension.identifier(),
         }
     }
 
@@ -155,7 +197,10 @@ impl DataType {
     pub fn name(&self) -> String {
         match self {
             Self::RawBits(size) => format!("r{}", size * 8),
-            // Self::Extension(extension) => extension.name(),
+            Self::DateTime64(unit) | Self::TimeDelta64(unit) => {
+                format!("{}[{unit}]", self.identifier())
+            }
+            Self::Extension(extension) => extension.name(),
             _ => self.identifier().to_string(),
         }
     }
@@ -163,24 +208,33 @@ impl DataType {
     /// Returns the metadata.
     #[must_use]
     pub fn metadata(&self) -> Metadata {
-        Metadata::new(&self.name())
-        // match self {
-        //     // Self::Extension(extension) => extension.metadata(),
-        //     _ => Metadata::new(&self.name()),
-        // }
+        match self {
+            Self::DateTime64(unit) | Self::TimeDelta64(unit) => {
+                let mut configuration = MetadataConfiguration::new();
+                configuration.insert("unit".to_string(), unit.to_string().into());
+                Metadata::new_with_configuration(self.identifier(), configuration)
+            }
+            Self::Extension(extension) => extension.metadata(),
+            _ => Metadata::new(&self.name()),
+        }
     }
 
     /// Returns the size in bytes.
     #[must_use]
-    pub const fn size(&self) -> usize {
+    pub fn size(&self) -> usize {
         match self {
             Self::Bool | Self::Int8 | Self::UInt8 => 1,
             Self::Int16 | Self::UInt16 | Self::Float16 | Self::BFloat16 => 2,
             Self::Int32 | Self::UInt32 | Self::Float32 => 4,
-            Self::Int64 | Self::UInt64 | Self::Float64 | Self::Complex64 => 8,
+            Self::Int64
+            | Self::UInt64
+            | Self::Float64
+            | Self::Complex64
+            | Self::DateTime64(_)
+            | Self::TimeDelta64(_) => 8,
             Self::Complex128 => 16,
             Self::RawBits(size) => *size,
-            // Self::Extension(extension) => extension.size(),
+            Self::Extension(extension) => extension.size(),
         }
     }
 
@@ -188,7 +242,10 @@ impl DataType {
     ///
     /// # Errors
     ///
-    /// Returns [`UnsupportedDataTypeError`] if the metadata is invalid or not associated with a registered data type plugin.
+    /// Returns [`UnsupportedDataTypeError`] if the metadata is invalid or not associated with a core or registered data type.
+    ///
+    /// Data types registered with the [`DataTypeRegistry`] are resolved after the core data types.
+    /// An otherwise unsupported data type with a [`OPAQUE_DATA_TYPE_SIZE_CONFIGURATION`] annotation is preserved as an [`OpaqueDataType`].
     pub fn from_metadata(metadata: &Metadata) -> Result<Self, UnsupportedDataTypeError> {
         let name = metadata.name();
 
@@ -208,6 +265,16 @@ impl DataType {
             "bfloat16" => return Ok(Self::BFloat16),
             "complex64" => return Ok(Self::Complex64),
             "complex128" => return Ok(Self::Complex128),
+            "datetime64" | "timedelta64" => {
+                let configuration: TimeUnitConfiguration = metadata
+                    .to_configuration()
+                    .map_err(|_| UnsupportedDataTypeError(name.to_string()))?;
+                return Ok(if name == "datetime64" {
+                    Self::DateTime64(configuration.unit)
+                } else {
+                    Self::TimeDelta64(configuration.unit)
+                });
+            }
             _ => {}
         };
 
@@ -220,17 +287,15 @@ impl DataType {
             }
         }
 
-        Err(UnsupportedDataTypeError(name.to_string()))
+        if let Some(extension) = DataTypeRegistry::create(metadata) {
+            return Ok(Self::Extension(extension?));
+        }
+
+        if let Some(opaque) = OpaqueDataType::from_metadata(metadata) {
+            return Ok(Self::Extension(Box::new(opaque)));
+        }
 
-        // for plugin in inventory::iter::<DataTypePlugin> {
-        //     if plugin.match_name(metadata.name()) {
-        //         return Ok(DataType::Extension(plugin.create(metadata)?));
-        //     }
-        // }
-        // Err(PluginCreateError::Unsupported {
-        //     name: metadata.name().to_string(),
-        //     plugin_type: "data type".to_string(),
-        // })
+        Err(UnsupportedDataTypeError(name.to_string()))
     }
 
     /// Create a fill value from metadata.
@@ -249,7 +314,9 @@ impl DataType {
             Self::Int8 => Ok(FV::from(fill_value.try_as_int::<i8>().ok_or_else(err)?)),
             Self::Int16 => Ok(FV::from(fill_value.try_as_int::<i16>().ok_or_else(err)?)),
             Self::Int32 => Ok(FV::from(fill_value.try_as_int::<i32>().ok_or_else(err)?)),
-            Self::Int64 => Ok(FV::from(fill_value.try_as_int::<i64>().ok_or_else(err)?)),
+            Self::Int64 | Self::DateTime64(_) | Self::TimeDelta64(_) => {
+                Ok(FV::from(fill_value.try_as_int::<i64>().ok_or_else(err)?))
+            }
             Self::UInt8 => Ok(FV::from(fill_value.try_as_uint::<u8>().ok_or_else(err)?)),
             Self::UInt16 => Ok(FV::from(fill_value.try_as_uint::<u16>().ok_or_else(err)?)),
             Self::UInt32 => Ok(FV::from(fill_value.try_as_uint::<u32>().ok_or_else(err)?)),
@@ -276,7 +343,8 @@ impl DataType {
                     self.name(),
                     fill_value.clone(),
                 ))
-            } // Self::Extension(extension) => extension.fill_value_from_metadata(fill_value),
+            }
+            Self::Extension(extension) => extension.fill_value_from_metadata(fill_value),
         }
     }
 
@@ -300,7 +368,9 @@ impl DataType {
             Self::Int32 => {
                 FillValueMetadata::Int(i64::from(i32::from_ne_bytes(bytes.try_into().unwrap())))
             }
-            Self::Int64 => FillValueMetadata::Int(i64::from_ne_bytes(bytes.try_into().unwrap())),
+            Self::Int64 | Self::DateTime64(_) | Self::TimeDelta64(_) => {
+                FillValueMetadata::Int(i64::from_ne_bytes(bytes.try_into().unwrap()))
+            }
             Self::UInt8 => {
                 FillValueMetadata::UInt(u64::from(u8::from_ne_bytes(bytes.try_into().unwrap())))
             }
@@ -338,7 +408,8 @@ impl DataType {
             Self::RawBits(size) => {
                 debug_assert_eq!(fill_value.as_ne_bytes().len(), *size);
                 FillValueMetadata::ByteArray(fill_value.as_ne_bytes().to_vec())
-            } // DataType::Extension(extension) => extension.metadata_fill_value(fill_value),
+            }
+            Self::Extension(extension) => extension.metadata_fill_value(fill_value),
         }
     }
 }
@@ -853,6 +924,48 @@ mod tests {
         assert_eq!(metadata, data_type.metadata_fill_value(&fill_value));
     }
 
+    #[test]
+    fn data_type_datetime64() {
+        let json = r#"{"name":"datetime64","configuration":{"unit":"ns"}}"#;
+        let metadata: Metadata = serde_json::from_str(json).unwrap();
+        let data_type = DataType::from_metadata(&metadata).unwrap();
+        assert_eq!(json, serde_json::to_string(&data_type.metadata()).unwrap());
+        assert_eq!(data_type, DataType::DateTime64(TimeUnit::Nanosecond));
+        assert_ne!(data_type, DataType::DateTime64(TimeUnit::Second));
+        assert_ne!(data_type, DataType::TimeDelta64(TimeUnit::Nanosecond));
+        assert_eq!(data_type.name(), "datetime64[ns]");
+        assert_eq!(data_type.size(), 8);
+
+        let metadata = serde_json::from_str::<FillValueMetadata>("-9223372036854775808").unwrap();
+        let fill_value = data_type.fill_value_from_metadata(&metadata).unwrap();
+        assert_eq!(fill_value.as_ne_bytes(), NAT.to_ne_bytes());
+        assert_eq!(metadata, data_type.metadata_fill_value(&fill_value));
+    }
+
+    #[test]
+    fn data_type_timedelta64() {
+        let json = r#"{"name":"timedelta64","configuration":{"unit":"D"}}"#;
+        let metadata: Metadata = serde_json::from_str(json).unwrap();
+        let data_type = DataType::from_metadata(&metadata).unwrap();
+        assert_eq!(json, serde_json::to_string(&data_type.metadata()).unwrap());
+        assert_eq!(data_type, DataType::TimeDelta64(TimeUnit::Day));
+        assert_eq!(data_type.name(), "timedelta64[D]");
+
+        let metadata = serde_json::from_str::<FillValueMetadata>("-7").unwrap();
+        let fill_value = data_type.fill_value_from_metadata(&metadata).unwrap();
+        assert_eq!(fill_value.as_ne_bytes(), (-7i64).to_ne_bytes());
+        assert_eq!(metadata, data_type.metadata_fill_value(&fill_value));
+
+        assert!(DataType::from_metadata(
+            &serde_json::from_str(r#"{"name":"timedelta64","configuration":{"unit":"x"}}"#)
+                .unwrap()
+        )
+        .is_err());
+        assert!(
+            DataType::from_metadata(&serde_json::from_str(r#""timedelta64""#).unwrap()).is_err()
+        );
+    }
+
     #[test]
     fn data_type_r8() {
         let json = r#""r8""#;
@@ -0,0 +1,166 @@
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+use crate::metadata::Metadata;
+
+use super::{DataTypeExtension, UnsupportedDataTypeError};
+
+/// A function which creates an extension data type from metadata.
+pub type DataTypeCreateFn =
+    fn(metadata: &Metadata) -> Result<Box<dyn DataTypeExtension>, UnsupportedDataTypeError>;
+
+static DATA_TYPE_REGISTRY: OnceLock<RwLock<HashMap<String, DataTypeCreateFn>>> = OnceLock::new();
+
+fn data_type_registry() -> &'static RwLock<HashMap<String, DataTypeCreateFn>> {
+    DATA_TYPE_REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// A runtime registry of extension data types.
+///
+/// An extension data type implements [`DataTypeExtension`], which defines its name, size, metadata, and fill value parsing.
+/// Extension data types registered at runtime are resolved by [`DataType::from_metadata`](super::DataType::from_metadata) (and therefore when opening an array) as a [`DataType::Extension`](super::DataType::Extension).
+/// Core data types take precedence and cannot be replaced.
+///
+/// The elements of an extension data type are treated as raw bytes by codecs, so the extension data type must be compatible with the codecs of an array (e.g. the `bytes` codec does not byte swap its elements).
+///
+/// ```
+/// # use zarrs::array::data_type::{DataType, DataTypeExtension, DataTypeRegistry, IncompatibleFillValueErrorMetadataError, UnsupportedDataTypeError};
+/// # use zarrs::array::{FillValue, FillValueMetadata};
+/// # use zarrs::metadata::Metadata;
+/// /// A 3 byte RGB colour.
+/// #[derive(Clone, Debug)]
+/// struct Rgb;
+///
+/// impl DataTypeExtension for Rgb {
+///     fn identifier(&self) -> &'static str {
+///         "example.rgb"
+///     }
+///
+///     fn name(&self) -> String {
+///         self.identifier().to_string()
+///     }
+///
+///     fn size(&self) -> usize {
+///         3
+///     }
+///
+///     fn metadata(&self) -> Metadata {
+///         Metadata::new(self.identifier())
+///     }
+///
+///     fn fill_value_from_metadata(
+///         &self,
+///         fill_value: &FillValueMetadata,
+///     ) -> Result<FillValue, IncompatibleFillValueErrorMetadataError> {
+///         match fill_value {
+///             FillValueMetadata::ByteArray(bytes) if bytes.len() == 3 => Ok(FillValue::new(bytes.clone())),
+///             _ => Err(IncompatibleFillValueErrorMetadataError::new(self.name(), fill_value.clone())),
+///         }
+///     }
+///
+///     fn metadata_fill_value(&self, fill_value: &FillValue) -> FillValueMetadata {
+///         FillValueMetadata::ByteArray(fill_value.as_ne_bytes().to_vec())
+///     }
+/// }
+///
+/// fn create_rgb(_metadata: &Metadata) -> Result<Box<dyn DataTypeExtension>, UnsupportedDataTypeError> {
+///     Ok(Box::new(Rgb))
+/// }
+///
+/// DataTypeRegistry::register("example.rgb", create_rgb);
+/// let data_type = DataType::from_metadata(&Metadata::new("example.rgb")).unwrap();
+/// assert_eq!(data_type.size(), 3);
+/// ```
+pub struct DataTypeRegistry;
+
+impl DataTypeRegistry {
+    /// Register an extension data type with `name` created by `create_fn`.
+    ///
+    /// Returns the previously registered create function for `name`, if any.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    pub fn register(
+        name: impl Into<String>,
+        create_fn: DataTypeCreateFn,
+    ) -> Option<DataTypeCreateFn> {
+        data_type_registry()
+            .write()
+            .unwrap()
+            .insert(name.into(), create_fn)
+    }
+
+    /// Unregister the extension data type with `name`.
+    ///
+    /// Returns the previously registered create function for `name`, if any.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    #[allow(clippy::must_use_candidate)]
+    pub fn unregister(name: &str) -> Option<DataTypeCreateFn> {
+        data_type_registry().write().unwrap().remove(name)
+    }
+
+    /// Returns true if an extension data type with `name` is registered.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    #[must_use]
+    pub fn is_registered(name: &str) -> bool {
+        data_type_registry().read().unwrap().contains_key(name)
+    }
+
+    /// Create an extension data type from `metadata` if a data type with its name is registered.
+    pub(crate) fn create(
+        metadata: &Metadata,
+    ) -> Option<Result<Box<dyn DataTypeExtension>, UnsupportedDataTypeError>> {
+        let create_fn = data_type_registry()
+            .read()
+            .unwrap()
+            .get(metadata.name())
+            .copied();
+        create_fn.map(|create_fn| create_fn(metadata))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::{data_type::OpaqueDataType, DataType};
+
+    use super::*;
+
+    fn create_data_type_test(
+        metadata: &Metadata,
+    ) -> Result<Box<dyn DataTypeExtension>, UnsupportedDataTypeError> {
+        Ok(Box::new(OpaqueDataType::new(metadata.clone(), 6)))
+    }
+
+    #[test]
+    fn data_type_registry() {
+        let name = "zarrs.test_data_type_registry";
+        let metadata = Metadata::new(name);
+        assert!(!DataTypeRegistry::is_registered(name));
+        assert!(DataType::from_metadata(&metadata).is_err());
+
+        assert!(DataTypeRegistry::register(name, create_data_type_test).is_none());
+        assert!(DataTypeRegistry::is_registered(name));
+        let data_type = DataType::from_metadata(&metadata).unwrap();
+        assert_eq!(data_type.name(), name);
+        assert_eq!(data_type.size(), 6);
+        assert_eq!(data_type.metadata(), metadata);
+
+        // Core data types cannot be replaced
+        DataTypeRegistry::register("uint8", create_data_type_test);
+        assert_eq!(
+            DataType::from_metadata(&Metadata::new("uint8")).unwrap(),
+            DataType::UInt8
+        );
+        DataTypeRegistry::unregister("uint8");
+
+        assert!(DataTypeRegistry::unregister(name).is_some());
+        assert!(!DataTypeRegistry::is_registered(name));
+        assert!(DataType::from_metadata(&metadata).is_err());
+    }
+}
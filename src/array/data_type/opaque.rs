@@ -0,0 +1,172 @@
+use crate::{
+    array::{FillValue, FillValueMetadata},
+    metadata::Metadata,
+};
+
+use super::{DataTypeExtension, IncompatibleFillValueErrorMetadataError};
+
+/// The data type configuration member annotating the size in bytes of an otherwise unsupported data type.
+pub const OPAQUE_DATA_TYPE_SIZE_CONFIGURATION: &str = "size_bytes";
+
+/// An opaque extension data type, preserving the metadata of an otherwise unsupported data type with a known size.
+///
+/// An unsupported data type is opened as an opaque data type if its metadata is annotated with its size in bytes in the [`OPAQUE_DATA_TYPE_SIZE_CONFIGURATION`] configuration member.
+/// For example:
+/// ```json
+/// {
+///     "name": "example.rgb",
+///     "configuration": {
+///         "size_bytes": 3
+///     }
+/// }
+/// ```
+/// This permits byte level access to arrays with data types that are not supported by `zarrs` (e.g. to copy or rechunk them), and the data type metadata is preserved when the array metadata is written.
+/// The fill value of an opaque data type must be an array of bytes.
+#[derive(Clone, Debug)]
+pub struct OpaqueDataType {
+    metadata: Metadata,
+    size: usize,
+}
+
+impl OpaqueDataType {
+    /// Create a new opaque data type with `metadata` and elements of `size` bytes.
+    #[must_use]
+    pub const fn new(metadata: Metadata, size: usize) -> Self {
+        Self { metadata, size }
+    }
+
+    /// Create an opaque data type from `metadata` if it is annotated with its size.
+    ///
+    /// Returns [`None`] if the [`OPAQUE_DATA_TYPE_SIZE_CONFIGURATION`] configuration member is absent or not a positive integer.
+    #[must_use]
+    pub fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        let size = metadata
+            .configuration()?
+            .get(OPAQUE_DATA_TYPE_SIZE_CONFIGURATION)?
+            .as_u64()?;
+        let size = usize::try_from(size).ok().filter(|size| *size > 0)?;
+        Some(Self::new(metadata.clone(), size))
+    }
+}
+
+impl DataTypeExtension for OpaqueDataType {
+    fn identifier(&self) -> &'static str {
+        "opaque"
+    }
+
+    fn name(&self) -> String {
+        self.metadata.name().to_string()
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn metadata(&self) -> Metadata {
+        self.metadata.clone()
+    }
+
+    fn fill_value_from_metadata(
+        &self,
+        fill_value: &FillValueMetadata,
+    ) -> Result<FillValue, IncompatibleFillValueErrorMetadataError> {
+        match fill_value {
+            FillValueMetadata::ByteArray(bytes) if bytes.len() == self.size => {
+                Ok(FillValue::new(bytes.clone()))
+            }
+            _ => Err(IncompatibleFillValueErrorMetadataError::new(
+                self.name(),
+                fill_value.clone(),
+            )),
+        }
+    }
+
+    fn metadata_fill_value(&self, fill_value: &FillValue) -> FillValueMetadata {
+        FillValueMetadata::ByteArray(fill_value.as_ne_bytes().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{Array, ArrayMetadata, DataType},
+        array_subset::ArraySubset,
+        storage::{store::MemoryStore, ReadableStorageTraits, StoreKey, WritableStorageTraits},
+    };
+
+    use super::*;
+
+    #[test]
+    fn data_type_opaque() {
+        let metadata: Metadata =
+            serde_json::from_str(r#"{"name": "example.rgb", "configuration": {"size_bytes": 3}}"#)
+                .unwrap();
+        let data_type = DataType::from_metadata(&metadata).unwrap();
+        assert_eq!(data_type.name(), "example.rgb");
+        assert_eq!(data_type.identifier(), "opaque");
+        assert_eq!(data_type.size(), 3);
+        assert_eq!(data_type.metadata(), metadata);
+        let fill_value = data_type
+            .fill_value_from_metadata(&FillValueMetadata::ByteArray(vec![1, 2, 3]))
+            .unwrap();
+        assert_eq!(
+            data_type.metadata_fill_value(&fill_value),
+            FillValueMetadata::ByteArray(vec![1, 2, 3])
+        );
+        assert!(data_type
+            .fill_value_from_metadata(&FillValueMetadata::UInt(0))
+            .is_err());
+
+        for json in [
+            r#""example.rgb""#,
+            r#"{"name": "example.rgb", "configuration": {"size_bytes": 0}}"#,
+            r#"{"name": "example.rgb", "configuration": {"size_bytes": "3"}}"#,
+        ] {
+            let metadata: Metadata = serde_json::from_str(json).unwrap();
+            assert!(DataType::from_metadata(&metadata).is_err());
+        }
+    }
+
+    #[test]
+    fn array_opaque_data_type() {
+        let json = r#"{
+            "zarr_format": 3,
+            "node_type": "array",
+            "shape": [4],
+            "data_type": {"name": "example.rgb", "configuration": {"size_bytes": 3}},
+            "chunk_grid": {"name": "regular", "configuration": {"chunk_shape": [2]}},
+            "chunk_key_encoding": {"name": "default", "configuration": {"separator": "/"}},
+            "fill_value": [0, 0, 0],
+            "codecs": [{"name": "bytes", "configuration": {"endian": "little"}}]
+        }"#;
+        let store = Arc::new(MemoryStore::new());
+        let key = StoreKey::new("array/zarr.json").unwrap();
+        store.set(&key, json.as_bytes()).unwrap();
+
+        let array = Array::new(store.clone(), "/array").unwrap();
+        let bytes: Vec<u8> = (0..12).collect();
+        array
+            .store_array_subset(&ArraySubset::new_with_shape(vec![4]), bytes.clone())
+            .unwrap();
+        assert_eq!(
+            array
+                .retrieve_array_subset(&ArraySubset::new_with_shape(vec![4]))
+                .unwrap(),
+            bytes
+        );
+
+        // The data type metadata is preserved
+        array.store_metadata().unwrap();
+        let metadata: ArrayMetadata =
+            serde_json::from_slice(&store.get(&key).unwrap().unwrap()).unwrap();
+        let ArrayMetadata::V3(metadata) = metadata else {
+            panic!()
+        };
+        assert_eq!(
+            serde_json::to_value(metadata.data_type).unwrap(),
+            serde_json::json!({"name": "example.rgb", "configuration": {"size_bytes": 3}})
+        );
+    }
+}
@@ -0,0 +1,416 @@
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+/// The time unit of a [`datetime64`](super::DataType::DateTime64) or [`timedelta64`](super::DataType::TimeDelta64) data type.
+///
+/// The units and their string representations match `NumPy` [datetime units](https://numpy.org/doc/stable/reference/arrays.datetime.html#datetime-units).
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug, Display)]
+pub enum TimeUnit {
+    /// Years.
+    #[serde(rename = "Y")]
+    #[display(fmt = "Y")]
+    Year,
+    /// Months.
+    #[serde(rename = "M")]
+    #[display(fmt = "M")]
+    Month,
+    /// Weeks.
+    #[serde(rename = "W")]
+    #[display(fmt = "W")]
+    Week,
+    /// Days.
+    #[serde(rename = "D")]
+    #[display(fmt = "D")]
+    Day,
+    /// Hours.
+    #[serde(rename = "h")]
+    #[display(fmt = "h")]
+    Hour,
+    /// Minutes.
+    #[serde(rename = "m")]
+    #[display(fmt = "m")]
+    Minute,
+    /// Seconds.
+    #[serde(rename = "s")]
+    #[display(fmt = "s")]
+    Second,
+    /// Milliseconds.
+    #[serde(rename = "ms")]
+    #[display(fmt = "ms")]
+    Millisecond,
+    /// Microseconds.
+    #[serde(rename = "us")]
+    #[display(fmt = "us")]
+    Microsecond,
+    /// Nanoseconds.
+    #[serde(rename = "ns")]
+    #[display(fmt = "ns")]
+    Nanosecond,
+    /// Picoseconds.
+    #[serde(rename = "ps")]
+    #[display(fmt = "ps")]
+    Picosecond,
+    /// Femtoseconds.
+    #[serde(rename = "fs")]
+    #[display(fmt = "fs")]
+    Femtosecond,
+    /// Attoseconds.
+    #[serde(rename = "as")]
+    #[display(fmt = "as")]
+    Attosecond,
+}
+
+/// The `NumPy` "not a time" value of `datetime64` and `timedelta64` elements.
+pub const NAT: i64 = i64::MIN;
+
+/// The configuration of a [`datetime64`](super::DataType::DateTime64) or [`timedelta64`](super::DataType::TimeDelta64) data type.
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct TimeUnitConfiguration {
+    pub unit: TimeUnit,
+}
+
+impl core::str::FromStr for TimeUnit {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string())).map_err(|_| ())
+    }
+}
+
+#[cfg(feature = "chrono")]
+const NANOSECONDS_PER_SECOND: i64 = 1_000_000_000;
+
+/// The length of a time unit.
+#[cfg(feature = "chrono")]
+enum TimeUnitLength {
+    /// A number of calendar months.
+    Months(i64),
+    /// A number of seconds.
+    Seconds(i64),
+    /// A fraction of a second.
+    SubSeconds(i64),
+}
+
+#[cfg(feature = "chrono")]
+impl TimeUnit {
+    const fn length(self) -> TimeUnitLength {
+        match self {
+            Self::Year => TimeUnitLength::Months(12),
+            Self::Month => TimeUnitLength::Months(1),
+            Self::Week => TimeUnitLength::Seconds(7 * 24 * 60 * 60),
+            Self::Day => TimeUnitLength::Seconds(24 * 60 * 60),
+            Self::Hour => TimeUnitLength::Seconds(60 * 60),
+            Self::Minute => TimeUnitLength::Seconds(60),
+            Self::Second => TimeUnitLength::Seconds(1),
+            Self::Millisecond => TimeUnitLength::SubSeconds(1_000),
+            Self::Microsecond => TimeUnitLength::SubSeconds(1_000_000),
+            Self::Nanosecond => TimeUnitLength::SubSeconds(1_000_000_000),
+            Self::Picosecond => TimeUnitLength::SubSeconds(1_000_000_000_000),
+            Self::Femtosecond => TimeUnitLength::SubSeconds(1_000_000_000_000_000),
+            Self::Attosecond => TimeUnitLength::SubSeconds(1_000_000_000_000_000_000),
+        }
+    }
+
+    /// Convert a number of fixed length units to seconds and nanoseconds.
+    ///
+    /// Units shorter than a nanosecond are rounded down to the nearest nanosecond.
+    fn to_seconds_nanoseconds(self, value: i64) -> Option<(i64, u32)> {
+        match self.length() {
+            TimeUnitLength::Months(_) => None,
+            TimeUnitLength::Seconds(seconds) => Some((value.checked_mul(seconds)?, 0)),
+            TimeUnitLength::SubSeconds(per_second) => {
+                let seconds = value.div_euclid(per_second);
+                let subseconds = value.rem_euclid(per_second);
+                let nanoseconds = if per_second > NANOSECONDS_PER_SECOND {
+                    subseconds / (per_second / NANOSECONDS_PER_SECOND)
+                } else {
+                    subseconds * (NANOSECONDS_PER_SECOND / per_second)
+                };
+                Some((seconds, u32::try_from(nanoseconds).ok()?))
+            }
+        }
+    }
+
+    /// Convert seconds and nanoseconds to a number of fixed length units, rounding down.
+    fn seconds_nanoseconds_to_value(self, seconds: i64, nanoseconds: u32) -> Option<i64> {
+        let nanoseconds = i64::from(nanoseconds);
+        match self.length() {
+            TimeUnitLength::Months(_) => None,
+            TimeUnitLength::Seconds(unit_seconds) => Some(seconds.div_euclid(unit_seconds)),
+            TimeUnitLength::SubSeconds(per_second) => {
+                let subseconds = if per_second > NANOSECONDS_PER_SECOND {
+                    nanoseconds.checked_mul(per_second / NANOSECONDS_PER_SECOND)?
+                } else {
+                    nanoseconds / (NANOSECONDS_PER_SECOND / per_second)
+                };
+                seconds.checked_mul(per_second)?.checked_add(subseconds)
+            }
+        }
+    }
+
+    /// Convert a `datetime64` element in this unit to a [`chrono::NaiveDateTime`].
+    ///
+    /// Returns [`None`] if the element is "not a time" ([`NAT`]) or it is outside the range of [`chrono::NaiveDateTime`].
+    /// Units shorter than a nanosecond are rounded down to the nearest nanosecond.
+    #[must_use]
+    pub fn value_to_datetime(self, value: i64) -> Option<chrono::NaiveDateTime> {
+        if value == NAT {
+            return None;
+        }
+        let epoch = chrono::DateTime::<chrono::Utc>::UNIX_EPOCH.naive_utc();
+        if let TimeUnitLength::Months(unit_months) = self.length() {
+            let months = value.checked_mul(unit_months)?;
+            let months_abs = chrono::Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+            if months >= 0 {
+                epoch.checked_add_months(months_abs)
+            } else {
+                epoch.checked_sub_months(months_abs)
+            }
+        } else {
+            let (seconds, nanoseconds) = self.to_seconds_nanoseconds(value)?;
+            chrono::DateTime::from_timestamp(seconds, nanoseconds)
+                .map(|datetime| datetime.naive_utc())
+        }
+    }
+
+    /// Convert a [`chrono::NaiveDateTime`] to a `datetime64` element in this unit.
+    ///
+    /// The datetime is rounded down to the nearest unit.
+    /// Returns [`None`] if the datetime cannot be represented in this unit.
+    #[must_use]
+    pub fn datetime_to_value(self, datetime: &chrono::NaiveDateTime) -> Option<i64> {
+        use chrono::Datelike;
+        let value = if let TimeUnitLength::Months(unit_months) = self.length() {
+            let months = i64::from(datetime.year() - 1970) * 12 + i64::from(datetime.month0());
+            months.div_euclid(unit_months)
+        } else {
+            let datetime = datetime.and_utc();
+            self.seconds_nanoseconds_to_value(
+                datetime.timestamp(),
+                datetime.timestamp_subsec_nanos(),
+            )?
+        };
+        (value != NAT).then_some(value)
+    }
+
+    /// Convert a `timedelta64` element in this unit to a [`chrono::TimeDelta`].
+    ///
+    /// Returns [`None`] if the element is "not a time" ([`NAT`]), it is outside the range of [`chrono::TimeDelta`], or the unit is years or months.
+    /// Units shorter than a nanosecond are rounded down to the nearest nanosecond.
+    #[must_use]
+    pub fn value_to_timedelta(self, value: i64) -> Option<chrono::TimeDelta> {
+        if value == NAT {
+            return None;
+        }
+        let (seconds, nanoseconds) = self.to_seconds_nanoseconds(value)?;
+        chrono::TimeDelta::new(seconds, nanoseconds)
+    }
+
+    /// Convert a [`chrono::TimeDelta`] to a `timedelta64` element in this unit.
+    ///
+    /// The time delta is rounded down to the nearest unit.
+    /// Returns [`None`] if the time delta cannot be represented in this unit or the unit is years or months.
+    #[must_use]
+    pub fn timedelta_to_value(self, timedelta: &chrono::TimeDelta) -> Option<i64> {
+        let mut seconds = timedelta.num_seconds();
+        let mut nanoseconds = timedelta.subsec_nanos();
+        if nanoseconds < 0 {
+            seconds -= 1;
+            nanoseconds += 1_000_000_000;
+        }
+        let value = self.seconds_nanoseconds_to_value(seconds, u32::try_from(nanoseconds).ok()?)?;
+        (value != NAT).then_some(value)
+    }
+}
+
+/// Convert the elements of a `datetime64` array with `unit` to [`chrono::NaiveDateTime`]s.
+///
+/// This is intended for the output of [`Array`](crate::array::Array) `_elements` methods with an [`i64`] element type.
+/// An element is [`None`] if it is "not a time" ([`NAT`]) or is outside the range of [`chrono::NaiveDateTime`].
+#[cfg(feature = "chrono")]
+#[must_use]
+pub fn datetime64_elements_to_chrono(
+    elements: &[i64],
+    unit: TimeUnit,
+) -> Vec<Option<chrono::NaiveDateTime>> {
+    elements
+        .iter()
+        .map(|element| unit.value_to_datetime(*element))
+        .collect()
+}
+
+/// Convert [`chrono::NaiveDateTime`]s to the elements of a `datetime64` array with `unit`.
+///
+/// This is intended for the input of [`Array`](crate::array::Array) `_elements` methods with an [`i64`] element type.
+/// A [`None`] datetime is converted to "not a time" ([`NAT`]).
+/// Returns [`None`] if any datetime cannot be represented in `unit`.
+#[cfg(feature = "chrono")]
+#[must_use]
+pub fn chrono_to_datetime64_elements(
+    datetimes: &[Option<chrono::NaiveDateTime>],
+    unit: TimeUnit,
+) -> Option<Vec<i64>> {
+    datetimes
+        .iter()
+        .map(|datetime| {
+            datetime
+                .as_ref()
+                .map_or(Some(NAT), |datetime| unit.datetime_to_value(datetime))
+        })
+        .collect()
+}
+
+/// Convert the elements of a `timedelta64` array with `unit` to [`chrono::TimeDelta`]s.
+///
+/// This is intended for the output of [`Array`](crate::array::Array) `_elements` methods with an [`i64`] element type.
+/// An element is [`None`] if it is "not a time" ([`NAT`]) or cannot be represented by a [`chrono::TimeDelta`].
+#[cfg(feature = "chrono")]
+#[must_use]
+pub fn timedelta64_elements_to_chrono(
+    elements: &[i64],
+    unit: TimeUnit,
+) -> Vec<Option<chrono::TimeDelta>> {
+    elements
+        .iter()
+        .map(|element| unit.value_to_timedelta(*element))
+        .collect()
+}
+
+/// Convert [`chrono::TimeDelta`]s to the elements of a `timedelta64` array with `unit`.
+///
+/// This is intended for the input of [`Array`](crate::array::Array) `_elements` methods with an [`i64`] element type.
+/// A [`None`] time delta is converted to "not a time" ([`NAT`]).
+/// Returns [`None`] if any time delta cannot be represented in `unit`.
+#[cfg(feature = "chrono")]
+#[must_use]
+pub fn chrono_to_timedelta64_elements(
+    timedeltas: &[Option<chrono::TimeDelta>],
+    unit: TimeUnit,
+) -> Option<Vec<i64>> {
+    timedeltas
+        .iter()
+        .map(|timedelta| {
+            timedelta
+                .as_ref()
+                .map_or(Some(NAT), |timedelta| unit.timedelta_to_value(timedelta))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_unit_str() {
+        assert_eq!("ns".parse::<TimeUnit>(), Ok(TimeUnit::Nanosecond));
+        assert_eq!("M".parse::<TimeUnit>(), Ok(TimeUnit::Month));
+        assert_eq!("m".parse::<TimeUnit>(), Ok(TimeUnit::Minute));
+        assert!("x".parse::<TimeUnit>().is_err());
+        assert_eq!(TimeUnit::Microsecond.to_string(), "us");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn time_unit_datetime() {
+        use chrono::{NaiveDate, Timelike};
+
+        let datetime = NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_nano_opt(12, 30, 15, 123_456_789)
+            .unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        for (unit, value, rounded) in [
+            (
+                TimeUnit::Year,
+                54,
+                NaiveDate::from_ymd_opt(2024, 1, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            ),
+            (
+                TimeUnit::Month,
+                649,
+                NaiveDate::from_ymd_opt(2024, 2, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            ),
+            (TimeUnit::Day, 19782, date),
+            (
+                TimeUnit::Second,
+                1_709_209_815,
+                datetime.with_nanosecond(0).unwrap(),
+            ),
+            (TimeUnit::Nanosecond, 1_709_209_815_123_456_789, datetime),
+        ] {
+            assert_eq!(unit.datetime_to_value(&datetime), Some(value));
+            assert_eq!(unit.value_to_datetime(value), Some(rounded));
+        }
+
+        let before_epoch = NaiveDate::from_ymd_opt(1969, 12, 31)
+            .unwrap()
+            .and_hms_milli_opt(23, 59, 59, 500)
+            .unwrap();
+        assert_eq!(TimeUnit::Second.datetime_to_value(&before_epoch), Some(-1));
+        assert_eq!(
+            TimeUnit::Millisecond.datetime_to_value(&before_epoch),
+            Some(-500)
+        );
+        assert_eq!(TimeUnit::Month.datetime_to_value(&before_epoch), Some(-1));
+        assert_eq!(
+            TimeUnit::Millisecond.value_to_datetime(-500),
+            Some(before_epoch)
+        );
+        assert_eq!(TimeUnit::Nanosecond.value_to_datetime(NAT), None);
+        assert_eq!(TimeUnit::Attosecond.datetime_to_value(&datetime), None);
+
+        let elements = vec![0, NAT, 86_400];
+        let datetimes = datetime64_elements_to_chrono(&elements, TimeUnit::Second);
+        assert_eq!(datetimes[1], None);
+        assert_eq!(
+            chrono_to_datetime64_elements(&datetimes, TimeUnit::Second),
+            Some(elements)
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn time_unit_timedelta() {
+        use chrono::TimeDelta;
+
+        let timedelta = TimeDelta::try_milliseconds(-1500).unwrap();
+        assert_eq!(TimeUnit::Second.timedelta_to_value(&timedelta), Some(-2));
+        assert_eq!(
+            TimeUnit::Millisecond.timedelta_to_value(&timedelta),
+            Some(-1500)
+        );
+        assert_eq!(
+            TimeUnit::Picosecond.timedelta_to_value(&timedelta),
+            Some(-1_500_000_000_000)
+        );
+        assert_eq!(
+            TimeUnit::Millisecond.value_to_timedelta(-1500),
+            Some(timedelta)
+        );
+        assert_eq!(
+            TimeUnit::Week.value_to_timedelta(2),
+            Some(TimeDelta::try_weeks(2).unwrap())
+        );
+        assert_eq!(TimeUnit::Month.value_to_timedelta(1), None);
+        assert_eq!(TimeUnit::Year.timedelta_to_value(&timedelta), None);
+
+        let elements = vec![NAT, 3, -7];
+        let timedeltas = timedelta64_elements_to_chrono(&elements, TimeUnit::Hour);
+        assert_eq!(timedeltas[0], None);
+        assert_eq!(
+            chrono_to_timedelta64_elements(&timedeltas, TimeUnit::Hour),
+            Some(elements)
+        );
+    }
+}
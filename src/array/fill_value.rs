@@ -142,64 +142,50 @@ impl FillValue {
     }
 
     /// Check if the bytes are equal to a sequence of the fill value.
-    #[allow(clippy::missing_panics_doc)]
+    ///
+    /// Trailing bytes which do not form a complete element are ignored.
+    ///
+    /// The bytes are compared in blocks against a repeated fill value pattern rather than element-by-element, so the comparison is vectorised regardless of the alignment of `bytes` or the size of the fill value.
+    /// All-zero fill values take a faster path which does not read a pattern.
     #[must_use]
     pub fn equals_all(&self, bytes: &[u8]) -> bool {
-        match self.0.len() {
-            1 => {
-                let fill_value = self.0[0];
-                let fill_value_128 = u128::from_ne_bytes([self.0[0]; 16]);
-                let (prefix, aligned, suffix) = unsafe { bytes.align_to::<u128>() };
-                prefix.iter().all(|x| x == &fill_value)
-                    && suffix.iter().all(|x| x == &fill_value)
-                    && aligned.iter().all(|x| x == &fill_value_128)
-            }
-            2 => {
-                let (prefix, aligned, suffix) = unsafe { bytes.align_to::<u128>() };
-                if prefix.is_empty() && suffix.is_empty() {
-                    let fill_value_128 =
-                        u128::from_ne_bytes(self.0[..2].repeat(8).try_into().unwrap());
-                    aligned.iter().all(|x| x == &fill_value_128)
-                } else {
-                    bytes.chunks_exact(2).all(|x| x == self.0)
-                }
-            }
-            4 => {
-                let (prefix, aligned, suffix) = unsafe { bytes.align_to::<u128>() };
-                if prefix.is_empty() && suffix.is_empty() {
-                    let fill_value_128 =
-                        u128::from_ne_bytes(self.0[..4].repeat(4).try_into().unwrap());
-                    aligned.iter().all(|x| x == &fill_value_128)
-                } else {
-                    bytes.chunks_exact(4).all(|x| x == self.0)
-                }
-            }
-            8 => {
-                let (prefix, aligned, suffix) = unsafe { bytes.align_to::<u128>() };
-                if prefix.is_empty() && suffix.is_empty() {
-                    let fill_value_128 =
-                        u128::from_ne_bytes(self.0[..8].repeat(2).try_into().unwrap());
-                    aligned.iter().all(|x| x == &fill_value_128)
-                } else {
-                    bytes.chunks_exact(8).all(|x| x == self.0)
-                }
-            }
-            16 => {
-                let (prefix, aligned, suffix) = unsafe { bytes.align_to::<u128>() };
-                if prefix.is_empty() && suffix.is_empty() {
-                    let fill_value_128 = u128::from_ne_bytes(self.0[..16].try_into().unwrap());
-                    aligned.iter().all(|x| x == &fill_value_128)
-                } else {
-                    bytes.chunks_exact(16).all(|x| x == self.0)
-                }
+        let element_size = self.0.len();
+        if element_size == 0 {
+            return true;
+        }
+        let bytes = &bytes[..bytes.len() - bytes.len() % element_size];
+
+        if self.0.iter().all(|&x| x == 0) {
+            let mut blocks = bytes.chunks_exact(EQUALS_ALL_BLOCK_SIZE);
+            blocks
+                .by_ref()
+                .all(|block| block.iter().fold(0, |acc, &x| acc | x) == 0)
+                && blocks.remainder().iter().all(|&x| x == 0)
+        } else if element_size <= EQUALS_ALL_BLOCK_SIZE {
+            let mut pattern = [0u8; EQUALS_ALL_BLOCK_SIZE];
+            let pattern_size = EQUALS_ALL_BLOCK_SIZE - EQUALS_ALL_BLOCK_SIZE % element_size;
+            for element in pattern[..pattern_size].chunks_exact_mut(element_size) {
+                element.copy_from_slice(&self.0);
             }
-            _ => bytes
-                .chunks_exact(self.0.len())
-                .all(|element| element == self.0),
+            equals_all_pattern(bytes, &pattern[..pattern_size])
+        } else {
+            equals_all_pattern(bytes, &self.0)
         }
     }
 }
 
+/// The size (in bytes) of the blocks compared by [`FillValue::equals_all`].
+const EQUALS_ALL_BLOCK_SIZE: usize = 512;
+
+/// Check if `bytes` is a sequence of `pattern`, where `bytes` may end with a partial `pattern`.
+fn equals_all_pattern(bytes: &[u8], pattern: &[u8]) -> bool {
+    let mut blocks = bytes.chunks_exact(pattern.len());
+    blocks.by_ref().all(|block| block == pattern) && {
+        let remainder = blocks.remainder();
+        remainder == &pattern[..remainder.len()]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::array::transmute_to_bytes_vec;
@@ -275,6 +261,44 @@ mod tests {
         assert!(!FillValue::from(1u64).equals_all(&transmute_to_bytes_vec(vec![0u64; 5])));
     }
 
+    #[test]
+    fn fill_value_equals_unaligned() {
+        let fill_value = FillValue::from(vec![1u8, 2, 3]);
+        let bytes = [1u8, 2, 3].repeat(1000);
+        for offset in 0..3 {
+            let bytes = &bytes[offset * 3..];
+            assert!(fill_value.equals_all(bytes));
+            // Trailing partial element is ignored
+            assert!(fill_value.equals_all(&bytes[..bytes.len() - 1]));
+        }
+        let mut bytes_last = bytes.clone();
+        *bytes_last.last_mut().unwrap() = 0;
+        assert!(!fill_value.equals_all(&bytes_last));
+        let mut bytes_first = bytes;
+        bytes_first[0] = 0;
+        assert!(!fill_value.equals_all(&bytes_first));
+    }
+
+    #[test]
+    fn fill_value_equals_zero() {
+        let bytes = vec![0u8; 1000 * 4 + 3];
+        assert!(FillValue::from(0u32).equals_all(&bytes[1..]));
+        for i in [0, 600, 1000 * 4 - 1] {
+            let mut bytes = vec![0u8; 1000 * 4];
+            bytes[i] = 1;
+            assert!(!FillValue::from(0u32).equals_all(&bytes));
+        }
+    }
+
+    #[test]
+    fn fill_value_equals_large() {
+        let fill_value = FillValue::from((0..=255).cycle().take(1000).collect::<Vec<u8>>());
+        let mut bytes = fill_value.as_ne_bytes().repeat(3);
+        assert!(fill_value.equals_all(&bytes));
+        bytes[2500] = 0;
+        assert!(!fill_value.equals_all(&bytes));
+    }
+
     #[test]
     fn fill_value_equals_complex32() {
         assert!(
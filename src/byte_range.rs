@@ -8,10 +8,12 @@
 //! - the bytes partial decoder of the next codec in the codec chain.
 //!
 //! This module provides the [`extract_byte_ranges`] convenience function for extracting byte ranges from a slice of bytes.
+//! [`extract_byte_ranges_bytes`] extracts byte ranges from [`Bytes`] without copying.
 //!
 
 use std::ops::Range;
 
+use bytes::Bytes;
 use thiserror::Error;
 
 /// A byte offset.
@@ -160,6 +162,26 @@ pub fn extract_byte_ranges(
     Ok(unsafe { extract_byte_ranges_unchecked(bytes, byte_ranges) })
 }
 
+/// Extract byte ranges from [`Bytes`] without copying.
+///
+/// Each output shares the underlying buffer of `bytes`.
+///
+/// # Errors
+/// Returns [`InvalidByteRangeError`] if any bytes are requested beyond the end of `bytes`.
+///
+/// # Panics
+/// Panics if attempting to reference a byte beyond `usize::MAX`.
+pub fn extract_byte_ranges_bytes(
+    bytes: &Bytes,
+    byte_ranges: &[ByteRange],
+) -> Result<Vec<Bytes>, InvalidByteRangeError> {
+    validate_byte_ranges(byte_ranges, bytes.len() as u64)?;
+    Ok(byte_ranges
+        .iter()
+        .map(|byte_range| bytes.slice(byte_range.to_range_usize(bytes.len() as u64)))
+        .collect())
+}
+
 /// Extract byte ranges from bytes.
 ///
 /// # Safety
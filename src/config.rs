@@ -3,7 +3,7 @@
 use std::sync::{OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 #[cfg(doc)]
-use crate::array::codec::CodecOptions;
+use crate::array::{codec::CodecOptions, Array};
 
 /// Global configuration options for the zarrs crate.
 ///
@@ -18,7 +18,7 @@ use crate::array::codec::CodecOptions;
 /// Note that regardless of this configuration option, checksum codecs may skip validation when partial decoding.
 ///
 /// ## Codec Concurrent Target
-/// > default: [`std::thread::available_parallelism`]`()`
+/// > default: [`std::thread::available_parallelism`]`()` (or 1 if unavailable, e.g. on `wasm32-unknown-unknown`)
 ///
 /// [`CodecOptions::concurrent_target()`] defaults to [`Config::codec_concurrent_target()`].
 ///
@@ -28,6 +28,15 @@ use crate::array::codec::CodecOptions;
 ///
 /// Note that the default codec concurrent target can be overridden for any encode/decode operation.
 /// This is performed automatically for many array operations (see the [chunk concurrent minimum](#chunk-concurrent-minimum) option).
+/// [`CodecOptions::max_memory()`] can additionally bound the number of chunks processed concurrently by array operations.
+///
+/// ## Store Empty Chunks
+/// > default: [`false`]
+///
+/// [`CodecOptions::store_empty_chunks()`] defaults to [`Config::store_empty_chunks()`].
+///
+/// If disabled, storing a chunk that is entirely the fill value erases the chunk from the store instead of encoding and storing it.
+/// If enabled, such chunks are encoded and stored like any other chunk, which keeps their keys present in the store (e.g. to mark a chunk as written).
 ///
 /// ## Chunk Concurrent Minimum
 /// > default: `4`
@@ -35,11 +44,35 @@ use crate::array::codec::CodecOptions;
 /// For array operations involving multiple chunks, this is the preferred minimum chunk concurrency.
 /// For example, `array_store_chunks` will concurrently encode and store up to four chunks at a time by default.
 /// The concurrency of internal codecs is adjusted to accomodate for the chunk concurrency in accordance with the concurrent target set in the [`CodecOptions`] parameter of an encode or decode method.
+///
+/// ## Include zarrs Metadata
+/// > default: [`true`]
+///
+/// [`Array::set_include_zarrs_metadata`] defaults to [`Config::include_zarrs_metadata()`] when an [`Array`] is created.
+///
+/// If enabled, the `zarrs` version and a link to its source code is written to the `_zarrs` attribute in array metadata.
+///
+/// ## Pretty Print Metadata
+/// > default: [`true`]
+///
+/// If enabled, array and group metadata JSON is pretty-printed when it is stored, otherwise it is stored in compact form.
+///
+/// ## Metadata Key
+/// > default: `zarr.json`
+///
+/// The name of the metadata document of a node (array or group), which is appended to the node path to form its store key (see [`meta_key`](crate::storage::meta_key)).
+/// This applies when reading and writing metadata.
+/// Changing this produces hierarchies which are not readable by other Zarr V3 implementations, so it is only intended for experimental layouts.
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     validate_checksums: bool,
     codec_concurrent_target: usize,
+    store_empty_chunks: bool,
     chunk_concurrent_minimum: usize,
+    include_zarrs_metadata: bool,
+    pretty_print_metadata: bool,
+    metadata_key: String,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -49,10 +82,15 @@ impl Default for Config {
         let concurrency_add = 0;
         Self {
             validate_checksums: true,
-            codec_concurrent_target: std::thread::available_parallelism().unwrap().get()
+            codec_concurrent_target: std::thread::available_parallelism()
+                .map_or(1, std::num::NonZeroUsize::get)
                 * concurrency_multiply
                 + concurrency_add,
+            store_empty_chunks: false,
             chunk_concurrent_minimum: 4,
+            include_zarrs_metadata: true,
+            pretty_print_metadata: true,
+            metadata_key: "zarr.json".to_string(),
         }
     }
 }
@@ -80,6 +118,17 @@ impl Config {
         self.codec_concurrent_target = concurrent_target;
     }
 
+    /// Get the [store empty chunks](#store-empty-chunks) configuration.
+    #[must_use]
+    pub fn store_empty_chunks(&self) -> bool {
+        self.store_empty_chunks
+    }
+
+    /// Set the [store empty chunks](#store-empty-chunks) configuration.
+    pub fn set_store_empty_chunks(&mut self, store_empty_chunks: bool) {
+        self.store_empty_chunks = store_empty_chunks;
+    }
+
     /// Get the [chunk concurrent minimum](#chunk-concurrent-minimum) configuration.
     #[must_use]
     pub fn chunk_concurrent_minimum(&self) -> usize {
@@ -90,6 +139,39 @@ impl Config {
     pub fn set_chunk_concurrent_minimum(&mut self, concurrent_minimum: usize) {
         self.chunk_concurrent_minimum = concurrent_minimum;
     }
+
+    /// Get the [include zarrs metadata](#include-zarrs-metadata) configuration.
+    #[must_use]
+    pub fn include_zarrs_metadata(&self) -> bool {
+        self.include_zarrs_metadata
+    }
+
+    /// Set the [include zarrs metadata](#include-zarrs-metadata) configuration.
+    pub fn set_include_zarrs_metadata(&mut self, include_zarrs_metadata: bool) {
+        self.include_zarrs_metadata = include_zarrs_metadata;
+    }
+
+    /// Get the [pretty print metadata](#pretty-print-metadata) configuration.
+    #[must_use]
+    pub fn pretty_print_metadata(&self) -> bool {
+        self.pretty_print_metadata
+    }
+
+    /// Set the [pretty print metadata](#pretty-print-metadata) configuration.
+    pub fn set_pretty_print_metadata(&mut self, pretty_print_metadata: bool) {
+        self.pretty_print_metadata = pretty_print_metadata;
+    }
+
+    /// Get the [metadata key](#metadata-key) configuration.
+    #[must_use]
+    pub fn metadata_key(&self) -> &str {
+        &self.metadata_key
+    }
+
+    /// Set the [metadata key](#metadata-key) configuration.
+    pub fn set_metadata_key(&mut self, metadata_key: String) {
+        self.metadata_key = metadata_key;
+    }
 }
 
 static CONFIG: OnceLock<RwLock<Config>> = OnceLock::new();
@@ -127,4 +209,44 @@ mod tests {
         assert!(!global_config().validate_checksums());
         global_config_mut().set_validate_checksums(true);
     }
+
+    #[test]
+    fn config_store_empty_chunks() {
+        let mut config = Config::default();
+        assert!(!config.store_empty_chunks());
+        config.set_store_empty_chunks(true);
+        assert!(config.store_empty_chunks());
+    }
+
+    #[test]
+    fn config_metadata() {
+        let mut config = Config::default();
+        assert!(config.include_zarrs_metadata());
+        assert!(config.pretty_print_metadata());
+        assert_eq!(config.metadata_key(), "zarr.json");
+        config.set_include_zarrs_metadata(false);
+        config.set_pretty_print_metadata(false);
+        config.set_metadata_key("metadata.json".to_string());
+        assert!(!config.include_zarrs_metadata());
+        assert!(!config.pretty_print_metadata());
+        assert_eq!(config.metadata_key(), "metadata.json");
+    }
+
+    #[test]
+    fn config_pretty_print_metadata() {
+        use crate::{
+            group::GroupBuilder,
+            storage::{meta_key, store::MemoryStore, ReadableStorageTraits},
+        };
+
+        let store = std::sync::Arc::new(MemoryStore::new());
+        let group = GroupBuilder::new().build(store.clone(), "/").unwrap();
+        let key = meta_key(group.path());
+        group.store_metadata().unwrap();
+        assert!(store.get(&key).unwrap().unwrap().contains(&b'\n'));
+        global_config_mut().set_pretty_print_metadata(false);
+        group.store_metadata().unwrap();
+        global_config_mut().set_pretty_print_metadata(true);
+        assert!(!store.get(&key).unwrap().unwrap().contains(&b'\n'));
+    }
 }
@@ -5,6 +5,7 @@
 //! See <https://zarr-specs.readthedocs.io/en/latest/v3/core/v3.0.html#group>.
 //!
 //! Use [`GroupBuilder`] to setup a new group, or use [`Group::new`] to read and/or write an existing group.
+//! Child groups and arrays can be created with [`Group::create_group`] and [`Group::create_array`], and listed with [`Group::children`].
 //!
 //! A group can optionally store attributes in metadata in an accompanying `zarr.json` file. For example:
 //! ```json
@@ -18,6 +19,9 @@
 //! }
 //! ```
 //! See <https://zarr-specs.readthedocs.io/en/latest/v3/core/v3.0.html#group-metadata> for more information on group metadata.
+//!
+//! Zarr V2 groups (with `.zgroup` and `.zattrs` metadata) are also supported.
+//! Their metadata is preserved as [`GroupMetadataV2`] and written back in the Zarr V2 format by [`Group::store_metadata`].
 
 mod group_builder;
 mod group_metadata;
@@ -25,22 +29,30 @@ mod group_metadata;
 use std::sync::Arc;
 
 use derive_more::Display;
+use serde::{de::DeserializeOwned, Serialize};
 use thiserror::Error;
 
 use crate::{
+    array::{Array, ArrayBuilder, ArrayCreateError},
     metadata::{AdditionalFields, UnsupportedAdditionalFieldError},
-    node::{NodePath, NodePathError},
+    node::{Node, NodeMetadata, NodeName, NodeNameError, NodePath, NodePathError},
     storage::{
-        meta_key, ReadableStorageTraits, StorageError, StorageHandle, WritableStorageTraits,
+        discover_children, erase_node, get_child_nodes, get_node_metadata, merge_attributes,
+        update_attributes, ListableStorageTraits, ReadableStorageTraits,
+        ReadableWritableStorageTraits, StorageError, StorageHandle, WritableStorageTraits,
     },
 };
 
 #[cfg(feature = "async")]
-use crate::storage::{AsyncReadableStorageTraits, AsyncWritableStorageTraits};
+use crate::storage::{
+    async_discover_children, async_erase_node, async_get_child_nodes, async_get_node_metadata,
+    async_merge_attributes, async_update_attributes, AsyncListableStorageTraits,
+    AsyncReadableStorageTraits, AsyncReadableWritableStorageTraits, AsyncWritableStorageTraits,
+};
 
 pub use self::{
     group_builder::GroupBuilder,
-    group_metadata::{GroupMetadata, GroupMetadataV3},
+    group_metadata::{GroupMetadata, GroupMetadataV2, GroupMetadataV3},
 };
 
 /// A group.
@@ -57,7 +69,7 @@ pub struct Group<TStorage: ?Sized> {
     #[allow(dead_code)]
     path: NodePath,
     /// The metadata.
-    metadata: GroupMetadataV3,
+    metadata: GroupMetadata,
 }
 
 impl<TStorage: ?Sized> Group<TStorage> {
@@ -73,7 +85,6 @@ impl<TStorage: ?Sized> Group<TStorage> {
         metadata: GroupMetadata,
     ) -> Result<Self, GroupCreateError> {
         let path = NodePath::new(path)?;
-        let GroupMetadata::V3(metadata) = metadata;
         validate_group_metadata(&metadata)?;
         Ok(Self {
             storage,
@@ -91,48 +102,108 @@ impl<TStorage: ?Sized> Group<TStorage> {
     /// Get attributes.
     #[must_use]
     pub const fn attributes(&self) -> &serde_json::Map<String, serde_json::Value> {
-        &self.metadata.attributes
+        match &self.metadata {
+            GroupMetadata::V3(metadata) => &metadata.attributes,
+            GroupMetadata::V2(metadata) => &metadata.attributes,
+        }
     }
 
     /// Get additional fields.
     #[must_use]
     pub const fn additional_fields(&self) -> &AdditionalFields {
-        &self.metadata.additional_fields
+        match &self.metadata {
+            GroupMetadata::V3(metadata) => &metadata.additional_fields,
+            GroupMetadata::V2(metadata) => &metadata.additional_fields,
+        }
     }
 
     /// Get metadata.
     #[must_use]
     pub fn metadata(&self) -> GroupMetadata {
-        self.metadata.clone().into()
+        self.metadata.clone()
     }
 
     /// Mutably borrow the group attributes.
     #[must_use]
     pub fn attributes_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
-        &mut self.metadata.attributes
+        match &mut self.metadata {
+            GroupMetadata::V3(metadata) => &mut metadata.attributes,
+            GroupMetadata::V2(metadata) => &mut metadata.attributes,
+        }
+    }
+
+    /// Deserialise the attribute `key` as `T`.
+    ///
+    /// Returns [`None`] if the attribute does not exist.
+    ///
+    /// # Errors
+    /// Returns a [`serde_json::Error`] if the attribute cannot be deserialised as `T`.
+    pub fn attribute<T: DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, serde_json::Error> {
+        self.attributes()
+            .get(key)
+            .map(|value| T::deserialize(value))
+            .transpose()
+    }
+
+    /// Serialise `value` and set it as the attribute `key`, returning the previous value of the attribute.
+    ///
+    /// This does **not** write to the store, use [`store_attributes`](Group::store_attributes) or [`store_metadata`](Group::store_metadata) to write the attributes to the store.
+    ///
+    /// # Errors
+    /// Returns a [`serde_json::Error`] if `value` cannot be serialised.
+    pub fn set_attribute<T: Serialize>(
+        &mut self,
+        key: &str,
+        value: T,
+    ) -> Result<Option<serde_json::Value>, serde_json::Error> {
+        Ok(self
+            .attributes_mut()
+            .insert(key.to_string(), serde_json::to_value(value)?))
     }
 
     /// Mutably borrow the additional fields.
     #[must_use]
     pub fn additional_fields_mut(&mut self) -> &mut AdditionalFields {
-        &mut self.metadata.additional_fields
+        match &mut self.metadata {
+            GroupMetadata::V3(metadata) => &mut metadata.additional_fields,
+            GroupMetadata::V2(metadata) => &mut metadata.additional_fields,
+        }
+    }
+
+    /// Return the path of the child node with `name`.
+    ///
+    /// # Errors
+    /// Returns a [`NodeNameError`] if `name` is not a valid node name or is the root node name.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn child_path(&self, name: &str) -> Result<NodePath, NodeNameError> {
+        let node_name = NodeName::new(name)?;
+        if node_name.is_root() {
+            return Err(NodeNameError::new(name));
+        }
+        let path = if self.path.as_str() == "/" {
+            format!("/{name}")
+        } else {
+            format!("{}/{name}", self.path.as_str())
+        };
+        Ok(NodePath::new(&path).unwrap()) // safe, the parent path and node name are valid
     }
 }
 
 impl<TStorage: ?Sized + ReadableStorageTraits> Group<TStorage> {
     /// Create a group in `storage` at `path`. The metadata is read from the store.
     ///
+    /// If `zarr.json` is absent, Zarr V2 group metadata is read from `.zgroup` and `.zattrs`.
+    /// The group is an implicit group with default metadata if no metadata exists.
+    ///
     /// # Errors
     ///
     /// Returns [`GroupCreateError`] if there is a storage error or any metadata is invalid.
     pub fn new(storage: Arc<TStorage>, path: &str) -> Result<Self, GroupCreateError> {
         let node_path = path.try_into()?;
-        let key = meta_key(&node_path);
-        let metadata: GroupMetadata = match storage.get(&key)? {
-            Some(metadata) => serde_json::from_slice(&metadata)
-                .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?,
-            None => GroupMetadataV3::default().into(),
-        };
+        let metadata = group_metadata(get_node_metadata(&*storage, &node_path)?)?;
         Self::new_with_metadata(storage, path, metadata)
     }
 }
@@ -146,12 +217,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits> Group<TStorage> {
     /// Returns [`GroupCreateError`] if there is a storage error or any metadata is invalid.
     pub async fn async_new(storage: Arc<TStorage>, path: &str) -> Result<Self, GroupCreateError> {
         let node_path = path.try_into()?;
-        let key = meta_key(&node_path);
-        let metadata: GroupMetadata = match storage.get(&key).await? {
-            Some(metadata) => serde_json::from_slice(&metadata)
-                .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?,
-            None => GroupMetadataV3::default().into(),
-        };
+        let metadata = group_metadata(async_get_node_metadata(&*storage, &node_path).await?)?;
         Self::new_with_metadata(storage, path, metadata)
     }
 }
@@ -168,6 +234,9 @@ pub enum GroupCreateError {
     /// An invalid node path
     #[error(transparent)]
     NodePathError(#[from] NodePathError),
+    /// An invalid node name.
+    #[error(transparent)]
+    NodeNameError(#[from] NodeNameError),
     /// Unsupported additional field.
     #[error(transparent)]
     UnsupportedAdditionalFieldError(UnsupportedAdditionalFieldError),
@@ -176,22 +245,143 @@ pub enum GroupCreateError {
     StorageError(#[from] StorageError),
 }
 
-fn validate_group_metadata(metadata: &GroupMetadataV3) -> Result<(), GroupCreateError> {
-    if !metadata.validate_format() {
-        Err(GroupCreateError::InvalidZarrFormat(metadata.zarr_format))
-    } else if !metadata.validate_node_type() {
-        Err(GroupCreateError::InvalidNodeType(
-            metadata.node_type.clone(),
-        ))
-    } else {
-        metadata
-            .additional_fields
-            .validate()
-            .map_err(GroupCreateError::UnsupportedAdditionalFieldError)
+/// Return the group metadata of node metadata, which must not be array metadata.
+fn group_metadata(metadata: NodeMetadata) -> Result<GroupMetadata, GroupCreateError> {
+    match metadata {
+        NodeMetadata::Group(metadata) => Ok(metadata),
+        NodeMetadata::Array(_) => Err(GroupCreateError::InvalidNodeType("array".to_string())),
+    }
+}
+
+fn validate_group_metadata(metadata: &GroupMetadata) -> Result<(), GroupCreateError> {
+    match metadata {
+        GroupMetadata::V3(metadata) => {
+            if !metadata.validate_format() {
+                Err(GroupCreateError::InvalidZarrFormat(metadata.zarr_format))
+            } else if !metadata.validate_node_type() {
+                Err(GroupCreateError::InvalidNodeType(
+                    metadata.node_type.clone(),
+                ))
+            } else {
+                metadata
+                    .additional_fields
+                    .validate()
+                    .map_err(GroupCreateError::UnsupportedAdditionalFieldError)
+            }
+        }
+        GroupMetadata::V2(metadata) => {
+            if metadata.validate_format() {
+                Ok(())
+            } else {
+                Err(GroupCreateError::InvalidZarrFormat(metadata.zarr_format))
+            }
+        }
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits> Group<TStorage> {
+    /// Return the child nodes of the group, including their descendants.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an underlying store error or child metadata is invalid.
+    pub fn children(&self) -> Result<Vec<Node>, StorageError> {
+        get_child_nodes(&*self.storage, &self.path)
+    }
+
+    /// Return the metadata of the direct children of the group.
+    fn child_metadatas(&self) -> Result<Vec<(NodePath, NodeMetadata)>, StorageError> {
+        discover_children(&*self.storage, &self.path)?
+            .iter()
+            .map(|prefix| {
+                let path: NodePath = prefix.try_into()?;
+                let metadata = get_node_metadata(&*self.storage, &path)?;
+                Ok((path, metadata))
+            })
+            .collect()
+    }
+
+    /// Return the child groups of the group, sharing the storage of the group.
+    ///
+    /// # Errors
+    /// Returns a [`GroupCreateError`] if there is an underlying store error or child group metadata is invalid.
+    pub fn child_groups(&self) -> Result<Vec<Self>, GroupCreateError> {
+        child_groups(&self.storage, self.child_metadatas()?)
+    }
+
+    /// Return the child arrays of the group, sharing the storage of the group.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayCreateError`] if there is an underlying store error or child array metadata is invalid.
+    pub fn child_arrays(&self) -> Result<Vec<Array<TStorage>>, ArrayCreateError> {
+        child_arrays(&self.storage, self.child_metadatas()?)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits> Group<TStorage> {
+    /// Async variant of [`children`](Group::children).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_children(&self) -> Result<Vec<Node>, StorageError> {
+        async_get_child_nodes(&*self.storage, &self.path).await
+    }
+
+    /// Async variant of [`child_metadatas`](Group::child_metadatas).
+    async fn async_child_metadatas(&self) -> Result<Vec<(NodePath, NodeMetadata)>, StorageError> {
+        let mut metadatas = Vec::new();
+        for prefix in &async_discover_children(&*self.storage, &self.path).await? {
+            let path: NodePath = prefix.try_into()?;
+            let metadata = async_get_node_metadata(&*self.storage, &path).await?;
+            metadatas.push((path, metadata));
+        }
+        Ok(metadatas)
+    }
+
+    /// Async variant of [`child_groups`](Group::child_groups).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_child_groups(&self) -> Result<Vec<Self>, GroupCreateError> {
+        child_groups(&self.storage, self.async_child_metadatas().await?)
+    }
+
+    /// Async variant of [`child_arrays`](Group::child_arrays).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_child_arrays(&self) -> Result<Vec<Array<TStorage>>, ArrayCreateError> {
+        child_arrays(&self.storage, self.async_child_metadatas().await?)
     }
 }
 
-impl<TStorage: ?Sized + ReadableStorageTraits> Group<TStorage> {}
+fn child_groups<TStorage: ?Sized>(
+    storage: &Arc<TStorage>,
+    metadatas: Vec<(NodePath, NodeMetadata)>,
+) -> Result<Vec<Group<TStorage>>, GroupCreateError> {
+    metadatas
+        .into_iter()
+        .filter_map(|(path, metadata)| match metadata {
+            NodeMetadata::Group(metadata) => Some(Group::new_with_metadata(
+                storage.clone(),
+                path.as_str(),
+                metadata,
+            )),
+            NodeMetadata::Array(_) => None,
+        })
+        .collect()
+}
+
+fn child_arrays<TStorage: ?Sized>(
+    storage: &Arc<TStorage>,
+    metadatas: Vec<(NodePath, NodeMetadata)>,
+) -> Result<Vec<Array<TStorage>>, ArrayCreateError> {
+    metadatas
+        .into_iter()
+        .filter_map(|(path, metadata)| match metadata {
+            NodeMetadata::Array(metadata) => Some(Array::new_with_metadata(
+                storage.clone(),
+                path.as_str(),
+                metadata,
+            )),
+            NodeMetadata::Group(_) => None,
+        })
+        .collect()
+}
 
 impl<TStorage: ?Sized + WritableStorageTraits + 'static> Group<TStorage> {
     /// Store metadata.
@@ -218,6 +408,136 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits> Group<TStorage> {
     }
 }
 
+impl<TStorage: ?Sized + WritableStorageTraits + 'static> Group<TStorage> {
+    /// Create a child group with `name` and default metadata, and store its metadata.
+    ///
+    /// The child group shares the storage of this group.
+    ///
+    /// # Errors
+    /// Returns a [`GroupCreateError`] if `name` is not a valid node name or there is an underlying store error.
+    pub fn create_group(&self, name: &str) -> Result<Self, GroupCreateError> {
+        let path = self.child_path(name)?;
+        let group = GroupBuilder::new().build(self.storage.clone(), path.as_str())?;
+        group.store_metadata()?;
+        Ok(group)
+    }
+
+    /// Create a child array with `name` from `builder`, and store its metadata.
+    ///
+    /// The child array shares the storage of this group.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayCreateError`] if `name` is not a valid node name, the array cannot be built, or there is an underlying store error.
+    pub fn create_array(
+        &self,
+        name: &str,
+        builder: &ArrayBuilder,
+    ) -> Result<Array<TStorage>, ArrayCreateError> {
+        let path = self.child_path(name)?;
+        let array = builder.build(self.storage.clone(), path.as_str())?;
+        array.store_metadata()?;
+        Ok(array)
+    }
+
+    /// Erase the child node (group or array) with `name` and all of its children.
+    ///
+    /// Succeeds if the child node does not exist.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if `name` is not a valid node name or there is an underlying store error.
+    pub fn delete_child(&self, name: &str) -> Result<(), StorageError> {
+        let path = self.child_path(name)?;
+        let storage_handle = StorageHandle::new(self.storage.clone());
+        erase_node(&storage_handle, &path)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Group<TStorage> {
+    /// Async variant of [`create_group`](Group::create_group).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_create_group(&self, name: &str) -> Result<Self, GroupCreateError> {
+        let path = self.child_path(name)?;
+        let group = GroupBuilder::new().build(self.storage.clone(), path.as_str())?;
+        group.async_store_metadata().await?;
+        Ok(group)
+    }
+
+    /// Async variant of [`create_array`](Group::create_array).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_create_array(
+        &self,
+        name: &str,
+        builder: &ArrayBuilder,
+    ) -> Result<Array<TStorage>, ArrayCreateError> {
+        let path = self.child_path(name)?;
+        let array = builder.build(self.storage.clone(), path.as_str())?;
+        array.async_store_metadata().await?;
+        Ok(array)
+    }
+
+    /// Async variant of [`delete_child`](Group::delete_child).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_delete_child(&self, name: &str) -> Result<(), StorageError> {
+        let path = self.child_path(name)?;
+        let storage_handle = StorageHandle::new(self.storage.clone());
+        async_erase_node(&storage_handle, &path).await
+    }
+}
+
+impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Group<TStorage> {
+    /// Store the attributes in the existing group metadata.
+    ///
+    /// Unlike [`store_metadata`](Group::store_metadata), only the attributes of the stored group metadata are updated.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the group metadata does not exist in the store or there is an underlying store error.
+    pub fn store_attributes(&self) -> Result<(), StorageError> {
+        let storage_handle = StorageHandle::new(self.storage.clone());
+        update_attributes(&storage_handle, self.path(), self.attributes())
+    }
+
+    /// Merge `patch` into the attributes of the stored group metadata, and update the attributes of the group to the merged attributes.
+    ///
+    /// The patch is applied with JSON merge patch semantics ([RFC 7386](https://www.rfc-editor.org/rfc/rfc7386)):
+    /// objects are merged recursively, a `null` value removes the attribute, and other values replace the existing attribute.
+    /// The stored metadata is read and written back under the lock of the metadata key, and with a conditional write if supported by the store.
+    /// Unlike [`store_attributes`](Group::store_attributes), concurrent writers updating different attributes do not overwrite each other.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the group metadata does not exist in the store or there is an underlying store error.
+    pub fn store_attributes_merge(
+        &mut self,
+        patch: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), StorageError> {
+        let storage_handle = StorageHandle::new(self.storage.clone());
+        *self.attributes_mut() = merge_attributes(&storage_handle, self.path(), patch)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits> Group<TStorage> {
+    /// Async variant of [`store_attributes`](Group::store_attributes).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_store_attributes(&self) -> Result<(), StorageError> {
+        let storage_handle = StorageHandle::new(self.storage.clone());
+        async_update_attributes(&storage_handle, self.path(), self.attributes()).await
+    }
+
+    /// Async variant of [`store_attributes_merge`](Group::store_attributes_merge).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_store_attributes_merge(
+        &mut self,
+        patch: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), StorageError> {
+        let storage_handle = StorageHandle::new(self.storage.clone());
+        *self.attributes_mut() =
+            async_merge_attributes(&storage_handle, self.path(), patch).await?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::storage::{store::MemoryStore, StoreKey};
@@ -398,6 +718,119 @@ mod tests {
         assert_eq!(metadata, group.metadata());
     }
 
+    #[test]
+    fn group_typed_attributes() {
+        let store = std::sync::Arc::new(MemoryStore::new());
+        let group_path = "/group";
+        let group_metadata: GroupMetadata = serde_json::from_str(
+            r#"{
+            "zarr_format": 3,
+            "node_type": "group",
+            "extra": { "must_understand": false }
+        }"#,
+        )
+        .unwrap();
+        let mut group =
+            Group::new_with_metadata(store.clone(), group_path, group_metadata).unwrap();
+        assert!(group.store_attributes().is_err()); // no metadata
+        group.store_metadata().unwrap();
+        *group.additional_fields_mut() = AdditionalFields::default();
+
+        group.set_attribute("spam", "ham").unwrap();
+        assert_eq!(
+            group.set_attribute("spam", "eggs").unwrap(),
+            Some(serde_json::Value::String("ham".to_string()))
+        );
+        assert_eq!(
+            group.attribute::<String>("spam").unwrap().as_deref(),
+            Some("eggs")
+        );
+        group.store_attributes().unwrap();
+
+        let group_stored = Group::new(store, group_path).unwrap();
+        assert_eq!(
+            group_stored.attribute::<String>("spam").unwrap().as_deref(),
+            Some("eggs")
+        );
+        assert_eq!(group_stored.additional_fields().as_map().len(), 1); // only attributes are updated
+    }
+
+    #[test]
+    fn group_store_attributes_merge() {
+        let store = Arc::new(MemoryStore::new());
+        let mut group = GroupBuilder::new().build(store.clone(), "/group").unwrap();
+        let patch = |json: serde_json::Value| json.as_object().unwrap().clone();
+        assert!(group
+            .store_attributes_merge(&patch(serde_json::json!({"spam": "ham"})))
+            .is_err()); // no metadata
+        group.set_attribute("spam", "ham").unwrap();
+        group
+            .set_attribute("nested", serde_json::json!({"a": 1, "b": 2}))
+            .unwrap();
+        group.store_metadata().unwrap();
+
+        // Concurrent merges of different attributes do not overwrite each other
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let store = store.clone();
+                scope.spawn(move || {
+                    let mut group = Group::new(store, "/group").unwrap();
+                    group
+                        .store_attributes_merge(&patch(
+                            serde_json::json!({ format!("attr{i}"): i }),
+                        ))
+                        .unwrap();
+                });
+            }
+        });
+        let group_stored = Group::new(store.clone(), "/group").unwrap();
+        for i in 0..8 {
+            assert_eq!(
+                group_stored.attribute::<i32>(&format!("attr{i}")).unwrap(),
+                Some(i)
+            );
+        }
+
+        // Objects are merged recursively and null removes an attribute
+        group
+            .store_attributes_merge(&patch(
+                serde_json::json!({"spam": null, "nested": {"b": null, "c": 3}, "eggs": 42}),
+            ))
+            .unwrap();
+        assert_eq!(group.attribute::<String>("spam").unwrap(), None);
+        assert_eq!(group.attribute::<i32>("attr0").unwrap(), Some(0));
+        assert_eq!(
+            group.attribute::<serde_json::Value>("nested").unwrap(),
+            Some(serde_json::json!({"a": 1, "c": 3}))
+        );
+        let group_stored = Group::new(store.clone(), "/group").unwrap();
+        assert_eq!(group_stored.attributes(), group.attributes());
+
+        // Zarr V2
+        store
+            .set(
+                &StoreKey::new("group_v2/.zgroup").unwrap(),
+                br#"{"zarr_format": 2}"#,
+            )
+            .unwrap();
+        let mut group = Group::new(store.clone(), "/group_v2").unwrap();
+        group
+            .store_attributes_merge(&patch(serde_json::json!({"spam": "ham"})))
+            .unwrap();
+        let zattrs: serde_json::Value = serde_json::from_slice(
+            &store
+                .get(&StoreKey::new("group_v2/.zattrs").unwrap())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(zattrs, serde_json::json!({"spam": "ham"}));
+        assert_eq!(
+            group.attribute::<String>("spam").unwrap().as_deref(),
+            Some("ham")
+        );
+    }
+
     #[test]
     fn group_default() {
         let store = std::sync::Arc::new(MemoryStore::new());
@@ -406,4 +839,118 @@ mod tests {
         assert_eq!(group.attributes(), &serde_json::Map::default());
         assert_eq!(group.additional_fields(), &AdditionalFields::default());
     }
+
+    #[test]
+    fn group_children() {
+        use crate::array::{ArrayBuilder, DataType, FillValue};
+
+        let store = Arc::new(MemoryStore::new());
+        let root = GroupBuilder::new().build(store.clone(), "/").unwrap();
+        root.store_metadata().unwrap();
+        let group = root.create_group("group").unwrap();
+        assert_eq!(group.path().as_str(), "/group");
+        let builder = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        );
+        let array = group.create_array("array", &builder).unwrap();
+        assert_eq!(array.path().as_str(), "/group/array");
+        group.create_group("subgroup").unwrap();
+        assert!(root.create_group("").is_err());
+        assert!(root.create_group("a/b").is_err());
+        assert!(group.create_array("__array", &builder).is_err());
+
+        assert_eq!(root.children().unwrap().len(), 1);
+        assert_eq!(group.children().unwrap().len(), 2);
+        let child_groups = group.child_groups().unwrap();
+        assert_eq!(child_groups.len(), 1);
+        assert_eq!(child_groups[0].path().as_str(), "/group/subgroup");
+        let child_arrays = group.child_arrays().unwrap();
+        assert_eq!(child_arrays.len(), 1);
+        assert_eq!(child_arrays[0].shape(), &[4, 4]);
+        assert!(root.child_arrays().unwrap().is_empty());
+
+        group.delete_child("array").unwrap();
+        group.delete_child("missing").unwrap();
+        assert!(group.delete_child("a/b").is_err());
+        assert_eq!(group.children().unwrap().len(), 1);
+        assert!(group.child_arrays().unwrap().is_empty());
+    }
+
+    #[test]
+    fn group_v2() {
+        let store = Arc::new(MemoryStore::new());
+        store
+            .set(
+                &StoreKey::new("group/.zgroup").unwrap(),
+                br#"{"zarr_format": 2}"#,
+            )
+            .unwrap();
+        store
+            .set(
+                &StoreKey::new("group/.zattrs").unwrap(),
+                br#"{"spam": "ham"}"#,
+            )
+            .unwrap();
+
+        let mut group = Group::new(store.clone(), "/group").unwrap();
+        assert!(matches!(group.metadata(), GroupMetadata::V2(_)));
+        assert_eq!(
+            group.attribute::<String>("spam").unwrap().as_deref(),
+            Some("ham")
+        );
+
+        group.set_attribute("eggs", 42).unwrap();
+        group.store_attributes().unwrap();
+        let zattrs: serde_json::Value = serde_json::from_slice(
+            &store
+                .get(&StoreKey::new("group/.zattrs").unwrap())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(zattrs, serde_json::json!({"spam": "ham", "eggs": 42}));
+
+        group.attributes_mut().clear();
+        group.store_metadata().unwrap();
+        let zgroup: serde_json::Value = serde_json::from_slice(
+            &store
+                .get(&StoreKey::new("group/.zgroup").unwrap())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(zgroup, serde_json::json!({"zarr_format": 2}));
+        assert!(store
+            .get(&StoreKey::new("group/.zattrs").unwrap())
+            .unwrap()
+            .is_none());
+        assert!(store
+            .get(&StoreKey::new("group/zarr.json").unwrap())
+            .unwrap()
+            .is_none());
+
+        let group = GroupBuilder::new()
+            .build(store.clone(), "/group/child")
+            .unwrap();
+        group.store_metadata().unwrap();
+        let group = Group::new(store.clone(), "/group").unwrap();
+        assert_eq!(group.child_groups().unwrap().len(), 1);
+
+        let array = crate::array::ArrayBuilder::new(
+            vec![4],
+            crate::array::DataType::UInt8,
+            vec![2].try_into().unwrap(),
+            crate::array::FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        assert_eq!(
+            Group::new(store, "/array").unwrap_err().to_string(),
+            "invalid zarr format array, expected group"
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! Zarr group metadata.
 //!
-//! See <https://zarr-specs.readthedocs.io/en/latest/v3/core/v3.0.html#group-metadata>.
+//! See <https://zarr-specs.readthedocs.io/en/latest/v3/core/v3.0.html#group-metadata> and <https://zarr-specs.readthedocs.io/en/latest/v2/v2.0.html#groups>.
 
 use derive_more::{Display, From};
 use serde::{Deserialize, Serialize};
@@ -13,6 +13,8 @@ use crate::metadata::AdditionalFields;
 pub enum GroupMetadata {
     /// Version 3.0.
     V3(GroupMetadataV3),
+    /// Version 2.0.
+    V2(GroupMetadataV2),
 }
 
 /// Zarr group metadata (storage specification v3).
@@ -80,3 +82,52 @@ impl GroupMetadataV3 {
         self.node_type == "group"
     }
 }
+
+/// Zarr group metadata (storage specification v2).
+///
+/// See <https://zarr-specs.readthedocs.io/en/latest/v2/v2.0.html#groups>.
+///
+/// An example `JSON` document for a v2 group (`.zgroup`):
+/// ```json
+/// {
+///     "zarr_format": 2
+/// }
+/// ```
+///
+/// The `attributes` are stored separately in `.zattrs`.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display)]
+#[display(fmt = "{}", "serde_json::to_string(self).unwrap_or_default()")]
+pub struct GroupMetadataV2 {
+    /// An integer defining the version of the storage specification to which the group adheres. Must be `2`.
+    pub zarr_format: usize,
+    /// Optional user defined attributes, stored in `.zattrs`.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub attributes: serde_json::Map<String, serde_json::Value>,
+    /// Additional fields.
+    #[serde(flatten)]
+    pub additional_fields: AdditionalFields,
+}
+
+impl Default for GroupMetadataV2 {
+    fn default() -> Self {
+        Self::new(serde_json::Map::new())
+    }
+}
+
+impl GroupMetadataV2 {
+    /// Create Zarr V2 group metadata.
+    #[must_use]
+    pub fn new(attributes: serde_json::Map<String, serde_json::Value>) -> Self {
+        Self {
+            zarr_format: 2,
+            attributes,
+            additional_fields: AdditionalFields::default(),
+        }
+    }
+
+    /// Validates that the `zarr_format` field is `2`.
+    #[must_use]
+    pub const fn validate_format(&self) -> bool {
+        self.zarr_format == 2
+    }
+}
@@ -12,27 +12,42 @@
 //! - [x] [ZEP0001 - Zarr specification version 3](https://zarr.dev/zeps/accepted/ZEP0001.html).
 //! - [x] [ZEP0002 - Sharding codec](https://zarr.dev/zeps/accepted/ZEP0002.html).
 //! - [x] [ZEP0003 - Variable chunking](https://zarr.dev/zeps/draft/ZEP0003.html) ([draft](https://github.com/orgs/zarr-developers/discussions/52)).
+//! - [x] Reading [Zarr V2](https://zarr-specs.readthedocs.io/en/latest/v2/v2.0.html) arrays (see [`ArrayMetadataV2`](crate::array::ArrayMetadataV2)), and reading and writing Zarr V2 groups (see [`GroupMetadataV2`](crate::group::GroupMetadataV2)).
+//! - [x] Reading [N5](https://github.com/saalfeldlab/n5) datasets (see [`ArrayMetadataN5`](crate::array::ArrayMetadataN5)) with the `n5` feature.
 //! - [x] Stores and storage adapters:
 //!   - Sync:
 //!     - [`FilesystemStore`](crate::storage::store::FilesystemStore).
 //!     - [`MemoryStore`](crate::storage::store::MemoryStore).
+//!     - [`MountStore`](crate::storage::store::MountStore) and [`OverlayStore`](crate::storage::store::OverlayStore) (compositions of stores).
 //!     - [`HTTPStore`](crate::storage::store::HTTPStore).
+//!     - [`ZipStore`](crate::storage::store::ZipStore).
+//!     - [`TarStore`](crate::storage::store::TarStore).
+//!     - [`LmdbStore`](crate::storage::store::LmdbStore) (a local [LMDB](http://www.lmdb.tech/doc/) key-value store).
 //!     - [`ZipStorageAdapter`](crate::storage::storage_adapter::ZipStorageAdapter).
+//!     - [`RetryStorageAdapter`](crate::storage::storage_adapter::RetryStorageAdapter).
 //!     - [`OpendalStore`](crate::storage::store::OpendalStore) (supports all [`opendal` services](https://docs.rs/opendal/latest/opendal/services/index.html) as [`opendal::BlockingOperator`]).
+//!     - [`ObjectStore`](crate::storage::store::ObjectStore) (supports all [`object_store` stores](https://docs.rs/object_store/latest/object_store/index.html#modules), including Amazon S3, Google Cloud Storage, and Microsoft Azure).
 //!   - Async:
 //!     - [`AsyncObjectStore`](crate::storage::store::AsyncObjectStore) (supports all [`object_store` stores](https://docs.rs/object_store/latest/object_store/index.html#modules)).
 //!     - [`AsyncOpendalStore`](crate::storage::store::AsyncOpendalStore) (supports all [`opendal` services](https://docs.rs/opendal/latest/opendal/services/index.html) as [`opendal::Operator`]).
-//! - [x] Data types: [core data types](crate::array::data_type::DataType), [raw bits](crate::array::data_type::DataType::RawBits), [float16](crate::array::data_type::DataType::Float16), [bfloat16](crate::array::data_type::DataType::BFloat16) [(spec issue)](https://github.com/zarr-developers/zarr-specs/issues/130).
+//!     - [`AsyncRetryStorageAdapter`](crate::storage::storage_adapter::AsyncRetryStorageAdapter).
+//!     - [`FetchStore`](crate::storage::store::FetchStore) (HTTP for WebAssembly in the browser).
+//!     - [`OpfsStore`](crate::storage::store::OpfsStore) (the origin private file system for WebAssembly in the browser).
+//!     - [`AsyncGrpcStore`](crate::storage::store::AsyncGrpcStore) (a client of a [`GrpcStoreService`](crate::storage::store::GrpcStoreService) serving any async store over gRPC).
+//! - [x] Data types: [core data types](crate::array::data_type::DataType), [raw bits](crate::array::data_type::DataType::RawBits), [float16](crate::array::data_type::DataType::Float16), [bfloat16](crate::array::data_type::DataType::BFloat16) [(spec issue)](https://github.com/zarr-developers/zarr-specs/issues/130), [datetime64](crate::array::data_type::DataType::DateTime64), [timedelta64](crate::array::data_type::DataType::TimeDelta64), [runtime registered extension data types](crate::array::data_type::DataTypeRegistry).
 //! - [x] Chunk grids: [regular](crate::array::chunk_grid::RegularChunkGrid), [rectangular](crate::array::chunk_grid::RectangularChunkGrid) ([draft](https://github.com/orgs/zarr-developers/discussions/52)).
 //! - [x] Chunk key encoding: [default](crate::array::chunk_key_encoding::DefaultChunkKeyEncoding), [v2](crate::array::chunk_key_encoding::V2ChunkKeyEncoding).
+//!   - Experimental: [radix](crate::array::chunk_key_encoding::RadixChunkKeyEncoding).
 //! - [x] Codecs:
 //!   - Array to array: [transpose](crate::array::codec::array_to_array::transpose).
-//!     - Experimental: [bitround](crate::array::codec::array_to_array::bitround).
+//!     - Experimental: [bitround](crate::array::codec::array_to_array::bitround), [fixedscaleoffset](crate::array::codec::array_to_array::fixedscaleoffset).
 //!   - Array to bytes: [bytes](crate::array::codec::array_to_bytes::bytes), [sharding indexed](crate::array::codec::array_to_bytes::sharding).
-//!     - Experimental: [zfp](crate::array::codec::array_to_bytes::zfp), [pcodec](crate::array::codec::array_to_bytes::pcodec).
+//!     - Experimental: [zfp](crate::array::codec::array_to_bytes::zfp), [pcodec](crate::array::codec::array_to_bytes::pcodec), [packbits](crate::array::codec::array_to_bytes::packbits), [`blosc2_nd`](crate::array::codec::array_to_bytes::blosc2_nd).
 //!   - Bytes to bytes: [blosc](crate::array::codec::bytes_to_bytes::blosc), [gzip](crate::array::codec::bytes_to_bytes::gzip), [zstd](crate::array::codec::bytes_to_bytes::zstd) [(spec issue)](https://github.com/zarr-developers/zarr-specs/pull/256), [crc32c checksum](crate::array::codec::bytes_to_bytes::crc32c).
-//!     - Experimental: [bz2](crate::array::codec::bytes_to_bytes::bz2).
-//! - [x] Storage transformers: [usage log](crate::storage::storage_transformer::UsageLogStorageTransformer), [performance metrics](crate::storage::storage_transformer::PerformanceMetricsStorageTransformer).
+//!     - Experimental: [blosc2](crate::array::codec::bytes_to_bytes::blosc2), [bz2](crate::array::codec::bytes_to_bytes::bz2), [shuffle](crate::array::codec::bytes_to_bytes::shuffle), [snappy](crate::array::codec::bytes_to_bytes::snappy).
+//! - [x] [xarray](crate::xarray) conventions: the `_ARRAY_DIMENSIONS` attribute and coordinate arrays.
+//! - [x] [OME-Zarr](crate::ome_zarr) conventions: typed `multiscales` metadata and multiscale arrays.
+//! - [x] Storage transformers: [usage log](crate::storage::storage_transformer::UsageLogStorageTransformer), [performance metrics](crate::storage::storage_transformer::PerformanceMetricsStorageTransformer), [quota](crate::storage::storage_transformer::QuotaStorageTransformer), [disk cache](crate::storage::storage_transformer::DiskCacheStorageTransformer).
 //!
 //! ## Crate Features
 //! The following crate features are enabled by default:
@@ -45,10 +60,25 @@
 //!    - The async API is runtime-agnostic. This has some limitations that are detailed in the [`Array`](crate::array::Array) docs.
 //!    - The async API is not as performant as the sync API.
 //!  - Codecs
-//!    - `bitround`, `zfp`, `bz2`, `pcodec`.
+//!    - `bitround`, `fixedscaleoffset`, `zfp`, `blosc2`, `bz2`, `pcodec`, `packbits`, `shuffle`, `snappy`.
 //!  - Stores
 //!    - `object_store`: support for [`object_store`] stores.
 //!    - `opendal`: support for [`opendal`] stores.
+//!    - `lmdb`: a sync [LMDB](http://www.lmdb.tech/doc/) key-value store.
+//!    - `tar`: a read only sync store of uncompressed tar files.
+//!    - `fetch`: an async HTTP store for WebAssembly in the browser using the [Fetch API](https://developer.mozilla.org/en-US/docs/Web/API/Fetch_API).
+//!    - `wasm`: the `fetch` store and an async store for WebAssembly in the browser backed by the [Origin Private File System](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system).
+//!    - `grpc`: an async gRPC store and a [`tonic`] service for serving stores over gRPC.
+//!  - `n5`: read N5 datasets with [`Array`](crate::array::Array).
+//!  - `chrono`: [`chrono`] conversions for the elements of [`datetime64`](crate::array::data_type::DataType::DateTime64) and [`timedelta64`](crate::array::data_type::DataType::TimeDelta64) arrays.
+//!  - `arrow`: [Apache Arrow](https://arrow.apache.org/) utility functions for [`Array`](crate::array::Array) with [`arrow_array`].
+//!  - `tracing`: instrument storage, codecs, and [`Array`](crate::array::Array) retrieve/store methods with [`tracing`] spans and events.
+//!
+//! ## WebAssembly
+//! Support for the `wasm32-unknown-unknown` target is experimental.
+//! Default features must be disabled (the `blosc` and `zstd` codecs depend on C libraries), and only `--no-default-features --features wasm,gzip,sharding,transpose` is checked in CI.
+//! Without WebAssembly threads, [`rayon`] executes parallel operations on the current thread and the default [codec concurrent target](crate::config::Config#codec-concurrent-target) is 1.
+//! Operations that block the current thread (e.g. the sync [`RetryStorageAdapter`](crate::storage::storage_adapter::RetryStorageAdapter) or waiting on a contended store lock) are not supported in the browser.
 //!
 //! ## Examples
 //! Examples can be run with `cargo run --example <EXAMPLE_NAME>`.
@@ -63,6 +93,8 @@
 //! [`sharded_array_write_read`](https://github.com/LDeakin/zarrs/blob/main/examples/sharded_array_write_read.rs),
 //! [`rectangular_array_write_read`](https://github.com/LDeakin/zarrs/blob/main/examples/rectangular_array_write_read.rs),
 //! [`zip_array_write_read`](https://github.com/LDeakin/zarrs/blob/main/examples/zip_array_write_read.rs),
+//! [`custom_chunk_key_encoding`](https://github.com/LDeakin/zarrs/blob/main/examples/custom_chunk_key_encoding.rs),
+//! [`custom_chunk_grid`](https://github.com/LDeakin/zarrs/blob/main/examples/custom_chunk_grid.rs),
 //! [`http_array_read`](https://github.com/LDeakin/zarrs/blob/main/examples/http_array_read.rs).
 //!
 //! #### Async API
@@ -111,9 +143,11 @@ pub mod config;
 pub mod group;
 pub mod metadata;
 pub mod node;
+pub mod ome_zarr;
 pub mod plugin;
 pub mod storage;
 pub mod version;
+pub mod xarray;
 
 /// Re-export [`bytemuck`].
 pub use bytemuck;
@@ -134,6 +168,10 @@ pub use serde_json;
 /// Re-export [`ndarray`].
 pub use ndarray;
 
+#[cfg(feature = "arrow")]
+/// Re-export [`arrow_array`].
+pub use arrow_array;
+
 #[cfg(feature = "object_store")]
 /// Re-export [`object_store`].
 pub use object_store;
@@ -142,6 +180,14 @@ pub use object_store;
 /// Re-export [`opendal`].
 pub use opendal;
 
+#[cfg(feature = "grpc")]
+/// Re-export [`tonic`].
+pub use tonic;
+
+#[cfg(feature = "tracing")]
+/// Re-export [`tracing`].
+pub use tracing;
+
 /// Get a mutable slice of the spare capacity in a vector.
 unsafe fn vec_spare_capacity_to_mut_slice<T>(vec: &mut Vec<T>) -> &mut [T] {
     let spare_capacity = vec.spare_capacity_mut();
@@ -152,3 +198,16 @@ unsafe fn vec_spare_capacity_to_mut_slice<T>(vec: &mut Vec<T>) -> &mut [T] {
         )
     }
 }
+
+/// Return a random number from the randomly seeded hasher of the standard library.
+fn random_u64() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+    std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish()
+}
+
+/// Return a random 128-bit identifier as a hexadecimal string.
+fn random_id() -> String {
+    format!("{:016x}{:016x}", random_u64(), random_u64())
+}
@@ -214,6 +214,53 @@ impl Metadata {
     }
 }
 
+/// Zarr V2 codec metadata with an `id` and flattened configuration.
+///
+/// For example:
+/// ```json
+/// {
+///     "id": "blosc",
+///     "cname": "lz4",
+///     "clevel": 5,
+///     "shuffle": 1
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct MetadataV2 {
+    id: String,
+    #[serde(flatten)]
+    configuration: MetadataConfiguration,
+}
+
+impl MetadataV2 {
+    /// Create Zarr V2 codec metadata from `id` and `configuration`.
+    #[must_use]
+    pub fn new(id: &str, configuration: MetadataConfiguration) -> Self {
+        Self {
+            id: id.into(),
+            configuration,
+        }
+    }
+
+    /// Returns the codec id.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the codec configuration.
+    #[must_use]
+    pub const fn configuration(&self) -> &MetadataConfiguration {
+        &self.configuration
+    }
+}
+
+impl core::fmt::Display for MetadataV2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).unwrap_or_default())
+    }
+}
+
 /// An invalid configuration error.
 #[derive(Debug, Error, From)]
 #[error("{name} is unsupported, configuration: {configuration:?}")]
@@ -4,7 +4,10 @@
 //!
 //! A [`Node`] has an associated [`NodePath`], [`NodeMetadata`], and children.
 //!
-//! The [`Node::hierarchy_tree`] function can be used to create a string representation of a the hierarchy below a node.
+//! Use [`Node::new`] to open the hierarchy below a path in a store, for example the root node `/`.
+//!
+//! The [`Node::hierarchy_tree`] and [`Node::tree`] functions can be used to create a string representation of a the hierarchy below a node.
+//! The descendants of a node can be iterated over with [`Node::descendants`], [`Node::arrays`], and [`Node::groups`].
 
 mod node_metadata;
 mod node_name;
@@ -17,15 +20,16 @@ use thiserror::Error;
 
 use crate::{
     array::ArrayMetadata,
-    group::GroupMetadataV3,
     storage::{
-        get_child_nodes, meta_key, ListableStorageTraits, ReadableStorageTraits, StorageError,
+        get_child_nodes, get_node_metadata, ListableStorageTraits, ReadableStorageTraits,
+        StorageError,
     },
 };
 
 #[cfg(feature = "async")]
 use crate::storage::{
-    async_get_child_nodes, AsyncListableStorageTraits, AsyncReadableStorageTraits,
+    async_get_child_nodes, async_get_node_metadata, AsyncListableStorageTraits,
+    AsyncReadableStorageTraits,
 };
 
 /// A Zarr hierarchy node.
@@ -57,6 +61,8 @@ pub enum NodeCreateError {
 impl Node {
     /// Create a new node at `path` and read metadata and children from `storage`.
     ///
+    /// Zarr V2 metadata is read if `zarr.json` does not exist, see [`get_node_metadata`].
+    ///
     /// # Errors
     ///
     /// Returns [`NodeCreateError`] if metadata is invalid or there is a failure to list child nodes.
@@ -65,14 +71,7 @@ impl Node {
         path: &str,
     ) -> Result<Self, NodeCreateError> {
         let path: NodePath = path.try_into()?;
-        let key = meta_key(&path);
-        let metadata = storage.get(&key)?;
-        let metadata: NodeMetadata = match metadata {
-            Some(metadata) => serde_json::from_slice(metadata.as_slice()).map_err(|e| {
-                NodeCreateError::StorageError(StorageError::InvalidMetadata(key, e.to_string()))
-            })?,
-            None => NodeMetadata::Group(GroupMetadataV3::default().into()),
-        };
+        let metadata = get_node_metadata(storage, &path)?;
         let children = match metadata {
             NodeMetadata::Array(_) => Vec::default(),
             NodeMetadata::Group(_) => get_child_nodes(storage, &path)?,
@@ -98,14 +97,7 @@ impl Node {
         path: &str,
     ) -> Result<Self, NodeCreateError> {
         let path: NodePath = path.try_into()?;
-        let key = meta_key(&path);
-        let metadata = storage.get(&key).await?;
-        let metadata: NodeMetadata = match metadata {
-            Some(metadata) => serde_json::from_slice(metadata.as_slice()).map_err(|e| {
-                NodeCreateError::StorageError(StorageError::InvalidMetadata(key, e.to_string()))
-            })?,
-            None => NodeMetadata::Group(GroupMetadataV3::default().into()),
-        };
+        let metadata = async_get_node_metadata(storage, &path).await?;
         let children = match metadata {
             NodeMetadata::Array(_) => Vec::default(),
             NodeMetadata::Group(_) => async_get_child_nodes(storage, &path).await?,
@@ -159,6 +151,55 @@ impl Node {
         &self.children
     }
 
+    /// Indicates if the node is an array.
+    #[must_use]
+    pub fn is_array(&self) -> bool {
+        matches!(self.metadata, NodeMetadata::Array(_))
+    }
+
+    /// Indicates if the node is a group.
+    #[must_use]
+    pub fn is_group(&self) -> bool {
+        matches!(self.metadata, NodeMetadata::Group(_))
+    }
+
+    /// Return an iterator over the descendants of the node in depth-first order.
+    ///
+    /// The node itself is not included.
+    #[must_use]
+    pub fn descendants(&self) -> NodeDescendants<'_> {
+        NodeDescendants {
+            stack: self.children.iter().rev().collect(),
+        }
+    }
+
+    /// Return an iterator over the descendant array nodes of the node in depth-first order.
+    pub fn arrays(&self) -> impl Iterator<Item = &Node> {
+        self.descendants().filter(|node| node.is_array())
+    }
+
+    /// Return an iterator over the descendant group nodes of the node in depth-first order.
+    pub fn groups(&self) -> impl Iterator<Item = &Node> {
+        self.descendants().filter(|node| node.is_group())
+    }
+
+    /// Return the label of a node with `name` in a tree representation.
+    fn tree_label(name: &str, metadata: &NodeMetadata) -> String {
+        match metadata {
+            NodeMetadata::Array(ArrayMetadata::V3(array_metadata)) => format!(
+                "{} {:?} {}",
+                name, array_metadata.shape, array_metadata.data_type
+            ),
+            NodeMetadata::Array(ArrayMetadata::V2(array_metadata)) => {
+                format!(
+                    "{} {:?} {}",
+                    name, array_metadata.shape, array_metadata.dtype
+                )
+            }
+            NodeMetadata::Group(_) => name.to_string(),
+        }
+    }
+
     /// Return a tree representation of a hierarchy as a string.
     ///
     /// Arrays are annotated with their shape and data type.
@@ -171,44 +212,77 @@ impl Node {
     /// ```
     #[must_use]
     pub fn hierarchy_tree(&self) -> String {
-        fn print_metadata(name: &str, string: &mut String, metadata: &NodeMetadata) {
-            match metadata {
-                NodeMetadata::Array(array_metadata) => {
-                    let ArrayMetadata::V3(array_metadata) = array_metadata;
-                    let s = format!(
-                        "{} {:?} {}",
-                        name, array_metadata.shape, array_metadata.data_type
-                    );
-                    string.push_str(&s);
-                }
-                NodeMetadata::Group(_) => {
-                    string.push_str(name);
-                }
-            };
-            string.push('\n');
-        }
-
         fn update_tree(string: &mut String, children: &[Node], depth: usize) {
             for child in children {
                 let name = child.name();
                 string.push_str(&" ".repeat(depth * 2));
-                print_metadata(name.as_str(), string, &child.metadata);
+                string.push_str(&Node::tree_label(name.as_str(), &child.metadata));
+                string.push('\n');
                 update_tree(string, &child.children, depth + 1);
             }
         }
 
-        let mut string = String::default();
-        print_metadata("/", &mut string, &self.metadata);
+        let mut string = Self::tree_label("/", &self.metadata);
+        string.push('\n');
         update_tree(&mut string, &self.children, 1);
         string
     }
+
+    /// Return a tree representation of a hierarchy as a string with box-drawing characters, similar to `zarr-python`.
+    ///
+    /// The root of the tree is labelled with the path of the node.
+    /// Arrays are annotated with their shape and data type.
+    /// For example:
+    /// ```text
+    /// /
+    /// ├── a
+    /// │   ├── baz [10000, 1000] float64
+    /// │   └── foo [10000, 1000] float64
+    /// └── b
+    /// ```
+    #[must_use]
+    pub fn tree(&self) -> String {
+        fn update_tree(string: &mut String, children: &[Node], prefix: &str) {
+            for (i, child) in children.iter().enumerate() {
+                let last = i + 1 == children.len();
+                string.push_str(prefix);
+                string.push_str(if last { "└── " } else { "├── " });
+                string.push_str(&Node::tree_label(child.name().as_str(), &child.metadata));
+                string.push('\n');
+                let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+                update_tree(string, &child.children, &prefix);
+            }
+        }
+
+        let mut string = Self::tree_label(self.path.as_str(), &self.metadata);
+        string.push('\n');
+        update_tree(&mut string, &self.children, "");
+        string
+    }
+}
+
+/// A depth-first iterator over the descendants of a [`Node`].
+///
+/// See [`Node::descendants`].
+pub struct NodeDescendants<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for NodeDescendants<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children.iter().rev());
+        Some(node)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
         array::{ArrayBuilder, FillValue},
-        group::GroupMetadata,
+        group::{GroupMetadata, GroupMetadataV3},
         storage::{store::MemoryStore, StoreKey, WritableStorageTraits},
     };
 
@@ -358,4 +432,110 @@ mod tests {
         );
         assert!(node.is_root());
     }
+
+    #[test]
+    fn node_tree() {
+        let store = std::sync::Arc::new(MemoryStore::new());
+        let array_builder = ArrayBuilder::new(
+            vec![10, 10],
+            crate::array::DataType::Float32,
+            vec![5, 5].try_into().unwrap(),
+            FillValue::from(0.0f32),
+        );
+        for path in ["/a/baz", "/a/foo", "/b/c/bar"] {
+            array_builder
+                .build(store.clone(), path)
+                .unwrap()
+                .store_metadata()
+                .unwrap();
+        }
+        let node = Node::new(&*store, "/").unwrap();
+        assert_eq!(
+            node.tree(),
+            "/
+├── a
+│   ├── baz [10, 10] float32
+│   └── foo [10, 10] float32
+└── b
+    └── c
+        └── bar [10, 10] float32
+"
+        );
+        assert_eq!(
+            node.hierarchy_tree(),
+            "/
+  a
+    baz [10, 10] float32
+    foo [10, 10] float32
+  b
+    c
+      bar [10, 10] float32
+"
+        );
+
+        let descendants: Vec<&str> = node
+            .descendants()
+            .map(|node| node.path().as_str())
+            .collect();
+        assert_eq!(
+            descendants,
+            ["/a", "/a/baz", "/a/foo", "/b", "/b/c", "/b/c/bar"]
+        );
+        let arrays: Vec<&str> = node.arrays().map(|node| node.path().as_str()).collect();
+        assert_eq!(arrays, ["/a/baz", "/a/foo", "/b/c/bar"]);
+        assert_eq!(node.groups().count(), 3);
+    }
+
+    #[test]
+    fn node_v2_v3_hierarchy() {
+        let store = std::sync::Arc::new(MemoryStore::new());
+        store
+            .set(
+                &StoreKey::new("v2/.zgroup").unwrap(),
+                br#"{"zarr_format": 2}"#,
+            )
+            .unwrap();
+        store
+            .set(&StoreKey::new("v2/.zattrs").unwrap(), br#"{"spam": "ham"}"#)
+            .unwrap();
+        store
+            .set(
+                &StoreKey::new("v2/array/.zarray").unwrap(),
+                br#"{
+                    "zarr_format": 2,
+                    "shape": [4, 4],
+                    "chunks": [2, 2],
+                    "dtype": "<u2",
+                    "compressor": null,
+                    "fill_value": 0,
+                    "order": "C",
+                    "filters": null
+                }"#,
+            )
+            .unwrap();
+        ArrayBuilder::new(
+            vec![10],
+            crate::array::DataType::Float32,
+            vec![5].try_into().unwrap(),
+            FillValue::from(0.0f32),
+        )
+        .build(store.clone(), "/v2/v3_array")
+        .unwrap()
+        .store_metadata()
+        .unwrap();
+
+        let node = Node::new(&*store, "/").unwrap();
+        assert_eq!(
+            node.tree(),
+            "/
+└── v2
+    ├── array [4, 4] <u2
+    └── v3_array [10] float32
+"
+        );
+        let NodeMetadata::Group(GroupMetadata::V2(metadata)) = node.children()[0].metadata() else {
+            panic!("expected Zarr V2 group metadata")
+        };
+        assert_eq!(metadata.attributes["spam"], "ham");
+    }
 }
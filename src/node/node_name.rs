@@ -13,6 +13,13 @@ pub struct NodeName(String);
 #[error("invalid node name {0}")]
 pub struct NodeNameError(String);
 
+impl NodeNameError {
+    /// Create a new node name error for `name`.
+    pub(crate) fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
 impl NodeName {
     /// Create a new Zarr node name from `name`.
     ///
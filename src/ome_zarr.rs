@@ -0,0 +1,806 @@
+//! [OME-Zarr](https://ngff.openmicroscopy.org/latest/) (NGFF) multiscale image conventions for Zarr hierarchies.
+//!
+//! An OME-Zarr multiscale image is a group with a `multiscales` attribute describing the axes of the image and the arrays (datasets) holding each resolution level.
+//! Each dataset has coordinate transformations mapping its array indices to physical coordinates, typically a `scale` and an optional `translation`.
+//!
+//! [`Multiscale`] and its components are typed representations of the `multiscales` metadata, which can be read from and written to a group with [`multiscales`] and [`set_multiscales`].
+//! Both the OME-Zarr 0.4 layout (a top-level `multiscales` attribute) and the OME-Zarr 0.5 layout (`multiscales` nested in an `ome` attribute) are supported for reading.
+//!
+//! A [`MultiscaleArray`] resolves a resolution level of a multiscale image to its [`Array`].
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use zarrs::array::{ArrayBuilder, DataType, FillValue};
+//! # use zarrs::group::GroupBuilder;
+//! # use zarrs::storage::store::MemoryStore;
+//! use zarrs::ome_zarr::{Axis, AxisType, CoordinateTransformation, Dataset, Multiscale, MultiscaleArray};
+//!
+//! let store = Arc::new(MemoryStore::new());
+//! let mut group = GroupBuilder::new().build(store.clone(), "/image")?;
+//! let axes = vec![
+//!     Axis::new("y", Some(AxisType::Space), Some("micrometer")),
+//!     Axis::new("x", Some(AxisType::Space), Some("micrometer")),
+//! ];
+//! let datasets = vec![
+//!     Dataset::new("0", vec![CoordinateTransformation::scale(vec![1.0, 1.0])]),
+//!     Dataset::new("1", vec![CoordinateTransformation::scale(vec![2.0, 2.0])]),
+//! ];
+//! zarrs::ome_zarr::set_multiscales(&mut group, &[Multiscale::new(axes, datasets)])?;
+//! group.store_metadata()?;
+//! for (path, size) in [("/image/0", 8), ("/image/1", 4)] {
+//!     ArrayBuilder::new(
+//!         vec![size, size],
+//!         DataType::UInt8,
+//!         vec![4, 4].try_into()?,
+//!         FillValue::from(0u8),
+//!     )
+//!     .build(store.clone(), path)?
+//!     .store_metadata()?;
+//! }
+//!
+//! let multiscale_array = MultiscaleArray::new(store, "/image")?;
+//! assert_eq!(multiscale_array.num_levels(), 2);
+//! assert_eq!(multiscale_array.level(1)?.shape(), &[4, 4]);
+//! assert_eq!(multiscale_array.scale(1)?, vec![2.0, 2.0]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    array::{Array, ArrayCreateError},
+    group::{Group, GroupCreateError},
+    node::{NodePath, NodePathError},
+    storage::ReadableStorageTraits,
+};
+
+#[cfg(feature = "async")]
+use crate::storage::AsyncReadableStorageTraits;
+
+/// The attribute holding the multiscales metadata of a group in OME-Zarr 0.4.
+pub const MULTISCALES: &str = "multiscales";
+
+/// The attribute holding the OME-Zarr metadata of a group in OME-Zarr 0.5.
+pub const OME: &str = "ome";
+
+/// An OME-Zarr error.
+#[derive(Debug, Error)]
+pub enum OmeZarrError {
+    /// The multiscales metadata could not be deserialised or serialised.
+    #[error("the multiscales metadata is invalid: {_0}")]
+    InvalidMultiscales(#[from] serde_json::Error),
+    /// The group does not have multiscales metadata.
+    #[error("the group does not have multiscales metadata")]
+    MissingMultiscales,
+    /// The axes are invalid.
+    #[error("the multiscale axes are invalid: {_0}")]
+    InvalidAxes(String),
+    /// The datasets are invalid.
+    #[error("the multiscale datasets are invalid: {_0}")]
+    InvalidDatasets(String),
+    /// The coordinate transformations are invalid.
+    #[error("the coordinate transformations are invalid: {_0}")]
+    InvalidCoordinateTransformations(String),
+    /// The resolution level is out of bounds.
+    #[error("resolution level {_0} is out of bounds for a multiscale with {_1} levels")]
+    InvalidLevel(usize, usize),
+    /// The dimensionality of a resolution level array does not match the number of axes.
+    #[error("the array dimensionality {_0} does not match the number of axes {_1}")]
+    IncompatibleDimensionality(usize, usize),
+    /// An invalid node path.
+    #[error(transparent)]
+    NodePathError(#[from] NodePathError),
+    /// An error opening the multiscale group.
+    #[error(transparent)]
+    GroupCreateError(#[from] GroupCreateError),
+    /// An error opening a resolution level array.
+    #[error(transparent)]
+    ArrayCreateError(#[from] ArrayCreateError),
+}
+
+/// The type of an [`Axis`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum AxisType {
+    /// A spatial axis.
+    Space,
+    /// A time axis.
+    Time,
+    /// A channel axis.
+    Channel,
+    /// A custom axis type.
+    Custom(String),
+}
+
+impl From<String> for AxisType {
+    fn from(axis_type: String) -> Self {
+        match axis_type.as_str() {
+            "space" => Self::Space,
+            "time" => Self::Time,
+            "channel" => Self::Channel,
+            _ => Self::Custom(axis_type),
+        }
+    }
+}
+
+impl From<AxisType> for String {
+    fn from(axis_type: AxisType) -> Self {
+        match axis_type {
+            AxisType::Space => "space".to_string(),
+            AxisType::Time => "time".to_string(),
+            AxisType::Channel => "channel".to_string(),
+            AxisType::Custom(axis_type) => axis_type,
+        }
+    }
+}
+
+/// An axis of a multiscale image.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Axis {
+    /// The name of the axis.
+    pub name: String,
+    /// The type of the axis.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub axis_type: Option<AxisType>,
+    /// The unit of the axis, e.g. `micrometer` or `second`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+}
+
+impl Axis {
+    /// Create a new axis.
+    #[must_use]
+    pub fn new(name: &str, axis_type: Option<AxisType>, unit: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            axis_type,
+            unit: unit.map(str::to_string),
+        }
+    }
+}
+
+/// A coordinate transformation.
+///
+/// Coordinate transformations referencing a binary `path` are not supported.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CoordinateTransformation {
+    /// The identity transformation.
+    Identity,
+    /// A scale transformation, with a scale per axis.
+    Scale {
+        /// The scale of each axis.
+        scale: Vec<f64>,
+    },
+    /// A translation transformation, with a translation per axis.
+    Translation {
+        /// The translation of each axis.
+        translation: Vec<f64>,
+    },
+}
+
+impl CoordinateTransformation {
+    /// Create a scale transformation.
+    #[must_use]
+    pub fn scale(scale: Vec<f64>) -> Self {
+        Self::Scale { scale }
+    }
+
+    /// Create a translation transformation.
+    #[must_use]
+    pub fn translation(translation: Vec<f64>) -> Self {
+        Self::Translation { translation }
+    }
+
+    /// Return the number of axes the transformation applies to, or [`None`] for the identity transformation.
+    #[must_use]
+    pub fn dimensionality(&self) -> Option<usize> {
+        match self {
+            Self::Identity => None,
+            Self::Scale { scale } => Some(scale.len()),
+            Self::Translation { translation } => Some(translation.len()),
+        }
+    }
+}
+
+/// Return the scale of `transformations`, if any.
+fn transformations_scale(transformations: &[CoordinateTransformation]) -> Option<&[f64]> {
+    transformations
+        .iter()
+        .find_map(|transformation| match transformation {
+            CoordinateTransformation::Scale { scale } => Some(scale.as_slice()),
+            _ => None,
+        })
+}
+
+/// Return the translation of `transformations`, if any.
+fn transformations_translation(transformations: &[CoordinateTransformation]) -> Option<&[f64]> {
+    transformations
+        .iter()
+        .find_map(|transformation| match transformation {
+            CoordinateTransformation::Translation { translation } => Some(translation.as_slice()),
+            _ => None,
+        })
+}
+
+/// Validate that `transformations` are a scale optionally followed by a translation, or a single identity transformation if `allow_identity`.
+fn validate_transformations(
+    transformations: &[CoordinateTransformation],
+    dimensionality: usize,
+    allow_identity: bool,
+) -> Result<(), OmeZarrError> {
+    let err = |message: &str| {
+        Err(OmeZarrError::InvalidCoordinateTransformations(
+            message.to_string(),
+        ))
+    };
+    match transformations {
+        [CoordinateTransformation::Identity] if allow_identity => return Ok(()),
+        [CoordinateTransformation::Scale { .. }]
+        | [CoordinateTransformation::Scale { .. }, CoordinateTransformation::Translation { .. }] => {
+        }
+        _ => return err("expected a scale optionally followed by a translation"),
+    }
+    for transformation in transformations {
+        if let Some(transformation_dimensionality) = transformation.dimensionality() {
+            if transformation_dimensionality != dimensionality {
+                return Err(OmeZarrError::InvalidCoordinateTransformations(format!(
+                    "transformation {transformation:?} does not match the number of axes {dimensionality}"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A dataset (resolution level) of a multiscale image.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Dataset {
+    /// The path of the dataset array relative to the multiscale group.
+    pub path: String,
+    /// The coordinate transformations of the dataset, a scale optionally followed by a translation.
+    pub coordinate_transformations: Vec<CoordinateTransformation>,
+}
+
+impl Dataset {
+    /// Create a new dataset.
+    #[must_use]
+    pub fn new(path: &str, coordinate_transformations: Vec<CoordinateTransformation>) -> Self {
+        Self {
+            path: path.to_string(),
+            coordinate_transformations,
+        }
+    }
+
+    /// Return the scale of the dataset, if any.
+    #[must_use]
+    pub fn scale(&self) -> Option<&[f64]> {
+        transformations_scale(&self.coordinate_transformations)
+    }
+
+    /// Return the translation of the dataset, if any.
+    #[must_use]
+    pub fn translation(&self) -> Option<&[f64]> {
+        transformations_translation(&self.coordinate_transformations)
+    }
+}
+
+/// A multiscale image.
+///
+/// The datasets are ordered from the highest to the lowest resolution.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Multiscale {
+    /// The OME-Zarr version (absent in OME-Zarr 0.5, where the version is in the `ome` attribute).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The name of the multiscale image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The axes of the multiscale image.
+    pub axes: Vec<Axis>,
+    /// The datasets (resolution levels) of the multiscale image.
+    pub datasets: Vec<Dataset>,
+    /// Coordinate transformations applied to every dataset after its own coordinate transformations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinate_transformations: Option<Vec<CoordinateTransformation>>,
+    /// The type of downscaling method, e.g. `gaussian`.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub downscaling_type: Option<String>,
+    /// Unstructured information about the downscaling method.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl Multiscale {
+    /// Create a new OME-Zarr 0.4 multiscale image from `axes` and `datasets`.
+    #[must_use]
+    pub fn new(axes: Vec<Axis>, datasets: Vec<Dataset>) -> Self {
+        Self {
+            version: Some("0.4".to_string()),
+            name: None,
+            axes,
+            datasets,
+            coordinate_transformations: None,
+            downscaling_type: None,
+            metadata: None,
+        }
+    }
+
+    /// Validate the multiscale image.
+    ///
+    /// The axes must have unique names and include two or three space axes, and at most one time and one channel axis.
+    /// A time axis must be first and space axes must be last.
+    /// There must be at least one dataset, and the coordinate transformations must match the number of axes.
+    ///
+    /// # Errors
+    /// Returns an [`OmeZarrError`] if the multiscale image is invalid.
+    pub fn validate(&self) -> Result<(), OmeZarrError> {
+        self.validate_axes()?;
+        if self.datasets.is_empty() {
+            return Err(OmeZarrError::InvalidDatasets(
+                "there must be at least one dataset".to_string(),
+            ));
+        }
+        for dataset in &self.datasets {
+            if dataset.path.is_empty() {
+                return Err(OmeZarrError::InvalidDatasets(
+                    "dataset paths must not be empty".to_string(),
+                ));
+            }
+            validate_transformations(&dataset.coordinate_transformations, self.axes.len(), false)?;
+        }
+        if let Some(transformations) = &self.coordinate_transformations {
+            validate_transformations(transformations, self.axes.len(), true)?;
+        }
+        Ok(())
+    }
+
+    fn validate_axes(&self) -> Result<(), OmeZarrError> {
+        let err = |message: String| Err(OmeZarrError::InvalidAxes(message));
+        if !(2..=5).contains(&self.axes.len()) {
+            return err(format!(
+                "there must be between 2 and 5 axes, found {}",
+                self.axes.len()
+            ));
+        }
+        for (i, axis) in self.axes.iter().enumerate() {
+            if self.axes[..i].iter().any(|other| other.name == axis.name) {
+                return err(format!("axis name {} is not unique", axis.name));
+            }
+        }
+        let count = |axis_type: &AxisType| {
+            self.axes
+                .iter()
+                .filter(|axis| axis.axis_type.as_ref() == Some(axis_type))
+                .count()
+        };
+        if !(2..=3).contains(&count(&AxisType::Space)) {
+            return err("there must be 2 or 3 space axes".to_string());
+        }
+        if count(&AxisType::Time) > 1 || count(&AxisType::Channel) > 1 {
+            return err("there must be at most one time and one channel axis".to_string());
+        }
+        // Time axes come first, then channel and custom axes, then space axes
+        let rank = |axis: &Axis| match axis.axis_type {
+            Some(AxisType::Time) => 0,
+            Some(AxisType::Space) => 2,
+            _ => 1,
+        };
+        if !self
+            .axes
+            .windows(2)
+            .all(|axes| rank(&axes[0]) <= rank(&axes[1]))
+        {
+            return err("axes must be ordered time, channel or custom, then space".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Return the multiscale images of `group`.
+///
+/// The `multiscales` metadata is read from the `ome` attribute (OME-Zarr 0.5) if present, otherwise from the `multiscales` attribute (OME-Zarr 0.4).
+/// Returns [`None`] if the group does not have multiscales metadata.
+///
+/// # Errors
+/// Returns an [`OmeZarrError`] if the multiscales metadata cannot be deserialised or is invalid.
+pub fn multiscales<TStorage: ?Sized>(
+    group: &Group<TStorage>,
+) -> Result<Option<Vec<Multiscale>>, OmeZarrError> {
+    let value = group
+        .attributes()
+        .get(OME)
+        .and_then(|ome| ome.get(MULTISCALES))
+        .or_else(|| group.attributes().get(MULTISCALES));
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let multiscales = Vec::<Multiscale>::deserialize(value)?;
+    for multiscale in &multiscales {
+        multiscale.validate()?;
+    }
+    Ok(Some(multiscales))
+}
+
+/// Set the multiscale images of `group`.
+///
+/// The `multiscales` metadata is written to the `ome` attribute if the group already has one (OME-Zarr 0.5), otherwise to the `multiscales` attribute (OME-Zarr 0.4).
+/// The group metadata is not stored.
+///
+/// # Errors
+/// Returns an [`OmeZarrError`] if a multiscale image is invalid.
+pub fn set_multiscales<TStorage: ?Sized>(
+    group: &mut Group<TStorage>,
+    multiscales: &[Multiscale],
+) -> Result<(), OmeZarrError> {
+    for multiscale in multiscales {
+        multiscale.validate()?;
+    }
+    let value = serde_json::to_value(multiscales)?;
+    match group.attributes_mut().get_mut(OME) {
+        Some(serde_json::Value::Object(ome)) => {
+            ome.insert(MULTISCALES.to_string(), value);
+        }
+        _ => {
+            group
+                .attributes_mut()
+                .insert(MULTISCALES.to_string(), value);
+        }
+    }
+    Ok(())
+}
+
+/// A multiscale image, resolving its resolution levels to arrays.
+#[derive(Clone, Debug)]
+pub struct MultiscaleArray<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    path: NodePath,
+    multiscale: Multiscale,
+}
+
+impl<TStorage: ?Sized> MultiscaleArray<TStorage> {
+    /// Create a multiscale array for the group in `storage` at `path` with `multiscale` metadata.
+    ///
+    /// # Errors
+    /// Returns an [`OmeZarrError`] if `path` or `multiscale` is invalid.
+    pub fn new_with_multiscale(
+        storage: Arc<TStorage>,
+        path: &str,
+        multiscale: Multiscale,
+    ) -> Result<Self, OmeZarrError> {
+        let path = NodePath::new(path)?;
+        multiscale.validate()?;
+        Ok(Self {
+            storage,
+            path,
+            multiscale,
+        })
+    }
+
+    fn new_with_group(
+        storage: Arc<TStorage>,
+        group: &Group<TStorage>,
+    ) -> Result<Self, OmeZarrError> {
+        let multiscale = multiscales(group)?
+            .and_then(|multiscales| multiscales.into_iter().next())
+            .ok_or(OmeZarrError::MissingMultiscales)?;
+        Self::new_with_multiscale(storage, group.path().as_str(), multiscale)
+    }
+
+    /// Get the path of the multiscale group.
+    #[must_use]
+    pub const fn path(&self) -> &NodePath {
+        &self.path
+    }
+
+    /// Get the multiscale metadata.
+    #[must_use]
+    pub const fn multiscale(&self) -> &Multiscale {
+        &self.multiscale
+    }
+
+    /// Get the axes.
+    #[must_use]
+    pub fn axes(&self) -> &[Axis] {
+        &self.multiscale.axes
+    }
+
+    /// Return the number of resolution levels.
+    #[must_use]
+    pub fn num_levels(&self) -> usize {
+        self.multiscale.datasets.len()
+    }
+
+    fn dataset(&self, level: usize) -> Result<&Dataset, OmeZarrError> {
+        self.multiscale
+            .datasets
+            .get(level)
+            .ok_or_else(|| OmeZarrError::InvalidLevel(level, self.num_levels()))
+    }
+
+    /// Return the path of the array of resolution `level`.
+    ///
+    /// # Errors
+    /// Returns an [`OmeZarrError`] if `level` is out of bounds or the dataset path is invalid.
+    pub fn level_path(&self, level: usize) -> Result<NodePath, OmeZarrError> {
+        let dataset_path = self.dataset(level)?.path.trim_matches('/');
+        let path = if self.path.as_str() == "/" {
+            format!("/{dataset_path}")
+        } else {
+            format!("{}/{dataset_path}", self.path.as_str())
+        };
+        Ok(NodePath::new(&path)?)
+    }
+
+    /// Return the scale of resolution `level`, combining the dataset and multiscale coordinate transformations.
+    ///
+    /// # Errors
+    /// Returns [`OmeZarrError::InvalidLevel`] if `level` is out of bounds.
+    pub fn scale(&self, level: usize) -> Result<Vec<f64>, OmeZarrError> {
+        let dataset = self.dataset(level)?;
+        let mut scale = dataset
+            .scale()
+            .map_or_else(|| vec![1.0; self.axes().len()], <[f64]>::to_vec);
+        if let Some(multiscale_scale) = self.multiscale_scale() {
+            std::iter::zip(&mut scale, multiscale_scale).for_each(|(scale, s)| *scale *= s);
+        }
+        Ok(scale)
+    }
+
+    /// Return the translation of resolution `level`, combining the dataset and multiscale coordinate transformations.
+    ///
+    /// # Errors
+    /// Returns [`OmeZarrError::InvalidLevel`] if `level` is out of bounds.
+    pub fn translation(&self, level: usize) -> Result<Vec<f64>, OmeZarrError> {
+        let dataset = self.dataset(level)?;
+        let mut translation = dataset
+            .translation()
+            .map_or_else(|| vec![0.0; self.axes().len()], <[f64]>::to_vec);
+        if let Some(multiscale_scale) = self.multiscale_scale() {
+            std::iter::zip(&mut translation, multiscale_scale).for_each(|(t, s)| *t *= s);
+        }
+        if let Some(multiscale_translation) = self
+            .multiscale
+            .coordinate_transformations
+            .as_deref()
+            .and_then(transformations_translation)
+        {
+            std::iter::zip(&mut translation, multiscale_translation).for_each(|(t0, t1)| *t0 += t1);
+        }
+        Ok(translation)
+    }
+
+    fn multiscale_scale(&self) -> Option<&[f64]> {
+        self.multiscale
+            .coordinate_transformations
+            .as_deref()
+            .and_then(transformations_scale)
+    }
+
+    fn validate_level_array(&self, array: &Array<TStorage>) -> Result<(), OmeZarrError> {
+        if array.dimensionality() == self.axes().len() {
+            Ok(())
+        } else {
+            Err(OmeZarrError::IncompatibleDimensionality(
+                array.dimensionality(),
+                self.axes().len(),
+            ))
+        }
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> MultiscaleArray<TStorage> {
+    /// Create a multiscale array for the group in `storage` at `path`.
+    ///
+    /// The first multiscale image of the group is used.
+    ///
+    /// # Errors
+    /// Returns an [`OmeZarrError`] if there is a storage error, or the group does not have valid multiscales metadata.
+    pub fn new(storage: Arc<TStorage>, path: &str) -> Result<Self, OmeZarrError> {
+        let group = Group::new(storage.clone(), path)?;
+        Self::new_with_group(storage, &group)
+    }
+
+    /// Return the array of resolution `level`.
+    ///
+    /// # Errors
+    /// Returns an [`OmeZarrError`] if `level` is out of bounds, there is a storage error, the array metadata is invalid, or the array dimensionality does not match the number of axes.
+    pub fn level(&self, level: usize) -> Result<Array<TStorage>, OmeZarrError> {
+        let path = self.level_path(level)?;
+        let array = Array::new(self.storage.clone(), path.as_str())?;
+        self.validate_level_array(&array)?;
+        Ok(array)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> MultiscaleArray<TStorage> {
+    /// Async variant of [`new`](MultiscaleArray::new).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_new(storage: Arc<TStorage>, path: &str) -> Result<Self, OmeZarrError> {
+        let group = Group::async_new(storage.clone(), path).await?;
+        Self::new_with_group(storage, &group)
+    }
+
+    /// Async variant of [`level`](MultiscaleArray::level).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_level(&self, level: usize) -> Result<Array<TStorage>, OmeZarrError> {
+        let path = self.level_path(level)?;
+        let array = Array::async_new(self.storage.clone(), path.as_str()).await?;
+        self.validate_level_array(&array)?;
+        Ok(array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        group::GroupBuilder,
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    const JSON_V0_4: &str = r#"{
+        "multiscales": [
+            {
+                "version": "0.4",
+                "name": "example",
+                "axes": [
+                    {"name": "c", "type": "channel"},
+                    {"name": "y", "type": "space", "unit": "micrometer"},
+                    {"name": "x", "type": "space", "unit": "micrometer"}
+                ],
+                "datasets": [
+                    {
+                        "path": "0",
+                        "coordinateTransformations": [{"type": "scale", "scale": [1.0, 0.5, 0.5]}]
+                    },
+                    {
+                        "path": "1",
+                        "coordinateTransformations": [
+                            {"type": "scale", "scale": [1.0, 1.0, 1.0]},
+                            {"type": "translation", "translation": [0.0, 0.25, 0.25]}
+                        ]
+                    }
+                ],
+                "coordinateTransformations": [{"type": "scale", "scale": [1.0, 2.0, 2.0]}],
+                "type": "gaussian"
+            }
+        ]
+    }"#;
+
+    fn multiscale() -> Multiscale {
+        let attributes: serde_json::Value = serde_json::from_str(JSON_V0_4).unwrap();
+        Vec::<Multiscale>::deserialize(&attributes[MULTISCALES]).unwrap()[0].clone()
+    }
+
+    #[test]
+    fn ome_zarr_multiscale() {
+        let multiscale = multiscale();
+        multiscale.validate().unwrap();
+        assert_eq!(multiscale.axes[0].axis_type, Some(AxisType::Channel));
+        assert_eq!(multiscale.downscaling_type.as_deref(), Some("gaussian"));
+        assert_eq!(
+            multiscale.datasets[1].translation(),
+            Some([0.0, 0.25, 0.25].as_slice())
+        );
+
+        let json = serde_json::to_value(&multiscale).unwrap();
+        let attributes: serde_json::Value = serde_json::from_str(JSON_V0_4).unwrap();
+        assert_eq!(json, attributes[MULTISCALES][0]);
+
+        let mut invalid = multiscale.clone();
+        invalid.axes.swap(0, 1);
+        assert!(matches!(
+            invalid.validate(),
+            Err(OmeZarrError::InvalidAxes(_))
+        ));
+        let mut invalid = multiscale.clone();
+        invalid.axes[0].name = "x".to_string();
+        assert!(matches!(
+            invalid.validate(),
+            Err(OmeZarrError::InvalidAxes(_))
+        ));
+        let mut invalid = multiscale.clone();
+        invalid.datasets[0].coordinate_transformations =
+            vec![CoordinateTransformation::scale(vec![1.0, 1.0])];
+        assert!(matches!(
+            invalid.validate(),
+            Err(OmeZarrError::InvalidCoordinateTransformations(_))
+        ));
+        let mut invalid = multiscale.clone();
+        invalid.datasets[0].coordinate_transformations.reverse();
+        invalid.datasets[1].coordinate_transformations.reverse();
+        assert!(matches!(
+            invalid.validate(),
+            Err(OmeZarrError::InvalidCoordinateTransformations(_))
+        ));
+        let mut invalid = multiscale;
+        invalid.datasets.clear();
+        assert!(matches!(
+            invalid.validate(),
+            Err(OmeZarrError::InvalidDatasets(_))
+        ));
+
+        let axis_type: AxisType = serde_json::from_str(r#""angle""#).unwrap();
+        assert_eq!(axis_type, AxisType::Custom("angle".to_string()));
+        assert_eq!(serde_json::to_string(&axis_type).unwrap(), r#""angle""#);
+    }
+
+    #[test]
+    fn ome_zarr_multiscale_array() {
+        let store = Arc::new(MemoryStore::new());
+        let mut group = GroupBuilder::new().build(store.clone(), "/image").unwrap();
+        assert!(multiscales(&group).unwrap().is_none());
+        set_multiscales(&mut group, &[multiscale()]).unwrap();
+        group.store_metadata().unwrap();
+        for (path, shape) in [("/image/0", vec![2, 8, 8]), ("/image/1", vec![2, 4])] {
+            let chunk_shape = vec![2; shape.len()];
+            ArrayBuilder::new(
+                shape,
+                DataType::UInt8,
+                chunk_shape.try_into().unwrap(),
+                FillValue::from(0u8),
+            )
+            .build(store.clone(), path)
+            .unwrap()
+            .store_metadata()
+            .unwrap();
+        }
+
+        let multiscale_array = MultiscaleArray::new(store.clone(), "/image").unwrap();
+        assert_eq!(multiscale_array.num_levels(), 2);
+        assert_eq!(multiscale_array.axes().len(), 3);
+        assert_eq!(multiscale_array.level_path(1).unwrap().as_str(), "/image/1");
+        assert_eq!(multiscale_array.level(0).unwrap().shape(), &[2, 8, 8]);
+        assert!(matches!(
+            multiscale_array.level(1),
+            Err(OmeZarrError::IncompatibleDimensionality(2, 3))
+        ));
+        assert!(matches!(
+            multiscale_array.level(2),
+            Err(OmeZarrError::InvalidLevel(2, 2))
+        ));
+        assert_eq!(multiscale_array.scale(0).unwrap(), vec![1.0, 1.0, 1.0]);
+        assert_eq!(multiscale_array.scale(1).unwrap(), vec![1.0, 2.0, 2.0]);
+        assert_eq!(
+            multiscale_array.translation(0).unwrap(),
+            vec![0.0, 0.0, 0.0]
+        );
+        assert_eq!(
+            multiscale_array.translation(1).unwrap(),
+            vec![0.0, 0.5, 0.5]
+        );
+
+        // OME-Zarr 0.5 nests the multiscales in the ome attribute
+        let mut group = GroupBuilder::new().build(store.clone(), "/image").unwrap();
+        let mut multiscale = multiscale();
+        multiscale.version = None;
+        group
+            .set_attribute(OME, serde_json::json!({"version": "0.5"}))
+            .unwrap();
+        set_multiscales(&mut group, &[multiscale.clone()]).unwrap();
+        assert!(group.attributes().get(MULTISCALES).is_none());
+        assert_eq!(multiscales(&group).unwrap(), Some(vec![multiscale]));
+        group.store_metadata().unwrap();
+        let multiscale_array = MultiscaleArray::new(store.clone(), "/image").unwrap();
+        assert_eq!(multiscale_array.multiscale().version, None);
+
+        GroupBuilder::new()
+            .build(store.clone(), "/empty")
+            .unwrap()
+            .store_metadata()
+            .unwrap();
+        assert!(matches!(
+            MultiscaleArray::new(store, "/empty"),
+            Err(OmeZarrError::MissingMultiscales)
+        ));
+    }
+}
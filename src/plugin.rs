@@ -6,6 +6,7 @@
 //! [Data types](`crate::array::data_type`) are not currently supported as an extension point.
 //!
 //! Plugins are registered at compile time using the [inventory] crate.
+//! Codecs can also be registered at runtime with the [`CodecRegistry`](crate::array::codec::CodecRegistry), which is useful on platforms where [inventory] is unreliable.
 //! At runtime, a name matching function is applied to identify which registered plugin is associated with the metadata.
 //! If a match is found, the plugin is created from the metadata.
 
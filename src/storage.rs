@@ -13,6 +13,7 @@
 
 pub mod storage_adapter;
 mod storage_handle;
+mod storage_maybe_async;
 mod storage_sync;
 pub mod storage_transformer;
 mod storage_value_io;
@@ -31,6 +32,7 @@ use thiserror::Error;
 use crate::{
     array::ChunkKeyEncoding,
     byte_range::{ByteOffset, ByteRange, InvalidByteRangeError},
+    config::global_config,
     node::{NodeNameError, NodePath, NodePathError},
 };
 
@@ -39,26 +41,34 @@ pub use store_prefix::{StorePrefix, StorePrefixError, StorePrefixes};
 
 #[cfg(feature = "async")]
 pub use self::storage_async::{
-    async_create_array, async_create_group, async_discover_children, async_discover_nodes,
-    async_erase_chunk, async_erase_node, async_get_child_nodes, async_node_exists,
+    async_create_array, async_create_array_if_match, async_create_group, async_discover_children,
+    async_discover_nodes, async_erase_chunk, async_erase_node, async_get_child_nodes,
+    async_get_node_metadata, async_merge_attributes, async_move_node, async_node_exists,
     async_node_exists_listable, async_retrieve_chunk, async_retrieve_partial_values,
-    async_store_chunk, async_store_set_partial_values, AsyncListableStorageTraits,
-    AsyncReadableListableStorageTraits, AsyncReadableStorageTraits,
-    AsyncReadableWritableListableStorageTraits, AsyncReadableWritableStorageTraits,
-    AsyncWritableStorageTraits,
+    async_store_chunk, async_store_chunk_stream, async_store_set_partial_values, async_sync_stores,
+    async_update_attributes, AsyncListableStorageTraits, AsyncReadableListableStorageTraits,
+    AsyncReadableStorageTraits, AsyncReadableWritableListableStorageTraits,
+    AsyncReadableWritableStorageTraits, AsyncWritableStorageTraits,
 };
 
 pub use self::storage_sync::{
-    create_array, create_group, discover_children, discover_nodes, erase_chunk, erase_node,
-    get_child_nodes, node_exists, node_exists_listable, retrieve_chunk, retrieve_partial_values,
-    store_chunk, store_set_partial_values, ListableStorageTraits, ReadableListableStorageTraits,
-    ReadableStorageTraits, ReadableWritableListableStorageTraits, ReadableWritableStorageTraits,
-    WritableStorageTraits,
+    create_array, create_array_if_match, create_group, discover_children, discover_nodes,
+    erase_chunk, erase_node, get_child_nodes, get_node_metadata, merge_attributes, move_node,
+    node_exists, node_exists_listable, retrieve_chunk, retrieve_partial_values, store_chunk,
+    store_set_partial_values, sync_stores, update_attributes, ListableStorageTraits,
+    ReadableListableStorageTraits, ReadableStorageTraits, ReadableWritableListableStorageTraits,
+    ReadableWritableStorageTraits, WritableStorageTraits,
 };
 pub use self::storage_transformer::StorageTransformerChain;
 
 pub use self::storage_handle::StorageHandle;
 
+#[cfg(feature = "async")]
+pub(crate) use self::storage_maybe_async::AsyncStorage;
+pub(crate) use self::storage_maybe_async::{
+    ready, MaybeAsyncReadableStorageTraits, MaybeAsyncWritableStorageTraits, SyncStorage,
+};
+
 pub use storage_value_io::StorageValueIO;
 
 /// [`Arc`] wrapped readable storage.
@@ -169,6 +179,167 @@ impl StoreKeysPrefixes {
     }
 }
 
+/// An opaque token identifying a version of a store value, such as an `ETag` or object generation.
+///
+/// Generation tokens are returned by [`ReadableStorageTraits::get_with_generation`] and [`WritableStorageTraits::set_if_match`], and are only meaningful to the store that returned them.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GenerationToken(String);
+
+impl GenerationToken {
+    /// Create a new generation token.
+    #[must_use]
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// Returns the generation token as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for GenerationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Options for [`sync_stores`].
+///
+/// By default, all keys are synchronised, values with matching sizes are assumed to be unchanged, and keys only in the destination are kept.
+#[derive(Debug, Clone)]
+pub struct SyncStoresOptions {
+    prefix: StorePrefix,
+    compare_values: bool,
+    erase_extraneous: bool,
+    concurrent_target: usize,
+}
+
+impl Default for SyncStoresOptions {
+    fn default() -> Self {
+        Self {
+            prefix: StorePrefix::root(),
+            compare_values: false,
+            erase_extraneous: false,
+            concurrent_target: global_config().codec_concurrent_target(),
+        }
+    }
+}
+
+impl SyncStoresOptions {
+    /// Create the default sync stores options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the prefix of the keys to synchronise.
+    #[must_use]
+    pub const fn prefix(&self) -> &StorePrefix {
+        &self.prefix
+    }
+
+    /// Set the prefix of the keys to synchronise, such as the prefix of a node.
+    pub fn set_prefix(&mut self, prefix: StorePrefix) -> &mut Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Return whether the values of keys with matching sizes are compared.
+    #[must_use]
+    pub const fn compare_values(&self) -> bool {
+        self.compare_values
+    }
+
+    /// Set whether the values of keys with matching sizes are compared.
+    ///
+    /// If disabled, a key in both stores with the same size is assumed to be unchanged.
+    /// If enabled, the value is retrieved from both stores and copied if it differs.
+    pub fn set_compare_values(&mut self, compare_values: bool) -> &mut Self {
+        self.compare_values = compare_values;
+        self
+    }
+
+    /// Return whether keys in the destination which are not in the source are erased.
+    #[must_use]
+    pub const fn erase_extraneous(&self) -> bool {
+        self.erase_extraneous
+    }
+
+    /// Set whether keys in the destination which are not in the source are erased.
+    pub fn set_erase_extraneous(&mut self, erase_extraneous: bool) -> &mut Self {
+        self.erase_extraneous = erase_extraneous;
+        self
+    }
+
+    /// Return the concurrent target.
+    #[must_use]
+    pub const fn concurrent_target(&self) -> usize {
+        self.concurrent_target
+    }
+
+    /// Set the number of keys to compare and copy concurrently.
+    ///
+    /// Defaults to [`Config::codec_concurrent_target`](crate::config::Config::codec_concurrent_target).
+    pub fn set_concurrent_target(&mut self, concurrent_target: usize) -> &mut Self {
+        self.concurrent_target = concurrent_target;
+        self
+    }
+}
+
+/// The report of [`sync_stores`].
+#[derive(Debug, Clone, Default)]
+pub struct SyncStoresReport {
+    keys_copied: StoreKeys,
+    keys_unchanged: usize,
+    keys_erased: StoreKeys,
+    bytes_copied: u64,
+}
+
+impl SyncStoresReport {
+    /// Return the keys which were missing or changed in the destination and were copied, in sorted order.
+    #[must_use]
+    pub fn keys_copied(&self) -> &[StoreKey] {
+        &self.keys_copied
+    }
+
+    /// Return the number of keys which were unchanged in the destination.
+    #[must_use]
+    pub const fn keys_unchanged(&self) -> usize {
+        self.keys_unchanged
+    }
+
+    /// Return the keys erased from the destination, in sorted order.
+    #[must_use]
+    pub fn keys_erased(&self) -> &[StoreKey] {
+        &self.keys_erased
+    }
+
+    /// Return the number of bytes copied.
+    #[must_use]
+    pub const fn bytes_copied(&self) -> u64 {
+        self.bytes_copied
+    }
+
+    /// Create a report from the number of bytes copied (if any) for each key of the source.
+    fn new(keys: Vec<(StoreKey, Option<u64>)>, mut keys_erased: StoreKeys) -> Self {
+        let mut report = Self::default();
+        for (key, bytes_copied) in keys {
+            if let Some(bytes_copied) = bytes_copied {
+                report.keys_copied.push(key);
+                report.bytes_copied += bytes_copied;
+            } else {
+                report.keys_unchanged += 1;
+            }
+        }
+        report.keys_copied.sort();
+        keys_erased.sort();
+        report.keys_erased = keys_erased;
+        report
+    }
+}
+
 /// A storage error.
 #[derive(Debug, Error)]
 pub enum StorageError {
@@ -205,6 +376,18 @@ pub enum StorageError {
     /// Unknown key size where the key size must be known.
     #[error("{0}")]
     UnknownKeySize(StoreKey),
+    /// A transient error persisted after the maximum number of retries.
+    #[error("retries exhausted after {0} retries: {1}")]
+    RetriesExhausted(usize, Box<StorageError>),
+    /// A storage quota was exceeded.
+    #[error("storage quota exceeded: {0}")]
+    QuotaExceeded(String),
+    /// A conditional write failed because the store value was modified or created concurrently.
+    #[error("the value at {0} was modified concurrently")]
+    PreconditionFailed(StoreKey),
+    /// A transient error which may succeed if retried, such as a timeout or a temporarily unavailable service.
+    #[error("{0}")]
+    Transient(String),
     /// Any other error.
     #[error("{0}")]
     Other(String),
@@ -225,22 +408,160 @@ impl From<String> for StorageError {
 #[cfg(feature = "opendal")]
 impl From<opendal::Error> for StorageError {
     fn from(err: opendal::Error) -> Self {
-        Self::Other(err.to_string())
+        if err.is_temporary() {
+            Self::Transient(err.to_string())
+        } else {
+            Self::Other(err.to_string())
+        }
+    }
+}
+
+fn meta_key_any(path: &NodePath, metadata_file_name: &str) -> StoreKey {
+    let path = path.as_str();
+    if path.eq("/") {
+        unsafe { StoreKey::new_unchecked(metadata_file_name.to_string()) }
+    } else {
+        let path = path.strip_prefix('/').unwrap_or(path);
+        unsafe { StoreKey::new_unchecked(path.to_string() + "/" + metadata_file_name) }
     }
 }
 
 /// Return the metadata key given a node path.
+///
+/// The metadata document name is [`Config::metadata_key`](crate::config::Config::metadata_key), which is `zarr.json` by default.
 #[must_use]
 pub fn meta_key(path: &NodePath) -> StoreKey {
+    meta_key_any(path, global_config().metadata_key())
+}
+
+/// Serialise node metadata to JSON, pretty-printed if [`Config::pretty_print_metadata`](crate::config::Config::pretty_print_metadata) is enabled.
+fn metadata_to_json<T: serde::Serialize + ?Sized>(
+    key: &StoreKey,
+    metadata: &T,
+) -> Result<Vec<u8>, StorageError> {
+    if global_config().pretty_print_metadata() {
+        serde_json::to_vec_pretty(metadata)
+    } else {
+        serde_json::to_vec(metadata)
+    }
+    .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))
+}
+
+/// Return the Zarr V2 array metadata key (`.zarray`) given a node path.
+#[must_use]
+pub fn meta_key_v2_array(path: &NodePath) -> StoreKey {
+    meta_key_any(path, ".zarray")
+}
+
+/// Return the Zarr V2 attributes key (`.zattrs`) given a node path.
+#[must_use]
+pub fn meta_key_v2_attributes(path: &NodePath) -> StoreKey {
+    meta_key_any(path, ".zattrs")
+}
+
+/// Return the N5 attributes key (`attributes.json`) given a node path.
+#[cfg(feature = "n5")]
+#[must_use]
+pub fn meta_key_n5(path: &NodePath) -> StoreKey {
+    meta_key_any(path, "attributes.json")
+}
+
+/// Return the key of the write journal with identifier `id` (`zarrs_write_journal/<id>.json`) of an array given a node path.
+#[must_use]
+pub fn write_journal_key(path: &NodePath, id: &str) -> StoreKey {
+    meta_key_any(path, &format!("zarrs_write_journal/{id}.json"))
+}
+
+/// Return the prefix of the write journal keys (`zarrs_write_journal/`) of an array given a node path.
+#[must_use]
+pub fn write_journal_prefix(path: &NodePath) -> StorePrefix {
     let path = path.as_str();
-    if path.eq("/") {
-        unsafe { StoreKey::new_unchecked("zarr.json".to_string()) }
+    let path = path.strip_prefix('/').unwrap_or(path);
+    if path.is_empty() {
+        unsafe { StorePrefix::new_unchecked("zarrs_write_journal/") }
     } else {
-        let path = path.strip_prefix('/').unwrap_or(path);
-        unsafe { StoreKey::new_unchecked(path.to_string() + "/zarr.json") }
+        unsafe { StorePrefix::new_unchecked(format!("{path}/zarrs_write_journal/")) }
     }
 }
 
+/// Return the key of the statistics of a chunk (`zarrs_statistics/<chunk key>.json`) of an array given a node path, chunk grid coordinates, and a chunk key encoding.
+#[must_use]
+pub fn statistics_key(
+    path: &NodePath,
+    chunk_grid_indices: &[u64],
+    chunk_key_encoding: &ChunkKeyEncoding,
+) -> StoreKey {
+    meta_key_any(
+        path,
+        &format!(
+            "zarrs_statistics/{}.json",
+            chunk_key_encoding.encode(chunk_grid_indices).as_str()
+        ),
+    )
+}
+
+/// Return the prefix of the chunk statistics keys (`zarrs_statistics/`) of an array given a node path.
+#[must_use]
+pub fn statistics_prefix(path: &NodePath) -> StorePrefix {
+    let path = path.as_str();
+    let path = path.strip_prefix('/').unwrap_or(path);
+    if path.is_empty() {
+        unsafe { StorePrefix::new_unchecked("zarrs_statistics/") }
+    } else {
+        unsafe { StorePrefix::new_unchecked(format!("{path}/zarrs_statistics/")) }
+    }
+}
+
+/// Return the Zarr V2 group metadata key (`.zgroup`) given a node path.
+#[must_use]
+pub fn meta_key_v2_group(path: &NodePath) -> StoreKey {
+    meta_key_any(path, ".zgroup")
+}
+
+/// Deserialise the node metadata (or attributes) at `key`.
+fn metadata_from_json<T: serde::de::DeserializeOwned>(
+    key: &StoreKey,
+    metadata: &[u8],
+) -> Result<T, StorageError> {
+    serde_json::from_slice(metadata)
+        .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))
+}
+
+/// Return the store prefixes of the nodes at `from` and `to` for moving a node.
+///
+/// # Errors
+/// Returns [`StorageError::Other`] if `from` or `to` is the root node, or `from` is `to` or an ancestor of `to`.
+fn move_node_prefixes(
+    from: &NodePath,
+    to: &NodePath,
+) -> Result<(StorePrefix, StorePrefix), StorageError> {
+    let from_prefix: StorePrefix = from.try_into()?;
+    let to_prefix: StorePrefix = to.try_into()?;
+    if from_prefix.as_str().is_empty()
+        || to_prefix.as_str().is_empty()
+        || to_prefix.as_str().starts_with(from_prefix.as_str())
+    {
+        Err(StorageError::Other(format!(
+            "cannot move node {from} to {to}"
+        )))
+    } else {
+        Ok((from_prefix, to_prefix))
+    }
+}
+
+/// Return the key of `key` under the prefix `from` moved to the prefix `to`.
+fn moved_key(
+    key: &StoreKey,
+    from: &StorePrefix,
+    to: &StorePrefix,
+) -> Result<StoreKey, StorageError> {
+    let suffix = key
+        .as_str()
+        .strip_prefix(from.as_str())
+        .unwrap_or(key.as_str());
+    Ok(StoreKey::new(to.as_str().to_string() + suffix)?)
+}
+
 /// Return the data key given a node path, chunk grid coordinates, and a chunk key encoding.
 #[must_use]
 pub fn data_key(
@@ -335,4 +656,93 @@ mod tests {
             storage_transformer_performance_metrics.reads()
         );
     }
+
+    fn move_node_test<
+        TStorage: ReadableStorageTraits + WritableStorageTraits + ListableStorageTraits,
+    >(
+        store: &TStorage,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        store.set(&StoreKey::new("a/zarr.json")?, &[0])?;
+        store.set(&StoreKey::new("a/b/zarr.json")?, &[1])?;
+        store.set(&StoreKey::new("a/b/c/0")?, &[2, 3])?;
+        store.set(&StoreKey::new("d/zarr.json")?, &[4])?;
+
+        let path = |path: &str| NodePath::new(path).unwrap();
+        assert!(move_node(store, &path("/"), &path("/e")).is_err());
+        assert!(move_node(store, &path("/a"), &path("/")).is_err());
+        assert!(move_node(store, &path("/a"), &path("/a/e")).is_err());
+        assert!(move_node(store, &path("/a"), &path("/d")).is_err());
+
+        move_node(store, &path("/a/b"), &path("/d/e/b"))?;
+        move_node(store, &path("/a"), &path("/ab"))?;
+        assert_eq!(
+            store.list()?,
+            &[
+                StoreKey::new("ab/zarr.json")?,
+                StoreKey::new("d/e/b/c/0")?,
+                StoreKey::new("d/e/b/zarr.json")?,
+                StoreKey::new("d/zarr.json")?,
+            ]
+        );
+        assert_eq!(
+            store.get(&StoreKey::new("d/e/b/c/0")?)?,
+            Some(vec![2, 3].into())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn move_node_memory() -> Result<(), Box<dyn std::error::Error>> {
+        move_node_test(&MemoryStore::new())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn move_node_filesystem() -> Result<(), Box<dyn std::error::Error>> {
+        let path = tempfile::TempDir::new()?;
+        move_node_test(&store::FilesystemStore::new(path.path())?.sorted())
+    }
+
+    #[test]
+    fn sync_stores_memory() -> Result<(), Box<dyn std::error::Error>> {
+        let src = MemoryStore::new();
+        let dst = MemoryStore::new();
+        src.set(&StoreKey::new("a/zarr.json")?, &[0])?;
+        src.set(&StoreKey::new("a/c/0")?, &[1, 2])?;
+        src.set(&StoreKey::new("a/c/1")?, &[3, 4])?;
+        src.set(&StoreKey::new("b/zarr.json")?, &[5])?;
+
+        let mut options = SyncStoresOptions::new();
+        options.set_prefix(StorePrefix::new("a/")?);
+        let report = sync_stores(&src, &dst, &options)?;
+        assert_eq!(report.keys_copied().len(), 3);
+        assert_eq!(report.keys_unchanged(), 0);
+        assert_eq!(report.bytes_copied(), 5);
+        assert!(dst.get(&StoreKey::new("b/zarr.json")?)?.is_none());
+
+        // Only changed and new keys are copied
+        src.set(&StoreKey::new("a/c/0")?, &[1, 2, 3])?;
+        src.set(&StoreKey::new("a/c/1")?, &[4, 4])?; // same size
+        src.set(&StoreKey::new("a/c/2")?, &[5])?;
+        dst.set(&StoreKey::new("a/c/3")?, &[6])?;
+        let report = sync_stores(&src, &dst, &options)?;
+        assert_eq!(
+            report.keys_copied(),
+            &[StoreKey::new("a/c/0")?, StoreKey::new("a/c/2")?]
+        );
+        assert_eq!(report.keys_unchanged(), 2);
+        assert_eq!(report.bytes_copied(), 4);
+        assert!(report.keys_erased().is_empty());
+        assert_eq!(dst.get(&StoreKey::new("a/c/1")?)?, Some(vec![3, 4].into()));
+
+        // Compare values and erase extraneous keys
+        options.set_compare_values(true).set_erase_extraneous(true);
+        let report = sync_stores(&src, &dst, &options)?;
+        assert_eq!(report.keys_copied(), &[StoreKey::new("a/c/1")?]);
+        assert_eq!(report.keys_unchanged(), 3);
+        assert_eq!(report.keys_erased(), &[StoreKey::new("a/c/3")?]);
+        assert_eq!(dst.get(&StoreKey::new("a/c/1")?)?, Some(vec![4, 4].into()));
+        assert_eq!(dst.list()?, src.list_prefix(&StorePrefix::new("a/")?)?);
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-//! Storage adapters. Includes a [zip](ZipStorageAdapter) implementation.
+//! Storage adapters. Includes a [zip](ZipStorageAdapter), [retry](RetryStorageAdapter), and [read only](ReadOnlyStorageAdapter) implementation.
 //!
 //! An adapter is a nested resource using a specified protocol they can be chained with a an absolute resource location (e.g. a filesystem store).
 
@@ -6,3 +6,16 @@
 mod zip;
 #[cfg(feature = "zip")]
 pub use self::zip::{ZipStorageAdapter, ZipStorageAdapterCreateError};
+
+mod readonly;
+pub use self::readonly::ReadOnlyStorageAdapter;
+
+mod retry;
+#[cfg(feature = "object_store")]
+pub(crate) use self::retry::is_transient_io_error_kind;
+pub use self::retry::{RetryPolicy, RetryStorageAdapter};
+
+#[cfg(feature = "async")]
+mod retry_async;
+#[cfg(feature = "async")]
+pub use self::retry_async::{AsyncRetryStorageAdapter, AsyncSleep};
@@ -0,0 +1,276 @@
+//! A read only storage adapter.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    storage::{
+        store_lock::StoreKeyMutex, GenerationToken, ListableStorageTraits, ReadableStorageTraits,
+        ReadableWritableStorageTraits, StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue,
+        StoreKeys, StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
+    },
+};
+
+#[cfg(feature = "async")]
+use crate::storage::{
+    store_lock::AsyncStoreKeyMutex, AsyncListableStorageTraits, AsyncReadableStorageTraits,
+    AsyncReadableWritableStorageTraits, AsyncWritableStorageTraits,
+};
+
+/// A storage adapter which prevents writes to the underlying storage.
+///
+/// Read and list operations are passed through to the underlying storage.
+/// All write operations (including requesting a store key mutex) return [`StorageError::ReadOnly`] without accessing the underlying storage.
+///
+/// The adapter implements the synchronous and asynchronous storage traits implemented by the underlying storage.
+pub struct ReadOnlyStorageAdapter<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+}
+
+impl<TStorage: ?Sized> ReadOnlyStorageAdapter<TStorage> {
+    /// Create a new read only storage adapter.
+    #[must_use]
+    pub const fn new(storage: Arc<TStorage>) -> Self {
+        Self { storage }
+    }
+
+    /// Return the underlying storage.
+    #[must_use]
+    pub fn storage(&self) -> Arc<TStorage> {
+        self.storage.clone()
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for ReadOnlyStorageAdapter<TStorage>
+{
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        self.storage.get_with_generation(key)
+    }
+
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.storage.get(key)
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        self.storage.get_partial_values_key(key, byte_ranges)
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.storage.get_partial_values(key_ranges)
+    }
+
+    fn size(&self) -> Result<u64, StorageError> {
+        self.storage.size()
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.storage.size_prefix(prefix)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.storage.size_key(key)
+    }
+}
+
+impl<TStorage: ?Sized + Send + Sync> WritableStorageTraits for ReadOnlyStorageAdapter<TStorage> {
+    fn set(&self, _key: &StoreKey, _value: &[u8]) -> Result<(), StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+
+    fn set_partial_values(
+        &self,
+        _key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+
+    fn erase(&self, _key: &StoreKey) -> Result<(), StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+
+    fn erase_values(&self, _keys: &[StoreKey]) -> Result<(), StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+
+    fn erase_prefix(&self, _prefix: &StorePrefix) -> Result<(), StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableWritableStorageTraits
+    for ReadOnlyStorageAdapter<TStorage>
+{
+    fn mutex(&self, _key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
+    for ReadOnlyStorageAdapter<TStorage>
+{
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.storage.list()
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.storage.list_prefix(prefix)
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.storage.list_dir(prefix)
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
+    for ReadOnlyStorageAdapter<TStorage>
+{
+    async fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        self.storage.get_with_generation(key).await
+    }
+
+    async fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.storage.get(key).await
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        self.storage.get_partial_values_key(key, byte_ranges).await
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.storage.get_partial_values(key_ranges).await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.storage.size().await
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.storage.size_prefix(prefix).await
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.storage.size_key(key).await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + Sync> AsyncWritableStorageTraits
+    for ReadOnlyStorageAdapter<TStorage>
+{
+    async fn set(&self, _key: &StoreKey, _value: Bytes) -> Result<(), StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+
+    async fn set_partial_values(
+        &self,
+        _key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+
+    async fn erase(&self, _key: &StoreKey) -> Result<(), StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+
+    async fn erase_values(&self, _keys: &[StoreKey]) -> Result<(), StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+
+    async fn erase_prefix(&self, _prefix: &StorePrefix) -> Result<(), StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableWritableStorageTraits
+    for ReadOnlyStorageAdapter<TStorage>
+{
+    async fn mutex(&self, _key: &StoreKey) -> Result<AsyncStoreKeyMutex, StorageError> {
+        Err(StorageError::ReadOnly)
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncListableStorageTraits> AsyncListableStorageTraits
+    for ReadOnlyStorageAdapter<TStorage>
+{
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.storage.list().await
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.storage.list_prefix(prefix).await
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.storage.list_dir(prefix).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::storage::{
+        store::MemoryStore, ListableStorageTraits, ReadableStorageTraits,
+        ReadableWritableStorageTraits, StorageError, StoreKey, StoreKeyStartValue, StorePrefix,
+        WritableStorageTraits,
+    };
+
+    use super::ReadOnlyStorageAdapter;
+
+    #[test]
+    fn readonly_storage_adapter() {
+        let store = Arc::new(MemoryStore::new());
+        let key = StoreKey::new("a/b").unwrap();
+        store.set(&key, &[0, 1, 2]).unwrap();
+
+        let adapter = ReadOnlyStorageAdapter::new(store.clone());
+        assert_eq!(adapter.get(&key).unwrap(), Some(vec![0, 1, 2].into()));
+        assert_eq!(adapter.list().unwrap(), vec![key.clone()]);
+        assert!(matches!(
+            adapter.set(&key, &[3]),
+            Err(StorageError::ReadOnly)
+        ));
+        assert!(matches!(
+            adapter.set_partial_values(&[StoreKeyStartValue::new(key.clone(), 0, &[3])]),
+            Err(StorageError::ReadOnly)
+        ));
+        assert!(matches!(adapter.erase(&key), Err(StorageError::ReadOnly)));
+        assert!(matches!(
+            adapter.erase_prefix(&StorePrefix::root()),
+            Err(StorageError::ReadOnly)
+        ));
+        assert!(matches!(adapter.mutex(&key), Err(StorageError::ReadOnly)));
+        assert_eq!(store.get(&key).unwrap(), Some(vec![0, 1, 2].into()));
+    }
+}
@@ -0,0 +1,424 @@
+//! A retry storage adapter.
+
+use std::{sync::Arc, time::Duration};
+
+use bytes::Bytes;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    storage::{
+        store_lock::StoreKeyMutex, GenerationToken, ListableStorageTraits, ReadableStorageTraits,
+        ReadableWritableStorageTraits, StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue,
+        StoreKeys, StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
+    },
+};
+
+/// A retry policy with exponential backoff and jitter.
+///
+/// The delay before retry `i` (zero indexed) is `initial_backoff * backoff_multiplier^i`, limited to `max_backoff`.
+/// If jitter is enabled, the delay is chosen uniformly at random between zero and that value ("full jitter").
+///
+/// By default, up to 3 retries are attempted with an initial backoff of 100ms, a backoff multiplier of 2, a maximum backoff of 10s, and jitter.
+/// Errors are retried if they are transient as determined by [`RetryPolicy::is_transient_default`], which can be changed with [`RetryPolicy::is_transient`].
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_retries: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    backoff_multiplier: f64,
+    jitter: bool,
+    is_transient: fn(&StorageError) -> bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            backoff_multiplier: 2.0,
+            jitter: true,
+            is_transient: Self::is_transient_default,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a new retry policy with default parameters.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of retries.
+    #[must_use]
+    pub const fn max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the backoff before the first retry.
+    #[must_use]
+    pub const fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Set the maximum backoff between retries.
+    #[must_use]
+    pub const fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Set the multiplier applied to the backoff after each retry.
+    #[must_use]
+    pub const fn backoff_multiplier(mut self, backoff_multiplier: f64) -> Self {
+        self.backoff_multiplier = backoff_multiplier;
+        self
+    }
+
+    /// Enable or disable jitter.
+    #[must_use]
+    pub const fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the function which determines if an error is transient and should be retried.
+    #[must_use]
+    pub fn is_transient(mut self, is_transient: fn(&StorageError) -> bool) -> Self {
+        self.is_transient = is_transient;
+        self
+    }
+
+    /// Returns true if `error` is transient under the default policy.
+    ///
+    /// An error is considered transient if it is
+    ///  - an IO error indicating an interruption, timeout, or connection issue, or
+    ///  - [`StorageError::Transient`], which stores return for failures such as timeouts, connection errors, and unavailable services.
+    ///
+    /// [`StorageError::Other`] is not considered transient, as it may indicate a permanent failure.
+    /// Use [`RetryPolicy::is_transient`] to retry other errors.
+    #[must_use]
+    pub fn is_transient_default(error: &StorageError) -> bool {
+        match error {
+            StorageError::IOError(err) => is_transient_io_error_kind(err.kind()),
+            StorageError::Transient(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Return the maximum number of retries.
+    #[must_use]
+    pub const fn get_max_retries(&self) -> usize {
+        self.max_retries
+    }
+
+    /// Returns true if `error` should be retried under this policy.
+    #[must_use]
+    pub fn should_retry(&self, error: &StorageError) -> bool {
+        (self.is_transient)(error)
+    }
+
+    /// Return the backoff before retry `retry` (zero indexed), including jitter if enabled.
+    #[must_use]
+    pub fn backoff(&self, retry: usize) -> Duration {
+        let multiplier = self
+            .backoff_multiplier
+            .powi(i32::try_from(retry).unwrap_or(i32::MAX));
+        let backoff = self
+            .initial_backoff
+            .mul_f64(multiplier.min(u32::MAX.into()))
+            .min(self.max_backoff);
+        if self.jitter {
+            backoff.mul_f64(random_unit())
+        } else {
+            backoff
+        }
+    }
+
+    /// Call `operation`, retrying transient errors with backoff.
+    ///
+    /// # Errors
+    /// Returns the error of `operation` if it is not transient, or [`StorageError::RetriesExhausted`] if the maximum number of retries is exceeded.
+    pub fn retry<T>(
+        &self,
+        mut operation: impl FnMut() -> Result<T, StorageError>,
+    ) -> Result<T, StorageError> {
+        let mut retry = 0;
+        loop {
+            match operation() {
+                Ok(value) => return Ok(value),
+                Err(err) if self.should_retry(&err) => {
+                    if retry >= self.max_retries {
+                        return Err(StorageError::RetriesExhausted(retry, Box::new(err)));
+                    }
+                    std::thread::sleep(self.backoff(retry));
+                    retry += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Return a random number in `[0, 1)`.
+#[allow(clippy::cast_precision_loss)]
+fn random_unit() -> f64 {
+    (crate::random_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// A storage adapter which retries transient failures of the underlying storage.
+///
+/// See [`RetryPolicy`] for the retry behaviour.
+/// If all retries fail, a [`StorageError::RetriesExhausted`] error is returned.
+pub struct RetryStorageAdapter<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    policy: RetryPolicy,
+}
+
+impl<TStorage: ?Sized> RetryStorageAdapter<TStorage> {
+    /// Create a new retry storage adapter with the default [`RetryPolicy`].
+    #[must_use]
+    pub fn new(storage: Arc<TStorage>) -> Self {
+        Self::new_with_policy(storage, RetryPolicy::default())
+    }
+
+    /// Create a new retry storage adapter with a custom [`RetryPolicy`].
+    #[must_use]
+    pub const fn new_with_policy(storage: Arc<TStorage>, policy: RetryPolicy) -> Self {
+        Self { storage, policy }
+    }
+
+    /// Return the retry policy.
+    #[must_use]
+    pub const fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for RetryStorageAdapter<TStorage>
+{
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        self.policy.retry(|| self.storage.get_with_generation(key))
+    }
+
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.policy.retry(|| self.storage.get(key))
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        self.policy
+            .retry(|| self.storage.get_partial_values_key(key, byte_ranges))
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.policy
+            .retry(|| self.storage.get_partial_values(key_ranges))
+    }
+
+    fn size(&self) -> Result<u64, StorageError> {
+        self.policy.retry(|| self.storage.size())
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.policy.retry(|| self.storage.size_prefix(prefix))
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.policy.retry(|| self.storage.size_key(key))
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits
+    for RetryStorageAdapter<TStorage>
+{
+    fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: &[u8],
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        self.policy
+            .retry(|| self.storage.set_if_match(key, value, generation))
+    }
+
+    fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        self.policy.retry(|| self.storage.set(key, value))
+    }
+
+    fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        self.policy
+            .retry(|| self.storage.set_partial_values(key_start_values))
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.policy.retry(|| self.storage.erase(key))
+    }
+
+    fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.policy.retry(|| self.storage.erase_values(keys))
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.policy.retry(|| self.storage.erase_prefix(prefix))
+    }
+}
+
+impl<TStorage: ?Sized + ReadableWritableStorageTraits> ReadableWritableStorageTraits
+    for RetryStorageAdapter<TStorage>
+{
+    fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+        self.storage.mutex(key)
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
+    for RetryStorageAdapter<TStorage>
+{
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.policy.retry(|| self.storage.list())
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.policy.retry(|| self.storage.list_prefix(prefix))
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.policy.retry(|| self.storage.list_dir(prefix))
+    }
+}
+
+/// Returns true if an IO error of `kind` indicates an interruption, timeout, or connection issue.
+pub(crate) fn is_transient_io_error_kind(kind: std::io::ErrorKind) -> bool {
+    use std::io::ErrorKind;
+    matches!(
+        kind,
+        ErrorKind::Interrupted
+            | ErrorKind::TimedOut
+            | ErrorKind::WouldBlock
+            | ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe
+            | ErrorKind::UnexpectedEof
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::storage::store::MemoryStore;
+
+    use super::*;
+
+    /// A store which fails with a transient error a number of times before each successful `get`.
+    struct FlakyStore {
+        store: MemoryStore,
+        failures: usize,
+        attempts: AtomicUsize,
+    }
+
+    impl ReadableStorageTraits for FlakyStore {
+        fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+            let attempt = self.attempts.fetch_add(1, Ordering::Relaxed);
+            if attempt % (self.failures + 1) < self.failures {
+                Err(StorageError::Transient("transient".to_string()))
+            } else {
+                self.store.get(key)
+            }
+        }
+
+        fn get_partial_values_key(
+            &self,
+            key: &StoreKey,
+            byte_ranges: &[ByteRange],
+        ) -> Result<Option<Vec<Bytes>>, StorageError> {
+            self.store.get_partial_values_key(key, byte_ranges)
+        }
+
+        fn get_partial_values(
+            &self,
+            key_ranges: &[StoreKeyRange],
+        ) -> Result<Vec<MaybeBytes>, StorageError> {
+            self.store.get_partial_values(key_ranges)
+        }
+
+        fn size_prefix(&self, _prefix: &StorePrefix) -> Result<u64, StorageError> {
+            Err(StorageError::Unsupported("size_prefix".to_string()))
+        }
+
+        fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+            self.store.size_key(key)
+        }
+    }
+
+    #[test]
+    fn retry_policy_backoff() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(10))
+            .max_backoff(Duration::from_millis(50))
+            .backoff_multiplier(2.0)
+            .jitter(false);
+        assert_eq!(policy.backoff(0), Duration::from_millis(10));
+        assert_eq!(policy.backoff(1), Duration::from_millis(20));
+        assert_eq!(policy.backoff(2), Duration::from_millis(40));
+        assert_eq!(policy.backoff(3), Duration::from_millis(50));
+        assert_eq!(policy.backoff(1000), Duration::from_millis(50));
+
+        let policy = policy.jitter(true);
+        for retry in 0..10 {
+            assert!(policy.backoff(retry) <= Duration::from_millis(50));
+        }
+    }
+
+    #[test]
+    fn retry_storage_adapter() {
+        let store = MemoryStore::new();
+        let key = StoreKey::new("key").unwrap();
+        store.set(&key, &[0, 1, 2]).unwrap();
+        let store = Arc::new(FlakyStore {
+            store,
+            failures: 2,
+            attempts: AtomicUsize::new(0),
+        });
+        let policy = RetryPolicy::new().initial_backoff(Duration::from_millis(1));
+
+        let adapter = RetryStorageAdapter::new_with_policy(store.clone(), policy.clone());
+        assert_eq!(adapter.get(&key).unwrap(), Some(vec![0, 1, 2].into()));
+        assert_eq!(store.attempts.load(Ordering::Relaxed), 3);
+
+        let adapter = RetryStorageAdapter::new_with_policy(store.clone(), policy.max_retries(1));
+        assert!(matches!(
+            adapter.get(&key),
+            Err(StorageError::RetriesExhausted(1, _))
+        ));
+
+        // Non transient errors are not retried
+        assert!(!RetryPolicy::is_transient_default(&StorageError::Other(
+            "other".to_string()
+        )));
+        assert!(matches!(
+            adapter.size_prefix(&StorePrefix::root()),
+            Err(StorageError::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,270 @@
+//! An asynchronous retry storage adapter.
+
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
+
+use bytes::Bytes;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    storage::{
+        store_lock::AsyncStoreKeyMutex, AsyncListableStorageTraits, AsyncReadableStorageTraits,
+        AsyncReadableWritableStorageTraits, AsyncWritableStorageTraits, GenerationToken,
+        StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes,
+        StorePrefix,
+    },
+};
+
+use super::RetryPolicy;
+
+/// An asynchronous sleep function.
+///
+/// This crate is async runtime-agnostic, so the sleep function must be provided.
+/// For example, `Arc::new(|duration| Box::pin(tokio::time::sleep(duration)))`.
+pub type AsyncSleep =
+    Arc<dyn Fn(Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+impl RetryPolicy {
+    /// Async variant of [`retry`](RetryPolicy::retry) which waits for the backoff with `sleep`.
+    ///
+    /// # Errors
+    /// Returns the error of `operation` if it is not transient, or [`StorageError::RetriesExhausted`] if the maximum number of retries is exceeded.
+    pub async fn async_retry<T, F: Future<Output = Result<T, StorageError>>>(
+        &self,
+        sleep: &AsyncSleep,
+        mut operation: impl FnMut() -> F,
+    ) -> Result<T, StorageError> {
+        let mut retry = 0;
+        loop {
+            match operation().await {
+                Ok(value) => return Ok(value),
+                Err(err) if self.should_retry(&err) => {
+                    if retry >= self.get_max_retries() {
+                        return Err(StorageError::RetriesExhausted(retry, Box::new(err)));
+                    }
+                    sleep(self.backoff(retry)).await;
+                    retry += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// An asynchronous storage adapter which retries transient failures of the underlying storage.
+///
+/// See [`RetryPolicy`] for the retry behaviour.
+/// If all retries fail, a [`StorageError::RetriesExhausted`] error is returned.
+pub struct AsyncRetryStorageAdapter<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    policy: RetryPolicy,
+    sleep: AsyncSleep,
+}
+
+impl<TStorage: ?Sized> AsyncRetryStorageAdapter<TStorage> {
+    /// Create a new asynchronous retry storage adapter with the default [`RetryPolicy`].
+    ///
+    /// `sleep` is used to wait between retries.
+    #[must_use]
+    pub fn new(storage: Arc<TStorage>, sleep: AsyncSleep) -> Self {
+        Self::new_with_policy(storage, RetryPolicy::default(), sleep)
+    }
+
+    /// Create a new asynchronous retry storage adapter with a custom [`RetryPolicy`].
+    ///
+    /// `sleep` is used to wait between retries.
+    #[must_use]
+    pub fn new_with_policy(storage: Arc<TStorage>, policy: RetryPolicy, sleep: AsyncSleep) -> Self {
+        Self {
+            storage,
+            policy,
+            sleep,
+        }
+    }
+
+    /// Return the retry policy.
+    #[must_use]
+    pub const fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+}
+
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
+    for AsyncRetryStorageAdapter<TStorage>
+{
+    async fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.get_with_generation(key))
+            .await
+    }
+
+    async fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.get(key))
+            .await
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || {
+                self.storage.get_partial_values_key(key, byte_ranges)
+            })
+            .await
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.get_partial_values(key_ranges))
+            .await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.size())
+            .await
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.size_prefix(prefix))
+            .await
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.size_key(key))
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncWritableStorageTraits> AsyncWritableStorageTraits
+    for AsyncRetryStorageAdapter<TStorage>
+{
+    async fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: Bytes,
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || {
+                self.storage.set_if_match(key, value.clone(), generation)
+            })
+            .await
+    }
+
+    async fn set(&self, key: &StoreKey, value: bytes::Bytes) -> Result<(), StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.set(key, value.clone()))
+            .await
+    }
+
+    async fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || {
+                self.storage.set_partial_values(key_start_values)
+            })
+            .await
+    }
+
+    async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.erase(key))
+            .await
+    }
+
+    async fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.erase_values(keys))
+            .await
+    }
+
+    async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.erase_prefix(prefix))
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits> AsyncReadableWritableStorageTraits
+    for AsyncRetryStorageAdapter<TStorage>
+{
+    async fn mutex(&self, key: &StoreKey) -> Result<AsyncStoreKeyMutex, StorageError> {
+        self.storage.mutex(key).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncListableStorageTraits> AsyncListableStorageTraits
+    for AsyncRetryStorageAdapter<TStorage>
+{
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.list())
+            .await
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.list_prefix(prefix))
+            .await
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.policy
+            .async_retry(&self.sleep, || self.storage.list_dir(prefix))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn async_retry_policy() {
+        let sleep: AsyncSleep = Arc::new(|_duration| Box::pin(async {}));
+        let policy = RetryPolicy::new().initial_backoff(Duration::from_millis(1));
+        let attempts = AtomicUsize::new(0);
+        let result = policy
+            .async_retry(&sleep, || {
+                let attempt = attempts.fetch_add(1, Ordering::Relaxed);
+                async move {
+                    if attempt < 2 {
+                        Err(StorageError::Transient("transient".to_string()))
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), 2);
+
+        let result = policy
+            .clone()
+            .max_retries(0)
+            .async_retry(&sleep, || async {
+                Err::<(), _>(StorageError::Transient("transient".to_string()))
+            })
+            .await;
+        assert!(matches!(result, Err(StorageError::RetriesExhausted(0, _))));
+    }
+}
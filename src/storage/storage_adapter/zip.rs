@@ -1,5 +1,7 @@
 //! A zip store.
 
+use bytes::Bytes;
+
 use crate::{
     array::{codec::extract_byte_ranges_read, MaybeBytes},
     byte_range::ByteRange,
@@ -69,7 +71,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits> ZipStorageAdapter<TStorage> {
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
         let mut zip_archive = self.zip_archive.lock();
         let mut zip_name = self.zip_path.clone();
         zip_name.push(key.as_str());
@@ -86,7 +88,10 @@ impl<TStorage: ?Sized + ReadableStorageTraits> ZipStorageAdapter<TStorage> {
         };
         let size = file.size();
 
-        let out = extract_byte_ranges_read(&mut file, size, byte_ranges)?;
+        let out = extract_byte_ranges_read(&mut file, size, byte_ranges)?
+            .into_iter()
+            .map(Bytes::from)
+            .collect();
         Ok(Some(out))
     }
 
@@ -109,7 +114,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
         self.get_impl(key, byte_ranges)
     }
 
@@ -367,7 +372,7 @@ mod tests {
             &"/a/b".try_into()?
         )?);
 
-        assert_eq!(store.get(&"a/b".try_into()?)?.unwrap(), &[0, 1, 2, 3]);
+        assert_eq!(store.get(&"a/b".try_into()?)?.unwrap(), &[0, 1, 2, 3][..]);
         assert_eq!(
             store.get(&"a/c".try_into()?)?.unwrap(),
             Vec::<u8>::new().as_slice()
@@ -421,7 +426,7 @@ mod tests {
             &"/b".try_into()?
         )?);
 
-        assert_eq!(store.get(&"b".try_into()?)?.unwrap(), &[0, 1, 2, 3]);
+        assert_eq!(store.get(&"b".try_into()?)?.unwrap(), &[0, 1, 2, 3][..]);
         // assert_eq!(store.get(&"c".try_into()?)?, Vec::<u8>::new().as_slice());
 
         Ok(())
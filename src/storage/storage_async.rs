@@ -1,19 +1,25 @@
 use async_recursion::async_recursion;
 
 use bytes::Bytes;
-use futures::{stream::FuturesUnordered, StreamExt};
+use futures::{
+    stream::{BoxStream, FuturesUnordered},
+    StreamExt, TryStreamExt,
+};
 use itertools::Itertools;
 
 use crate::{
-    array::{ArrayMetadata, ChunkKeyEncoding, MaybeBytes},
+    array::{ArrayMetadata, ArrayMetadataV2, ChunkKeyEncoding, MaybeBytes},
     byte_range::ByteRange,
-    group::{GroupMetadata, GroupMetadataV3},
+    group::{GroupMetadata, GroupMetadataV2, GroupMetadataV3},
     node::{Node, NodeMetadata, NodePath},
 };
 
 use super::{
-    data_key, meta_key, store_lock::AsyncStoreKeyMutex, StorageError, StoreKey, StoreKeyRange,
+    data_key, meta_key, meta_key_v2_array, meta_key_v2_attributes, meta_key_v2_group,
+    metadata_from_json, metadata_to_json, move_node_prefixes, moved_key,
+    store_lock::AsyncStoreKeyMutex, GenerationToken, StorageError, StoreKey, StoreKeyRange,
     StoreKeyStartValue, StoreKeys, StoreKeysPrefixes, StorePrefix, StorePrefixes,
+    SyncStoresOptions, SyncStoresReport,
 };
 
 /// Async readable storage traits.
@@ -39,7 +45,7 @@ pub trait AsyncReadableStorageTraits: Send + Sync {
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError>;
+    ) -> Result<Option<Vec<Bytes>>, StorageError>;
 
     /// Retrieve partial bytes from a list of [`StoreKeyRange`].
     ///
@@ -74,6 +80,20 @@ pub trait AsyncReadableStorageTraits: Send + Sync {
     /// Returns a [`StorageError`] if there is an underlying storage error.
     async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError>;
 
+    /// Retrieve the value (bytes) associated with a given [`StoreKey`] and its [`GenerationToken`].
+    ///
+    /// Returns [`None`] if the key is not found.
+    /// The generation token is [`None`] if the store does not support conditional writes (see [`AsyncWritableStorageTraits::set_if_match`]), which is the default.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an error with the underlying store.
+    async fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        Ok(self.get(key).await?.map(|value| (value, None)))
+    }
+
     /// Return the size in bytes of the readable storage.
     ///
     /// # Errors
@@ -185,10 +205,14 @@ pub async fn async_store_set_partial_values<T: AsyncReadableWritableStorageTrait
         .map(|(key, group)| async move {
             // Lock the store key
             let mutex = store.mutex(&key).await?;
-            let _lock = mutex.lock().await;
+            let _lock = mutex.lock().await?;
 
             // Read the store key
-            let mut bytes = store.get(&key.clone()).await?.unwrap_or_else(Vec::default);
+            let mut bytes: Vec<u8> = store
+                .get(&key.clone())
+                .await?
+                .map(Vec::from)
+                .unwrap_or_default();
 
             // Expand the store key if needed
             let end_max =
@@ -224,6 +248,22 @@ pub trait AsyncWritableStorageTraits: Send + Sync {
     /// Returns a [`StorageError`] on failure to store.
     async fn set(&self, key: &StoreKey, value: bytes::Bytes) -> Result<(), StorageError>;
 
+    /// Store the concatenated bytes of `stream` at a [`StoreKey`].
+    ///
+    /// Stores that can write a value incrementally (e.g. with a multipart upload) should override this, so that the entire value does not need to be held in memory.
+    /// The default implementation collects `stream` and calls [`set`](AsyncWritableStorageTraits::set).
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if `stream` yields an error or on failure to store.
+    async fn set_stream<'a>(
+        &self,
+        key: &StoreKey,
+        stream: BoxStream<'a, Result<Bytes, StorageError>>,
+    ) -> Result<(), StorageError> {
+        let value: Vec<Bytes> = stream.try_collect().await?;
+        self.set(key, value.concat().into()).await
+    }
+
     /// Store bytes according to a list of [`StoreKeyStartValue`].
     ///
     /// # Errors
@@ -259,6 +299,26 @@ pub trait AsyncWritableStorageTraits: Send + Sync {
     /// # Errors
     /// Returns a [`StorageError`] if there is an underlying storage error.
     async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError>;
+
+    /// Store bytes at a [`StoreKey`] if the current value matches `generation`.
+    ///
+    /// If `generation` is [`None`], the value is only stored if the key does not exist.
+    /// Returns the [`GenerationToken`] of the stored value if it is known.
+    ///
+    /// This is an optional operation for stores that support conditional writes (e.g. object stores with `If-Match` preconditions).
+    ///
+    /// # Errors
+    /// Returns [`StorageError::PreconditionFailed`] if the current value does not match `generation`, [`StorageError::Unsupported`] if conditional writes are not supported, or a [`StorageError`] if there is an underlying storage error.
+    async fn set_if_match(
+        &self,
+        _key: &StoreKey,
+        _value: Bytes,
+        _generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        Err(StorageError::Unsupported(
+            "the store does not support conditional writes".to_string(),
+        ))
+    }
 }
 
 /// A supertrait of [`AsyncReadableStorageTraits`] and [`AsyncWritableStorageTraits`].
@@ -295,6 +355,45 @@ impl<T> AsyncReadableWritableListableStorageTraits for T where
 {
 }
 
+/// Asynchronously get the metadata of the node at `path`.
+///
+/// Zarr V2 metadata (`.zarray` or `.zgroup` and `.zattrs`) is read if `zarr.json` does not exist.
+/// The node is an implicit group with default metadata if no metadata exists.
+///
+/// # Errors
+/// Returns a [`StorageError`] if the metadata is invalid or there is an underlying error with the store.
+pub async fn async_get_node_metadata<TStorage: ?Sized + AsyncReadableStorageTraits>(
+    storage: &TStorage,
+    path: &NodePath,
+) -> Result<NodeMetadata, StorageError> {
+    let key = meta_key(path);
+    if let Some(metadata) = storage.get(&key).await? {
+        return metadata_from_json(&key, &metadata);
+    }
+
+    let key = meta_key_v2_array(path);
+    if let Some(metadata) = storage.get(&key).await? {
+        let mut metadata: ArrayMetadataV2 = metadata_from_json(&key, &metadata)?;
+        let key = meta_key_v2_attributes(path);
+        if let Some(attributes) = storage.get(&key).await? {
+            metadata.attributes = metadata_from_json(&key, &attributes)?;
+        }
+        return Ok(NodeMetadata::Array(metadata.into()));
+    }
+
+    let key = meta_key_v2_group(path);
+    if let Some(metadata) = storage.get(&key).await? {
+        let mut metadata: GroupMetadataV2 = metadata_from_json(&key, &metadata)?;
+        let key = meta_key_v2_attributes(path);
+        if let Some(attributes) = storage.get(&key).await? {
+            metadata.attributes = metadata_from_json(&key, &attributes)?;
+        }
+        return Ok(NodeMetadata::Group(metadata.into()));
+    }
+
+    Ok(NodeMetadata::Group(GroupMetadataV3::default().into()))
+}
+
 /// Asynchronously get the child nodes.
 ///
 /// # Errors
@@ -310,16 +409,8 @@ pub async fn async_get_child_nodes<
     let mut nodes: Vec<Node> = Vec::new();
     // FIXME: Asynchronously get metadata of all prefixes
     for prefix in &prefixes {
-        let key = meta_key(&prefix.try_into()?);
-        let child_metadata = match storage.get(&key).await? {
-            Some(child_metadata) => {
-                let metadata: NodeMetadata = serde_json::from_slice(child_metadata.as_slice())
-                    .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
-                metadata
-            }
-            None => NodeMetadata::Group(GroupMetadataV3::default().into()),
-        };
         let path: NodePath = prefix.try_into()?;
+        let child_metadata = async_get_node_metadata(storage, &path).await?;
         let children = match child_metadata {
             NodeMetadata::Array(_) => Vec::default(),
             NodeMetadata::Group(_) => async_get_child_nodes(storage, &path).await?,
@@ -331,6 +422,8 @@ pub async fn async_get_child_nodes<
 
 /// Asynchronously create a group.
 ///
+/// Zarr V2 group metadata is stored in `.zgroup` and `.zattrs`.
+///
 /// # Errors
 /// Returns a [`StorageError`] if there is an underlying error with the store.
 pub async fn async_create_group(
@@ -338,11 +431,23 @@ pub async fn async_create_group(
     path: &NodePath,
     group: &GroupMetadata,
 ) -> Result<(), StorageError> {
-    let key = meta_key(path);
-    let json = serde_json::to_vec_pretty(group)
-        .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
-    storage.set(&meta_key(path), json.into()).await?;
-    Ok(())
+    match group {
+        GroupMetadata::V3(_) => {
+            let key = meta_key(path);
+            let json = metadata_to_json(&key, group)?;
+            storage.set(&key, json.into()).await
+        }
+        GroupMetadata::V2(group) => {
+            let key = meta_key_v2_group(path);
+            let zgroup = GroupMetadataV2 {
+                attributes: serde_json::Map::new(),
+                ..group.clone()
+            };
+            let json = metadata_to_json(&key, &zgroup)?;
+            storage.set(&key, json.into()).await?;
+            async_store_attributes_v2(storage, path, &group.attributes).await
+        }
+    }
 }
 
 /// Asynchronously create an array.
@@ -355,12 +460,140 @@ pub async fn async_create_array(
     array: &ArrayMetadata,
 ) -> Result<(), StorageError> {
     let key = meta_key(path);
-    let json = serde_json::to_vec_pretty(array)
-        .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
+    let json = metadata_to_json(&key, array)?;
     storage.set(&meta_key(path), json.into()).await?;
     Ok(())
 }
 
+/// Asynchronously create an array if the generation of its existing metadata matches `generation`.
+///
+/// See [`create_array_if_match`](crate::storage::create_array_if_match).
+///
+/// # Errors
+/// Returns [`StorageError::PreconditionFailed`] if the metadata was modified concurrently,
+/// [`StorageError::Unsupported`] if the store does not support conditional writes,
+/// or a [`StorageError`] if there is an underlying error with the store.
+pub async fn async_create_array_if_match(
+    storage: &dyn AsyncWritableStorageTraits,
+    path: &NodePath,
+    array: &ArrayMetadata,
+    generation: Option<&GenerationToken>,
+) -> Result<Option<GenerationToken>, StorageError> {
+    let key = meta_key(path);
+    let json = metadata_to_json(&key, array)?;
+    storage.set_if_match(&key, json.into(), generation).await
+}
+
+/// Asynchronously update the attributes in the metadata of the node at `path`.
+///
+/// Only the `attributes` field of the existing metadata is replaced, other fields are left unchanged.
+///
+/// # Errors
+/// Returns a [`StorageError`] if the node metadata does not exist or is invalid, or there is an underlying error with the store.
+pub async fn async_update_attributes(
+    storage: &dyn AsyncReadableWritableStorageTraits,
+    path: &NodePath,
+    attributes: &serde_json::Map<String, serde_json::Value>,
+) -> Result<(), StorageError> {
+    let key = meta_key(path);
+    let mutex = storage.mutex(&key).await?;
+    let _lock = mutex.lock().await?;
+    let Some(metadata) = storage.get(&key).await? else {
+        // Zarr V2 attributes are stored separately in `.zattrs`
+        if storage.size_key(&meta_key_v2_array(path)).await?.is_some()
+            || storage.size_key(&meta_key_v2_group(path)).await?.is_some()
+        {
+            return async_store_attributes_v2(storage, path, attributes).await;
+        }
+        return Err(StorageError::InvalidMetadata(
+            key,
+            "the metadata does not exist".to_string(),
+        ));
+    };
+    let json = super::storage_sync::patch_attributes(&key, &metadata, attributes)?;
+    storage.set(&key, json.into()).await
+}
+
+/// Asynchronously merge `attributes_patch` into the attributes in the metadata of the node at `path`, and return the merged attributes.
+///
+/// See [`merge_attributes`](super::merge_attributes).
+///
+/// # Errors
+/// Returns a [`StorageError`] if the node metadata does not exist or is invalid, or there is an underlying error with the store.
+pub async fn async_merge_attributes(
+    storage: &dyn AsyncReadableWritableStorageTraits,
+    path: &NodePath,
+    attributes_patch: &serde_json::Map<String, serde_json::Value>,
+) -> Result<serde_json::Map<String, serde_json::Value>, StorageError> {
+    let key = meta_key(path);
+    let mutex = storage.mutex(&key).await?;
+    let _lock = mutex.lock().await?;
+    loop {
+        let Some((metadata, generation)) = storage.get_with_generation(&key).await? else {
+            // Zarr V2 attributes are stored separately in `.zattrs`
+            if storage.size_key(&meta_key_v2_array(path)).await?.is_some()
+                || storage.size_key(&meta_key_v2_group(path)).await?.is_some()
+            {
+                return async_merge_attributes_v2(storage, path, attributes_patch).await;
+            }
+            return Err(StorageError::InvalidMetadata(
+                key,
+                "the metadata does not exist".to_string(),
+            ));
+        };
+        let mut attributes = super::storage_sync::metadata_attributes(&key, &metadata)?;
+        super::storage_sync::merge_patch(&mut attributes, attributes_patch);
+        let json = super::storage_sync::patch_attributes(&key, &metadata, &attributes)?;
+        if let Some(generation) = generation {
+            match storage
+                .set_if_match(&key, json.into(), Some(&generation))
+                .await
+            {
+                Ok(_) => return Ok(attributes),
+                Err(StorageError::PreconditionFailed(_)) => {}
+                Err(err) => return Err(err),
+            }
+        } else {
+            storage.set(&key, json.into()).await?;
+            return Ok(attributes);
+        }
+    }
+}
+
+/// Asynchronously merge `attributes_patch` into the Zarr V2 attributes in `.zattrs`, and return the merged attributes.
+async fn async_merge_attributes_v2(
+    storage: &dyn AsyncReadableWritableStorageTraits,
+    path: &NodePath,
+    attributes_patch: &serde_json::Map<String, serde_json::Value>,
+) -> Result<serde_json::Map<String, serde_json::Value>, StorageError> {
+    let key = meta_key_v2_attributes(path);
+    let mutex = storage.mutex(&key).await?;
+    let _lock = mutex.lock().await?;
+    let mut attributes = match storage.get(&key).await? {
+        Some(attributes) => serde_json::from_slice(&attributes)
+            .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?,
+        None => serde_json::Map::new(),
+    };
+    super::storage_sync::merge_patch(&mut attributes, attributes_patch);
+    async_store_attributes_v2(storage, path, &attributes).await?;
+    Ok(attributes)
+}
+
+/// Store Zarr V2 `attributes` in `.zattrs`, which is erased if `attributes` is empty.
+async fn async_store_attributes_v2<TStorage: ?Sized + AsyncWritableStorageTraits>(
+    storage: &TStorage,
+    path: &NodePath,
+    attributes: &serde_json::Map<String, serde_json::Value>,
+) -> Result<(), StorageError> {
+    let key = meta_key_v2_attributes(path);
+    if attributes.is_empty() {
+        storage.erase(&key).await
+    } else {
+        let json = metadata_to_json(&key, attributes)?;
+        storage.set(&key, json.into()).await
+    }
+}
+
 /// Asynchronously store a chunk.
 ///
 /// # Errors
@@ -381,6 +614,25 @@ pub async fn async_store_chunk(
     Ok(())
 }
 
+/// Asynchronously store a chunk from a stream of its encoded bytes.
+///
+/// # Errors
+/// Returns a [`StorageError`] if `stream` yields an error or there is an underlying error with the store.
+pub async fn async_store_chunk_stream(
+    storage: &dyn AsyncWritableStorageTraits,
+    array_path: &NodePath,
+    chunk_grid_indices: &[u64],
+    chunk_key_encoding: &ChunkKeyEncoding,
+    stream: BoxStream<'_, Result<Bytes, StorageError>>,
+) -> Result<(), StorageError> {
+    storage
+        .set_stream(
+            &data_key(array_path, chunk_grid_indices, chunk_key_encoding),
+            stream,
+        )
+        .await
+}
+
 /// Asynchronously retrieve a chunk.
 ///
 /// # Errors
@@ -487,6 +739,107 @@ pub async fn async_erase_node(
     storage.erase_prefix(&prefix).await
 }
 
+/// Asynchronously move a node (group or array) and all of its children from `from` to `to`.
+///
+/// Each key of the node is copied to `to` and the node at `from` is erased.
+///
+/// # Errors
+/// Returns a [`StorageError`] if:
+///  - `from` or `to` is the root node, or `from` is `to` or an ancestor of `to`,
+///  - a node already exists at `to`, or
+///  - there is an underlying error with the store.
+pub async fn async_move_node<
+    TStorage: ?Sized + AsyncReadableStorageTraits + AsyncWritableStorageTraits + AsyncListableStorageTraits,
+>(
+    storage: &TStorage,
+    from: &NodePath,
+    to: &NodePath,
+) -> Result<(), StorageError> {
+    let (from_prefix, to_prefix) = move_node_prefixes(from, to)?;
+    if !storage.list_prefix(&to_prefix).await?.is_empty() {
+        return Err(StorageError::Other(format!(
+            "a node already exists at {to}"
+        )));
+    }
+    for key in storage.list_prefix(&from_prefix).await? {
+        if let Some(value) = storage.get(&key).await? {
+            storage
+                .set(&moved_key(&key, &from_prefix, &to_prefix)?, value)
+                .await?;
+        }
+    }
+    storage.erase_prefix(&from_prefix).await
+}
+
+/// Asynchronously synchronise the keys of `dst` with `src`, copying only keys which are missing or changed in `dst`.
+///
+/// See [`sync_stores`](super::sync_stores).
+///
+/// # Errors
+/// Returns a [`StorageError`] if there is an underlying error with either store.
+pub async fn async_sync_stores<
+    TSrc: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits,
+    TDst: ?Sized + AsyncReadableStorageTraits + AsyncWritableStorageTraits + AsyncListableStorageTraits,
+>(
+    src: &TSrc,
+    dst: &TDst,
+    options: &SyncStoresOptions,
+) -> Result<SyncStoresReport, StorageError> {
+    let src_keys = src.list_prefix(options.prefix()).await?;
+    let dst_keys: std::collections::HashSet<StoreKey> = dst
+        .list_prefix(options.prefix())
+        .await?
+        .into_iter()
+        .collect();
+    let keys_erased: StoreKeys = if options.erase_extraneous() {
+        let src_keys: std::collections::HashSet<&StoreKey> = src_keys.iter().collect();
+        dst_keys
+            .iter()
+            .filter(|key| !src_keys.contains(key))
+            .cloned()
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let copy = |key: StoreKey, value: MaybeBytes| async move {
+        if let Some(value) = value {
+            let bytes_copied = value.len() as u64;
+            dst.set(&key, value).await?;
+            Ok::<_, StorageError>((key, Some(bytes_copied)))
+        } else {
+            // The key was erased from the source
+            Ok((key, None))
+        }
+    };
+    let dst_keys = &dst_keys;
+    let sync_key = |key: StoreKey| async move {
+        if dst_keys.contains(&key) && src.size_key(&key).await? == dst.size_key(&key).await? {
+            if options.compare_values() {
+                let value = src.get(&key).await?;
+                if value == dst.get(&key).await? {
+                    Ok((key, None))
+                } else {
+                    copy(key, value).await
+                }
+            } else {
+                Ok((key, None))
+            }
+        } else {
+            let value = src.get(&key).await?;
+            copy(key, value).await
+        }
+    };
+    let mut stream = futures::stream::iter(src_keys.into_iter().map(sync_key))
+        .buffer_unordered(std::cmp::max(options.concurrent_target(), 1));
+    let mut keys = Vec::new();
+    while let Some(item) = stream.next().await {
+        keys.push(item?);
+    }
+    dst.erase_values(&keys_erased).await?;
+    Ok(SyncStoresReport::new(keys, keys_erased))
+}
+
 /// Asynchronously check if a node exists.
 ///
 /// # Errors
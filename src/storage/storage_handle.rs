@@ -1,12 +1,17 @@
 use std::sync::Arc;
 
+use bytes::Bytes;
+
 use crate::{array::MaybeBytes, byte_range::ByteRange};
 
 use super::{
-    store_lock::StoreKeyMutex, ListableStorageTraits, ReadableStorageTraits,
+    store_lock::StoreKeyMutex, GenerationToken, ListableStorageTraits, ReadableStorageTraits,
     ReadableWritableStorageTraits, StorageError, StoreKey, StorePrefix, WritableStorageTraits,
 };
 
+#[cfg(feature = "async")]
+use futures::stream::BoxStream;
+
 #[cfg(feature = "async")]
 use super::{
     store_lock::AsyncStoreKeyMutex, AsyncListableStorageTraits, AsyncReadableStorageTraits,
@@ -16,6 +21,8 @@ use super::{
 /// A storage handle.
 ///
 /// This is a handle to borrowed storage which can be owned and cloned, even if the storage it references is unsized.
+///
+/// If the `tracing` feature is enabled, reads and writes through a storage handle emit `trace` level events recording the key and the number of bytes read or written.
 #[derive(Clone)]
 pub struct StorageHandle<TStorage: ?Sized>(Arc<TStorage>);
 
@@ -27,23 +34,39 @@ impl<TStorage: ?Sized> StorageHandle<TStorage> {
 }
 
 impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits for StorageHandle<TStorage> {
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        self.0.get_with_generation(key)
+    }
+
     fn get(&self, key: &super::StoreKey) -> Result<MaybeBytes, super::StorageError> {
-        self.0.get(key)
+        let value = self.0.get(key);
+        #[cfg(feature = "tracing")]
+        trace_get(key, &value);
+        value
     }
 
     fn get_partial_values_key(
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
-        self.0.get_partial_values_key(key, byte_ranges)
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let values = self.0.get_partial_values_key(key, byte_ranges);
+        #[cfg(feature = "tracing")]
+        trace_get_partial_values_key(key, &values);
+        values
     }
 
     fn get_partial_values(
         &self,
         key_ranges: &[super::StoreKeyRange],
     ) -> Result<Vec<MaybeBytes>, StorageError> {
-        self.0.get_partial_values(key_ranges)
+        let values = self.0.get_partial_values(key_ranges);
+        #[cfg(feature = "tracing")]
+        trace_get_partial_values(&values);
+        values
     }
 
     fn size(&self) -> Result<u64, super::StorageError> {
@@ -80,7 +103,20 @@ impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits for Storage
 }
 
 impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits for StorageHandle<TStorage> {
+    fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: &[u8],
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%key, bytes_written = value.len(), "set_if_match");
+        self.0.set_if_match(key, value, generation)
+    }
+
     fn set(&self, key: &super::StoreKey, value: &[u8]) -> Result<(), super::StorageError> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%key, bytes_written = value.len(), "set");
         self.0.set(key, value)
     }
 
@@ -88,10 +124,14 @@ impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits for Storage
         &self,
         key_start_values: &[super::StoreKeyStartValue],
     ) -> Result<(), super::StorageError> {
+        #[cfg(feature = "tracing")]
+        trace_set_partial_values(key_start_values);
         self.0.set_partial_values(key_start_values)
     }
 
     fn erase(&self, key: &super::StoreKey) -> Result<(), super::StorageError> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%key, "erase");
         self.0.erase(key)
     }
 
@@ -117,23 +157,39 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits> ReadableWritableStorageTr
 impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
     for StorageHandle<TStorage>
 {
+    async fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        self.0.get_with_generation(key).await
+    }
+
     async fn get(&self, key: &super::StoreKey) -> Result<MaybeBytes, super::StorageError> {
-        self.0.get(key).await
+        let value = self.0.get(key).await;
+        #[cfg(feature = "tracing")]
+        trace_get(key, &value);
+        value
     }
 
     async fn get_partial_values_key(
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
-        self.0.get_partial_values_key(key, byte_ranges).await
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let values = self.0.get_partial_values_key(key, byte_ranges).await;
+        #[cfg(feature = "tracing")]
+        trace_get_partial_values_key(key, &values);
+        values
     }
 
     async fn get_partial_values(
         &self,
         key_ranges: &[super::StoreKeyRange],
     ) -> Result<Vec<MaybeBytes>, StorageError> {
-        self.0.get_partial_values(key_ranges).await
+        let values = self.0.get_partial_values(key_ranges).await;
+        #[cfg(feature = "tracing")]
+        trace_get_partial_values(&values);
+        values
     }
 
     async fn size_prefix(&self, prefix: &super::StorePrefix) -> Result<u64, super::StorageError> {
@@ -178,18 +234,45 @@ impl<TStorage: ?Sized + AsyncListableStorageTraits> AsyncListableStorageTraits
 impl<TStorage: ?Sized + AsyncWritableStorageTraits> AsyncWritableStorageTraits
     for StorageHandle<TStorage>
 {
+    async fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: Bytes,
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%key, bytes_written = value.len(), "set_if_match");
+        self.0.set_if_match(key, value, generation).await
+    }
+
     async fn set(&self, key: &StoreKey, value: bytes::Bytes) -> Result<(), StorageError> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%key, bytes_written = value.len(), "set");
         self.0.set(key, value).await
     }
 
+    async fn set_stream<'a>(
+        &self,
+        key: &StoreKey,
+        stream: BoxStream<'a, Result<Bytes, StorageError>>,
+    ) -> Result<(), StorageError> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%key, "set_stream");
+        self.0.set_stream(key, stream).await
+    }
+
     async fn set_partial_values(
         &self,
         key_start_values: &[super::StoreKeyStartValue],
     ) -> Result<(), super::StorageError> {
+        #[cfg(feature = "tracing")]
+        trace_set_partial_values(key_start_values);
         self.0.set_partial_values(key_start_values).await
     }
 
     async fn erase(&self, key: &super::StoreKey) -> Result<(), super::StorageError> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%key, "erase");
         self.0.erase(key).await
     }
 
@@ -211,3 +294,53 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits> AsyncReadableWritabl
         self.0.mutex(key).await
     }
 }
+
+#[cfg(feature = "tracing")]
+fn trace_get(key: &StoreKey, value: &Result<MaybeBytes, StorageError>) {
+    if let Ok(value) = value {
+        tracing::trace!(
+            %key,
+            bytes_read = value.as_ref().map_or(0, Bytes::len),
+            exists = value.is_some(),
+            "get"
+        );
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn trace_get_partial_values_key(key: &StoreKey, values: &Result<Option<Vec<Bytes>>, StorageError>) {
+    if let Ok(values) = values {
+        tracing::trace!(
+            %key,
+            bytes_read = values
+                .as_ref()
+                .map_or(0, |values| values.iter().map(Bytes::len).sum::<usize>()),
+            exists = values.is_some(),
+            "get_partial_values_key"
+        );
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn trace_get_partial_values(values: &Result<Vec<MaybeBytes>, StorageError>) {
+    if let Ok(values) = values {
+        tracing::trace!(
+            bytes_read = values
+                .iter()
+                .map(|value| value.as_ref().map_or(0, Bytes::len))
+                .sum::<usize>(),
+            "get_partial_values"
+        );
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn trace_set_partial_values(key_start_values: &[super::StoreKeyStartValue]) {
+    for key_start_value in key_start_values {
+        tracing::trace!(
+            key = %key_start_value.key,
+            bytes_written = key_start_value.value.len(),
+            "set_partial_values"
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! Storage traits shared by the sync and async APIs.
+//!
+//! Code written once against [`MaybeAsyncReadableStorageTraits`] and [`MaybeAsyncWritableStorageTraits`] as an `async fn` serves both APIs:
+//!  - the async API awaits it with [`AsyncStorage`], and
+//!  - the sync API runs it with [`SyncStorage`] and [`ready`], since the futures of sync storage are ready when they are created.
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+};
+
+use bytes::Bytes;
+
+use crate::array::MaybeBytes;
+
+use super::{ReadableStorageTraits, StorageError, StoreKey, WritableStorageTraits};
+
+#[cfg(feature = "async")]
+use std::pin::Pin;
+
+#[cfg(feature = "async")]
+use super::{AsyncReadableStorageTraits, AsyncWritableStorageTraits};
+
+/// Storage which may be sync or async.
+pub(crate) trait MaybeAsyncStorage: Send + Sync {
+    /// The future returned by a storage operation.
+    type Future<'a, T: Send + 'a>: Future<Output = T> + Send + 'a
+    where
+        Self: 'a;
+}
+
+/// Readable storage which may be sync or async.
+pub(crate) trait MaybeAsyncReadableStorageTraits: MaybeAsyncStorage {
+    /// Retrieve the value associated with `key`, or [`None`] if the key is not found.
+    fn get<'a>(&'a self, key: &'a StoreKey) -> Self::Future<'a, Result<MaybeBytes, StorageError>>;
+}
+
+/// Writable storage which may be sync or async.
+pub(crate) trait MaybeAsyncWritableStorageTraits: MaybeAsyncStorage {
+    /// Store `value` at `key`.
+    fn set<'a>(
+        &'a self,
+        key: &'a StoreKey,
+        value: Bytes,
+    ) -> Self::Future<'a, Result<(), StorageError>>;
+
+    /// Erase the value at `key`.
+    fn erase<'a>(&'a self, key: &'a StoreKey) -> Self::Future<'a, Result<(), StorageError>>;
+}
+
+/// Sync storage with [`MaybeAsyncStorage`] futures that are ready when they are created.
+pub(crate) struct SyncStorage<TStorage: ?Sized>(pub(crate) Arc<TStorage>);
+
+impl<TStorage: ?Sized + Send + Sync> MaybeAsyncStorage for SyncStorage<TStorage> {
+    type Future<'a, T: Send + 'a>
+        = std::future::Ready<T>
+    where
+        Self: 'a;
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> MaybeAsyncReadableStorageTraits
+    for SyncStorage<TStorage>
+{
+    fn get<'a>(&'a self, key: &'a StoreKey) -> Self::Future<'a, Result<MaybeBytes, StorageError>> {
+        std::future::ready(self.0.get(key))
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> MaybeAsyncWritableStorageTraits
+    for SyncStorage<TStorage>
+{
+    fn set<'a>(
+        &'a self,
+        key: &'a StoreKey,
+        value: Bytes,
+    ) -> Self::Future<'a, Result<(), StorageError>> {
+        std::future::ready(self.0.set(key, &value))
+    }
+
+    fn erase<'a>(&'a self, key: &'a StoreKey) -> Self::Future<'a, Result<(), StorageError>> {
+        std::future::ready(self.0.erase(key))
+    }
+}
+
+/// Async storage with [`MaybeAsyncStorage`] futures.
+#[cfg(feature = "async")]
+pub(crate) struct AsyncStorage<TStorage: ?Sized>(pub(crate) Arc<TStorage>);
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + Send + Sync> MaybeAsyncStorage for AsyncStorage<TStorage> {
+    type Future<'a, T: Send + 'a>
+        = Pin<Box<dyn Future<Output = T> + Send + 'a>>
+    where
+        Self: 'a;
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits> MaybeAsyncReadableStorageTraits
+    for AsyncStorage<TStorage>
+{
+    fn get<'a>(&'a self, key: &'a StoreKey) -> Self::Future<'a, Result<MaybeBytes, StorageError>> {
+        self.0.get(key)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncWritableStorageTraits> MaybeAsyncWritableStorageTraits
+    for AsyncStorage<TStorage>
+{
+    fn set<'a>(
+        &'a self,
+        key: &'a StoreKey,
+        value: Bytes,
+    ) -> Self::Future<'a, Result<(), StorageError>> {
+        self.0.set(key, value)
+    }
+
+    fn erase<'a>(&'a self, key: &'a StoreKey) -> Self::Future<'a, Result<(), StorageError>> {
+        self.0.erase(key)
+    }
+}
+
+struct NoopWake;
+
+impl Wake for NoopWake {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// Return the output of a `future` which is ready when it is first polled.
+///
+/// This runs code written against the [`MaybeAsyncStorage`] traits with [`SyncStorage`].
+///
+/// # Panics
+/// Panics if `future` is pending, which indicates that it awaited something other than [`SyncStorage`].
+pub(crate) fn ready<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(NoopWake));
+    let mut context = Context::from_waker(&waker);
+    match pin!(future).poll(&mut context) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("a sync storage future is pending"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::store::MemoryStore;
+
+    use super::*;
+
+    async fn copy<TStorage: MaybeAsyncReadableStorageTraits + MaybeAsyncWritableStorageTraits>(
+        storage: &TStorage,
+        from: &StoreKey,
+        to: &StoreKey,
+    ) -> Result<(), StorageError> {
+        if let Some(value) = storage.get(from).await? {
+            storage.set(to, value).await?;
+        }
+        storage.erase(from).await
+    }
+
+    #[test]
+    fn sync_storage() {
+        let store = Arc::new(MemoryStore::new());
+        let (a, b) = (StoreKey::new("a").unwrap(), StoreKey::new("b").unwrap());
+        store.set(&a, &[0, 1, 2]).unwrap();
+        ready(copy(&SyncStorage(store.clone()), &a, &b)).unwrap();
+        assert_eq!(store.get(&a).unwrap(), None);
+        assert_eq!(store.get(&b).unwrap(), Some(vec![0, 1, 2].into()));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn async_storage() {
+        let store = Arc::new(crate::storage::store::AsyncObjectStore::new(
+            object_store::memory::InMemory::new(),
+        ));
+        let (a, b) = (StoreKey::new("a").unwrap(), StoreKey::new("b").unwrap());
+        AsyncWritableStorageTraits::set(&*store, &a, vec![0, 1, 2].into())
+            .await
+            .unwrap();
+        copy(&AsyncStorage(store.clone()), &a, &b).await.unwrap();
+        assert_eq!(
+            AsyncReadableStorageTraits::get(&*store, &a).await.unwrap(),
+            None
+        );
+        assert_eq!(
+            AsyncReadableStorageTraits::get(&*store, &b).await.unwrap(),
+            Some(vec![0, 1, 2].into())
+        );
+    }
+}
@@ -1,15 +1,22 @@
+use std::collections::HashSet;
+
+use bytes::Bytes;
 use itertools::Itertools;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rayon_iter_concurrent_limit::iter_concurrent_limit;
 
 use crate::{
-    array::{ArrayMetadata, ChunkKeyEncoding, MaybeBytes},
+    array::{ArrayMetadata, ArrayMetadataV2, ChunkKeyEncoding, MaybeBytes},
     byte_range::ByteRange,
-    group::{GroupMetadata, GroupMetadataV3},
+    group::{GroupMetadata, GroupMetadataV2, GroupMetadataV3},
     node::{Node, NodeMetadata, NodePath},
 };
 
 use super::{
-    data_key, meta_key, store_lock::StoreKeyMutex, StorageError, StoreKey, StoreKeyRange,
-    StoreKeyStartValue, StoreKeys, StoreKeysPrefixes, StorePrefix, StorePrefixes,
+    data_key, meta_key, meta_key_v2_array, meta_key_v2_attributes, meta_key_v2_group,
+    metadata_from_json, metadata_to_json, move_node_prefixes, moved_key, store_lock::StoreKeyMutex,
+    GenerationToken, StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue, StoreKeys,
+    StoreKeysPrefixes, StorePrefix, StorePrefixes, SyncStoresOptions, SyncStoresReport,
 };
 
 /// Readable storage traits.
@@ -34,7 +41,7 @@ pub trait ReadableStorageTraits: Send + Sync {
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError>;
+    ) -> Result<Option<Vec<Bytes>>, StorageError>;
 
     /// Retrieve partial bytes from a list of [`StoreKeyRange`].
     ///
@@ -69,6 +76,20 @@ pub trait ReadableStorageTraits: Send + Sync {
     /// Returns a [`StorageError`] if there is an underlying storage error.
     fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError>;
 
+    /// Retrieve the value (bytes) associated with a given [`StoreKey`] and its [`GenerationToken`].
+    ///
+    /// Returns [`None`] if the key is not found.
+    /// The generation token is [`None`] if the store does not support conditional writes (see [`WritableStorageTraits::set_if_match`]), which is the default.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an error with the underlying store.
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        Ok(self.get(key)?.map(|value| (value, None)))
+    }
+
     /// Return the total size in bytes of the storage.
     ///
     /// # Errors
@@ -172,10 +193,10 @@ pub fn store_set_partial_values<T: ReadableWritableStorageTraits>(
         .try_for_each(|(key, group)| {
             // Lock the store key
             let mutex = store.mutex(&key)?;
-            let _lock = mutex.lock();
+            let _lock = mutex.lock()?;
 
             // Read the store key
-            let mut bytes = store.get(&key)?.unwrap_or_default();
+            let mut bytes: Vec<u8> = store.get(&key)?.map(Vec::from).unwrap_or_default();
 
             // Expand the store key if needed
             let end_max =
@@ -244,6 +265,37 @@ pub trait WritableStorageTraits: Send + Sync {
     /// # Errors
     /// Returns a [`StorageError`] is the prefix is not in the store, or the erase otherwise fails.
     fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError>;
+
+    /// Rename all [`StoreKey`] under the [`StorePrefix`] `from` to be under the [`StorePrefix`] `to`.
+    ///
+    /// This is an optional operation for stores that can natively rename keys (e.g. by renaming a directory).
+    /// Returns false without modifying the store if it is not supported.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an underlying storage error.
+    fn rename_prefix(&self, _from: &StorePrefix, _to: &StorePrefix) -> Result<bool, StorageError> {
+        Ok(false)
+    }
+
+    /// Store bytes at a [`StoreKey`] if the current value matches `generation`.
+    ///
+    /// If `generation` is [`None`], the value is only stored if the key does not exist.
+    /// Returns the [`GenerationToken`] of the stored value if it is known.
+    ///
+    /// This is an optional operation for stores that support conditional writes (e.g. object stores with `If-Match` preconditions).
+    ///
+    /// # Errors
+    /// Returns [`StorageError::PreconditionFailed`] if the current value does not match `generation`, [`StorageError::Unsupported`] if conditional writes are not supported, or a [`StorageError`] if there is an underlying storage error.
+    fn set_if_match(
+        &self,
+        _key: &StoreKey,
+        _value: &[u8],
+        _generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        Err(StorageError::Unsupported(
+            "the store does not support conditional writes".to_string(),
+        ))
+    }
 }
 
 /// A supertrait of [`ReadableStorageTraits`] and [`WritableStorageTraits`].
@@ -271,6 +323,45 @@ impl<T> ReadableWritableListableStorageTraits for T where
 {
 }
 
+/// Get the metadata of the node at `path`.
+///
+/// Zarr V2 metadata (`.zarray` or `.zgroup` and `.zattrs`) is read if `zarr.json` does not exist.
+/// The node is an implicit group with default metadata if no metadata exists.
+///
+/// # Errors
+/// Returns a [`StorageError`] if the metadata is invalid or there is an underlying error with the store.
+pub fn get_node_metadata<TStorage: ?Sized + ReadableStorageTraits>(
+    storage: &TStorage,
+    path: &NodePath,
+) -> Result<NodeMetadata, StorageError> {
+    let key = meta_key(path);
+    if let Some(metadata) = storage.get(&key)? {
+        return metadata_from_json(&key, &metadata);
+    }
+
+    let key = meta_key_v2_array(path);
+    if let Some(metadata) = storage.get(&key)? {
+        let mut metadata: ArrayMetadataV2 = metadata_from_json(&key, &metadata)?;
+        let key = meta_key_v2_attributes(path);
+        if let Some(attributes) = storage.get(&key)? {
+            metadata.attributes = metadata_from_json(&key, &attributes)?;
+        }
+        return Ok(NodeMetadata::Array(metadata.into()));
+    }
+
+    let key = meta_key_v2_group(path);
+    if let Some(metadata) = storage.get(&key)? {
+        let mut metadata: GroupMetadataV2 = metadata_from_json(&key, &metadata)?;
+        let key = meta_key_v2_attributes(path);
+        if let Some(attributes) = storage.get(&key)? {
+            metadata.attributes = metadata_from_json(&key, &attributes)?;
+        }
+        return Ok(NodeMetadata::Group(metadata.into()));
+    }
+
+    Ok(NodeMetadata::Group(GroupMetadataV3::default().into()))
+}
+
 /// Get the child nodes.
 ///
 /// # Errors
@@ -282,16 +373,8 @@ pub fn get_child_nodes<TStorage: ?Sized + ReadableStorageTraits + ListableStorag
     let prefixes = discover_children(storage, path)?;
     let mut nodes: Vec<Node> = Vec::new();
     for prefix in &prefixes {
-        let key = meta_key(&prefix.try_into()?);
-        let child_metadata = match storage.get(&key)? {
-            Some(child_metadata) => {
-                let metadata: NodeMetadata = serde_json::from_slice(child_metadata.as_slice())
-                    .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
-                metadata
-            }
-            None => NodeMetadata::Group(GroupMetadataV3::default().into()),
-        };
         let path: NodePath = prefix.try_into()?;
+        let child_metadata = get_node_metadata(storage, &path)?;
         let children = match child_metadata {
             NodeMetadata::Array(_) => Vec::default(),
             NodeMetadata::Group(_) => get_child_nodes(storage, &path)?,
@@ -303,6 +386,8 @@ pub fn get_child_nodes<TStorage: ?Sized + ReadableStorageTraits + ListableStorag
 
 /// Create a group.
 ///
+/// Zarr V2 group metadata is stored in `.zgroup` and `.zattrs`.
+///
 /// # Errors
 /// Returns a [`StorageError`] if there is an underlying error with the store.
 pub fn create_group(
@@ -310,11 +395,23 @@ pub fn create_group(
     path: &NodePath,
     group: &GroupMetadata,
 ) -> Result<(), StorageError> {
-    let key = meta_key(path);
-    let json = serde_json::to_vec_pretty(group)
-        .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
-    storage.set(&meta_key(path), &json)?;
-    Ok(())
+    match group {
+        GroupMetadata::V3(_) => {
+            let key = meta_key(path);
+            let json = metadata_to_json(&key, group)?;
+            storage.set(&key, &json)
+        }
+        GroupMetadata::V2(group) => {
+            let key = meta_key_v2_group(path);
+            let zgroup = GroupMetadataV2 {
+                attributes: serde_json::Map::new(),
+                ..group.clone()
+            };
+            let json = metadata_to_json(&key, &zgroup)?;
+            storage.set(&key, &json)?;
+            store_attributes_v2(storage, path, &group.attributes)
+        }
+    }
 }
 
 /// Create an array.
@@ -327,12 +424,205 @@ pub fn create_array(
     array: &ArrayMetadata,
 ) -> Result<(), StorageError> {
     let key = meta_key(path);
-    let json = serde_json::to_vec_pretty(array)
-        .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
+    let json = metadata_to_json(&key, array)?;
     storage.set(&key, &json)?;
     Ok(())
 }
 
+/// Create an array if the generation of its existing metadata matches `generation`.
+///
+/// If `generation` is [`None`], the array is only created if its metadata does not exist.
+/// Returns the generation of the stored metadata, if the store reports one.
+///
+/// # Errors
+/// Returns [`StorageError::PreconditionFailed`] if the metadata was modified concurrently,
+/// [`StorageError::Unsupported`] if the store does not support conditional writes,
+/// or a [`StorageError`] if there is an underlying error with the store.
+pub fn create_array_if_match(
+    storage: &dyn WritableStorageTraits,
+    path: &NodePath,
+    array: &ArrayMetadata,
+    generation: Option<&GenerationToken>,
+) -> Result<Option<GenerationToken>, StorageError> {
+    let key = meta_key(path);
+    let json = metadata_to_json(&key, array)?;
+    storage.set_if_match(&key, &json, generation)
+}
+
+/// Update the attributes in the metadata of the node at `path`.
+///
+/// Only the `attributes` field of the existing metadata is replaced, other fields are left unchanged.
+///
+/// # Errors
+/// Returns a [`StorageError`] if the node metadata does not exist or is invalid, or there is an underlying error with the store.
+pub fn update_attributes(
+    storage: &dyn ReadableWritableStorageTraits,
+    path: &NodePath,
+    attributes: &serde_json::Map<String, serde_json::Value>,
+) -> Result<(), StorageError> {
+    let key = meta_key(path);
+    let mutex = storage.mutex(&key)?;
+    let _lock = mutex.lock()?;
+    let Some(metadata) = storage.get(&key)? else {
+        // Zarr V2 attributes are stored separately in `.zattrs`
+        if storage.size_key(&meta_key_v2_array(path))?.is_some()
+            || storage.size_key(&meta_key_v2_group(path))?.is_some()
+        {
+            return store_attributes_v2(storage, path, attributes);
+        }
+        return Err(StorageError::InvalidMetadata(
+            key,
+            "the metadata does not exist".to_string(),
+        ));
+    };
+    let json = patch_attributes(&key, &metadata, attributes)?;
+    storage.set(&key, &json)
+}
+
+/// Merge `attributes_patch` into the attributes in the metadata of the node at `path`, and return the merged attributes.
+///
+/// The patch is applied with JSON merge patch semantics ([RFC 7386](https://www.rfc-editor.org/rfc/rfc7386)):
+/// objects are merged recursively, a `null` value removes the attribute, and other values replace the existing attribute.
+///
+/// The existing metadata is read and the merged metadata written while holding the lock of the metadata key.
+/// If the store supports conditional writes, the merged metadata is only written if the metadata was not modified since it was read, otherwise the merge is retried.
+/// This prevents concurrent writers updating different attributes from overwriting each other.
+///
+/// # Errors
+/// Returns a [`StorageError`] if the node metadata does not exist or is invalid, or there is an underlying error with the store.
+pub fn merge_attributes(
+    storage: &dyn ReadableWritableStorageTraits,
+    path: &NodePath,
+    attributes_patch: &serde_json::Map<String, serde_json::Value>,
+) -> Result<serde_json::Map<String, serde_json::Value>, StorageError> {
+    let key = meta_key(path);
+    let mutex = storage.mutex(&key)?;
+    let _lock = mutex.lock()?;
+    loop {
+        let Some((metadata, generation)) = storage.get_with_generation(&key)? else {
+            // Zarr V2 attributes are stored separately in `.zattrs`
+            if storage.size_key(&meta_key_v2_array(path))?.is_some()
+                || storage.size_key(&meta_key_v2_group(path))?.is_some()
+            {
+                return merge_attributes_v2(storage, path, attributes_patch);
+            }
+            return Err(StorageError::InvalidMetadata(
+                key,
+                "the metadata does not exist".to_string(),
+            ));
+        };
+        let mut attributes = metadata_attributes(&key, &metadata)?;
+        merge_patch(&mut attributes, attributes_patch);
+        let json = patch_attributes(&key, &metadata, &attributes)?;
+        if let Some(generation) = generation {
+            match storage.set_if_match(&key, &json, Some(&generation)) {
+                Ok(_) => return Ok(attributes),
+                Err(StorageError::PreconditionFailed(_)) => {}
+                Err(err) => return Err(err),
+            }
+        } else {
+            storage.set(&key, &json)?;
+            return Ok(attributes);
+        }
+    }
+}
+
+/// Merge `attributes_patch` into the Zarr V2 attributes in `.zattrs`, and return the merged attributes.
+fn merge_attributes_v2(
+    storage: &dyn ReadableWritableStorageTraits,
+    path: &NodePath,
+    attributes_patch: &serde_json::Map<String, serde_json::Value>,
+) -> Result<serde_json::Map<String, serde_json::Value>, StorageError> {
+    let key = meta_key_v2_attributes(path);
+    let mutex = storage.mutex(&key)?;
+    let _lock = mutex.lock()?;
+    let mut attributes = match storage.get(&key)? {
+        Some(attributes) => serde_json::from_slice(&attributes)
+            .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?,
+        None => serde_json::Map::new(),
+    };
+    merge_patch(&mut attributes, attributes_patch);
+    store_attributes_v2(storage, path, &attributes)?;
+    Ok(attributes)
+}
+
+/// Store Zarr V2 `attributes` in `.zattrs`, which is erased if `attributes` is empty.
+fn store_attributes_v2<TStorage: ?Sized + WritableStorageTraits>(
+    storage: &TStorage,
+    path: &NodePath,
+    attributes: &serde_json::Map<String, serde_json::Value>,
+) -> Result<(), StorageError> {
+    let key = meta_key_v2_attributes(path);
+    if attributes.is_empty() {
+        storage.erase(&key)
+    } else {
+        let json = metadata_to_json(&key, attributes)?;
+        storage.set(&key, &json)
+    }
+}
+
+/// Replace the `attributes` field of the serialised node metadata `metadata`.
+pub(crate) fn patch_attributes(
+    key: &StoreKey,
+    metadata: &[u8],
+    attributes: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<u8>, StorageError> {
+    let mut metadata: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(metadata)
+        .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
+    if attributes.is_empty() {
+        metadata.remove("attributes");
+    } else {
+        metadata.insert(
+            "attributes".to_string(),
+            serde_json::Value::Object(attributes.clone()),
+        );
+    }
+    metadata_to_json(key, &metadata)
+}
+
+/// Return the `attributes` field of the serialised node metadata `metadata`.
+pub(crate) fn metadata_attributes(
+    key: &StoreKey,
+    metadata: &[u8],
+) -> Result<serde_json::Map<String, serde_json::Value>, StorageError> {
+    let metadata: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(metadata)
+        .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
+    match metadata.get("attributes") {
+        Some(serde_json::Value::Object(attributes)) => Ok(attributes.clone()),
+        Some(_) => Err(StorageError::InvalidMetadata(
+            key.clone(),
+            "the attributes are not an object".to_string(),
+        )),
+        None => Ok(serde_json::Map::new()),
+    }
+}
+
+/// Merge `patch` into `target` with JSON merge patch semantics ([RFC 7386](https://www.rfc-editor.org/rfc/rfc7386)).
+pub(crate) fn merge_patch(
+    target: &mut serde_json::Map<String, serde_json::Value>,
+    patch: &serde_json::Map<String, serde_json::Value>,
+) {
+    for (name, value) in patch {
+        match value {
+            serde_json::Value::Null => {
+                target.remove(name);
+            }
+            serde_json::Value::Object(patch) => {
+                if let Some(serde_json::Value::Object(target)) = target.get_mut(name) {
+                    merge_patch(target, patch);
+                } else {
+                    let mut value = serde_json::Map::new();
+                    merge_patch(&mut value, patch);
+                    target.insert(name.clone(), serde_json::Value::Object(value));
+                }
+            }
+            value => {
+                target.insert(name.clone(), value.clone());
+            }
+        }
+    }
+}
+
 /// Store a chunk.
 ///
 /// # Errors
@@ -450,6 +740,110 @@ pub fn erase_node(
     storage.erase_prefix(&prefix)
 }
 
+/// Move a node (group or array) and all of its children from `from` to `to`.
+///
+/// The keys of the node are renamed with [`WritableStorageTraits::rename_prefix`] if supported by the store (e.g. [`FilesystemStore`](crate::storage::store::FilesystemStore)).
+/// Otherwise, each key of the node is copied to `to` and the node at `from` is erased.
+///
+/// # Errors
+/// Returns a [`StorageError`] if:
+///  - `from` or `to` is the root node, or `from` is `to` or an ancestor of `to`,
+///  - a node already exists at `to`, or
+///  - there is an underlying error with the store.
+pub fn move_node<
+    TStorage: ?Sized + ReadableStorageTraits + WritableStorageTraits + ListableStorageTraits,
+>(
+    storage: &TStorage,
+    from: &NodePath,
+    to: &NodePath,
+) -> Result<(), StorageError> {
+    let (from_prefix, to_prefix) = move_node_prefixes(from, to)?;
+    if !storage.list_prefix(&to_prefix)?.is_empty() {
+        return Err(StorageError::Other(format!(
+            "a node already exists at {to}"
+        )));
+    }
+    if storage.rename_prefix(&from_prefix, &to_prefix)? {
+        return Ok(());
+    }
+    for key in storage.list_prefix(&from_prefix)? {
+        if let Some(value) = storage.get(&key)? {
+            storage.set(&moved_key(&key, &from_prefix, &to_prefix)?, &value)?;
+        }
+    }
+    storage.erase_prefix(&from_prefix)
+}
+
+/// Synchronise the keys of `dst` with `src`, copying only keys which are missing or changed in `dst`.
+///
+/// The keys under [`SyncStoresOptions::prefix`] are listed in both stores.
+/// A key in both stores is considered changed if its size differs, or if its value differs and [`SyncStoresOptions::compare_values`] is enabled.
+/// Keys are compared and copied in parallel, subject to [`SyncStoresOptions::concurrent_target`].
+/// Keys in `dst` which are not in `src` are erased if [`SyncStoresOptions::erase_extraneous`] is enabled.
+///
+/// This can be used to incrementally mirror a hierarchy, such as a growing array, from one store to another (e.g. from a filesystem to an object store).
+///
+/// # Errors
+/// Returns a [`StorageError`] if there is an underlying error with either store.
+pub fn sync_stores<
+    TSrc: ?Sized + ReadableStorageTraits + ListableStorageTraits,
+    TDst: ?Sized + ReadableStorageTraits + WritableStorageTraits + ListableStorageTraits,
+>(
+    src: &TSrc,
+    dst: &TDst,
+    options: &SyncStoresOptions,
+) -> Result<SyncStoresReport, StorageError> {
+    let src_keys = src.list_prefix(options.prefix())?;
+    let dst_keys: HashSet<StoreKey> = dst.list_prefix(options.prefix())?.into_iter().collect();
+    let keys_erased = if options.erase_extraneous() {
+        let src_keys: HashSet<&StoreKey> = src_keys.iter().collect();
+        dst_keys
+            .iter()
+            .filter(|key| !src_keys.contains(key))
+            .cloned()
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let copy = |key: &StoreKey, value: MaybeBytes| -> Result<Option<u64>, StorageError> {
+        if let Some(value) = value {
+            dst.set(key, &value)?;
+            Ok(Some(value.len() as u64))
+        } else {
+            // The key was erased from the source
+            Ok(None)
+        }
+    };
+    let sync_key = |key: StoreKey| -> Result<(StoreKey, Option<u64>), StorageError> {
+        let bytes_copied =
+            if dst_keys.contains(&key) && src.size_key(&key)? == dst.size_key(&key)? {
+                if options.compare_values() {
+                    let value = src.get(&key)?;
+                    if value == dst.get(&key)? {
+                        None
+                    } else {
+                        copy(&key, value)?
+                    }
+                } else {
+                    None
+                }
+            } else {
+                copy(&key, src.get(&key)?)?
+            };
+        Ok((key, bytes_copied))
+    };
+    let keys = iter_concurrent_limit!(
+        std::cmp::max(options.concurrent_target(), 1),
+        src_keys.into_par_iter(),
+        map,
+        sync_key
+    )
+    .collect::<Result<Vec<_>, _>>()?;
+    dst.erase_values(&keys_erased)?;
+    Ok(SyncStoresReport::new(keys, keys_erased))
+}
+
 /// Check if a node exists.
 ///
 /// # Errors
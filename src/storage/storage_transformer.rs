@@ -1,14 +1,23 @@
-//! Zarr storage transformers. Includes [performance metrics](performance_metrics::PerformanceMetricsStorageTransformer) and [usage log](usage_log::UsageLogStorageTransformer) implementations for internal use.
+//! Zarr storage transformers. Includes [performance metrics](performance_metrics::PerformanceMetricsStorageTransformer), [usage log](usage_log::UsageLogStorageTransformer), [quota](quota::QuotaStorageTransformer), [disk cache](disk_cache::DiskCacheStorageTransformer), and [versioning](versioning::VersioningStorageTransformer) implementations for internal use.
 //!
 //! See <https://zarr-specs.readthedocs.io/en/latest/v3/core/v3.0.html#id23>.
 
+mod disk_cache;
 mod performance_metrics;
+mod quota;
 mod storage_transformer_chain;
 mod usage_log;
+mod versioning;
 
-pub use performance_metrics::PerformanceMetricsStorageTransformer;
+pub use disk_cache::DiskCacheStorageTransformer;
+pub use performance_metrics::{
+    LatencyHistogram, PerformanceMetricsOperationReport, PerformanceMetricsReport,
+    PerformanceMetricsStorageTransformer, StorageOperation,
+};
+pub use quota::{QuotaStorageTransformer, StorageQuota, StorageQuotaUsage};
 pub use storage_transformer_chain::StorageTransformerChain;
 pub use usage_log::UsageLogStorageTransformer;
+pub use versioning::{VersioningStorageTransformer, VERSIONING_MANIFEST_KEY};
 
 use std::sync::Arc;
 
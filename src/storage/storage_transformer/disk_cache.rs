@@ -0,0 +1,770 @@
+//! A storage transformer which caches retrieved values on a local disk.
+
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use bytes::Bytes;
+use parking_lot::Mutex;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::{extract_byte_ranges_bytes, ByteRange},
+    metadata::Metadata,
+    storage::{
+        store::{FilesystemStore, FilesystemStoreCreateError},
+        store_lock::StoreKeyMutex,
+        GenerationToken, ListableStorage, ListableStorageTraits, ReadableListableStorage,
+        ReadableStorage, ReadableStorageTraits, ReadableWritableListableStorage,
+        ReadableWritableStorage, ReadableWritableStorageTraits, StorageError, StoreKey,
+        StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes, StorePrefix,
+        WritableStorage, WritableStorageTraits,
+    },
+};
+
+#[cfg(feature = "async")]
+use crate::storage::{
+    store_lock::AsyncStoreKeyMutex, AsyncListableStorage, AsyncListableStorageTraits,
+    AsyncReadableListableStorage, AsyncReadableStorage, AsyncReadableStorageTraits,
+    AsyncReadableWritableListableStorage, AsyncReadableWritableStorageTraits, AsyncWritableStorage,
+    AsyncWritableStorageTraits,
+};
+
+use super::StorageTransformerExtension;
+
+#[derive(Debug)]
+struct DiskCacheEntry {
+    size: u64,
+    cached: SystemTime,
+    last_access: u64,
+}
+
+#[derive(Debug, Default)]
+struct DiskCacheState {
+    entries: HashMap<StoreKey, DiskCacheEntry>,
+    size: u64,
+    access_counter: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl DiskCacheState {
+    fn remove(&mut self, key: &StoreKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.size -= entry.size;
+        }
+    }
+}
+
+/// The disk cache storage transformer. Caches values retrieved from slow (e.g. remote) storage in a local directory.
+///
+/// This storage transformer is for internal use and will not to be included in `storage_transformers` array metadata.
+///
+/// Retrieved values are mirrored into a [`FilesystemStore`] at the cache directory, and subsequent retrievals of the same key are served from the cache.
+///  - Partially retrieving a value which is not cached retrieves and caches the entire value.
+///  - Cached values older than the time to live (TTL) are retrieved again from the underlying storage.
+///  - If the size of the cache exceeds the maximum size, the least recently used values are evicted.
+///  - Writes and erases are passed through to the underlying storage and invalidate the affected cached values.
+///
+/// Values cached in the directory by a previous disk cache storage transformer are reused, with their age determined by their file modification time.
+/// The cache directory is accessed synchronously, even through the asynchronous storage traits.
+#[derive(Debug)]
+pub struct DiskCacheStorageTransformer {
+    cache: FilesystemStore,
+    ttl: Option<Duration>,
+    max_size: Option<u64>,
+    state: Mutex<DiskCacheState>,
+}
+
+impl DiskCacheStorageTransformer {
+    /// Create a new disk cache storage transformer with a cache directory at `path`.
+    ///
+    /// Cached values do not expire and the cache size is unbounded by default.
+    ///
+    /// # Errors
+    /// Returns a [`FilesystemStoreCreateError`] if the cache directory is not valid, or existing values in the cache directory cannot be indexed.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, FilesystemStoreCreateError> {
+        let cache = FilesystemStore::new(&path)?;
+        let mut state = DiskCacheState::default();
+        if path.as_ref().exists() {
+            let keys = cache.list().map_err(|err| {
+                FilesystemStoreCreateError::IOError(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    err.to_string(),
+                ))
+            })?;
+            for key in keys {
+                let metadata = std::fs::metadata(cache.key_to_fspath(&key))?;
+                state.size += metadata.len();
+                state.entries.insert(
+                    key,
+                    DiskCacheEntry {
+                        size: metadata.len(),
+                        cached: metadata.modified()?,
+                        last_access: 0,
+                    },
+                );
+            }
+        }
+        Ok(Self {
+            cache,
+            ttl: None,
+            max_size: None,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Set the time to live of cached values.
+    ///
+    /// If [`None`], cached values do not expire.
+    pub fn set_ttl(&mut self, ttl: Option<Duration>) {
+        self.ttl = ttl;
+    }
+
+    /// Set the maximum size in bytes of the cache.
+    ///
+    /// If [`None`], the cache size is unbounded.
+    /// Values larger than the maximum size are not cached.
+    pub fn set_max_size(&mut self, max_size: Option<u64>) {
+        self.max_size = max_size;
+    }
+
+    /// Returns the time to live of cached values.
+    #[must_use]
+    pub const fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// Returns the maximum size in bytes of the cache.
+    #[must_use]
+    pub const fn max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+
+    /// Returns the total size in bytes of the cached values.
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.state.lock().size
+    }
+
+    /// Returns the number of cached values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Returns true if there are no cached values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.state.lock().entries.is_empty()
+    }
+
+    /// Returns the number of retrievals served from the cache.
+    #[must_use]
+    pub fn hits(&self) -> usize {
+        self.state.lock().hits
+    }
+
+    /// Returns the number of retrievals not served from the cache.
+    #[must_use]
+    pub fn misses(&self) -> usize {
+        self.state.lock().misses
+    }
+
+    /// Erase all cached values.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the cache directory cannot be cleared.
+    pub fn clear(&self) -> Result<(), StorageError> {
+        let mut state = self.state.lock();
+        self.cache.erase_prefix(&StorePrefix::root())?;
+        state.entries.clear();
+        state.size = 0;
+        Ok(())
+    }
+
+    fn create_transformer<TStorage: ?Sized + 'static>(
+        self: Arc<Self>,
+        storage: Arc<TStorage>,
+    ) -> Arc<DiskCacheStorageTransformerImpl<TStorage>> {
+        Arc::new(DiskCacheStorageTransformerImpl {
+            storage,
+            transformer: self,
+        })
+    }
+
+    /// Returns true if a cached value is older than the time to live.
+    fn expired(&self, entry: &DiskCacheEntry) -> bool {
+        self.ttl
+            .is_some_and(|ttl| entry.cached.elapsed().is_ok_and(|elapsed| elapsed > ttl))
+    }
+
+    /// Retrieve the value of `key` from the cache if it is cached and has not expired.
+    ///
+    /// Expired values are evicted. Records a cache hit or miss.
+    ///
+    /// The state is only locked to look up and update the entry of `key`, not while accessing the cache directory.
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        let expired = {
+            let mut state = self.state.lock();
+            match state.entries.get(key) {
+                Some(entry) if self.expired(entry) => {
+                    state.remove(key);
+                    state.misses += 1;
+                    true
+                }
+                Some(_) => false,
+                None => {
+                    state.misses += 1;
+                    return Ok(None);
+                }
+            }
+        };
+        if expired {
+            self.cache.erase(key)?;
+            return Ok(None);
+        }
+
+        let value = self.cache.get(key)?;
+        let mut state = self.state.lock();
+        if value.is_some() {
+            state.access_counter += 1;
+            let access_counter = state.access_counter;
+            if let Some(entry) = state.entries.get_mut(key) {
+                entry.last_access = access_counter;
+            }
+            state.hits += 1;
+        } else {
+            // The cached value was removed externally
+            state.remove(key);
+            state.misses += 1;
+        }
+        Ok(value)
+    }
+
+    /// Cache `value` at `key`, evicting the least recently used values if the cache exceeds its maximum size.
+    fn insert(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        let size = value.len() as u64;
+        if self.max_size.is_some_and(|max_size| size > max_size) {
+            return Ok(());
+        }
+        self.cache.set(key, value)?;
+
+        let mut state = self.state.lock();
+        state.remove(key);
+        state.access_counter += 1;
+        let last_access = state.access_counter;
+        state.entries.insert(
+            key.clone(),
+            DiskCacheEntry {
+                size,
+                cached: SystemTime::now(),
+                last_access,
+            },
+        );
+        state.size += size;
+        let mut evicted = Vec::new();
+        if let Some(max_size) = self.max_size {
+            while state.size > max_size {
+                let Some(lru_key) = state
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_access)
+                    .map(|(key, _)| key.clone())
+                else {
+                    break;
+                };
+                state.remove(&lru_key);
+                evicted.push(lru_key);
+            }
+        }
+        drop(state);
+
+        for key in &evicted {
+            self.cache.erase(key)?;
+        }
+        Ok(())
+    }
+
+    /// Invalidate the cached values of the keys matching `invalidated`.
+    fn invalidate(&self, invalidated: impl Fn(&StoreKey) -> bool) -> Result<(), StorageError> {
+        let keys: Vec<StoreKey> = {
+            let mut state = self.state.lock();
+            let keys: Vec<StoreKey> = state
+                .entries
+                .keys()
+                .filter(|key| invalidated(key))
+                .cloned()
+                .collect();
+            for key in &keys {
+                state.remove(key);
+            }
+            keys
+        };
+        for key in &keys {
+            self.cache.erase(key)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the size of the value of `key` if it is cached and has not expired.
+    fn size_key(&self, key: &StoreKey) -> Option<u64> {
+        let state = self.state.lock();
+        state
+            .entries
+            .get(key)
+            .filter(|entry| !self.expired(entry))
+            .map(|entry| entry.size)
+    }
+}
+
+impl StorageTransformerExtension for DiskCacheStorageTransformer {
+    /// Returns [`None`], since this storage transformer is not intended to be included in array `storage_transformers` metadata.
+    fn create_metadata(&self) -> Option<Metadata> {
+        None
+    }
+
+    fn create_readable_transformer(self: Arc<Self>, storage: ReadableStorage) -> ReadableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_writable_transformer(self: Arc<Self>, storage: WritableStorage) -> WritableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_readable_writable_transformer(
+        self: Arc<Self>,
+        storage: ReadableWritableStorage,
+    ) -> ReadableWritableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_listable_transformer(self: Arc<Self>, storage: ListableStorage) -> ListableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_readable_listable_transformer(
+        self: Arc<Self>,
+        storage: ReadableListableStorage,
+    ) -> ReadableListableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_readable_writable_listable_transformer(
+        self: Arc<Self>,
+        storage: ReadableWritableListableStorage,
+    ) -> ReadableWritableListableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_readable_transformer(
+        self: Arc<Self>,
+        storage: AsyncReadableStorage,
+    ) -> AsyncReadableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_writable_transformer(
+        self: Arc<Self>,
+        storage: AsyncWritableStorage,
+    ) -> AsyncWritableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_listable_transformer(
+        self: Arc<Self>,
+        storage: AsyncListableStorage,
+    ) -> AsyncListableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_readable_listable_transformer(
+        self: Arc<Self>,
+        storage: AsyncReadableListableStorage,
+    ) -> AsyncReadableListableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_readable_writable_listable_transformer(
+        self: Arc<Self>,
+        storage: AsyncReadableWritableListableStorage,
+    ) -> AsyncReadableWritableListableStorage {
+        self.create_transformer(storage)
+    }
+}
+
+#[derive(Debug)]
+struct DiskCacheStorageTransformerImpl<TStorage: ?Sized + 'static> {
+    storage: Arc<TStorage>,
+    transformer: Arc<DiskCacheStorageTransformer>,
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for DiskCacheStorageTransformerImpl<TStorage>
+{
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        // Generation tokens must reflect the underlying storage, so the cache is bypassed
+        self.storage.get_with_generation(key)
+    }
+
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        if let Some(value) = self.transformer.get(key)? {
+            return Ok(Some(value));
+        }
+        let value = self.storage.get(key)?;
+        if let Some(value) = &value {
+            self.transformer.insert(key, value)?;
+        }
+        Ok(value)
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        self.get(key)?
+            .map(|value| extract_byte_ranges_bytes(&value, byte_ranges))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges)
+    }
+
+    fn size(&self) -> Result<u64, StorageError> {
+        self.storage.size()
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.storage.size_prefix(prefix)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        if let Some(size) = self.transformer.size_key(key) {
+            Ok(Some(size))
+        } else {
+            self.storage.size_key(key)
+        }
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
+    for DiskCacheStorageTransformerImpl<TStorage>
+{
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.storage.list()
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.storage.list_prefix(prefix)
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.storage.list_dir(prefix)
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits
+    for DiskCacheStorageTransformerImpl<TStorage>
+{
+    fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: &[u8],
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        self.transformer
+            .invalidate(|invalidated| invalidated == key)?;
+        self.storage.set_if_match(key, value, generation)
+    }
+
+    fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        self.transformer
+            .invalidate(|invalidated| invalidated == key)?;
+        self.storage.set(key, value)
+    }
+
+    fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        self.transformer.invalidate(|invalidated| {
+            key_start_values
+                .iter()
+                .any(|key_start_value| &key_start_value.key == invalidated)
+        })?;
+        self.storage.set_partial_values(key_start_values)
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.transformer
+            .invalidate(|invalidated| invalidated == key)?;
+        self.storage.erase(key)
+    }
+
+    fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.transformer
+            .invalidate(|invalidated| keys.contains(invalidated))?;
+        self.storage.erase_values(keys)
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.transformer
+            .invalidate(|invalidated| invalidated.has_prefix(prefix))?;
+        self.storage.erase_prefix(prefix)
+    }
+}
+
+impl<TStorage: ?Sized + ReadableWritableStorageTraits> ReadableWritableStorageTraits
+    for DiskCacheStorageTransformerImpl<TStorage>
+{
+    fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+        self.storage.mutex(key)
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
+    for DiskCacheStorageTransformerImpl<TStorage>
+{
+    async fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        // Generation tokens must reflect the underlying storage, so the cache is bypassed
+        self.storage.get_with_generation(key).await
+    }
+
+    async fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        if let Some(value) = self.transformer.get(key)? {
+            return Ok(Some(value));
+        }
+        let value = self.storage.get(key).await?;
+        if let Some(value) = &value {
+            self.transformer.insert(key, value)?;
+        }
+        Ok(value)
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        AsyncReadableStorageTraits::get(self, key)
+            .await?
+            .map(|value| extract_byte_ranges_bytes(&value, byte_ranges))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges).await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.storage.size().await
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.storage.size_prefix(prefix).await
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        if let Some(size) = self.transformer.size_key(key) {
+            Ok(Some(size))
+        } else {
+            self.storage.size_key(key).await
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncListableStorageTraits> AsyncListableStorageTraits
+    for DiskCacheStorageTransformerImpl<TStorage>
+{
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.storage.list().await
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.storage.list_prefix(prefix).await
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.storage.list_dir(prefix).await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncWritableStorageTraits> AsyncWritableStorageTraits
+    for DiskCacheStorageTransformerImpl<TStorage>
+{
+    async fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: Bytes,
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        self.transformer
+            .invalidate(|invalidated| invalidated == key)?;
+        self.storage.set_if_match(key, value, generation).await
+    }
+
+    async fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), StorageError> {
+        self.transformer
+            .invalidate(|invalidated| invalidated == key)?;
+        self.storage.set(key, value).await
+    }
+
+    async fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        self.transformer.invalidate(|invalidated| {
+            key_start_values
+                .iter()
+                .any(|key_start_value| &key_start_value.key == invalidated)
+        })?;
+        self.storage.set_partial_values(key_start_values).await
+    }
+
+    async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.transformer
+            .invalidate(|invalidated| invalidated == key)?;
+        self.storage.erase(key).await
+    }
+
+    async fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.transformer
+            .invalidate(|invalidated| keys.contains(invalidated))?;
+        self.storage.erase_values(keys).await
+    }
+
+    async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.transformer
+            .invalidate(|invalidated| invalidated.has_prefix(prefix))?;
+        self.storage.erase_prefix(prefix).await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits> AsyncReadableWritableStorageTraits
+    for DiskCacheStorageTransformerImpl<TStorage>
+{
+    async fn mutex(&self, key: &StoreKey) -> Result<AsyncStoreKeyMutex, StorageError> {
+        self.storage.mutex(key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::store::MemoryStore;
+
+    use super::*;
+
+    #[test]
+    fn disk_cache_storage_transformer() {
+        let path = tempfile::TempDir::new().unwrap();
+        let store = Arc::new(MemoryStore::new());
+        let mut cache = DiskCacheStorageTransformer::new(path.path()).unwrap();
+        cache.set_max_size(Some(10));
+        let cache = Arc::new(cache);
+        let storage = cache
+            .clone()
+            .create_readable_writable_transformer(store.clone());
+
+        let key_a = StoreKey::new("a").unwrap();
+        let key_b = StoreKey::new("b/c").unwrap();
+        store.set(&key_a, &[0, 1, 2, 3]).unwrap();
+        store.set(&key_b, &[4, 5, 6, 7, 8, 9]).unwrap();
+
+        // Retrieved values are cached
+        assert_eq!(storage.get(&key_a).unwrap().unwrap(), vec![0, 1, 2, 3]);
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+        assert_eq!(
+            storage
+                .get_partial_values_key(&key_a, &[ByteRange::FromEnd(0, Some(2))])
+                .unwrap()
+                .unwrap(),
+            vec![vec![2, 3]]
+        );
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        store.set(&key_a, &[0]).unwrap();
+        assert_eq!(storage.get(&key_a).unwrap().unwrap(), vec![0, 1, 2, 3]);
+        assert_eq!(storage.size_key(&key_a).unwrap(), Some(4));
+
+        // The least recently used value is evicted if the maximum size is exceeded
+        assert_eq!(
+            storage
+                .get_partial_values_key(&key_b, &[ByteRange::FromStart(1, Some(2))])
+                .unwrap()
+                .unwrap(),
+            vec![vec![5, 6]]
+        );
+        assert_eq!((cache.len(), cache.size()), (2, 10));
+        let key_c = StoreKey::new("c").unwrap();
+        store.set(&key_c, &[10, 11, 12]).unwrap();
+        assert_eq!(storage.get(&key_c).unwrap().unwrap(), vec![10, 11, 12]);
+        assert_eq!((cache.len(), cache.size()), (2, 9));
+        assert_eq!(storage.get(&key_a).unwrap().unwrap(), vec![0]);
+        assert_eq!((cache.len(), cache.size()), (3, 10));
+
+        // Writes and erases invalidate cached values
+        storage.set(&key_b, &[1, 2]).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(storage.get(&key_b).unwrap().unwrap(), vec![1, 2]);
+        storage
+            .erase_prefix(&StorePrefix::new("b/").unwrap())
+            .unwrap();
+        assert!(storage.get(&key_b).unwrap().is_none());
+        assert_eq!((cache.len(), cache.size()), (2, 4));
+
+        // Values cached in the cache directory are reused
+        let cache = DiskCacheStorageTransformer::new(path.path()).unwrap();
+        assert_eq!((cache.len(), cache.size()), (2, 4));
+        cache.clear().unwrap();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn disk_cache_storage_transformer_ttl() {
+        let path = tempfile::TempDir::new().unwrap();
+        let store = Arc::new(MemoryStore::new());
+        let mut cache = DiskCacheStorageTransformer::new(path.path()).unwrap();
+        cache.set_ttl(Some(Duration::ZERO));
+        let cache = Arc::new(cache);
+        let storage = cache.clone().create_readable_transformer(store.clone());
+
+        let key = StoreKey::new("a").unwrap();
+        store.set(&key, &[0, 1]).unwrap();
+        assert_eq!(storage.get(&key).unwrap().unwrap(), vec![0, 1]);
+        std::thread::sleep(Duration::from_millis(10));
+        store.set(&key, &[2]).unwrap();
+        assert_eq!(storage.get(&key).unwrap().unwrap(), vec![2]);
+        assert_eq!((cache.hits(), cache.misses()), (0, 2));
+    }
+}
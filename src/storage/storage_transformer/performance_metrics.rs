@@ -1,14 +1,18 @@
 //! A storage transformer which records performance metrics.
 
+use bytes::Bytes;
+use parking_lot::Mutex;
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
 use crate::{
     array::MaybeBytes,
     metadata::Metadata,
     storage::{
-        store_lock::StoreKeyMutex, ListableStorage, ListableStorageTraits, ReadableListableStorage,
-        ReadableStorage, ReadableStorageTraits, ReadableWritableListableStorage,
-        ReadableWritableStorage, ReadableWritableStorageTraits, StorageError, StoreKey,
-        StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes, StorePrefix,
-        WritableStorage, WritableStorageTraits,
+        store_lock::StoreKeyMutex, GenerationToken, ListableStorage, ListableStorageTraits,
+        ReadableListableStorage, ReadableStorage, ReadableStorageTraits,
+        ReadableWritableListableStorage, ReadableWritableStorage, ReadableWritableStorageTraits,
+        StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes,
+        StorePrefix, WritableStorage, WritableStorageTraits,
     },
 };
 
@@ -20,17 +24,305 @@ use crate::storage::{
     AsyncWritableStorageTraits,
 };
 
-use std::sync::{
-    atomic::{AtomicUsize, Ordering},
-    Arc,
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
 use super::StorageTransformerExtension;
 
+/// The number of finite buckets of a [`LatencyHistogram`].
+const LATENCY_HISTOGRAM_BUCKETS: usize = 25;
+
+/// A storage operation recorded by the [`PerformanceMetricsStorageTransformer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageOperation {
+    /// Retrieving a value.
+    Get,
+    /// Retrieving byte ranges of values.
+    GetPartialValues,
+    /// Retrieving the size of a key or prefix.
+    Size,
+    /// Listing keys or prefixes.
+    List,
+    /// Storing a value.
+    Set,
+    /// Storing byte ranges of values.
+    SetPartialValues,
+    /// Erasing values.
+    Erase,
+    /// Requesting a store key mutex.
+    Lock,
+}
+
+impl StorageOperation {
+    /// Return the name of the operation.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Get => "get",
+            Self::GetPartialValues => "get_partial_values",
+            Self::Size => "size",
+            Self::List => "list",
+            Self::Set => "set",
+            Self::SetPartialValues => "set_partial_values",
+            Self::Erase => "erase",
+            Self::Lock => "lock",
+        }
+    }
+}
+
+/// A histogram of operation latencies.
+///
+/// The bucket upper bounds are powers of two microseconds from 1µs to 2<sup>24</sup>µs (about 16.8s), followed by an unbounded bucket.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    counts: [u64; LATENCY_HISTOGRAM_BUCKETS + 1],
+    sum: Duration,
+    max: Duration,
+}
+
+impl LatencyHistogram {
+    /// Return the upper bound of the bucket at `index`, or [`None`] for the unbounded bucket.
+    fn bucket_upper_bound(index: usize) -> Option<Duration> {
+        (index < LATENCY_HISTOGRAM_BUCKETS).then(|| Duration::from_micros(1 << index))
+    }
+
+    fn record(&mut self, latency: Duration) {
+        let index = (0..LATENCY_HISTOGRAM_BUCKETS)
+            .find(|&index| latency <= Duration::from_micros(1 << index))
+            .unwrap_or(LATENCY_HISTOGRAM_BUCKETS);
+        self.counts[index] += 1;
+        self.sum += latency;
+        self.max = self.max.max(latency);
+    }
+
+    /// Return the number of recorded latencies.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Return the sum of the recorded latencies.
+    #[must_use]
+    pub const fn sum(&self) -> Duration {
+        self.sum
+    }
+
+    /// Return the maximum recorded latency.
+    #[must_use]
+    pub const fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Return the mean recorded latency, or [`None`] if no latencies are recorded.
+    #[must_use]
+    pub fn mean(&self) -> Option<Duration> {
+        let count = u32::try_from(self.count()).ok()?;
+        (count > 0).then(|| self.sum / count)
+    }
+
+    /// Return an upper bound of the `quantile` (e.g. `0.99`) of the recorded latencies, or [`None`] if no latencies are recorded.
+    ///
+    /// The upper bound is the upper bound of the bucket containing the quantile, limited to the maximum recorded latency.
+    #[must_use]
+    pub fn quantile(&self, quantile: f64) -> Option<Duration> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let rank = ((quantile.clamp(0.0, 1.0) * count as f64).ceil() as u64).max(1);
+        let mut cumulative = 0;
+        for (index, bucket_count) in self.counts.iter().enumerate() {
+            cumulative += bucket_count;
+            if cumulative >= rank {
+                return Some(
+                    Self::bucket_upper_bound(index).map_or(self.max, |bound| bound.min(self.max)),
+                );
+            }
+        }
+        Some(self.max)
+    }
+
+    /// Return the upper bound and count of each bucket.
+    ///
+    /// The upper bound of the last bucket is [`None`] (unbounded).
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(index, count)| (Self::bucket_upper_bound(index), *count))
+    }
+}
+
+impl Serialize for LatencyHistogram {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Bucket {
+            le_seconds: Option<f64>,
+            count: u64,
+        }
+        let buckets: Vec<Bucket> = self
+            .buckets()
+            .map(|(upper_bound, count)| Bucket {
+                le_seconds: upper_bound.as_ref().map(Duration::as_secs_f64),
+                count,
+            })
+            .collect();
+        let mut state = serializer.serialize_struct("LatencyHistogram", 4)?;
+        state.serialize_field("count", &self.count())?;
+        state.serialize_field("sum_seconds", &self.sum.as_secs_f64())?;
+        state.serialize_field("max_seconds", &self.max.as_secs_f64())?;
+        state.serialize_field("buckets", &buckets)?;
+        state.end()
+    }
+}
+
+/// The metrics of a [`StorageOperation`] under a key prefix.
+#[derive(Debug, Clone, Default)]
+struct OperationMetrics {
+    bytes: u64,
+    latency: LatencyHistogram,
+}
+
+/// The metrics of a [`StorageOperation`] under a key prefix in a [`PerformanceMetricsReport`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PerformanceMetricsOperationReport {
+    /// The key prefix.
+    pub prefix: String,
+    /// The operation.
+    pub operation: StorageOperation,
+    /// The number of bytes read or written.
+    pub bytes: u64,
+    /// The latency histogram, which includes the number of operations.
+    pub latency: LatencyHistogram,
+}
+
+/// A report of the metrics recorded by a [`PerformanceMetricsStorageTransformer`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PerformanceMetricsReport {
+    /// The number of bytes read.
+    pub bytes_read: usize,
+    /// The number of bytes written.
+    pub bytes_written: usize,
+    /// The number of read requests.
+    pub reads: usize,
+    /// The number of write requests.
+    pub writes: usize,
+    /// The number of lock requests.
+    pub locks: usize,
+    /// The metrics of each operation under each key prefix, ordered by prefix and operation.
+    pub operations: Vec<PerformanceMetricsOperationReport>,
+}
+
+impl PerformanceMetricsReport {
+    /// Return the report as pretty printed JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Return the report in the [Prometheus text exposition format](https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format).
+    ///
+    /// Operation latencies are `zarrs_storage_operation_duration_seconds` histograms and operation bytes are `zarrs_storage_operation_bytes_total` counters, labelled by `prefix` and `operation`.
+    #[must_use]
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        for (name, help, value) in [
+            (
+                "zarrs_storage_bytes_read_total",
+                "The number of bytes read.",
+                self.bytes_read,
+            ),
+            (
+                "zarrs_storage_bytes_written_total",
+                "The number of bytes written.",
+                self.bytes_written,
+            ),
+            (
+                "zarrs_storage_reads_total",
+                "The number of read requests.",
+                self.reads,
+            ),
+            (
+                "zarrs_storage_writes_total",
+                "The number of write requests.",
+                self.writes,
+            ),
+            (
+                "zarrs_storage_locks_total",
+                "The number of lock requests.",
+                self.locks,
+            ),
+        ] {
+            writeln!(
+                out,
+                "# HELP {name} {help}\n# TYPE {name} counter\n{name} {value}"
+            )
+            .unwrap();
+        }
+
+        let labels = |operation: &PerformanceMetricsOperationReport| {
+            format!(
+                "prefix=\"{}\",operation=\"{}\"",
+                operation.prefix.replace('\\', "\\\\").replace('"', "\\\""),
+                operation.operation.as_str()
+            )
+        };
+        let name = "zarrs_storage_operation_duration_seconds";
+        writeln!(
+            out,
+            "# HELP {name} The latency of storage operations.\n# TYPE {name} histogram"
+        )
+        .unwrap();
+        for operation in &self.operations {
+            let labels = labels(operation);
+            let mut cumulative = 0;
+            for (upper_bound, count) in operation.latency.buckets() {
+                cumulative += count;
+                let le = upper_bound.map_or_else(
+                    || "+Inf".to_string(),
+                    |bound| bound.as_secs_f64().to_string(),
+                );
+                writeln!(out, "{name}_bucket{{{labels},le=\"{le}\"}} {cumulative}").unwrap();
+            }
+            writeln!(
+                out,
+                "{name}_sum{{{labels}}} {}",
+                operation.latency.sum().as_secs_f64()
+            )
+            .unwrap();
+            writeln!(out, "{name}_count{{{labels}}} {cumulative}").unwrap();
+        }
+        let name = "zarrs_storage_operation_bytes_total";
+        writeln!(out, "# HELP {name} The number of bytes read or written by storage operations.\n# TYPE {name} counter").unwrap();
+        for operation in &self.operations {
+            writeln!(out, "{name}{{{}}} {}", labels(operation), operation.bytes).unwrap();
+        }
+        out
+    }
+}
+
 /// The performance metrics storage transformer. Accumulates metrics, such as bytes read and written.
 ///
 /// This storage transformer is for internal use and will not to be included in `storage_transformers` array metadata.
 /// It is intended to aid in testing by allowing the application to validate that metrics (e.g., bytes read/written, total read/write operations, lock requests) match expected values for specific operations.
+///
+/// The transformer also records a [`LatencyHistogram`] and the bytes read or written for each [`StorageOperation`] under each key prefix, which enables analysis of the I/O performance of array access patterns.
+/// Keys are grouped by their first `prefix_depth` path components (e.g. `array/c/` for the key `array/c/0/1` with a prefix depth of 2), see [`PerformanceMetricsStorageTransformer::new_with_prefix_depth`].
+/// Operations on multiple keys are grouped under the prefix of the keys if they share one, and otherwise under the root prefix.
+/// The recorded metrics can be exported as JSON or Prometheus text with [`PerformanceMetricsStorageTransformer::report`].
 #[derive(Debug, Default)]
 pub struct PerformanceMetricsStorageTransformer {
     bytes_read: AtomicUsize,
@@ -38,15 +330,121 @@ pub struct PerformanceMetricsStorageTransformer {
     reads: AtomicUsize,
     writes: AtomicUsize,
     locks: AtomicUsize,
+    prefix_depth: usize,
+    operations: Mutex<BTreeMap<(StorePrefix, StorageOperation), OperationMetrics>>,
 }
 
 impl PerformanceMetricsStorageTransformer {
     /// Create a new performance metrics storage transformer.
+    ///
+    /// Operation metrics are recorded under the root prefix.
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Create a new performance metrics storage transformer which records operation metrics under key prefixes with up to `prefix_depth` path components.
+    #[must_use]
+    pub fn new_with_prefix_depth(prefix_depth: usize) -> Self {
+        Self {
+            prefix_depth,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the maximum number of path components of the key prefixes of operation metrics.
+    #[must_use]
+    pub const fn prefix_depth(&self) -> usize {
+        self.prefix_depth
+    }
+
+    /// Returns a report of the recorded metrics.
+    #[must_use]
+    pub fn report(&self) -> PerformanceMetricsReport {
+        let operations = self
+            .operations
+            .lock()
+            .iter()
+            .map(
+                |((prefix, operation), metrics)| PerformanceMetricsOperationReport {
+                    prefix: prefix.as_str().to_string(),
+                    operation: *operation,
+                    bytes: metrics.bytes,
+                    latency: metrics.latency.clone(),
+                },
+            )
+            .collect();
+        PerformanceMetricsReport {
+            bytes_read: self.bytes_read(),
+            bytes_written: self.bytes_written(),
+            reads: self.reads(),
+            writes: self.writes(),
+            locks: self.locks(),
+            operations,
+        }
+    }
+
+    /// Returns the latency histogram of `operation` under `prefix`, if the operation was recorded.
+    #[must_use]
+    pub fn latency(
+        &self,
+        prefix: &StorePrefix,
+        operation: StorageOperation,
+    ) -> Option<LatencyHistogram> {
+        self.operations
+            .lock()
+            .get(&(prefix.clone(), operation))
+            .map(|metrics| metrics.latency.clone())
+    }
+
+    fn record(
+        &self,
+        operation: StorageOperation,
+        prefix: StorePrefix,
+        bytes: usize,
+        start: Instant,
+    ) {
+        let latency = start.elapsed();
+        let mut operations = self.operations.lock();
+        let metrics = operations.entry((prefix, operation)).or_default();
+        metrics.bytes += bytes as u64;
+        metrics.latency.record(latency);
+    }
+
+    /// Return the first `prefix_depth` components of `path`, which excludes the final component if `path` is a key.
+    fn truncate_prefix(&self, path: &str, is_key: bool) -> StorePrefix {
+        let mut components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+        if is_key {
+            components.pop();
+        }
+        components.truncate(self.prefix_depth);
+        if components.is_empty() {
+            StorePrefix::root()
+        } else {
+            unsafe { StorePrefix::new_unchecked(components.join("/") + "/") }
+        }
+    }
+
+    fn key_prefix(&self, key: &StoreKey) -> StorePrefix {
+        self.truncate_prefix(key.as_str(), true)
+    }
+
+    fn prefix_prefix(&self, prefix: &StorePrefix) -> StorePrefix {
+        self.truncate_prefix(prefix.as_str(), false)
+    }
+
+    fn keys_prefix<'a>(&self, mut keys: impl Iterator<Item = &'a StoreKey>) -> StorePrefix {
+        let Some(first) = keys.next() else {
+            return StorePrefix::root();
+        };
+        let prefix = self.key_prefix(first);
+        if keys.all(|key| self.key_prefix(key) == prefix) {
+            prefix
+        } else {
+            StorePrefix::root()
+        }
+    }
+
     /// Returns the number of bytes read.
     pub fn bytes_read(&self) -> usize {
         self.bytes_read.load(Ordering::Relaxed)
@@ -172,15 +570,44 @@ struct PerformanceMetricsStorageTransformerImpl<TStorage: ?Sized + 'static> {
 impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
     for PerformanceMetricsStorageTransformerImpl<TStorage>
 {
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        let start = Instant::now();
+        let value = self.storage.get_with_generation(key);
+        let bytes_read = value
+            .as_ref()
+            .map_or(0, |v| v.as_ref().map_or(0, |(bytes, _)| bytes.len()));
+        self.transformer
+            .bytes_read
+            .fetch_add(bytes_read, Ordering::Relaxed);
+        self.transformer.reads.fetch_add(1, Ordering::Relaxed);
+        self.transformer.record(
+            StorageOperation::Get,
+            self.transformer.key_prefix(key),
+            bytes_read,
+            start,
+        );
+        value
+    }
+
     fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        let start = Instant::now();
         let value = self.storage.get(key);
         let bytes_read = value
             .as_ref()
-            .map_or(0, |v| v.as_ref().map_or(0, std::vec::Vec::len));
+            .map_or(0, |v| v.as_ref().map_or(0, Bytes::len));
         self.transformer
             .bytes_read
             .fetch_add(bytes_read, Ordering::Relaxed);
         self.transformer.reads.fetch_add(1, Ordering::Relaxed);
+        self.transformer.record(
+            StorageOperation::Get,
+            self.transformer.key_prefix(key),
+            bytes_read,
+            start,
+        );
         value
     }
 
@@ -188,10 +615,20 @@ impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
         &self,
         key: &StoreKey,
         byte_ranges: &[crate::byte_range::ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let start = Instant::now();
         let values = self.storage.get_partial_values_key(key, byte_ranges)?;
+        let bytes_read = values
+            .as_ref()
+            .map_or(0, |values| values.iter().map(Bytes::len).sum());
+        self.transformer.record(
+            StorageOperation::GetPartialValues,
+            self.transformer.key_prefix(key),
+            bytes_read,
+            start,
+        );
         if let Some(values) = &values {
-            let bytes_read = values.iter().map(Vec::len).sum();
+            let bytes_read = values.iter().map(Bytes::len).sum();
             self.transformer
                 .bytes_read
                 .fetch_add(bytes_read, Ordering::Relaxed);
@@ -206,11 +643,19 @@ impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
         &self,
         key_ranges: &[StoreKeyRange],
     ) -> Result<Vec<MaybeBytes>, StorageError> {
+        let start = Instant::now();
         let values = self.storage.get_partial_values(key_ranges)?;
         let bytes_read = values
             .iter()
-            .map(|value| value.as_ref().map_or(0, Vec::len))
+            .map(|value| value.as_ref().map_or(0, Bytes::len))
             .sum::<usize>();
+        self.transformer.record(
+            StorageOperation::GetPartialValues,
+            self.transformer
+                .keys_prefix(key_ranges.iter().map(|key_range| &key_range.key)),
+            bytes_read,
+            start,
+        );
         self.transformer
             .bytes_read
             .fetch_add(bytes_read, Ordering::Relaxed);
@@ -221,15 +666,35 @@ impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
     }
 
     fn size(&self) -> Result<u64, StorageError> {
-        self.storage.size()
+        let start = Instant::now();
+        let result = self.storage.size();
+        self.transformer
+            .record(StorageOperation::Size, StorePrefix::root(), 0, start);
+        result
     }
 
     fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
-        self.storage.size_prefix(prefix)
+        let start = Instant::now();
+        let result = self.storage.size_prefix(prefix);
+        self.transformer.record(
+            StorageOperation::Size,
+            self.transformer.prefix_prefix(prefix),
+            0,
+            start,
+        );
+        result
     }
 
     fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
-        self.storage.size_key(key)
+        let start = Instant::now();
+        let result = self.storage.size_key(key);
+        self.transformer.record(
+            StorageOperation::Size,
+            self.transformer.key_prefix(key),
+            0,
+            start,
+        );
+        result
     }
 }
 
@@ -237,27 +702,78 @@ impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
     for PerformanceMetricsStorageTransformerImpl<TStorage>
 {
     fn list(&self) -> Result<StoreKeys, StorageError> {
-        self.storage.list()
+        let start = Instant::now();
+        let result = self.storage.list();
+        self.transformer
+            .record(StorageOperation::List, StorePrefix::root(), 0, start);
+        result
     }
 
     fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
-        self.storage.list_prefix(prefix)
+        let start = Instant::now();
+        let result = self.storage.list_prefix(prefix);
+        self.transformer.record(
+            StorageOperation::List,
+            self.transformer.prefix_prefix(prefix),
+            0,
+            start,
+        );
+        result
     }
 
     fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
-        self.storage.list_dir(prefix)
+        let start = Instant::now();
+        let result = self.storage.list_dir(prefix);
+        self.transformer.record(
+            StorageOperation::List,
+            self.transformer.prefix_prefix(prefix),
+            0,
+            start,
+        );
+        result
     }
 }
 
 impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits
     for PerformanceMetricsStorageTransformerImpl<TStorage>
 {
+    fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: &[u8],
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        let bytes_written = value.len();
+        self.transformer
+            .bytes_written
+            .fetch_add(bytes_written, Ordering::Relaxed);
+        self.transformer.writes.fetch_add(1, Ordering::Relaxed);
+        let start = Instant::now();
+        let result = self.storage.set_if_match(key, value, generation);
+        self.transformer.record(
+            StorageOperation::Set,
+            self.transformer.key_prefix(key),
+            bytes_written,
+            start,
+        );
+        result
+    }
+
     fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        let bytes_written = value.len();
         self.transformer
             .bytes_written
-            .fetch_add(value.len(), Ordering::Relaxed);
+            .fetch_add(bytes_written, Ordering::Relaxed);
         self.transformer.writes.fetch_add(1, Ordering::Relaxed);
-        self.storage.set(key, value)
+        let start = Instant::now();
+        let result = self.storage.set(key, value);
+        self.transformer.record(
+            StorageOperation::Set,
+            self.transformer.key_prefix(key),
+            bytes_written,
+            start,
+        );
+        result
     }
 
     fn set_partial_values(
@@ -274,19 +790,52 @@ impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits
         self.transformer
             .writes
             .fetch_add(key_start_values.len(), Ordering::Relaxed);
-        self.storage.set_partial_values(key_start_values)
+        let start = Instant::now();
+        let result = self.storage.set_partial_values(key_start_values);
+        self.transformer.record(
+            StorageOperation::SetPartialValues,
+            self.transformer
+                .keys_prefix(key_start_values.iter().map(|ksv| &ksv.key)),
+            bytes_written,
+            start,
+        );
+        result
     }
 
     fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
-        self.storage.erase(key)
+        let start = Instant::now();
+        let result = self.storage.erase(key);
+        self.transformer.record(
+            StorageOperation::Erase,
+            self.transformer.key_prefix(key),
+            0,
+            start,
+        );
+        result
     }
 
     fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
-        self.storage.erase_values(keys)
+        let start = Instant::now();
+        let result = self.storage.erase_values(keys);
+        self.transformer.record(
+            StorageOperation::Erase,
+            self.transformer.keys_prefix(keys.iter()),
+            0,
+            start,
+        );
+        result
     }
 
     fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
-        self.storage.erase_prefix(prefix)
+        let start = Instant::now();
+        let result = self.storage.erase_prefix(prefix);
+        self.transformer.record(
+            StorageOperation::Erase,
+            self.transformer.prefix_prefix(prefix),
+            0,
+            start,
+        );
+        result
     }
 }
 
@@ -295,7 +844,15 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits> ReadableWritableStorageTr
 {
     fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
         self.transformer.locks.fetch_add(1, Ordering::Relaxed);
-        self.storage.mutex(key)
+        let start = Instant::now();
+        let result = self.storage.mutex(key);
+        self.transformer.record(
+            StorageOperation::Lock,
+            self.transformer.key_prefix(key),
+            0,
+            start,
+        );
+        result
     }
 }
 
@@ -304,15 +861,44 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits> ReadableWritableStorageTr
 impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
     for PerformanceMetricsStorageTransformerImpl<TStorage>
 {
+    async fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        let start = Instant::now();
+        let value = self.storage.get_with_generation(key).await;
+        let bytes_read = value
+            .as_ref()
+            .map_or(0, |v| v.as_ref().map_or(0, |(bytes, _)| bytes.len()));
+        self.transformer
+            .bytes_read
+            .fetch_add(bytes_read, Ordering::Relaxed);
+        self.transformer.reads.fetch_add(1, Ordering::Relaxed);
+        self.transformer.record(
+            StorageOperation::Get,
+            self.transformer.key_prefix(key),
+            bytes_read,
+            start,
+        );
+        value
+    }
+
     async fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        let start = Instant::now();
         let value = self.storage.get(key).await;
         let bytes_read = value
             .as_ref()
-            .map_or(0, |v| v.as_ref().map_or(0, std::vec::Vec::len));
+            .map_or(0, |v| v.as_ref().map_or(0, Bytes::len));
         self.transformer
             .bytes_read
             .fetch_add(bytes_read, Ordering::Relaxed);
         self.transformer.reads.fetch_add(1, Ordering::Relaxed);
+        self.transformer.record(
+            StorageOperation::Get,
+            self.transformer.key_prefix(key),
+            bytes_read,
+            start,
+        );
         value
     }
 
@@ -320,13 +906,23 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
         &self,
         key: &StoreKey,
         byte_ranges: &[crate::byte_range::ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let start = Instant::now();
         let values = self
             .storage
             .get_partial_values_key(key, byte_ranges)
             .await?;
+        let bytes_read = values
+            .as_ref()
+            .map_or(0, |values| values.iter().map(Bytes::len).sum());
+        self.transformer.record(
+            StorageOperation::GetPartialValues,
+            self.transformer.key_prefix(key),
+            bytes_read,
+            start,
+        );
         if let Some(values) = &values {
-            let bytes_read = values.iter().map(Vec::len).sum();
+            let bytes_read = values.iter().map(Bytes::len).sum();
             self.transformer
                 .bytes_read
                 .fetch_add(bytes_read, Ordering::Relaxed);
@@ -341,11 +937,19 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
         &self,
         key_ranges: &[StoreKeyRange],
     ) -> Result<Vec<MaybeBytes>, StorageError> {
+        let start = Instant::now();
         let values = self.storage.get_partial_values(key_ranges).await?;
         let bytes_read = values
             .iter()
-            .map(|value| value.as_ref().map_or(0, Vec::len))
+            .map(|value| value.as_ref().map_or(0, Bytes::len))
             .sum::<usize>();
+        self.transformer.record(
+            StorageOperation::GetPartialValues,
+            self.transformer
+                .keys_prefix(key_ranges.iter().map(|key_range| &key_range.key)),
+            bytes_read,
+            start,
+        );
         self.transformer
             .bytes_read
             .fetch_add(bytes_read, Ordering::Relaxed);
@@ -356,15 +960,35 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
     }
 
     async fn size(&self) -> Result<u64, StorageError> {
-        self.storage.size().await
+        let start = Instant::now();
+        let result = self.storage.size().await;
+        self.transformer
+            .record(StorageOperation::Size, StorePrefix::root(), 0, start);
+        result
     }
 
     async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
-        self.storage.size_prefix(prefix).await
+        let start = Instant::now();
+        let result = self.storage.size_prefix(prefix).await;
+        self.transformer.record(
+            StorageOperation::Size,
+            self.transformer.prefix_prefix(prefix),
+            0,
+            start,
+        );
+        result
     }
 
     async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
-        self.storage.size_key(key).await
+        let start = Instant::now();
+        let result = self.storage.size_key(key).await;
+        self.transformer.record(
+            StorageOperation::Size,
+            self.transformer.key_prefix(key),
+            0,
+            start,
+        );
+        result
     }
 }
 
@@ -374,15 +998,35 @@ impl<TStorage: ?Sized + AsyncListableStorageTraits> AsyncListableStorageTraits
     for PerformanceMetricsStorageTransformerImpl<TStorage>
 {
     async fn list(&self) -> Result<StoreKeys, StorageError> {
-        self.storage.list().await
+        let start = Instant::now();
+        let result = self.storage.list().await;
+        self.transformer
+            .record(StorageOperation::List, StorePrefix::root(), 0, start);
+        result
     }
 
     async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
-        self.storage.list_prefix(prefix).await
+        let start = Instant::now();
+        let result = self.storage.list_prefix(prefix).await;
+        self.transformer.record(
+            StorageOperation::List,
+            self.transformer.prefix_prefix(prefix),
+            0,
+            start,
+        );
+        result
     }
 
     async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
-        self.storage.list_dir(prefix).await
+        let start = Instant::now();
+        let result = self.storage.list_dir(prefix).await;
+        self.transformer.record(
+            StorageOperation::List,
+            self.transformer.prefix_prefix(prefix),
+            0,
+            start,
+        );
+        result
     }
 }
 
@@ -391,12 +1035,43 @@ impl<TStorage: ?Sized + AsyncListableStorageTraits> AsyncListableStorageTraits
 impl<TStorage: ?Sized + AsyncWritableStorageTraits> AsyncWritableStorageTraits
     for PerformanceMetricsStorageTransformerImpl<TStorage>
 {
+    async fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: Bytes,
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        let bytes_written = value.len();
+        self.transformer
+            .bytes_written
+            .fetch_add(bytes_written, Ordering::Relaxed);
+        self.transformer.writes.fetch_add(1, Ordering::Relaxed);
+        let start = Instant::now();
+        let result = self.storage.set_if_match(key, value, generation).await;
+        self.transformer.record(
+            StorageOperation::Set,
+            self.transformer.key_prefix(key),
+            bytes_written,
+            start,
+        );
+        result
+    }
+
     async fn set(&self, key: &StoreKey, value: bytes::Bytes) -> Result<(), StorageError> {
+        let bytes_written = value.len();
         self.transformer
             .bytes_written
-            .fetch_add(value.len(), Ordering::Relaxed);
+            .fetch_add(bytes_written, Ordering::Relaxed);
         self.transformer.writes.fetch_add(1, Ordering::Relaxed);
-        self.storage.set(key, value).await
+        let start = Instant::now();
+        let result = self.storage.set(key, value).await;
+        self.transformer.record(
+            StorageOperation::Set,
+            self.transformer.key_prefix(key),
+            bytes_written,
+            start,
+        );
+        result
     }
 
     async fn set_partial_values(
@@ -413,19 +1088,52 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits> AsyncWritableStorageTraits
         self.transformer
             .writes
             .fetch_add(key_start_values.len(), Ordering::Relaxed);
-        self.storage.set_partial_values(key_start_values).await
+        let start = Instant::now();
+        let result = self.storage.set_partial_values(key_start_values).await;
+        self.transformer.record(
+            StorageOperation::SetPartialValues,
+            self.transformer
+                .keys_prefix(key_start_values.iter().map(|ksv| &ksv.key)),
+            bytes_written,
+            start,
+        );
+        result
     }
 
     async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
-        self.storage.erase(key).await
+        let start = Instant::now();
+        let result = self.storage.erase(key).await;
+        self.transformer.record(
+            StorageOperation::Erase,
+            self.transformer.key_prefix(key),
+            0,
+            start,
+        );
+        result
     }
 
     async fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
-        self.storage.erase_values(keys).await
+        let start = Instant::now();
+        let result = self.storage.erase_values(keys).await;
+        self.transformer.record(
+            StorageOperation::Erase,
+            self.transformer.keys_prefix(keys.iter()),
+            0,
+            start,
+        );
+        result
     }
 
     async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
-        self.storage.erase_prefix(prefix).await
+        let start = Instant::now();
+        let result = self.storage.erase_prefix(prefix).await;
+        self.transformer.record(
+            StorageOperation::Erase,
+            self.transformer.prefix_prefix(prefix),
+            0,
+            start,
+        );
+        result
     }
 }
 
@@ -436,6 +1144,109 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits> AsyncReadableWritabl
 {
     async fn mutex(&self, key: &StoreKey) -> Result<AsyncStoreKeyMutex, StorageError> {
         self.transformer.locks.fetch_add(1, Ordering::Relaxed);
-        self.storage.mutex(key).await
+        let start = Instant::now();
+        let result = self.storage.mutex(key).await;
+        self.transformer.record(
+            StorageOperation::Lock,
+            self.transformer.key_prefix(key),
+            0,
+            start,
+        );
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{byte_range::ByteRange, storage::store::MemoryStore};
+
+    use super::*;
+
+    #[test]
+    fn latency_histogram() {
+        let mut histogram = LatencyHistogram::default();
+        assert_eq!(histogram.mean(), None);
+        assert_eq!(histogram.quantile(0.5), None);
+        for micros in [1, 3, 3, 100, 5_000_000, 60_000_000] {
+            histogram.record(Duration::from_micros(micros));
+        }
+        assert_eq!(histogram.count(), 6);
+        assert_eq!(histogram.max(), Duration::from_secs(60));
+        assert_eq!(histogram.quantile(0.0), Some(Duration::from_micros(1)));
+        assert_eq!(histogram.quantile(0.5), Some(Duration::from_micros(4)));
+        assert_eq!(histogram.quantile(0.6), Some(Duration::from_micros(128)));
+        assert_eq!(histogram.quantile(1.0), Some(Duration::from_secs(60)));
+        let buckets: Vec<_> = histogram.buckets().collect();
+        assert_eq!(buckets.len(), LATENCY_HISTOGRAM_BUCKETS + 1);
+        assert_eq!(buckets[2], (Some(Duration::from_micros(4)), 2));
+        assert_eq!(buckets[LATENCY_HISTOGRAM_BUCKETS], (None, 1));
+    }
+
+    #[test]
+    fn performance_metrics_report() {
+        let performance_metrics = Arc::new(
+            PerformanceMetricsStorageTransformer::new_with_prefix_depth(1),
+        );
+        let storage = performance_metrics
+            .clone()
+            .create_readable_writable_listable_transformer(Arc::new(MemoryStore::new()));
+        storage
+            .set(&"a/b/c".try_into().unwrap(), &[0, 1, 2])
+            .unwrap();
+        storage.set(&"a/d".try_into().unwrap(), &[3]).unwrap();
+        storage.set(&"e".try_into().unwrap(), &[4, 5]).unwrap();
+        storage.get(&"a/b/c".try_into().unwrap()).unwrap();
+        storage
+            .get_partial_values(&[
+                StoreKeyRange::new("a/b/c".try_into().unwrap(), ByteRange::FromStart(1, None)),
+                StoreKeyRange::new("e".try_into().unwrap(), ByteRange::FromStart(0, Some(1))),
+            ])
+            .unwrap();
+        storage.list_prefix(&"a/b/".try_into().unwrap()).unwrap();
+
+        let report = performance_metrics.report();
+        assert_eq!(report.bytes_written, 6);
+        assert_eq!(report.bytes_read, 6);
+        let operations: Vec<_> = report
+            .operations
+            .iter()
+            .map(|operation| {
+                (
+                    operation.prefix.as_str(),
+                    operation.operation,
+                    operation.latency.count(),
+                    operation.bytes,
+                )
+            })
+            .collect();
+        assert_eq!(
+            operations,
+            vec![
+                ("", StorageOperation::GetPartialValues, 1, 3),
+                ("", StorageOperation::Set, 1, 2),
+                ("a/", StorageOperation::Get, 1, 3),
+                ("a/", StorageOperation::List, 1, 0),
+                ("a/", StorageOperation::Set, 2, 4),
+            ]
+        );
+        assert_eq!(
+            performance_metrics
+                .latency(&"a/".try_into().unwrap(), StorageOperation::Set)
+                .unwrap()
+                .count(),
+            2
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["operations"][4]["operation"], "set");
+        assert_eq!(json["operations"][4]["latency"]["count"], 2);
+
+        let prometheus = report.to_prometheus();
+        assert!(prometheus.contains("zarrs_storage_bytes_written_total 6\n"));
+        assert!(prometheus.contains(
+            "zarrs_storage_operation_duration_seconds_bucket{prefix=\"a/\",operation=\"set\",le=\"+Inf\"} 2\n"
+        ));
+        assert!(prometheus
+            .contains("zarrs_storage_operation_bytes_total{prefix=\"a/\",operation=\"set\"} 4\n"));
     }
 }
@@ -0,0 +1,619 @@
+//! A storage transformer which enforces usage quotas.
+
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use parking_lot::Mutex;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    metadata::Metadata,
+    storage::{
+        store_lock::StoreKeyMutex, GenerationToken, ListableStorage, ListableStorageTraits,
+        ReadableListableStorage, ReadableStorage, ReadableStorageTraits,
+        ReadableWritableListableStorage, ReadableWritableStorage, ReadableWritableStorageTraits,
+        StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes,
+        StorePrefix, WritableStorage, WritableStorageTraits,
+    },
+};
+
+#[cfg(feature = "async")]
+use crate::storage::{
+    store_lock::AsyncStoreKeyMutex, AsyncListableStorage, AsyncListableStorageTraits,
+    AsyncReadableListableStorage, AsyncReadableStorage, AsyncReadableStorageTraits,
+    AsyncReadableWritableListableStorage, AsyncReadableWritableStorageTraits, AsyncWritableStorage,
+    AsyncWritableStorageTraits,
+};
+
+use super::StorageTransformerExtension;
+
+/// Storage usage limits enforced by a [`QuotaStorageTransformer`].
+///
+/// All limits are disabled by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageQuota {
+    bytes_written: Option<u64>,
+    keys: Option<u64>,
+    requests_per_second: Option<u64>,
+}
+
+impl StorageQuota {
+    /// Create a new storage quota with no limits.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of bytes that can be written.
+    #[must_use]
+    pub const fn max_bytes_written(mut self, max_bytes_written: u64) -> Self {
+        self.bytes_written = Some(max_bytes_written);
+        self
+    }
+
+    /// Set the maximum number of distinct keys that can be written.
+    #[must_use]
+    pub const fn max_keys(mut self, max_keys: u64) -> Self {
+        self.keys = Some(max_keys);
+        self
+    }
+
+    /// Set the maximum number of storage requests per second.
+    #[must_use]
+    pub const fn max_requests_per_second(mut self, max_requests_per_second: u64) -> Self {
+        self.requests_per_second = Some(max_requests_per_second);
+        self
+    }
+}
+
+/// The accumulated usage of a [`QuotaStorageTransformer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageQuotaUsage {
+    /// The number of bytes written.
+    pub bytes_written: u64,
+    /// The number of distinct keys written and not subsequently erased.
+    pub keys: u64,
+    /// The number of storage requests.
+    pub requests: u64,
+}
+
+#[derive(Debug, Default)]
+struct QuotaState {
+    bytes_written: u64,
+    keys: HashSet<StoreKey>,
+    requests: u64,
+    window_start: Option<Instant>,
+    window_requests: u64,
+}
+
+/// The quota storage transformer. Enforces limits on the bytes written, keys written, and request rate.
+///
+/// This storage transformer is for internal use and will not to be included in `storage_transformers` array metadata.
+/// It is intended to bound how much a client (e.g. a tenant of an ingestion service) can write through a shared storage handle, such as that of an [`Array`](crate::array::Array).
+///
+/// Operations which would exceed a [`StorageQuota`] fail with [`StorageError::QuotaExceeded`] without accessing the underlying storage.
+///  - The bytes written are accumulated over all write operations, and are not reduced if keys are erased.
+///  - The keys are the distinct keys written through the transformer, a key no longer counts towards the limit if it is erased through the transformer.
+///  - Every storage method call (except requesting a store key mutex) counts as one request. The request rate is limited in fixed one second windows.
+///
+/// The accumulated usage is returned by [`usage`](QuotaStorageTransformer::usage).
+#[derive(Debug)]
+pub struct QuotaStorageTransformer {
+    quota: StorageQuota,
+    state: Mutex<QuotaState>,
+}
+
+impl QuotaStorageTransformer {
+    /// Create a new quota storage transformer.
+    #[must_use]
+    pub fn new(quota: StorageQuota) -> Self {
+        Self {
+            quota,
+            state: Mutex::new(QuotaState::default()),
+        }
+    }
+
+    /// Returns the storage quota.
+    #[must_use]
+    pub const fn quota(&self) -> &StorageQuota {
+        &self.quota
+    }
+
+    /// Returns the accumulated usage.
+    #[must_use]
+    pub fn usage(&self) -> StorageQuotaUsage {
+        let state = self.state.lock();
+        StorageQuotaUsage {
+            bytes_written: state.bytes_written,
+            keys: state.keys.len() as u64,
+            requests: state.requests,
+        }
+    }
+
+    fn create_transformer<TStorage: ?Sized + 'static>(
+        self: Arc<Self>,
+        storage: Arc<TStorage>,
+    ) -> Arc<QuotaStorageTransformerImpl<TStorage>> {
+        Arc::new(QuotaStorageTransformerImpl {
+            storage,
+            transformer: self,
+        })
+    }
+
+    fn check_request_rate(&self, state: &mut QuotaState) -> Result<(), StorageError> {
+        if let Some(max_requests_per_second) = self.quota.requests_per_second {
+            let now = Instant::now();
+            let window_elapsed = state.window_start.map_or(true, |window_start| {
+                now.duration_since(window_start) >= Duration::from_secs(1)
+            });
+            if window_elapsed {
+                state.window_start = Some(now);
+                state.window_requests = 0;
+            }
+            if state.window_requests >= max_requests_per_second {
+                return Err(StorageError::QuotaExceeded(format!(
+                    "request rate exceeds {max_requests_per_second} requests per second"
+                )));
+            }
+            state.window_requests += 1;
+        }
+        state.requests += 1;
+        Ok(())
+    }
+
+    /// Record a request which does not write.
+    fn request(&self) -> Result<(), StorageError> {
+        self.check_request_rate(&mut self.state.lock())
+    }
+
+    /// Record a request which writes `bytes` to `keys`.
+    fn write<'a>(
+        &self,
+        keys: impl IntoIterator<Item = &'a StoreKey>,
+        bytes: u64,
+    ) -> Result<(), StorageError> {
+        let mut state = self.state.lock();
+        let new_keys: HashSet<&StoreKey> = keys
+            .into_iter()
+            .filter(|key| !state.keys.contains(*key))
+            .collect();
+        let bytes_written = state.bytes_written + bytes;
+        if let Some(max_bytes_written) = self.quota.bytes_written {
+            if bytes_written > max_bytes_written {
+                return Err(StorageError::QuotaExceeded(format!(
+                    "writing {bytes} bytes would exceed the limit of {max_bytes_written} bytes written"
+                )));
+            }
+        }
+        let num_keys = (state.keys.len() + new_keys.len()) as u64;
+        if let Some(max_keys) = self.quota.keys {
+            if num_keys > max_keys {
+                return Err(StorageError::QuotaExceeded(format!(
+                    "writing {} new keys would exceed the limit of {max_keys} keys",
+                    new_keys.len()
+                )));
+            }
+        }
+        self.check_request_rate(&mut state)?;
+        state.bytes_written = bytes_written;
+        state.keys.extend(new_keys.into_iter().cloned());
+        Ok(())
+    }
+
+    /// Record a request which erases the keys matching `erased`.
+    fn erase(&self, erased: impl Fn(&StoreKey) -> bool) -> Result<(), StorageError> {
+        let mut state = self.state.lock();
+        self.check_request_rate(&mut state)?;
+        state.keys.retain(|key| !erased(key));
+        Ok(())
+    }
+}
+
+impl StorageTransformerExtension for QuotaStorageTransformer {
+    /// Returns [`None`], since this storage transformer is not intended to be included in array `storage_transformers` metadata.
+    fn create_metadata(&self) -> Option<Metadata> {
+        None
+    }
+
+    fn create_readable_transformer(self: Arc<Self>, storage: ReadableStorage) -> ReadableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_writable_transformer(self: Arc<Self>, storage: WritableStorage) -> WritableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_readable_writable_transformer(
+        self: Arc<Self>,
+        storage: ReadableWritableStorage,
+    ) -> ReadableWritableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_listable_transformer(self: Arc<Self>, storage: ListableStorage) -> ListableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_readable_listable_transformer(
+        self: Arc<Self>,
+        storage: ReadableListableStorage,
+    ) -> ReadableListableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_readable_writable_listable_transformer(
+        self: Arc<Self>,
+        storage: ReadableWritableListableStorage,
+    ) -> ReadableWritableListableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_readable_transformer(
+        self: Arc<Self>,
+        storage: AsyncReadableStorage,
+    ) -> AsyncReadableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_writable_transformer(
+        self: Arc<Self>,
+        storage: AsyncWritableStorage,
+    ) -> AsyncWritableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_listable_transformer(
+        self: Arc<Self>,
+        storage: AsyncListableStorage,
+    ) -> AsyncListableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_readable_listable_transformer(
+        self: Arc<Self>,
+        storage: AsyncReadableListableStorage,
+    ) -> AsyncReadableListableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_readable_writable_listable_transformer(
+        self: Arc<Self>,
+        storage: AsyncReadableWritableListableStorage,
+    ) -> AsyncReadableWritableListableStorage {
+        self.create_transformer(storage)
+    }
+}
+
+#[derive(Debug)]
+struct QuotaStorageTransformerImpl<TStorage: ?Sized + 'static> {
+    storage: Arc<TStorage>,
+    transformer: Arc<QuotaStorageTransformer>,
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for QuotaStorageTransformerImpl<TStorage>
+{
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        self.transformer.request()?;
+        self.storage.get_with_generation(key)
+    }
+
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.transformer.request()?;
+        self.storage.get(key)
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        self.transformer.request()?;
+        self.storage.get_partial_values_key(key, byte_ranges)
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.transformer.request()?;
+        self.storage.get_partial_values(key_ranges)
+    }
+
+    fn size(&self) -> Result<u64, StorageError> {
+        self.transformer.request()?;
+        self.storage.size()
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.transformer.request()?;
+        self.storage.size_prefix(prefix)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.transformer.request()?;
+        self.storage.size_key(key)
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
+    for QuotaStorageTransformerImpl<TStorage>
+{
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.transformer.request()?;
+        self.storage.list()
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.transformer.request()?;
+        self.storage.list_prefix(prefix)
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.transformer.request()?;
+        self.storage.list_dir(prefix)
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits
+    for QuotaStorageTransformerImpl<TStorage>
+{
+    fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: &[u8],
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        self.transformer.write([key], value.len() as u64)?;
+        self.storage.set_if_match(key, value, generation)
+    }
+
+    fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        self.transformer.write([key], value.len() as u64)?;
+        self.storage.set(key, value)
+    }
+
+    fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        let bytes = key_start_values
+            .iter()
+            .map(|ksv| ksv.value.len() as u64)
+            .sum();
+        self.transformer
+            .write(key_start_values.iter().map(|ksv| &ksv.key), bytes)?;
+        self.storage.set_partial_values(key_start_values)
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.transformer.erase(|erased| erased == key)?;
+        self.storage.erase(key)
+    }
+
+    fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.transformer.erase(|erased| keys.contains(erased))?;
+        self.storage.erase_values(keys)
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.transformer.erase(|erased| erased.has_prefix(prefix))?;
+        self.storage.erase_prefix(prefix)
+    }
+}
+
+impl<TStorage: ?Sized + ReadableWritableStorageTraits> ReadableWritableStorageTraits
+    for QuotaStorageTransformerImpl<TStorage>
+{
+    fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+        self.storage.mutex(key)
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
+    for QuotaStorageTransformerImpl<TStorage>
+{
+    async fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        self.transformer.request()?;
+        self.storage.get_with_generation(key).await
+    }
+
+    async fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.transformer.request()?;
+        self.storage.get(key).await
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        self.transformer.request()?;
+        self.storage.get_partial_values_key(key, byte_ranges).await
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.transformer.request()?;
+        self.storage.get_partial_values(key_ranges).await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.transformer.request()?;
+        self.storage.size().await
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.transformer.request()?;
+        self.storage.size_prefix(prefix).await
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.transformer.request()?;
+        self.storage.size_key(key).await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncListableStorageTraits> AsyncListableStorageTraits
+    for QuotaStorageTransformerImpl<TStorage>
+{
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.transformer.request()?;
+        self.storage.list().await
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.transformer.request()?;
+        self.storage.list_prefix(prefix).await
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.transformer.request()?;
+        self.storage.list_dir(prefix).await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncWritableStorageTraits> AsyncWritableStorageTraits
+    for QuotaStorageTransformerImpl<TStorage>
+{
+    async fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: Bytes,
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        self.transformer.write([key], value.len() as u64)?;
+        self.storage.set_if_match(key, value, generation).await
+    }
+
+    async fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), StorageError> {
+        self.transformer.write([key], value.len() as u64)?;
+        self.storage.set(key, value).await
+    }
+
+    async fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        let bytes = key_start_values
+            .iter()
+            .map(|ksv| ksv.value.len() as u64)
+            .sum();
+        self.transformer
+            .write(key_start_values.iter().map(|ksv| &ksv.key), bytes)?;
+        self.storage.set_partial_values(key_start_values).await
+    }
+
+    async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.transformer.erase(|erased| erased == key)?;
+        self.storage.erase(key).await
+    }
+
+    async fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.transformer.erase(|erased| keys.contains(erased))?;
+        self.storage.erase_values(keys).await
+    }
+
+    async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.transformer.erase(|erased| erased.has_prefix(prefix))?;
+        self.storage.erase_prefix(prefix).await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits> AsyncReadableWritableStorageTraits
+    for QuotaStorageTransformerImpl<TStorage>
+{
+    async fn mutex(&self, key: &StoreKey) -> Result<AsyncStoreKeyMutex, StorageError> {
+        self.storage.mutex(key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::store::MemoryStore;
+
+    use super::*;
+
+    #[test]
+    fn quota_storage_transformer() {
+        let store = Arc::new(MemoryStore::new());
+        let quota = Arc::new(QuotaStorageTransformer::new(
+            StorageQuota::new().max_bytes_written(10).max_keys(2),
+        ));
+        let storage = quota.clone().create_readable_writable_transformer(store);
+
+        let key_a = StoreKey::new("a").unwrap();
+        let key_b = StoreKey::new("b").unwrap();
+        let key_c = StoreKey::new("c").unwrap();
+        storage.set(&key_a, &[0; 4]).unwrap();
+        storage.set(&key_a, &[0; 2]).unwrap();
+        storage.set(&key_b, &[0; 2]).unwrap();
+        assert!(matches!(
+            storage.set(&key_c, &[0; 1]),
+            Err(StorageError::QuotaExceeded(_))
+        ));
+        assert!(matches!(
+            storage.set(&key_b, &[0; 3]),
+            Err(StorageError::QuotaExceeded(_))
+        ));
+        assert!(storage.get(&key_c).unwrap().is_none());
+        assert_eq!(
+            quota.usage(),
+            StorageQuotaUsage {
+                bytes_written: 8,
+                keys: 2,
+                requests: 4,
+            }
+        );
+
+        storage.erase(&key_b).unwrap();
+        storage.set(&key_c, &[0; 1]).unwrap();
+        assert_eq!(quota.usage().keys, 2);
+        assert_eq!(quota.usage().bytes_written, 9);
+    }
+
+    #[test]
+    fn quota_storage_transformer_request_rate() {
+        let store = Arc::new(MemoryStore::new());
+        let quota = Arc::new(QuotaStorageTransformer::new(
+            StorageQuota::new().max_requests_per_second(3),
+        ));
+        let storage = quota.clone().create_readable_writable_transformer(store);
+
+        let key = StoreKey::new("a").unwrap();
+        storage.set(&key, &[0]).unwrap();
+        storage.get(&key).unwrap();
+        storage.get(&key).unwrap();
+        assert!(matches!(
+            storage.get(&key),
+            Err(StorageError::QuotaExceeded(_))
+        ));
+        assert_eq!(quota.usage().requests, 3);
+    }
+}
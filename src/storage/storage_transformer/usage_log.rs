@@ -5,6 +5,7 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use bytes::Bytes;
 use itertools::Itertools;
 
 use crate::{
@@ -12,14 +13,17 @@ use crate::{
     byte_range::ByteRange,
     metadata::Metadata,
     storage::{
-        store_lock::StoreKeyMutex, ListableStorage, ListableStorageTraits, ReadableListableStorage,
-        ReadableStorage, ReadableStorageTraits, ReadableWritableListableStorage,
-        ReadableWritableStorage, ReadableWritableStorageTraits, StorageError, StoreKey,
-        StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes, StorePrefix,
-        WritableStorage, WritableStorageTraits,
+        store_lock::StoreKeyMutex, GenerationToken, ListableStorage, ListableStorageTraits,
+        ReadableListableStorage, ReadableStorage, ReadableStorageTraits,
+        ReadableWritableListableStorage, ReadableWritableStorage, ReadableWritableStorageTraits,
+        StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes,
+        StorePrefix, WritableStorage, WritableStorageTraits,
     },
 };
 
+#[cfg(feature = "async")]
+use futures::stream::BoxStream;
+
 #[cfg(feature = "async")]
 use crate::storage::{
     store_lock::AsyncStoreKeyMutex, AsyncListableStorage, AsyncListableStorageTraits,
@@ -186,15 +190,29 @@ struct UsageLogStorageTransformerImpl<TStorage: ?Sized> {
 impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
     for UsageLogStorageTransformerImpl<TStorage>
 {
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        let result = self.storage.get_with_generation(key);
+        writeln!(
+            self.handle.lock().unwrap(),
+            "{}get_with_generation({key}) -> len={:?}",
+            (self.prefix_func)(),
+            result
+                .as_ref()
+                .map(|v| v.as_ref().map_or(0, |(bytes, _)| bytes.len()))
+        )?;
+        result
+    }
+
     fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
         let result = self.storage.get(key);
         writeln!(
             self.handle.lock().unwrap(),
             "{}get({key}) -> len={:?}",
             (self.prefix_func)(),
-            result
-                .as_ref()
-                .map(|v| v.as_ref().map_or(0, std::vec::Vec::len))
+            result.as_ref().map(|v| v.as_ref().map_or(0, Bytes::len))
         )?;
         result
     }
@@ -203,7 +221,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
         let result = self.storage.get_partial_values_key(key, byte_ranges);
         writeln!(
             self.handle.lock().unwrap(),
@@ -212,7 +230,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
             byte_ranges.iter().format(", "),
             result.as_ref().map(|v| {
                 v.as_ref()
-                    .map_or(vec![], |v| v.iter().map(std::vec::Vec::len).collect_vec())
+                    .map_or(vec![], |v| v.iter().map(Bytes::len).collect_vec())
             })
         )?;
         result
@@ -228,10 +246,9 @@ impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
             "{}get_partial_values([{}]) -> len={:?}",
             (self.prefix_func)(),
             key_ranges.iter().format(", "),
-            result.as_ref().map(|v| {
-                v.iter()
-                    .map(|v| v.iter().map(std::vec::Vec::len).collect_vec())
-            })
+            result
+                .as_ref()
+                .map(|v| { v.iter().map(|v| v.iter().map(Bytes::len).collect_vec()) })
         )?;
         result
     }
@@ -316,6 +333,22 @@ impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
 impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits
     for UsageLogStorageTransformerImpl<TStorage>
 {
+    fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: &[u8],
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        let result = self.storage.set_if_match(key, value, generation);
+        writeln!(
+            self.handle.lock().unwrap(),
+            "{}set_if_match({key}, len={}) -> {result:?}",
+            (self.prefix_func)(),
+            value.len()
+        )?;
+        result
+    }
+
     fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
         let result = self.storage.set(key, value);
         writeln!(
@@ -392,15 +425,29 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits> ReadableWritableStorageTr
 impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
     for UsageLogStorageTransformerImpl<TStorage>
 {
+    async fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        let result = self.storage.get_with_generation(key).await;
+        writeln!(
+            self.handle.lock().unwrap(),
+            "{}get_with_generation({key}) -> len={:?}",
+            (self.prefix_func)(),
+            result
+                .as_ref()
+                .map(|v| v.as_ref().map_or(0, |(bytes, _)| bytes.len()))
+        )?;
+        result
+    }
+
     async fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
         let result = self.storage.get(key).await;
         writeln!(
             self.handle.lock().unwrap(),
             "{}get({key}) -> len={:?}",
             (self.prefix_func)(),
-            result
-                .as_ref()
-                .map(|v| v.as_ref().map_or(0, std::vec::Vec::len))
+            result.as_ref().map(|v| v.as_ref().map_or(0, Bytes::len))
         )?;
         result
     }
@@ -409,7 +456,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
         let result = self.storage.get_partial_values_key(key, byte_ranges).await;
         writeln!(
             self.handle.lock().unwrap(),
@@ -418,7 +465,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
             byte_ranges.iter().format(", "),
             result.as_ref().map(|v| {
                 v.as_ref()
-                    .map_or(vec![], |v| v.iter().map(std::vec::Vec::len).collect_vec())
+                    .map_or(vec![], |v| v.iter().map(Bytes::len).collect_vec())
             })
         )?;
         result
@@ -434,10 +481,9 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
             "{}get_partial_values([{}]) -> len={:?}",
             (self.prefix_func)(),
             key_ranges.iter().format(", "),
-            result.as_ref().map(|v| {
-                v.iter()
-                    .map(|v| v.iter().map(std::vec::Vec::len).collect_vec())
-            })
+            result
+                .as_ref()
+                .map(|v| { v.iter().map(|v| v.iter().map(Bytes::len).collect_vec()) })
         )?;
         result
     }
@@ -526,6 +572,22 @@ impl<TStorage: ?Sized + AsyncListableStorageTraits> AsyncListableStorageTraits
 impl<TStorage: ?Sized + AsyncWritableStorageTraits> AsyncWritableStorageTraits
     for UsageLogStorageTransformerImpl<TStorage>
 {
+    async fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: Bytes,
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        let len = value.len();
+        let result = self.storage.set_if_match(key, value, generation).await;
+        writeln!(
+            self.handle.lock().unwrap(),
+            "{}set_if_match({key}, len={len}) -> {result:?}",
+            (self.prefix_func)()
+        )?;
+        result
+    }
+
     async fn set(&self, key: &StoreKey, value: bytes::Bytes) -> Result<(), StorageError> {
         let len = value.len();
         let result = self.storage.set(key, value).await;
@@ -537,6 +599,20 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits> AsyncWritableStorageTraits
         result
     }
 
+    async fn set_stream<'a>(
+        &self,
+        key: &StoreKey,
+        stream: BoxStream<'a, Result<Bytes, StorageError>>,
+    ) -> Result<(), StorageError> {
+        let result = self.storage.set_stream(key, stream).await;
+        writeln!(
+            self.handle.lock().unwrap(),
+            "{}set_stream({key}) -> {result:?}",
+            (self.prefix_func)()
+        )?;
+        result
+    }
+
     async fn set_partial_values(
         &self,
         key_start_values: &[StoreKeyStartValue],
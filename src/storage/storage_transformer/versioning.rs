@@ -0,0 +1,1033 @@
+//! A storage transformer which retains previous versions of store values.
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashSet},
+    sync::Arc,
+};
+
+use bytes::Bytes;
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    metadata::Metadata,
+    storage::{
+        store_lock::StoreKeyMutex, ListableStorage, ListableStorageTraits, ReadableListableStorage,
+        ReadableStorage, ReadableStorageTraits, ReadableWritableListableStorage,
+        ReadableWritableStorage, ReadableWritableStorageTraits, StorageError, StoreKey,
+        StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes, StorePrefix,
+        WritableStorage, WritableStorageTraits,
+    },
+};
+
+#[cfg(feature = "async")]
+use crate::storage::{
+    store_lock::AsyncStoreKeyMutex, AsyncListableStorage, AsyncListableStorageTraits,
+    AsyncReadableListableStorage, AsyncReadableStorage, AsyncReadableStorageTraits,
+    AsyncReadableWritableListableStorage, AsyncReadableWritableStorageTraits, AsyncWritableStorage,
+    AsyncWritableStorageTraits,
+};
+
+use super::StorageTransformerExtension;
+
+/// The default key of the manifest of a [`VersioningStorageTransformer`].
+pub const VERSIONING_MANIFEST_KEY: &str = "zarrs_versions.json";
+
+/// The generation of the value of each key in a version.
+type VersionKeys = BTreeMap<String, u64>;
+
+/// The changes to the keys of a version relative to the previous version.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct VersionDelta {
+    /// The generation of the keys which were set.
+    set: VersionKeys,
+    /// The keys which were erased.
+    erased: BTreeSet<String>,
+}
+
+impl VersionDelta {
+    /// Apply the changes to `keys`.
+    fn apply(&self, keys: &mut VersionKeys) {
+        for key in &self.erased {
+            keys.remove(key);
+        }
+        keys.extend(
+            self.set
+                .iter()
+                .map(|(key, generation)| (key.clone(), *generation)),
+        );
+    }
+}
+
+/// The manifest of a [`VersioningStorageTransformer`], stored at the manifest key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct VersionManifest {
+    /// The last generation when the last version was committed.
+    generation: u64,
+    /// The number of committed versions.
+    versions: usize,
+}
+
+/// The uncommitted head version of a [`VersioningStorageTransformer`], stored at the head key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HeadManifest<'a> {
+    /// The last generation.
+    generation: u64,
+    /// The changes to the keys of the last committed version.
+    delta: Cow<'a, VersionDelta>,
+}
+
+/// The versions of a [`VersioningStorageTransformer`].
+#[derive(Debug, Clone, Default)]
+struct VersionState {
+    /// The last generation.
+    generation: u64,
+    /// The changes of each committed version.
+    versions: Vec<VersionDelta>,
+    /// The keys of the last committed version.
+    committed: VersionKeys,
+    /// The keys of the uncommitted head version.
+    head: VersionKeys,
+    /// The changes of the head version relative to the last committed version.
+    head_delta: VersionDelta,
+}
+
+impl VersionState {
+    fn new(manifest: &VersionManifest, versions: Vec<VersionDelta>, head: HeadManifest) -> Self {
+        let mut committed = VersionKeys::default();
+        for version in &versions {
+            version.apply(&mut committed);
+        }
+        let head_delta = head.delta.into_owned();
+        let mut head_keys = committed.clone();
+        head_delta.apply(&mut head_keys);
+        Self {
+            generation: manifest.generation.max(head.generation),
+            versions,
+            committed,
+            head: head_keys,
+            head_delta,
+        }
+    }
+
+    /// Returns true if `generation` of `key` is referenced by the last committed version.
+    fn is_committed(&self, key: &str, generation: u64) -> bool {
+        self.committed.get(key) == Some(&generation)
+    }
+}
+
+/// Return the key of the value of `key` with `generation` in the underlying storage.
+fn generation_key(key: &str, generation: u64) -> Result<StoreKey, StorageError> {
+    Ok(StoreKey::new(format!("{key}~{generation}"))?)
+}
+
+/// Return the key of the changes of the committed `version` for the manifest at `manifest_key`.
+fn version_key(manifest_key: &StoreKey, version: usize) -> StoreKey {
+    // SAFETY: a valid key with a suffix is a valid key
+    unsafe { StoreKey::new_unchecked(format!("{manifest_key}~{version}")) }
+}
+
+/// Return the key of the uncommitted head version for the manifest at `manifest_key`.
+fn head_key(manifest_key: &StoreKey) -> StoreKey {
+    // SAFETY: a valid key with a suffix is a valid key
+    unsafe { StoreKey::new_unchecked(format!("{manifest_key}~head")) }
+}
+
+fn deserialize<T: DeserializeOwned>(key: &StoreKey, bytes: &[u8]) -> Result<T, StorageError> {
+    serde_json::from_slice(bytes)
+        .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))
+}
+
+fn serialize<T: Serialize>(key: &StoreKey, value: &T) -> Result<Vec<u8>, StorageError> {
+    serde_json::to_vec(value)
+        .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))
+}
+
+/// A committed version, returned by [`VersioningStorageTransformer::commit_head`].
+struct CommittedVersion {
+    version: usize,
+    version_key: StoreKey,
+    version_delta: Vec<u8>,
+    manifest: Vec<u8>,
+}
+
+/// The versioning storage transformer. Retains previous versions of store values so that a store can be read as of a committed version.
+///
+/// This storage transformer is for internal use and will not to be included in `storage_transformers` array metadata.
+///
+/// Every write through the transformer stores the value under a new generation-suffixed key (`{key}~{generation}`) in the underlying storage, rather than overwriting the value at `key`.
+/// The manifest in the underlying storage at the manifest key (e.g. [`VERSIONING_MANIFEST_KEY`]) records the number of committed versions.
+/// Each committed version is stored at `{manifest_key}~{version}` as the changes to the generation of the keys of the previous version.
+/// The changes of the uncommitted head version are stored at `{manifest_key}~head` and updated after every write, so the head version is persisted even if it is not committed.
+///
+/// [`commit`](VersioningStorageTransformer::commit) snapshots the head version as a new committed version.
+/// [`as_of`](VersioningStorageTransformer::as_of) returns a read only transformer for a committed version, e.g. to open an [`Array`](crate::array::Array) as it was when the version was committed.
+/// Values referenced by a committed version are never erased, values that are overwritten or erased before they are committed are erased from the underlying storage.
+///
+/// Values are written to the underlying storage concurrently, only updates of the head version are serialised.
+/// Partial writes ([`set_partial_values`](WritableStorageTraits::set_partial_values)) are not supported.
+/// The underlying storage should not be written to other than through the transformer.
+#[derive(Debug)]
+pub struct VersioningStorageTransformer {
+    manifest_key: StoreKey,
+    head_key: StoreKey,
+    read_only: bool,
+    state: Mutex<VersionState>,
+    write_lock: Mutex<()>,
+    #[cfg(feature = "async")]
+    async_write_lock: async_lock::Mutex<()>,
+}
+
+impl VersioningStorageTransformer {
+    /// Create a new versioning storage transformer with no versions and its manifest at `manifest_key`.
+    #[must_use]
+    pub fn new(manifest_key: StoreKey) -> Self {
+        Self::new_with_state(manifest_key, false, VersionState::default())
+    }
+
+    fn new_with_state(manifest_key: StoreKey, read_only: bool, state: VersionState) -> Self {
+        Self {
+            head_key: head_key(&manifest_key),
+            manifest_key,
+            read_only,
+            state: Mutex::new(state),
+            write_lock: Mutex::default(),
+            #[cfg(feature = "async")]
+            async_write_lock: async_lock::Mutex::default(),
+        }
+    }
+
+    /// Return the state from the `manifest`, the serialised changes of each committed version, and the serialised `head`.
+    fn state_from_bytes(
+        manifest_key: &StoreKey,
+        manifest: Option<Bytes>,
+        versions: impl IntoIterator<Item = (StoreKey, Option<Bytes>)>,
+        head: Option<Bytes>,
+    ) -> Result<VersionState, StorageError> {
+        let manifest: VersionManifest = manifest
+            .map(|bytes| deserialize(manifest_key, &bytes))
+            .transpose()?
+            .unwrap_or_default();
+        let versions = versions
+            .into_iter()
+            .take(manifest.versions)
+            .map(|(key, bytes)| {
+                let bytes = bytes.ok_or_else(|| {
+                    StorageError::InvalidMetadata(key.clone(), "version is missing".to_string())
+                })?;
+                deserialize(&key, &bytes)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if versions.len() != manifest.versions {
+            return Err(StorageError::InvalidMetadata(
+                manifest_key.clone(),
+                "versions are missing".to_string(),
+            ));
+        }
+        let head: HeadManifest = head
+            .map(|bytes| deserialize(&head_key(manifest_key), &bytes))
+            .transpose()?
+            .unwrap_or_default();
+        Ok(VersionState::new(&manifest, versions, head))
+    }
+
+    /// Return the number of committed versions in the manifest at `manifest_key`.
+    fn num_versions_from_bytes(
+        manifest_key: &StoreKey,
+        manifest: Option<&Bytes>,
+    ) -> Result<usize, StorageError> {
+        Ok(manifest
+            .map(|bytes| deserialize::<VersionManifest>(manifest_key, bytes))
+            .transpose()?
+            .unwrap_or_default()
+            .versions)
+    }
+
+    /// Open a versioning storage transformer with its manifest at `manifest_key` in `storage`.
+    ///
+    /// The transformer has no versions if the manifest does not exist.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the manifest is invalid or there is an underlying storage error.
+    pub fn open(
+        storage: &dyn ReadableStorageTraits,
+        manifest_key: StoreKey,
+    ) -> Result<Self, StorageError> {
+        let manifest = storage.get(&manifest_key)?;
+        let num_versions = Self::num_versions_from_bytes(&manifest_key, manifest.as_ref())?;
+        let versions = (0..num_versions)
+            .map(|version| {
+                let key = version_key(&manifest_key, version);
+                let bytes = storage.get(&key)?;
+                Ok((key, bytes))
+            })
+            .collect::<Result<Vec<_>, StorageError>>()?;
+        let head = storage.get(&head_key(&manifest_key))?;
+        let state = Self::state_from_bytes(&manifest_key, manifest, versions, head)?;
+        Ok(Self::new_with_state(manifest_key, false, state))
+    }
+
+    #[cfg(feature = "async")]
+    /// Async variant of [`open`](VersioningStorageTransformer::open).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_open(
+        storage: &dyn AsyncReadableStorageTraits,
+        manifest_key: StoreKey,
+    ) -> Result<Self, StorageError> {
+        let manifest = storage.get(&manifest_key).await?;
+        let num_versions = Self::num_versions_from_bytes(&manifest_key, manifest.as_ref())?;
+        let mut versions = Vec::with_capacity(num_versions);
+        for version in 0..num_versions {
+            let key = version_key(&manifest_key, version);
+            let bytes = storage.get(&key).await?;
+            versions.push((key, bytes));
+        }
+        let head = storage.get(&head_key(&manifest_key)).await?;
+        let state = Self::state_from_bytes(&manifest_key, manifest, versions, head)?;
+        Ok(Self::new_with_state(manifest_key, false, state))
+    }
+
+    /// Returns the manifest key.
+    #[must_use]
+    pub const fn manifest_key(&self) -> &StoreKey {
+        &self.manifest_key
+    }
+
+    /// Returns true if the transformer is read only (i.e. it was created with [`as_of`](VersioningStorageTransformer::as_of)).
+    #[must_use]
+    pub const fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns the number of committed versions.
+    #[must_use]
+    pub fn num_versions(&self) -> usize {
+        self.state.lock().versions.len()
+    }
+
+    /// Return a read only versioning storage transformer for the committed version `version`.
+    ///
+    /// Returns [`None`] if `version` has not been committed.
+    #[must_use]
+    pub fn as_of(&self, version: usize) -> Option<Self> {
+        let state = self.state.lock();
+        let mut head = VersionKeys::default();
+        for version in state.versions.get(..=version)? {
+            version.apply(&mut head);
+        }
+        Some(Self::new_with_state(
+            self.manifest_key.clone(),
+            true,
+            VersionState {
+                generation: state.generation,
+                head,
+                ..VersionState::default()
+            },
+        ))
+    }
+
+    /// Commit the head version and store it in `storage`.
+    ///
+    /// Returns the committed version.
+    ///
+    /// # Errors
+    /// Returns [`StorageError::ReadOnly`] if the transformer is read only, or a [`StorageError`] if there is an underlying storage error.
+    pub fn commit(&self, storage: &dyn WritableStorageTraits) -> Result<usize, StorageError> {
+        let _lock = self.write_lock.lock();
+        let committed = self.commit_head()?;
+        storage.set(&committed.version_key, &committed.version_delta)?;
+        storage.set(&self.manifest_key, &committed.manifest)?;
+        storage.erase(&self.head_key)?;
+        Ok(committed.version)
+    }
+
+    #[cfg(feature = "async")]
+    /// Async variant of [`commit`](VersioningStorageTransformer::commit).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_commit(
+        &self,
+        storage: &dyn AsyncWritableStorageTraits,
+    ) -> Result<usize, StorageError> {
+        let _lock = self.async_write_lock.lock().await;
+        let committed = self.commit_head()?;
+        storage
+            .set(&committed.version_key, committed.version_delta.into())
+            .await?;
+        storage
+            .set(&self.manifest_key, committed.manifest.into())
+            .await?;
+        storage.erase(&self.head_key).await?;
+        Ok(committed.version)
+    }
+
+    fn create_transformer<TStorage: ?Sized + 'static>(
+        self: Arc<Self>,
+        storage: Arc<TStorage>,
+    ) -> Arc<VersioningStorageTransformerImpl<TStorage>> {
+        Arc::new(VersioningStorageTransformerImpl {
+            storage,
+            transformer: self,
+        })
+    }
+
+    /// Return the serialised head version.
+    fn serialize_head(&self, state: &VersionState) -> Result<Vec<u8>, StorageError> {
+        serialize(
+            &self.head_key,
+            &HeadManifest {
+                generation: state.generation,
+                delta: Cow::Borrowed(&state.head_delta),
+            },
+        )
+    }
+
+    /// Snapshot the head version as a committed version, returning the version and its serialised changes and manifest.
+    fn commit_head(&self) -> Result<CommittedVersion, StorageError> {
+        if self.read_only {
+            return Err(StorageError::ReadOnly);
+        }
+        let mut state = self.state.lock();
+        let state = &mut *state;
+        let version = state.versions.len();
+        let version_key = version_key(&self.manifest_key, version);
+        let version_delta = serialize(&version_key, &state.head_delta)?;
+        let manifest = serialize(
+            &self.manifest_key,
+            &VersionManifest {
+                generation: state.generation,
+                versions: version + 1,
+            },
+        )?;
+        state.versions.push(std::mem::take(&mut state.head_delta));
+        state.committed.clone_from(&state.head);
+        Ok(CommittedVersion {
+            version,
+            version_key,
+            version_delta,
+            manifest,
+        })
+    }
+
+    /// Return the key of the value of `key` in the underlying storage, or [`None`] if `key` is not in the head version.
+    fn head_key(&self, key: &StoreKey) -> Result<Option<StoreKey>, StorageError> {
+        self.state
+            .lock()
+            .head
+            .get(key.as_str())
+            .map(|generation| generation_key(key.as_str(), *generation))
+            .transpose()
+    }
+
+    /// Return the keys of the head version with `prefix`, and their keys in the underlying storage.
+    fn head_keys(&self, prefix: &StorePrefix) -> Result<Vec<(StoreKey, StoreKey)>, StorageError> {
+        self.state
+            .lock()
+            .head
+            .range(prefix.as_str().to_string()..)
+            .take_while(|(key, _)| key.starts_with(prefix.as_str()))
+            .map(|(key, generation)| Ok((StoreKey::new(key)?, generation_key(key, *generation)?)))
+            .collect()
+    }
+
+    /// Return the key and generation for writing a new value of `key` in the underlying storage.
+    fn next_generation(&self, key: &StoreKey) -> Result<(StoreKey, u64), StorageError> {
+        if self.read_only {
+            return Err(StorageError::ReadOnly);
+        }
+        let mut state = self.state.lock();
+        state.generation += 1;
+        let generation = state.generation;
+        Ok((generation_key(key.as_str(), generation)?, generation))
+    }
+
+    /// Update the head version with the value of `key` at `generation`.
+    ///
+    /// Returns the serialised head version and the keys of uncommitted values in the underlying storage which are no longer referenced.
+    fn head_set(
+        &self,
+        key: &StoreKey,
+        generation: u64,
+    ) -> Result<(Vec<u8>, Vec<StoreKey>), StorageError> {
+        let mut state = self.state.lock();
+        let replaced = state.head.insert(key.to_string(), generation);
+        state.head_delta.set.insert(key.to_string(), generation);
+        state.head_delta.erased.remove(key.as_str());
+        let unreferenced = replaced
+            .filter(|generation| !state.is_committed(key.as_str(), *generation))
+            .map(|generation| generation_key(key.as_str(), generation))
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((self.serialize_head(&state)?, unreferenced))
+    }
+
+    /// Erase the keys matching `erased` from the head version.
+    ///
+    /// Returns the serialised head version and the keys of uncommitted values in the underlying storage which are no longer referenced.
+    fn head_erase(
+        &self,
+        erased: impl Fn(&str) -> bool,
+    ) -> Result<(Vec<u8>, Vec<StoreKey>), StorageError> {
+        if self.read_only {
+            return Err(StorageError::ReadOnly);
+        }
+        let mut state = self.state.lock();
+        let state = &mut *state;
+        let mut removed = vec![];
+        state.head.retain(|key, generation| {
+            if erased(key) {
+                removed.push((key.clone(), *generation));
+                false
+            } else {
+                true
+            }
+        });
+        for (key, _) in &removed {
+            state.head_delta.set.remove(key);
+            if state.committed.contains_key(key) {
+                state.head_delta.erased.insert(key.clone());
+            }
+        }
+        let unreferenced = removed
+            .into_iter()
+            .filter(|(key, generation)| !state.is_committed(key, *generation))
+            .map(|(key, generation)| generation_key(&key, generation))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((self.serialize_head(state)?, unreferenced))
+    }
+}
+
+impl StorageTransformerExtension for VersioningStorageTransformer {
+    /// Returns [`None`], since this storage transformer is not intended to be included in array `storage_transformers` metadata.
+    fn create_metadata(&self) -> Option<Metadata> {
+        None
+    }
+
+    fn create_readable_transformer(self: Arc<Self>, storage: ReadableStorage) -> ReadableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_writable_transformer(self: Arc<Self>, storage: WritableStorage) -> WritableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_readable_writable_transformer(
+        self: Arc<Self>,
+        storage: ReadableWritableStorage,
+    ) -> ReadableWritableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_listable_transformer(self: Arc<Self>, storage: ListableStorage) -> ListableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_readable_listable_transformer(
+        self: Arc<Self>,
+        storage: ReadableListableStorage,
+    ) -> ReadableListableStorage {
+        self.create_transformer(storage)
+    }
+
+    fn create_readable_writable_listable_transformer(
+        self: Arc<Self>,
+        storage: ReadableWritableListableStorage,
+    ) -> ReadableWritableListableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_readable_transformer(
+        self: Arc<Self>,
+        storage: AsyncReadableStorage,
+    ) -> AsyncReadableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_writable_transformer(
+        self: Arc<Self>,
+        storage: AsyncWritableStorage,
+    ) -> AsyncWritableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_listable_transformer(
+        self: Arc<Self>,
+        storage: AsyncListableStorage,
+    ) -> AsyncListableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_readable_listable_transformer(
+        self: Arc<Self>,
+        storage: AsyncReadableListableStorage,
+    ) -> AsyncReadableListableStorage {
+        self.create_transformer(storage)
+    }
+
+    #[cfg(feature = "async")]
+    fn create_async_readable_writable_listable_transformer(
+        self: Arc<Self>,
+        storage: AsyncReadableWritableListableStorage,
+    ) -> AsyncReadableWritableListableStorage {
+        self.create_transformer(storage)
+    }
+}
+
+#[derive(Debug)]
+struct VersioningStorageTransformerImpl<TStorage: ?Sized + 'static> {
+    storage: Arc<TStorage>,
+    transformer: Arc<VersioningStorageTransformer>,
+}
+
+impl<TStorage: ?Sized> VersioningStorageTransformerImpl<TStorage> {
+    fn list_prefix_head(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        Ok(self
+            .transformer
+            .head_keys(prefix)?
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect())
+    }
+
+    fn list_dir_head(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        let mut keys: StoreKeys = vec![];
+        let mut prefixes: BTreeSet<StorePrefix> = BTreeSet::default();
+        for key in self.list_prefix_head(prefix)? {
+            let key_strip = key.as_str().strip_prefix(prefix.as_str()).unwrap();
+            if let Some((child, _)) = key_strip.split_once('/') {
+                prefixes.insert(StorePrefix::new(prefix.as_str().to_string() + child + "/")?);
+            } else {
+                keys.push(key);
+            }
+        }
+        Ok(StoreKeysPrefixes {
+            keys,
+            prefixes: prefixes.into_iter().collect(),
+        })
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for VersioningStorageTransformerImpl<TStorage>
+{
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        match self.transformer.head_key(key)? {
+            Some(key) => self.storage.get(&key),
+            None => Ok(None),
+        }
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        match self.transformer.head_key(key)? {
+            Some(key) => self.storage.get_partial_values_key(&key, byte_ranges),
+            None => Ok(None),
+        }
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges)
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        let mut size = 0;
+        for (_, key) in self.transformer.head_keys(prefix)? {
+            size += self.storage.size_key(&key)?.unwrap_or_default();
+        }
+        Ok(size)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        match self.transformer.head_key(key)? {
+            Some(key) => self.storage.size_key(&key),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<TStorage: ?Sized> ListableStorageTraits for VersioningStorageTransformerImpl<TStorage>
+where
+    TStorage: ListableStorageTraits,
+{
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.list_prefix_head(&StorePrefix::root())
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.list_prefix_head(prefix)
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.list_dir_head(prefix)
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits
+    for VersioningStorageTransformerImpl<TStorage>
+{
+    fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        let (generation_key, generation) = self.transformer.next_generation(key)?;
+        self.storage.set(&generation_key, value)?;
+        let unreferenced = {
+            let _lock = self.transformer.write_lock.lock();
+            let (head, unreferenced) = self.transformer.head_set(key, generation)?;
+            self.storage.set(&self.transformer.head_key, &head)?;
+            unreferenced
+        };
+        self.storage.erase_values(&unreferenced)
+    }
+
+    fn set_partial_values(
+        &self,
+        _key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        Err(StorageError::Unsupported(
+            "the versioning storage transformer does not support partial writes".to_string(),
+        ))
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.erase_values(std::slice::from_ref(key))
+    }
+
+    fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        let keys: HashSet<&str> = keys.iter().map(StoreKey::as_str).collect();
+        let unreferenced = {
+            let _lock = self.transformer.write_lock.lock();
+            let (head, unreferenced) = self
+                .transformer
+                .head_erase(|erased| keys.contains(erased))?;
+            self.storage.set(&self.transformer.head_key, &head)?;
+            unreferenced
+        };
+        self.storage.erase_values(&unreferenced)
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        let unreferenced = {
+            let _lock = self.transformer.write_lock.lock();
+            let (head, unreferenced) = self
+                .transformer
+                .head_erase(|erased| erased.starts_with(prefix.as_str()))?;
+            self.storage.set(&self.transformer.head_key, &head)?;
+            unreferenced
+        };
+        self.storage.erase_values(&unreferenced)
+    }
+}
+
+impl<TStorage: ?Sized + ReadableWritableStorageTraits> ReadableWritableStorageTraits
+    for VersioningStorageTransformerImpl<TStorage>
+{
+    fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+        self.storage.mutex(key)
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
+    for VersioningStorageTransformerImpl<TStorage>
+{
+    async fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        match self.transformer.head_key(key)? {
+            Some(key) => self.storage.get(&key).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        match self.transformer.head_key(key)? {
+            Some(key) => self.storage.get_partial_values_key(&key, byte_ranges).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges).await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.size_prefix(&StorePrefix::root()).await
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        let mut size = 0;
+        for (_, key) in self.transformer.head_keys(prefix)? {
+            size += self.storage.size_key(&key).await?.unwrap_or_default();
+        }
+        Ok(size)
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        match self.transformer.head_key(key)? {
+            Some(key) => self.storage.size_key(&key).await,
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncListableStorageTraits> AsyncListableStorageTraits
+    for VersioningStorageTransformerImpl<TStorage>
+{
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.list_prefix_head(&StorePrefix::root())
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.list_prefix_head(prefix)
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.list_dir_head(prefix)
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncWritableStorageTraits> AsyncWritableStorageTraits
+    for VersioningStorageTransformerImpl<TStorage>
+{
+    async fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), StorageError> {
+        let (generation_key, generation) = self.transformer.next_generation(key)?;
+        self.storage.set(&generation_key, value).await?;
+        let unreferenced = {
+            let _lock = self.transformer.async_write_lock.lock().await;
+            let (head, unreferenced) = self.transformer.head_set(key, generation)?;
+            self.storage
+                .set(&self.transformer.head_key, head.into())
+                .await?;
+            unreferenced
+        };
+        self.storage.erase_values(&unreferenced).await
+    }
+
+    async fn set_partial_values(
+        &self,
+        _key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        Err(StorageError::Unsupported(
+            "the versioning storage transformer does not support partial writes".to_string(),
+        ))
+    }
+
+    async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.erase_values(std::slice::from_ref(key)).await
+    }
+
+    async fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        let keys: HashSet<&str> = keys.iter().map(StoreKey::as_str).collect();
+        let unreferenced = {
+            let _lock = self.transformer.async_write_lock.lock().await;
+            let (head, unreferenced) = self
+                .transformer
+                .head_erase(|erased| keys.contains(erased))?;
+            self.storage
+                .set(&self.transformer.head_key, head.into())
+                .await?;
+            unreferenced
+        };
+        self.storage.erase_values(&unreferenced).await
+    }
+
+    async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        let unreferenced = {
+            let _lock = self.transformer.async_write_lock.lock().await;
+            let (head, unreferenced) = self
+                .transformer
+                .head_erase(|erased| erased.starts_with(prefix.as_str()))?;
+            self.storage
+                .set(&self.transformer.head_key, head.into())
+                .await?;
+            unreferenced
+        };
+        self.storage.erase_values(&unreferenced).await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits> AsyncReadableWritableStorageTraits
+    for VersioningStorageTransformerImpl<TStorage>
+{
+    async fn mutex(&self, key: &StoreKey) -> Result<AsyncStoreKeyMutex, StorageError> {
+        self.storage.mutex(key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{Array, ArrayBuilder, DataType, FillValue},
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn versioning_storage_transformer() {
+        let store = Arc::new(MemoryStore::new());
+        let manifest_key = StoreKey::new(VERSIONING_MANIFEST_KEY).unwrap();
+        let versioning = Arc::new(VersioningStorageTransformer::new(manifest_key.clone()));
+        let storage = versioning
+            .clone()
+            .create_readable_writable_listable_transformer(store.clone());
+
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(storage.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        array
+            .store_chunk_elements::<u8>(&[0, 0], vec![1; 4])
+            .unwrap();
+        array
+            .store_chunk_elements::<u8>(&[0, 0], vec![2; 4])
+            .unwrap();
+        assert_eq!(versioning.commit(&*store).unwrap(), 0);
+
+        array
+            .store_chunk_elements::<u8>(&[0, 0], vec![3; 4])
+            .unwrap();
+        array
+            .store_chunk_elements::<u8>(&[1, 1], vec![4; 4])
+            .unwrap();
+        assert_eq!(versioning.commit(&*store).unwrap(), 1);
+        array.erase_chunk(&[0, 0]).unwrap();
+
+        // The overwritten uncommitted value of the first version is erased
+        assert_eq!(
+            store.list().unwrap(),
+            [
+                "array/c/0/0~3",
+                "array/c/0/0~4",
+                "array/c/1/1~5",
+                "array/zarr.json~1",
+                "zarrs_versions.json",
+                "zarrs_versions.json~0",
+                "zarrs_versions.json~1",
+                "zarrs_versions.json~head"
+            ]
+            .map(|key| StoreKey::new(key).unwrap())
+        );
+        assert_eq!(
+            storage.list().unwrap(),
+            ["array/c/1/1", "array/zarr.json"].map(|key| StoreKey::new(key).unwrap())
+        );
+
+        // Versions only store the changes to the previous version
+        let version_1: VersionDelta = serde_json::from_slice(
+            &store
+                .get(&StoreKey::new("zarrs_versions.json~1").unwrap())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            version_1.set.keys().collect::<Vec<_>>(),
+            ["array/c/0/0", "array/c/1/1"]
+        );
+        assert!(version_1.erased.is_empty());
+
+        let versioning = VersioningStorageTransformer::open(&*store, manifest_key).unwrap();
+        assert_eq!(versioning.num_versions(), 2);
+        // The uncommitted head version is restored
+        let versioning = Arc::new(versioning);
+        assert_eq!(
+            versioning
+                .clone()
+                .create_readable_writable_listable_transformer(store.clone())
+                .list()
+                .unwrap(),
+            ["array/c/1/1", "array/zarr.json"].map(|key| StoreKey::new(key).unwrap())
+        );
+        assert!(versioning.as_of(2).is_none());
+        let chunk_elements = |version: usize| {
+            let versioning = Arc::new(versioning.as_of(version).unwrap());
+            assert!(versioning.read_only());
+            let storage = versioning.create_readable_writable_listable_transformer(store.clone());
+            let array = Array::new(storage.clone(), "/array").unwrap();
+            assert!(matches!(
+                storage.set(&StoreKey::new("a").unwrap(), &[]),
+                Err(StorageError::ReadOnly)
+            ));
+            [
+                array.retrieve_chunk_elements::<u8>(&[0, 0]).unwrap(),
+                array.retrieve_chunk_elements::<u8>(&[1, 1]).unwrap(),
+            ]
+        };
+        assert_eq!(chunk_elements(0), [vec![2; 4], vec![0; 4]]);
+        assert_eq!(chunk_elements(1), [vec![3; 4], vec![4; 4]]);
+        assert_eq!(
+            array.retrieve_chunk_elements::<u8>(&[0, 0]).unwrap(),
+            vec![0; 4]
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn versioning_storage_transformer_async() {
+        let store = Arc::new(crate::storage::store::AsyncObjectStore::new(
+            object_store::memory::InMemory::new(),
+        ));
+        let manifest_key = StoreKey::new(VERSIONING_MANIFEST_KEY).unwrap();
+        let versioning = Arc::new(VersioningStorageTransformer::new(manifest_key.clone()));
+        let storage = versioning
+            .clone()
+            .create_async_readable_writable_listable_transformer(store.clone());
+
+        // Values are written concurrently
+        let keys = (0..8)
+            .map(|i| StoreKey::new(format!("key{i}")).unwrap())
+            .collect::<Vec<_>>();
+        futures::future::try_join_all(
+            keys.iter()
+                .map(|key| storage.set(key, Bytes::from_static(&[0]))),
+        )
+        .await
+        .unwrap();
+        assert_eq!(versioning.async_commit(&*store).await.unwrap(), 0);
+        storage.erase_values(&keys[..4]).await.unwrap();
+        storage
+            .set(&keys[4], Bytes::from_static(&[1]))
+            .await
+            .unwrap();
+
+        let versioning = VersioningStorageTransformer::async_open(&*store, manifest_key)
+            .await
+            .unwrap();
+        assert_eq!(versioning.num_versions(), 1);
+        let storage =
+            Arc::new(versioning).create_async_readable_listable_transformer(store.clone());
+        assert_eq!(storage.list().await.unwrap(), keys[4..]);
+        assert_eq!(
+            storage.get(&keys[4]).await.unwrap(),
+            Some(Bytes::from_static(&[1]))
+        );
+    }
+}
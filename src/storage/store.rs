@@ -8,20 +8,45 @@ mod store_async;
 mod store_sync;
 // mod store_plugin;
 
-pub use store_sync::filesystem_store::{FilesystemStore, FilesystemStoreCreateError};
+pub use store_sync::filesystem_store::{
+    FilesystemStore, FilesystemStoreCreateError, FilesystemStoreOptions,
+};
 pub use store_sync::memory_store::MemoryStore;
+pub use store_sync::mount_store::MountStore;
+pub use store_sync::overlay_store::OverlayStore;
 
 #[cfg(feature = "http")]
-pub use store_sync::http_store::{HTTPStore, HTTPStoreCreateError};
+pub use store_sync::http_store::{HTTPStore, HTTPStoreBuilder, HTTPStoreCreateError};
 
+#[cfg(feature = "lmdb")]
+pub use store_sync::lmdb_store::{LmdbStore, LmdbStoreCreateError, LMDB_STORE_DEFAULT_MAP_SIZE};
+
+#[cfg(all(feature = "async", feature = "object_store"))]
+pub use store_async::object_store::{
+    AsyncObjectStore, ASYNC_OBJECT_STORE_DEFAULT_MULTIPART_THRESHOLD,
+};
 #[cfg(feature = "object_store")]
-pub use store_async::object_store::AsyncObjectStore;
+pub use store_sync::object_store::ObjectStore;
 
 #[cfg(feature = "opendal")]
 pub use store_async::opendal::AsyncOpendalStore;
 #[cfg(feature = "opendal")]
 pub use store_sync::opendal::OpendalStore;
 
+#[cfg(feature = "fetch")]
+pub use store_async::fetch::{FetchStore, FetchStoreCreateError};
+#[cfg(feature = "wasm")]
+pub use store_async::opfs::OpfsStore;
+
+#[cfg(feature = "grpc")]
+pub use store_async::grpc::{AsyncGrpcStore, AsyncGrpcStoreCreateError, GrpcStoreService};
+
+#[cfg(feature = "tar")]
+pub use store_sync::tar_store::{TarStore, TarStoreCreateError};
+
+#[cfg(feature = "zip")]
+pub use store_sync::zip_store::{ZipStore, ZipStoreCreateError};
+
 // pub use store_plugin::{StorePlugin, StorePluginCreateError}; // Currently disabled.
 
 // /// A readable store plugin.
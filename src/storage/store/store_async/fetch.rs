@@ -0,0 +1,269 @@
+//! An asynchronous HTTP store for WebAssembly in the browser, backed by the [Fetch API](https://developer.mozilla.org/en-US/docs/Web/API/Fetch_API).
+
+use std::{ops::Range, str::FromStr};
+
+use bytes::Bytes;
+use send_wrapper::SendWrapper;
+use thiserror::Error;
+use url::Url;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    storage::{AsyncReadableStorageTraits, StorageError, StoreKey, StoreKeyRange, StorePrefix},
+};
+
+/// An asynchronous HTTP store for WebAssembly in the browser.
+///
+/// Requests are made with the [Fetch API](https://developer.mozilla.org/en-US/docs/Web/API/Fetch_API) of the global scope, which can be a window or a web worker.
+/// This store is intended for the `wasm32-unknown-unknown` target, and requests fail on other targets.
+///
+/// Partial reads use single part range requests, which are issued concurrently.
+/// Cross-origin servers must allow the `Range` request header and expose the `Content-Length` response header (CORS) for partial reads.
+///
+/// Fetch futures are not [`Send`], so they are wrapped in a [`SendWrapper`] to satisfy the bounds of the [`AsyncReadableStorageTraits`].
+/// The store must only be used from the thread it was created on, which is always the case without WebAssembly threads.
+#[derive(Debug)]
+pub struct FetchStore {
+    base_url: Url,
+    headers: Vec<(String, String)>,
+}
+
+/// A fetch store creation error.
+#[derive(Debug, Error)]
+pub enum FetchStoreCreateError {
+    /// The URL is not valid.
+    #[error("base URL {0} is not valid")]
+    InvalidBaseURL(String),
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub(super) fn js_error(err: JsValue) -> StorageError {
+    StorageError::Other(format!("fetch error: {err:?}"))
+}
+
+impl FetchStore {
+    /// Create a new fetch store at a given `base_url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FetchStoreCreateError`] if `base_url` is not a valid URL.
+    pub fn new(base_url: &str) -> Result<Self, FetchStoreCreateError> {
+        let base_url = Url::from_str(base_url)
+            .map_err(|_| FetchStoreCreateError::InvalidBaseURL(base_url.into()))?;
+        Ok(Self {
+            base_url,
+            headers: Vec::new(),
+        })
+    }
+
+    /// Add a header to all requests (e.g. `Authorization`).
+    #[must_use]
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Maps a [`StoreKey`] to a HTTP [`Url`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is invalid.
+    pub fn key_to_url(&self, key: &StoreKey) -> Result<Url, url::ParseError> {
+        let mut url = self.base_url.as_str().to_string();
+        if !key.as_str().is_empty() {
+            url +=
+                ("/".to_string() + key.as_str().strip_prefix('/').unwrap_or(key.as_str())).as_str();
+        }
+        Url::parse(&url)
+    }
+
+    /// Fetch the resource at `url` with `method` and an optional byte `range`.
+    async fn fetch(
+        &self,
+        url: &Url,
+        method: &str,
+        range: Option<&Range<u64>>,
+    ) -> Result<web_sys::Response, StorageError> {
+        let headers = web_sys::Headers::new().map_err(js_error)?;
+        for (name, value) in &self.headers {
+            headers.set(name, value).map_err(js_error)?;
+        }
+        if let Some(range) = range {
+            headers
+                .set("Range", &format!("bytes={}-{}", range.start, range.end - 1))
+                .map_err(js_error)?;
+        }
+        let init = web_sys::RequestInit::new();
+        init.set_method(method);
+        init.set_headers(&headers);
+        let request =
+            web_sys::Request::new_with_str_and_init(url.as_str(), &init).map_err(js_error)?;
+
+        // The global scope is either a window or a web worker
+        let global = js_sys::global();
+        let promise = if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+            window.fetch_with_request(&request)
+        } else if let Some(worker) = global.dyn_ref::<web_sys::WorkerGlobalScope>() {
+            worker.fetch_with_request(&request)
+        } else {
+            return Err(StorageError::Other(
+                "the fetch API is not available in the global scope".to_string(),
+            ));
+        };
+        let response = JsFuture::from(promise).await.map_err(js_error)?;
+        response.dyn_into().map_err(js_error)
+    }
+
+    /// Return the body of `response`.
+    async fn response_bytes(response: &web_sys::Response) -> Result<Bytes, StorageError> {
+        let buffer = JsFuture::from(response.array_buffer().map_err(js_error)?)
+            .await
+            .map_err(js_error)?;
+        Ok(js_sys::Uint8Array::new(&buffer).to_vec().into())
+    }
+
+    /// Retrieve a single byte `range` of the resource at `url`.
+    async fn get_range(&self, url: &Url, range: &Range<u64>) -> Result<Bytes, StorageError> {
+        if range.is_empty() {
+            return Ok(Bytes::new());
+        }
+        let response = self.fetch(url, "GET", Some(range)).await?;
+        match response.status() {
+            206 => {
+                let bytes = Self::response_bytes(&response).await?;
+                if bytes.len() as u64 == range.end - range.start {
+                    Ok(bytes)
+                } else {
+                    Err(StorageError::from(
+                        "http partial content response did not include the requested byte range",
+                    ))
+                }
+            }
+            200 => {
+                // Received all bytes
+                let bytes = Self::response_bytes(&response).await?;
+                let start = usize::try_from(range.start).unwrap();
+                let end = usize::try_from(range.end).unwrap();
+                if end <= bytes.len() {
+                    Ok(bytes.slice(start..end))
+                } else {
+                    Err(StorageError::from(
+                        "http response did not include the requested byte range",
+                    ))
+                }
+            }
+            status => Err(StorageError::from(format!(
+                "the http server responded with status {status} for the byte range request"
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncReadableStorageTraits for FetchStore {
+    async fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        let url = self
+            .key_to_url(key)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+        SendWrapper::new(async move {
+            let response = self.fetch(&url, "GET", None).await?;
+            match response.status() {
+                200 => Ok(Some(Self::response_bytes(&response).await?)),
+                404 => Ok(None),
+                status => Err(StorageError::from(format!(
+                    "http unexpected status code: {status}"
+                ))),
+            }
+        })
+        .await
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let url = self
+            .key_to_url(key)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+        let Some(size) = self.size_key(key).await? else {
+            return Ok(None);
+        };
+        let ranges: Vec<Range<u64>> = byte_ranges
+            .iter()
+            .map(|byte_range| byte_range.to_range(size))
+            .collect();
+        SendWrapper::new(async move {
+            let futures = ranges.iter().map(|range| self.get_range(&url, range));
+            Ok(Some(futures::future::try_join_all(futures).await?))
+        })
+        .await
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges).await
+    }
+
+    async fn size_prefix(&self, _prefix: &StorePrefix) -> Result<u64, StorageError> {
+        Err(StorageError::Unsupported(
+            "size_prefix() not supported for fetch store".into(),
+        ))
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        let url = self
+            .key_to_url(key)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+        SendWrapper::new(async move {
+            let response = self.fetch(&url, "HEAD", None).await?;
+            match response.status() {
+                200 => {
+                    let length = response
+                        .headers()
+                        .get("Content-Length")
+                        .map_err(js_error)?
+                        .and_then(|header_str| u64::from_str(&header_str).ok())
+                        .ok_or_else(|| StorageError::from("content length response is invalid"))?;
+                    Ok(Some(length))
+                }
+                404 => Ok(None),
+                status => Err(StorageError::from(format!(
+                    "http size_key has status code {status}"
+                ))),
+            }
+        })
+        .await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        Err(StorageError::Unsupported(
+            "size() not supported for fetch store".into(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fetch_store_key_to_url() {
+        assert!(FetchStore::new("not a url").is_err());
+        let store = FetchStore::new("https://example.com/data.zarr")
+            .unwrap()
+            .with_header("Authorization", "Bearer token");
+        assert_eq!(
+            store
+                .key_to_url(&StoreKey::new("array/zarr.json").unwrap())
+                .unwrap()
+                .as_str(),
+            "https://example.com/data.zarr/array/zarr.json"
+        );
+    }
+}
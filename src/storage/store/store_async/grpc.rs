@@ -0,0 +1,605 @@
+//! An asynchronous store and server communicating over [gRPC](https://grpc.io/).
+//!
+//! A [`GrpcStoreService`] serves any asynchronous readable and listable store, so a central data service can serve chunk bytes to many clients.
+//! An [`AsyncGrpcStore`] is a client of the service.
+//!
+//! The service implements the `zarrs.store.v1.Store` protocol defined in `proto/zarrs/store/v1/store.proto` of the `zarrs` repository, so clients and servers can also be implemented in other languages.
+//! The client and server are generated from the protocol definition with [`tonic-build`](https://docs.rs/tonic-build).
+//!
+//! Errors are mapped to gRPC status codes, so clients can distinguish permanent errors (e.g. `INVALID_ARGUMENT` or `UNIMPLEMENTED`) from transient errors (e.g. `UNAVAILABLE`) which can be retried.
+
+// The server methods return a large `tonic::Status` error, as required by the generated `Store` trait
+#![allow(clippy::result_large_err)]
+
+use std::{convert::Infallible, sync::Arc};
+
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use itertools::Itertools;
+use thiserror::Error;
+use tonic::{
+    body::BoxBody,
+    codegen::{http, Body, BoxFuture, BoxStream, Context, Poll, Service, StdError},
+    server::NamedService,
+    transport::{Channel, Endpoint},
+    Code, Request, Response, Status,
+};
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    storage::{
+        storage_adapter::RetryPolicy, AsyncListableStorageTraits, AsyncReadableStorageTraits,
+        StorageError, StoreKey, StoreKeyRange, StoreKeys, StoreKeysPrefixes, StorePrefix,
+    },
+};
+
+/// The client, server, and messages of the `zarrs.store.v1` protocol, generated by `tonic-build`.
+#[allow(missing_docs, clippy::all, clippy::pedantic)]
+mod proto {
+    tonic::include_proto!("zarrs.store.v1");
+}
+
+use proto::{store_client::StoreClient, store_server::StoreServer};
+
+impl From<ByteRange> for proto::ByteRange {
+    fn from(byte_range: ByteRange) -> Self {
+        match byte_range {
+            ByteRange::FromStart(offset, length) => Self {
+                offset,
+                length,
+                from_end: false,
+            },
+            ByteRange::FromEnd(offset, length) => Self {
+                offset,
+                length,
+                from_end: true,
+            },
+        }
+    }
+}
+
+impl From<proto::ByteRange> for ByteRange {
+    fn from(byte_range: proto::ByteRange) -> Self {
+        if byte_range.from_end {
+            Self::FromEnd(byte_range.offset, byte_range.length)
+        } else {
+            Self::FromStart(byte_range.offset, byte_range.length)
+        }
+    }
+}
+
+/// Convert a gRPC status returned by a [`GrpcStoreService`] to a [`StorageError`].
+///
+/// Statuses indicating that the service is temporarily unable to respond are returned as [`StorageError::Transient`].
+/// Other statuses are permanent and are not retried by the default [`RetryPolicy`].
+fn status_to_storage_error(status: &Status) -> StorageError {
+    let message = format!("gRPC error {:?}: {}", status.code(), status.message());
+    match status.code() {
+        Code::Unavailable | Code::DeadlineExceeded | Code::ResourceExhausted | Code::Aborted => {
+            StorageError::Transient(message)
+        }
+        Code::Unimplemented => StorageError::Unsupported(message),
+        Code::InvalidArgument
+        | Code::NotFound
+        | Code::AlreadyExists
+        | Code::PermissionDenied
+        | Code::Unauthenticated
+        | Code::FailedPrecondition
+        | Code::OutOfRange
+        | Code::Cancelled
+        | Code::DataLoss
+        | Code::Internal
+        | Code::Unknown
+        | Code::Ok => StorageError::Other(message),
+    }
+}
+
+/// Convert a [`StorageError`] to a gRPC status.
+fn storage_error_to_status(err: &StorageError) -> Status {
+    let code = match err {
+        StorageError::InvalidStoreKey(_)
+        | StorageError::StorePrefixError(_)
+        | StorageError::InvalidByteRangeError(_) => Code::InvalidArgument,
+        StorageError::Unsupported(_) => Code::Unimplemented,
+        StorageError::ReadOnly => Code::PermissionDenied,
+        err if RetryPolicy::is_transient_default(err) => Code::Unavailable,
+        _ => Code::Internal,
+    };
+    Status::new(code, err.to_string())
+}
+
+/// An asynchronous store which retrieves values from a [`GrpcStoreService`].
+///
+/// The store is read-only and listable.
+/// [`get_partial_values`](AsyncReadableStorageTraits::get_partial_values) retrieves all key ranges with a single streaming request.
+/// Transient errors of the service (e.g. an unavailable service) are returned as [`StorageError::Transient`], so they are retried by a [`RetryPolicy`].
+#[derive(Debug, Clone)]
+pub struct AsyncGrpcStore {
+    client: StoreClient<Channel>,
+}
+
+/// An asynchronous gRPC store creation error.
+#[derive(Debug, Error)]
+pub enum AsyncGrpcStoreCreateError {
+    /// The endpoint is not a valid URI.
+    #[error("endpoint {0} is not valid")]
+    InvalidEndpoint(String),
+    /// The connection to the endpoint failed.
+    #[error(transparent)]
+    TransportError(#[from] tonic::transport::Error),
+}
+
+impl AsyncGrpcStore {
+    /// Create a new gRPC store communicating over `channel`.
+    ///
+    /// Use a [`Channel`] to configure the connection (e.g. timeouts, TLS, or load balancing across many servers).
+    #[must_use]
+    pub fn new(channel: Channel) -> Self {
+        let client = StoreClient::new(channel).max_decoding_message_size(usize::MAX);
+        Self { client }
+    }
+
+    /// Create a new gRPC store connected to the server at `endpoint` (e.g. `http://[::1]:50051`).
+    ///
+    /// # Errors
+    /// Returns an [`AsyncGrpcStoreCreateError`] if `endpoint` is not a valid URI or the connection fails.
+    pub async fn connect(endpoint: &str) -> Result<Self, AsyncGrpcStoreCreateError> {
+        let channel = Endpoint::from_shared(endpoint.to_string())
+            .map_err(|_| AsyncGrpcStoreCreateError::InvalidEndpoint(endpoint.to_string()))?
+            .connect()
+            .await?;
+        Ok(Self::new(channel))
+    }
+
+    fn keys_prefixes(response: proto::ListResponse) -> Result<StoreKeysPrefixes, StorageError> {
+        let keys = response
+            .keys
+            .into_iter()
+            .map(StoreKey::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        let prefixes = response
+            .prefixes
+            .into_iter()
+            .map(StorePrefix::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(StoreKeysPrefixes { keys, prefixes })
+    }
+}
+
+fn key_request(key: &StoreKey) -> proto::KeyRequest {
+    proto::KeyRequest {
+        key: key.as_str().to_string(),
+    }
+}
+
+fn prefix_request(prefix: &StorePrefix) -> proto::PrefixRequest {
+    proto::PrefixRequest {
+        prefix: prefix.as_str().to_string(),
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl AsyncReadableStorageTraits for AsyncGrpcStore {
+    async fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        let response = self
+            .client
+            .clone()
+            .get(key_request(key))
+            .await
+            .map_err(|status| status_to_storage_error(&status))?;
+        Ok(response.into_inner().value)
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let key_ranges = byte_ranges
+            .iter()
+            .map(|byte_range| StoreKeyRange::new(key.clone(), *byte_range))
+            .collect::<Vec<_>>();
+        Ok(self
+            .get_partial_values(&key_ranges)
+            .await?
+            .into_iter()
+            .collect())
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        if key_ranges.is_empty() {
+            return Ok(Vec::new());
+        }
+        let request = proto::GetPartialValuesRequest {
+            key_ranges: key_ranges
+                .iter()
+                .map(|key_range| proto::KeyRange {
+                    key: key_range.key.as_str().to_string(),
+                    byte_range: Some(key_range.byte_range.into()),
+                })
+                .collect(),
+        };
+        let mut stream = self
+            .client
+            .clone()
+            .get_partial_values(request)
+            .await
+            .map_err(|status| status_to_storage_error(&status))?
+            .into_inner();
+
+        let mut values = vec![None; key_ranges.len()];
+        let mut received = vec![false; key_ranges.len()];
+        while let Some(partial_value) = stream
+            .message()
+            .await
+            .map_err(|status| status_to_storage_error(&status))?
+        {
+            let index = usize::try_from(partial_value.index)
+                .ok()
+                .filter(|index| *index < key_ranges.len())
+                .ok_or_else(|| {
+                    StorageError::Other(format!(
+                        "gRPC store returned a partial value with invalid index {}",
+                        partial_value.index
+                    ))
+                })?;
+            values[index] = partial_value.value;
+            received[index] = true;
+        }
+        if received.iter().all(|received| *received) {
+            Ok(values)
+        } else {
+            Err(StorageError::Other(
+                "gRPC store did not return all partial values".to_string(),
+            ))
+        }
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        let response = self
+            .client
+            .clone()
+            .size_prefix(prefix_request(prefix))
+            .await
+            .map_err(|status| status_to_storage_error(&status))?;
+        Ok(response.into_inner().size)
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        let response = self
+            .client
+            .clone()
+            .size_key(key_request(key))
+            .await
+            .map_err(|status| status_to_storage_error(&status))?;
+        Ok(response.into_inner().size)
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.size_prefix(&StorePrefix::root()).await
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl AsyncListableStorageTraits for AsyncGrpcStore {
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.list_prefix(&StorePrefix::root()).await
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        let response = self
+            .client
+            .clone()
+            .list_prefix(prefix_request(prefix))
+            .await
+            .map_err(|status| status_to_storage_error(&status))?;
+        Ok(Self::keys_prefixes(response.into_inner())?.keys)
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        let response = self
+            .client
+            .clone()
+            .list_dir(prefix_request(prefix))
+            .await
+            .map_err(|status| status_to_storage_error(&status))?;
+        Self::keys_prefixes(response.into_inner())
+    }
+}
+
+/// A gRPC service which serves an asynchronous readable and listable store to [`AsyncGrpcStore`] clients.
+///
+/// Serve the store with a [`tonic::transport::Server`]:
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use zarrs::storage::{store::GrpcStoreService, AsyncListableStorageTraits, AsyncReadableStorageTraits};
+/// # async fn serve<TStorage: AsyncReadableStorageTraits + AsyncListableStorageTraits + 'static>(
+/// #     store: Arc<TStorage>,
+/// # ) -> Result<(), Box<dyn std::error::Error>> {
+/// tonic::transport::Server::builder()
+///     .add_service(GrpcStoreService::new(store))
+///     .serve("[::1]:50051".parse()?)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct GrpcStoreService<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+}
+
+impl<TStorage: ?Sized> Clone for GrpcStoreService<TStorage> {
+    fn clone(&self) -> Self {
+        Self {
+            storage: self.storage.clone(),
+        }
+    }
+}
+
+impl<TStorage: ?Sized> GrpcStoreService<TStorage> {
+    /// Create a new gRPC service serving `storage`.
+    #[must_use]
+    pub fn new(storage: Arc<TStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+fn parse_key(key: String) -> Result<StoreKey, Status> {
+    StoreKey::new(key).map_err(|err| storage_error_to_status(&err.into()))
+}
+
+fn parse_prefix(prefix: String) -> Result<StorePrefix, Status> {
+    StorePrefix::new(prefix).map_err(|err| storage_error_to_status(&err.into()))
+}
+
+fn list_response(keys: &[StoreKey], prefixes: &[StorePrefix]) -> proto::ListResponse {
+    proto::ListResponse {
+        keys: keys.iter().map(|key| key.as_str().to_string()).collect(),
+        prefixes: prefixes
+            .iter()
+            .map(|prefix| prefix.as_str().to_string())
+            .collect(),
+    }
+}
+
+#[tonic::async_trait]
+impl<TStorage> proto::store_server::Store for GrpcStoreService<TStorage>
+where
+    TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits + 'static,
+{
+    type GetPartialValuesStream = BoxStream<proto::PartialValue>;
+
+    async fn get(
+        &self,
+        request: Request<proto::KeyRequest>,
+    ) -> Result<Response<proto::GetResponse>, Status> {
+        let key = parse_key(request.into_inner().key)?;
+        let value = self
+            .storage
+            .get(&key)
+            .await
+            .map_err(|err| storage_error_to_status(&err))?;
+        Ok(Response::new(proto::GetResponse { value }))
+    }
+
+    /// Stream the partial values of `request`, retrieving the byte ranges of consecutive key ranges with the same key together.
+    async fn get_partial_values(
+        &self,
+        request: Request<proto::GetPartialValuesRequest>,
+    ) -> Result<Response<Self::GetPartialValuesStream>, Status> {
+        let key_ranges = request
+            .into_inner()
+            .key_ranges
+            .into_iter()
+            .map(|key_range| {
+                let byte_range = key_range
+                    .byte_range
+                    .map_or(ByteRange::FromStart(0, None), ByteRange::from);
+                Ok((parse_key(key_range.key)?, byte_range))
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+        let groups = key_ranges
+            .into_iter()
+            .enumerate()
+            .group_by(|(_, (key, _))| key.clone())
+            .into_iter()
+            .map(|(key, group)| {
+                let (indices, byte_ranges): (Vec<_>, Vec<_>) = group
+                    .map(|(index, (_, byte_range))| (index as u64, byte_range))
+                    .unzip();
+                (key, indices, byte_ranges)
+            })
+            .collect::<Vec<_>>();
+        let storage = self.storage.clone();
+        let stream = futures::stream::iter(groups)
+            .then(move |(key, indices, byte_ranges)| {
+                let storage = storage.clone();
+                async move {
+                    let values = storage
+                        .get_partial_values_key(&key, &byte_ranges)
+                        .await
+                        .map_err(|err| storage_error_to_status(&err))?;
+                    let values: Vec<MaybeBytes> = values.map_or_else(
+                        || vec![None; indices.len()],
+                        |values| values.into_iter().map(Some).collect(),
+                    );
+                    Ok::<_, Status>(futures::stream::iter(
+                        std::iter::zip(indices, values)
+                            .map(|(index, value)| Ok(proto::PartialValue { index, value })),
+                    ))
+                }
+            })
+            .try_flatten();
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn size_key(
+        &self,
+        request: Request<proto::KeyRequest>,
+    ) -> Result<Response<proto::SizeKeyResponse>, Status> {
+        let key = parse_key(request.into_inner().key)?;
+        let size = self
+            .storage
+            .size_key(&key)
+            .await
+            .map_err(|err| storage_error_to_status(&err))?;
+        Ok(Response::new(proto::SizeKeyResponse { size }))
+    }
+
+    async fn size_prefix(
+        &self,
+        request: Request<proto::PrefixRequest>,
+    ) -> Result<Response<proto::SizeResponse>, Status> {
+        let prefix = parse_prefix(request.into_inner().prefix)?;
+        let size = self
+            .storage
+            .size_prefix(&prefix)
+            .await
+            .map_err(|err| storage_error_to_status(&err))?;
+        Ok(Response::new(proto::SizeResponse { size }))
+    }
+
+    async fn list_prefix(
+        &self,
+        request: Request<proto::PrefixRequest>,
+    ) -> Result<Response<proto::ListResponse>, Status> {
+        let prefix = parse_prefix(request.into_inner().prefix)?;
+        let keys = self
+            .storage
+            .list_prefix(&prefix)
+            .await
+            .map_err(|err| storage_error_to_status(&err))?;
+        Ok(Response::new(list_response(&keys, &[])))
+    }
+
+    async fn list_dir(
+        &self,
+        request: Request<proto::PrefixRequest>,
+    ) -> Result<Response<proto::ListResponse>, Status> {
+        let prefix = parse_prefix(request.into_inner().prefix)?;
+        let keys_prefixes = self
+            .storage
+            .list_dir(&prefix)
+            .await
+            .map_err(|err| storage_error_to_status(&err))?;
+        Ok(Response::new(list_response(
+            keys_prefixes.keys(),
+            keys_prefixes.prefixes(),
+        )))
+    }
+}
+
+impl<TStorage, B> Service<http::Request<B>> for GrpcStoreService<TStorage>
+where
+    TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits + 'static,
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        StoreServer::new(self.clone()).call(request)
+    }
+}
+
+impl<TStorage: ?Sized> NamedService for GrpcStoreService<TStorage> {
+    const NAME: &'static str = "zarrs.store.v1.Store";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[cfg(feature = "object_store")]
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn grpc() -> Result<(), Box<dyn Error>> {
+        use crate::storage::store::AsyncObjectStore;
+
+        let store = Arc::new(AsyncObjectStore::new(object_store::memory::InMemory::new()));
+        super::super::test_util::store_write(&*store).await?;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let incoming = futures::stream::unfold(listener, |listener| async move {
+            let stream = listener.accept().await.map(|(stream, _)| stream);
+            Some((stream, listener))
+        });
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(GrpcStoreService::new(store))
+                .serve_with_incoming(incoming),
+        );
+
+        let store = AsyncGrpcStore::connect(&format!("http://{address}")).await?;
+        super::super::test_util::store_read(&store).await?;
+        super::super::test_util::store_list(&store).await?;
+
+        // A missing key in the middle of a partial request
+        assert_eq!(
+            store
+                .get_partial_values(&[
+                    StoreKeyRange::new("a/b".try_into()?, ByteRange::FromEnd(0, Some(1))),
+                    StoreKeyRange::new("notfound".try_into()?, ByteRange::FromStart(0, None)),
+                    StoreKeyRange::new("a/b".try_into()?, ByteRange::FromStart(0, Some(1))),
+                ])
+                .await?,
+            vec![Some(vec![2].into()), None, Some(vec![0].into())]
+        );
+        assert!(store
+            .get_partial_values_key(&"notfound".try_into()?, &[ByteRange::FromStart(0, None)])
+            .await?
+            .is_none());
+
+        server.abort();
+        Ok(())
+    }
+
+    #[test]
+    fn grpc_status_storage_error() {
+        let is_transient =
+            |status: &Status| RetryPolicy::is_transient_default(&status_to_storage_error(status));
+        assert!(is_transient(&Status::unavailable("unavailable")));
+        assert!(is_transient(&Status::deadline_exceeded(
+            "deadline exceeded"
+        )));
+        assert!(!is_transient(&Status::invalid_argument("invalid argument")));
+        assert!(!is_transient(&Status::not_found("not found")));
+        assert!(!is_transient(&Status::permission_denied(
+            "permission denied"
+        )));
+        assert!(!is_transient(&Status::internal("internal")));
+        assert!(matches!(
+            status_to_storage_error(&Status::unimplemented("unimplemented")),
+            StorageError::Unsupported(_)
+        ));
+
+        assert_eq!(
+            storage_error_to_status(&StorageError::Transient("transient".to_string())).code(),
+            Code::Unavailable
+        );
+        assert_eq!(
+            storage_error_to_status(&StorageError::Other("other".to_string())).code(),
+            Code::Internal
+        );
+    }
+
+    #[tokio::test]
+    async fn grpc_invalid_endpoint() {
+        assert!(matches!(
+            AsyncGrpcStore::connect("not a uri").await,
+            Err(AsyncGrpcStoreCreateError::InvalidEndpoint(_))
+        ));
+    }
+}
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
-use futures::{StreamExt, TryStreamExt};
+use bytes::Bytes;
+use futures::{stream::BoxStream, Stream, StreamExt, TryStreamExt};
 use object_store::path::Path;
+use tokio::io::AsyncWriteExt;
 
 use crate::{
     array::MaybeBytes,
@@ -9,40 +11,27 @@ use crate::{
     storage::{
         store_lock::{AsyncDefaultStoreLocks, AsyncStoreKeyMutex, AsyncStoreLocks},
         AsyncListableStorageTraits, AsyncReadableStorageTraits, AsyncReadableWritableStorageTraits,
-        AsyncWritableStorageTraits, StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue,
-        StoreKeys, StoreKeysPrefixes, StorePrefix,
+        AsyncWritableStorageTraits, GenerationToken, StorageError, StoreKey, StoreKeyRange,
+        StoreKeyStartValue, StoreKeys, StoreKeysPrefixes, StorePrefix,
     },
 };
 
-impl From<object_store::Error> for StorageError {
-    fn from(err: object_store::Error) -> Self {
-        Self::Other(err.to_string())
-    }
-}
+use super::super::store_sync::object_store::{
+    generation_from_parts, generation_to_put_mode, handle_put_result, handle_result, key_to_path,
+};
 
-/// Maps a [`StoreKey`] to an [`object_store`] path.
-fn key_to_path(key: &StoreKey) -> object_store::path::Path {
-    object_store::path::Path::from(key.as_str())
-}
-
-/// Map [`object_store::Error::NotFound`] to None, pass through other errors
-fn handle_result<T>(result: Result<T, object_store::Error>) -> Result<Option<T>, StorageError> {
-    match result {
-        Ok(result) => Ok(Some(result)),
-        Err(err) => {
-            if matches!(err, object_store::Error::NotFound { .. }) {
-                Ok(None)
-            } else {
-                Err(err.into())
-            }
-        }
-    }
-}
+/// The default size in bytes above which values are stored with a multipart upload by an [`AsyncObjectStore`].
+pub const ASYNC_OBJECT_STORE_DEFAULT_MULTIPART_THRESHOLD: usize = 100 * 1024 * 1024;
 
 /// An asynchronous store backed by an [`object_store::ObjectStore`].
+///
+/// Values larger than the multipart threshold are stored with a multipart upload, so that they are not limited by the maximum size of a single `PUT` request.
+/// See [`set_multipart_threshold`](AsyncObjectStore::set_multipart_threshold).
+/// Values that are produced incrementally (e.g. the encoded inner chunks of a large shard) are streamed into a multipart upload by [`set_stream`](AsyncWritableStorageTraits::set_stream) once they exceed the multipart threshold, so they are not held entirely in memory.
 pub struct AsyncObjectStore<T: object_store::ObjectStore> {
     object_store: T,
     locks: AsyncStoreLocks,
+    multipart_threshold: Option<usize>,
 }
 
 impl<T: object_store::ObjectStore> AsyncObjectStore<T> {
@@ -58,8 +47,51 @@ impl<T: object_store::ObjectStore> AsyncObjectStore<T> {
         Self {
             object_store,
             locks: store_locks,
+            multipart_threshold: Some(ASYNC_OBJECT_STORE_DEFAULT_MULTIPART_THRESHOLD),
         }
     }
+
+    /// Return the size in bytes above which values are stored with a multipart upload.
+    #[must_use]
+    pub const fn multipart_threshold(&self) -> Option<usize> {
+        self.multipart_threshold
+    }
+
+    /// Set the size in bytes above which values are stored with a multipart upload.
+    ///
+    /// Defaults to [`ASYNC_OBJECT_STORE_DEFAULT_MULTIPART_THRESHOLD`].
+    /// If [`None`], values are always stored with a single `PUT` request.
+    pub fn set_multipart_threshold(&mut self, multipart_threshold: Option<usize>) {
+        self.multipart_threshold = multipart_threshold;
+    }
+
+    /// Store a value at `key` by streaming its bytes into a multipart upload.
+    ///
+    /// The multipart upload is aborted if `stream` yields an error.
+    async fn set_multipart<S>(&self, key: &StoreKey, stream: S) -> Result<(), StorageError>
+    where
+        S: Stream<Item = Result<Bytes, StorageError>> + Send,
+    {
+        let path = key_to_path(key);
+        let (multipart_id, mut writer) = self.object_store.put_multipart(&path).await?;
+        let result = async {
+            futures::pin_mut!(stream);
+            while let Some(bytes) = stream.next().await {
+                writer.write_all(&bytes?).await?;
+            }
+            writer.shutdown().await?;
+            Ok(())
+        }
+        .await;
+        if result.is_err() {
+            // The original error takes precedence over a failure to clean up the upload
+            let _ = self
+                .object_store
+                .abort_multipart(&path, &multipart_id)
+                .await;
+        }
+        result
+    }
 }
 
 #[async_trait::async_trait]
@@ -68,7 +100,22 @@ impl<T: object_store::ObjectStore> AsyncReadableStorageTraits for AsyncObjectSto
         let get = handle_result(self.object_store.get(&key_to_path(key)).await)?;
         if let Some(get) = get {
             let bytes = get.bytes().await?;
-            Ok(Some(bytes.to_vec()))
+            Ok(Some(bytes))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        let get = handle_result(self.object_store.get(&key_to_path(key)).await)?;
+        if let Some(get) = get {
+            let generation =
+                generation_from_parts(get.meta.e_tag.clone(), get.meta.version.clone());
+            let bytes = get.bytes().await?;
+            Ok(Some((bytes, generation)))
         } else {
             Ok(None)
         }
@@ -78,7 +125,7 @@ impl<T: object_store::ObjectStore> AsyncReadableStorageTraits for AsyncObjectSto
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
         let Some(size) = self.size_key(key).await? else {
             return Ok(None);
         };
@@ -95,7 +142,7 @@ impl<T: object_store::ObjectStore> AsyncReadableStorageTraits for AsyncObjectSto
                 std::iter::zip(ranges, get_ranges)
                     .map(|(range, bytes)| {
                         if range.len() == bytes.len() {
-                            Ok(bytes.to_vec())
+                            Ok(bytes)
                         } else {
                             Err(StorageError::Other(format!(
                                 "Unexpected length of bytes returned, expected {}, got {}",
@@ -155,8 +202,56 @@ impl<T: object_store::ObjectStore> AsyncReadableStorageTraits for AsyncObjectSto
 #[async_trait::async_trait]
 impl<T: object_store::ObjectStore> AsyncWritableStorageTraits for AsyncObjectStore<T> {
     async fn set(&self, key: &StoreKey, value: bytes::Bytes) -> Result<(), StorageError> {
-        self.object_store.put(&key_to_path(key), value).await?;
-        Ok(())
+        match self.multipart_threshold {
+            Some(multipart_threshold) if value.len() > multipart_threshold => {
+                self.set_multipart(key, futures::stream::once(async { Ok(value) }))
+                    .await
+            }
+            _ => {
+                self.object_store.put(&key_to_path(key), value).await?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn set_stream<'a>(
+        &self,
+        key: &StoreKey,
+        mut stream: BoxStream<'a, Result<Bytes, StorageError>>,
+    ) -> Result<(), StorageError> {
+        // Buffer the start of the value, and only switch to a multipart upload once it exceeds the multipart threshold
+        let mut buffered: Vec<Bytes> = Vec::new();
+        let mut buffered_len = 0;
+        if let Some(multipart_threshold) = self.multipart_threshold {
+            while buffered_len <= multipart_threshold {
+                let Some(bytes) = stream.next().await else {
+                    return self.set(key, buffered.concat().into()).await;
+                };
+                let bytes = bytes?;
+                buffered_len += bytes.len();
+                buffered.push(bytes);
+            }
+            let buffered = futures::stream::iter(buffered.into_iter().map(Ok));
+            self.set_multipart(key, buffered.chain(stream)).await
+        } else {
+            let value: Vec<Bytes> = stream.try_collect().await?;
+            self.set(key, value.concat().into()).await
+        }
+    }
+
+    async fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: Bytes,
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        let options = generation_to_put_mode(generation).into();
+        handle_put_result(
+            key,
+            self.object_store
+                .put_opts(&key_to_path(key), value, options)
+                .await,
+        )
     }
 
     async fn set_partial_values(
@@ -284,4 +379,179 @@ mod tests {
         super::super::test_util::store_list(&store).await?;
         Ok(())
     }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn multipart() -> Result<(), Box<dyn Error>> {
+        let path = tempfile::TempDir::new()?;
+        let mut store = AsyncObjectStore::new(
+            object_store::local::LocalFileSystem::new_with_prefix(path.path())?,
+        );
+        assert_eq!(
+            store.multipart_threshold(),
+            Some(ASYNC_OBJECT_STORE_DEFAULT_MULTIPART_THRESHOLD)
+        );
+        store.set_multipart_threshold(Some(4));
+        super::super::test_util::store_write(&store).await?;
+        super::super::test_util::store_read(&store).await?;
+
+        let key = StoreKey::new("a/large")?;
+        let value: Vec<u8> = (0..100).collect();
+        store.set(&key, value.clone().into()).await?;
+        assert_eq!(store.get(&key).await?, Some(value.clone().into()));
+
+        // Stream
+        let key = StoreKey::new("a/stream")?;
+        let parts = value
+            .chunks(30)
+            .map(|part| Ok(Bytes::copy_from_slice(part)))
+            .collect::<Vec<_>>();
+        store
+            .set_stream(&key, futures::stream::iter(parts).boxed())
+            .await?;
+        assert_eq!(store.get(&key).await?, Some(value.into()));
+
+        // A stream below the threshold is stored with a single request
+        let key = StoreKey::new("a/small_stream")?;
+        let parts = vec![
+            Ok(Bytes::from_static(&[0, 1])),
+            Ok(Bytes::from_static(&[2])),
+        ];
+        store
+            .set_stream(&key, futures::stream::iter(parts).boxed())
+            .await?;
+        assert_eq!(store.get(&key).await?, Some(Bytes::from_static(&[0, 1, 2])));
+
+        // A stream error aborts the upload
+        let key = StoreKey::new("a/aborted")?;
+        let parts = vec![
+            Ok(Bytes::from_static(&[0, 1, 2, 3, 4])),
+            Err(StorageError::Other("encode failed".to_string())),
+        ];
+        assert!(store
+            .set_stream(&key, futures::stream::iter(parts).boxed())
+            .await
+            .is_err());
+        assert!(store.get(&key).await?.is_none());
+        Ok(())
+    }
+
+    /// An object store which fails with a timeout a number of times before each successful `get`.
+    #[derive(Debug)]
+    struct FlakyObjectStore {
+        object_store: object_store::memory::InMemory,
+        failures: usize,
+        attempts: std::sync::atomic::AtomicUsize,
+    }
+
+    impl std::fmt::Display for FlakyObjectStore {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "FlakyObjectStore")
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl object_store::ObjectStore for FlakyObjectStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            bytes: Bytes,
+            opts: object_store::PutOptions,
+        ) -> object_store::Result<object_store::PutResult> {
+            self.object_store.put_opts(location, bytes, opts).await
+        }
+
+        async fn put_multipart(
+            &self,
+            location: &Path,
+        ) -> object_store::Result<(
+            object_store::MultipartId,
+            Box<dyn tokio::io::AsyncWrite + Unpin + Send>,
+        )> {
+            self.object_store.put_multipart(location).await
+        }
+
+        async fn abort_multipart(
+            &self,
+            location: &Path,
+            multipart_id: &object_store::MultipartId,
+        ) -> object_store::Result<()> {
+            self.object_store
+                .abort_multipart(location, multipart_id)
+                .await
+        }
+
+        async fn get_opts(
+            &self,
+            location: &Path,
+            options: object_store::GetOptions,
+        ) -> object_store::Result<object_store::GetResult> {
+            let attempt = self
+                .attempts
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            if attempt % (self.failures + 1) < self.failures {
+                Err(object_store::Error::Generic {
+                    store: "FlakyObjectStore",
+                    source: Box::new(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "timed out",
+                    )),
+                })
+            } else {
+                self.object_store.get_opts(location, options).await
+            }
+        }
+
+        async fn delete(&self, location: &Path) -> object_store::Result<()> {
+            self.object_store.delete(location).await
+        }
+
+        fn list(
+            &self,
+            prefix: Option<&Path>,
+        ) -> BoxStream<'_, object_store::Result<object_store::ObjectMeta>> {
+            self.object_store.list(prefix)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&Path>,
+        ) -> object_store::Result<object_store::ListResult> {
+            self.object_store.list_with_delimiter(prefix).await
+        }
+
+        async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+            self.object_store.copy(from, to).await
+        }
+
+        async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+            self.object_store.copy_if_not_exists(from, to).await
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_transient() -> Result<(), Box<dyn Error>> {
+        use crate::storage::storage_adapter::{AsyncRetryStorageAdapter, AsyncSleep, RetryPolicy};
+
+        let object_store = FlakyObjectStore {
+            object_store: object_store::memory::InMemory::new(),
+            failures: 2,
+            attempts: std::sync::atomic::AtomicUsize::new(0),
+        };
+        let store = Arc::new(AsyncObjectStore::new(object_store));
+        let key = StoreKey::new("key")?;
+        store.set(&key, vec![0, 1, 2].into()).await?;
+
+        // Timeouts are transient
+        assert!(matches!(
+            store.get(&key).await,
+            Err(StorageError::Transient(_))
+        ));
+
+        let sleep: AsyncSleep = Arc::new(|_duration| Box::pin(async {}));
+        let policy = RetryPolicy::new().initial_backoff(std::time::Duration::from_millis(1));
+        let adapter = AsyncRetryStorageAdapter::new_with_policy(store.clone(), policy, sleep);
+        assert_eq!(adapter.get(&key).await?, Some(vec![0, 1, 2].into()));
+        Ok(())
+    }
 }
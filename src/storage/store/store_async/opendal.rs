@@ -1,5 +1,6 @@
 use std::{future::IntoFuture, sync::Arc};
 
+use bytes::Bytes;
 use opendal::Operator;
 
 use crate::{
@@ -14,6 +15,9 @@ use crate::{
 };
 
 /// An asynchronous store backed by an [`Operator`].
+///
+/// This gives access to any of the storage services supported by [`opendal`], such as local filesystems, `WebDAV`, HDFS, and many cloud object stores.
+/// A missing key is reported as [`None`] rather than an error.
 pub struct AsyncOpendalStore {
     operator: Operator,
     locks: AsyncStoreLocks,
@@ -53,60 +57,53 @@ fn handle_result<T>(result: Result<T, opendal::Error>) -> Result<Option<T>, Stor
 #[async_trait::async_trait]
 impl AsyncReadableStorageTraits for AsyncOpendalStore {
     async fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
-        handle_result(self.operator.read(key.as_str()).await)
+        Ok(handle_result(self.operator.read(key.as_str()).await)?.map(Bytes::from))
     }
 
     async fn get_partial_values_key(
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
         use futures::FutureExt;
-        // FIXME: Does opendal offer a better way of retrieving multiple byte ranges?
         // FIXME: Coalesce like object_store?
-        if byte_ranges
+        let Some(size) = self.size_key(key).await? else {
+            return Ok(None);
+        };
+        let futures = byte_ranges
             .iter()
-            .all(|byte_range| matches!(byte_range, ByteRange::FromEnd(_, _)))
-        {
-            let futures = byte_ranges
-                .iter()
-                .map(|byte_range| {
-                    self.operator
-                        .read_with(key.as_str())
-                        .range(byte_range.offset()..)
-                        .into_future()
-                })
-                .collect::<Vec<_>>();
-            handle_result(futures::future::try_join_all(futures).await)
-        } else {
-            let size = self
-                .size_key(key)
-                .await?
-                .ok_or(StorageError::UnknownKeySize(key.clone()))?;
-            let futures = byte_ranges
-                .iter()
-                .map(|byte_range| {
-                    let start = byte_range.start(size);
-                    let end = byte_range.end(size);
-                    self.operator
-                        .read_with(key.as_str())
-                        .range(start..end)
-                        .into_future()
-                        .map(move |bytes| match bytes {
-                            Ok(bytes) => {
-                                if (end - start) == bytes.len() as u64 {
-                                    Ok(bytes)
-                                } else {
-                                    Err(InvalidByteRangeError::new(*byte_range, bytes.len() as u64)
-                                        .into())
-                                }
+            .map(|byte_range| {
+                let (ByteRange::FromStart(offset, length) | ByteRange::FromEnd(offset, length)) =
+                    byte_range;
+                if offset + length.unwrap_or(0) > size {
+                    return futures::future::ready(Err(InvalidByteRangeError::new(
+                        *byte_range,
+                        size,
+                    )
+                    .into()))
+                    .boxed();
+                }
+                let start = byte_range.start(size);
+                let end = byte_range.end(size);
+                self.operator
+                    .read_with(key.as_str())
+                    .range(start..end)
+                    .into_future()
+                    .map(move |bytes| match bytes {
+                        Ok(bytes) => {
+                            if (end - start) == bytes.len() as u64 {
+                                Ok(Bytes::from(bytes))
+                            } else {
+                                Err(InvalidByteRangeError::new(*byte_range, bytes.len() as u64)
+                                    .into())
                             }
-                            Err(err) => Err(StorageError::from(err.to_string())),
-                        })
-                })
-                .collect::<Vec<_>>();
-            futures::future::try_join_all(futures).await.map(Some)
-        }
+                        }
+                        Err(err) => Err(StorageError::from(err.to_string())),
+                    })
+                    .boxed()
+            })
+            .collect::<Vec<_>>();
+        futures::future::try_join_all(futures).await.map(Some)
     }
 
     async fn get_partial_values(
@@ -234,6 +231,29 @@ mod tests {
         super::super::test_util::store_write(&store).await?;
         super::super::test_util::store_read(&store).await?;
         super::super::test_util::store_list(&store).await?;
+
+        let key = StoreKey::new("partial")?;
+        store.set(&key, vec![0, 1, 2, 3, 4, 5].into()).await?;
+        assert_eq!(
+            store
+                .get_partial_values_key(
+                    &key,
+                    &[
+                        ByteRange::FromEnd(1, Some(2)),
+                        ByteRange::FromEnd(0, Some(1))
+                    ]
+                )
+                .await?,
+            Some(vec![vec![3, 4].into(), vec![5].into()])
+        );
+        assert!(store
+            .get_partial_values_key(&key, &[ByteRange::FromEnd(5, Some(2))])
+            .await
+            .is_err());
+        assert!(store
+            .get_partial_values_key(&StoreKey::new("missing")?, &[ByteRange::FromStart(0, None)])
+            .await?
+            .is_none());
         Ok(())
     }
 
@@ -0,0 +1,439 @@
+//! An asynchronous store for WebAssembly in the browser, backed by the [Origin Private File System](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system) (OPFS).
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use itertools::Itertools;
+use send_wrapper::SendWrapper;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{FileSystemDirectoryHandle, FileSystemFileHandle};
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    storage::{
+        store_lock::{AsyncDefaultStoreLocks, AsyncStoreKeyMutex, AsyncStoreLocks},
+        AsyncListableStorageTraits, AsyncReadableStorageTraits, AsyncReadableWritableStorageTraits,
+        AsyncWritableStorageTraits, StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue,
+        StoreKeys, StoreKeysPrefixes, StorePrefix,
+    },
+};
+
+use super::fetch::js_error;
+
+/// An asynchronous store for WebAssembly in the browser, backed by the Origin Private File System (OPFS).
+///
+/// Each store key is a file in a directory of the OPFS, and each `/` separated component of a key is a subdirectory.
+/// The OPFS is private to the origin of a web application and persists between sessions, so this store can be used for offline caching of arrays.
+/// This store is intended for the `wasm32-unknown-unknown` target, and operations fail on other targets.
+///
+/// OPFS futures are not [`Send`], so they are wrapped in a [`SendWrapper`] to satisfy the bounds of the async storage traits.
+/// The store must only be used from the thread it was created on, which is always the case without WebAssembly threads.
+pub struct OpfsStore {
+    root: SendWrapper<FileSystemDirectoryHandle>,
+    locks: AsyncStoreLocks,
+}
+
+/// Return true if `err` is a `NotFoundError` or `TypeMismatchError` [`DOMException`](web_sys::DomException).
+fn is_not_found(err: &JsValue) -> bool {
+    err.dyn_ref::<web_sys::DomException>()
+        .is_some_and(|err| matches!(err.name().as_str(), "NotFoundError" | "TypeMismatchError"))
+}
+
+/// Resolve a promise, mapping [`is_not_found`] errors to [`None`].
+async fn resolve_optional(promise: js_sys::Promise) -> Result<Option<JsValue>, StorageError> {
+    match JsFuture::from(promise).await {
+        Ok(value) => Ok(Some(value)),
+        Err(err) if is_not_found(&err) => Ok(None),
+        Err(err) => Err(js_error(err)),
+    }
+}
+
+/// Split a store key into its directory components and file name.
+fn key_components(key: &StoreKey) -> (Vec<&str>, &str) {
+    let mut components: Vec<&str> = key.as_str().split('/').collect();
+    let name = components.pop().unwrap_or_default();
+    (components, name)
+}
+
+/// Split a store prefix into its directory components.
+fn prefix_components(prefix: &StorePrefix) -> Vec<&str> {
+    prefix
+        .as_str()
+        .split('/')
+        .filter(|component| !component.is_empty())
+        .collect()
+}
+
+/// Return the contents of `file` within `range`, or all of its contents if [`None`].
+async fn read_file(
+    file: &web_sys::File,
+    range: Option<std::ops::Range<u64>>,
+) -> Result<Bytes, StorageError> {
+    #[allow(clippy::cast_precision_loss)]
+    let promise = match range {
+        Some(range) => file
+            .slice_with_f64_and_f64(range.start as f64, range.end as f64)
+            .map_err(js_error)?
+            .array_buffer(),
+        None => file.array_buffer(),
+    };
+    let buffer = JsFuture::from(promise).await.map_err(js_error)?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec().into())
+}
+
+impl OpfsStore {
+    /// Create a new OPFS store in the root directory of the origin private file system.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the origin private file system is not available.
+    pub async fn new() -> Result<Self, StorageError> {
+        SendWrapper::new(async {
+            let global = js_sys::global();
+            let storage = if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+                window.navigator().storage()
+            } else if let Some(worker) = global.dyn_ref::<web_sys::WorkerGlobalScope>() {
+                worker.navigator().storage()
+            } else {
+                return Err(StorageError::Other(
+                    "the storage manager is not available in the global scope".to_string(),
+                ));
+            };
+            let root = JsFuture::from(storage.get_directory())
+                .await
+                .map_err(js_error)?;
+            Ok(Self::new_with_directory(root.unchecked_into()))
+        })
+        .await
+    }
+
+    /// Create a new OPFS store in `directory`.
+    ///
+    /// This can be used to isolate the store in a subdirectory of the origin private file system.
+    #[must_use]
+    pub fn new_with_directory(directory: FileSystemDirectoryHandle) -> Self {
+        Self::new_with_directory_and_locks(directory, Arc::new(AsyncDefaultStoreLocks::default()))
+    }
+
+    /// Create a new OPFS store in `directory` with non-default store locks.
+    #[must_use]
+    pub fn new_with_directory_and_locks(
+        directory: FileSystemDirectoryHandle,
+        store_locks: AsyncStoreLocks,
+    ) -> Self {
+        Self {
+            root: SendWrapper::new(directory),
+            locks: store_locks,
+        }
+    }
+
+    /// Return the subdirectory of the root directory at `components`.
+    ///
+    /// Returns [`None`] if the directory does not exist and `create` is false.
+    async fn directory(
+        &self,
+        components: &[&str],
+        create: bool,
+    ) -> Result<Option<FileSystemDirectoryHandle>, StorageError> {
+        let mut directory: FileSystemDirectoryHandle = (*self.root).clone();
+        for component in components {
+            let options = web_sys::FileSystemGetDirectoryOptions::new();
+            options.set_create(create);
+            let Some(handle) =
+                resolve_optional(directory.get_directory_handle_with_options(component, &options))
+                    .await?
+            else {
+                return Ok(None);
+            };
+            directory = handle.unchecked_into();
+        }
+        Ok(Some(directory))
+    }
+
+    /// Return the file handle of `key`.
+    ///
+    /// Returns [`None`] if the file does not exist and `create` is false.
+    async fn file_handle(
+        &self,
+        key: &StoreKey,
+        create: bool,
+    ) -> Result<Option<FileSystemFileHandle>, StorageError> {
+        let (components, name) = key_components(key);
+        let Some(directory) = self.directory(&components, create).await? else {
+            return Ok(None);
+        };
+        let options = web_sys::FileSystemGetFileOptions::new();
+        options.set_create(create);
+        Ok(
+            resolve_optional(directory.get_file_handle_with_options(name, &options))
+                .await?
+                .map(JsCast::unchecked_into),
+        )
+    }
+
+    /// Return the file of `key`, or [`None`] if it does not exist.
+    async fn file(&self, key: &StoreKey) -> Result<Option<web_sys::File>, StorageError> {
+        let Some(file_handle) = self.file_handle(key, false).await? else {
+            return Ok(None);
+        };
+        Ok(resolve_optional(file_handle.get_file())
+            .await?
+            .map(JsCast::unchecked_into))
+    }
+
+    /// Write `key_start_values` to the file of `key` in a single writable stream.
+    ///
+    /// The existing contents of the file are discarded if `truncate` is true.
+    async fn write(
+        &self,
+        key: &StoreKey,
+        key_start_values: &[&StoreKeyStartValue<'_>],
+        truncate: bool,
+    ) -> Result<(), StorageError> {
+        let file_handle = self
+            .file_handle(key, true)
+            .await?
+            .ok_or_else(|| StorageError::Other(format!("failed to create {key}")))?;
+        let options = web_sys::FileSystemCreateWritableOptions::new();
+        options.set_keep_existing_data(!truncate);
+        let writable: web_sys::FileSystemWritableFileStream =
+            JsFuture::from(file_handle.create_writable_with_options(&options))
+                .await
+                .map_err(js_error)?
+                .unchecked_into();
+        for key_start_value in key_start_values {
+            #[allow(clippy::cast_precision_loss)]
+            JsFuture::from(
+                writable
+                    .seek_with_f64(key_start_value.start as f64)
+                    .map_err(js_error)?,
+            )
+            .await
+            .map_err(js_error)?;
+            JsFuture::from(
+                writable
+                    .write_with_u8_array(key_start_value.value)
+                    .map_err(js_error)?,
+            )
+            .await
+            .map_err(js_error)?;
+        }
+        JsFuture::from(writable.close()).await.map_err(js_error)?;
+        Ok(())
+    }
+
+    /// List the keys (and prefixes if `recursive` is false) in the directory at `prefix`.
+    async fn list_directory(
+        &self,
+        prefix: &StorePrefix,
+        recursive: bool,
+    ) -> Result<StoreKeysPrefixes, StorageError> {
+        let mut keys = Vec::new();
+        let mut prefixes = Vec::new();
+        let Some(directory) = self.directory(&prefix_components(prefix), false).await? else {
+            return Ok(StoreKeysPrefixes { keys, prefixes });
+        };
+        let mut directories = vec![(prefix.as_str().to_string(), directory)];
+        while let Some((path, directory)) = directories.pop() {
+            let entries = directory.values();
+            loop {
+                let next: js_sys::IteratorNext = JsFuture::from(entries.next().map_err(js_error)?)
+                    .await
+                    .map_err(js_error)?
+                    .unchecked_into();
+                if next.done() {
+                    break;
+                }
+                let handle: web_sys::FileSystemHandle = next.value().unchecked_into();
+                let name = path.clone() + &handle.name();
+                match handle.kind() {
+                    web_sys::FileSystemHandleKind::File => keys.push(StoreKey::new(name)?),
+                    web_sys::FileSystemHandleKind::Directory if recursive => {
+                        directories.push((name + "/", handle.unchecked_into()));
+                    }
+                    _ => prefixes.push(StorePrefix::new(name + "/")?),
+                }
+            }
+        }
+        keys.sort();
+        prefixes.sort();
+        Ok(StoreKeysPrefixes { keys, prefixes })
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncReadableStorageTraits for OpfsStore {
+    async fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        SendWrapper::new(async {
+            match self.file(key).await? {
+                Some(file) => Ok(Some(read_file(&file, None).await?)),
+                None => Ok(None),
+            }
+        })
+        .await
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        SendWrapper::new(async {
+            let Some(file) = self.file(key).await? else {
+                return Ok(None);
+            };
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let size = file.size() as u64;
+            let mut out = Vec::with_capacity(byte_ranges.len());
+            for byte_range in byte_ranges {
+                let range = byte_range.to_range(size);
+                if range.end > size {
+                    return Err(StorageError::Other(format!(
+                        "byte range {byte_range} is out of bounds of {key} with size {size}"
+                    )));
+                }
+                out.push(read_file(&file, Some(range)).await?);
+            }
+            Ok(Some(out))
+        })
+        .await
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges).await
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        let mut size = 0;
+        for key in self.list_prefix(prefix).await? {
+            if let Some(size_key) = self.size_key(&key).await? {
+                size += size_key;
+            }
+        }
+        Ok(size)
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        SendWrapper::new(async {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Ok(self.file(key).await?.map(|file| file.size() as u64))
+        })
+        .await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.size_prefix(&StorePrefix::root()).await
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncWritableStorageTraits for OpfsStore {
+    async fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), StorageError> {
+        let key_start_value = StoreKeyStartValue::new(key.clone(), 0, &value);
+        SendWrapper::new(self.write(key, &[&key_start_value], true)).await
+    }
+
+    async fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        // Group by key
+        let key_start_values = key_start_values
+            .iter()
+            .into_group_map_by(|key_start_value| key_start_value.key.clone());
+        for (key, key_start_values) in key_start_values {
+            let mutex = self.mutex(&key).await?;
+            let _lock = mutex.lock().await?;
+            SendWrapper::new(self.write(&key, &key_start_values, false)).await?;
+        }
+        Ok(())
+    }
+
+    async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        SendWrapper::new(async {
+            let (components, name) = key_components(key);
+            if let Some(directory) = self.directory(&components, false).await? {
+                resolve_optional(directory.remove_entry(name)).await?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        SendWrapper::new(async {
+            let mut components = prefix_components(prefix);
+            let names = if let Some(name) = components.pop() {
+                vec![name.to_string()]
+            } else {
+                // Erase all entries of the root directory
+                let list_dir = self.list_directory(prefix, false).await?;
+                list_dir
+                    .keys()
+                    .iter()
+                    .map(|key| key.as_str().to_string())
+                    .chain(
+                        list_dir
+                            .prefixes()
+                            .iter()
+                            .map(|prefix| prefix.as_str().trim_end_matches('/').to_string()),
+                    )
+                    .collect()
+            };
+            if let Some(directory) = self.directory(&components, false).await? {
+                let options = web_sys::FileSystemRemoveOptions::new();
+                options.set_recursive(true);
+                for name in names {
+                    resolve_optional(directory.remove_entry_with_options(&name, &options)).await?;
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncReadableWritableStorageTraits for OpfsStore {
+    async fn mutex(&self, key: &StoreKey) -> Result<AsyncStoreKeyMutex, StorageError> {
+        Ok(self.locks.mutex(key).await)
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncListableStorageTraits for OpfsStore {
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.list_prefix(&StorePrefix::root()).await
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        SendWrapper::new(async { Ok(self.list_directory(prefix, true).await?.keys().clone()) })
+            .await
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        SendWrapper::new(self.list_directory(prefix, false)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opfs_key_components() {
+        let key = StoreKey::new("a/b/c").unwrap();
+        assert_eq!(key_components(&key), (vec!["a", "b"], "c"));
+        let key = StoreKey::new("c").unwrap();
+        assert_eq!(key_components(&key), (vec![], "c"));
+        assert_eq!(
+            prefix_components(&StorePrefix::new("a/b/").unwrap()),
+            vec!["a", "b"]
+        );
+        assert!(prefix_components(&StorePrefix::root()).is_empty());
+    }
+}
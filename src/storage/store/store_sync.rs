@@ -1,12 +1,26 @@
 pub mod filesystem_store;
 pub mod memory_store;
+pub mod mount_store;
+pub mod overlay_store;
 
 #[cfg(feature = "http")]
 pub mod http_store;
 
+#[cfg(feature = "lmdb")]
+pub mod lmdb_store;
+
+#[cfg(feature = "object_store")]
+pub mod object_store;
+
 #[cfg(feature = "opendal")]
 pub mod opendal;
 
+#[cfg(feature = "tar")]
+pub mod tar_store;
+
+#[cfg(feature = "zip")]
+pub mod zip_store;
+
 #[cfg(test)]
 mod test_util {
     use std::error::Error;
@@ -61,7 +75,7 @@ mod test_util {
     pub fn store_read<T: ReadableStorageTraits>(store: &T) -> Result<(), Box<dyn Error>> {
         assert!(store.get(&"notfound".try_into()?)?.is_none());
         assert!(store.size_key(&"notfound".try_into()?)?.is_none());
-        assert_eq!(store.get(&"a/b".try_into()?)?, Some(vec![0, 1, 2]));
+        assert_eq!(store.get(&"a/b".try_into()?)?, Some(vec![0, 1, 2].into()));
         assert_eq!(store.size_key(&"a/b".try_into()?)?, Some(3));
         assert_eq!(store.size_key(&"a/c".try_into()?)?, Some(1));
         assert_eq!(store.size_key(&"i/j/k".try_into()?)?, Some(2));
@@ -73,7 +87,7 @@ mod test_util {
                     ByteRange::FromEnd(0, Some(1))
                 ]
             )?,
-            Some(vec![vec![1], vec![2]])
+            Some(vec![vec![1].into(), vec![2].into()])
         );
         assert_eq!(
             store.get_partial_values(&[
@@ -81,7 +95,11 @@ mod test_util {
                 StoreKeyRange::new("a/b".try_into()?, ByteRange::FromEnd(1, Some(2))),
                 StoreKeyRange::new("i/j/k".try_into()?, ByteRange::FromStart(1, Some(1))),
             ])?,
-            vec![Some(vec![1, 2]), Some(vec![0, 1]), Some(vec![1])]
+            vec![
+                Some(vec![1, 2].into()),
+                Some(vec![0, 1].into()),
+                Some(vec![1].into())
+            ]
         );
         assert!(store
             .get_partial_values(&[StoreKeyRange::new(
@@ -2,15 +2,16 @@
 //!
 //! See <https://zarr-specs.readthedocs.io/en/latest/v3/stores/filesystem/v1.0.html>.
 
+use bytes::Bytes;
+
 use crate::{
     array::MaybeBytes,
     byte_range::{ByteOffset, ByteRange},
     storage::{
         store_lock::{DefaultStoreLocks, StoreKeyMutex, StoreLocks},
-        store_set_partial_values, ListableStorageTraits, ReadableStorageTraits,
-        ReadableWritableStorageTraits, StorageError, StoreKey, StoreKeyError, StoreKeyRange,
-        StoreKeyStartValue, StoreKeys, StoreKeysPrefixes, StorePrefix, StorePrefixes,
-        WritableStorageTraits,
+        ListableStorageTraits, ReadableStorageTraits, ReadableWritableStorageTraits, StorageError,
+        StoreKey, StoreKeyError, StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes,
+        StorePrefix, StorePrefixes, WritableStorageTraits,
     },
 };
 
@@ -22,6 +23,7 @@ use std::{
     collections::HashMap,
     fs::{File, OpenOptions},
     io::{Read, Seek, SeekFrom, Write},
+    num::NonZeroUsize,
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
@@ -47,14 +49,84 @@ use std::{
 //     FilesystemStore::new(path).map_err(|e| StorePluginCreateError::Other(e.to_string()))
 // }
 
+/// The alignment of buffers, offsets, and lengths for direct I/O.
+#[cfg(target_os = "linux")]
+const DIRECT_IO_ALIGNMENT: usize = 4096;
+
+/// Options for writing values to a [`FilesystemStore`].
+///
+/// These options can reduce page cache pressure and file fragmentation when writing large arrays, such as on parallel filesystems (e.g. Lustre, GPFS).
+/// By default, values are written through the page cache in a single write without preallocation.
+#[derive(Debug, Clone, Default)]
+pub struct FilesystemStoreOptions {
+    direct_io: bool,
+    preallocate: bool,
+    write_buffer_size: Option<NonZeroUsize>,
+}
+
+impl FilesystemStoreOptions {
+    /// Create the default filesystem store options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return whether values are written with direct I/O.
+    #[must_use]
+    pub const fn direct_io(&self) -> bool {
+        self.direct_io
+    }
+
+    /// Set whether values are written with direct I/O (`O_DIRECT`), bypassing the page cache.
+    ///
+    /// Direct I/O is only used when an entire value is written with [`set`](WritableStorageTraits::set) on Linux.
+    /// Values are written through the page cache if the filesystem does not support direct I/O, on other platforms, or for partial writes.
+    pub fn set_direct_io(&mut self, direct_io: bool) -> &mut Self {
+        self.direct_io = direct_io;
+        self
+    }
+
+    /// Return whether space for values is preallocated before writing.
+    #[must_use]
+    pub const fn preallocate(&self) -> bool {
+        self.preallocate
+    }
+
+    /// Set whether space for values is preallocated (`posix_fallocate`) before writing.
+    ///
+    /// Preallocation is only supported on Linux and is ignored on other platforms.
+    pub fn set_preallocate(&mut self, preallocate: bool) -> &mut Self {
+        self.preallocate = preallocate;
+        self
+    }
+
+    /// Return the write buffer size.
+    #[must_use]
+    pub const fn write_buffer_size(&self) -> Option<NonZeroUsize> {
+        self.write_buffer_size
+    }
+
+    /// Set the write buffer size, the maximum number of bytes issued in a single write.
+    ///
+    /// If [`None`], values are written in a single write.
+    /// With direct I/O, the write buffer size is rounded up to the direct I/O alignment.
+    pub fn set_write_buffer_size(&mut self, write_buffer_size: Option<NonZeroUsize>) -> &mut Self {
+        self.write_buffer_size = write_buffer_size;
+        self
+    }
+}
+
 /// A file system store.
 ///
 /// See <https://zarr-specs.readthedocs.io/en/latest/v3/stores/filesystem/v1.0.html>.
+///
+/// Use [`FilesystemStore::new_with_options`] to configure direct I/O, preallocation, and write buffering with [`FilesystemStoreOptions`].
 #[derive(Debug)]
 pub struct FilesystemStore {
     base_path: PathBuf,
     sort: bool,
     readonly: bool,
+    options: FilesystemStoreOptions,
     files: Mutex<HashMap<StoreKey, Arc<RwLock<()>>>>,
     locks: StoreLocks,
 }
@@ -100,11 +172,33 @@ impl FilesystemStore {
             base_path,
             sort: false,
             readonly,
+            options: FilesystemStoreOptions::default(),
             files: Mutex::default(),
             locks: store_locks,
         })
     }
 
+    /// Create a new file system store at a given `base_path` with non-default write `options`.
+    ///
+    /// # Errors
+    /// Returns a [`FilesystemStoreCreateError`] if `base_directory`:
+    ///   - is not valid, or
+    ///   - it points to an existing file rather than a directory.
+    pub fn new_with_options<P: AsRef<Path>>(
+        base_path: P,
+        options: FilesystemStoreOptions,
+    ) -> Result<Self, FilesystemStoreCreateError> {
+        let mut store = Self::new(base_path)?;
+        store.options = options;
+        Ok(store)
+    }
+
+    /// Return the write options of the store.
+    #[must_use]
+    pub const fn options(&self) -> &FilesystemStoreOptions {
+        &self.options
+    }
+
     /// Makes the store sort directories/files when walking.
     #[must_use]
     pub const fn sorted(mut self) -> Self {
@@ -168,20 +262,305 @@ impl FilesystemStore {
             }
         }
 
+        // Write the entire value with direct I/O if enabled and supported
+        #[cfg(target_os = "linux")]
+        if self.options.direct_io
+            && offset.is_none()
+            && truncate
+            && self.set_direct(&key_path, value)?
+        {
+            return Ok(());
+        }
+
         let mut file = OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(truncate)
             .open(key_path)?;
 
+        // Preallocate
+        #[cfg(target_os = "linux")]
+        if self.options.preallocate {
+            preallocate(&file, offset.unwrap_or_default(), value.len())?;
+        }
+
         // Write
         if let Some(offset) = offset {
             file.seek(SeekFrom::Start(offset))?;
         }
-        file.write_all(value)?;
+        write_buffered(&mut file, value, self.options.write_buffer_size)?;
+
+        Ok(())
+    }
+
+    /// Write `key_start_values` to the file of `key`.
+    ///
+    /// The file is opened once and contiguous values are coalesced into positioned (vectored on Linux) writes.
+    fn set_partial_values_key(
+        &self,
+        key: &StoreKey,
+        key_start_values: &[&StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        let file = self.get_file_mutex(key);
+        let _lock = file.write();
+
+        // Create directories
+        let key_path = self.key_to_fspath(key);
+        if let Some(parent) = key_path.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        #[allow(unused_mut)]
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(key_path)?;
+
+        let runs = contiguous_runs(key_start_values);
+
+        // Preallocate
+        #[cfg(target_os = "linux")]
+        if self.options.preallocate {
+            let start = runs.iter().map(|run| run.0).min().unwrap_or_default();
+            let end = key_start_values
+                .iter()
+                .map(|key_start_value| key_start_value.end())
+                .max()
+                .unwrap_or_default();
+            preallocate(&file, start, usize::try_from(end - start).unwrap())?;
+        }
+
+        // Write
+        for (offset, values) in runs {
+            #[cfg(target_os = "linux")]
+            write_vectored_at(&file, offset, &values, self.options.write_buffer_size)?;
+            #[cfg(not(target_os = "linux"))]
+            {
+                file.seek(SeekFrom::Start(offset))?;
+                for value in values {
+                    write_buffered(&mut file, value, self.options.write_buffer_size)?;
+                }
+            }
+        }
 
         Ok(())
     }
+
+    /// Write an entire value to `key_path` with direct I/O.
+    ///
+    /// Returns false if the filesystem does not support direct I/O.
+    #[cfg(target_os = "linux")]
+    fn set_direct(&self, key_path: &Path, value: &[u8]) -> Result<bool, StorageError> {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        #[derive(Clone, Copy)]
+        #[repr(C, align(4096))]
+        struct AlignedBlock([u8; DIRECT_IO_ALIGNMENT]);
+
+        let mut file = match OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .custom_flags(libc::O_DIRECT)
+            .open(key_path)
+        {
+            Ok(file) => file,
+            Err(err) if err.raw_os_error() == Some(libc::EINVAL) => return Ok(false),
+            Err(err) => return Err(err.into()),
+        };
+
+        if self.options.preallocate {
+            preallocate(&file, 0, value.len())?;
+        }
+
+        // Copy the value to an aligned buffer padded to the alignment
+        let num_blocks = (value.len() + DIRECT_IO_ALIGNMENT - 1) / DIRECT_IO_ALIGNMENT;
+        let mut blocks = vec![AlignedBlock([0; DIRECT_IO_ALIGNMENT]); num_blocks];
+        let buffer = unsafe {
+            std::slice::from_raw_parts_mut(
+                blocks.as_mut_ptr().cast::<u8>(),
+                num_blocks * DIRECT_IO_ALIGNMENT,
+            )
+        };
+        buffer[..value.len()].copy_from_slice(value);
+
+        let write_buffer_size = self.options.write_buffer_size.map(|write_buffer_size| {
+            let aligned = (write_buffer_size.get() + DIRECT_IO_ALIGNMENT - 1) / DIRECT_IO_ALIGNMENT
+                * DIRECT_IO_ALIGNMENT;
+            unsafe { NonZeroUsize::new_unchecked(aligned) }
+        });
+        match write_buffered(&mut file, buffer, write_buffer_size) {
+            Ok(()) => {}
+            Err(err) if err.raw_os_error() == Some(libc::EINVAL) => return Ok(false),
+            Err(err) => return Err(err.into()),
+        }
+
+        // Remove the padding
+        file.set_len(value.len() as u64)?;
+        Ok(true)
+    }
+}
+
+/// Write `value` to `file` in writes of at most `write_buffer_size` bytes.
+fn write_buffered(
+    file: &mut File,
+    value: &[u8],
+    write_buffer_size: Option<NonZeroUsize>,
+) -> std::io::Result<()> {
+    match write_buffer_size {
+        Some(write_buffer_size) => value
+            .chunks(write_buffer_size.get())
+            .try_for_each(|block| file.write_all(block)),
+        None => file.write_all(value),
+    }
+}
+
+/// Group `key_start_values` into runs of contiguous values, each starting at an offset.
+///
+/// Values are sorted by their offset unless any values overlap, in which case their order is preserved so that later values take precedence.
+fn contiguous_runs<'a>(key_start_values: &[&StoreKeyStartValue<'a>]) -> Vec<(u64, Vec<&'a [u8]>)> {
+    let mut sorted = key_start_values.to_vec();
+    sorted.sort_by_key(|key_start_value| key_start_value.start);
+    let overlapping = sorted.windows(2).any(|pair| pair[0].end() > pair[1].start);
+    let key_start_values = if overlapping {
+        key_start_values.to_vec()
+    } else {
+        sorted
+    };
+
+    let mut runs: Vec<(u64, u64, Vec<&'a [u8]>)> = Vec::new();
+    for key_start_value in key_start_values {
+        match runs.last_mut() {
+            Some((_, end, values)) if *end == key_start_value.start => {
+                values.push(key_start_value.value);
+                *end = key_start_value.end();
+            }
+            _ => runs.push((
+                key_start_value.start,
+                key_start_value.end(),
+                vec![key_start_value.value],
+            )),
+        }
+    }
+    runs.into_iter()
+        .map(|(start, _, values)| (start, values))
+        .collect()
+}
+
+/// Write `values` contiguously to `file` starting at `offset` with `pwritev`.
+///
+/// Each write is limited to `write_buffer_size` bytes (if set) and the maximum number of buffers per `pwritev` call.
+#[cfg(target_os = "linux")]
+fn write_vectored_at(
+    file: &File,
+    mut offset: u64,
+    mut values: &[&[u8]],
+    write_buffer_size: Option<NonZeroUsize>,
+) -> std::io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    /// The maximum number of buffers in a single `pwritev` call (`UIO_MAXIOV`).
+    const MAX_IOVECS: usize = 1024;
+
+    let max_write = write_buffer_size.map_or(usize::MAX, NonZeroUsize::get);
+    // The number of bytes of the first value already written
+    let mut written = 0;
+    loop {
+        // Skip written and empty values
+        while let Some(value) = values.first() {
+            if written < value.len() {
+                break;
+            }
+            values = &values[1..];
+            written = 0;
+        }
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let mut iovecs = Vec::with_capacity(values.len().min(MAX_IOVECS));
+        let mut length = 0;
+        for (i, value) in values.iter().take(MAX_IOVECS).enumerate() {
+            let value = if i == 0 { &value[written..] } else { value };
+            let value = &value[..value.len().min(max_write - length)];
+            iovecs.push(libc::iovec {
+                iov_base: value.as_ptr().cast_mut().cast(),
+                iov_len: value.len(),
+            });
+            length += value.len();
+            if length == max_write {
+                break;
+            }
+        }
+
+        let offset_t = libc::off_t::try_from(offset)
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidInput))?;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let result = unsafe {
+            libc::pwritev(
+                file.as_raw_fd(),
+                iovecs.as_ptr(),
+                iovecs.len() as libc::c_int,
+                offset_t,
+            )
+        };
+        let mut bytes_written = match result {
+            0 => return Err(std::io::Error::from(std::io::ErrorKind::WriteZero)),
+            result if result < 0 => {
+                let err = std::io::Error::last_os_error();
+                if err.kind() == std::io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err);
+            }
+            #[allow(clippy::cast_sign_loss)]
+            result => result as usize,
+        };
+        offset += bytes_written as u64;
+
+        // Advance past the written bytes
+        while bytes_written > 0 {
+            let remaining = values[0].len() - written;
+            if bytes_written < remaining {
+                written += bytes_written;
+                bytes_written = 0;
+            } else {
+                bytes_written -= remaining;
+                values = &values[1..];
+                written = 0;
+            }
+        }
+    }
+}
+
+/// Preallocate `length` bytes of `file` starting at `offset`.
+///
+/// Filesystems which do not support preallocation are ignored.
+#[cfg(target_os = "linux")]
+fn preallocate(file: &File, offset: u64, length: usize) -> std::io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    if length == 0 {
+        return Ok(());
+    }
+    let to_off_t = |value: u64| {
+        libc::off_t::try_from(value)
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidInput))
+    };
+    let result = unsafe {
+        libc::posix_fallocate(
+            file.as_raw_fd(),
+            to_off_t(offset)?,
+            to_off_t(length as u64)?,
+        )
+    };
+    match result {
+        0 | libc::EOPNOTSUPP | libc::EINVAL => Ok(()),
+        errno => Err(std::io::Error::from_raw_os_error(errno)),
+    }
 }
 
 impl ReadableStorageTraits for FilesystemStore {
@@ -195,7 +574,7 @@ impl ReadableStorageTraits for FilesystemStore {
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
         let file = self.get_file_mutex(key);
         let _lock = file.read();
 
@@ -236,7 +615,7 @@ impl ReadableStorageTraits for FilesystemStore {
                     }
                 }
             };
-            out.push(bytes);
+            out.push(Bytes::from(bytes));
         }
 
         Ok(Some(out))
@@ -296,7 +675,23 @@ impl WritableStorageTraits for FilesystemStore {
             return Err(StorageError::ReadOnly);
         }
 
-        store_set_partial_values(self, key_start_values)
+        // Group by key, preserving the order of values for each key
+        let mut keys: Vec<&StoreKey> = Vec::new();
+        let mut key_values: HashMap<&StoreKey, Vec<&StoreKeyStartValue>> = HashMap::new();
+        for key_start_value in key_start_values {
+            key_values
+                .entry(&key_start_value.key)
+                .or_insert_with(|| {
+                    keys.push(&key_start_value.key);
+                    Vec::new()
+                })
+                .push(key_start_value);
+        }
+
+        for key in keys {
+            self.set_partial_values_key(key, &key_values[key])?;
+        }
+        Ok(())
     }
 
     fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
@@ -337,6 +732,28 @@ impl WritableStorageTraits for FilesystemStore {
             Ok(())
         }
     }
+
+    fn rename_prefix(&self, from: &StorePrefix, to: &StorePrefix) -> Result<bool, StorageError> {
+        if self.readonly {
+            return Err(StorageError::ReadOnly);
+        }
+
+        let _lock = self.files.lock(); // lock all operations
+
+        let to_path = self.prefix_to_fs_path(to);
+        if let Some(parent) = to_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let result = std::fs::rename(self.prefix_to_fs_path(from), to_path);
+        if let Err(err) = result {
+            match err.kind() {
+                std::io::ErrorKind::NotFound => Ok(true),
+                _ => Err(err.into()),
+            }
+        } else {
+            Ok(true)
+        }
+    }
 }
 
 impl ReadableWritableStorageTraits for FilesystemStore {
@@ -423,4 +840,70 @@ mod tests {
         super::super::test_util::store_list(&store)?;
         Ok(())
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn filesystem_options() -> Result<(), Box<dyn Error>> {
+        let path = tempfile::TempDir::new()?;
+        let mut options = FilesystemStoreOptions::new();
+        options
+            .set_direct_io(true)
+            .set_preallocate(true)
+            .set_write_buffer_size(NonZeroUsize::new(3));
+        let store = FilesystemStore::new_with_options(path.path(), options)?.sorted();
+        assert!(store.options().direct_io());
+        super::super::test_util::store_write(&store)?;
+        super::super::test_util::store_read(&store)?;
+        super::super::test_util::store_list(&store)?;
+
+        let key = StoreKey::new("large")?;
+        let value: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        store.set(&key, &value)?;
+        assert_eq!(store.get(&key)?, Some(value.clone().into()));
+        assert_eq!(store.size_key(&key)?, Some(10_000));
+        store.set(&key, &value[..5])?;
+        assert_eq!(store.get(&key)?, Some(value[..5].to_vec().into()));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn filesystem_set_partial_values() -> Result<(), Box<dyn Error>> {
+        for write_buffer_size in [None, NonZeroUsize::new(3)] {
+            let path = tempfile::TempDir::new()?;
+            let mut options = FilesystemStoreOptions::new();
+            options
+                .set_preallocate(true)
+                .set_write_buffer_size(write_buffer_size);
+            let store = FilesystemStore::new_with_options(path.path(), options)?;
+            let key_a = StoreKey::new("a/b")?;
+            let key_c = StoreKey::new("c")?;
+            store.set(&key_a, &[0; 4])?;
+
+            // Unordered, contiguous, and interleaved keys with a gap
+            store.set_partial_values(&[
+                StoreKeyStartValue::new(key_a.clone(), 6, &[6, 7]),
+                StoreKeyStartValue::new(key_c.clone(), 2, &[2]),
+                StoreKeyStartValue::new(key_a.clone(), 1, &[1, 2]),
+                StoreKeyStartValue::new(key_a.clone(), 3, &[3, 4, 5]),
+                StoreKeyStartValue::new(key_a.clone(), 8, &[]),
+            ])?;
+            assert_eq!(
+                store.get(&key_a)?,
+                Some(vec![0, 1, 2, 3, 4, 5, 6, 7].into())
+            );
+            assert_eq!(store.get(&key_c)?, Some(vec![0, 0, 2].into()));
+
+            // Overlapping values are applied in order
+            store.set_partial_values(&[
+                StoreKeyStartValue::new(key_a.clone(), 2, &[9, 9]),
+                StoreKeyStartValue::new(key_a.clone(), 0, &[8, 8, 8]),
+            ])?;
+            assert_eq!(
+                store.get(&key_a)?,
+                Some(vec![8, 8, 8, 9, 4, 5, 6, 7].into())
+            );
+        }
+        Ok(())
+    }
 }
@@ -1,5 +1,7 @@
 //! A HTTP store.
 
+use bytes::Bytes;
+
 use crate::{
     array::MaybeBytes,
     byte_range::ByteRange,
@@ -7,23 +9,52 @@ use crate::{
 };
 
 use itertools::Itertools;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use reqwest::{
-    header::{HeaderValue, CONTENT_LENGTH, RANGE},
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_LENGTH, RANGE},
     StatusCode, Url,
 };
-use std::str::FromStr;
+use std::{ops::Range, str::FromStr, time::Duration};
 use thiserror::Error;
 
+/// The default maximum gap between byte ranges of a HTTP store for them to be coalesced into a single range.
+pub const HTTP_STORE_DEFAULT_COALESCE_GAP: u64 = 1024 * 1024;
+
 /// A HTTP store.
+///
+/// Use [`HTTPStoreBuilder`] to configure the HTTP client (e.g. default headers for authentication, timeouts, and redirects).
+///
+/// Adjacent or nearby byte ranges in [`get_partial_values_key`](ReadableStorageTraits::get_partial_values_key) requests are coalesced into single ranges.
+/// See [`set_coalesce_gap`](HTTPStore::set_coalesce_gap) and [`set_batch_range_requests`](HTTPStore::set_batch_range_requests).
 #[derive(Debug)]
 pub struct HTTPStore {
     base_url: Url,
+    client: reqwest::blocking::Client,
     batch_range_requests: bool,
+    coalesce_gap: u64,
 }
 
 impl From<reqwest::Error> for StorageError {
     fn from(err: reqwest::Error) -> Self {
-        Self::Other(err.to_string())
+        if err.is_timeout() || err.is_connect() || err.status().is_some_and(is_transient_status) {
+            Self::Transient(err.to_string())
+        } else {
+            Self::Other(err.to_string())
+        }
+    }
+}
+
+/// Returns true if an HTTP response `status` indicates a transient failure (a server error or rate limiting).
+fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Return the error for an unexpected HTTP response `status` with `message`.
+fn status_error(status: StatusCode, message: String) -> StorageError {
+    if is_transient_status(status) {
+        StorageError::Transient(message)
+    } else {
+        StorageError::Other(message)
     }
 }
 
@@ -34,29 +65,59 @@ impl From<url::ParseError> for StorageError {
 }
 
 impl HTTPStore {
-    /// Create a new HTTP store at a given `base_url`.
+    /// Create a new HTTP store at a given `base_url` with a default HTTP client.
     ///
     /// # Errors
     ///
     /// Returns a [`HTTPStoreCreateError`] if `base_url` is not a valid URL.
     pub fn new(base_url: &str) -> Result<Self, HTTPStoreCreateError> {
+        Self::new_with_client(base_url, reqwest::blocking::Client::new())
+    }
+
+    /// Create a new HTTP store at a given `base_url` with a custom HTTP `client`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`HTTPStoreCreateError`] if `base_url` is not a valid URL.
+    pub fn new_with_client(
+        base_url: &str,
+        client: reqwest::blocking::Client,
+    ) -> Result<Self, HTTPStoreCreateError> {
         let base_url = Url::from_str(base_url)
             .map_err(|_| HTTPStoreCreateError::InvalidBaseURL(base_url.into()))?;
         Ok(Self {
             base_url,
+            client,
             batch_range_requests: true,
+            coalesce_gap: HTTP_STORE_DEFAULT_COALESCE_GAP,
         })
     }
 
+    /// Return the HTTP client.
+    #[must_use]
+    pub const fn client(&self) -> &reqwest::blocking::Client {
+        &self.client
+    }
+
     /// Set whether to batch range requests.
     ///
     /// Defaults to true.
     /// Some servers do not fully support multipart ranges and might return an entire resource given such a request.
-    /// It may be preferable to disable batched range requests in this case, so that each range request is a single part range.
+    /// It may be preferable to disable batched range requests in this case, so that each (coalesced) range request is a single part range.
+    /// Single part range requests are issued in parallel.
     pub fn set_batch_range_requests(&mut self, batch_range_requests: bool) {
         self.batch_range_requests = batch_range_requests;
     }
 
+    /// Set the maximum gap in bytes between byte ranges for them to be coalesced into a single range request.
+    ///
+    /// Defaults to [`HTTP_STORE_DEFAULT_COALESCE_GAP`].
+    /// Only adjacent and overlapping byte ranges are coalesced if the gap is zero.
+    /// A larger gap reduces the number of requests at the cost of retrieving unrequested bytes.
+    pub fn set_coalesce_gap(&mut self, coalesce_gap: u64) {
+        self.coalesce_gap = coalesce_gap;
+    }
+
     /// Maps a [`StoreKey`] to a HTTP [`Url`].
     ///
     /// # Errors
@@ -70,40 +131,57 @@ impl HTTPStore {
         }
         Url::parse(&url)
     }
-}
 
-impl ReadableStorageTraits for HTTPStore {
-    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
-        let url = self.key_to_url(key)?;
-        let client = reqwest::blocking::Client::new();
-        let response = client.get(url).send()?;
+    /// Retrieve a single byte `range` of the resource at `url`.
+    fn get_range(&self, url: Url, range: &Range<u64>) -> Result<Bytes, StorageError> {
+        let range_header =
+            HeaderValue::from_str(&format!("bytes={}-{}", range.start, range.end - 1)).unwrap();
+        let response = self.client.get(url).header(RANGE, range_header).send()?;
         match response.status() {
-            StatusCode::OK => Ok(Some(response.bytes()?.to_vec())),
-            StatusCode::NOT_FOUND => Ok(None),
-            _ => Err(StorageError::from(format!(
-                "http unexpected status code: {}",
-                response.status()
-            ))),
+            StatusCode::NOT_FOUND => Err(StorageError::from("the http server returned a NOT FOUND status for the byte range request, but returned a non zero size for CONTENT_LENGTH")),
+            StatusCode::PARTIAL_CONTENT => {
+                let bytes = response.bytes()?;
+                if bytes.len() as u64 == range.end - range.start {
+                    Ok(bytes)
+                } else {
+                    Err(StorageError::from(
+                        "http partial content response did not include the requested byte range",
+                    ))
+                }
+            }
+            StatusCode::OK => {
+                // Received all bytes
+                let bytes = response.bytes()?;
+                let start = usize::try_from(range.start).unwrap();
+                let end = usize::try_from(range.end).unwrap();
+                if end <= bytes.len() {
+                    Ok(bytes.slice(start..end))
+                } else {
+                    Err(StorageError::from(
+                        "http response did not include the requested byte range",
+                    ))
+                }
+            }
+            status => Err(status_error(
+                status,
+                format!("the http server responded with status {status} for the byte range request"),
+            )),
         }
     }
 
-    fn get_partial_values_key(
+    /// Retrieve multiple byte `ranges` of the resource at `url` in a single multipart range request.
+    fn get_ranges_batched(
         &self,
-        key: &StoreKey,
-        byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
-        let url = self.key_to_url(key)?;
-        let client = reqwest::blocking::Client::new();
-        let Some(size) = self.size_key(key)? else {
-            return Ok(None);
-        };
-        let bytes_strs = byte_ranges
+        url: Url,
+        ranges: &[Range<u64>],
+    ) -> Result<Vec<Bytes>, StorageError> {
+        let bytes_strs = ranges
             .iter()
-            .map(|byte_range| format!("{}-{}", byte_range.start(size), byte_range.end(size) - 1))
+            .map(|range| format!("{}-{}", range.start, range.end - 1))
             .join(", ");
 
-        let range = HeaderValue::from_str(&format!("bytes={bytes_strs}")).unwrap();
-        let response = client.get(url).header(RANGE, range).send()?;
+        let range_header = HeaderValue::from_str(&format!("bytes={bytes_strs}")).unwrap();
+        let response = self.client.get(url).header(RANGE, range_header).send()?;
 
         match response.status() {
             StatusCode::NOT_FOUND => Err(StorageError::from("the http server returned a NOT FOUND status for the byte range request, but returned a non zero size for CONTENT_LENGTH")),
@@ -111,18 +189,17 @@ impl ReadableStorageTraits for HTTPStore {
                 // TODO: Gracefully handle a response from the server which does not include all requested by ranges
                 let mut bytes = response.bytes()?;
                 if bytes.len() as u64
-                    == byte_ranges
+                    == ranges
                         .iter()
-                        .map(|byte_range| byte_range.length(size))
+                        .map(|range| range.end - range.start)
                         .sum::<u64>()
                 {
-                    let mut out = Vec::with_capacity(byte_ranges.len());
-                    for byte_range in byte_ranges {
-                        let bytes_range =
-                            bytes.split_to(usize::try_from(byte_range.length(size)).unwrap());
-                        out.push(bytes_range.to_vec());
-                    }
-                    Ok(Some(out))
+                    Ok(ranges
+                        .iter()
+                        .map(|range| {
+                            bytes.split_to(usize::try_from(range.end - range.start).unwrap())
+                        })
+                        .collect())
                 } else {
                     Err(StorageError::from(
                         "http partial content response did not include all requested byte ranges",
@@ -132,21 +209,108 @@ impl ReadableStorageTraits for HTTPStore {
             StatusCode::OK => {
                 // Received all bytes
                 let bytes = response.bytes()?;
-                let mut out = Vec::with_capacity(byte_ranges.len());
-                for byte_range in byte_ranges {
-                    let start = usize::try_from(byte_range.start(size)).unwrap();
-                    let end = usize::try_from(byte_range.end(size)).unwrap();
-                    out.push(bytes[start..end].to_vec());
-                }
-                Ok(Some(out))
+                ranges
+                    .iter()
+                    .map(|range| {
+                        let start = usize::try_from(range.start).unwrap();
+                        let end = usize::try_from(range.end).unwrap();
+                        if end <= bytes.len() {
+                            Ok(bytes.slice(start..end))
+                        } else {
+                            Err(StorageError::from(
+                                "http response did not include all requested byte ranges",
+                            ))
+                        }
+                    })
+                    .collect()
             }
-            _ => Err(StorageError::from(format!(
-                "the http server responded with status {} for the byte range request",
-                response.status()
-            ))),
+            status => Err(status_error(
+                status,
+                format!("the http server responded with status {status} for the byte range request"),
+            )),
+        }
+    }
+}
+
+/// Coalesce byte `ranges` separated by at most `gap` bytes.
+///
+/// Returns the sorted coalesced ranges. Empty ranges are omitted.
+fn coalesce_ranges(ranges: &[Range<u64>], gap: u64) -> Vec<Range<u64>> {
+    let mut ranges: Vec<Range<u64>> = ranges
+        .iter()
+        .filter(|range| !range.is_empty())
+        .cloned()
+        .collect();
+    ranges.sort_by_key(|range| range.start);
+    let mut coalesced: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match coalesced.last_mut() {
+            Some(last) if range.start <= last.end.saturating_add(gap) => {
+                last.end = last.end.max(range.end);
+            }
+            _ => coalesced.push(range),
+        }
+    }
+    coalesced
+}
+
+impl ReadableStorageTraits for HTTPStore {
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        let url = self.key_to_url(key)?;
+        let response = self.client.get(url).send()?;
+        match response.status() {
+            StatusCode::OK => Ok(Some(response.bytes()?)),
+            StatusCode::NOT_FOUND => Ok(None),
+            status => Err(status_error(
+                status,
+                format!("http unexpected status code: {status}"),
+            )),
         }
     }
 
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let url = self.key_to_url(key)?;
+        let Some(size) = self.size_key(key)? else {
+            return Ok(None);
+        };
+        let ranges: Vec<Range<u64>> = byte_ranges
+            .iter()
+            .map(|byte_range| byte_range.to_range(size))
+            .collect();
+
+        // Coalesce nearby byte ranges, then retrieve them with a multipart range request or parallel single part range requests
+        let coalesced_ranges = coalesce_ranges(&ranges, self.coalesce_gap);
+        let coalesced_bytes = if self.batch_range_requests && coalesced_ranges.len() > 1 {
+            self.get_ranges_batched(url, &coalesced_ranges)?
+        } else {
+            coalesced_ranges
+                .par_iter()
+                .map(|range| self.get_range(url.clone(), range))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        // Extract the requested byte ranges from the coalesced byte ranges
+        let out = ranges
+            .iter()
+            .map(|range| {
+                if range.is_empty() {
+                    return Bytes::new();
+                }
+                let index = coalesced_ranges
+                    .partition_point(|coalesced_range| coalesced_range.end < range.end);
+                let coalesced_start = coalesced_ranges[index].start;
+                let start = usize::try_from(range.start - coalesced_start).unwrap();
+                let end = usize::try_from(range.end - coalesced_start).unwrap();
+                coalesced_bytes[index].slice(start..end)
+            })
+            .collect();
+        Ok(Some(out))
+    }
+
     fn get_partial_values(
         &self,
         key_ranges: &[StoreKeyRange],
@@ -162,8 +326,7 @@ impl ReadableStorageTraits for HTTPStore {
 
     fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
         let url = self.key_to_url(key)?;
-        let client = reqwest::blocking::Client::new();
-        let response = client.head(url).send()?;
+        let response = self.client.head(url).send()?;
         match response.status() {
             StatusCode::OK => {
                 let length = response
@@ -175,14 +338,166 @@ impl ReadableStorageTraits for HTTPStore {
                 Ok(Some(length))
             }
             StatusCode::NOT_FOUND => Ok(None),
-            _ => Err(StorageError::from(format!(
-                "http size_key has status code {}",
-                response.status()
-            ))),
+            status => Err(status_error(
+                status,
+                format!("http size_key has status code {status}"),
+            )),
         }
     }
 }
 
+/// A [`HTTPStore`] builder.
+///
+/// The HTTP client is configured with the builder methods, unless a custom client is set with [`client`](HTTPStoreBuilder::client).
+///
+/// ```
+/// # use zarrs::storage::store::HTTPStoreBuilder;
+/// let store = HTTPStoreBuilder::new("https://example.com/data.zarr")
+///     .bearer_auth("token")
+///     .timeout(std::time::Duration::from_secs(30))
+///     .max_redirects(3)
+///     .build()?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct HTTPStoreBuilder {
+    base_url: String,
+    client: Option<reqwest::blocking::Client>,
+    default_headers: HeaderMap,
+    bearer_auth: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    max_redirects: Option<usize>,
+    batch_range_requests: bool,
+    coalesce_gap: u64,
+}
+
+impl HTTPStoreBuilder {
+    /// Create a new HTTP store builder for a store at a given `base_url`.
+    #[must_use]
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            client: None,
+            default_headers: HeaderMap::new(),
+            bearer_auth: None,
+            timeout: None,
+            connect_timeout: None,
+            max_redirects: None,
+            batch_range_requests: true,
+            coalesce_gap: HTTP_STORE_DEFAULT_COALESCE_GAP,
+        }
+    }
+
+    /// Set a custom HTTP client.
+    ///
+    /// The default headers, bearer authentication, timeouts, and redirect options of the builder are ignored if a custom client is set.
+    pub fn client(&mut self, client: reqwest::blocking::Client) -> &mut Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Add a default header sent with every request.
+    pub fn header(&mut self, name: HeaderName, value: HeaderValue) -> &mut Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Set the default headers sent with every request.
+    ///
+    /// Replaces any previously added default headers.
+    pub fn default_headers(&mut self, default_headers: HeaderMap) -> &mut Self {
+        self.default_headers = default_headers;
+        self
+    }
+
+    /// Authenticate every request with a bearer `token` in the `Authorization` header.
+    pub fn bearer_auth(&mut self, token: &str) -> &mut Self {
+        self.bearer_auth = Some(token.to_string());
+        self
+    }
+
+    /// Set the total timeout of each request, from connecting until the response body has been read.
+    ///
+    /// Defaults to 30 seconds.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for connecting to the server.
+    ///
+    /// There is no connect timeout by default.
+    pub fn connect_timeout(&mut self, connect_timeout: Duration) -> &mut Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Set the maximum number of redirects to follow.
+    ///
+    /// Redirects are not followed if `max_redirects` is zero.
+    /// Defaults to 10.
+    pub fn max_redirects(&mut self, max_redirects: usize) -> &mut Self {
+        self.max_redirects = Some(max_redirects);
+        self
+    }
+
+    /// Set whether to batch range requests. See [`HTTPStore::set_batch_range_requests`].
+    pub fn batch_range_requests(&mut self, batch_range_requests: bool) -> &mut Self {
+        self.batch_range_requests = batch_range_requests;
+        self
+    }
+
+    /// Set the maximum gap in bytes between coalesced byte ranges. See [`HTTPStore::set_coalesce_gap`].
+    pub fn coalesce_gap(&mut self, coalesce_gap: u64) -> &mut Self {
+        self.coalesce_gap = coalesce_gap;
+        self
+    }
+
+    /// Build the HTTP store.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`HTTPStoreCreateError`] if
+    ///  - the base URL is not valid,
+    ///  - the bearer authentication token is not a valid header value, or
+    ///  - the HTTP client cannot be built.
+    pub fn build(&self) -> Result<HTTPStore, HTTPStoreCreateError> {
+        let client = if let Some(client) = &self.client {
+            client.clone()
+        } else {
+            let mut default_headers = self.default_headers.clone();
+            if let Some(token) = &self.bearer_auth {
+                let mut value =
+                    HeaderValue::from_str(&format!("Bearer {token}")).map_err(|_| {
+                        HTTPStoreCreateError::InvalidHeaderValue(AUTHORIZATION.to_string())
+                    })?;
+                value.set_sensitive(true);
+                default_headers.insert(AUTHORIZATION, value);
+            }
+            let mut client = reqwest::blocking::Client::builder().default_headers(default_headers);
+            if let Some(timeout) = self.timeout {
+                client = client.timeout(timeout);
+            }
+            if let Some(connect_timeout) = self.connect_timeout {
+                client = client.connect_timeout(connect_timeout);
+            }
+            if let Some(max_redirects) = self.max_redirects {
+                client = client.redirect(if max_redirects == 0 {
+                    reqwest::redirect::Policy::none()
+                } else {
+                    reqwest::redirect::Policy::limited(max_redirects)
+                });
+            }
+            client.build()?
+        };
+        let mut store = HTTPStore::new_with_client(&self.base_url, client)?;
+        store.set_batch_range_requests(self.batch_range_requests);
+        store.set_coalesce_gap(self.coalesce_gap);
+        Ok(store)
+    }
+}
+
 /// A HTTP store creation error.
 #[derive(Debug, Error)]
 pub enum HTTPStoreCreateError {
@@ -192,6 +507,12 @@ pub enum HTTPStoreCreateError {
     /// The URL is not valid.
     #[error("base URL {0} is not valid")]
     InvalidBaseURL(String),
+    /// A header value is not valid.
+    #[error("the value of header {0} is not valid")]
+    InvalidHeaderValue(String),
+    /// The HTTP client could not be built.
+    #[error(transparent)]
+    ClientError(#[from] reqwest::Error),
 }
 
 #[cfg(test)]
@@ -209,6 +530,50 @@ mod tests {
         "https://raw.githubusercontent.com/LDeakin/zarrs/main/tests/data/hierarchy.zarr";
     const ARRAY_PATH_REF: &str = "/a/baz";
 
+    #[test]
+    fn http_store_builder() {
+        let store = HTTPStoreBuilder::new(HTTP_TEST_PATH_REF)
+            .header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_static("key"),
+            )
+            .bearer_auth("token")
+            .timeout(Duration::from_secs(10))
+            .connect_timeout(Duration::from_secs(5))
+            .max_redirects(0)
+            .batch_range_requests(false)
+            .coalesce_gap(0)
+            .build()
+            .unwrap();
+        assert!(!store.batch_range_requests);
+        assert_eq!(store.coalesce_gap, 0);
+        assert!(matches!(
+            HTTPStoreBuilder::new("not a url").build(),
+            Err(HTTPStoreCreateError::InvalidBaseURL(_))
+        ));
+        assert!(matches!(
+            HTTPStoreBuilder::new(HTTP_TEST_PATH_REF)
+                .bearer_auth("invalid\ntoken")
+                .build(),
+            Err(HTTPStoreCreateError::InvalidHeaderValue(_))
+        ));
+        assert!(HTTPStoreBuilder::new(HTTP_TEST_PATH_REF)
+            .client(reqwest::blocking::Client::new())
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn http_store_coalesce_ranges() {
+        assert_eq!(
+            coalesce_ranges(&[8..10, 0..2, 2..4, 5..6, 3..3, 5..7], 0),
+            vec![0..4, 5..7, 8..10]
+        );
+        assert_eq!(coalesce_ranges(&[8..10, 0..2, 2..4, 5..6], 2), vec![0..10]);
+        assert_eq!(coalesce_ranges(&[0..2, 8..10], 5), vec![0..2, 8..10]);
+        assert_eq!(coalesce_ranges(&[0..2, 8..10, 1..9], 0), vec![0..10]);
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn http_store_size() {
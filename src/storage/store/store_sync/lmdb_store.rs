@@ -0,0 +1,415 @@
+//! An LMDB store.
+//!
+//! [LMDB](http://www.lmdb.tech/doc/) is a memory-mapped key-value store with ordered keys.
+//! It is well suited to high-throughput local workloads with many small values (e.g. many small chunks) which are slow to store as individual files.
+
+use bytes::Bytes;
+use heed::{types::Bytes as LmdbBytes, Database, Env, EnvOpenOptions, RoTxn};
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::{ByteRange, InvalidByteRangeError},
+    storage::{
+        store_lock::{DefaultStoreLocks, StoreKeyMutex, StoreLocks},
+        ListableStorageTraits, ReadableStorageTraits, ReadableWritableStorageTraits, StorageError,
+        StoreKey, StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes, StorePrefix,
+        WritableStorageTraits,
+    },
+};
+
+use thiserror::Error;
+
+use std::{
+    ops::Bound,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The default maximum size of an [`LmdbStore`] in bytes (1 TiB).
+///
+/// The map size is the size of the virtual memory map of the database, not the size of the database file.
+pub const LMDB_STORE_DEFAULT_MAP_SIZE: usize = 1 << 40;
+
+impl From<heed::Error> for StorageError {
+    fn from(err: heed::Error) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
+/// Return the exclusive upper bound of the keys starting with `prefix`, or [`None`] if unbounded.
+fn prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut upper = prefix.to_vec();
+    while let Some(last) = upper.pop() {
+        if last < u8::MAX {
+            upper.push(last + 1);
+            return Some(upper);
+        }
+    }
+    None
+}
+
+/// Return the key range of the keys starting with `prefix` given its exclusive `upper` bound.
+///
+/// LMDB does not support empty keys, so the range of an empty prefix is unbounded.
+fn prefix_range<'a>(
+    prefix: &'a [u8],
+    upper: Option<&'a [u8]>,
+) -> (Bound<&'a [u8]>, Bound<&'a [u8]>) {
+    (
+        if prefix.is_empty() {
+            Bound::Unbounded
+        } else {
+            Bound::Included(prefix)
+        },
+        upper.map_or(Bound::Unbounded, Bound::Excluded),
+    )
+}
+
+fn bytes_to_key(key: &[u8]) -> Result<StoreKey, StorageError> {
+    let key = std::str::from_utf8(key).map_err(|err| StorageError::Other(err.to_string()))?;
+    Ok(StoreKey::new(key)?)
+}
+
+/// An LMDB store.
+///
+/// Keys are stored in a single LMDB database in lexicographic order, so prefix listing and [`erase_prefix`](WritableStorageTraits::erase_prefix) are range scans and range deletes rather than full scans.
+/// Each write operation is performed in a single write transaction, so [`set_partial_values`](WritableStorageTraits::set_partial_values) and [`erase_values`](WritableStorageTraits::erase_values) are atomic.
+///
+/// An LMDB environment must only be opened once per process.
+/// Share an [`LmdbStore`] (e.g. with an [`Arc`]) rather than opening the same path multiple times.
+pub struct LmdbStore {
+    path: PathBuf,
+    env: Env,
+    database: Database<LmdbBytes, LmdbBytes>,
+    locks: StoreLocks,
+}
+
+impl std::fmt::Debug for LmdbStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LmdbStore")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LmdbStore {
+    /// Open or create an LMDB store in the directory at `path` with the [default map size](LMDB_STORE_DEFAULT_MAP_SIZE).
+    ///
+    /// # Errors
+    /// Returns a [`LmdbStoreCreateError`] if the directory cannot be created or the LMDB environment cannot be opened.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, LmdbStoreCreateError> {
+        Self::new_with_map_size(path, LMDB_STORE_DEFAULT_MAP_SIZE)
+    }
+
+    /// Open or create an LMDB store in the directory at `path` with a maximum size of `map_size` bytes.
+    ///
+    /// # Errors
+    /// Returns a [`LmdbStoreCreateError`] if the directory cannot be created or the LMDB environment cannot be opened.
+    pub fn new_with_map_size<P: AsRef<Path>>(
+        path: P,
+        map_size: usize,
+    ) -> Result<Self, LmdbStoreCreateError> {
+        let path = path.as_ref().to_path_buf();
+        if path.is_file() {
+            return Err(LmdbStoreCreateError::ExistingFile(path));
+        }
+        std::fs::create_dir_all(&path)?;
+        // SAFETY: the environment is opened once per store and the database file is only modified through LMDB.
+        let env = unsafe { EnvOpenOptions::new().map_size(map_size).open(&path)? };
+        let mut wtxn = env.write_txn()?;
+        let database = env.create_database(&mut wtxn, None)?;
+        wtxn.commit()?;
+        Ok(Self {
+            path,
+            env,
+            database,
+            locks: Arc::new(DefaultStoreLocks::default()),
+        })
+    }
+
+    /// Return the path of the LMDB environment directory.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Flush the data buffers of the store to disk.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the flush fails.
+    pub fn sync(&self) -> Result<(), StorageError> {
+        Ok(self.env.force_sync()?)
+    }
+
+    fn list_prefix_txn(
+        &self,
+        rtxn: &RoTxn,
+        prefix: &StorePrefix,
+    ) -> Result<StoreKeys, StorageError> {
+        let prefix = prefix.as_str().as_bytes();
+        let upper = prefix_upper_bound(prefix);
+        let mut keys = StoreKeys::new();
+        for item in self
+            .database
+            .remap_data_type::<heed::types::DecodeIgnore>()
+            .range(rtxn, &prefix_range(prefix, upper.as_deref()))?
+        {
+            let (key, ()) = item?;
+            keys.push(bytes_to_key(key)?);
+        }
+        Ok(keys)
+    }
+}
+
+impl ReadableStorageTraits for LmdbStore {
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        let rtxn = self.env.read_txn()?;
+        Ok(self
+            .database
+            .get(&rtxn, key.as_str().as_bytes())?
+            .map(Bytes::copy_from_slice))
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let rtxn = self.env.read_txn()?;
+        let Some(value) = self.database.get(&rtxn, key.as_str().as_bytes())? else {
+            return Ok(None);
+        };
+        let size = value.len() as u64;
+        let mut out = Vec::with_capacity(byte_ranges.len());
+        for byte_range in byte_ranges {
+            if byte_range.end(size) > size {
+                return Err(InvalidByteRangeError::new(*byte_range, size).into());
+            }
+            let start = usize::try_from(byte_range.start(size)).unwrap();
+            let end = usize::try_from(byte_range.end(size)).unwrap();
+            out.push(Bytes::copy_from_slice(&value[start..end]));
+        }
+        Ok(Some(out))
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges)
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        let rtxn = self.env.read_txn()?;
+        let prefix = prefix.as_str().as_bytes();
+        let upper = prefix_upper_bound(prefix);
+        let mut size = 0;
+        for item in self
+            .database
+            .range(&rtxn, &prefix_range(prefix, upper.as_deref()))?
+        {
+            let (_, value) = item?;
+            size += value.len() as u64;
+        }
+        Ok(size)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        let rtxn = self.env.read_txn()?;
+        Ok(self
+            .database
+            .get(&rtxn, key.as_str().as_bytes())?
+            .map(|value| value.len() as u64))
+    }
+}
+
+impl WritableStorageTraits for LmdbStore {
+    fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        let mut wtxn = self.env.write_txn()?;
+        self.database
+            .put(&mut wtxn, key.as_str().as_bytes(), value)?;
+        Ok(wtxn.commit()?)
+    }
+
+    fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        // Write transactions are serialised, so the read-modify-write of each value is atomic without a store lock
+        let mut wtxn = self.env.write_txn()?;
+        for key_start_value in key_start_values {
+            let key = key_start_value.key.as_str().as_bytes();
+            let mut value = self
+                .database
+                .get(&wtxn, key)?
+                .map(<[u8]>::to_vec)
+                .unwrap_or_default();
+            let start = usize::try_from(key_start_value.start).unwrap();
+            let end = start + key_start_value.value.len();
+            if value.len() < end {
+                value.resize(end, 0);
+            }
+            value[start..end].copy_from_slice(key_start_value.value);
+            self.database.put(&mut wtxn, key, &value)?;
+        }
+        Ok(wtxn.commit()?)
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        let mut wtxn = self.env.write_txn()?;
+        self.database.delete(&mut wtxn, key.as_str().as_bytes())?;
+        Ok(wtxn.commit()?)
+    }
+
+    fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        let mut wtxn = self.env.write_txn()?;
+        for key in keys {
+            self.database.delete(&mut wtxn, key.as_str().as_bytes())?;
+        }
+        Ok(wtxn.commit()?)
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        let mut wtxn = self.env.write_txn()?;
+        let prefix = prefix.as_str().as_bytes();
+        if prefix.is_empty() {
+            self.database.clear(&mut wtxn)?;
+        } else {
+            let upper = prefix_upper_bound(prefix);
+            self.database
+                .delete_range(&mut wtxn, &prefix_range(prefix, upper.as_deref()))?;
+        }
+        Ok(wtxn.commit()?)
+    }
+}
+
+impl ReadableWritableStorageTraits for LmdbStore {
+    fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+        Ok(self.locks.mutex(key))
+    }
+}
+
+impl ListableStorageTraits for LmdbStore {
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.list_prefix(&StorePrefix::root())
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        let rtxn = self.env.read_txn()?;
+        self.list_prefix_txn(&rtxn, prefix)
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        let rtxn = self.env.read_txn()?;
+        let database = self.database.remap_data_type::<heed::types::DecodeIgnore>();
+        let prefix_bytes = prefix.as_str().as_bytes();
+        let prefix_upper = prefix_upper_bound(prefix_bytes);
+
+        // Seek past all keys of each child prefix once it is found, rather than scanning them
+        let mut keys = StoreKeys::new();
+        let mut prefixes = Vec::new();
+        let mut lower = match prefix_range(prefix_bytes, None).0 {
+            Bound::Included(prefix) => Bound::Included(prefix.to_vec()),
+            _ => Bound::Unbounded,
+        };
+        loop {
+            let range = (
+                match &lower {
+                    Bound::Included(lower) => Bound::Included(lower.as_slice()),
+                    Bound::Excluded(lower) => Bound::Excluded(lower.as_slice()),
+                    Bound::Unbounded => Bound::Unbounded,
+                },
+                prefix_upper
+                    .as_deref()
+                    .map_or(Bound::Unbounded, Bound::Excluded),
+            );
+            let Some(item) = database.range(&rtxn, &range)?.next() else {
+                break;
+            };
+            let (key, ()) = item?;
+            let key_strip = &key[prefix_bytes.len()..];
+            if let Some(position) = key_strip.iter().position(|&byte| byte == b'/') {
+                let child_prefix = &key[..=prefix_bytes.len() + position];
+                let child_prefix_str = std::str::from_utf8(child_prefix)
+                    .map_err(|err| StorageError::Other(err.to_string()))?;
+                prefixes.push(StorePrefix::new(child_prefix_str)?);
+                match prefix_upper_bound(child_prefix) {
+                    Some(child_upper) => lower = Bound::Included(child_upper),
+                    None => break,
+                }
+            } else {
+                keys.push(bytes_to_key(key)?);
+                lower = Bound::Excluded(key.to_vec());
+            }
+        }
+        Ok(StoreKeysPrefixes { keys, prefixes })
+    }
+}
+
+/// An LMDB store creation error.
+#[derive(Debug, Error)]
+pub enum LmdbStoreCreateError {
+    /// An IO error.
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    /// The path points to an existing file rather than a directory.
+    #[error("{0} is an existing file, not a directory")]
+    ExistingFile(PathBuf),
+    /// An LMDB error.
+    #[error("{0}")]
+    LmdbError(String),
+}
+
+impl From<heed::Error> for LmdbStoreCreateError {
+    fn from(err: heed::Error) -> Self {
+        Self::LmdbError(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn lmdb() -> Result<(), Box<dyn Error>> {
+        let path = tempfile::TempDir::new()?;
+        let store = LmdbStore::new_with_map_size(path.path(), 1 << 20)?;
+        super::super::test_util::store_write(&store)?;
+        super::super::test_util::store_read(&store)?;
+        super::super::test_util::store_list(&store)?;
+
+        // Listing the root separates top level keys from prefixes
+        let list_dir = store.list_dir(&StorePrefix::root())?;
+        assert!(list_dir.keys().is_empty());
+        assert_eq!(list_dir.prefixes(), &["a/".try_into()?, "i/".try_into()?]);
+        Ok(())
+    }
+
+    #[test]
+    fn lmdb_erase_prefix() -> Result<(), Box<dyn Error>> {
+        let path = tempfile::TempDir::new()?;
+        let store = LmdbStore::new_with_map_size(path.path(), 1 << 20)?;
+        for key in ["a/b", "a/c/d", "ab", "a0", "b"] {
+            store.set(&key.try_into()?, &[0])?;
+        }
+        store.erase_prefix(&"a/".try_into()?)?;
+        assert_eq!(
+            store.list()?,
+            &["a0".try_into()?, "ab".try_into()?, "b".try_into()?]
+        );
+        store.erase_prefix(&StorePrefix::root())?;
+        assert!(store.list()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn lmdb_create_error() -> Result<(), Box<dyn Error>> {
+        let file = tempfile::NamedTempFile::new()?;
+        assert!(matches!(
+            LmdbStore::new(file.path()),
+            Err(LmdbStoreCreateError::ExistingFile(_))
+        ));
+        Ok(())
+    }
+}
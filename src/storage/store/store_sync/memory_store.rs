@@ -1,5 +1,6 @@
 //! An in-memory store.
 
+use bytes::Bytes;
 use parking_lot::RwLock;
 use std::sync::Mutex;
 
@@ -8,21 +9,24 @@ use crate::{
     byte_range::{ByteOffset, ByteRange, InvalidByteRangeError},
     storage::{
         store_lock::{DefaultStoreLocks, StoreKeyMutex, StoreLocks},
-        store_set_partial_values, ListableStorageTraits, ReadableStorageTraits,
+        store_set_partial_values, GenerationToken, ListableStorageTraits, ReadableStorageTraits,
         ReadableWritableStorageTraits, StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue,
         StoreKeys, StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
     },
 };
 
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet},
+    hash::{Hash, Hasher},
     sync::Arc,
 };
 
 /// An in-memory store.
+///
+/// Values are held as reference counted [`Bytes`], so retrieving a value or a byte range of a value does not copy it.
 #[derive(Debug)]
 pub struct MemoryStore {
-    data_map: Mutex<BTreeMap<StoreKey, Arc<RwLock<Vec<u8>>>>>,
+    data_map: Mutex<BTreeMap<StoreKey, Arc<RwLock<Bytes>>>>,
     locks: StoreLocks,
 }
 
@@ -48,7 +52,7 @@ impl MemoryStore {
         }
     }
 
-    fn set_impl(&self, key: &StoreKey, value: &[u8], offset: Option<ByteOffset>, _truncate: bool) {
+    fn set_impl(&self, key: &StoreKey, value: &[u8], offset: Option<ByteOffset>, truncate: bool) {
         let mut data_map = self.data_map.lock().unwrap();
         let data = data_map
             .entry(key.clone())
@@ -58,18 +62,28 @@ impl MemoryStore {
         let mut data = data.write();
 
         let offset = offset.unwrap_or(0);
-        if offset == 0 && data.is_empty() {
+        if offset == 0 && (truncate || data.is_empty()) {
             // fast path
-            *data = value.to_vec();
+            *data = Bytes::copy_from_slice(value);
         } else {
+            // Does not copy if the value is not referenced elsewhere
+            let mut data_mut = Vec::from(std::mem::take(&mut *data));
             let length = usize::try_from(offset + value.len() as u64).unwrap();
-            if data.len() < length {
-                data.resize(length, 0);
+            if data_mut.len() < length {
+                data_mut.resize(length, 0);
             }
             let offset = usize::try_from(offset).unwrap();
-            data[offset..offset + value.len()].copy_from_slice(value);
+            data_mut[offset..offset + value.len()].copy_from_slice(value);
+            *data = Bytes::from(data_mut);
         }
     }
+
+    /// Return the generation token of a value, which is derived from its content.
+    fn generation(value: &[u8]) -> GenerationToken {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        GenerationToken::new(format!("{:016x}", hasher.finish()))
+    }
 }
 
 impl ReadableStorageTraits for MemoryStore {
@@ -86,11 +100,21 @@ impl ReadableStorageTraits for MemoryStore {
         }
     }
 
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        Ok(self.get(key)?.map(|value| {
+            let generation = Self::generation(&value);
+            (value, Some(generation))
+        }))
+    }
+
     fn get_partial_values_key(
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
         let data_map = self.data_map.lock().unwrap();
         let data = data_map.get(key);
         if let Some(data) = data {
@@ -104,7 +128,7 @@ impl ReadableStorageTraits for MemoryStore {
                 if end > data.len() {
                     return Err(InvalidByteRangeError::new(*byte_range, data.len() as u64).into());
                 }
-                let bytes = data[start..end].to_vec();
+                let bytes = data.slice(start..end);
                 out.push(bytes);
             }
             Ok(Some(out))
@@ -144,6 +168,27 @@ impl WritableStorageTraits for MemoryStore {
         Ok(())
     }
 
+    fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: &[u8],
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        // The map lock is held for the duration of the comparison and write
+        let mut data_map = self.data_map.lock().unwrap();
+        let current = data_map.get(key).map(|data| Self::generation(&data.read()));
+        if current.as_ref() != generation {
+            return Err(StorageError::PreconditionFailed(key.clone()));
+        }
+        let data = data_map
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(RwLock::default()))
+            .clone();
+        *data.write() = Bytes::copy_from_slice(value);
+        drop(data_map);
+        Ok(Some(Self::generation(value)))
+    }
+
     fn set_partial_values(
         &self,
         key_start_values: &[StoreKeyStartValue],
@@ -229,4 +274,26 @@ mod tests {
         super::super::test_util::store_list(&store)?;
         Ok(())
     }
+
+    #[test]
+    fn memory_set_if_match() -> Result<(), Box<dyn Error>> {
+        let store = MemoryStore::new();
+        let key = StoreKey::new("a")?;
+        let generation = store.set_if_match(&key, &[0, 1], None)?.unwrap();
+        assert!(matches!(
+            store.set_if_match(&key, &[2], None),
+            Err(StorageError::PreconditionFailed(_))
+        ));
+        let (value, current) = store.get_with_generation(&key)?.unwrap();
+        assert_eq!(value.as_ref(), &[0, 1]);
+        assert_eq!(current.as_ref(), Some(&generation));
+        let generation_new = store.set_if_match(&key, &[2], Some(&generation))?.unwrap();
+        assert!(matches!(
+            store.set_if_match(&key, &[3], Some(&generation)),
+            Err(StorageError::PreconditionFailed(_))
+        ));
+        store.set_if_match(&key, &[3], Some(&generation_new))?;
+        assert_eq!(store.get(&key)?.unwrap().as_ref(), &[3]);
+        Ok(())
+    }
 }
@@ -0,0 +1,371 @@
+//! A store which mounts stores at prefixes.
+
+use bytes::Bytes;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    storage::{
+        store_lock::StoreKeyMutex, GenerationToken, ListableStorageTraits, ReadableStorageTraits,
+        ReadableWritableListableStorage, ReadableWritableStorageTraits, StorageError, StoreKey,
+        StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes, StorePrefix,
+        WritableStorageTraits,
+    },
+};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A store which maps prefixes to different underlying stores.
+///
+/// A key is resolved to the store mounted at the longest prefix of the key, and the mount prefix is stripped from the key.
+/// For example, if a store is mounted at `raw/`, the key `raw/array/zarr.json` is the key `array/zarr.json` of that store.
+/// A store mounted at the root prefix receives all keys which are not under any other mount.
+///
+/// This allows a hierarchy to span multiple backends (e.g. `raw/` on an object store and `derived/` on a local filesystem) and be manipulated through a single [`Array`](crate::array::Array)/[`Group`](crate::group::Group) API.
+/// Read only stores can be mounted by wrapping them in a [`ReadOnlyStorageAdapter`](crate::storage::storage_adapter::ReadOnlyStorageAdapter).
+///
+/// Keys without a mount do not exist, and writing them fails.
+/// Listing includes the mount prefixes and excludes keys shadowed by a nested mount.
+#[derive(Default)]
+pub struct MountStore {
+    mounts: BTreeMap<StorePrefix, ReadableWritableListableStorage>,
+}
+
+impl std::fmt::Debug for MountStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MountStore")
+            .field("mounts", &self.mounts.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl MountStore {
+    /// Create a new mount store without any mounts.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mount `storage` at `prefix`, replacing any store already mounted at `prefix`.
+    pub fn mount(
+        &mut self,
+        prefix: StorePrefix,
+        storage: ReadableWritableListableStorage,
+    ) -> &mut Self {
+        self.mounts.insert(prefix, storage);
+        self
+    }
+
+    /// Unmount the store at `prefix`, returning it if it was mounted.
+    pub fn unmount(&mut self, prefix: &StorePrefix) -> Option<ReadableWritableListableStorage> {
+        self.mounts.remove(prefix)
+    }
+
+    /// Return the mount prefixes.
+    #[must_use]
+    pub fn mount_prefixes(&self) -> Vec<StorePrefix> {
+        self.mounts.keys().cloned().collect()
+    }
+
+    /// Return the mount prefix and store of `key`, and the key in that store.
+    fn resolve(
+        &self,
+        key: &StoreKey,
+    ) -> Option<(&StorePrefix, &ReadableWritableListableStorage, StoreKey)> {
+        // If multiple mount prefixes are a prefix of the key, the longest is the lexicographically greatest
+        self.mounts
+            .iter()
+            .rev()
+            .find(|(prefix, _)| key.has_prefix(prefix))
+            .map(|(prefix, storage)| {
+                let key_inner = &key.as_str()[prefix.as_str().len()..];
+                (prefix, storage, unsafe {
+                    StoreKey::new_unchecked(key_inner)
+                })
+            })
+    }
+
+    fn resolve_or_err(
+        &self,
+        key: &StoreKey,
+    ) -> Result<(&ReadableWritableListableStorage, StoreKey), StorageError> {
+        self.resolve(key)
+            .map(|(_, storage, key_inner)| (storage, key_inner))
+            .ok_or_else(|| StorageError::Other(format!("no store is mounted for key {key}")))
+    }
+
+    /// Return true if `key` of the store mounted at `mount_prefix` is not shadowed by a nested mount.
+    fn is_visible(&self, mount_prefix: &StorePrefix, key: &StoreKey) -> bool {
+        self.resolve(key)
+            .is_some_and(|(prefix, _, _)| prefix == mount_prefix)
+    }
+
+    fn key_outer(mount_prefix: &StorePrefix, key_inner: &StoreKey) -> StoreKey {
+        unsafe { StoreKey::new_unchecked(mount_prefix.as_str().to_string() + key_inner.as_str()) }
+    }
+
+    fn prefix_outer(mount_prefix: &StorePrefix, prefix_inner: &StorePrefix) -> StorePrefix {
+        unsafe {
+            StorePrefix::new_unchecked(mount_prefix.as_str().to_string() + prefix_inner.as_str())
+        }
+    }
+}
+
+impl ReadableStorageTraits for MountStore {
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        match self.resolve(key) {
+            Some((_, storage, key_inner)) => storage.get(&key_inner),
+            None => Ok(None),
+        }
+    }
+
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        match self.resolve(key) {
+            Some((_, storage, key_inner)) => storage.get_with_generation(&key_inner),
+            None => Ok(None),
+        }
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        match self.resolve(key) {
+            Some((_, storage, key_inner)) => {
+                storage.get_partial_values_key(&key_inner, byte_ranges)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges)
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        let mut size = 0;
+        for key in self.list_prefix(prefix)? {
+            size += self.size_key(&key)?.unwrap_or_default();
+        }
+        Ok(size)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        match self.resolve(key) {
+            Some((_, storage, key_inner)) => storage.size_key(&key_inner),
+            None => Ok(None),
+        }
+    }
+}
+
+impl WritableStorageTraits for MountStore {
+    fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        let (storage, key_inner) = self.resolve_or_err(key)?;
+        storage.set(&key_inner, value)
+    }
+
+    fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        for key_start_value in key_start_values {
+            let (storage, key_inner) = self.resolve_or_err(&key_start_value.key)?;
+            storage.set_partial_values(&[StoreKeyStartValue::new(
+                key_inner,
+                key_start_value.start,
+                key_start_value.value,
+            )])?;
+        }
+        Ok(())
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        match self.resolve(key) {
+            Some((_, storage, key_inner)) => storage.erase(&key_inner),
+            None => Ok(()),
+        }
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        for (mount_prefix, storage) in &self.mounts {
+            if let Some(prefix_inner) = prefix.as_str().strip_prefix(mount_prefix.as_str()) {
+                storage.erase_prefix(&unsafe { StorePrefix::new_unchecked(prefix_inner) })?;
+            } else if mount_prefix.as_str().starts_with(prefix.as_str()) {
+                storage.erase_prefix(&StorePrefix::root())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: &[u8],
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        let (storage, key_inner) = self.resolve_or_err(key)?;
+        storage.set_if_match(&key_inner, value, generation)
+    }
+}
+
+impl ReadableWritableStorageTraits for MountStore {
+    fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+        let (storage, key_inner) = self.resolve_or_err(key)?;
+        storage.mutex(&key_inner)
+    }
+}
+
+impl ListableStorageTraits for MountStore {
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.list_prefix(&StorePrefix::root())
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        let mut keys = BTreeSet::new();
+        for (mount_prefix, storage) in &self.mounts {
+            let keys_inner =
+                if let Some(prefix_inner) = prefix.as_str().strip_prefix(mount_prefix.as_str()) {
+                    storage.list_prefix(&unsafe { StorePrefix::new_unchecked(prefix_inner) })?
+                } else if mount_prefix.as_str().starts_with(prefix.as_str()) {
+                    storage.list()?
+                } else {
+                    continue;
+                };
+            keys.extend(
+                keys_inner
+                    .iter()
+                    .map(|key_inner| Self::key_outer(mount_prefix, key_inner))
+                    .filter(|key| self.is_visible(mount_prefix, key)),
+            );
+        }
+        Ok(keys.into_iter().collect())
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        let mut keys = BTreeSet::new();
+        let mut prefixes = BTreeSet::new();
+        for (mount_prefix, storage) in &self.mounts {
+            if let Some(prefix_inner) = prefix.as_str().strip_prefix(mount_prefix.as_str()) {
+                let list_dir =
+                    storage.list_dir(&unsafe { StorePrefix::new_unchecked(prefix_inner) })?;
+                keys.extend(
+                    list_dir
+                        .keys()
+                        .iter()
+                        .map(|key_inner| Self::key_outer(mount_prefix, key_inner))
+                        .filter(|key| self.is_visible(mount_prefix, key)),
+                );
+                prefixes.extend(
+                    list_dir
+                        .prefixes()
+                        .iter()
+                        .map(|prefix_inner| Self::prefix_outer(mount_prefix, prefix_inner)),
+                );
+            } else if let Some(mount_suffix) = mount_prefix.as_str().strip_prefix(prefix.as_str()) {
+                // The mount prefix is a descendant of the prefix
+                let child = mount_suffix.split('/').next().unwrap_or_default();
+                prefixes.insert(StorePrefix::new(prefix.as_str().to_string() + child + "/")?);
+            }
+        }
+        Ok(StoreKeysPrefixes {
+            keys: keys.into_iter().collect(),
+            prefixes: prefixes.into_iter().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, sync::Arc};
+
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn mount_store() -> Result<(), Box<dyn Error>> {
+        let store_root = Arc::new(MemoryStore::new());
+        let store_f = Arc::new(MemoryStore::new());
+        let store_i = Arc::new(MemoryStore::new());
+        let mut store = MountStore::new();
+        store
+            .mount(StorePrefix::root(), store_root.clone())
+            .mount("a/f/".try_into()?, store_f.clone())
+            .mount("i/".try_into()?, store_i.clone());
+        assert_eq!(
+            store.mount_prefixes(),
+            &[StorePrefix::root(), "a/f/".try_into()?, "i/".try_into()?]
+        );
+        super::super::test_util::store_write(&store)?;
+        super::super::test_util::store_read(&store)?;
+        super::super::test_util::store_list(&store)?;
+
+        // Keys are stored in the store with the longest mount prefix, relative to that prefix
+        assert_eq!(store_f.list()?, &["g".try_into()?, "h".try_into()?]);
+        assert_eq!(store_i.list()?, &["j/k".try_into()?]);
+        assert_eq!(
+            store_root.list()?,
+            &["a/b".try_into()?, "a/c".try_into()?, "a/d/e".try_into()?]
+        );
+
+        // Keys of a store shadowed by a nested mount are not visible
+        store_root.set(&"i/shadowed".try_into()?, &[])?;
+        assert!(store.get(&"i/shadowed".try_into()?)?.is_none());
+        assert_eq!(
+            store.list_prefix(&"i/".try_into()?)?,
+            &["i/j/k".try_into()?]
+        );
+
+        // Mount prefixes are listed even if the mounted store is empty
+        store.mount("x/y/".try_into()?, Arc::new(MemoryStore::new()));
+        assert_eq!(
+            store.list_dir(&StorePrefix::root())?.prefixes(),
+            &["a/".try_into()?, "i/".try_into()?, "x/".try_into()?]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn mount_store_array() -> Result<(), Box<dyn Error>> {
+        let store_raw = Arc::new(MemoryStore::new());
+        let mut store = MountStore::new();
+        store.mount("raw/".try_into()?, store_raw.clone());
+        let store = Arc::new(store);
+
+        let array = ArrayBuilder::new(
+            vec![4],
+            DataType::UInt8,
+            vec![2].try_into()?,
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/raw/array")?;
+        array.store_metadata()?;
+        array.store_chunk_elements::<u8>(&[1], vec![1, 2])?;
+        assert_eq!(
+            store_raw.list()?,
+            &["array/c/1".try_into()?, "array/zarr.json".try_into()?]
+        );
+
+        // Keys outside of any mount do not exist and cannot be written
+        let array = ArrayBuilder::new(
+            vec![4],
+            DataType::UInt8,
+            vec![2].try_into()?,
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/derived/array")?;
+        assert!(array.store_metadata().is_err());
+        assert!(store.get(&"derived/array/zarr.json".try_into()?)?.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,439 @@
+//! Synchronous stores backed by an [`object_store::ObjectStore`].
+//!
+//! This includes cloud stores such as Amazon S3, Google Cloud Storage, and Microsoft Azure Blob Storage with the `aws`, `gcp`, and `azure` features of the `object_store` crate.
+//!
+//! ```
+//! # use zarrs::storage::{store::ObjectStore, ReadableStorageTraits, WritableStorageTraits};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // e.g. object_store::aws::AmazonS3Builder::from_env().with_bucket_name("bucket").build()?
+//! let object_store = object_store::memory::InMemory::new();
+//! let store = ObjectStore::new(object_store)?;
+//! store.set(&"key".try_into()?, &[0, 1, 2])?;
+//! assert_eq!(store.get(&"key".try_into()?)?, Some(vec![0, 1, 2].into()));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    storage::{
+        storage_adapter::is_transient_io_error_kind,
+        store_lock::{DefaultStoreLocks, StoreKeyMutex, StoreLocks},
+        GenerationToken, ListableStorageTraits, ReadableStorageTraits,
+        ReadableWritableStorageTraits, StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue,
+        StoreKeys, StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
+    },
+};
+
+impl From<object_store::Error> for StorageError {
+    fn from(err: object_store::Error) -> Self {
+        if is_transient_object_store_error(&err) {
+            Self::Transient(err.to_string())
+        } else {
+            Self::Other(err.to_string())
+        }
+    }
+}
+
+/// Returns true if an [`object_store::Error`] is retryable.
+///
+/// An error is retryable if its source chain contains an IO error indicating an interruption, timeout, or connection issue.
+/// With the `http` feature, a [`reqwest::Error`] in the source chain is also retryable if it is a timeout, a connection failure, or has a server error or rate limiting status.
+fn is_transient_object_store_error(err: &object_store::Error) -> bool {
+    if !matches!(err, object_store::Error::Generic { .. }) {
+        return false;
+    }
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<std::io::Error>() {
+            if is_transient_io_error_kind(err.kind()) {
+                return true;
+            }
+        }
+        #[cfg(feature = "http")]
+        if let Some(err) = err.downcast_ref::<reqwest::Error>() {
+            if err.is_timeout()
+                || err.is_connect()
+                || err.status().is_some_and(|status| {
+                    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                })
+            {
+                return true;
+            }
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Maps a [`StoreKey`] to an [`object_store`] path.
+pub(crate) fn key_to_path(key: &StoreKey) -> object_store::path::Path {
+    object_store::path::Path::from(key.as_str())
+}
+
+/// Map [`object_store::Error::NotFound`] to None, pass through other errors
+pub(crate) fn handle_result<T>(
+    result: Result<T, object_store::Error>,
+) -> Result<Option<T>, StorageError> {
+    match result {
+        Ok(result) => Ok(Some(result)),
+        Err(err) => {
+            if matches!(err, object_store::Error::NotFound { .. }) {
+                Ok(None)
+            } else {
+                Err(err.into())
+            }
+        }
+    }
+}
+
+/// Encode the `e_tag` and `version` of an object as a [`GenerationToken`].
+pub(crate) fn generation_from_parts(
+    e_tag: Option<String>,
+    version: Option<String>,
+) -> Option<GenerationToken> {
+    match (e_tag, version) {
+        (None, None) => None,
+        (e_tag, version) => Some(GenerationToken::new(format!(
+            "{}\n{}",
+            e_tag.unwrap_or_default(),
+            version.unwrap_or_default()
+        ))),
+    }
+}
+
+/// Map a [`GenerationToken`] to the [`object_store::PutMode`] of a conditional write.
+///
+/// A value is only created if it does not exist if `generation` is [`None`].
+pub(crate) fn generation_to_put_mode(
+    generation: Option<&GenerationToken>,
+) -> object_store::PutMode {
+    generation.map_or(object_store::PutMode::Create, |generation| {
+        let (e_tag, version) = generation
+            .as_str()
+            .split_once('\n')
+            .unwrap_or((generation.as_str(), ""));
+        let non_empty = |part: &str| (!part.is_empty()).then(|| part.to_string());
+        object_store::PutMode::Update(object_store::UpdateVersion {
+            e_tag: non_empty(e_tag),
+            version: non_empty(version),
+        })
+    })
+}
+
+/// Map the result of a conditional write to a [`GenerationToken`].
+pub(crate) fn handle_put_result(
+    key: &StoreKey,
+    result: Result<object_store::PutResult, object_store::Error>,
+) -> Result<Option<GenerationToken>, StorageError> {
+    match result {
+        Ok(result) => Ok(generation_from_parts(result.e_tag, result.version)),
+        Err(
+            object_store::Error::Precondition { .. } | object_store::Error::AlreadyExists { .. },
+        ) => Err(StorageError::PreconditionFailed(key.clone())),
+        Err(object_store::Error::NotImplemented | object_store::Error::NotSupported { .. }) => Err(
+            StorageError::Unsupported("the store does not support conditional writes".to_string()),
+        ),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// A synchronous store backed by an [`object_store::ObjectStore`].
+///
+/// Operations are blocked on an internal [`tokio`] runtime.
+/// The methods of this store must not be called from within an asynchronous context, such as a [`tokio`] runtime.
+/// Use an [`AsyncObjectStore`](crate::storage::store::AsyncObjectStore) in that case.
+pub struct ObjectStore<T: object_store::ObjectStore> {
+    store: T,
+    runtime: tokio::runtime::Runtime,
+    locks: StoreLocks,
+}
+
+impl<T: object_store::ObjectStore> ObjectStore<T> {
+    /// Create a new [`ObjectStore`].
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the internal runtime cannot be created.
+    pub fn new(object_store: T) -> Result<Self, StorageError> {
+        Self::new_with_locks(object_store, Arc::new(DefaultStoreLocks::default()))
+    }
+
+    /// Create a new [`ObjectStore`] with non-default store locks.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the internal runtime cannot be created.
+    pub fn new_with_locks(object_store: T, store_locks: StoreLocks) -> Result<Self, StorageError> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            store: object_store,
+            runtime,
+            locks: store_locks,
+        })
+    }
+
+    fn list_paths(&self, prefix: Option<&Path>) -> Result<StoreKeys, StorageError> {
+        let mut list = self
+            .runtime
+            .block_on(self.store.list(prefix).try_collect::<Vec<_>>())?
+            .into_iter()
+            .map(|object_meta| {
+                let path: &str = object_meta.location.as_ref();
+                StoreKey::try_from(path)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        list.sort();
+        Ok(list)
+    }
+
+    fn size_paths(&self, prefix: Option<&Path>) -> Result<u64, StorageError> {
+        self.runtime.block_on(async {
+            let mut locations = self.store.list(prefix);
+            let mut size = 0;
+            while let Some(item) = locations.next().await {
+                let meta = item?;
+                size += u64::try_from(meta.size).unwrap();
+            }
+            Ok(size)
+        })
+    }
+}
+
+impl<T: object_store::ObjectStore> ReadableStorageTraits for ObjectStore<T> {
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.runtime.block_on(async {
+            let get = handle_result(self.store.get(&key_to_path(key)).await)?;
+            if let Some(get) = get {
+                let bytes = get.bytes().await?;
+                Ok(Some(bytes))
+            } else {
+                Ok(None)
+            }
+        })
+    }
+
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        self.runtime.block_on(async {
+            let get = handle_result(self.store.get(&key_to_path(key)).await)?;
+            if let Some(get) = get {
+                let generation =
+                    generation_from_parts(get.meta.e_tag.clone(), get.meta.version.clone());
+                let bytes = get.bytes().await?;
+                Ok(Some((bytes, generation)))
+            } else {
+                Ok(None)
+            }
+        })
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let Some(size) = self.size_key(key)? else {
+            return Ok(None);
+        };
+        let ranges = byte_ranges
+            .iter()
+            .map(|byte_range| byte_range.to_range_usize(size))
+            .collect::<Vec<_>>();
+        let get_ranges = self
+            .runtime
+            .block_on(self.store.get_ranges(&key_to_path(key), &ranges));
+        let Some(get_ranges) = handle_result(get_ranges)? else {
+            return Ok(None);
+        };
+        Ok(Some(
+            std::iter::zip(ranges, get_ranges)
+                .map(|(range, bytes)| {
+                    if range.len() == bytes.len() {
+                        Ok(bytes)
+                    } else {
+                        Err(StorageError::Other(format!(
+                            "Unexpected length of bytes returned, expected {}, got {}",
+                            range.len(),
+                            bytes.len()
+                        )))
+                    }
+                })
+                .collect::<Result<_, StorageError>>()?,
+        ))
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges)
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        let prefix: object_store::path::Path = prefix.as_str().into();
+        self.size_paths(Some(&prefix))
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        Ok(
+            handle_result(self.runtime.block_on(self.store.head(&key_to_path(key))))?
+                .map(|meta| meta.size as u64),
+        )
+    }
+
+    fn size(&self) -> Result<u64, StorageError> {
+        self.size_paths(None)
+    }
+}
+
+impl<T: object_store::ObjectStore> WritableStorageTraits for ObjectStore<T> {
+    fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        let bytes = bytes::Bytes::copy_from_slice(value);
+        self.runtime
+            .block_on(self.store.put(&key_to_path(key), bytes))?;
+        Ok(())
+    }
+
+    fn set_if_match(
+        &self,
+        key: &StoreKey,
+        value: &[u8],
+        generation: Option<&GenerationToken>,
+    ) -> Result<Option<GenerationToken>, StorageError> {
+        let bytes = bytes::Bytes::copy_from_slice(value);
+        let options = generation_to_put_mode(generation).into();
+        handle_put_result(
+            key,
+            self.runtime
+                .block_on(self.store.put_opts(&key_to_path(key), bytes, options)),
+        )
+    }
+
+    fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        crate::storage::store_set_partial_values(self, key_start_values)
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        handle_result(self.runtime.block_on(self.store.delete(&key_to_path(key))))?;
+        Ok(())
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        let prefix: object_store::path::Path = prefix.as_str().into();
+        let locations = self
+            .store
+            .list(Some(&prefix))
+            .map_ok(|m| m.location)
+            .boxed();
+        self.runtime.block_on(
+            self.store
+                .delete_stream(locations)
+                .try_collect::<Vec<Path>>(),
+        )?;
+        Ok(())
+    }
+}
+
+impl<T: object_store::ObjectStore> ReadableWritableStorageTraits for ObjectStore<T> {
+    fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+        Ok(self.locks.mutex(key))
+    }
+}
+
+impl<T: object_store::ObjectStore> ListableStorageTraits for ObjectStore<T> {
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.list_paths(None)
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        let path: object_store::path::Path = prefix.as_str().into();
+        self.list_paths(Some(&path))
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        let path: object_store::path::Path = prefix.as_str().into();
+        let list_result = self
+            .runtime
+            .block_on(self.store.list_with_delimiter(Some(&path)))?;
+        let mut prefixes = list_result
+            .common_prefixes
+            .iter()
+            .map(|path| {
+                let path: &str = path.as_ref();
+                StorePrefix::new(path.to_string() + "/")
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut keys = list_result
+            .objects
+            .iter()
+            .map(|object_meta| {
+                let path: &str = object_meta.location.as_ref();
+                StoreKey::try_from(path)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        keys.sort();
+        prefixes.sort();
+        Ok(StoreKeysPrefixes { keys, prefixes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn memory() -> Result<(), Box<dyn Error>> {
+        let store = ObjectStore::new(object_store::memory::InMemory::new())?;
+        super::super::test_util::store_write(&store)?;
+        super::super::test_util::store_read(&store)?;
+        super::super::test_util::store_list(&store)?;
+        Ok(())
+    }
+
+    #[test]
+    fn memory_set_if_match() -> Result<(), Box<dyn Error>> {
+        let store = ObjectStore::new(object_store::memory::InMemory::new())?;
+        let key = StoreKey::new("a")?;
+        let generation = store.set_if_match(&key, &[0, 1], None)?;
+        assert!(matches!(
+            store.set_if_match(&key, &[2], None),
+            Err(StorageError::PreconditionFailed(_))
+        ));
+        let (_, current) = store.get_with_generation(&key)?.unwrap();
+        assert_eq!(current, generation);
+        store.set_if_match(&key, &[2], current.as_ref())?;
+        assert!(matches!(
+            store.set_if_match(&key, &[3], current.as_ref()),
+            Err(StorageError::PreconditionFailed(_))
+        ));
+        assert_eq!(store.get(&key)?.unwrap().as_ref(), &[2]);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn filesystem() -> Result<(), Box<dyn Error>> {
+        let path = tempfile::TempDir::new()?;
+        let store = ObjectStore::new(object_store::local::LocalFileSystem::new_with_prefix(
+            path.path(),
+        )?)?;
+        super::super::test_util::store_write(&store)?;
+        super::super::test_util::store_read(&store)?;
+        super::super::test_util::store_list(&store)?;
+        Ok(())
+    }
+}
@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
+use bytes::Bytes;
 use opendal::BlockingOperator;
 
 use crate::{
     array::MaybeBytes,
-    byte_range::ByteRange,
+    byte_range::{ByteRange, InvalidByteRangeError},
     storage::{
         store_lock::{DefaultStoreLocks, StoreKeyMutex, StoreLocks},
         ListableStorageTraits, ReadableStorageTraits, ReadableWritableStorageTraits, StorageError,
@@ -13,7 +14,7 @@ use crate::{
     },
 };
 
-/// An asynchronous store backed by a [`BlockingOperator`].
+/// A synchronous store backed by a [`BlockingOperator`].
 pub struct OpendalStore {
     operator: BlockingOperator,
     locks: StoreLocks,
@@ -53,61 +54,41 @@ fn handle_result<T>(result: Result<T, opendal::Error>) -> Result<Option<T>, Stor
 #[async_trait::async_trait]
 impl ReadableStorageTraits for OpendalStore {
     fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
-        handle_result(self.operator.read(key.as_str()))
+        Ok(handle_result(self.operator.read(key.as_str()))?.map(Bytes::from))
     }
 
     fn get_partial_values_key(
         &self,
         key: &StoreKey,
         byte_ranges: &[ByteRange],
-    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
-        // FIXME: Does opendal offer a better way of retrieving multiple byte ranges?
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
         // FIXME: Coalesce like object_store?
-        if byte_ranges
+        let Some(size) = self.size_key(key)? else {
+            return Ok(None);
+        };
+        byte_ranges
             .iter()
-            .all(|byte_range| matches!(byte_range, ByteRange::FromEnd(_, _)))
-        {
-            let bytes = byte_ranges
-                .iter()
-                .map(|byte_range| {
-                    self.operator
-                        .read_with(key.as_str())
-                        .range(byte_range.offset()..)
-                        .call()
-                })
-                .collect::<Result<Vec<_>, _>>();
-            handle_result(bytes)
-        } else {
-            let size = self
-                .size_key(key)?
-                .ok_or(StorageError::UnknownKeySize(key.clone()))?;
-            let bytes = byte_ranges
-                .iter()
-                .map(|byte_range| {
-                    let start = byte_range.start(size);
-                    let end = byte_range.end(size);
-                    match self
-                        .operator
-                        .read_with(key.as_str())
-                        .range(start..end)
-                        .call()
-                    {
-                        Ok(bytes) => {
-                            if (end - start) == bytes.len() as u64 {
-                                Ok(bytes)
-                            } else {
-                                Err(opendal::Error::new(
-                                    opendal::ErrorKind::InvalidInput,
-                                    "InvalidByteRangeError",
-                                ))
-                            }
-                        }
-                        Err(err) => Err(err),
-                    }
-                })
-                .collect::<Result<Vec<_>, _>>();
-            handle_result(bytes)
-        }
+            .map(|byte_range| {
+                let (ByteRange::FromStart(offset, length) | ByteRange::FromEnd(offset, length)) =
+                    byte_range;
+                if offset + length.unwrap_or(0) > size {
+                    return Err(InvalidByteRangeError::new(*byte_range, size).into());
+                }
+                let start = byte_range.start(size);
+                let end = byte_range.end(size);
+                let bytes = self
+                    .operator
+                    .read_with(key.as_str())
+                    .range(start..end)
+                    .call()?;
+                if (end - start) == bytes.len() as u64 {
+                    Ok(Bytes::from(bytes))
+                } else {
+                    Err(InvalidByteRangeError::new(*byte_range, bytes.len() as u64).into())
+                }
+            })
+            .collect::<Result<Vec<_>, StorageError>>()
+            .map(Some)
     }
 
     fn get_partial_values(
@@ -239,6 +220,25 @@ mod tests {
         super::super::test_util::store_write(&store)?;
         super::super::test_util::store_read(&store)?;
         super::super::test_util::store_list(&store)?;
+
+        let key = StoreKey::new("partial")?;
+        store.set(&key, &[0, 1, 2, 3, 4, 5])?;
+        assert_eq!(
+            store.get_partial_values_key(
+                &key,
+                &[
+                    ByteRange::FromEnd(1, Some(2)),
+                    ByteRange::FromEnd(0, Some(1))
+                ]
+            )?,
+            Some(vec![vec![3, 4].into(), vec![5].into()])
+        );
+        assert!(store
+            .get_partial_values_key(&key, &[ByteRange::FromEnd(5, Some(2))])
+            .is_err());
+        assert!(store
+            .get_partial_values_key(&StoreKey::new("missing")?, &[ByteRange::FromStart(0, None)])?
+            .is_none());
         Ok(())
     }
 
@@ -0,0 +1,326 @@
+//! A copy-on-write overlay store.
+
+use bytes::Bytes;
+use parking_lot::Mutex;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    storage::{
+        store_lock::StoreKeyMutex, GenerationToken, ListableStorageTraits, ReadableListableStorage,
+        ReadableStorageTraits, ReadableWritableListableStorage, ReadableWritableStorageTraits,
+        StorageError, StoreKey, StoreKeyRange, StoreKeyStartValue, StoreKeys, StoreKeysPrefixes,
+        StorePrefix, WritableStorageTraits,
+    },
+};
+
+use std::collections::BTreeSet;
+
+/// Keys and prefixes of the base store which have been erased through an [`OverlayStore`].
+#[derive(Debug, Default)]
+struct Whiteouts {
+    keys: BTreeSet<StoreKey>,
+    prefixes: BTreeSet<StorePrefix>,
+}
+
+impl Whiteouts {
+    fn hides(&self, key: &StoreKey) -> bool {
+        self.keys.contains(key) || self.prefixes.iter().any(|prefix| key.has_prefix(prefix))
+    }
+}
+
+/// A union of a read only base store and a writable overlay store with copy-on-write semantics.
+///
+/// Values are read from the overlay store if they exist there, and otherwise from the base store.
+/// All writes go to the overlay store, so the base store is never modified.
+/// A partial write to a value which only exists in the base store first copies the value to the overlay store.
+/// Erasing a key or prefix erases it from the overlay store and hides it in the base store.
+///
+/// For example, an overlay store can be used to apply and test modifications to a hierarchy in a remote read only store, with the modifications written to a local store.
+///
+/// Erased keys and prefixes of the base store are only recorded in memory for the lifetime of the overlay store.
+pub struct OverlayStore {
+    base: ReadableListableStorage,
+    overlay: ReadableWritableListableStorage,
+    whiteouts: Mutex<Whiteouts>,
+}
+
+impl std::fmt::Debug for OverlayStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OverlayStore")
+            .field("whiteouts", &self.whiteouts)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OverlayStore {
+    /// Create a new overlay store of `overlay` over `base`.
+    #[must_use]
+    pub fn new(base: ReadableListableStorage, overlay: ReadableWritableListableStorage) -> Self {
+        Self {
+            base,
+            overlay,
+            whiteouts: Mutex::default(),
+        }
+    }
+
+    /// Return the base store.
+    #[must_use]
+    pub fn base(&self) -> ReadableListableStorage {
+        self.base.clone()
+    }
+
+    /// Return the overlay store.
+    #[must_use]
+    pub fn overlay(&self) -> ReadableWritableListableStorage {
+        self.overlay.clone()
+    }
+
+    /// Returns true if `key` of the base store is hidden because it was erased.
+    #[must_use]
+    pub fn is_erased(&self, key: &StoreKey) -> bool {
+        self.whiteouts.lock().hides(key)
+    }
+
+    /// Return the base store if `key` is not hidden in it.
+    fn base_if_visible(&self, key: &StoreKey) -> Option<&ReadableListableStorage> {
+        if self.is_erased(key) {
+            None
+        } else {
+            Some(&self.base)
+        }
+    }
+
+    /// Returns true if a key of the base store is visible under `prefix`.
+    fn base_prefix_visible(&self, prefix: &StorePrefix) -> Result<bool, StorageError> {
+        let whiteouts = self.whiteouts.lock();
+        if whiteouts
+            .prefixes
+            .iter()
+            .any(|whiteout| prefix.as_str().starts_with(whiteout.as_str()))
+        {
+            Ok(false)
+        } else if whiteouts.keys.iter().any(|key| key.has_prefix(prefix)) {
+            drop(whiteouts);
+            Ok(self
+                .base
+                .list_prefix(prefix)?
+                .iter()
+                .any(|key| !self.is_erased(key)))
+        } else {
+            Ok(true)
+        }
+    }
+}
+
+impl ReadableStorageTraits for OverlayStore {
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        match self.overlay.get(key)? {
+            Some(value) => Ok(Some(value)),
+            None => match self.base_if_visible(key) {
+                Some(base) => base.get(key),
+                None => Ok(None),
+            },
+        }
+    }
+
+    fn get_with_generation(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<(Bytes, Option<GenerationToken>)>, StorageError> {
+        match self.overlay.get_with_generation(key)? {
+            Some(value) => Ok(Some(value)),
+            None => match self.base_if_visible(key) {
+                Some(base) => base.get_with_generation(key),
+                None => Ok(None),
+            },
+        }
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        match self.overlay.get_partial_values_key(key, byte_ranges)? {
+            Some(values) => Ok(Some(values)),
+            None => match self.base_if_visible(key) {
+                Some(base) => base.get_partial_values_key(key, byte_ranges),
+                None => Ok(None),
+            },
+        }
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges)
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        let mut size = 0;
+        for key in self.list_prefix(prefix)? {
+            size += self.size_key(&key)?.unwrap_or_default();
+        }
+        Ok(size)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        match self.overlay.size_key(key)? {
+            Some(size) => Ok(Some(size)),
+            None => match self.base_if_visible(key) {
+                Some(base) => base.size_key(key),
+                None => Ok(None),
+            },
+        }
+    }
+}
+
+impl WritableStorageTraits for OverlayStore {
+    fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        self.overlay.set(key, value)
+    }
+
+    fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        for key_start_value in key_start_values {
+            let key = &key_start_value.key;
+            if self.overlay.size_key(key)?.is_none() {
+                // Copy the value from the base store before it is modified
+                if let Some(value) = self
+                    .base_if_visible(key)
+                    .map(|base| base.get(key))
+                    .transpose()?
+                    .flatten()
+                {
+                    self.overlay.set(key, &value)?;
+                }
+            }
+        }
+        self.overlay.set_partial_values(key_start_values)
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.overlay.erase(key)?;
+        self.whiteouts.lock().keys.insert(key.clone());
+        Ok(())
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.overlay.erase_prefix(prefix)?;
+        let mut whiteouts = self.whiteouts.lock();
+        whiteouts.keys.retain(|key| !key.has_prefix(prefix));
+        whiteouts.prefixes.insert(prefix.clone());
+        Ok(())
+    }
+}
+
+impl ReadableWritableStorageTraits for OverlayStore {
+    fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+        self.overlay.mutex(key)
+    }
+}
+
+impl ListableStorageTraits for OverlayStore {
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.list_prefix(&StorePrefix::root())
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        let mut keys: BTreeSet<StoreKey> = self.overlay.list_prefix(prefix)?.into_iter().collect();
+        keys.extend(
+            self.base
+                .list_prefix(prefix)?
+                .into_iter()
+                .filter(|key| !self.is_erased(key)),
+        );
+        Ok(keys.into_iter().collect())
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        let list_dir_overlay = self.overlay.list_dir(prefix)?;
+        let list_dir_base = self.base.list_dir(prefix)?;
+        let mut keys: BTreeSet<StoreKey> = list_dir_overlay.keys().iter().cloned().collect();
+        keys.extend(
+            list_dir_base
+                .keys()
+                .iter()
+                .filter(|key| !self.is_erased(key))
+                .cloned(),
+        );
+        let mut prefixes: BTreeSet<StorePrefix> =
+            list_dir_overlay.prefixes().iter().cloned().collect();
+        for prefix in list_dir_base.prefixes() {
+            if !prefixes.contains(prefix) && self.base_prefix_visible(prefix)? {
+                prefixes.insert(prefix.clone());
+            }
+        }
+        Ok(StoreKeysPrefixes {
+            keys: keys.into_iter().collect(),
+            prefixes: prefixes.into_iter().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, sync::Arc};
+
+    use crate::storage::store::MemoryStore;
+
+    use super::*;
+
+    #[test]
+    fn overlay_store() -> Result<(), Box<dyn Error>> {
+        let store = OverlayStore::new(Arc::new(MemoryStore::new()), Arc::new(MemoryStore::new()));
+        super::super::test_util::store_write(&store)?;
+        super::super::test_util::store_read(&store)?;
+        super::super::test_util::store_list(&store)?;
+        Ok(())
+    }
+
+    #[test]
+    fn overlay_store_copy_on_write() -> Result<(), Box<dyn Error>> {
+        let base = Arc::new(MemoryStore::new());
+        super::super::test_util::store_write(base.as_ref())?;
+        let overlay = Arc::new(MemoryStore::new());
+        let store = OverlayStore::new(base.clone(), overlay.clone());
+
+        // Values are read from the base store
+        super::super::test_util::store_read(&store)?;
+        super::super::test_util::store_list(&store)?;
+
+        // A partial write copies the value from the base store
+        store.set_partial_values(&[StoreKeyStartValue::new("a/b".try_into()?, 3, &[3])])?;
+        assert_eq!(
+            store.get(&"a/b".try_into()?)?,
+            Some(vec![0, 1, 2, 3].into())
+        );
+        assert_eq!(
+            overlay.get(&"a/b".try_into()?)?,
+            Some(vec![0, 1, 2, 3].into())
+        );
+        assert_eq!(base.get(&"a/b".try_into()?)?, Some(vec![0, 1, 2].into()));
+
+        // Erased keys and prefixes are hidden in the base store
+        store.erase(&"a/c".try_into()?)?;
+        store.erase_prefix(&"a/f/".try_into()?)?;
+        store.erase(&"a/d/e".try_into()?)?;
+        assert!(store.get(&"a/c".try_into()?)?.is_none());
+        assert!(store.is_erased(&"a/f/g".try_into()?));
+        assert_eq!(store.list()?, &["a/b".try_into()?, "i/j/k".try_into()?]);
+        let list_dir = store.list_dir(&"a/".try_into()?)?;
+        assert_eq!(list_dir.keys(), &["a/b".try_into()?]);
+        assert!(list_dir.prefixes().is_empty());
+        assert_eq!(base.list()?.len(), 6);
+
+        // An erased key can be written again
+        store.set(&"a/f/g".try_into()?, &[4])?;
+        assert_eq!(store.get(&"a/f/g".try_into()?)?, Some(vec![4].into()));
+        assert!(store.get(&"a/f/h".try_into()?)?.is_none());
+        Ok(())
+    }
+}
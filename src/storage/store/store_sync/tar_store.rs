@@ -0,0 +1,281 @@
+//! A tar store.
+//!
+//! Only uncompressed tar archives are supported, since values are read directly from their offsets in the archive.
+
+use bytes::Bytes;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::{ByteRange, InvalidByteRangeError},
+    storage::{
+        ListableStorageTraits, ReadableStorageTraits, StorageError, StoreKey, StoreKeyRange,
+        StoreKeys, StoreKeysPrefixes, StorePrefix,
+    },
+};
+
+use thiserror::Error;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+/// The location of a value in a tar archive.
+#[derive(Debug, Clone, Copy)]
+struct TarEntry {
+    offset: u64,
+    size: u64,
+}
+
+/// A read only store of an uncompressed tar file on the filesystem.
+///
+/// The archive is indexed when the store is opened, so values are read from their offsets in the archive without extraction.
+/// Directory entries and other non-regular file entries (e.g. links) are ignored.
+/// If an archive contains the same key more than once, the last entry takes precedence.
+///
+/// A tar file can be created from any listable store with [`TarStore::create`].
+#[derive(Debug)]
+pub struct TarStore {
+    path: PathBuf,
+    entries: BTreeMap<StoreKey, TarEntry>,
+}
+
+impl TarStore {
+    /// Open a tar store for the tar file at `path`.
+    ///
+    /// # Errors
+    /// Returns a [`TarStoreCreateError`] if `path` is not a valid path to a tar file or an entry of the archive is not a valid store key.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, TarStoreCreateError> {
+        let path = path.as_ref().to_path_buf();
+        if path.is_dir() {
+            return Err(TarStoreCreateError::ExistingDir(path));
+        }
+        let mut archive = tar::Archive::new(BufReader::new(File::open(&path)?));
+        let mut entries = BTreeMap::new();
+        for entry in archive.entries()? {
+            let entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let entry_path = entry.path()?;
+            let entry_path = entry_path.to_string_lossy();
+            let key = entry_path.strip_prefix("./").unwrap_or(&entry_path);
+            let key = StoreKey::new(key)
+                .map_err(|_| TarStoreCreateError::InvalidEntry(entry_path.to_string()))?;
+            entries.insert(
+                key,
+                TarEntry {
+                    offset: entry.raw_file_position(),
+                    size: entry.size(),
+                },
+            );
+        }
+        Ok(Self { path, entries })
+    }
+
+    /// Create a tar file at `path` with the content of `storage`, then open it as a tar store.
+    ///
+    /// Any existing file at `path` is overwritten.
+    ///
+    /// # Errors
+    /// Returns a [`TarStoreCreateError`] if the tar file cannot be written or `storage` cannot be read.
+    pub fn create<
+        P: AsRef<Path>,
+        TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits,
+    >(
+        path: P,
+        storage: &TStorage,
+    ) -> Result<Self, TarStoreCreateError> {
+        let path = path.as_ref();
+        if path.is_dir() {
+            return Err(TarStoreCreateError::ExistingDir(path.to_path_buf()));
+        }
+        let mut builder = tar::Builder::new(File::create(path)?);
+        for key in storage.list()? {
+            let Some(value) = storage.get(&key)? else {
+                continue;
+            };
+            let mut header = tar::Header::new_gnu();
+            header.set_size(value.len() as u64);
+            header.set_mode(0o644);
+            builder.append_data(&mut header, key.as_str(), value.as_ref())?;
+        }
+        builder.into_inner()?;
+
+        Self::new(path)
+    }
+
+    /// Return the path of the tar file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_byte_ranges(
+        &self,
+        entry: TarEntry,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Vec<Bytes>, StorageError> {
+        let mut file = File::open(&self.path)?;
+        let mut out = Vec::with_capacity(byte_ranges.len());
+        for byte_range in byte_ranges {
+            let end = byte_range.end(entry.size);
+            if end > entry.size {
+                return Err(InvalidByteRangeError::new(*byte_range, entry.size).into());
+            }
+            let start = byte_range.start(entry.size);
+            let mut bytes = vec![0; usize::try_from(end - start).unwrap()];
+            file.seek(SeekFrom::Start(entry.offset + start))?;
+            file.read_exact(&mut bytes)?;
+            out.push(Bytes::from(bytes));
+        }
+        Ok(out)
+    }
+}
+
+impl ReadableStorageTraits for TarStore {
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        Ok(self
+            .get_partial_values_key(key, &[ByteRange::FromStart(0, None)])?
+            .map(|mut values| values.remove(0)))
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        self.entries
+            .get(key)
+            .map(|entry| self.read_byte_ranges(*entry, byte_ranges))
+            .transpose()
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges)
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        Ok(self
+            .entries
+            .iter()
+            .filter(|(key, _)| key.has_prefix(prefix))
+            .map(|(_, entry)| entry.size)
+            .sum())
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        Ok(self.entries.get(key).map(|entry| entry.size))
+    }
+}
+
+impl ListableStorageTraits for TarStore {
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        Ok(self.entries.keys().cloned().collect())
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        Ok(self
+            .entries
+            .keys()
+            .filter(|key| key.has_prefix(prefix))
+            .cloned()
+            .collect())
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        let mut keys: StoreKeys = vec![];
+        let mut prefixes: BTreeSet<StorePrefix> = BTreeSet::default();
+        for key in self.entries.keys().filter(|key| key.has_prefix(prefix)) {
+            let key_strip = key.as_str().strip_prefix(prefix.as_str()).unwrap();
+            if let Some((child, _)) = key_strip.split_once('/') {
+                prefixes.insert(StorePrefix::new(prefix.as_str().to_string() + child + "/")?);
+            } else {
+                keys.push(key.clone());
+            }
+        }
+        let prefixes = prefixes.into_iter().collect();
+        Ok(StoreKeysPrefixes { keys, prefixes })
+    }
+}
+
+/// A tar store creation error.
+#[derive(Debug, Error)]
+pub enum TarStoreCreateError {
+    /// An IO error.
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    /// An existing directory.
+    #[error("{0} is an existing directory, not a tar file")]
+    ExistingDir(PathBuf),
+    /// An entry of the archive is not a valid store key.
+    #[error("tar entry {0} is not a valid store key")]
+    InvalidEntry(String),
+    /// A storage error.
+    #[error(transparent)]
+    StorageError(#[from] StorageError),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::store::MemoryStore;
+
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn tar_store() -> Result<(), Box<dyn Error>> {
+        let memory_store = MemoryStore::new();
+        super::super::test_util::store_write(&memory_store)?;
+
+        let path = tempfile::TempDir::new()?;
+        let tar_path = path.path().join("store.tar");
+        let store = TarStore::create(&tar_path, &memory_store)?;
+        assert_eq!(store.path(), tar_path);
+        super::super::test_util::store_read(&store)?;
+        super::super::test_util::store_list(&store)?;
+
+        let store = TarStore::new(&tar_path)?;
+        super::super::test_util::store_read(&store)?;
+        super::super::test_util::store_list(&store)?;
+
+        assert!(TarStore::new(path.path()).is_err());
+        assert!(TarStore::new(path.path().join("missing.tar")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn tar_store_archive() -> Result<(), Box<dyn Error>> {
+        // An archive of a directory (e.g. `tar -cf store.tar -C dir .`) has directory entries and `./` prefixed paths
+        let path = tempfile::TempDir::new()?;
+        let tar_path = path.path().join("store.tar");
+        let mut builder = tar::Builder::new(File::create(&tar_path)?);
+        let mut header = tar::Header::new_ustar();
+        header.set_entry_type(tar::EntryType::Directory);
+        header.set_size(0);
+        builder.append_data(&mut header, "./array/", std::io::empty())?;
+        let mut header = tar::Header::new_ustar();
+        header.set_size(4);
+        builder.append_data(&mut header, "./array/zarr.json", b"{}\n\n".as_slice())?;
+        builder.into_inner()?;
+
+        let store = TarStore::new(&tar_path)?;
+        assert_eq!(store.list()?, &["array/zarr.json".try_into()?]);
+        assert_eq!(
+            store.get(&"array/zarr.json".try_into()?)?,
+            Some(b"{}\n\n".to_vec().into())
+        );
+        assert_eq!(
+            store.list_dir(&StorePrefix::root())?.prefixes(),
+            &["array/".try_into()?]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+//! A zip store.
+//!
+//! Values are stored uncompressed in the zip archive, since chunks are typically already compressed by their codecs.
+
+use bytes::Bytes;
+
+use crate::{
+    array::MaybeBytes,
+    byte_range::ByteRange,
+    storage::{
+        storage_adapter::{ZipStorageAdapter, ZipStorageAdapterCreateError},
+        store::{FilesystemStore, FilesystemStoreCreateError},
+        ListableStorageTraits, ReadableStorageTraits, StorageError, StoreKey, StoreKeyRange,
+        StoreKeys, StoreKeysPrefixes, StorePrefix,
+    },
+};
+
+use thiserror::Error;
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// A read only store of a zip file on the filesystem.
+///
+/// A zip file can be created from any listable store with [`ZipStore::create`].
+pub struct ZipStore {
+    path: PathBuf,
+    zip_storage_adapter: ZipStorageAdapter<FilesystemStore>,
+}
+
+impl ZipStore {
+    /// Open a zip store for the zip file at `path`.
+    ///
+    /// # Errors
+    /// Returns a [`ZipStoreCreateError`] if `path` is not a valid path to a zip file.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, ZipStoreCreateError> {
+        let path = path.as_ref().to_path_buf();
+        if path.is_dir() {
+            return Err(ZipStoreCreateError::ExistingDir(path));
+        }
+        let (Some(file_name), Some(parent)) = (
+            path.file_name().and_then(std::ffi::OsStr::to_str),
+            path.parent(),
+        ) else {
+            return Err(ZipStoreCreateError::InvalidPath(path));
+        };
+        let parent = if parent.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            parent
+        };
+        let key =
+            StoreKey::new(file_name).map_err(|_| ZipStoreCreateError::InvalidPath(path.clone()))?;
+        let storage = Arc::new(FilesystemStore::new(parent)?);
+        let zip_storage_adapter = ZipStorageAdapter::new(storage, key)?;
+        Ok(Self {
+            path,
+            zip_storage_adapter,
+        })
+    }
+
+    /// Create a zip file at `path` with the content of `storage`, then open it as a zip store.
+    ///
+    /// Any existing file at `path` is overwritten.
+    ///
+    /// # Errors
+    /// Returns a [`ZipStoreCreateError`] if the zip file cannot be written or `storage` cannot be read.
+    pub fn create<
+        P: AsRef<Path>,
+        TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits,
+    >(
+        path: P,
+        storage: &TStorage,
+    ) -> Result<Self, ZipStoreCreateError> {
+        let path = path.as_ref();
+        if path.is_dir() {
+            return Err(ZipStoreCreateError::ExistingDir(path.to_path_buf()));
+        }
+        let keys = storage.list()?;
+
+        let mut zip = ZipWriter::new(File::create(path)?);
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+
+        // Write directory entries so that directories can be listed
+        let mut directories = BTreeSet::new();
+        for key in &keys {
+            let mut parent = key.parent();
+            while !parent.as_str().is_empty() && directories.insert(parent.clone()) {
+                parent = parent.parent().unwrap_or_else(StorePrefix::root);
+            }
+        }
+        for directory in directories {
+            zip.add_directory(directory.as_str(), options)
+                .map_err(|err| ZipStoreCreateError::ZipError(err.to_string()))?;
+        }
+
+        for key in &keys {
+            let Some(value) = storage.get(key)? else {
+                continue;
+            };
+            zip.start_file(
+                key.as_str(),
+                options.large_file(value.len() as u64 >= u64::from(u32::MAX)),
+            )
+            .map_err(|err| ZipStoreCreateError::ZipError(err.to_string()))?;
+            zip.write_all(&value)?;
+        }
+        zip.finish()
+            .map_err(|err| ZipStoreCreateError::ZipError(err.to_string()))?;
+
+        Self::new(path)
+    }
+
+    /// Return the path of the zip file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl ReadableStorageTraits for ZipStore {
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.zip_storage_adapter.get(key)
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        self.zip_storage_adapter
+            .get_partial_values_key(key, byte_ranges)
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.zip_storage_adapter.get_partial_values(key_ranges)
+    }
+
+    fn size(&self) -> Result<u64, StorageError> {
+        self.size_prefix(&StorePrefix::root())
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.zip_storage_adapter.size_prefix(prefix)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.zip_storage_adapter.size_key(key)
+    }
+}
+
+impl ListableStorageTraits for ZipStore {
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.zip_storage_adapter.list()
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.zip_storage_adapter.list_prefix(prefix)
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.zip_storage_adapter.list_dir(prefix)
+    }
+}
+
+/// A zip store creation error.
+#[derive(Debug, Error)]
+pub enum ZipStoreCreateError {
+    /// An IO error.
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    /// An existing directory.
+    #[error("{0} is an existing directory, not a zip file")]
+    ExistingDir(PathBuf),
+    /// The path is not valid for a zip store.
+    #[error("path {0} is not valid for a zip store")]
+    InvalidPath(PathBuf),
+    /// A zip error.
+    #[error("{0}")]
+    ZipError(String),
+    /// A filesystem store creation error.
+    #[error(transparent)]
+    FilesystemStoreCreateError(#[from] FilesystemStoreCreateError),
+    /// A zip storage adapter creation error.
+    #[error(transparent)]
+    ZipStorageAdapterCreateError(#[from] ZipStorageAdapterCreateError),
+    /// A storage error.
+    #[error(transparent)]
+    StorageError(#[from] StorageError),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::store::MemoryStore;
+
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn zip_store() -> Result<(), Box<dyn Error>> {
+        let memory_store = MemoryStore::new();
+        super::super::test_util::store_write(&memory_store)?;
+
+        let path = tempfile::TempDir::new()?;
+        let zip_path = path.path().join("store.zip");
+        let store = ZipStore::create(&zip_path, &memory_store)?;
+        assert_eq!(store.path(), zip_path);
+        super::super::test_util::store_read(&store)?;
+        super::super::test_util::store_list(&store)?;
+
+        let store = ZipStore::new(&zip_path)?;
+        super::super::test_util::store_read(&store)?;
+        super::super::test_util::store_list(&store)?;
+
+        assert!(ZipStore::new(path.path()).is_err());
+        assert!(ZipStore::new(path.path().join("missing.zip")).is_err());
+        Ok(())
+    }
+}
@@ -10,13 +10,25 @@
 //!    - Async variants use [`async_lock::Mutex`].
 //!  - [`DisabledStoreLocks`] (with [`DisabledStoreMutex`]) and their async variants disable locks for potentially improved performance.
 //!    - **Requires careful usage of [`Array`](crate::array::Array) to maintain data integrity** (see [`Array`](crate::array::Array) for more information).
+//!  - [`KeyLockProviderStoreLocks`] (with [`KeyLockProviderStoreMutex`]) delegate locking to a pluggable [`KeyLockProvider`] for locking across processes or machines.
+//!    - [`FileKeyLockProvider`] locks files in a shared directory with `flock` (Unix only).
+//!    - `RedisKeyLockProvider` locks keys in a Redis server (requires the `redis` feature).
+//!    - Async variants use an `AsyncKeyLockProvider` with `AsyncKeyLockProviderStoreLocks`.
 //!
-//! Specialised locks are planned for distributed applications.
+//! Acquiring a lock is fallible, as a [`KeyLockProvider`] may fail (e.g. if a lock service is unavailable).
+//! Errors are returned by [`StoreKeyMutexTraits::lock`] and propagated by the operation that requested the lock.
 
+#[cfg(feature = "redis")]
+mod key_lock_provider_redis;
 #[cfg(feature = "async")]
 pub mod store_lock_async;
 pub mod store_lock_sync;
 
+#[cfg(all(feature = "redis", feature = "async"))]
+pub use key_lock_provider_redis::AsyncRedisKeyLockGuard;
+#[cfg(feature = "redis")]
+pub use key_lock_provider_redis::{RedisKeyLockGuard, RedisKeyLockProvider};
+
 pub use store_lock_sync::{
     StoreKeyMutex, StoreKeyMutexGuard, StoreKeyMutexGuardTraits, StoreKeyMutexTraits, StoreLocks,
     StoreLocksTraits,
@@ -34,8 +46,20 @@ pub use store_lock_async::{
     disabled_async::{
         AsyncDisabledStoreLocks, AsyncDisabledStoreMutex, AsyncDisabledStoreMutexGuard,
     },
+    key_lock_provider_async::{
+        AsyncKeyLockGuard, AsyncKeyLockGuardTraits, AsyncKeyLockProvider,
+        AsyncKeyLockProviderStoreLocks, AsyncKeyLockProviderStoreMutex,
+        AsyncKeyLockProviderStoreMutexGuard,
+    },
 };
 pub use store_lock_sync::{
     default_sync::{DefaultStoreLocks, DefaultStoreMutex, DefaultStoreMutexGuard},
     disabled_sync::{DisabledStoreLocks, DisabledStoreMutex, DisabledStoreMutexGuard},
+    key_lock_provider::{
+        KeyLockGuard, KeyLockGuardTraits, KeyLockProvider, KeyLockProviderStoreLocks,
+        KeyLockProviderStoreMutex, KeyLockProviderStoreMutexGuard,
+    },
 };
+
+#[cfg(unix)]
+pub use store_lock_sync::key_lock_provider::{FileKeyLockGuard, FileKeyLockProvider};
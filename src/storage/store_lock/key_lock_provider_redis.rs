@@ -0,0 +1,267 @@
+//! A key lock provider backed by a Redis server.
+
+use std::time::{Duration, Instant};
+
+use crate::storage::{StorageError, StoreKey};
+
+use super::{KeyLockGuard, KeyLockGuardTraits, KeyLockProvider};
+
+#[cfg(feature = "async")]
+use super::{AsyncKeyLockGuard, AsyncKeyLockGuardTraits, AsyncKeyLockProvider};
+
+/// Deletes `KEYS[1]` if its value is `ARGV[1]`, so that only the holder of a lock releases it.
+const RELEASE_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("DEL", KEYS[1])
+else
+    return 0
+end
+"#;
+
+impl From<redis::RedisError> for StorageError {
+    fn from(err: redis::RedisError) -> Self {
+        if err.is_timeout()
+            || err.is_connection_dropped()
+            || err.is_connection_refusal()
+            || err.is_io_error()
+        {
+            Self::Transient(err.to_string())
+        } else {
+            Self::Other(err.to_string())
+        }
+    }
+}
+
+/// A key lock provider which locks keys in a [Redis](https://redis.io/) server.
+///
+/// The lock on a key is acquired by setting the Redis key `<prefix><key>` to a random token if it does not exist (`SET <key> <token> NX PX <expiry>`), retrying every retry interval until it succeeds or the timeout elapses.
+/// The lock is released when its guard is dropped by deleting the Redis key if it still holds the token.
+///
+/// Locks expire so that a lock held by a process that terminated unexpectedly is eventually released.
+/// The expiry must exceed the duration of any operation holding a lock, otherwise another process may acquire the lock before it is released.
+///
+/// By default, the prefix is `zarrs_lock:`, locks expire after 60s, the retry interval is 10ms, and there is no timeout.
+#[derive(Debug, Clone)]
+pub struct RedisKeyLockProvider {
+    client: redis::Client,
+    prefix: String,
+    expiry: Duration,
+    retry_interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl RedisKeyLockProvider {
+    /// Create a new Redis key lock provider with `client`.
+    #[must_use]
+    pub fn new(client: redis::Client) -> Self {
+        Self {
+            client,
+            prefix: "zarrs_lock:".to_string(),
+            expiry: Duration::from_secs(60),
+            retry_interval: Duration::from_millis(10),
+            timeout: None,
+        }
+    }
+
+    /// Create a new Redis key lock provider connecting to the Redis server at `url` (e.g. `redis://127.0.0.1/`).
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if `url` is not a valid Redis URL.
+    pub fn open(url: &str) -> Result<Self, StorageError> {
+        Ok(Self::new(redis::Client::open(url)?))
+    }
+
+    /// Set the prefix of the Redis keys of locks.
+    #[must_use]
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Set the duration after which a lock expires.
+    #[must_use]
+    pub const fn expiry(mut self, expiry: Duration) -> Self {
+        self.expiry = expiry;
+        self
+    }
+
+    /// Set the interval between attempts to acquire a lock that is held.
+    #[must_use]
+    pub const fn retry_interval(mut self, retry_interval: Duration) -> Self {
+        self.retry_interval = retry_interval;
+        self
+    }
+
+    /// Set the maximum duration to wait to acquire a lock, or [`None`] to wait indefinitely.
+    ///
+    /// If the timeout elapses, acquiring the lock fails with [`StorageError::Transient`].
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn redis_key(&self, key: &StoreKey) -> String {
+        format!("{}{}", self.prefix, key.as_str())
+    }
+
+    fn set_command(&self, redis_key: &str, token: &str) -> redis::Cmd {
+        let expiry_ms = u64::try_from(self.expiry.as_millis()).unwrap_or(u64::MAX);
+        let mut command = redis::cmd("SET");
+        command
+            .arg(redis_key)
+            .arg(token)
+            .arg("NX")
+            .arg("PX")
+            .arg(expiry_ms.max(1));
+        command
+    }
+
+    /// Return an error if the timeout to acquire the lock on `key` started at `start` has elapsed.
+    fn check_timeout(&self, start: Instant, key: &StoreKey) -> Result<(), StorageError> {
+        match self.timeout {
+            Some(timeout) if start.elapsed() >= timeout => Err(StorageError::Transient(format!(
+                "timed out acquiring the lock on {key}"
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A guard holding a lock acquired by a [`RedisKeyLockProvider`].
+pub struct RedisKeyLockGuard {
+    connection: redis::Connection,
+    redis_key: String,
+    token: String,
+}
+
+impl KeyLockGuardTraits for RedisKeyLockGuard {}
+
+impl Drop for RedisKeyLockGuard {
+    fn drop(&mut self) {
+        // If the lock cannot be released, it is released when it expires
+        let _ = redis::Script::new(RELEASE_SCRIPT)
+            .key(&self.redis_key)
+            .arg(&self.token)
+            .invoke::<i64>(&mut self.connection);
+    }
+}
+
+impl KeyLockProvider for RedisKeyLockProvider {
+    fn lock(&self, key: &StoreKey) -> Result<KeyLockGuard, StorageError> {
+        let mut connection = self.client.get_connection()?;
+        let redis_key = self.redis_key(key);
+        let token = crate::random_id();
+        let command = self.set_command(&redis_key, &token);
+        let start = Instant::now();
+        loop {
+            let acquired: Option<String> = command.query(&mut connection)?;
+            if acquired.is_some() {
+                return Ok(Box::new(RedisKeyLockGuard {
+                    connection,
+                    redis_key,
+                    token,
+                }));
+            }
+            self.check_timeout(start, key)?;
+            std::thread::sleep(self.retry_interval);
+        }
+    }
+}
+
+/// A guard holding a lock acquired asynchronously by a [`RedisKeyLockProvider`].
+///
+/// The lock is released by a task spawned on the current [`tokio`] runtime when the guard is dropped.
+/// If the guard is dropped outside of a runtime, the lock is released when it expires.
+#[cfg(feature = "async")]
+pub struct AsyncRedisKeyLockGuard {
+    connection: redis::aio::MultiplexedConnection,
+    redis_key: String,
+    token: String,
+}
+
+#[cfg(feature = "async")]
+impl AsyncKeyLockGuardTraits for AsyncRedisKeyLockGuard {}
+
+#[cfg(feature = "async")]
+impl Drop for AsyncRedisKeyLockGuard {
+    fn drop(&mut self) {
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let mut connection = self.connection.clone();
+            let redis_key = std::mem::take(&mut self.redis_key);
+            let token = std::mem::take(&mut self.token);
+            runtime.spawn(async move {
+                let _ = redis::Script::new(RELEASE_SCRIPT)
+                    .key(redis_key)
+                    .arg(token)
+                    .invoke_async::<_, i64>(&mut connection)
+                    .await;
+            });
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncKeyLockProvider for RedisKeyLockProvider {
+    async fn lock(&self, key: &StoreKey) -> Result<AsyncKeyLockGuard, StorageError> {
+        let mut connection = self.client.get_multiplexed_async_connection().await?;
+        let redis_key = self.redis_key(key);
+        let token = crate::random_id();
+        let command = self.set_command(&redis_key, &token);
+        let start = Instant::now();
+        loop {
+            let acquired: Option<String> = command.query_async(&mut connection).await?;
+            if acquired.is_some() {
+                return Ok(Box::new(AsyncRedisKeyLockGuard {
+                    connection,
+                    redis_key,
+                    token,
+                }));
+            }
+            self.check_timeout(start, key)?;
+            tokio::time::sleep(self.retry_interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::storage::{
+        store::MemoryStore, store_lock::KeyLockProviderStoreLocks, ReadableWritableStorageTraits,
+    };
+
+    use super::*;
+
+    #[test]
+    fn store_redis_key_lock_unavailable() {
+        // Nothing listens on port 1, so acquiring a lock fails rather than panicking
+        let provider = RedisKeyLockProvider::open("redis://127.0.0.1:1/").unwrap();
+        let store = MemoryStore::new_with_locks(Arc::new(KeyLockProviderStoreLocks::new(
+            Arc::new(provider),
+        )));
+        let key = StoreKey::new("key").unwrap();
+        assert!(matches!(
+            store.mutex(&key).unwrap().lock(),
+            Err(StorageError::Transient(_))
+        ));
+    }
+
+    #[test]
+    #[ignore = "requires a Redis server at redis://127.0.0.1/"]
+    fn store_redis_key_lock() {
+        let provider = RedisKeyLockProvider::open("redis://127.0.0.1/")
+            .unwrap()
+            .timeout(Some(Duration::from_millis(50)));
+        let key = StoreKey::new("zarrs_test/key").unwrap();
+        let guard = KeyLockProvider::lock(&provider, &key).unwrap();
+        assert!(matches!(
+            KeyLockProvider::lock(&provider, &key),
+            Err(StorageError::Transient(_))
+        ));
+        drop(guard);
+        assert!(KeyLockProvider::lock(&provider, &key).is_ok());
+    }
+}
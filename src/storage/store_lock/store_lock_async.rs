@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 pub mod default_async;
 pub mod disabled_async;
+pub mod key_lock_provider_async;
 
 /// Asynchronous store key lock manager.
 pub type AsyncStoreLocks = Arc<dyn AsyncStoreLocksTraits>;
@@ -25,8 +26,10 @@ pub trait AsyncStoreKeyMutexTraits: Send + Sync {
     /// Acquires a mutex, blocking the current thread until it is able to do so.
     ///
     /// When the returned guard goes out of scope, the mutex will be unlocked.
-    #[must_use]
-    async fn lock(&self) -> AsyncStoreKeyMutexGuard<'_>;
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`](crate::storage::StorageError) if the mutex cannot be acquired (e.g. if an [`AsyncKeyLockProvider`](key_lock_provider_async::AsyncKeyLockProvider) fails).
+    async fn lock(&self) -> Result<AsyncStoreKeyMutexGuard<'_>, crate::storage::StorageError>;
 }
 
 /// Store key mutex guard.
@@ -4,7 +4,7 @@ use std::{collections::HashMap, sync::Arc};
 
 use async_lock::{Mutex, MutexGuard};
 
-use crate::storage::StoreKey;
+use crate::storage::{StorageError, StoreKey};
 
 use super::{
     AsyncStoreKeyMutex, AsyncStoreKeyMutexGuard, AsyncStoreKeyMutexGuardTraits,
@@ -24,8 +24,10 @@ pub struct AsyncDefaultStoreMutex(Arc<Mutex<()>>);
 
 #[async_trait::async_trait]
 impl AsyncStoreKeyMutexTraits for AsyncDefaultStoreMutex {
-    async fn lock(&self) -> AsyncStoreKeyMutexGuard<'_> {
-        Box::new(AsyncDefaultStoreMutexGuard::<'_>(self.0.lock().await))
+    async fn lock(&self) -> Result<AsyncStoreKeyMutexGuard<'_>, StorageError> {
+        Ok(Box::new(AsyncDefaultStoreMutexGuard::<'_>(
+            self.0.lock().await,
+        )))
     }
 }
 
@@ -65,7 +67,7 @@ mod tests {
             let locks_held = locks_held.clone();
             tokio::task::spawn(async move {
                 let mutex = store.mutex(&key).await.unwrap();
-                let _lock = mutex.lock().await;
+                let _lock = mutex.lock().await.unwrap();
                 locks_held.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
                 std::thread::sleep(Duration::from_millis(10));
                 let locks_held = locks_held.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
@@ -1,6 +1,6 @@
 //! Disabled asynchronous store mutex.
 
-use crate::storage::StoreKey;
+use crate::storage::{StorageError, StoreKey};
 
 use super::{
     AsyncStoreKeyMutex, AsyncStoreKeyMutexGuard, AsyncStoreKeyMutexGuardTraits,
@@ -19,8 +19,8 @@ pub struct AsyncDisabledStoreMutex;
 
 #[async_trait::async_trait]
 impl AsyncStoreKeyMutexTraits for AsyncDisabledStoreMutex {
-    async fn lock(&self) -> AsyncStoreKeyMutexGuard<'_> {
-        Box::new(AsyncDisabledStoreMutexGuard)
+    async fn lock(&self) -> Result<AsyncStoreKeyMutexGuard<'_>, StorageError> {
+        Ok(Box::new(AsyncDisabledStoreMutexGuard))
     }
 }
 
@@ -60,7 +60,7 @@ mod tests {
             let locks_held = locks_held.clone();
             tokio::task::spawn(async move {
                 let mutex = store.mutex(&key).await.unwrap();
-                let _lock = mutex.lock().await;
+                let _lock = mutex.lock().await.unwrap();
                 locks_held.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
                 std::thread::sleep(Duration::from_millis(10));
                 let locks_held = locks_held.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
@@ -0,0 +1,167 @@
+//! Asynchronous store locks backed by an [`AsyncKeyLockProvider`].
+//!
+//! An [`AsyncKeyLockProvider`] is the asynchronous counterpart of a [`KeyLockProvider`](crate::storage::store_lock::KeyLockProvider).
+//! [`AsyncKeyLockProviderStoreLocks`] adapts a provider to [`AsyncStoreLocksTraits`], so that it can be used by asynchronous stores (e.g. `AsyncObjectStore::new_with_locks`).
+//!
+//! Implementations include:
+//!  - `RedisKeyLockProvider` (requires the `redis` feature), which locks keys in a Redis server.
+
+use std::sync::Arc;
+
+use crate::storage::{StorageError, StoreKey};
+
+use super::{
+    AsyncStoreKeyMutex, AsyncStoreKeyMutexGuard, AsyncStoreKeyMutexGuardTraits,
+    AsyncStoreKeyMutexTraits, AsyncStoreLocksTraits,
+};
+
+/// A guard which releases an asynchronous key lock when dropped.
+pub type AsyncKeyLockGuard = Box<dyn AsyncKeyLockGuardTraits>;
+
+/// Traits for an asynchronous key lock guard.
+pub trait AsyncKeyLockGuardTraits: Send + Sync {}
+
+/// Traits for an asynchronous provider of exclusive advisory locks on store keys.
+#[async_trait::async_trait]
+pub trait AsyncKeyLockProvider: Send + Sync + core::fmt::Debug {
+    /// Acquire an exclusive lock on `key`, waiting until it is able to do so.
+    ///
+    /// The lock is released when the returned guard is dropped.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the lock cannot be acquired.
+    async fn lock(&self, key: &StoreKey) -> Result<AsyncKeyLockGuard, StorageError>;
+}
+
+/// Asynchronous key lock provider store mutex guard.
+#[allow(dead_code)]
+pub struct AsyncKeyLockProviderStoreMutexGuard(AsyncKeyLockGuard);
+
+impl AsyncStoreKeyMutexGuardTraits for AsyncKeyLockProviderStoreMutexGuard {}
+
+/// Asynchronous key lock provider store mutex.
+#[derive(Debug)]
+pub struct AsyncKeyLockProviderStoreMutex {
+    provider: Arc<dyn AsyncKeyLockProvider>,
+    key: StoreKey,
+}
+
+#[async_trait::async_trait]
+impl AsyncStoreKeyMutexTraits for AsyncKeyLockProviderStoreMutex {
+    async fn lock(&self) -> Result<AsyncStoreKeyMutexGuard<'_>, StorageError> {
+        let guard = self.provider.lock(&self.key).await?;
+        Ok(Box::new(AsyncKeyLockProviderStoreMutexGuard(guard)))
+    }
+}
+
+/// Asynchronous store locks backed by an [`AsyncKeyLockProvider`].
+///
+/// If the provider fails to acquire a lock, the error is returned by [`AsyncStoreKeyMutexTraits::lock`].
+#[derive(Debug, Clone)]
+pub struct AsyncKeyLockProviderStoreLocks(Arc<dyn AsyncKeyLockProvider>);
+
+impl AsyncKeyLockProviderStoreLocks {
+    /// Create asynchronous store locks backed by `provider`.
+    #[must_use]
+    pub fn new(provider: Arc<dyn AsyncKeyLockProvider>) -> Self {
+        Self(provider)
+    }
+
+    /// Return the key lock provider.
+    #[must_use]
+    pub fn provider(&self) -> &Arc<dyn AsyncKeyLockProvider> {
+        &self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncStoreLocksTraits for AsyncKeyLockProviderStoreLocks {
+    async fn mutex(&self, key: &StoreKey) -> AsyncStoreKeyMutex {
+        Box::new(AsyncKeyLockProviderStoreMutex {
+            provider: self.0.clone(),
+            key: key.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use crate::storage::{store::AsyncObjectStore, AsyncReadableWritableStorageTraits};
+
+    use super::*;
+
+    /// An asynchronous key lock provider which locks keys in a map of mutexes.
+    #[derive(Debug, Default)]
+    struct MapKeyLockProvider(async_lock::Mutex<HashMap<StoreKey, Arc<async_lock::Mutex<()>>>>);
+
+    struct MapKeyLockGuard(#[allow(dead_code)] async_lock::MutexGuardArc<()>);
+
+    impl AsyncKeyLockGuardTraits for MapKeyLockGuard {}
+
+    #[async_trait::async_trait]
+    impl AsyncKeyLockProvider for MapKeyLockProvider {
+        async fn lock(&self, key: &StoreKey) -> Result<AsyncKeyLockGuard, StorageError> {
+            let mutex = self.0.lock().await.entry(key.clone()).or_default().clone();
+            Ok(Box::new(MapKeyLockGuard(mutex.lock_arc().await)))
+        }
+    }
+
+    /// An asynchronous key lock provider which always fails to acquire a lock.
+    #[derive(Debug)]
+    struct FailingKeyLockProvider;
+
+    #[async_trait::async_trait]
+    impl AsyncKeyLockProvider for FailingKeyLockProvider {
+        async fn lock(&self, _key: &StoreKey) -> Result<AsyncKeyLockGuard, StorageError> {
+            Err(StorageError::Transient(
+                "lock service unavailable".to_string(),
+            ))
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    #[cfg_attr(miri, ignore)]
+    async fn store_key_lock_provider_async() {
+        let locks = AsyncKeyLockProviderStoreLocks::new(Arc::new(MapKeyLockProvider::default()));
+        let store = Arc::new(AsyncObjectStore::new_with_locks(
+            object_store::memory::InMemory::default(),
+            Arc::new(locks),
+        ));
+        let locks_held = Arc::new(AtomicUsize::new(0));
+        let futures = (0..20).map(|_| {
+            let key = StoreKey::new("key").unwrap();
+            let store = store.clone();
+            let locks_held = locks_held.clone();
+            tokio::task::spawn(async move {
+                let mutex = store.mutex(&key).await.unwrap();
+                let _lock = mutex.lock().await.unwrap();
+                locks_held.fetch_add(1, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                std::thread::sleep(Duration::from_millis(1));
+                locks_held.fetch_sub(1, Ordering::SeqCst) == 1
+            })
+        });
+        let result = futures::future::try_join_all(futures).await.unwrap();
+        assert!(result.iter().all(|b| *b));
+    }
+
+    #[tokio::test]
+    async fn store_key_lock_provider_error_async() {
+        let locks = AsyncKeyLockProviderStoreLocks::new(Arc::new(FailingKeyLockProvider));
+        let store = AsyncObjectStore::new_with_locks(
+            object_store::memory::InMemory::default(),
+            Arc::new(locks),
+        );
+        let key = StoreKey::new("key").unwrap();
+        assert!(matches!(
+            store.mutex(&key).await.unwrap().lock().await,
+            Err(StorageError::Transient(_))
+        ));
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 pub mod default_sync;
 pub mod disabled_sync;
+pub mod key_lock_provider;
 
 /// Store key lock manager.
 pub type StoreLocks = Arc<dyn StoreLocksTraits>;
@@ -23,8 +24,10 @@ pub trait StoreKeyMutexTraits {
     /// Acquires a mutex, blocking the current thread until it is able to do so.
     ///
     /// When the returned guard goes out of scope, the mutex will be unlocked.
-    #[must_use]
-    fn lock(&self) -> StoreKeyMutexGuard<'_>;
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`](crate::storage::StorageError) if the mutex cannot be acquired (e.g. if a [`KeyLockProvider`](key_lock_provider::KeyLockProvider) fails).
+    fn lock(&self) -> Result<StoreKeyMutexGuard<'_>, crate::storage::StorageError>;
 }
 
 /// Store key mutex guard.
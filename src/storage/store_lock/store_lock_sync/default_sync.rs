@@ -4,7 +4,7 @@ use std::{collections::HashMap, sync::Arc};
 
 use parking_lot::{Mutex, MutexGuard};
 
-use crate::storage::StoreKey;
+use crate::storage::{StorageError, StoreKey};
 
 use super::{
     StoreKeyMutex, StoreKeyMutexGuard, StoreKeyMutexGuardTraits, StoreKeyMutexTraits,
@@ -23,8 +23,8 @@ impl StoreKeyMutexGuardTraits for DefaultStoreMutexGuard<'_> {}
 pub struct DefaultStoreMutex(Arc<Mutex<()>>);
 
 impl StoreKeyMutexTraits for DefaultStoreMutex {
-    fn lock(&self) -> StoreKeyMutexGuard<'_> {
-        Box::new(DefaultStoreMutexGuard::<'_>(self.0.lock()))
+    fn lock(&self) -> Result<StoreKeyMutexGuard<'_>, StorageError> {
+        Ok(Box::new(DefaultStoreMutexGuard::<'_>(self.0.lock())))
     }
 }
 
@@ -59,7 +59,7 @@ mod tests {
         let locks_held = AtomicUsize::new(0);
         (0..20).into_par_iter().for_each(|_| {
             let mutex = store.mutex(&key).unwrap();
-            let _lock = mutex.lock();
+            let _lock = mutex.lock().unwrap();
             locks_held.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
             std::thread::sleep(Duration::from_millis(10));
             let locks_held = locks_held.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
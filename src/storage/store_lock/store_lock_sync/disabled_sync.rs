@@ -1,6 +1,6 @@
 //! Disabled synchronous store mutex.
 
-use crate::storage::StoreKey;
+use crate::storage::{StorageError, StoreKey};
 
 use super::{
     StoreKeyMutex, StoreKeyMutexGuard, StoreKeyMutexGuardTraits, StoreKeyMutexTraits,
@@ -18,8 +18,8 @@ impl StoreKeyMutexGuardTraits for DisabledStoreMutexGuard {}
 pub struct DisabledStoreMutex;
 
 impl StoreKeyMutexTraits for DisabledStoreMutex {
-    fn lock(&self) -> StoreKeyMutexGuard<'_> {
-        Box::new(DisabledStoreMutexGuard)
+    fn lock(&self) -> Result<StoreKeyMutexGuard<'_>, StorageError> {
+        Ok(Box::new(DisabledStoreMutexGuard))
     }
 }
 
@@ -54,7 +54,7 @@ mod tests {
         let locks_held = AtomicUsize::new(0);
         assert!((0..20).into_par_iter().any(|_| {
             let mutex = store.mutex(&key).unwrap();
-            let _lock = mutex.lock();
+            let _lock = mutex.lock().unwrap();
             locks_held.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
             std::thread::sleep(Duration::from_millis(10));
             let locks_held = locks_held.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
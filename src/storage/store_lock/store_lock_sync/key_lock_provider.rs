@@ -0,0 +1,237 @@
+//! Synchronous store locks backed by a [`KeyLockProvider`].
+//!
+//! A [`KeyLockProvider`] acquires an exclusive advisory lock on a [`StoreKey`] which may be shared across processes or machines.
+//! [`KeyLockProviderStoreLocks`] adapts a provider to [`StoreLocksTraits`], so that it can be used by stores (e.g. [`MemoryStore::new_with_locks`](crate::storage::store::MemoryStore::new_with_locks)) or arrays (e.g. [`ArrayBuilder::store_locks`](crate::array::ArrayBuilder::store_locks)).
+//!
+//! Implementations include:
+//!  - [`FileKeyLockProvider`] (Unix only), which locks files in a directory with `flock`.
+//!    The directory must be on a filesystem that supports `flock` and that is shared by all processes (e.g. a local disk, or an NFS mount with lock support).
+//!  - `RedisKeyLockProvider` (requires the `redis` feature), which locks keys in a Redis server.
+//!
+//! If a provider fails to acquire a lock, the error is returned by [`StoreKeyMutexTraits::lock`] and propagated by the operation that requested the lock.
+
+use std::sync::Arc;
+
+use crate::storage::{StorageError, StoreKey};
+
+use super::{
+    StoreKeyMutex, StoreKeyMutexGuard, StoreKeyMutexGuardTraits, StoreKeyMutexTraits,
+    StoreLocksTraits,
+};
+
+/// A guard which releases a key lock when dropped.
+pub type KeyLockGuard = Box<dyn KeyLockGuardTraits>;
+
+/// Traits for a key lock guard.
+pub trait KeyLockGuardTraits: Send {}
+
+/// Traits for a provider of exclusive advisory locks on store keys.
+pub trait KeyLockProvider: Send + Sync + core::fmt::Debug {
+    /// Acquire an exclusive lock on `key`, blocking the current thread until it is able to do so.
+    ///
+    /// The lock is released when the returned guard is dropped.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the lock cannot be acquired.
+    fn lock(&self, key: &StoreKey) -> Result<KeyLockGuard, StorageError>;
+}
+
+/// Key lock provider store mutex guard.
+#[allow(dead_code)]
+pub struct KeyLockProviderStoreMutexGuard(KeyLockGuard);
+
+impl StoreKeyMutexGuardTraits for KeyLockProviderStoreMutexGuard {}
+
+/// Key lock provider store mutex.
+#[derive(Debug)]
+pub struct KeyLockProviderStoreMutex {
+    provider: Arc<dyn KeyLockProvider>,
+    key: StoreKey,
+}
+
+impl StoreKeyMutexTraits for KeyLockProviderStoreMutex {
+    fn lock(&self) -> Result<StoreKeyMutexGuard<'_>, StorageError> {
+        let guard = self.provider.lock(&self.key)?;
+        Ok(Box::new(KeyLockProviderStoreMutexGuard(guard)))
+    }
+}
+
+/// Store locks backed by a [`KeyLockProvider`].
+///
+/// If the provider fails to acquire a lock, the error is returned by [`StoreKeyMutexTraits::lock`].
+#[derive(Debug, Clone)]
+pub struct KeyLockProviderStoreLocks(Arc<dyn KeyLockProvider>);
+
+impl KeyLockProviderStoreLocks {
+    /// Create store locks backed by `provider`.
+    #[must_use]
+    pub fn new(provider: Arc<dyn KeyLockProvider>) -> Self {
+        Self(provider)
+    }
+
+    /// Return the key lock provider.
+    #[must_use]
+    pub fn provider(&self) -> &Arc<dyn KeyLockProvider> {
+        &self.0
+    }
+}
+
+impl StoreLocksTraits for KeyLockProviderStoreLocks {
+    fn mutex(&self, key: &StoreKey) -> StoreKeyMutex {
+        Box::new(KeyLockProviderStoreMutex {
+            provider: self.0.clone(),
+            key: key.clone(),
+        })
+    }
+}
+
+#[cfg(unix)]
+pub use file::{FileKeyLockGuard, FileKeyLockProvider};
+
+#[cfg(unix)]
+mod file {
+    use std::{
+        fs::{File, OpenOptions},
+        os::fd::AsRawFd,
+        path::PathBuf,
+    };
+
+    use crate::storage::{StorageError, StoreKey};
+
+    use super::{KeyLockGuard, KeyLockGuardTraits, KeyLockProvider};
+
+    /// A key lock guard holding a `flock` lock on a lock file.
+    #[derive(Debug)]
+    pub struct FileKeyLockGuard(File);
+
+    impl KeyLockGuardTraits for FileKeyLockGuard {}
+
+    impl Drop for FileKeyLockGuard {
+        fn drop(&mut self) {
+            // Closing the file also releases the lock
+            unsafe { libc::flock(self.0.as_raw_fd(), libc::LOCK_UN) };
+        }
+    }
+
+    /// A key lock provider which locks files in a directory with `flock`.
+    ///
+    /// The lock file of a key is at `<lock_directory>/<key>.lock`.
+    /// Lock files are created as required and are not removed.
+    ///
+    /// `flock` locks are held by an open file description, so they exclude other threads of the same process as well as other processes.
+    #[derive(Debug)]
+    pub struct FileKeyLockProvider {
+        lock_directory: PathBuf,
+    }
+
+    impl FileKeyLockProvider {
+        /// Create a new file key lock provider with lock files in `lock_directory`.
+        #[must_use]
+        pub fn new(lock_directory: impl Into<PathBuf>) -> Self {
+            Self {
+                lock_directory: lock_directory.into(),
+            }
+        }
+
+        /// Return the lock directory.
+        #[must_use]
+        pub fn lock_directory(&self) -> &PathBuf {
+            &self.lock_directory
+        }
+
+        fn lock_path(&self, key: &StoreKey) -> PathBuf {
+            self.lock_directory.join(format!("{}.lock", key.as_str()))
+        }
+    }
+
+    impl KeyLockProvider for FileKeyLockProvider {
+        fn lock(&self, key: &StoreKey) -> Result<KeyLockGuard, StorageError> {
+            let path = self.lock_path(key);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(path)?;
+            loop {
+                if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+                    return Ok(Box::new(FileKeyLockGuard(file)));
+                }
+                let err = std::io::Error::last_os_error();
+                if err.kind() != std::io::ErrorKind::Interrupted {
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+    use crate::storage::{store::MemoryStore, ReadableWritableStorageTraits};
+
+    use super::*;
+
+    /// A key lock provider which always fails to acquire a lock.
+    #[derive(Debug)]
+    struct FailingKeyLockProvider;
+
+    impl KeyLockProvider for FailingKeyLockProvider {
+        fn lock(&self, _key: &StoreKey) -> Result<KeyLockGuard, StorageError> {
+            Err(StorageError::Transient(
+                "lock service unavailable".to_string(),
+            ))
+        }
+    }
+
+    #[test]
+    fn store_key_lock_provider_error_sync() {
+        let locks = KeyLockProviderStoreLocks::new(Arc::new(FailingKeyLockProvider));
+        let store = MemoryStore::new_with_locks(Arc::new(locks));
+        let key = StoreKey::new("key").unwrap();
+        assert!(matches!(
+            store.mutex(&key).unwrap().lock(),
+            Err(StorageError::Transient(_))
+        ));
+        assert!(matches!(
+            crate::storage::update_attributes(
+                &store,
+                &crate::node::NodePath::new("/").unwrap(),
+                &serde_json::Map::new()
+            ),
+            Err(StorageError::Transient(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    #[cfg_attr(miri, ignore)]
+    fn store_file_key_lock_sync() {
+        let path = tempfile::TempDir::new().unwrap();
+        let locks = KeyLockProviderStoreLocks::new(Arc::new(FileKeyLockProvider::new(
+            path.path().join("locks"),
+        )));
+        let store = MemoryStore::new_with_locks(Arc::new(locks));
+        let key = StoreKey::new("group/key").unwrap();
+        let locks_held = AtomicUsize::new(0);
+        (0..20).into_par_iter().for_each(|_| {
+            let mutex = store.mutex(&key).unwrap();
+            let _lock = mutex.lock().unwrap();
+            locks_held.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(1));
+            let locks_held = locks_held.fetch_sub(1, Ordering::SeqCst);
+            assert_eq!(locks_held, 1);
+        });
+        assert!(path.path().join("locks/group/key.lock").exists());
+    }
+}
@@ -0,0 +1,390 @@
+//! [xarray](https://docs.xarray.dev/en/stable/internals/zarr-encoding-spec.html) conventions for Zarr hierarchies.
+//!
+//! xarray follows the `NetCDF` data model, where each dimension of an array is named and arrays sharing a dimension name share that dimension.
+//! Zarr V2 has no dimension names, so xarray stores them in the `_ARRAY_DIMENSIONS` attribute of each array.
+//! xarray also reads Zarr V3 arrays with the `_ARRAY_DIMENSIONS` attribute, so writing it alongside [`dimension_names`](crate::array::Array::dimension_names) ensures that a hierarchy opens cleanly in any version of xarray.
+//!
+//! A coordinate array (a dimension scale in HDF5 terminology) is a one dimensional array with the same name as its dimension.
+//! Coordinate arrays hold the labels of a dimension (e.g. time stamps or positions) and can be discovered with [`coordinate_arrays`].
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use zarrs::array::{ArrayBuilder, DataType, FillValue};
+//! # use zarrs::storage::store::MemoryStore;
+//! let store = Arc::new(MemoryStore::new());
+//! let mut array = ArrayBuilder::new(
+//!     vec![8, 8],
+//!     DataType::Float32,
+//!     vec![4, 4].try_into()?,
+//!     FillValue::from(0.0f32),
+//! )
+//! .dimension_names(Some(["y", "x"]))
+//! .build(store, "/temperature")?;
+//! zarrs::xarray::set_array_dimensions_from_dimension_names(&mut array)?;
+//! assert_eq!(
+//!     zarrs::xarray::array_dimensions(&array)?,
+//!     Some(vec!["y".to_string(), "x".to_string()])
+//! );
+//! array.store_metadata()?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::{
+    array::{Array, ArrayCreateError},
+    group::Group,
+    storage::{ListableStorageTraits, ReadableStorageTraits},
+};
+
+#[cfg(feature = "async")]
+use crate::storage::{AsyncListableStorageTraits, AsyncReadableStorageTraits};
+
+/// The attribute holding the dimension names of an array in the xarray conventions.
+pub const ARRAY_DIMENSIONS: &str = "_ARRAY_DIMENSIONS";
+
+/// An xarray conventions error.
+#[derive(Debug, Error)]
+pub enum XarrayError {
+    /// The `_ARRAY_DIMENSIONS` attribute is not a list of strings.
+    #[error("the _ARRAY_DIMENSIONS attribute is invalid: {_0}")]
+    InvalidArrayDimensions(#[from] serde_json::Error),
+    /// The number of dimensions does not match the array dimensionality.
+    #[error("the number of array dimensions {_0} does not match the array dimensionality {_1}")]
+    IncompatibleDimensionality(usize, usize),
+    /// The `_ARRAY_DIMENSIONS` attribute does not match the dimension names of the array.
+    #[error("the array dimensions {_0:?} do not match the dimension names {_1:?}")]
+    MismatchedDimensionNames(Vec<String>, Vec<Option<String>>),
+    /// A dimension of the array has no name.
+    #[error("dimension {_0} of the array has no name")]
+    UnnamedDimension(usize),
+    /// Arrays sharing a dimension have a different length in that dimension.
+    #[error("dimension {_0} has conflicting lengths {_1} and {_2}")]
+    ConflictingDimensionLength(String, u64, u64),
+    /// An error creating a child array.
+    #[error(transparent)]
+    ArrayCreateError(#[from] ArrayCreateError),
+}
+
+/// Return the dimension names of `array` from its `_ARRAY_DIMENSIONS` attribute.
+///
+/// Returns [`None`] if the array does not have the `_ARRAY_DIMENSIONS` attribute.
+///
+/// # Errors
+/// Returns an [`XarrayError`] if the attribute is not a list of strings or does not match the dimensionality of the array.
+pub fn array_dimensions<TStorage: ?Sized>(
+    array: &Array<TStorage>,
+) -> Result<Option<Vec<String>>, XarrayError> {
+    let Some(dimensions) = array.attribute::<Vec<String>>(ARRAY_DIMENSIONS)? else {
+        return Ok(None);
+    };
+    if dimensions.len() != array.dimensionality() {
+        return Err(XarrayError::IncompatibleDimensionality(
+            dimensions.len(),
+            array.dimensionality(),
+        ));
+    }
+    Ok(Some(dimensions))
+}
+
+/// Validate that `dimensions` match the dimensionality and [`dimension_names`](Array::dimension_names) of `array`.
+fn validate_dimensions<TStorage: ?Sized>(
+    array: &Array<TStorage>,
+    dimensions: &[String],
+) -> Result<(), XarrayError> {
+    if dimensions.len() != array.dimensionality() {
+        return Err(XarrayError::IncompatibleDimensionality(
+            dimensions.len(),
+            array.dimensionality(),
+        ));
+    }
+    if let Some(dimension_names) = array.dimension_names() {
+        let matches = std::iter::zip(dimensions, dimension_names)
+            .all(|(dimension, name)| name.as_str() == Some(dimension.as_str()));
+        if !matches {
+            return Err(XarrayError::MismatchedDimensionNames(
+                dimensions.to_vec(),
+                dimension_names
+                    .iter()
+                    .map(|name| name.as_str().map(str::to_string))
+                    .collect(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Validate the `_ARRAY_DIMENSIONS` attribute of `array`.
+///
+/// The attribute is valid if it is absent, or if it matches the dimensionality of the array and the [`dimension_names`](Array::dimension_names) of the array (if any).
+///
+/// # Errors
+/// Returns an [`XarrayError`] if the attribute is invalid.
+pub fn validate_array_dimensions<TStorage: ?Sized>(
+    array: &Array<TStorage>,
+) -> Result<(), XarrayError> {
+    match array_dimensions(array)? {
+        Some(dimensions) => validate_dimensions(array, &dimensions),
+        None => Ok(()),
+    }
+}
+
+/// Set the `_ARRAY_DIMENSIONS` attribute of `array` to `dimensions`.
+///
+/// The array metadata is not stored.
+///
+/// # Errors
+/// Returns an [`XarrayError`] if `dimensions` does not match the dimensionality of the array or the [`dimension_names`](Array::dimension_names) of the array (if any).
+pub fn set_array_dimensions<TStorage: ?Sized>(
+    array: &mut Array<TStorage>,
+    dimensions: &[impl AsRef<str>],
+) -> Result<(), XarrayError> {
+    let dimensions: Vec<String> = dimensions
+        .iter()
+        .map(|dimension| dimension.as_ref().to_string())
+        .collect();
+    validate_dimensions(array, &dimensions)?;
+    array.set_attribute(ARRAY_DIMENSIONS, dimensions)?;
+    Ok(())
+}
+
+/// Set the `_ARRAY_DIMENSIONS` attribute of `array` to its [`dimension_names`](Array::dimension_names).
+///
+/// The array metadata is not stored.
+///
+/// # Errors
+/// Returns [`XarrayError::UnnamedDimension`] if the array does not have dimension names or any dimension is unnamed.
+pub fn set_array_dimensions_from_dimension_names<TStorage: ?Sized>(
+    array: &mut Array<TStorage>,
+) -> Result<(), XarrayError> {
+    let dimensions = named_dimensions(array)?;
+    array.set_attribute(ARRAY_DIMENSIONS, dimensions)?;
+    Ok(())
+}
+
+/// Return the [`dimension_names`](Array::dimension_names) of `array`, requiring that every dimension is named.
+fn named_dimensions<TStorage: ?Sized>(array: &Array<TStorage>) -> Result<Vec<String>, XarrayError> {
+    let Some(dimension_names) = array.dimension_names() else {
+        return Err(XarrayError::UnnamedDimension(0));
+    };
+    dimension_names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            name.as_str()
+                .map(str::to_string)
+                .ok_or(XarrayError::UnnamedDimension(i))
+        })
+        .collect()
+}
+
+/// Return the dimensions of `array` from its `_ARRAY_DIMENSIONS` attribute, or from its [`dimension_names`](Array::dimension_names) if the attribute is absent.
+///
+/// Returns [`None`] if the array has no `_ARRAY_DIMENSIONS` attribute and any dimension is unnamed.
+///
+/// # Errors
+/// Returns an [`XarrayError`] if the `_ARRAY_DIMENSIONS` attribute is invalid.
+pub fn dimensions<TStorage: ?Sized>(
+    array: &Array<TStorage>,
+) -> Result<Option<Vec<String>>, XarrayError> {
+    match array_dimensions(array)? {
+        Some(dimensions) => {
+            validate_dimensions(array, &dimensions)?;
+            Ok(Some(dimensions))
+        }
+        None => Ok(named_dimensions(array).ok()),
+    }
+}
+
+/// Returns true if `array` is a coordinate array.
+///
+/// A coordinate array is one dimensional and its dimension has the same name as the array.
+///
+/// # Errors
+/// Returns an [`XarrayError`] if the `_ARRAY_DIMENSIONS` attribute is invalid.
+pub fn is_coordinate_array<TStorage: ?Sized>(array: &Array<TStorage>) -> Result<bool, XarrayError> {
+    let name = array.path().as_str().rsplit('/').next().unwrap_or_default();
+    Ok(match dimensions(array)? {
+        Some(dimensions) => dimensions.len() == 1 && dimensions[0] == name,
+        None => false,
+    })
+}
+
+/// Return the length of each dimension shared by `arrays`.
+///
+/// Arrays without dimensions (see [`dimensions`]) are ignored.
+///
+/// # Errors
+/// Returns an [`XarrayError`] if the `_ARRAY_DIMENSIONS` attribute of an array is invalid or arrays sharing a dimension have a different length in that dimension.
+pub fn dimension_lengths<TStorage: ?Sized>(
+    arrays: &[Array<TStorage>],
+) -> Result<BTreeMap<String, u64>, XarrayError> {
+    let mut lengths = BTreeMap::<String, u64>::new();
+    for array in arrays {
+        let Some(dimensions) = dimensions(array)? else {
+            continue;
+        };
+        for (dimension, &length) in std::iter::zip(dimensions, array.shape()) {
+            match lengths.get(&dimension) {
+                Some(&existing) if existing != length => {
+                    return Err(XarrayError::ConflictingDimensionLength(
+                        dimension, existing, length,
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    lengths.insert(dimension, length);
+                }
+            }
+        }
+    }
+    Ok(lengths)
+}
+
+/// Return the coordinate arrays that are children of `group`.
+///
+/// See [`is_coordinate_array`].
+///
+/// # Errors
+/// Returns an [`XarrayError`] if there is an underlying store error, child array metadata is invalid, or the `_ARRAY_DIMENSIONS` attribute of a child array is invalid.
+pub fn coordinate_arrays<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits>(
+    group: &Group<TStorage>,
+) -> Result<Vec<Array<TStorage>>, XarrayError> {
+    filter_coordinate_arrays(group.child_arrays()?)
+}
+
+#[cfg(feature = "async")]
+/// Async variant of [`coordinate_arrays`].
+#[allow(clippy::missing_errors_doc)]
+pub async fn async_coordinate_arrays<
+    TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits,
+>(
+    group: &Group<TStorage>,
+) -> Result<Vec<Array<TStorage>>, XarrayError> {
+    filter_coordinate_arrays(group.async_child_arrays().await?)
+}
+
+fn filter_coordinate_arrays<TStorage: ?Sized>(
+    arrays: Vec<Array<TStorage>>,
+) -> Result<Vec<Array<TStorage>>, XarrayError> {
+    let mut coordinate_arrays = Vec::new();
+    for array in arrays {
+        if is_coordinate_array(&array)? {
+            coordinate_arrays.push(array);
+        }
+    }
+    Ok(coordinate_arrays)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        array::{ArrayBuilder, DataType, DimensionName, FillValue},
+        group::GroupBuilder,
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    fn array_builder(shape: Vec<u64>) -> ArrayBuilder {
+        let chunk_shape = vec![2; shape.len()];
+        ArrayBuilder::new(
+            shape,
+            DataType::Float32,
+            chunk_shape.try_into().unwrap(),
+            FillValue::from(0.0f32),
+        )
+    }
+
+    #[test]
+    fn xarray_array_dimensions() {
+        let store = Arc::new(MemoryStore::new());
+        let mut array = array_builder(vec![4, 6])
+            .dimension_names(Some(["y", "x"]))
+            .build(store.clone(), "/data")
+            .unwrap();
+        assert_eq!(array_dimensions(&array).unwrap(), None);
+        assert_eq!(
+            dimensions(&array).unwrap(),
+            Some(vec!["y".to_string(), "x".to_string()])
+        );
+
+        assert!(matches!(
+            set_array_dimensions(&mut array, &["y"]),
+            Err(XarrayError::IncompatibleDimensionality(1, 2))
+        ));
+        assert!(matches!(
+            set_array_dimensions(&mut array, &["x", "y"]),
+            Err(XarrayError::MismatchedDimensionNames(_, _))
+        ));
+        set_array_dimensions(&mut array, &["y", "x"]).unwrap();
+        validate_array_dimensions(&array).unwrap();
+
+        // An attribute that does not match the dimension names is invalid
+        array.set_attribute(ARRAY_DIMENSIONS, ["a", "b"]).unwrap();
+        assert!(validate_array_dimensions(&array).is_err());
+        array.set_attribute(ARRAY_DIMENSIONS, [0, 1]).unwrap();
+        assert!(matches!(
+            array_dimensions(&array),
+            Err(XarrayError::InvalidArrayDimensions(_))
+        ));
+
+        let mut array = array_builder(vec![4, 6])
+            .dimension_names(Some([DimensionName::new("y"), DimensionName::default()]))
+            .build(store, "/data")
+            .unwrap();
+        assert!(matches!(
+            set_array_dimensions_from_dimension_names(&mut array),
+            Err(XarrayError::UnnamedDimension(1))
+        ));
+        assert_eq!(dimensions(&array).unwrap(), None);
+    }
+
+    #[test]
+    fn xarray_coordinate_arrays() {
+        let store = Arc::new(MemoryStore::new());
+        GroupBuilder::new()
+            .build(store.clone(), "/")
+            .unwrap()
+            .store_metadata()
+            .unwrap();
+        for (path, shape, dims) in [
+            ("/x", vec![6], vec!["x"]),
+            ("/y", vec![4], vec!["y"]),
+            ("/data", vec![4, 6], vec!["y", "x"]),
+            ("/z", vec![4], vec!["y"]),
+        ] {
+            let mut array = array_builder(shape).build(store.clone(), path).unwrap();
+            set_array_dimensions(&mut array, &dims).unwrap();
+            array.store_metadata().unwrap();
+        }
+
+        let group = Group::new(store.clone(), "/").unwrap();
+        let mut names: Vec<String> = coordinate_arrays(&group)
+            .unwrap()
+            .iter()
+            .map(|array| array.path().as_str().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["/x", "/y"]);
+
+        let lengths = dimension_lengths(&group.child_arrays().unwrap()).unwrap();
+        assert_eq!(
+            lengths,
+            BTreeMap::from([("x".to_string(), 6), ("y".to_string(), 4)])
+        );
+
+        let mut arrays = group.child_arrays().unwrap();
+        let mut array = array_builder(vec![5]).build(store, "/w").unwrap();
+        set_array_dimensions(&mut array, &["x"]).unwrap();
+        arrays.push(array);
+        assert!(matches!(
+            dimension_lengths(&arrays),
+            Err(XarrayError::ConflictingDimensionLength(_, 6, 5))
+        ));
+    }
+}
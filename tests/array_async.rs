@@ -85,6 +85,19 @@ async fn array_async_read(array: Array<AsyncObjectStore<InMemory>>) -> Result<()
     assert_eq!(array.async_retrieve_array_subset(&ArraySubset::new_with_ranges(&[5..7, 5..6])).await?, [0, 0]); // OOB -> fill value
     assert_eq!(array.async_retrieve_array_subset(&ArraySubset::new_with_ranges(&[0..5, 0..5])).await?, [1, 2, 3, 4, 0, 5, 6, 7, 8, 0, 9, 10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]); // OOB -> fill value
 
+    assert!(array.async_retrieve_array_subset_elements_as::<u16, f32>(&ArraySubset::new_with_ranges(&[0..4, 0..4]), f32::from).await.is_err());
+    assert_eq!(array.async_retrieve_array_subset_elements_as::<u8, f32>(&ArraySubset::new_with_ranges(&[1..3, 1..3]), |x| f32::from(x) * 0.5).await?, [3.0, 3.5, 5.0, 0.0]);
+    assert_eq!(array.async_retrieve_array_subset_elements_as::<u8, u16>(&ArraySubset::new_with_ranges(&[0..5, 0..5]), |x| u16::from(x) + 100).await?, [101, 102, 103, 104, 100, 105, 106, 107, 108, 100, 109, 110, 100, 100, 100, 100, 100, 100, 100, 100, 100, 100, 100, 100, 100]); // OOB -> fill value
+    assert_eq!(array.async_retrieve_array_subsets(&[ArraySubset::new_with_ranges(&[1..3, 1..3]), ArraySubset::new_with_ranges(&[0..0, 0..0]), ArraySubset::new_with_ranges(&[0..1, 1..4])]).await?, [vec![6, 7, 10, 0], vec![], vec![2, 3, 4]]);
+    assert_eq!(array.async_retrieve_array_subsets_elements::<u8>(&[ArraySubset::new_with_ranges(&[2..3, 0..2]), ArraySubset::new_with_ranges(&[0..5, 0..5])]).await?, [vec![9, 10], vec![1, 2, 3, 4, 0, 5, 6, 7, 8, 0, 9, 10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]]); // OOB -> fill value
+    assert!(array.async_retrieve_array_subsets(&[ArraySubset::new_with_ranges(&[0..1, 0..1]), ArraySubset::new_with_ranges(&[0..1])]).await.is_err());
+    assert!(array.async_retrieve_array_subsets_elements::<u16>(&[ArraySubset::new_with_ranges(&[0..1, 0..1])]).await.is_err());
+    assert_eq!(array.async_retrieve_elements_at::<u8>(&[vec![2, 1], vec![0, 0], vec![3, 3], vec![0, 3], vec![2, 1]]).await?, [10, 1, 0, 4, 10]);
+    assert_eq!(array.async_retrieve_elements_at::<u8>(&[]).await?, Vec::<u8>::new());
+    assert!(array.async_retrieve_elements_at::<u8>(&[vec![4, 0]]).await.is_err());
+    assert!(array.async_retrieve_elements_at::<u8>(&[vec![0]]).await.is_err());
+    assert!(array.async_retrieve_elements_at::<u16>(&[vec![0, 0]]).await.is_err());
+
     assert!(array.async_retrieve_array_subset_ndarray::<u8>(&ArraySubset::new_with_ranges(&[0..4])).await.is_err());
     assert!(array.async_retrieve_array_subset_ndarray::<u16>(&ArraySubset::new_with_ranges(&[0..4, 0..4])).await.is_err());
     assert_eq!(array.async_retrieve_array_subset_ndarray::<u8>(&ArraySubset::new_with_ranges(&[0..0, 0..0])).await?, ndarray::Array2::<u8>::zeros((0, 0)).into_dyn());
@@ -246,3 +259,96 @@ async fn array_async_read_shard_compress() -> Result<(), Box<dyn std::error::Err
     .unwrap();
     array_async_read(array).await
 }
+
+#[cfg(all(feature = "async", feature = "object_store"))]
+#[tokio::test]
+#[rustfmt::skip]
+async fn array_async_resize_append() -> Result<(), Box<dyn std::error::Error>> {
+    let store = Arc::new(AsyncObjectStore::new(InMemory::new()));
+    let array_path = "/array";
+    let mut array = ArrayBuilder::new(
+        vec![4, 4], // array shape
+        DataType::UInt8,
+        vec![2, 2].try_into().unwrap(), // regular chunk shape
+        FillValue::from(0u8),
+    )
+    .bytes_to_bytes_codecs(vec![])
+    .build(store, array_path)?;
+    array.async_store_array_subset(&ArraySubset::new_with_shape(vec![4, 4]), (1..=16).collect()).await?;
+
+    array.async_resize(vec![3, 2]).await?;
+    assert_eq!(array.async_retrieve_chunk_if_exists(&[0, 1]).await?, None);
+    assert_eq!(array.async_retrieve_chunk(&[1, 0]).await?, [9, 10, 0, 0]);
+
+    array.async_append_elements::<u8>(1, vec![17, 18, 19]).await?;
+    assert_eq!(array.shape(), &[3, 3]);
+    assert_eq!(array.async_retrieve_array_subset(&ArraySubset::new_with_shape(vec![3, 3])).await?, [1, 2, 17, 5, 6, 18, 9, 10, 19]);
+
+    Ok(())
+}
+
+#[cfg(all(feature = "async", feature = "object_store"))]
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn array_async_erase() -> Result<(), Box<dyn std::error::Error>> {
+    use zarrs::storage::AsyncListableStorageTraits;
+
+    let store = Arc::new(AsyncObjectStore::new(InMemory::new()));
+    let array = ArrayBuilder::new(
+        vec![4, 4],
+        DataType::UInt8,
+        vec![2, 2].try_into().unwrap(),
+        FillValue::from(0u8),
+    )
+    .build(store.clone(), "/array")?;
+    array.async_store_metadata().await?;
+    array
+        .async_store_array_subset(&ArraySubset::new_with_shape(vec![4, 4]), (1..=16).collect())
+        .await?;
+    assert_eq!(store.list().await?.len(), 5);
+
+    array.async_erase().await?;
+    assert!(store.list().await?.is_empty());
+
+    Ok(())
+}
+
+#[cfg(all(feature = "async", feature = "object_store"))]
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn array_async_store_shard_multipart() -> Result<(), Box<dyn std::error::Error>> {
+    use zarrs::array::codec::array_to_bytes::sharding::ShardingIndexLocation;
+    use zarrs::object_store::local::LocalFileSystem;
+
+    let path = tempfile::TempDir::new()?;
+    let mut store = AsyncObjectStore::new(LocalFileSystem::new_with_prefix(path.path())?);
+    // Every encoded inner chunk exceeds the threshold, so shards are uploaded in parts
+    store.set_multipart_threshold(Some(8));
+    let array = ArrayBuilder::new(
+        vec![8, 8],
+        DataType::UInt16,
+        vec![8, 8].try_into().unwrap(),
+        FillValue::from(0u16),
+    )
+    .array_to_bytes_codec(Box::new(
+        ShardingCodecBuilder::new(vec![4, 4].try_into().unwrap())
+            .index_location(ShardingIndexLocation::End)
+            .build(),
+    ))
+    .build(Arc::new(store), "/array")?;
+
+    let elements: Vec<u16> = (0..64).collect();
+    array
+        .async_store_array_subset_elements(
+            &ArraySubset::new_with_shape(vec![8, 8]),
+            elements.clone(),
+        )
+        .await?;
+    assert_eq!(
+        array
+            .async_retrieve_array_subset_elements::<u16>(&ArraySubset::new_with_shape(vec![8, 8]))
+            .await?,
+        elements
+    );
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use zarrs::array::codec::{array_to_bytes::sharding::ShardingCodecBuilder, GzipCodec};
-use zarrs::array::{Array, ArrayBuilder, ArrayView, DataType, FillValue};
+use zarrs::array::{Array, ArrayBuilder, ArrayView, ArrayViewMut, DataType, FillValue};
 use zarrs::array_subset::ArraySubset;
 use zarrs::storage::store::MemoryStore;
 
@@ -80,6 +80,20 @@ fn array_sync_read(array: Array<MemoryStore>) -> Result<(), Box<dyn std::error::
     assert_eq!(array.retrieve_array_subset(&ArraySubset::new_with_ranges(&[5..7, 5..6]))?, [0, 0]); // OOB -> fill value
     assert_eq!(array.retrieve_array_subset(&ArraySubset::new_with_ranges(&[0..5, 0..5]))?, [1, 2, 3, 4, 0, 5, 6, 7, 8, 0, 9, 10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]); // OOB -> fill value
 
+    assert!(array.retrieve_array_subset_elements_as::<u16, f32>(&ArraySubset::new_with_ranges(&[0..4, 0..4]), f32::from).is_err());
+    assert_eq!(array.retrieve_array_subset_elements_as::<u8, f32>(&ArraySubset::new_with_ranges(&[0..0, 0..0]), f32::from)?, Vec::<f32>::new());
+    assert_eq!(array.retrieve_array_subset_elements_as::<u8, f32>(&ArraySubset::new_with_ranges(&[1..3, 1..3]), |x| f32::from(x) * 0.5)?, [3.0, 3.5, 5.0, 0.0]);
+    assert_eq!(array.retrieve_array_subset_elements_as::<u8, u16>(&ArraySubset::new_with_ranges(&[0..5, 0..5]), |x| u16::from(x) + 100)?, [101, 102, 103, 104, 100, 105, 106, 107, 108, 100, 109, 110, 100, 100, 100, 100, 100, 100, 100, 100, 100, 100, 100, 100, 100]); // OOB -> fill value
+    assert_eq!(array.retrieve_array_subsets(&[ArraySubset::new_with_ranges(&[1..3, 1..3]), ArraySubset::new_with_ranges(&[0..0, 0..0]), ArraySubset::new_with_ranges(&[0..1, 1..4])])?, [vec![6, 7, 10, 0], vec![], vec![2, 3, 4]]);
+    assert_eq!(array.retrieve_array_subsets_elements::<u8>(&[ArraySubset::new_with_ranges(&[2..3, 0..2]), ArraySubset::new_with_ranges(&[0..5, 0..5])])?, [vec![9, 10], vec![1, 2, 3, 4, 0, 5, 6, 7, 8, 0, 9, 10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]]); // OOB -> fill value
+    assert!(array.retrieve_array_subsets(&[ArraySubset::new_with_ranges(&[0..1, 0..1]), ArraySubset::new_with_ranges(&[0..1])]).is_err());
+    assert!(array.retrieve_array_subsets_elements::<u16>(&[ArraySubset::new_with_ranges(&[0..1, 0..1])]).is_err());
+    assert_eq!(array.retrieve_elements_at::<u8>(&[vec![2, 1], vec![0, 0], vec![3, 3], vec![0, 3], vec![2, 1]])?, [10, 1, 0, 4, 10]);
+    assert_eq!(array.retrieve_elements_at::<u8>(&[])?, Vec::<u8>::new());
+    assert!(array.retrieve_elements_at::<u8>(&[vec![4, 0]]).is_err());
+    assert!(array.retrieve_elements_at::<u8>(&[vec![0]]).is_err());
+    assert!(array.retrieve_elements_at::<u16>(&[vec![0, 0]]).is_err());
+
     assert!(array.retrieve_array_subset_ndarray::<u8>(&ArraySubset::new_with_ranges(&[0..4])).is_err());
     assert!(array.retrieve_array_subset_ndarray::<u16>(&ArraySubset::new_with_ranges(&[0..4, 0..4])).is_err());
     assert_eq!(array.retrieve_array_subset_ndarray::<u8>(&ArraySubset::new_with_ranges(&[0..0, 0..0]))?, ndarray::Array2::<u8>::zeros((0, 0)).into_dyn());
@@ -240,3 +254,175 @@ fn array_sync_read_shard_compress() -> Result<(), Box<dyn std::error::Error>> {
     .unwrap();
     array_sync_read(array)
 }
+
+#[test]
+#[rustfmt::skip]
+fn array_sync_resize_append() -> Result<(), Box<dyn std::error::Error>> {
+    use zarrs::storage::{ListableStorageTraits, StorePrefix};
+
+    let store = Arc::new(MemoryStore::default());
+    let array_path = "/array";
+    let mut array = ArrayBuilder::new(
+        vec![4, 4], // array shape
+        DataType::UInt8,
+        vec![2, 2].try_into().unwrap(), // regular chunk shape
+        FillValue::from(0u8),
+    )
+    .bytes_to_bytes_codecs(vec![])
+    .build(store.clone(), array_path)?;
+    array.store_array_subset(&ArraySubset::new_with_shape(vec![4, 4]), (1..=16).collect())?;
+
+    // Shrink: chunks [*, 1] are erased, elements of chunks [1, 0] outside of the new shape are set to the fill value
+    array.resize(vec![3, 2])?;
+    assert_eq!(array.shape(), &[3, 2]);
+    assert_eq!(store.list_prefix(&StorePrefix::new("array/")?)?.len(), 2);
+    assert_eq!(array.retrieve_chunk(&[1, 0])?, [9, 10, 0, 0]);
+
+    // Grow: revealed elements hold the fill value
+    array.resize(vec![4, 4])?;
+    assert_eq!(array.retrieve_array_subset(&ArraySubset::new_with_shape(vec![4, 4]))?, [1, 2, 0, 0, 5, 6, 0, 0, 9, 10, 0, 0, 0, 0, 0, 0]);
+    assert!(array.resize(vec![4]).is_err());
+
+    // Append
+    array.append_elements::<u8>(0, vec![17, 18, 19, 20])?;
+    assert_eq!(array.shape(), &[5, 4]);
+    assert_eq!(array.retrieve_array_subset(&ArraySubset::new_with_ranges(&[3..5, 0..4]))?, [0, 0, 0, 0, 17, 18, 19, 20]);
+    array.append(1, vec![21, 22, 23, 24, 25])?;
+    assert_eq!(array.shape(), &[5, 5]);
+    assert_eq!(array.retrieve_array_subset(&ArraySubset::new_with_ranges(&[0..5, 4..5]))?, [21, 22, 23, 24, 25]);
+    assert!(array.append(1, vec![1, 2, 3]).is_err());
+    assert!(array.append(2, vec![1, 2, 3, 4, 5]).is_err());
+    #[cfg(feature = "ndarray")]
+    {
+        array.append_ndarray::<u8, _, _>(0, ndarray::array![[26, 27, 28, 29, 30]])?;
+        assert_eq!(array.shape(), &[6, 5]);
+        assert_eq!(array.retrieve_array_subset(&ArraySubset::new_with_ranges(&[5..6, 0..5]))?, [26, 27, 28, 29, 30]);
+        assert!(array.append_ndarray::<u8, _, _>(0, ndarray::array![[1, 2]]).is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+#[rustfmt::skip]
+fn array_sync_append_ragged() -> Result<(), Box<dyn std::error::Error>> {
+    use zarrs::array::chunk_grid::{ChunkGrid, RectangularChunkGrid};
+
+    let store = Arc::new(MemoryStore::default());
+    let mut array = ArrayBuilder::new(
+        vec![3, 4], // array shape
+        DataType::UInt8,
+        ChunkGrid::new(RectangularChunkGrid::new(&[[1, 2].try_into()?, 2.try_into()?])),
+        FillValue::from(0u8),
+    )
+    .bytes_to_bytes_codecs(vec![])
+    .build(store.clone(), "/array")?;
+    array.store_array_subset_elements::<u8>(&ArraySubset::new_with_shape(vec![3, 4]), (1..=12).collect())?;
+
+    // Append a chunk of 3 rows, the existing chunks are untouched
+    array.append_ragged_elements::<u8>(0, (13..=24).collect())?;
+    assert_eq!(array.shape(), &[6, 4]);
+    assert_eq!(array.chunk_grid_shape().unwrap(), &[3, 2]);
+    assert_eq!(array.chunk_shape(&[2, 0])?, [3, 2].try_into()?);
+    assert_eq!(array.retrieve_chunk(&[2, 1])?, [15, 16, 19, 20, 23, 24]);
+    assert_eq!(array.retrieve_array_subset_elements::<u8>(&ArraySubset::new_with_shape(vec![6, 4]))?, (1..=24).collect::<Vec<u8>>());
+    assert_eq!(
+        array.chunk_grid().create_metadata(),
+        serde_json::from_str(r#"{"name":"rectangular","configuration":{"chunk_shape":[[1,2,3],2]}}"#)?
+    );
+
+    // The fixed dimension only accepts chunks of its chunk size
+    array.append_ragged_elements::<u8>(1, (25..=36).collect())?;
+    assert_eq!(array.shape(), &[6, 6]);
+    assert!(array.append_ragged_elements::<u8>(1, vec![0; 6]).is_err());
+
+    // Not a rectangular chunk grid
+    let mut array = ArrayBuilder::new(vec![4, 4], DataType::UInt8, vec![2, 2].try_into()?, FillValue::from(0u8))
+        .build(store, "/regular")?;
+    assert!(array.append_ragged_elements::<u8>(0, vec![0; 4]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn array_sync_erase() -> Result<(), Box<dyn std::error::Error>> {
+    use zarrs::storage::{ListableStorageTraits, StoreKey, WritableStorageTraits};
+
+    let store = Arc::new(MemoryStore::default());
+    let array = ArrayBuilder::new(
+        vec![4, 4],
+        DataType::UInt8,
+        vec![2, 2].try_into().unwrap(),
+        FillValue::from(0u8),
+    )
+    .build(store.clone(), "/group/array")?;
+    array.store_metadata()?;
+    array.store_array_subset(&ArraySubset::new_with_shape(vec![4, 4]), (1..=16).collect())?;
+    let sibling = StoreKey::new("group/sibling/zarr.json")?;
+    store.set(&sibling, b"{}")?;
+    assert_eq!(store.list()?.len(), 6);
+
+    array.erase()?;
+    assert_eq!(store.list()?, vec![sibling]);
+    assert!(Array::new(store.clone(), "/group/array").is_err());
+
+    // Erasing an array that does not exist succeeds
+    array.erase()?;
+
+    Ok(())
+}
+
+#[test]
+fn array_sync_read_into_array_view_mut() -> Result<(), Box<dyn std::error::Error>> {
+    use rayon::prelude::*;
+
+    let store = Arc::new(MemoryStore::default());
+    let array = ArrayBuilder::new(
+        vec![4, 4], // array shape
+        DataType::UInt8,
+        vec![2, 2].try_into().unwrap(), // regular chunk shape
+        FillValue::from(0u8),
+    )
+    .build(store, "/array")?;
+    let elements: Vec<u8> = (0..16).collect();
+    array
+        .store_array_subset_elements(&ArraySubset::new_with_shape(vec![4, 4]), elements.clone())?;
+
+    let mut data = vec![0u8; 16];
+    let shape = &[4, 4];
+    let mut array_view =
+        ArrayViewMut::new(&mut data, shape, ArraySubset::new_with_shape(vec![4, 4]))?;
+    let chunks = ArraySubset::new_with_shape(vec![2, 2]);
+    let chunk_indices: Vec<Vec<u64>> = chunks.indices().into_iter().collect();
+    let chunk_subsets = chunk_indices
+        .iter()
+        .map(|chunk_indices| array.chunk_subset(chunk_indices))
+        .collect::<Result<Vec<_>, _>>()?;
+    let chunk_views = array_view.split_disjoint(&chunk_subsets)?;
+    chunk_indices
+        .into_par_iter()
+        .zip(chunk_views)
+        .try_for_each(|(chunk_indices, mut chunk_view)| {
+            array.retrieve_chunk_into_array_view_mut(&chunk_indices, &mut chunk_view)
+        })?;
+    assert_eq!(data, elements);
+
+    let mut data = vec![0u8; 16];
+    let mut array_view =
+        ArrayViewMut::new(&mut data, shape, ArraySubset::new_with_shape(vec![4, 4]))?;
+    assert!(array_view
+        .split_disjoint(&[
+            ArraySubset::new_with_ranges(&[0..2, 0..2]),
+            ArraySubset::new_with_ranges(&[1..3, 1..3]),
+        ])
+        .is_err());
+    let mut subset_views =
+        array_view.split_disjoint(&[ArraySubset::new_with_ranges(&[1..3, 0..4])])?;
+    array.retrieve_array_subset_into_array_view_mut(
+        &ArraySubset::new_with_ranges(&[0..2, 0..4]),
+        &mut subset_views[0],
+    )?;
+    assert_eq!(data, [0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+
+    Ok(())
+}